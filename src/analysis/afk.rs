@@ -0,0 +1,119 @@
+//! AFK detection
+//!
+//! When neither keyboard and mouse input nor the picture change for a while
+//! (a long crafting queue, waiting in town) the player is away, so zone OCR
+//! and the screen rules reading it pause while capture keeps running. Any
+//! input or change on screen resumes them.
+
+use std::time::{Duration, Instant};
+
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+use crate::capture::diff::FrameDiff;
+use crate::capture::CapturedFrame;
+
+/// Tells whether the player is away from the captured game
+#[derive(Debug, Clone)]
+pub struct AfkDetector {
+    /// Whole frames as they last changed (never refreshed on its own)
+    frames: FrameDiff,
+    /// When the picture last changed, once a frame was seen
+    last_frame_change: Option<Instant>,
+}
+
+impl Default for AfkDetector {
+    fn default() -> Self {
+        Self {
+            frames: FrameDiff::new(Duration::MAX),
+            last_frame_change: None,
+        }
+    }
+}
+
+impl AfkDetector {
+    /// Note a captured frame, to tell whether the picture still changes
+    pub fn observe(&mut self, frame: &CapturedFrame) {
+        self.observe_at(frame, Instant::now());
+    }
+
+    fn observe_at(&mut self, frame: &CapturedFrame, now: Instant) {
+        if self
+            .frames
+            .changed("frame", frame, 0, 0, frame.width, frame.height)
+        {
+            self.last_frame_change = Some(now);
+        }
+    }
+
+    /// Whether input and the picture both sat still for at least `after`
+    ///
+    /// Nobody counts as away before a frame was seen.
+    pub fn is_afk(&self, input_idle: Duration, after: Duration) -> bool {
+        self.is_afk_at(Instant::now(), input_idle, after)
+    }
+
+    fn is_afk_at(&self, now: Instant, input_idle: Duration, after: Duration) -> bool {
+        input_idle >= after
+            && self
+                .last_frame_change
+                .is_some_and(|changed| now.duration_since(changed) >= after)
+    }
+
+    /// Forget the frames seen, e.g. when capture stops
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Time since the last keyboard or mouse input to any app
+pub fn input_idle() -> Duration {
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    // SAFETY: GetLastInputInfo only fills in the struct it's given, whose
+    // size is set; GetTickCount has no preconditions
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Duration::ZERO;
+        }
+        // Both tick counts wrap after 49.7 days
+        Duration::from_millis(GetTickCount().wrapping_sub(info.dwTime) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A frame filled with one gray level
+    fn gray_frame(level: u8) -> CapturedFrame {
+        CapturedFrame::new(vec![level; 32 * 32 * 4], 32, 32)
+    }
+
+    #[test]
+    fn test_afk_needs_idle_input_and_picture() {
+        let mut detector = AfkDetector::default();
+        let start = Instant::now();
+        let after = Duration::from_secs(300);
+        let later = start + after;
+
+        // Nothing seen yet
+        assert!(!detector.is_afk_at(later, after, after));
+
+        detector.observe_at(&gray_frame(40), start);
+        detector.observe_at(&gray_frame(40), start + Duration::from_secs(200));
+        assert!(detector.is_afk_at(later, after, after));
+        // Recent input
+        assert!(!detector.is_afk_at(later, Duration::from_secs(10), after));
+
+        // The picture changed
+        detector.observe_at(&gray_frame(200), later);
+        assert!(!detector.is_afk_at(later + Duration::from_secs(10), after, after));
+        assert!(detector.is_afk_at(later + after, after, after));
+
+        detector.reset();
+        assert!(!detector.is_afk_at(later + after, after, after));
+    }
+}
@@ -0,0 +1,234 @@
+//! Coaching checklists
+//!
+//! Unchecks checklists on their schedule and checks items whose condition
+//! shows on screen (a recognized screen, a firing color trigger, a zone
+//! reading a text). Nothing is pressed in the game; the checklist only
+//! reminds the player what's left to do.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::storage::profiles::{Checklist, ChecklistCondition, ChecklistReset};
+
+const SECS_PER_HOUR: u64 = 60 * 60;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+
+/// What's on screen right now, for auto-checking items
+#[derive(Debug, Default)]
+pub struct ChecklistContext<'a> {
+    /// ID of the recognized screen
+    pub screen_id: Option<&'a str>,
+    /// IDs of the firing color triggers
+    pub color_triggers: Option<&'a HashSet<String>>,
+    /// Latest reading of each OCR zone, by zone ID
+    pub zone_texts: HashMap<&'a str, &'a str>,
+}
+
+impl ChecklistContext<'_> {
+    /// Whether the condition holds
+    pub fn is_met(&self, condition: &ChecklistCondition) -> bool {
+        match condition {
+            ChecklistCondition::Screen { screen_id } => self.screen_id == Some(screen_id.as_str()),
+            ChecklistCondition::ColorTrigger { trigger_id } => self
+                .color_triggers
+                .is_some_and(|active| active.contains(trigger_id)),
+            ChecklistCondition::ZoneText { zone_id, text } => {
+                let text = text.trim().to_lowercase();
+                !text.is_empty()
+                    && self
+                        .zone_texts
+                        .get(zone_id.as_str())
+                        .is_some_and(|reading| reading.to_lowercase().contains(&text))
+            }
+        }
+    }
+}
+
+/// Latest scheduled reset at or before `now` (unix seconds)
+///
+/// `session_start` is when the app started. Returns `None` for manual resets.
+pub fn last_reset_due(reset: ChecklistReset, now: u64, session_start: u64) -> Option<u64> {
+    match reset {
+        ChecklistReset::Manual => None,
+        ChecklistReset::SessionStart => Some(session_start),
+        ChecklistReset::Daily { hour_utc } => {
+            let offset = hour_utc.min(23) as u64 * SECS_PER_HOUR;
+            let day = now.checked_sub(offset)? / SECS_PER_DAY;
+            Some(day * SECS_PER_DAY + offset)
+        }
+        ChecklistReset::Weekly { weekday, hour_utc } => {
+            let offset = hour_utc.min(23) as u64 * SECS_PER_HOUR;
+            let day = now.checked_sub(offset)? / SECS_PER_DAY;
+            // 1970-01-01 was a Thursday (Monday = 0)
+            let days_since = (day + 3 + 7 - weekday.min(6) as u64) % 7;
+            Some(day.checked_sub(days_since)? * SECS_PER_DAY + offset)
+        }
+    }
+}
+
+/// Current unix time in seconds
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Uncheck every item of the checklist
+pub fn reset(checklist: &mut Checklist, now: u64) {
+    for item in &mut checklist.items {
+        item.done = false;
+    }
+    checklist.last_reset = Some(now);
+}
+
+/// Uncheck the checklists whose scheduled reset passed since their last one
+///
+/// Returns the names of the checklists reset.
+pub fn apply_resets(checklists: &mut [Checklist], now: u64, session_start: u64) -> Vec<String> {
+    let mut reset_names = Vec::new();
+    for checklist in checklists {
+        let Some(due) = last_reset_due(checklist.reset, now, session_start) else {
+            continue;
+        };
+        if checklist.last_reset.map_or(true, |last| last < due) {
+            reset(checklist, now);
+            reset_names.push(checklist.name.clone());
+        }
+    }
+    reset_names
+}
+
+/// Check the unchecked items whose condition holds
+///
+/// Returns the texts of the items checked.
+pub fn auto_check(checklists: &mut [Checklist], context: &ChecklistContext) -> Vec<String> {
+    let mut checked = Vec::new();
+    for item in checklists.iter_mut().flat_map(|c| c.items.iter_mut()) {
+        if !item.done && item.auto_check.as_ref().is_some_and(|c| context.is_met(c)) {
+            item.done = true;
+            checked.push(item.text.clone());
+        }
+    }
+    checked
+}
+
+/// Check the first unchecked item of the checklist
+///
+/// Returns its text, or `None` if everything is done.
+pub fn tick_next(checklist: &mut Checklist) -> Option<String> {
+    let item = checklist.items.iter_mut().find(|item| !item.done)?;
+    item.done = true;
+    Some(item.text.clone())
+}
+
+/// The checklist shown on the overlay and ticked by the hotkey
+pub fn pinned(checklists: &[Checklist]) -> Option<&Checklist> {
+    checklists.iter().find(|c| c.pinned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::ChecklistItem;
+
+    /// Monday 2024-01-01 00:00 UTC
+    const MONDAY: u64 = 1_704_067_200;
+
+    fn item(text: &str, auto_check: Option<ChecklistCondition>) -> ChecklistItem {
+        ChecklistItem {
+            id: text.to_string(),
+            text: text.to_string(),
+            done: false,
+            auto_check,
+        }
+    }
+
+    fn checklist(reset: ChecklistReset) -> Checklist {
+        Checklist {
+            id: "raid".to_string(),
+            name: "Raid prep".to_string(),
+            items: vec![
+                item(
+                    "Buy flasks",
+                    Some(ChecklistCondition::ZoneText {
+                        zone_id: "log".to_string(),
+                        text: "Flask bought".to_string(),
+                    }),
+                ),
+                item("Repair gear", None),
+            ],
+            reset,
+            last_reset: None,
+            pinned: true,
+            phases: vec![],
+        }
+    }
+
+    #[test]
+    fn test_last_reset_due() {
+        let daily = ChecklistReset::Daily { hour_utc: 6 };
+        assert_eq!(
+            last_reset_due(daily, MONDAY + 7 * SECS_PER_HOUR, 0),
+            Some(MONDAY + 6 * SECS_PER_HOUR)
+        );
+        // Before the hour, the previous day's reset is the latest
+        assert_eq!(
+            last_reset_due(daily, MONDAY + 5 * SECS_PER_HOUR, 0),
+            Some(MONDAY - 18 * SECS_PER_HOUR)
+        );
+
+        let wednesday = ChecklistReset::Weekly {
+            weekday: 2,
+            hour_utc: 0,
+        };
+        assert_eq!(
+            last_reset_due(wednesday, MONDAY + SECS_PER_HOUR, 0),
+            Some(MONDAY - 5 * SECS_PER_DAY)
+        );
+        assert_eq!(
+            last_reset_due(wednesday, MONDAY + 2 * SECS_PER_DAY, 0),
+            Some(MONDAY + 2 * SECS_PER_DAY)
+        );
+
+        assert_eq!(
+            last_reset_due(ChecklistReset::SessionStart, MONDAY, 42),
+            Some(42)
+        );
+        assert_eq!(last_reset_due(ChecklistReset::Manual, MONDAY, 42), None);
+    }
+
+    #[test]
+    fn test_apply_resets() {
+        let mut checklists = vec![checklist(ChecklistReset::Daily { hour_utc: 0 })];
+        checklists[0].items[1].done = true;
+        checklists[0].last_reset = Some(MONDAY - SECS_PER_HOUR);
+
+        assert_eq!(apply_resets(&mut checklists, MONDAY + 10, 0), ["Raid prep"]);
+        assert!(!checklists[0].items[1].done);
+        assert_eq!(checklists[0].last_reset, Some(MONDAY + 10));
+
+        // Already reset today
+        checklists[0].items[1].done = true;
+        assert!(apply_resets(&mut checklists, MONDAY + SECS_PER_HOUR, 0).is_empty());
+        assert!(checklists[0].items[1].done);
+    }
+
+    #[test]
+    fn test_auto_check_and_tick() {
+        let mut checklists = vec![checklist(ChecklistReset::Manual)];
+        let mut context = ChecklistContext::default();
+        context.zone_texts.insert("log", "Nothing yet");
+        assert!(auto_check(&mut checklists, &context).is_empty());
+
+        context.zone_texts.insert("log", "FLASK BOUGHT x2");
+        assert_eq!(auto_check(&mut checklists, &context), ["Buy flasks"]);
+        assert!(auto_check(&mut checklists, &context).is_empty());
+
+        assert_eq!(
+            tick_next(&mut checklists[0]).as_deref(),
+            Some("Repair gear")
+        );
+        assert_eq!(tick_next(&mut checklists[0]), None);
+    }
+}
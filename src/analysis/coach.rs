@@ -0,0 +1,186 @@
+//! Coach mode
+//!
+//! Compares the pace of numeric zone readings in the current session (gold
+//! earned, deaths, kills) with the player's average over previous sessions.
+//! A zone's pace in a session is how much its value changed per hour between
+//! its first and last reading.
+
+use std::collections::BTreeMap;
+
+use crate::storage::database::SessionReadings;
+use crate::vision::zone_text::numeric_value;
+
+/// Previous sessions the average is taken over
+pub const AVERAGE_SESSIONS: usize = 10;
+
+/// Shortest span of readings a pace is taken from, in seconds; over less
+/// time a single change would count as a huge rate
+const MIN_SPAN_SECS: i64 = 5 * 60;
+
+/// Pace of one zone: this session against previous ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZonePace {
+    /// Zone ID
+    pub zone_id: String,
+    /// Zone name (as last recorded)
+    pub zone_name: String,
+    /// Change per hour in the current session
+    pub current: f64,
+    /// Average change per hour over previous sessions with a pace
+    pub average: Option<f64>,
+    /// Number of previous sessions the average is taken over
+    pub sessions: usize,
+}
+
+impl ZonePace {
+    /// Relative difference of the current pace from the average, e.g. -0.2
+    /// for 20% below (None without an average to compare with)
+    pub fn deviation(&self) -> Option<f64> {
+        let average = self.average?;
+        (average.abs() > f64::EPSILON).then(|| (self.current - average) / average.abs())
+    }
+
+    /// Nudge for the overlay when the pace is off the average by at least
+    /// `threshold` (0.2 = 20%)
+    pub fn nudge(&self, threshold: f64) -> Option<String> {
+        let deviation = self.deviation()?;
+        if deviation.abs() < threshold {
+            return None;
+        }
+        Some(format!(
+            "{} pace is {:.0}% {} your average ({} vs {})",
+            self.zone_name,
+            deviation.abs() * 100.0,
+            if deviation < 0.0 { "below" } else { "above" },
+            format_rate(self.current),
+            format_rate(self.average?)
+        ))
+    }
+}
+
+/// A rate per hour as text, per minute when it's fast, e.g. "85/min" or "2.5/h"
+pub fn format_rate(per_hour: f64) -> String {
+    if per_hour.abs() >= 600.0 {
+        format!("{:.0}/min", per_hour / 60.0)
+    } else {
+        format!("{:.1}/h", per_hour)
+    }
+}
+
+/// Pace of every numeric zone of a session, by zone ID, with its name
+fn session_paces(session: &SessionReadings) -> BTreeMap<&str, (&str, f64)> {
+    let mut spans: BTreeMap<&str, (&str, (i64, f64), (i64, f64))> = BTreeMap::new();
+    for reading in &session.readings {
+        let Some(value) = numeric_value(&reading.text) else {
+            continue;
+        };
+        let name = reading.zone_name.as_str();
+        spans
+            .entry(reading.zone_id.as_str())
+            .and_modify(|(last_name, _, last)| {
+                *last_name = name;
+                *last = (reading.at, value);
+            })
+            .or_insert((name, (reading.at, value), (reading.at, value)));
+    }
+
+    spans
+        .into_iter()
+        .filter_map(|(zone_id, (name, (start, first), (end, last)))| {
+            let span = end - start;
+            (span >= MIN_SPAN_SECS)
+                .then(|| (zone_id, (name, (last - first) * 3600.0 / span as f64)))
+        })
+        .collect()
+}
+
+/// Compare the first (current) session's paces with the average of the rest
+pub fn compare(sessions: &[SessionReadings]) -> Vec<ZonePace> {
+    let Some((current, previous)) = sessions.split_first() else {
+        return Vec::new();
+    };
+    let previous: Vec<_> = previous.iter().map(session_paces).collect();
+
+    session_paces(current)
+        .into_iter()
+        .map(|(zone_id, (zone_name, pace))| {
+            let paces: Vec<f64> = previous
+                .iter()
+                .filter_map(|session| session.get(zone_id).map(|(_, pace)| *pace))
+                .collect();
+            ZonePace {
+                zone_id: zone_id.to_string(),
+                zone_name: zone_name.to_string(),
+                current: pace,
+                average: (!paces.is_empty())
+                    .then(|| paces.iter().sum::<f64>() / paces.len() as f64),
+                sessions: paces.len(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::RecordedReading;
+
+    fn session(session_id: i64, readings: &[(&str, i64, &str)]) -> SessionReadings {
+        SessionReadings {
+            session_id,
+            readings: readings
+                .iter()
+                .map(|&(zone_id, at, text)| RecordedReading {
+                    zone_id: zone_id.to_string(),
+                    zone_name: zone_id.to_string(),
+                    at,
+                    text: text.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare_with_average() {
+        let sessions = [
+            // Current: 800 gold in an hour, too short to tell deaths
+            session(
+                3,
+                &[
+                    ("gold", 0, "1,000"),
+                    ("deaths", 0, "0"),
+                    ("gold", 1800, "n/a"),
+                    ("deaths", 60, "1"),
+                    ("gold", 3600, "1,800"),
+                ],
+            ),
+            session(2, &[("gold", 0, "0"), ("gold", 1800, "600")]),
+            session(1, &[("gold", 0, "0"), ("gold", 3600, "800")]),
+        ];
+
+        let paces = compare(&sessions);
+        assert_eq!(paces.len(), 1);
+        let gold = &paces[0];
+        assert_eq!(gold.current, 800.0);
+        assert_eq!(gold.average, Some(1000.0));
+        assert_eq!(gold.sessions, 2);
+        assert!((gold.deviation().unwrap() + 0.2).abs() < 1e-9);
+        assert_eq!(
+            gold.nudge(0.15).unwrap(),
+            "gold pace is 20% below your average (13/min vs 17/min)"
+        );
+        assert_eq!(gold.nudge(0.25), None);
+
+        // Nothing to compare with
+        let first = compare(&sessions[..1]);
+        assert_eq!(first[0].average, None);
+        assert_eq!(first[0].nudge(0.0), None);
+        assert!(compare(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(2.5), "2.5/h");
+        assert_eq!(format_rate(6000.0), "100/min");
+    }
+}
@@ -0,0 +1,231 @@
+//! Session goals
+//!
+//! Goals set before capture starts (play at most two hours, reach level 30,
+//! clear three dungeons) are tracked while capturing and scored when capture
+//! stops. Values to reach come from the same live variables as the watch
+//! window; counters are ticked from the dashboard.
+
+use std::time::{Duration, Instant};
+
+use crate::analysis::watch::WatchValues;
+use crate::analysis::wellness::format_playtime;
+use crate::config::{SessionGoal, SessionGoalKind};
+use crate::vision::zone_text::numeric_value;
+
+/// A goal and how far the session got
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    /// The goal as it was set
+    pub goal: SessionGoal,
+    /// Ticks of a counter goal
+    pub count: u32,
+    /// Highest value of a reach goal's variable read so far
+    pub best: Option<f64>,
+}
+
+impl GoalProgress {
+    /// Whether the goal is met after playing for `played`
+    ///
+    /// A playtime limit counts as met until it's exceeded.
+    pub fn is_met(&self, played: Duration) -> bool {
+        match self.goal.kind {
+            SessionGoalKind::MaxPlaytime { minutes } => played.as_secs() <= minutes as u64 * 60,
+            SessionGoalKind::Reach { target, .. } => self.best.is_some_and(|best| best >= target),
+            SessionGoalKind::Count { target } => self.count >= target,
+        }
+    }
+
+    /// Share of the goal done (0.0-1.0); for a playtime limit, of the time
+    pub fn fraction(&self, played: Duration) -> f32 {
+        let fraction = match self.goal.kind {
+            SessionGoalKind::MaxPlaytime { minutes } => {
+                played.as_secs_f64() / (minutes.max(1) as f64 * 60.0)
+            }
+            SessionGoalKind::Reach { target, .. } => self
+                .best
+                .map_or(0.0, |best| best / target.max(f64::EPSILON)),
+            SessionGoalKind::Count { target } => self.count as f64 / target.max(1) as f64,
+        };
+        fraction.clamp(0.0, 1.0) as f32
+    }
+
+    /// Progress as text, e.g. "1h 34m of 2h 00m", "27 of 30" or "2 of 3"
+    pub fn describe(&self, played: Duration) -> String {
+        match self.goal.kind {
+            SessionGoalKind::MaxPlaytime { minutes } => format!(
+                "{} of {}",
+                format_playtime(played),
+                format_playtime(Duration::from_secs(minutes as u64 * 60))
+            ),
+            SessionGoalKind::Reach { target, .. } => format!(
+                "{} of {}",
+                self.best.map_or("-".to_string(), |best| best.to_string()),
+                target
+            ),
+            SessionGoalKind::Count { target } => format!("{} of {}", self.count, target),
+        }
+    }
+}
+
+/// Goals of the session being captured
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionGoals {
+    /// Each goal with its progress, in the order they were set
+    pub goals: Vec<GoalProgress>,
+    /// When the session started
+    started: Instant,
+}
+
+impl SessionGoals {
+    /// Start tracking goals (none when no goal is set)
+    pub fn start(goals: &[SessionGoal], now: Instant) -> Option<Self> {
+        (!goals.is_empty()).then(|| Self {
+            goals: goals
+                .iter()
+                .map(|goal| GoalProgress {
+                    goal: goal.clone(),
+                    count: 0,
+                    best: None,
+                })
+                .collect(),
+            started: now,
+        })
+    }
+
+    /// Time played since the session started
+    pub fn played(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started)
+    }
+
+    /// Take in the current live values for goals to reach
+    pub fn update(&mut self, values: &WatchValues) {
+        for progress in &mut self.goals {
+            if let SessionGoalKind::Reach { ref variable, .. } = progress.goal.kind {
+                if let Some(value) = values.get(variable).and_then(|v| numeric_value(v)) {
+                    progress.best = Some(progress.best.map_or(value, |best| best.max(value)));
+                }
+            }
+        }
+    }
+
+    /// Tick a counter goal
+    pub fn increment(&mut self, goal_id: &str) {
+        if let Some(progress) = self.goals.iter_mut().find(|p| p.goal.id == goal_id) {
+            progress.count += 1;
+        }
+    }
+
+    /// Number of goals met
+    pub fn met(&self, now: Instant) -> usize {
+        let played = self.played(now);
+        self.goals.iter().filter(|p| p.is_met(played)).count()
+    }
+
+    /// Scorecard of the session, e.g.
+    /// "Session goals 2/3 met: Playtime 1h 34m of 2h 00m ✓, Level 27 of 30 ✗, ..."
+    pub fn scorecard(&self, now: Instant) -> String {
+        let played = self.played(now);
+        let goals: Vec<String> = self
+            .goals
+            .iter()
+            .map(|p| {
+                format!(
+                    "{} {} {}",
+                    p.goal.name,
+                    p.describe(played),
+                    if p.is_met(played) { "✓" } else { "✗" }
+                )
+            })
+            .collect();
+        format!(
+            "Session goals {}/{} met: {}",
+            self.met(now),
+            self.goals.len(),
+            goals.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(id: &str, name: &str, kind: SessionGoalKind) -> SessionGoal {
+        SessionGoal {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_goal_progress() {
+        let start = Instant::now();
+        let goals = [
+            goal(
+                "time",
+                "Playtime",
+                SessionGoalKind::MaxPlaytime { minutes: 120 },
+            ),
+            goal(
+                "level",
+                "Level",
+                SessionGoalKind::Reach {
+                    variable: "zone.Level".to_string(),
+                    target: 30.0,
+                },
+            ),
+            goal(
+                "clears",
+                "Dungeon clears",
+                SessionGoalKind::Count { target: 3 },
+            ),
+        ];
+        assert!(SessionGoals::start(&[], start).is_none());
+        let mut session = SessionGoals::start(&goals, start).unwrap();
+
+        let mut values = WatchValues::new();
+        values.insert("zone.Level".to_string(), "31".to_string());
+        session.update(&values);
+        // A misread lower value doesn't undo the best
+        values.insert("zone.Level".to_string(), "3".to_string());
+        session.update(&values);
+        session.increment("clears");
+        session.increment("clears");
+
+        let hour = start + Duration::from_secs(3600);
+        assert_eq!(session.met(hour), 2);
+        assert_eq!(session.goals[1].best, Some(31.0));
+        assert_eq!(session.goals[2].describe(Duration::ZERO), "2 of 3");
+        assert!((session.goals[0].fraction(Duration::from_secs(3600)) - 0.5).abs() < 0.001);
+
+        session.increment("clears");
+        assert_eq!(session.met(hour), 3);
+        // Playing past the limit misses it
+        assert_eq!(session.met(start + Duration::from_secs(3 * 3600)), 2);
+    }
+
+    #[test]
+    fn test_scorecard() {
+        let start = Instant::now();
+        let goals = [
+            goal(
+                "time",
+                "Playtime",
+                SessionGoalKind::MaxPlaytime { minutes: 120 },
+            ),
+            goal(
+                "clears",
+                "Dungeon clears",
+                SessionGoalKind::Count { target: 3 },
+            ),
+        ];
+        let mut session = SessionGoals::start(&goals, start).unwrap();
+        session.increment("clears");
+
+        assert_eq!(
+            session.scorecard(start + Duration::from_secs(94 * 60)),
+            "Session goals 1/2 met: Playtime 1h 34m of 2h 00m ✓, Dungeon clears 1 of 3 ✗"
+        );
+    }
+}
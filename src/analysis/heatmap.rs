@@ -0,0 +1,120 @@
+//! Tip heatmap
+//!
+//! Counts tips shown per cell of a grid laid over the frame, from the regions
+//! they were raised from, to tell which HUD areas raise most tips. Every
+//! cell a region touches counts the tip once.
+
+use std::collections::BTreeMap;
+
+use crate::storage::database::TipOrigin;
+
+/// Tips shown per cell of a grid over the frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    /// Cells across
+    pub columns: usize,
+    /// Cells down
+    pub rows: usize,
+    /// Tip count of each cell, row by row
+    cells: Vec<u32>,
+}
+
+impl Heatmap {
+    /// Count the tips of `origins` on a grid of `columns` by `rows` cells
+    pub fn new(origins: &[TipOrigin], columns: usize, rows: usize) -> Self {
+        let mut heatmap = Self {
+            columns,
+            rows,
+            cells: vec![0; columns * rows],
+        };
+        for origin in origins {
+            let (x, y, width, height) = origin.bounds;
+            let across = cell_span(x, width, columns);
+            for row in cell_span(y, height, rows) {
+                for column in across.clone() {
+                    heatmap.cells[row * columns + column] += 1;
+                }
+            }
+        }
+        heatmap
+    }
+
+    /// Tips counted in a cell
+    pub fn get(&self, column: usize, row: usize) -> u32 {
+        self.cells[row * self.columns + column]
+    }
+
+    /// Highest count of any cell
+    pub fn max(&self) -> u32 {
+        self.cells.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Cells of `cells` along a side that a region from `start` of `size`
+/// (fractions of the side) touches; at least one
+fn cell_span(start: f32, size: f32, cells: usize) -> std::ops::Range<usize> {
+    if cells == 0 {
+        return 0..0;
+    }
+    let last = cells - 1;
+    let first = ((start.clamp(0.0, 1.0) * cells as f32) as usize).min(last);
+    let end =
+        (((start + size).clamp(0.0, 1.0) * cells as f32).ceil() as usize).clamp(first + 1, cells);
+    first..end
+}
+
+/// Tips shown per source, most first
+pub fn by_source(origins: &[TipOrigin]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for origin in origins {
+        *counts.entry(origin.source.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(source, count)| (source.to_string(), count))
+        .collect();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(source: &str, bounds: (f32, f32, f32, f32)) -> TipOrigin {
+        TipOrigin {
+            tip_id: "tip".to_string(),
+            source: source.to_string(),
+            bounds,
+        }
+    }
+
+    #[test]
+    fn test_heatmap_counts_cells_touched() {
+        let origins = [
+            // Top left cell
+            origin("label Gold", (0.0, 0.0, 0.1, 0.1)),
+            origin("label Gold", (0.05, 0.05, 0.1, 0.1)),
+            // Across the two bottom cells
+            origin("watch zone.health", (0.25, 0.75, 0.5, 0.25)),
+            // A sliver at the right edge still counts
+            origin("label Ammo", (1.0, 0.0, 0.0, 0.0)),
+        ];
+        let heatmap = Heatmap::new(&origins, 2, 2);
+
+        assert_eq!(heatmap.get(0, 0), 2);
+        assert_eq!(heatmap.get(1, 0), 1);
+        assert_eq!(heatmap.get(0, 1), 1);
+        assert_eq!(heatmap.get(1, 1), 1);
+        assert_eq!(heatmap.max(), 2);
+
+        assert_eq!(
+            by_source(&origins),
+            [
+                ("label Gold".to_string(), 2),
+                ("label Ammo".to_string(), 1),
+                ("watch zone.health".to_string(), 1),
+            ]
+        );
+    }
+}
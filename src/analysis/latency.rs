@@ -0,0 +1,105 @@
+//! Capture-to-tip latency
+//!
+//! Frames carry the time they were captured, and tips derived from them
+//! carry it along, so the delay the player actually sees can be measured:
+//! from the frame appearing on screen to the tip about it. Tips whose data
+//! is older than the latency budget are dropped rather than shown late.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Samples kept per stage
+const MAX_SAMPLES: usize = 120;
+
+/// Recent latencies of one stage, newest last
+#[derive(Debug, Clone, Default)]
+pub struct LatencySamples {
+    samples: VecDeque<Duration>,
+}
+
+impl LatencySamples {
+    /// Add a measurement, forgetting the oldest past the limit
+    pub fn record(&mut self, latency: Duration) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    /// Latest measurement
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    /// Mean of the recent measurements
+    pub fn average(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().sum::<Duration>() / self.samples.len() as u32)
+    }
+
+    /// Latency that `percentile` percent of the recent measurements stay within
+    pub fn percentile(&self, percentile: u8) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        let rank = (percentile.min(100) as usize * sorted.len()).div_ceil(100);
+        Some(sorted[rank.saturating_sub(1)])
+    }
+}
+
+/// Time since capture at each processing stage
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    /// Frame age when the dashboard picked the frame up
+    pub frame_pickup: LatencySamples,
+    /// Frame age when its zones were read
+    pub zone_ocr: LatencySamples,
+    /// Time spent reading a frame's zones
+    pub zone_ocr_time: LatencySamples,
+    /// Frame age when a tip based on it was shown
+    pub tip: LatencySamples,
+    /// Tips dropped because their data was older than the budget
+    pub stale_tips: u64,
+}
+
+/// Whether data this old is past the budget (0 = no budget)
+pub fn is_stale(age: Duration, budget_ms: u32) -> bool {
+    budget_ms > 0 && age > Duration::from_millis(budget_ms as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_samples() {
+        let mut samples = LatencySamples::default();
+        assert_eq!(samples.average(), None);
+        assert_eq!(samples.percentile(95), None);
+
+        for ms in 1..=100 {
+            samples.record(Duration::from_millis(ms));
+        }
+        assert_eq!(samples.last(), Some(Duration::from_millis(100)));
+        assert_eq!(samples.average(), Some(Duration::from_micros(50_500)));
+        assert_eq!(samples.percentile(95), Some(Duration::from_millis(95)));
+        assert_eq!(samples.percentile(100), Some(Duration::from_millis(100)));
+
+        // Only the most recent samples are kept
+        for _ in 0..MAX_SAMPLES {
+            samples.record(Duration::from_millis(10));
+        }
+        assert_eq!(samples.percentile(100), Some(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_stale_data() {
+        assert!(!is_stale(Duration::from_millis(900), 1000));
+        assert!(is_stale(Duration::from_millis(1100), 1000));
+        assert!(!is_stale(Duration::from_secs(60), 0));
+    }
+}
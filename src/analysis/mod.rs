@@ -4,14 +4,30 @@
 //! Processes vision results and applies game-specific rules to generate tips and alerts.
 //! Uses rhai scripting for customizable game profiles.
 
+pub mod afk;
+pub mod checklist;
+pub mod coach;
 pub mod events;
+pub mod goals;
+pub mod heatmap;
+pub mod latency;
+pub mod phases;
+pub mod quiet_hours;
+pub mod reference;
 pub mod rules;
+pub mod stepper;
+pub mod template;
+pub mod watch;
+pub mod wellness;
 
 use crate::vision::VisionResult;
 use anyhow::Result;
 
+/// Lowest priority of a critical tip (shown even during quiet hours)
+pub const CRITICAL_PRIORITY: u32 = 76;
+
 /// A tip or alert generated by the analysis engine
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Tip {
     /// Tip identifier
     pub id: String,
@@ -23,6 +39,9 @@ pub struct Tip {
     pub duration_ms: Option<u64>,
     /// Whether to play audio notification
     pub play_sound: bool,
+    /// When the frame the tip is based on was captured (None for tips that
+    /// don't react to the screen)
+    pub captured_at: Option<std::time::Instant>,
 }
 
 /// Analysis engine that processes vision results
@@ -0,0 +1,167 @@
+//! Game phases
+//!
+//! Tracks which phase of a match (early, mid, late game) is underway, so
+//! zones and checklists limited to some phases only run in those.
+//! Phases are reached in the order the profile lists them and a match never
+//! goes back to an earlier one; starting a new match starts over.
+
+use std::time::{Duration, Instant};
+
+use super::checklist::ChecklistContext;
+use crate::storage::profiles::{GamePhase, PhaseStart};
+
+/// Whether something limited to `phases` is active in the current phase
+///
+/// An empty list means every phase. Without a current phase (the profile
+/// defines none, or none was reached yet) only unlimited things are active.
+pub fn is_active(phases: &[String], current: Option<&str>) -> bool {
+    phases.is_empty() || current.is_some_and(|current| phases.iter().any(|p| p == current))
+}
+
+/// Whether a phase's start has been reached
+pub fn has_started(start: &PhaseStart, elapsed: Duration, context: &ChecklistContext) -> bool {
+    match start {
+        PhaseStart::MatchStart => true,
+        PhaseStart::Elapsed { minutes } => elapsed >= Duration::from_secs(*minutes as u64 * 60),
+        PhaseStart::Seen { condition } => context.is_met(condition),
+    }
+}
+
+/// Index of the phase the match is in now
+///
+/// The latest phase whose start was reached, as long as it comes after the
+/// current one.
+pub fn reached_phase(
+    phases: &[GamePhase],
+    current: Option<usize>,
+    elapsed: Duration,
+    context: &ChecklistContext,
+) -> Option<usize> {
+    let first_candidate = current.map_or(0, |current| current + 1);
+    phases
+        .iter()
+        .enumerate()
+        .skip(first_candidate)
+        .rev()
+        .find(|(_, phase)| has_started(&phase.start, elapsed, context))
+        .map(|(index, _)| index)
+        .or(current)
+}
+
+/// Phase of the current match
+#[derive(Debug)]
+pub struct PhaseTracker {
+    match_start: Instant,
+    current: Option<String>,
+}
+
+impl Default for PhaseTracker {
+    fn default() -> Self {
+        Self {
+            match_start: Instant::now(),
+            current: None,
+        }
+    }
+}
+
+impl PhaseTracker {
+    /// Start a new match from its first phase
+    pub fn new_match(&mut self) {
+        *self = Self::default();
+    }
+
+    /// When the current match started
+    pub fn match_start(&self) -> Instant {
+        self.match_start
+    }
+
+    /// ID of the current phase
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Move on to the latest phase reached
+    ///
+    /// Returns whether the phase changed. A phase removed from the profile
+    /// ends, and the match is in no phase until another one is reached.
+    pub fn update(&mut self, phases: &[GamePhase], context: &ChecklistContext) -> bool {
+        let current = self
+            .current
+            .as_ref()
+            .and_then(|id| phases.iter().position(|phase| phase.id == *id));
+        let reached = reached_phase(phases, current, self.match_start.elapsed(), context)
+            .map(|index| phases[index].id.clone());
+        if reached == self.current {
+            return false;
+        }
+        self.current = reached;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::ChecklistCondition;
+
+    fn phase(id: &str, start: PhaseStart) -> GamePhase {
+        GamePhase {
+            id: id.to_string(),
+            name: id.to_string(),
+            start,
+        }
+    }
+
+    fn phases() -> Vec<GamePhase> {
+        vec![
+            phase("early", PhaseStart::MatchStart),
+            phase("mid", PhaseStart::Elapsed { minutes: 15 }),
+            phase(
+                "late",
+                PhaseStart::Seen {
+                    condition: ChecklistCondition::Screen {
+                        screen_id: "baron".to_string(),
+                    },
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_reached_phase() {
+        let phases = phases();
+        let mut context = ChecklistContext::default();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        assert_eq!(reached_phase(&phases, None, minutes(0), &context), Some(0));
+        assert_eq!(
+            reached_phase(&phases, Some(0), minutes(16), &context),
+            Some(1)
+        );
+
+        // A condition can skip ahead of a phase that starts by time
+        context.screen_id = Some("baron");
+        assert_eq!(
+            reached_phase(&phases, Some(0), minutes(5), &context),
+            Some(2)
+        );
+
+        // Never back to an earlier phase
+        context.screen_id = None;
+        assert_eq!(
+            reached_phase(&phases, Some(2), minutes(16), &context),
+            Some(2)
+        );
+        assert_eq!(reached_phase(&[], None, minutes(16), &context), None);
+    }
+
+    #[test]
+    fn test_is_active() {
+        let late = ["late".to_string()];
+        assert!(is_active(&[], None));
+        assert!(is_active(&[], Some("early")));
+        assert!(is_active(&late, Some("late")));
+        assert!(!is_active(&late, Some("early")));
+        assert!(!is_active(&late, None));
+    }
+}
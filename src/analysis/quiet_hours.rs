@@ -0,0 +1,137 @@
+//! Quiet hours
+//!
+//! During quiet hours only critical tips reach the overlay. Quiet hours come
+//! from a daily schedule in local time and, optionally, from Windows' own
+//! do-not-disturb state (presentation mode, quiet time).
+
+use crate::analysis::{Tip, CRITICAL_PRIORITY};
+use crate::config::QuietHoursSettings;
+
+/// A moment in local time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    /// Day of the week (0 = Monday)
+    pub weekday: u8,
+    /// Hour (0-23)
+    pub hour: u8,
+}
+
+impl LocalTime {
+    /// The current local time
+    pub fn now() -> Self {
+        use windows::Win32::System::SystemInformation::GetLocalTime;
+
+        // SAFETY: GetLocalTime only fills in the returned struct
+        let time = unsafe { GetLocalTime() };
+        Self {
+            // SYSTEMTIME counts from Sunday
+            weekday: ((time.wDayOfWeek + 6) % 7) as u8,
+            hour: time.wHour as u8,
+        }
+    }
+}
+
+/// Whether the schedule keeps quiet at the given time
+///
+/// A schedule ending before it starts runs past midnight and belongs to the
+/// day it started on. Equal start and end hours mean the whole day.
+pub fn is_scheduled(settings: &QuietHoursSettings, time: LocalTime) -> bool {
+    if !settings.enabled {
+        return false;
+    }
+    let day = |weekday: u8| settings.days[(weekday % 7) as usize];
+    let yesterday = (time.weekday + 6) % 7;
+    let (start, end) = (settings.start_hour, settings.end_hour);
+
+    if start < end {
+        day(time.weekday) && (start..end).contains(&time.hour)
+    } else if start > end {
+        (day(time.weekday) && time.hour >= start) || (day(yesterday) && time.hour < end)
+    } else {
+        day(time.weekday)
+    }
+}
+
+/// Whether Windows asks apps not to disturb the user
+pub fn windows_do_not_disturb() -> bool {
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME,
+    };
+
+    // Full-screen games report "busy" too, so only the explicit states count
+    // SAFETY: SHQueryUserNotificationState has no preconditions
+    match unsafe { SHQueryUserNotificationState() } {
+        Ok(state) => state == QUNS_PRESENTATION_MODE || state == QUNS_QUIET_TIME,
+        Err(_) => false,
+    }
+}
+
+/// Whether it's quiet right now, by schedule or by Windows
+pub fn is_quiet_now(settings: &QuietHoursSettings) -> bool {
+    is_scheduled(settings, LocalTime::now())
+        || (settings.follow_windows && windows_do_not_disturb())
+}
+
+/// Whether a tip may be shown
+pub fn allows(tip: &Tip, quiet: bool) -> bool {
+    !quiet || tip.priority >= CRITICAL_PRIORITY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(weekday: u8, hour: u8) -> LocalTime {
+        LocalTime { weekday, hour }
+    }
+
+    fn settings(start_hour: u8, end_hour: u8) -> QuietHoursSettings {
+        QuietHoursSettings {
+            enabled: true,
+            start_hour,
+            end_hour,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_schedule_within_day() {
+        let settings = settings(9, 17);
+        assert!(!is_scheduled(&settings, at(0, 8)));
+        assert!(is_scheduled(&settings, at(0, 9)));
+        assert!(is_scheduled(&settings, at(0, 16)));
+        assert!(!is_scheduled(&settings, at(0, 17)));
+    }
+
+    #[test]
+    fn test_schedule_past_midnight() {
+        let mut settings = settings(22, 8);
+        assert!(is_scheduled(&settings, at(0, 23)));
+        assert!(is_scheduled(&settings, at(1, 7)));
+        assert!(!is_scheduled(&settings, at(1, 12)));
+
+        // Only Friday nights: Saturday morning is still quiet, Sunday's isn't
+        settings.days = [false, false, false, false, true, false, false];
+        assert!(is_scheduled(&settings, at(4, 23)));
+        assert!(is_scheduled(&settings, at(5, 2)));
+        assert!(!is_scheduled(&settings, at(5, 23)));
+        assert!(!is_scheduled(&settings, at(6, 2)));
+
+        settings.enabled = false;
+        assert!(!is_scheduled(&settings, at(4, 23)));
+    }
+
+    #[test]
+    fn test_only_critical_tips_allowed_when_quiet() {
+        let tip = |priority| Tip {
+            id: "tip".to_string(),
+            message: "Tip".to_string(),
+            priority,
+            duration_ms: None,
+            ..Default::default()
+        };
+        assert!(allows(&tip(50), false));
+        assert!(!allows(&tip(50), true));
+        assert!(allows(&tip(CRITICAL_PRIORITY), true));
+    }
+}
@@ -0,0 +1,239 @@
+//! Script reference
+//!
+//! What rule scripts and tip messages can use: the language's operators and
+//! built-in functions, placeholders, and the pipeline variables (the same
+//! names as in the watch window). The dashboard's reference panel lists and
+//! searches these next to the variables' live values.
+
+/// Group of reference entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// Comparisons, logic and control flow
+    Operators,
+    /// Text functions
+    Text,
+    /// Number functions
+    Numbers,
+    /// Placeholders in tip messages and library rule parameters
+    Placeholders,
+}
+
+impl Section {
+    /// Every section, in display order
+    pub const ALL: [Section; 4] = [
+        Section::Operators,
+        Section::Text,
+        Section::Numbers,
+        Section::Placeholders,
+    ];
+
+    /// Heading in the reference panel
+    pub fn label(self) -> &'static str {
+        match self {
+            Section::Operators => "Operators",
+            Section::Text => "Text",
+            Section::Numbers => "Numbers",
+            Section::Placeholders => "Placeholders",
+        }
+    }
+}
+
+/// A documented operator, function or placeholder
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceEntry {
+    /// Section it's listed under
+    pub section: Section,
+    /// How it's written
+    pub signature: &'static str,
+    /// What it does
+    pub description: &'static str,
+    /// Short example
+    pub example: &'static str,
+}
+
+const fn entry(
+    section: Section,
+    signature: &'static str,
+    description: &'static str,
+    example: &'static str,
+) -> ReferenceEntry {
+    ReferenceEntry {
+        section,
+        signature,
+        description,
+        example,
+    }
+}
+
+/// Everything scripts and tip messages can use, by section
+pub const ENTRIES: &[ReferenceEntry] = &[
+    entry(
+        Section::Operators,
+        "a == b, a != b",
+        "Equal, not equal (text is compared exactly)",
+        r#"screen == "Shop""#,
+    ),
+    entry(
+        Section::Operators,
+        "a < b, a <= b, a > b, a >= b",
+        "Compare numbers",
+        "health < 30",
+    ),
+    entry(
+        Section::Operators,
+        "a && b, a || b, !a",
+        "Both, either, not",
+        "scene.dark && !quiet_hours",
+    ),
+    entry(
+        Section::Operators,
+        "text in other",
+        "Whether text appears in another text",
+        r#""LOW" in ammo"#,
+    ),
+    entry(
+        Section::Operators,
+        "if condition { ... } else { ... }",
+        "Run code only when a condition holds",
+        r#"if gold >= 1300 { alert("Buy boots") }"#,
+    ),
+    entry(
+        Section::Text,
+        "text.contains(part)",
+        "Whether the text contains another (case-sensitive)",
+        r#"status.contains("Poisoned")"#,
+    ),
+    entry(
+        Section::Text,
+        "text.starts_with(part), text.ends_with(part)",
+        "Whether the text begins or ends with another",
+        r#"area.starts_with("Act")"#,
+    ),
+    entry(
+        Section::Text,
+        "text.to_lower(), text.to_upper()",
+        "The text in lower or upper case, for comparing regardless of case",
+        r#"screen.to_lower() == "shop""#,
+    ),
+    entry(
+        Section::Text,
+        "text.len()",
+        "Number of characters",
+        "name.len() > 0",
+    ),
+    entry(
+        Section::Text,
+        "text.index_of(part)",
+        "Position of another text, or -1 if it's not there",
+        r#"log.index_of("killed")"#,
+    ),
+    entry(
+        Section::Text,
+        "text.sub_string(start, length)",
+        "Part of the text",
+        "timer.sub_string(0, 2)",
+    ),
+    entry(
+        Section::Text,
+        "parse_int(text), parse_float(text)",
+        "A zone's text as a number (fails on text that isn't one)",
+        "parse_int(gold) >= 1300",
+    ),
+    entry(
+        Section::Numbers,
+        "abs(x), min(a, b), max(a, b)",
+        "Absolute value, smaller, larger",
+        "max(health, 0)",
+    ),
+    entry(
+        Section::Numbers,
+        "x.round(), x.floor(), x.ceiling()",
+        "Round a decimal number",
+        "(gold / 100.0).floor()",
+    ),
+    entry(
+        Section::Numbers,
+        "x.to_int(), x.to_float()",
+        "Convert between whole and decimal numbers",
+        "health.to_float() / 100.0",
+    ),
+    entry(
+        Section::Placeholders,
+        "{name}",
+        "In a tip message, the value of the profile variable `name`",
+        "Level {level}: buy the upgrade",
+    ),
+    entry(
+        Section::Placeholders,
+        "const parameter",
+        "In a library rule, each parameter is a constant holding the profile's value",
+        "if timer.playtime > minutes * 60 { alert(message) }",
+    ),
+];
+
+/// What a pipeline variable holds, from its name
+pub fn describe_variable(name: &str) -> &'static str {
+    match name {
+        "screen" => "Name of the recognized screen",
+        "screen.confidence" => "How sure screen recognition is (0-1)",
+        "phase" => "Name of the current game phase",
+        "scene.dark" => "Whether the scene is dark (caves, night)",
+        "scene.brightness" => "Average scene brightness (0-1)",
+        "cursor.x" | "cursor.y" => "Mouse cursor position in the frame, in pixels",
+        "capture.running" => "Whether capture is running",
+        "capture.fps" => "Frames captured per second",
+        "quiet_hours" => "Whether quiet hours hold back tips",
+        "timer.match" => "Seconds since the match started",
+        "timer.playtime" => "Seconds played this session",
+        _ if name.starts_with("zone.") => "Text read from an OCR zone",
+        _ if name.starts_with("trigger.") => "Whether a color trigger is active",
+        _ if name.starts_with("var.") => "Profile variable you entered",
+        _ => "",
+    }
+}
+
+/// Whether every word of a search query appears in one of the texts
+///
+/// Case-insensitive; an empty query matches everything.
+pub fn matches(query: &str, texts: &[&str]) -> bool {
+    let texts: Vec<String> = texts.iter().map(|text| text.to_lowercase()).collect();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| texts.iter().any(|text| text.contains(word)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        assert!(matches("", &["anything"]));
+        assert!(matches(
+            "LOWER case",
+            &["text.to_lower()", "regardless of case"]
+        ));
+        assert!(!matches("lower round", &["text.to_lower()", "case"]));
+
+        let found: Vec<_> = ENTRIES
+            .iter()
+            .filter(|e| matches("number", &[e.signature, e.description]))
+            .map(|e| e.signature)
+            .collect();
+        assert!(found.contains(&"parse_int(text), parse_float(text)"));
+        assert!(Section::ALL
+            .iter()
+            .all(|section| ENTRIES.iter().any(|e| e.section == *section)));
+    }
+
+    #[test]
+    fn test_describe_variable() {
+        assert_eq!(describe_variable("zone.Gold"), "Text read from an OCR zone");
+        assert_eq!(
+            describe_variable("timer.match"),
+            "Seconds since the match started"
+        );
+        assert_eq!(describe_variable("unknown"), "");
+    }
+}
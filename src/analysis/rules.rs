@@ -5,6 +5,8 @@
 
 use anyhow::Result;
 
+use crate::storage::profiles::{ConfidenceFloor, LowConfidenceAction, RuleDefinition};
+
 /// A rule definition from a game profile
 #[derive(Debug, Clone)]
 pub struct Rule {
@@ -16,6 +18,97 @@ pub struct Rule {
     pub enabled: bool,
     /// Rhai script code
     pub script: String,
+    /// Game phases the rule runs in (empty = all)
+    pub phases: Vec<String>,
+    /// Lowest confidence of the readings the rule acts on (None = any)
+    pub confidence_floor: Option<ConfidenceFloor>,
+}
+
+impl From<&RuleDefinition> for Rule {
+    fn from(definition: &RuleDefinition) -> Self {
+        Self {
+            id: definition.id.clone(),
+            name: definition.name.clone(),
+            enabled: definition.enabled,
+            script: definition.script.clone(),
+            phases: definition.phases.clone(),
+            confidence_floor: definition.confidence_floor,
+        }
+    }
+}
+
+/// Script functions that look at the recognized screen
+const SCREEN_FUNCTIONS: [&str; 3] = ["screen_is", "is_child_of", "has_screen"];
+
+/// How far a rule can trust the readings it acts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputConfidence {
+    /// At or above the rule's confidence floor
+    Confident,
+    /// Below it: tips are shown marked as uncertain
+    Uncertain,
+    /// Below it: the rule doesn't run
+    Suppressed,
+}
+
+impl Rule {
+    /// Whether the rule's script refers to a zone by ID
+    pub fn reads_zone(&self, zone_id: &str) -> bool {
+        self.mentions(zone_id)
+    }
+
+    /// Whether the rule's script uses a name as a whole identifier
+    fn mentions(&self, name: &str) -> bool {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        self.script.match_indices(name).any(|(start, _)| {
+            let before = self.script[..start].chars().next_back();
+            let after = self.script[start + name.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+    }
+
+    /// Lowest confidence of the zones the rule reads and, if it looks at
+    /// the screen, of the recognized screen (None if it reads neither)
+    pub fn input_confidence(&self, game_state: &GameState) -> Option<f32> {
+        let zones = game_state
+            .zone_confidences
+            .iter()
+            .filter(|(zone_id, _)| self.reads_zone(zone_id))
+            .map(|(_, &confidence)| confidence);
+        let screen = game_state
+            .screen_context
+            .has_screen()
+            .then_some(game_state.screen_context.confidence)
+            .filter(|_| SCREEN_FUNCTIONS.iter().any(|f| self.mentions(f)));
+        zones.chain(screen).reduce(f32::min)
+    }
+
+    /// Whether the readings the rule acts on clear its confidence floor
+    pub fn check_confidence(&self, game_state: &GameState) -> InputConfidence {
+        let Some(floor) = self.confidence_floor else {
+            return InputConfidence::Confident;
+        };
+        match self.input_confidence(game_state) {
+            Some(confidence) if confidence < floor.min => match floor.below {
+                LowConfidenceAction::MarkUncertain => InputConfidence::Uncertain,
+                LowConfidenceAction::Suppress => InputConfidence::Suppressed,
+            },
+            _ => InputConfidence::Confident,
+        }
+    }
+
+    /// Whether a zone the rule reads is covered by a popup, so its value is stale
+    pub fn reads_occluded_zone(&self, game_state: &GameState) -> bool {
+        game_state
+            .occluded_zones
+            .iter()
+            .any(|zone_id| self.reads_zone(zone_id))
+    }
+
+    /// Whether the rule runs in the current game phase
+    pub fn in_phase(&self, game_state: &GameState) -> bool {
+        super::phases::is_active(&self.phases, game_state.phase.as_deref())
+    }
 }
 
 /// Rules engine powered by rhai
@@ -38,7 +131,9 @@ impl RulesEngine {
 
     /// Evaluate all rules against current game state
     pub fn evaluate(&self, _game_state: &GameState) -> Result<Vec<RuleResult>> {
-        // TODO: Run all enabled rules
+        // TODO: Run all enabled rules of the current phase that aren't
+        // suppressed by occluded zones or low-confidence readings, and give
+        // tips a way to show the results of uncertain ones
         Ok(vec![])
     }
 }
@@ -54,10 +149,29 @@ impl Default for RulesEngine {
 pub struct GameState {
     /// Detected text by region ID
     pub text_values: std::collections::HashMap<String, String>,
+    /// OCR confidence (0.0 - 1.0) of the detected text by region ID
+    pub zone_confidences: std::collections::HashMap<String, f32>,
     /// Detected elements by ID
     pub elements: std::collections::HashMap<String, bool>,
     /// Current screen recognition context
     pub screen_context: ScreenContext,
+    /// Zones covered by a popup screen, whose text values are stale
+    pub occluded_zones: std::collections::HashSet<String>,
+    /// Scene brightness and saturation (e.g. `scene.dark` in a cave or at night)
+    pub scene: crate::vision::scene::SceneMetrics,
+    /// Mouse cursor position in the frame, if it was over the capture
+    pub cursor: Option<(u32, u32)>,
+    /// Profile variables entered by the user, by name
+    pub variables: std::collections::HashMap<String, String>,
+    /// ID of the current game phase
+    pub phase: Option<String>,
+}
+
+impl GameState {
+    /// A profile variable's value as a number, if it is one
+    pub fn number(&self, variable: &str) -> Option<f64> {
+        self.variables.get(variable)?.trim().parse().ok()
+    }
 }
 
 /// Screen recognition context for rules
@@ -132,3 +246,101 @@ pub struct RuleResult {
     /// Whether to trigger alert
     pub alert: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(script: &str) -> Rule {
+        Rule {
+            id: "low_health".to_string(),
+            name: "Low health".to_string(),
+            enabled: true,
+            script: script.to_string(),
+            phases: vec![],
+            confidence_floor: None,
+        }
+    }
+
+    #[test]
+    fn test_rules_reading_occluded_zones() {
+        let rule = rule(r#"if zone("health") < 30 { alert("Heal") }"#);
+        assert!(rule.reads_zone("health"));
+        assert!(!rule.reads_zone("heal"));
+
+        let mut game_state = GameState::default();
+        assert!(!rule.reads_occluded_zone(&game_state));
+        game_state.occluded_zones.insert("health_max".to_string());
+        assert!(!rule.reads_occluded_zone(&game_state));
+        game_state.occluded_zones.insert("health".to_string());
+        assert!(rule.reads_occluded_zone(&game_state));
+    }
+
+    #[test]
+    fn test_rules_run_in_their_phases() {
+        let mut rule = rule(r#"alert("Buy wards")"#);
+        let mut game_state = GameState::default();
+        assert!(rule.in_phase(&game_state));
+
+        rule.phases = vec!["late".to_string()];
+        assert!(!rule.in_phase(&game_state));
+        game_state.phase = Some("late".to_string());
+        assert!(rule.in_phase(&game_state));
+    }
+
+    #[test]
+    fn test_confidence_floor() {
+        let mut rule = rule(r#"if zone("health") < 30 && screen_is("Battle") { alert("Heal") }"#);
+        let mut game_state = GameState::default();
+        game_state
+            .zone_confidences
+            .insert("health".to_string(), 0.9);
+        game_state.zone_confidences.insert("mana".to_string(), 0.2);
+        game_state.screen_context.current_screen_id = Some("battle".to_string());
+        game_state.screen_context.confidence = 0.5;
+
+        assert_eq!(rule.input_confidence(&game_state), Some(0.5));
+        assert_eq!(
+            rule.check_confidence(&game_state),
+            InputConfidence::Confident
+        );
+
+        rule.confidence_floor = Some(ConfidenceFloor {
+            min: 0.6,
+            below: LowConfidenceAction::MarkUncertain,
+        });
+        assert_eq!(
+            rule.check_confidence(&game_state),
+            InputConfidence::Uncertain
+        );
+        rule.confidence_floor = Some(ConfidenceFloor {
+            min: 0.6,
+            below: LowConfidenceAction::Suppress,
+        });
+        assert_eq!(
+            rule.check_confidence(&game_state),
+            InputConfidence::Suppressed
+        );
+
+        game_state.screen_context.confidence = 0.95;
+        assert_eq!(
+            rule.check_confidence(&game_state),
+            InputConfidence::Confident
+        );
+    }
+
+    #[test]
+    fn test_numeric_variables() {
+        let mut game_state = GameState::default();
+        game_state
+            .variables
+            .insert("level".to_string(), " 42 ".to_string());
+        game_state
+            .variables
+            .insert("difficulty".to_string(), "Hard".to_string());
+
+        assert_eq!(game_state.number("level"), Some(42.0));
+        assert_eq!(game_state.number("difficulty"), None);
+        assert_eq!(game_state.number("missing"), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Pipeline pause and single-step
+//!
+//! Pausing holds live zone OCR and screen recognition on what they last
+//! read. Each step then runs them once on the next captured frame and keeps
+//! what every stage produced for it: the zone crops as OCR got them, every
+//! screen's match score and how each rule's checks came out. Stepped
+//! readings aren't recorded to the session.
+
+use std::time::{Duration, Instant};
+
+use super::rules::{GameState, InputConfidence, Rule};
+use crate::vision::screen_recognition::ScreenScore;
+use crate::vision::TextRegion;
+
+/// Whether the pipeline runs live or waits for steps
+#[derive(Debug, Default)]
+pub struct PipelineStepper {
+    /// Live analysis is held
+    paused: bool,
+    /// A step waits for the next frame
+    step_requested: bool,
+    /// Steps taken since starting
+    steps: u64,
+}
+
+impl PipelineStepper {
+    /// Whether live analysis is held
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Hold live analysis
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Let live analysis run again
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.step_requested = false;
+    }
+
+    /// Run the pipeline once on the next frame, pausing it first
+    pub fn request_step(&mut self) {
+        self.paused = true;
+        self.step_requested = true;
+    }
+
+    /// Whether a step waits for a frame
+    pub fn wants_step(&self) -> bool {
+        self.paused && self.step_requested
+    }
+
+    /// Start the requested step, returning its number
+    pub fn take_step(&mut self) -> Option<u64> {
+        if !self.wants_step() {
+            return None;
+        }
+        self.step_requested = false;
+        self.steps += 1;
+        Some(self.steps)
+    }
+}
+
+/// A zone as it was read on the stepped frame
+#[derive(Debug, Clone)]
+pub struct ZoneStep {
+    /// Zone ID
+    pub zone_id: String,
+    /// Zone name
+    pub zone_name: String,
+    /// Region OCR got, after geometry correction and preprocessing
+    /// (RGBA data, width, height)
+    pub crop: Option<(Vec<u8>, u32, u32)>,
+    /// Text regions the backend found in it
+    pub regions: Vec<TextRegion>,
+    /// Reading kept for the zone
+    pub text: String,
+    /// Confidence of the reading
+    pub confidence: Option<f32>,
+    /// Why the zone wasn't read, if it wasn't
+    pub skipped: Option<String>,
+}
+
+impl ZoneStep {
+    /// A zone the step didn't read
+    pub fn skipped(zone_id: &str, zone_name: &str, reason: impl Into<String>) -> Self {
+        Self {
+            zone_id: zone_id.to_string(),
+            zone_name: zone_name.to_string(),
+            crop: None,
+            regions: Vec::new(),
+            text: String::new(),
+            confidence: None,
+            skipped: Some(reason.into()),
+        }
+    }
+}
+
+/// How a rule's checks came out on the stepped frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleStep {
+    /// Rule ID
+    pub rule_id: String,
+    /// Rule name
+    pub name: String,
+    /// Whether the rule is enabled
+    pub enabled: bool,
+    /// Whether it runs in the current game phase
+    pub in_phase: bool,
+    /// Whether a zone it reads is covered by a popup
+    pub occluded: bool,
+    /// Lowest confidence of the readings it acts on
+    pub input_confidence: Option<f32>,
+    /// How those readings compare with its confidence floor
+    pub confidence: InputConfidence,
+}
+
+impl RuleStep {
+    /// Check a rule against the stepped frame's game state
+    pub fn check(rule: &Rule, game_state: &GameState) -> Self {
+        Self {
+            rule_id: rule.id.clone(),
+            name: rule.name.clone(),
+            enabled: rule.enabled,
+            in_phase: rule.in_phase(game_state),
+            occluded: rule.reads_occluded_zone(game_state),
+            input_confidence: rule.input_confidence(game_state),
+            confidence: rule.check_confidence(game_state),
+        }
+    }
+
+    /// Whether the rule would run on the frame
+    pub fn runs(&self) -> bool {
+        self.enabled
+            && self.in_phase
+            && !self.occluded
+            && self.confidence != InputConfidence::Suppressed
+    }
+}
+
+/// What each stage produced for one stepped frame
+#[derive(Debug, Clone)]
+pub struct StepArtifacts {
+    /// Number of the step
+    pub step: u64,
+    /// When the frame was stepped
+    pub at: Instant,
+    /// Frame size
+    pub frame_size: (u32, u32),
+    /// Time the whole step took
+    pub duration: Duration,
+    /// Zones, in zone order
+    pub zones: Vec<ZoneStep>,
+    /// Screens, in priority order (empty when recognition is off)
+    pub screens: Vec<ScreenScore>,
+    /// Rules of the active profile
+    pub rules: Vec<RuleStep>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::{ConfidenceFloor, LowConfidenceAction};
+
+    #[test]
+    fn test_steps_only_while_paused() {
+        let mut stepper = PipelineStepper::default();
+        assert_eq!(stepper.take_step(), None);
+
+        stepper.request_step();
+        assert!(stepper.is_paused());
+        assert_eq!(stepper.take_step(), Some(1));
+        assert_eq!(stepper.take_step(), None);
+        assert!(stepper.is_paused());
+
+        stepper.request_step();
+        stepper.resume();
+        assert!(!stepper.wants_step());
+        stepper.request_step();
+        assert_eq!(stepper.take_step(), Some(2));
+    }
+
+    #[test]
+    fn test_rule_step_checks() {
+        let rule = Rule {
+            id: "low_hp".to_string(),
+            name: "Low HP".to_string(),
+            enabled: true,
+            script: "if health < 30 { tip(\"Heal\") }".to_string(),
+            phases: vec!["combat".to_string()],
+            confidence_floor: Some(ConfidenceFloor {
+                min: 0.8,
+                below: LowConfidenceAction::Suppress,
+            }),
+        };
+        let mut game_state = GameState {
+            phase: Some("combat".to_string()),
+            ..Default::default()
+        };
+        game_state
+            .zone_confidences
+            .insert("health".to_string(), 0.9);
+        let step = RuleStep::check(&rule, &game_state);
+        assert!(step.runs());
+        assert_eq!(step.input_confidence, Some(0.9));
+
+        game_state
+            .zone_confidences
+            .insert("health".to_string(), 0.5);
+        let step = RuleStep::check(&rule, &game_state);
+        assert_eq!(step.confidence, InputConfidence::Suppressed);
+        assert!(!step.runs());
+
+        game_state
+            .zone_confidences
+            .insert("health".to_string(), 0.9);
+        game_state.occluded_zones.insert("health".to_string());
+        assert!(!RuleStep::check(&rule, &game_state).runs());
+
+        game_state.occluded_zones.clear();
+        game_state.phase = Some("menu".to_string());
+        let step = RuleStep::check(&rule, &game_state);
+        assert!(!step.in_phase && !step.runs());
+    }
+}
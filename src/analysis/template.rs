@@ -0,0 +1,59 @@
+//! Tip message templates
+//!
+//! Tip messages may name profile variables as `{name}`, filled in when the
+//! tip is shown (e.g. "Level {level}: buy the upgrade").
+
+use std::collections::HashMap;
+
+/// Replace `{name}` placeholders with the variables' values
+///
+/// Placeholders naming no variable are left as written.
+pub fn fill_template(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| Some((end, variables.get(after[..end].trim())?)));
+        match value {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_template() {
+        let variables: HashMap<String, String> = [
+            ("level".to_string(), "42".to_string()),
+            ("difficulty".to_string(), "Hard".to_string()),
+        ]
+        .into();
+
+        assert_eq!(
+            fill_template("Level {level} on { difficulty }", &variables),
+            "Level 42 on Hard"
+        );
+        assert_eq!(
+            fill_template("{unknown} {level}", &variables),
+            "{unknown} 42"
+        );
+        assert_eq!(fill_template("{{level}} {", &variables), "{42} {");
+        assert_eq!(fill_template("No variables", &variables), "No variables");
+    }
+}
@@ -0,0 +1,231 @@
+//! Watch expressions
+//!
+//! A debugger-style watch list over the pipeline's live values (zone
+//! readings, the current screen and phase, color triggers, scene flags,
+//! timers). A watch can carry an ad-hoc condition that alerts, like a
+//! breakpoint, when it starts to hold; nothing is saved to the profile.
+
+use std::collections::BTreeMap;
+
+use crate::vision::zone_text::numeric_value;
+
+/// Live values by variable name, e.g. `zone.health` or `screen`
+pub type WatchValues = BTreeMap<String, String>;
+
+/// Alerts kept in the watch window
+pub const MAX_HITS: usize = 50;
+
+/// Variable name of a named zone or trigger, e.g. `zone.Ammo_Count`
+///
+/// Spaces become underscores; without a name the ID is used.
+pub fn variable_name(prefix: &str, name: &str, id: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+    format!("{}.{}", prefix, if name.is_empty() { id } else { &name })
+}
+
+/// How a watched value is tested
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WatchOp {
+    /// The value differs from the previous one
+    #[default]
+    Changes,
+    /// The value is the text (case-insensitive)
+    Equals,
+    /// The value isn't the text (case-insensitive)
+    NotEquals,
+    /// The value contains the text (case-insensitive)
+    Contains,
+    /// The value is a number above the threshold
+    Above,
+    /// The value is a number below the threshold
+    Below,
+}
+
+impl WatchOp {
+    /// Every test, in menu order
+    pub const ALL: [WatchOp; 6] = [
+        WatchOp::Changes,
+        WatchOp::Equals,
+        WatchOp::NotEquals,
+        WatchOp::Contains,
+        WatchOp::Above,
+        WatchOp::Below,
+    ];
+
+    /// Short label for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            WatchOp::Changes => "changes",
+            WatchOp::Equals => "=",
+            WatchOp::NotEquals => "!=",
+            WatchOp::Contains => "contains",
+            WatchOp::Above => ">",
+            WatchOp::Below => "<",
+        }
+    }
+
+    /// Whether the test compares against a text or number
+    pub fn takes_operand(self) -> bool {
+        self != WatchOp::Changes
+    }
+}
+
+/// Condition that alerts when it starts to hold
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchCondition {
+    /// Test applied to the value
+    pub op: WatchOp,
+    /// Text or number compared against
+    pub operand: String,
+}
+
+impl WatchCondition {
+    /// Whether the condition holds for a value (`None` = not available)
+    ///
+    /// `Changes` holds when the value differs from the previous one, but not
+    /// for the first value seen.
+    pub fn holds(&self, previous: Option<&str>, current: Option<&str>) -> bool {
+        let Some(current) = current else {
+            return false;
+        };
+        let operand = self.operand.trim();
+        match self.op {
+            WatchOp::Changes => previous.is_some_and(|previous| previous != current),
+            WatchOp::Equals => current.trim().eq_ignore_ascii_case(operand),
+            WatchOp::NotEquals => !current.trim().eq_ignore_ascii_case(operand),
+            WatchOp::Contains => current
+                .to_lowercase()
+                .contains(operand.to_lowercase().as_str()),
+            WatchOp::Above => compare(current, operand).is_some_and(|(v, t)| v > t),
+            WatchOp::Below => compare(current, operand).is_some_and(|(v, t)| v < t),
+        }
+    }
+}
+
+/// A value and its threshold as numbers
+fn compare(value: &str, threshold: &str) -> Option<(f64, f64)> {
+    Some((numeric_value(value)?, numeric_value(threshold)?))
+}
+
+/// A pinned variable
+#[derive(Debug, Clone, Default)]
+pub struct Watch {
+    /// Variable name
+    pub variable: String,
+    /// Alert condition (None = only show the value)
+    pub condition: Option<WatchCondition>,
+    /// Freeze the vision preview on the frame the condition started to hold
+    pub break_on_hit: bool,
+    /// Times the condition started to hold
+    pub hits: u32,
+    /// Value at the last update
+    pub value: Option<String>,
+    /// Whether the condition held at the last update
+    held: bool,
+}
+
+impl Watch {
+    /// Watch a variable without a condition
+    pub fn new(variable: impl Into<String>) -> Self {
+        Self {
+            variable: variable.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Take the variable's current value
+    ///
+    /// Returns whether the condition just started to hold (a hit). A
+    /// condition that keeps holding doesn't hit again until it stopped
+    /// holding in between.
+    pub fn update(&mut self, values: &WatchValues) -> bool {
+        let current = values.get(&self.variable).cloned();
+        let holds = self
+            .condition
+            .as_ref()
+            .is_some_and(|condition| condition.holds(self.value.as_deref(), current.as_deref()));
+        // Every change is a new hit
+        let hit = holds
+            && (!self.held || self.condition.as_ref().map(|c| c.op) == Some(WatchOp::Changes));
+
+        self.held = holds;
+        self.value = current;
+        if hit {
+            self.hits += 1;
+        }
+        hit
+    }
+
+    /// Forget the previous value and hits, e.g. after the condition changed
+    pub fn rearm(&mut self) {
+        self.held = false;
+        self.hits = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> WatchValues {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_variable_names() {
+        assert_eq!(
+            variable_name("zone", " Ammo  count ", "z1"),
+            "zone.Ammo_count"
+        );
+        assert_eq!(variable_name("trigger", "", "t1"), "trigger.t1");
+    }
+
+    #[test]
+    fn test_conditions() {
+        let condition = |op, operand: &str| WatchCondition {
+            op,
+            operand: operand.to_string(),
+        };
+
+        assert!(condition(WatchOp::Below, "30").holds(None, Some("25%")));
+        assert!(!condition(WatchOp::Below, "30").holds(None, Some("1,250")));
+        assert!(!condition(WatchOp::Above, "30").holds(None, Some("n/a")));
+        assert!(condition(WatchOp::Equals, "Shop").holds(None, Some(" shop ")));
+        assert!(condition(WatchOp::Contains, "low").holds(None, Some("Ammo LOW")));
+        assert!(!condition(WatchOp::NotEquals, "x").holds(None, None));
+
+        let changes = condition(WatchOp::Changes, "");
+        assert!(!changes.holds(None, Some("a")));
+        assert!(!changes.holds(Some("a"), Some("a")));
+        assert!(changes.holds(Some("a"), Some("b")));
+    }
+
+    #[test]
+    fn test_hits_when_condition_starts_to_hold() {
+        let mut watch = Watch::new("zone.health");
+        watch.condition = Some(WatchCondition {
+            op: WatchOp::Below,
+            operand: "30".to_string(),
+        });
+
+        assert!(!watch.update(&values(&[("zone.health", "80")])));
+        assert!(watch.update(&values(&[("zone.health", "20")])));
+        // Still low: no new hit
+        assert!(!watch.update(&values(&[("zone.health", "15")])));
+        assert!(!watch.update(&values(&[("zone.health", "90")])));
+        assert!(watch.update(&values(&[("zone.health", "10")])));
+        assert_eq!(watch.hits, 2);
+        assert_eq!(watch.value.as_deref(), Some("10"));
+
+        // Every change is a hit
+        let mut watch = Watch::new("screen");
+        watch.condition = Some(WatchCondition::default());
+        assert!(!watch.update(&values(&[("screen", "Lobby")])));
+        assert!(watch.update(&values(&[("screen", "Shop")])));
+        assert!(watch.update(&values(&[("screen", "Lobby")])));
+        assert!(!watch.update(&values(&[("screen", "Lobby")])));
+    }
+}
@@ -0,0 +1,188 @@
+//! Playtime reminders and limits
+//!
+//! Tracks how long the player has been playing without a break, counting
+//! from when capture starts. Stopping capture for a few minutes doesn't end
+//! the session; only a real break does.
+
+use std::time::{Duration, Instant};
+
+use crate::config::WellnessSettings;
+
+/// Pause after which play counts as a new session
+pub const MIN_BREAK: Duration = Duration::from_secs(5 * 60);
+
+/// Something to tell the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WellnessEvent {
+    /// Time for a break; played this long without one
+    BreakReminder { played: Duration },
+    /// Played past the limit; show the overlay nag
+    NagStarted { played: Duration },
+    /// Capture stopped; hide the overlay nag
+    NagEnded,
+}
+
+/// Continuous play session
+#[derive(Debug, Default)]
+pub struct PlaySession {
+    /// When the session started
+    started: Option<Instant>,
+    /// When capture stopped, if it did
+    paused_at: Option<Instant>,
+    /// Break reminders shown this session
+    reminders: u32,
+    /// Whether the overlay nag is showing
+    nagging: bool,
+}
+
+impl PlaySession {
+    /// Time played in the current session (zero between sessions)
+    pub fn played(&self, now: Instant) -> Duration {
+        let end = self.paused_at.unwrap_or(now);
+        self.started.map_or(Duration::ZERO, |started| {
+            end.saturating_duration_since(started)
+        })
+    }
+
+    /// Whether the overlay nag is showing
+    pub fn is_nagging(&self) -> bool {
+        self.nagging
+    }
+
+    /// Advance the session, returning what to tell the player
+    pub fn update(
+        &mut self,
+        capturing: bool,
+        now: Instant,
+        settings: &WellnessSettings,
+    ) -> Vec<WellnessEvent> {
+        let mut events = Vec::new();
+
+        if !capturing {
+            if self.started.is_some() && self.paused_at.is_none() {
+                self.paused_at = Some(now);
+            }
+            if self.nagging {
+                self.nagging = false;
+                events.push(WellnessEvent::NagEnded);
+            }
+            if self
+                .paused_at
+                .is_some_and(|paused| now.saturating_duration_since(paused) >= MIN_BREAK)
+            {
+                *self = Self::default();
+            }
+            return events;
+        }
+
+        if let Some(paused) = self.paused_at.take() {
+            // Resuming within the break time continues the session
+            if let Some(ref mut started) = self.started {
+                *started += now.saturating_duration_since(paused);
+            }
+        }
+        let started = *self.started.get_or_insert(now);
+        let played = now.saturating_duration_since(started);
+
+        if settings.break_reminder_minutes > 0 {
+            let interval = Duration::from_secs(settings.break_reminder_minutes as u64 * 60);
+            let due = (played.as_secs() / interval.as_secs()) as u32;
+            if due > self.reminders {
+                self.reminders = due;
+                events.push(WellnessEvent::BreakReminder { played });
+            }
+        }
+
+        let nag_after = Duration::from_secs(settings.overlay_nag_minutes as u64 * 60);
+        if settings.overlay_nag_minutes > 0 && played >= nag_after && !self.nagging {
+            self.nagging = true;
+            events.push(WellnessEvent::NagStarted { played });
+        }
+
+        events
+    }
+}
+
+/// Format a play time as "2h 05m" or "45m"
+pub fn format_playtime(played: Duration) -> String {
+    let minutes = played.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn settings() -> WellnessSettings {
+        WellnessSettings {
+            break_reminder_minutes: 30,
+            overlay_nag_minutes: 90,
+        }
+    }
+
+    #[test]
+    fn test_reminders_and_nag() {
+        let start = Instant::now();
+        let mut session = PlaySession::default();
+
+        assert!(session.update(true, start, &settings()).is_empty());
+        assert!(session
+            .update(true, start + 29 * MINUTE, &settings())
+            .is_empty());
+        assert_eq!(
+            session.update(true, start + 31 * MINUTE, &settings()),
+            [WellnessEvent::BreakReminder {
+                played: 31 * MINUTE
+            }]
+        );
+        assert!(session
+            .update(true, start + 40 * MINUTE, &settings())
+            .is_empty());
+
+        assert_eq!(
+            session.update(true, start + 90 * MINUTE, &settings()),
+            [
+                WellnessEvent::BreakReminder {
+                    played: 90 * MINUTE
+                },
+                WellnessEvent::NagStarted {
+                    played: 90 * MINUTE
+                }
+            ]
+        );
+        assert!(session.is_nagging());
+
+        assert_eq!(
+            session.update(false, start + 91 * MINUTE, &settings()),
+            [WellnessEvent::NagEnded]
+        );
+    }
+
+    #[test]
+    fn test_short_pause_continues_session() {
+        let start = Instant::now();
+        let mut session = PlaySession::default();
+
+        session.update(true, start, &settings());
+        session.update(false, start + 20 * MINUTE, &settings());
+        session.update(true, start + 22 * MINUTE, &settings());
+        // The two paused minutes don't count as play
+        assert_eq!(session.played(start + 25 * MINUTE), 23 * MINUTE);
+
+        session.update(false, start + 25 * MINUTE, &settings());
+        session.update(false, start + 31 * MINUTE, &settings());
+        assert_eq!(session.played(start + 31 * MINUTE), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_format_playtime() {
+        assert_eq!(format_playtime(45 * MINUTE), "45m");
+        assert_eq!(format_playtime(125 * MINUTE), "2h 05m");
+    }
+}
@@ -5,7 +5,7 @@
 //! including shared state and inter-component communication.
 
 use anyhow::Result;
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crossbeam_channel::Receiver;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use std::thread::JoinHandle;
@@ -14,6 +14,7 @@ use tracing::info;
 use crate::analysis::Tip;
 use crate::config::AppConfig;
 use crate::overlay::{OverlayConfig, OverlayManager};
+use crate::shared::channels::{event_queue, EventSender, COMMAND_QUEUE_CAPACITY};
 use crate::shared::{DashboardToOverlay, OverlayToDashboard, SharedAppState};
 
 /// Main application coordinator
@@ -21,7 +22,7 @@ pub struct GamersToolKitApp {
     /// Shared state between dashboard and overlay
     pub shared_state: Arc<RwLock<SharedAppState>>,
     /// Channel to send messages to overlay
-    pub to_overlay: Sender<DashboardToOverlay>,
+    pub to_overlay: EventSender<DashboardToOverlay>,
     /// Channel to receive messages from overlay
     pub from_overlay: Receiver<OverlayToDashboard>,
     /// Handle to overlay thread
@@ -34,8 +35,8 @@ impl GamersToolKitApp {
     /// Create a new application coordinator
     pub fn new(config: AppConfig) -> Result<Self> {
         let shared_state = Arc::new(RwLock::new(SharedAppState::new(config)));
-        let (to_overlay, _overlay_rx) = unbounded();
-        let (_overlay_tx, from_overlay) = unbounded();
+        let (to_overlay, _overlay_rx) = event_queue(COMMAND_QUEUE_CAPACITY);
+        let (_overlay_tx, from_overlay) = event_queue::<OverlayToDashboard>(COMMAND_QUEUE_CAPACITY);
 
         Ok(Self {
             shared_state,
@@ -83,7 +84,7 @@ impl GamersToolKitApp {
     }
 
     /// Get a tip sender for external use
-    pub fn tip_sender(&self) -> Option<Sender<Tip>> {
+    pub fn tip_sender(&self) -> Option<EventSender<Tip>> {
         self.overlay_manager.as_ref().map(|m| m.tip_sender())
     }
 
@@ -0,0 +1,111 @@
+//! Cropping window captures to the client area
+//!
+//! A windowed game's capture includes its title bar and borders, which a
+//! borderless one doesn't have, so zones placed in one mode are off in the
+//! other. Cropping frames to the client area makes both show the same pixels.
+
+use std::ffi::c_void;
+use windows::Win32::Foundation::{HWND, POINT, RECT};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::Graphics::Gdi::ClientToScreen;
+use windows::Win32::UI::WindowsAndMessaging::{GetClientRect, GetWindowRect};
+
+/// Screen rectangle a window capture shows
+///
+/// Captures cover the visible frame, without the invisible resize borders
+/// `GetWindowRect` includes.
+pub(super) fn visible_bounds(hwnd: usize) -> Option<RECT> {
+    let hwnd = HWND(hwnd as *mut c_void);
+    let mut rect = RECT::default();
+    // SAFETY: plain Win32 queries writing into a local that outlives them
+    unsafe {
+        let visible = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut RECT as *mut c_void,
+            std::mem::size_of::<RECT>() as u32,
+        );
+        if visible.is_err() {
+            GetWindowRect(hwnd, &mut rect).ok()?;
+        }
+    }
+    Some(rect)
+}
+
+/// Client area of a window in a `width` x `height` frame of it, as
+/// `(x, y, width, height)` in frame pixels
+pub fn client_area_in_frame(hwnd: usize, width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+    let bounds = visible_bounds(hwnd)?;
+    let handle = HWND(hwnd as *mut c_void);
+    let mut client = RECT::default();
+    let mut origin = POINT::default();
+    // SAFETY: plain Win32 queries writing into locals that outlive them
+    unsafe {
+        GetClientRect(handle, &mut client).ok()?;
+        if !ClientToScreen(handle, &mut origin).as_bool() {
+            return None;
+        }
+    }
+    crop_rect(
+        (
+            origin.x,
+            origin.y,
+            origin.x + client.right,
+            origin.y + client.bottom,
+        ),
+        (bounds.left, bounds.top, bounds.right, bounds.bottom),
+        width,
+        height,
+    )
+}
+
+/// The `client` screen rectangle inside a frame showing `bounds`, both as
+/// `(left, top, right, bottom)`; None if the client area is empty or the
+/// frame already shows nothing else
+fn crop_rect(
+    client: (i32, i32, i32, i32),
+    bounds: (i32, i32, i32, i32),
+    width: u32,
+    height: u32,
+) -> Option<(u32, u32, u32, u32)> {
+    let span_x = i64::from(bounds.2 - bounds.0);
+    let span_y = i64::from(bounds.3 - bounds.1);
+    if span_x <= 0 || span_y <= 0 {
+        return None;
+    }
+    // Window sizes can differ from frame sizes (DPI scaling)
+    let to_x = |x: i32| {
+        (i64::from(x.clamp(bounds.0, bounds.2) - bounds.0) * i64::from(width) / span_x) as u32
+    };
+    let to_y = |y: i32| {
+        (i64::from(y.clamp(bounds.1, bounds.3) - bounds.1) * i64::from(height) / span_y) as u32
+    };
+    let (left, top) = (to_x(client.0), to_y(client.1));
+    let (right, bottom) = (to_x(client.2), to_y(client.3));
+    if right <= left || bottom <= top || (right - left, bottom - top) == (width, height) {
+        return None;
+    }
+    Some((left, top, right - left, bottom - top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crop_rect() {
+        // 8px borders and a 31px title bar around a 1280x720 client area
+        let bounds = (100, 100, 1396, 859);
+        let client = (108, 131, 1388, 851);
+        assert_eq!(
+            crop_rect(client, bounds, 1296, 759),
+            Some((8, 31, 1280, 720))
+        );
+        // Half-size frame
+        assert_eq!(crop_rect(client, bounds, 648, 379), Some((4, 15, 640, 360)));
+        // Borderless: nothing to crop
+        assert_eq!(crop_rect(bounds, bounds, 1296, 759), None);
+        // Minimized window
+        assert_eq!(crop_rect(client, (0, 0, 0, 0), 1296, 759), None);
+    }
+}
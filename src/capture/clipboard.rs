@@ -0,0 +1,29 @@
+//! Clipboard images
+//!
+//! Reads an image pasted to the clipboard (e.g. a screenshot someone sent) as
+//! a frame, so it can go through the same vision pipeline as captured frames.
+
+use anyhow::{anyhow, Result};
+
+use super::CapturedFrame;
+
+/// Read the image currently on the clipboard
+pub fn frame_from_clipboard() -> Result<CapturedFrame> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| anyhow!("Failed to open the clipboard: {}", e))?;
+    let image = clipboard.get_image().map_err(|e| match e {
+        arboard::Error::ContentNotAvailable => anyhow!("No image on the clipboard"),
+        e => anyhow!("Failed to read the clipboard image: {}", e),
+    })?;
+
+    if image.width == 0 || image.height == 0 {
+        return Err(anyhow!("The clipboard image is empty"));
+    }
+
+    // arboard returns RGBA, the frame's native layout
+    Ok(CapturedFrame::new(
+        image.bytes.into_owned(),
+        image.width as u32,
+        image.height as u32,
+    ))
+}
@@ -0,0 +1,360 @@
+//! Composite capture
+//!
+//! Dual-window games (a flight sim and its moving map, a game and a companion
+//! app) can be captured as one: each source is captured on its own, cropped
+//! to its region and placed side by side in a single frame that one vision
+//! pipeline processes. Zones name the source they belong to and are placed
+//! within it, so they stay put when another source changes size.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use super::{elevation, CaptureConfig, CaptureTarget, CapturedFrame, PixelFormat, ScreenCapture};
+use crate::config::{CompositeSource, CompositeTarget};
+use crate::shared::channels::BroadcastSender;
+
+/// Where a source ended up in the composite frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRect {
+    /// Source identifier
+    pub id: String,
+    /// Bounds (x, y, width, height) as fractions of the composite frame
+    pub bounds: (f32, f32, f32, f32),
+}
+
+/// Arrangement of the sources in the latest composite frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompositeLayout {
+    /// Sources from left to right
+    pub sources: Vec<SourceRect>,
+}
+
+impl CompositeLayout {
+    /// Bounds of a source in the composite frame
+    pub fn source_bounds(&self, id: &str) -> Option<(f32, f32, f32, f32)> {
+        self.sources
+            .iter()
+            .find(|source| source.id == id)
+            .map(|source| source.bounds)
+    }
+}
+
+/// Zone bounds in the captured frame
+///
+/// Bounds of a zone without a source are relative to the whole frame; those
+/// of a zone with one are relative to that source. `None` while the zone's
+/// source isn't part of the capture.
+pub fn frame_bounds(
+    layout: Option<&CompositeLayout>,
+    source: Option<&str>,
+    bounds: (f32, f32, f32, f32),
+) -> Option<(f32, f32, f32, f32)> {
+    let Some(source) = source else {
+        return Some(bounds);
+    };
+    let (sx, sy, sw, sh) = layout?.source_bounds(source)?;
+    Some((
+        sx + bounds.0 * sw,
+        sy + bounds.1 * sh,
+        bounds.2 * sw,
+        bounds.3 * sh,
+    ))
+}
+
+/// Zone bounds relative to a source, from bounds in the captured frame
+///
+/// The inverse of [`frame_bounds`], for moving a zone to another source
+/// without moving it on screen.
+pub fn source_bounds(
+    layout: Option<&CompositeLayout>,
+    source: Option<&str>,
+    bounds: (f32, f32, f32, f32),
+) -> Option<(f32, f32, f32, f32)> {
+    let Some(source) = source else {
+        return Some(bounds);
+    };
+    let (sx, sy, sw, sh) = layout?.source_bounds(source)?;
+    if sw <= 0.0 || sh <= 0.0 {
+        return None;
+    }
+    Some((
+        (bounds.0 - sx) / sw,
+        (bounds.1 - sy) / sh,
+        bounds.2 / sw,
+        bounds.3 / sh,
+    ))
+}
+
+/// Crop each source's frame to its region and place them side by side
+///
+/// Sources are top-aligned; the space below shorter ones stays black.
+pub fn compose(parts: &[(&CompositeSource, &CapturedFrame)]) -> (CapturedFrame, CompositeLayout) {
+    let crops: Vec<(u32, u32, u32, u32)> = parts
+        .iter()
+        .map(|(source, frame)| crop_rect(frame, source.region))
+        .collect();
+    let width: u32 = crops.iter().map(|c| c.2).sum();
+    let height = crops.iter().map(|c| c.3).max().unwrap_or(0);
+
+    // Grayscale parts stay grayscale, unless another part is in color
+    let format = if parts.iter().all(|(_, f)| f.format == PixelFormat::Gray8) {
+        PixelFormat::Gray8
+    } else {
+        PixelFormat::Rgba8
+    };
+    let bytes_per_pixel = format.bytes_per_pixel();
+    let mut data = vec![0u8; width as usize * height as usize * bytes_per_pixel];
+    if format == PixelFormat::Rgba8 {
+        for pixel in data.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+    }
+
+    let stride = width as usize * bytes_per_pixel;
+    let mut sources = Vec::with_capacity(parts.len());
+    let mut left = 0;
+    for ((source, frame), (x, y, w, h)) in parts.iter().zip(&crops) {
+        let frame = match format {
+            PixelFormat::Rgba8 => frame.to_rgba(),
+            PixelFormat::Gray8 => Cow::Borrowed(*frame),
+        };
+        let row_len = *w as usize * bytes_per_pixel;
+        for row in 0..*h as usize {
+            let src =
+                ((*y as usize + row) * frame.stride() as usize) + *x as usize * bytes_per_pixel;
+            let dst = row * stride + left as usize * bytes_per_pixel;
+            data[dst..dst + row_len].copy_from_slice(&frame.data[src..src + row_len]);
+        }
+        sources.push(SourceRect {
+            id: source.id.clone(),
+            bounds: (
+                left as f32 / width as f32,
+                0.0,
+                *w as f32 / width as f32,
+                *h as f32 / height as f32,
+            ),
+        });
+        left += w;
+    }
+
+    // The composite is as old as its oldest part
+    let mut frame = CapturedFrame::new(data, width, height);
+    frame.format = format;
+    if let Some(oldest) = parts.iter().map(|(_, part)| part.timestamp).min() {
+        frame.timestamp = oldest;
+    }
+    (frame, CompositeLayout { sources })
+}
+
+/// Pixel rectangle of a region (fractions of the frame), at least 1x1
+fn crop_rect(frame: &CapturedFrame, region: Option<(f32, f32, f32, f32)>) -> (u32, u32, u32, u32) {
+    let (rx, ry, rw, rh) = region.unwrap_or((0.0, 0.0, 1.0, 1.0));
+    let x = ((rx.clamp(0.0, 1.0) * frame.width as f32) as u32).min(frame.width.saturating_sub(1));
+    let y = ((ry.clamp(0.0, 1.0) * frame.height as f32) as u32).min(frame.height.saturating_sub(1));
+    let w = ((rw.clamp(0.0, 1.0) * frame.width as f32) as u32)
+        .max(1)
+        .min(frame.width - x);
+    let h = ((rh.clamp(0.0, 1.0) * frame.height as f32) as u32)
+        .max(1)
+        .min(frame.height - y);
+    (x, y, w, h)
+}
+
+/// Capture target of a single source
+pub fn source_target(target: &CompositeTarget) -> CaptureTarget {
+    match target {
+        CompositeTarget::Window(title) => CaptureTarget::Window(title.clone()),
+        CompositeTarget::Monitor(index) => CaptureTarget::MonitorIndex(*index),
+    }
+}
+
+/// State shared between a composite capture and its `ScreenCapture`
+pub(super) struct CompositeShared {
+    pub running: Arc<AtomicBool>,
+    pub source_closed: Arc<AtomicBool>,
+    pub fps_limit: Arc<AtomicU32>,
+    pub snapshot_interval_ms: Arc<AtomicU64>,
+    pub low_priority: Arc<AtomicBool>,
+    pub layout: Arc<RwLock<Option<CompositeLayout>>>,
+}
+
+/// Capture every source and send composite frames until stopped (blocking)
+pub(super) fn run_composite(
+    config: CaptureConfig,
+    sources: Vec<CompositeSource>,
+    tx: BroadcastSender<CapturedFrame>,
+    shared: CompositeShared,
+) -> Result<()> {
+    if sources.is_empty() {
+        anyhow::bail!("Composite capture has no sources");
+    }
+
+    let mut captures = Vec::with_capacity(sources.len());
+    for source in &sources {
+        let mut capture = ScreenCapture::new(CaptureConfig {
+            target: source_target(&source.target),
+            gpu_frames: false,
+            ..config.clone()
+        })?;
+        capture
+            .start()
+            .with_context(|| format!("Failed to start source '{}'", source.id))?;
+        captures.push(capture);
+    }
+    info!("Composite capture started with {} sources", sources.len());
+
+    let result = compose_frames(&config, &sources, &captures, &tx, &shared);
+    for capture in &mut captures {
+        let _ = capture.stop();
+    }
+    *shared.layout.write() = None;
+    result
+}
+
+/// Compose the latest frames of the sources whenever one of them updates
+fn compose_frames(
+    config: &CaptureConfig,
+    sources: &[CompositeSource],
+    captures: &[ScreenCapture],
+    tx: &BroadcastSender<CapturedFrame>,
+    shared: &CompositeShared,
+) -> Result<()> {
+    let mut latest: Vec<Option<Arc<CapturedFrame>>> = vec![None; sources.len()];
+
+    while shared.running.load(Ordering::SeqCst) {
+        let fps_limit = shared.fps_limit.load(Ordering::Relaxed);
+        // Sources snap at the snapshot interval; frames are composed as they come
+        let snapshot_ms = shared.snapshot_interval_ms.load(Ordering::Relaxed);
+        let low_priority = shared.low_priority.load(Ordering::Relaxed);
+        let mut updated = false;
+
+        for (i, (source, capture)) in sources.iter().zip(captures).enumerate() {
+            capture.set_fps_limit((fps_limit > 0).then_some(fps_limit));
+            capture.set_snapshot_interval(
+                (snapshot_ms > 0).then(|| Duration::from_millis(snapshot_ms)),
+            );
+            capture.set_low_priority(low_priority);
+
+            if let Some(frame) = capture.try_next_frame() {
+                latest[i] = Some(frame);
+                updated = true;
+            }
+
+            if !capture.is_running() {
+                if capture.access_denied() {
+                    return Err(elevation::access_denied_error()).context(format!(
+                        "Source '{}' belongs to a program running as administrator",
+                        source.id
+                    ));
+                }
+                if capture.source_closed() {
+                    info!("Composite source '{}' closed", source.id);
+                    shared.source_closed.store(true, Ordering::SeqCst);
+                    return Ok(());
+                }
+                anyhow::bail!("Source '{}' stopped capturing", source.id);
+            }
+        }
+
+        if updated {
+            let parts: Option<Vec<_>> = sources
+                .iter()
+                .zip(&latest)
+                .map(|(source, frame)| frame.as_deref().map(|frame| (source, frame)))
+                .collect();
+            // Wait until every source delivered a frame
+            if let Some(parts) = parts {
+                let (frame, layout) = compose(&parts);
+                if shared.layout.read().as_ref() != Some(&layout) {
+                    *shared.layout.write() = Some(layout);
+                }
+                tx.send(frame);
+            }
+        }
+
+        let fps = match fps_limit {
+            0 => config.max_fps,
+            limit => limit.min(config.max_fps),
+        };
+        std::thread::sleep(Duration::from_millis(1000 / fps.max(1) as u64));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(id: &str, region: Option<(f32, f32, f32, f32)>) -> CompositeSource {
+        CompositeSource {
+            id: id.to_string(),
+            target: CompositeTarget::Monitor(0),
+            region,
+        }
+    }
+
+    fn solid_frame(width: u32, height: u32, value: u8) -> CapturedFrame {
+        CapturedFrame::new(vec![value; (width * height * 4) as usize], width, height)
+    }
+
+    #[test]
+    fn test_compose_side_by_side() {
+        let game = solid_frame(4, 2, 10);
+        let map = solid_frame(4, 4, 20);
+        let game_source = source("game", None);
+        let map_source = source("map", Some((0.5, 0.0, 0.5, 1.0)));
+        let (frame, layout) = compose(&[(&game_source, &game), (&map_source, &map)]);
+
+        assert_eq!(frame.dimensions(), (6, 4));
+        // Game on the left, the right half of the map next to it
+        assert_eq!(frame.data[0], 10);
+        assert_eq!(frame.data[4 * 4], 20);
+        // Below the shorter game window stays black
+        assert_eq!(&frame.data[3 * 6 * 4..3 * 6 * 4 + 4], &[0, 0, 0, 255]);
+
+        assert_eq!(
+            layout.source_bounds("game"),
+            Some((0.0, 0.0, 4.0 / 6.0, 0.5))
+        );
+        assert_eq!(
+            layout.source_bounds("map"),
+            Some((4.0 / 6.0, 0.0, 2.0 / 6.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_zone_frame_bounds() {
+        let layout = CompositeLayout {
+            sources: vec![
+                SourceRect {
+                    id: "game".to_string(),
+                    bounds: (0.0, 0.0, 0.5, 1.0),
+                },
+                SourceRect {
+                    id: "map".to_string(),
+                    bounds: (0.5, 0.0, 0.5, 0.5),
+                },
+            ],
+        };
+        let bounds = (0.5, 0.5, 0.5, 0.5);
+
+        assert_eq!(frame_bounds(Some(&layout), None, bounds), Some(bounds));
+        assert_eq!(
+            frame_bounds(Some(&layout), Some("map"), bounds),
+            Some((0.75, 0.25, 0.25, 0.25))
+        );
+        assert_eq!(frame_bounds(Some(&layout), Some("radio"), bounds), None);
+        assert_eq!(frame_bounds(None, Some("map"), bounds), None);
+
+        assert_eq!(
+            source_bounds(Some(&layout), Some("map"), (0.75, 0.25, 0.25, 0.25)),
+            Some(bounds)
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Mouse cursor position in captured frames
+//!
+//! The cursor isn't drawn into frames for OCR, but rules can still know where
+//! the player is pointing. The screen position is mapped into the captured
+//! window or monitor and scaled to the frame's size.
+
+use std::ffi::c_void;
+use windows::Win32::Foundation::{POINT, RECT};
+use windows::Win32::Graphics::Gdi::{GetMonitorInfoW, HMONITOR, MONITORINFO};
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+/// What a capture covers on screen, as raw handles so it can cross threads
+#[derive(Debug, Clone, Copy)]
+pub enum CursorArea {
+    /// A window (`HWND`)
+    Window(usize),
+    /// A monitor (`HMONITOR`)
+    Monitor(usize),
+}
+
+impl CursorArea {
+    /// Screen rectangle of the captured area right now
+    fn rect(self) -> Option<RECT> {
+        match self {
+            CursorArea::Window(hwnd) => super::client_area::visible_bounds(hwnd),
+            CursorArea::Monitor(hmonitor) => {
+                let mut info = MONITORINFO {
+                    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                    ..Default::default()
+                };
+                // SAFETY: GetMonitorInfoW writes into a local
+                let found =
+                    unsafe { GetMonitorInfoW(HMONITOR(hmonitor as *mut c_void), &mut info) };
+                found.as_bool().then_some(info.rcMonitor)
+            }
+        }
+    }
+
+    /// Where the cursor is in a `width` x `height` frame of this area
+    pub fn cursor_in_frame(self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let mut point = POINT::default();
+        // SAFETY: GetCursorPos writes into a local
+        unsafe { GetCursorPos(&mut point).ok()? };
+        let rect = self.rect()?;
+        map_to_frame(
+            (point.x, point.y),
+            (rect.left, rect.top, rect.right, rect.bottom),
+            width,
+            height,
+        )
+    }
+}
+
+/// A screen point in a frame showing the `(left, top, right, bottom)` screen
+/// rectangle, or None if it's outside
+fn map_to_frame(
+    point: (i32, i32),
+    rect: (i32, i32, i32, i32),
+    width: u32,
+    height: u32,
+) -> Option<(u32, u32)> {
+    let (left, top, right, bottom) = rect;
+    let (x, y) = point;
+    if x < left || y < top || x >= right || y >= bottom {
+        return None;
+    }
+    // Window sizes can differ from frame sizes (DPI scaling, capture scale)
+    let scale = |offset: i32, span: i32, size: u32| {
+        (i64::from(offset) * i64::from(size) / i64::from(span)) as u32
+    };
+    Some((
+        scale(x - left, right - left, width),
+        scale(y - top, bottom - top, height),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_to_frame() {
+        let rect = (100, 50, 900, 650);
+        assert_eq!(map_to_frame((100, 50), rect, 800, 600), Some((0, 0)));
+        assert_eq!(map_to_frame((500, 350), rect, 400, 300), Some((200, 150)));
+        assert_eq!(map_to_frame((899, 649), rect, 800, 600), Some((799, 599)));
+        assert_eq!(map_to_frame((900, 300), rect, 800, 600), None);
+        assert_eq!(map_to_frame((-5, 300), rect, 800, 600), None);
+    }
+}
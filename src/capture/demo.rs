@@ -0,0 +1,332 @@
+//! Demo game
+//!
+//! A built-in stand-in for a game: an animated HUD with health, gold and a
+//! match timer that keep changing, a red edge when health runs low and a
+//! shop screen that comes up every minute. Captured like any other target,
+//! so zones, screens, color triggers and the overlay can be tried out and
+//! worked on without owning a game.
+
+use ab_glyph::{FontRef, PxScale};
+use anyhow::{anyhow, Result};
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use super::{CapturedFrame, FrameRate};
+use crate::shared::channels::BroadcastSender;
+
+/// Size of the demo frames
+pub const DEMO_SIZE: (u32, u32) = (1280, 720);
+
+/// Seconds from respawn to the next one; health drains over a life
+const LIFE_SECS: u64 = 40;
+/// Seconds between shop visits; gold is spent at each
+const SHOP_CYCLE_SECS: u64 = 60;
+/// Seconds the shop stays open, at the end of each cycle
+const SHOP_OPEN_SECS: u64 = 8;
+/// Health below which the screen edge turns red
+pub const LOW_HEALTH: u32 = 25;
+
+const BACKGROUND: Rgba<u8> = Rgba([24, 38, 52, 255]);
+const PANEL: Rgba<u8> = Rgba([12, 16, 24, 255]);
+const TEXT: Rgba<u8> = Rgba([236, 236, 236, 255]);
+const GOLD: Rgba<u8> = Rgba([250, 200, 60, 255]);
+const HEALTH: Rgba<u8> = Rgba([70, 200, 90, 255]);
+const LOW: Rgba<u8> = Rgba([220, 40, 40, 255]);
+
+/// What the HUD shows at a moment of the match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoState {
+    /// Match time in seconds
+    pub seconds: u64,
+    /// Health out of 100
+    pub health: u32,
+    /// Gold carried
+    pub gold: u32,
+    /// Whether the shop screen is up
+    pub shop_open: bool,
+}
+
+impl DemoState {
+    /// The HUD after `elapsed` of play
+    ///
+    /// Health drains from 100 over each life, gold grows until it's spent
+    /// when the shop closes. The same time always gives the same state.
+    pub fn at(elapsed: Duration) -> Self {
+        let seconds = elapsed.as_secs();
+        let life = seconds % LIFE_SECS;
+        let shop = seconds % SHOP_CYCLE_SECS;
+        Self {
+            seconds,
+            health: (100 - life * 9 / 4) as u32,
+            gold: (250 + shop * 15) as u32,
+            shop_open: shop >= SHOP_CYCLE_SECS - SHOP_OPEN_SECS,
+        }
+    }
+
+    /// Whether the red low-health edge is shown
+    pub fn low_health(&self) -> bool {
+        self.health < LOW_HEALTH
+    }
+
+    /// Match timer text, e.g. `03:07`
+    pub fn timer(&self) -> String {
+        format!("{:02}:{:02}", self.seconds / 60, self.seconds % 60)
+    }
+}
+
+/// Draws demo frames
+pub struct DemoGame {
+    font: FontRef<'static>,
+    started: Instant,
+}
+
+impl DemoGame {
+    /// Start a new match
+    pub fn new() -> Result<Self> {
+        let font = FontRef::try_from_slice(epaint_default_fonts::HACK_REGULAR)
+            .map_err(|e| anyhow!("Failed to load the demo font: {}", e))?;
+        Ok(Self {
+            font,
+            started: Instant::now(),
+        })
+    }
+
+    /// The frame for the current moment of the match
+    pub fn next_frame(&self) -> CapturedFrame {
+        let elapsed = self.started.elapsed();
+        let image = self.render(&DemoState::at(elapsed), elapsed.as_secs_f32());
+        let (width, height) = image.dimensions();
+        CapturedFrame::new(image.into_raw(), width, height)
+    }
+
+    /// Draw the HUD over a moving scene
+    ///
+    /// `time` (seconds) only moves the scene; what the HUD reads is `state`.
+    pub fn render(&self, state: &DemoState, time: f32) -> RgbaImage {
+        let (width, height) = DEMO_SIZE;
+        let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+        self.draw_scene(&mut image, time);
+        if state.low_health() {
+            draw_edge(&mut image, LOW, 16);
+        }
+        self.draw_health(&mut image, state.health);
+        self.draw_centered(&mut image, &state.timer(), width as i32 / 2, 28, 40.0, TEXT);
+        self.draw_gold(&mut image, state.gold);
+        if state.shop_open {
+            self.draw_shop(&mut image, state.gold);
+        }
+        draw_text_mut(
+            &mut image,
+            Rgba([140, 150, 160, 255]),
+            width as i32 - 96,
+            height as i32 - 36,
+            PxScale::from(20.0),
+            &self.font,
+            "DEMO",
+        );
+        image
+    }
+
+    /// Ground, and a few enemies drifting across it
+    fn draw_scene(&self, image: &mut RgbaImage, time: f32) {
+        let (width, height) = DEMO_SIZE;
+        draw_filled_rect_mut(
+            image,
+            Rect::at(0, height as i32 * 2 / 3).of_size(width, height / 3),
+            Rgba([34, 56, 40, 255]),
+        );
+        for i in 0..4 {
+            let speed = 60.0 + 25.0 * i as f32;
+            let x = (time * speed + 320.0 * i as f32) % (width as f32 + 80.0) - 40.0;
+            let y = 300.0 + 70.0 * i as f32 + (time * 1.5 + i as f32).sin() * 30.0;
+            draw_filled_circle_mut(image, (x as i32, y as i32), 26, Rgba([150, 60, 60, 255]));
+        }
+    }
+
+    /// Health bar with its value, top left
+    fn draw_health(&self, image: &mut RgbaImage, health: u32) {
+        draw_filled_rect_mut(image, Rect::at(24, 24).of_size(380, 72), PANEL);
+        draw_text_mut(
+            image,
+            TEXT,
+            40,
+            30,
+            PxScale::from(28.0),
+            &self.font,
+            &format!("HP {} / 100", health),
+        );
+        draw_filled_rect_mut(
+            image,
+            Rect::at(40, 68).of_size(348, 16),
+            Rgba([50, 50, 50, 255]),
+        );
+        let color = if health < LOW_HEALTH { LOW } else { HEALTH };
+        if health > 0 {
+            draw_filled_rect_mut(
+                image,
+                Rect::at(40, 68).of_size(348 * health / 100, 16),
+                color,
+            );
+        }
+    }
+
+    /// Gold carried, top right
+    fn draw_gold(&self, image: &mut RgbaImage, gold: u32) {
+        let (width, _) = DEMO_SIZE;
+        draw_filled_rect_mut(
+            image,
+            Rect::at(width as i32 - 284, 24).of_size(260, 56),
+            PANEL,
+        );
+        draw_filled_circle_mut(image, (width as i32 - 256, 52), 12, GOLD);
+        draw_text_mut(
+            image,
+            GOLD,
+            width as i32 - 232,
+            34,
+            PxScale::from(32.0),
+            &self.font,
+            &format!("Gold {}", gold),
+        );
+    }
+
+    /// Shop screen in the middle, with prices to compare the gold against
+    fn draw_shop(&self, image: &mut RgbaImage, gold: u32) {
+        let (width, height) = DEMO_SIZE;
+        let (panel_width, panel_height) = (560, 360);
+        let left = (width - panel_width) as i32 / 2;
+        let top = (height - panel_height) as i32 / 2;
+        draw_filled_rect_mut(
+            image,
+            Rect::at(left, top).of_size(panel_width, panel_height),
+            PANEL,
+        );
+        self.draw_centered(image, "SHOP", width as i32 / 2, top + 24, 44.0, GOLD);
+
+        let items = [
+            ("Boots", 300),
+            ("Potion", 50),
+            ("Sword", 450),
+            ("Shield", 1200),
+        ];
+        for (i, (item, price)) in items.into_iter().enumerate() {
+            let y = top + 100 + 56 * i as i32;
+            let color = if price <= gold {
+                TEXT
+            } else {
+                Rgba([120, 120, 120, 255])
+            };
+            let scale = PxScale::from(30.0);
+            draw_text_mut(image, color, left + 48, y, scale, &self.font, item);
+            draw_text_mut(
+                image,
+                color,
+                left + panel_width as i32 - 160,
+                y,
+                scale,
+                &self.font,
+                &price.to_string(),
+            );
+        }
+    }
+
+    /// Text centered on `center_x`
+    fn draw_centered(
+        &self,
+        image: &mut RgbaImage,
+        text: &str,
+        center_x: i32,
+        y: i32,
+        size: f32,
+        color: Rgba<u8>,
+    ) {
+        let scale = PxScale::from(size);
+        let (text_width, _) = text_size(scale, &self.font, text);
+        draw_text_mut(
+            image,
+            color,
+            center_x - text_width as i32 / 2,
+            y,
+            scale,
+            &self.font,
+            text,
+        );
+    }
+}
+
+/// Colored border around the whole frame
+fn draw_edge(image: &mut RgbaImage, color: Rgba<u8>, thickness: u32) {
+    let (width, height) = image.dimensions();
+    for rect in [
+        Rect::at(0, 0).of_size(width, thickness),
+        Rect::at(0, (height - thickness) as i32).of_size(width, thickness),
+        Rect::at(0, 0).of_size(thickness, height),
+        Rect::at((width - thickness) as i32, 0).of_size(thickness, height),
+    ] {
+        draw_filled_rect_mut(image, rect, color);
+    }
+}
+
+/// Send demo frames at the capture's frame rate until it stops
+pub(super) fn run_demo(
+    max_fps: u32,
+    tx: BroadcastSender<CapturedFrame>,
+    running: Arc<AtomicBool>,
+    frame_rate: FrameRate,
+) -> Result<()> {
+    let game = DemoGame::new()?;
+    info!("Demo game started");
+
+    while running.load(Ordering::SeqCst) {
+        let interval = frame_rate.interval(max_fps);
+        let started = Instant::now();
+
+        tx.send(game.next_frame());
+
+        if let Some(rest) = interval.checked_sub(started.elapsed()) {
+            std::thread::sleep(rest);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_state() {
+        let start = DemoState::at(Duration::ZERO);
+        assert_eq!(start.health, 100);
+        assert_eq!(start.gold, 250);
+        assert!(!start.shop_open && !start.low_health());
+
+        let late = DemoState::at(Duration::from_secs(LIFE_SECS - 1));
+        assert!(late.low_health(), "health {}", late.health);
+        assert_eq!(late.timer(), "00:39");
+
+        // Shop opens at the end of the cycle, then the gold is spent
+        assert!(DemoState::at(Duration::from_secs(55)).shop_open);
+        let after_shop = DemoState::at(Duration::from_secs(60));
+        assert!(!after_shop.shop_open);
+        assert_eq!(after_shop.gold, 250);
+        assert_eq!(after_shop.timer(), "01:00");
+    }
+
+    #[test]
+    fn test_render_low_health_edge() {
+        let game = DemoGame::new().unwrap();
+        let healthy = game.render(&DemoState::at(Duration::ZERO), 0.0);
+        let low = game.render(&DemoState::at(Duration::from_secs(LIFE_SECS - 1)), 0.0);
+
+        assert_eq!(healthy.dimensions(), DEMO_SIZE);
+        assert_eq!(*healthy.get_pixel(4, 400), BACKGROUND);
+        assert_eq!(*low.get_pixel(4, 400), LOW);
+    }
+}
@@ -0,0 +1,205 @@
+//! Frame differencing
+//!
+//! Most HUD values only change every few seconds, so reading a zone on every
+//! frame mostly reads the same pixels again. [`FrameDiff`] remembers a coarse
+//! brightness grid of each region the last time it was read and tells whether
+//! the region has changed since, so unchanged zones can skip OCR.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::{CapturedFrame, PixelFormat};
+
+/// Size of a grid cell in pixels
+const CELL_SIZE: u32 = 8;
+
+/// Change of a cell's average brightness (0-255) that counts as a change;
+/// smaller ones are capture and compression noise
+const CELL_THRESHOLD: u8 = 3;
+
+/// Regions are reported changed at least this often, so a reading can't
+/// get stuck on a change that stayed under the threshold
+pub const DEFAULT_REFRESH: Duration = Duration::from_secs(5);
+
+/// Coarse brightness grid of a region
+#[derive(Debug, Clone, PartialEq)]
+struct Signature {
+    width: u32,
+    height: u32,
+    cells: Vec<u8>,
+}
+
+impl Signature {
+    /// Grid of a `w` x `h` region at (`x`, `y`) of a frame
+    fn of(frame: &CapturedFrame, x: u32, y: u32, w: u32, h: u32) -> Self {
+        let x_end = (x + w).min(frame.width);
+        let y_end = (y + h).min(frame.height);
+        let cols = w.div_ceil(CELL_SIZE);
+        let rows = h.div_ceil(CELL_SIZE);
+        let mut sums = vec![(0u32, 0u32); (cols * rows) as usize];
+
+        // Every other pixel in each direction is plenty for a brightness average
+        for py in (y..y_end).step_by(2) {
+            let row = (py - y) / CELL_SIZE;
+            for px in (x..x_end).step_by(2) {
+                let i = (py * frame.width + px) as usize;
+                let luma = match frame.format {
+                    PixelFormat::Rgba8 => {
+                        let pixel = &frame.data[i * 4..i * 4 + 3];
+                        (pixel[0] as u32 * 77 + pixel[1] as u32 * 150 + pixel[2] as u32 * 29) >> 8
+                    }
+                    PixelFormat::Gray8 => frame.data[i] as u32,
+                };
+                let cell = &mut sums[(row * cols + (px - x) / CELL_SIZE) as usize];
+                cell.0 += luma;
+                cell.1 += 1;
+            }
+        }
+
+        Self {
+            width: w,
+            height: h,
+            cells: sums
+                .into_iter()
+                .map(|(sum, count)| (sum / count.max(1)) as u8)
+                .collect(),
+        }
+    }
+
+    /// Whether any cell differs from the other grid by more than the threshold
+    fn differs(&self, other: &Signature) -> bool {
+        self.width != other.width
+            || self.height != other.height
+            || self
+                .cells
+                .iter()
+                .zip(&other.cells)
+                .any(|(a, b)| a.abs_diff(*b) > CELL_THRESHOLD)
+    }
+}
+
+/// Remembers regions as they were last read, to tell which have changed
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    /// Grid of each region when it last counted as changed, by key (zone ID)
+    regions: HashMap<String, (Signature, Instant)>,
+    refresh: Duration,
+}
+
+impl Default for FrameDiff {
+    fn default() -> Self {
+        Self::new(DEFAULT_REFRESH)
+    }
+}
+
+impl FrameDiff {
+    /// Differencing that reports each region changed at least every `refresh`
+    pub fn new(refresh: Duration) -> Self {
+        Self {
+            regions: HashMap::new(),
+            refresh,
+        }
+    }
+
+    /// Whether a region of a frame changed since it last counted as changed
+    ///
+    /// A region seen for the first time, resized, or last changed `refresh`
+    /// ago counts as changed. A changed region is remembered as it is now.
+    pub fn changed(
+        &mut self,
+        key: &str,
+        frame: &CapturedFrame,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> bool {
+        self.changed_at(key, frame, x, y, w, h, Instant::now())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn changed_at(
+        &mut self,
+        key: &str,
+        frame: &CapturedFrame,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+        now: Instant,
+    ) -> bool {
+        if w == 0 || h == 0 || x >= frame.width || y >= frame.height {
+            return true;
+        }
+
+        let signature = Signature::of(frame, x, y, w, h);
+        let changed = self.regions.get(key).map_or(true, |(previous, at)| {
+            now.duration_since(*at) >= self.refresh || signature.differs(previous)
+        });
+        if changed {
+            self.regions.insert(key.to_string(), (signature, now));
+        }
+        changed
+    }
+
+    /// Forget every region, so each counts as changed next time
+    pub fn reset(&mut self) {
+        self.regions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A gray frame with a white square at (`x`, `y`)
+    fn frame_with_square(x: u32, y: u32) -> CapturedFrame {
+        let (width, height) = (64, 32);
+        let mut data = vec![60u8; (width * height * 4) as usize];
+        for py in y..y + 6 {
+            for px in x..x + 6 {
+                let i = ((py * width + px) * 4) as usize;
+                data[i..i + 3].copy_from_slice(&[255, 255, 255]);
+            }
+        }
+        CapturedFrame::new(data, width, height)
+    }
+
+    #[test]
+    fn test_changed_regions() {
+        let mut diff = FrameDiff::default();
+        let start = Instant::now();
+        let first = frame_with_square(4, 4);
+        let moved = frame_with_square(40, 4);
+
+        // First look, then the same pixels
+        assert!(diff.changed_at("left", &first, 0, 0, 32, 32, start));
+        assert!(diff.changed_at("right", &first, 32, 0, 32, 32, start));
+        assert!(!diff.changed_at("left", &first, 0, 0, 32, 32, start));
+
+        // The square moved from the left region into the right one
+        assert!(diff.changed_at("left", &moved, 0, 0, 32, 32, start));
+        assert!(diff.changed_at("right", &moved, 32, 0, 32, 32, start));
+        assert!(!diff.changed_at("right", &moved, 32, 0, 32, 32, start));
+
+        // A resized region, and any region after the refresh interval
+        assert!(diff.changed_at("right", &moved, 32, 0, 24, 32, start));
+        assert!(diff.changed_at("left", &moved, 0, 0, 32, 32, start + DEFAULT_REFRESH));
+
+        diff.reset();
+        assert!(diff.changed_at("left", &moved, 0, 0, 32, 32, start));
+    }
+
+    #[test]
+    fn test_noise_is_unchanged() {
+        let mut diff = FrameDiff::default();
+        let frame = frame_with_square(4, 4);
+        let mut noisy = frame.clone();
+        for value in noisy.data.iter_mut().step_by(7) {
+            *value = value.saturating_add(2);
+        }
+
+        assert!(diff.changed("zone", &frame, 0, 0, 32, 32));
+        assert!(!diff.changed("zone", &noisy, 0, 0, 32, 32));
+    }
+}
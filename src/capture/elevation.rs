@@ -0,0 +1,166 @@
+//! Elevated window detection
+//!
+//! Windows Graphics Capture can't capture the windows of processes running
+//! as administrator unless GamersToolKit runs elevated too. Some games and
+//! launchers do, and the capture then fails or never delivers a frame, so
+//! such windows are checked before capturing and reported as access denied.
+
+use anyhow::{Context, Result};
+use windows::core::{w, HSTRING};
+use windows::Win32::Foundation::{CloseHandle, E_ACCESSDENIED, HANDLE, HWND};
+use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows::Win32::UI::Shell::ShellExecuteW;
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+/// How `E_ACCESSDENIED` shows up in error messages
+const ACCESS_DENIED_CODE: &str = "0x80070005";
+
+/// Whether GamersToolKit runs as administrator
+pub fn is_elevated() -> bool {
+    // SAFETY: the pseudo handle of the current process is always valid
+    token_elevated(unsafe { GetCurrentProcess() }).unwrap_or(false)
+}
+
+/// Whether a process runs as administrator
+///
+/// A process whose token can't be read from here counts as elevated, since
+/// that's what an elevated process looks like to a non-elevated one.
+pub fn process_is_elevated(pid: u32) -> bool {
+    // SAFETY: the process handle is closed after use
+    unsafe {
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return true;
+        };
+        let elevated = token_elevated(process);
+        let _ = CloseHandle(process);
+        match elevated {
+            Ok(elevated) => elevated,
+            Err(e) => e.code() == E_ACCESSDENIED,
+        }
+    }
+}
+
+/// Elevation of a process's token
+fn token_elevated(process: HANDLE) -> windows::core::Result<bool> {
+    // SAFETY: the token handle is closed after use, and the elevation struct
+    // outlives the call with its size passed alongside
+    unsafe {
+        let mut token = HANDLE::default();
+        OpenProcessToken(process, TOKEN_QUERY, &mut token)?;
+
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut returned = 0;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut _ as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(token);
+        result.map(|()| elevation.TokenIsElevated != 0)
+    }
+}
+
+/// Whether an error (or one of its causes) is Windows denying access
+pub fn is_access_denied(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<windows::core::Error>()
+            .is_some_and(|e| e.code() == E_ACCESSDENIED)
+            || mentions_access_denied(&cause.to_string())
+    })
+}
+
+/// Whether an error message reports `E_ACCESSDENIED`
+///
+/// windows-capture wraps the errors of its own `windows` version, which
+/// can't be downcast to ours, so the message is checked too.
+fn mentions_access_denied(message: &str) -> bool {
+    message.to_ascii_lowercase().contains(ACCESS_DENIED_CODE)
+}
+
+/// Error for capturing the window of an elevated process
+pub fn access_denied_error() -> windows::core::Error {
+    windows::core::Error::from(E_ACCESSDENIED)
+}
+
+/// Start another instance of GamersToolKit as administrator
+///
+/// Windows asks for consent first; declining it is reported as an error.
+/// The current instance should exit once this succeeds.
+pub fn relaunch_elevated() -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate GamersToolKit")?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let parameters = join_args(&args);
+
+    // SAFETY: all strings outlive the call
+    let instance = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("runas"),
+            &HSTRING::from(exe.as_os_str()),
+            &HSTRING::from(parameters),
+            None,
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // ShellExecute returns a value above 32 on success
+    if instance.0 as isize <= 32 {
+        anyhow::bail!(
+            "Failed to restart as administrator: {}",
+            windows::core::Error::from_win32()
+        );
+    }
+    Ok(())
+}
+
+/// Command line arguments joined into one string, quoted where needed
+fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains([' ', '\t', '"']) {
+                format!("\"{}\"", arg.replace('"', "\\\""))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_denied_messages() {
+        assert!(mentions_access_denied("Access is denied. (0x80070005)"));
+        assert!(!mentions_access_denied(
+            "Window 'Notepad' not found (0x80070490)"
+        ));
+
+        let error = anyhow::Error::new(access_denied_error()).context("Failed to capture");
+        assert!(is_access_denied(&error));
+        assert!(!is_access_denied(&anyhow::anyhow!(
+            "Window 'Game' not found"
+        )));
+    }
+
+    #[test]
+    fn test_join_args() {
+        let args = [
+            "--safe-mode".to_string(),
+            "C:\\Program Files\\profile.toml".to_string(),
+        ];
+        assert_eq!(
+            join_args(&args),
+            "--safe-mode \"C:\\Program Files\\profile.toml\""
+        );
+        assert_eq!(join_args(&[]), "");
+    }
+}
@@ -1,19 +1,65 @@
 #![allow(dead_code)]
 //! Frame data structures for captured screen content
-
+//!
+//! Frames are normally downloaded from the GPU as they arrive. A [`GpuFrame`]
+//! stays a Direct3D 11 texture instead, and only the regions the vision
+//! layer reads (zones) are downloaded, which saves copying every 4K frame.
+//!
+//! HDR games are captured as half-float scRGB and tone-mapped to SDR here
+//! ([`HdrToneMap`]); 8-bit capture of an HDR screen comes out washed out,
+//! which OCR reads badly.
+//!
+//! Grayscale capture converts frames to one byte per pixel on arrival, a
+//! quarter of the memory of RGBA, for runs that only match templates.
+//! Readers that need color expand them with [`CapturedFrame::rgba`].
+
+use anyhow::{Context, Result};
+use d3d11::core::Interface;
+use d3d11::Win32::Graphics::Direct3D11::{
+    ID3D11Device, ID3D11DeviceContext, ID3D11Multithread, ID3D11Texture2D, D3D11_BOX,
+    D3D11_CPU_ACCESS_READ, D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ, D3D11_TEXTURE2D_DESC,
+    D3D11_USAGE_DEFAULT, D3D11_USAGE_STAGING,
+};
+use std::borrow::Cow;
 use std::time::Instant;
 
+/// Layout of the pixels of a [`CapturedFrame`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelFormat {
+    /// Four bytes per pixel: red, green, blue, alpha
+    #[default]
+    Rgba8,
+    /// One byte of luminance per pixel
+    Gray8,
+}
+
+impl PixelFormat {
+    /// Bytes each pixel takes
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgba8 => 4,
+            PixelFormat::Gray8 => 1,
+        }
+    }
+}
+
 /// A captured frame from the screen
 #[derive(Debug, Clone)]
 pub struct CapturedFrame {
-    /// Raw RGBA pixel data
+    /// Raw pixel data, laid out as `format` says
     pub data: Vec<u8>,
+    /// Layout of `data`
+    pub format: PixelFormat,
     /// Frame width in pixels
     pub width: u32,
     /// Frame height in pixels
     pub height: u32,
-    /// Timestamp when frame was captured
+    /// When the frame was captured; tips based on it carry this along to
+    /// measure and bound their latency
     pub timestamp: Instant,
+    /// Mouse cursor position in frame pixels, when it was over the captured
+    /// area (the cursor itself isn't drawn into the pixels)
+    pub cursor: Option<(u32, u32)>,
 }
 
 impl CapturedFrame {
@@ -21,9 +67,11 @@ impl CapturedFrame {
     pub fn new(data: Vec<u8>, width: u32, height: u32) -> Self {
         Self {
             data,
+            format: PixelFormat::Rgba8,
             width,
             height,
             timestamp: Instant::now(),
+            cursor: None,
         }
     }
 
@@ -36,9 +84,64 @@ impl CapturedFrame {
         }
         Self {
             data,
+            format: PixelFormat::Rgba8,
+            width,
+            height,
+            timestamp: Instant::now(),
+            cursor: None,
+        }
+    }
+
+    /// Create a new captured frame from HDR rows (`Rgba16F` scRGB, `row_pitch`
+    /// bytes apart), tone-mapped to SDR RGBA
+    pub fn from_hdr(
+        rows: &[u8],
+        row_pitch: usize,
+        width: u32,
+        height: u32,
+        tone_map: &HdrToneMap,
+    ) -> Self {
+        let row_bytes = width as usize * 8;
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for row in rows.chunks(row_pitch).take(height as usize) {
+            for pixel in row[..row_bytes].chunks_exact(8) {
+                let channel = |i: usize| u16::from_le_bytes([pixel[i], pixel[i + 1]]);
+                data.extend_from_slice(&[
+                    tone_map.map(channel(0)),
+                    tone_map.map(channel(2)),
+                    tone_map.map(channel(4)),
+                    255,
+                ]);
+            }
+        }
+        Self {
+            data,
+            format: PixelFormat::Rgba8,
+            width,
+            height,
+            timestamp: Instant::now(),
+            cursor: None,
+        }
+    }
+
+    /// Create a new grayscale frame from BGRA rows `row_pitch` bytes apart
+    pub fn gray_from_bgra(rows: &[u8], row_pitch: usize, width: u32, height: u32) -> Self {
+        let row_bytes = width as usize * 4;
+        let mut data = Vec::with_capacity(width as usize * height as usize);
+        for row in rows.chunks(row_pitch).take(height as usize) {
+            data.extend(
+                row[..row_bytes]
+                    .chunks_exact(4)
+                    .map(|bgra| luminance(bgra[2], bgra[1], bgra[0])),
+            );
+        }
+        Self {
+            data,
+            format: PixelFormat::Gray8,
             width,
             height,
             timestamp: Instant::now(),
+            cursor: None,
         }
     }
 
@@ -54,7 +157,7 @@ impl CapturedFrame {
 
     /// Get bytes per row (stride)
     pub fn stride(&self) -> u32 {
-        self.width * 4
+        self.width * self.format.bytes_per_pixel() as u32
     }
 
     /// Extract a region of interest from the frame
@@ -64,40 +167,353 @@ impl CapturedFrame {
             return None;
         }
 
-        let mut region_data = Vec::with_capacity((w * h * 4) as usize);
+        let bytes_per_pixel = self.format.bytes_per_pixel();
+        let mut region_data = Vec::with_capacity(w as usize * h as usize * bytes_per_pixel);
         let stride = self.stride() as usize;
 
         for row in 0..h {
-            let src_start = ((y + row) as usize * stride) + (x as usize * 4);
-            let src_end = src_start + (w as usize * 4);
+            let src_start = ((y + row) as usize * stride) + (x as usize * bytes_per_pixel);
+            let src_end = src_start + (w as usize * bytes_per_pixel);
             region_data.extend_from_slice(&self.data[src_start..src_end]);
         }
 
         Some(CapturedFrame {
             data: region_data,
+            format: self.format,
             width: w,
             height: h,
             timestamp: self.timestamp,
+            cursor: cursor_in_region(self.cursor, x, y, w, h),
         })
     }
 
+    /// The frame shrunk by `scale` (0.5 = half the width and height), each
+    /// pixel the average of those it covers; unchanged when `scale` is 1 or more
+    pub fn scaled(self, scale: f32) -> CapturedFrame {
+        if scale >= 1.0 || self.width == 0 || self.height == 0 {
+            return self;
+        }
+        let width = ((self.width as f32 * scale).round() as u32).max(1);
+        let height = ((self.height as f32 * scale).round() as u32).max(1);
+        // Source pixels covered by destination pixel `i` of `size` along a side
+        let span = |i: u32, size: u32, source: u32| {
+            let start = (u64::from(i) * u64::from(source) / u64::from(size)) as usize;
+            let end = (u64::from(i + 1) * u64::from(source) / u64::from(size)) as usize;
+            start..end.max(start + 1)
+        };
+
+        let stride = self.stride() as usize;
+        let bytes_per_pixel = self.format.bytes_per_pixel();
+        let mut data = Vec::with_capacity(width as usize * height as usize * bytes_per_pixel);
+        for y in 0..height {
+            let rows = span(y, height, self.height);
+            for x in 0..width {
+                let columns = span(x, width, self.width);
+                let mut sum = [0u32; 4];
+                for row in rows.clone() {
+                    let start = row * stride + columns.start * bytes_per_pixel;
+                    let end = row * stride + columns.end * bytes_per_pixel;
+                    for pixel in self.data[start..end].chunks_exact(bytes_per_pixel) {
+                        for (total, &value) in sum.iter_mut().zip(pixel) {
+                            *total += u32::from(value);
+                        }
+                    }
+                }
+                let count = (rows.len() * columns.len()) as u32;
+                data.extend(
+                    sum[..bytes_per_pixel]
+                        .iter()
+                        .map(|total| ((total + count / 2) / count) as u8),
+                );
+            }
+        }
+
+        CapturedFrame {
+            data,
+            format: self.format,
+            width,
+            height,
+            timestamp: self.timestamp,
+            cursor: self.cursor.map(|(x, y)| {
+                (
+                    (u64::from(x) * u64::from(width) / u64::from(self.width)) as u32,
+                    (u64::from(y) * u64::from(height) / u64::from(self.height)) as u32,
+                )
+            }),
+        }
+    }
+
+    /// RGBA pixels of the frame; a grayscale frame is expanded
+    pub fn rgba(&self) -> Cow<'_, [u8]> {
+        match self.format {
+            PixelFormat::Rgba8 => Cow::Borrowed(&self.data),
+            PixelFormat::Gray8 => Cow::Owned(
+                self.data
+                    .iter()
+                    .flat_map(|&value| [value, value, value, 255])
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The frame with RGBA pixels; a grayscale frame is expanded
+    pub fn to_rgba(&self) -> Cow<'_, CapturedFrame> {
+        match self.format {
+            PixelFormat::Rgba8 => Cow::Borrowed(self),
+            PixelFormat::Gray8 => Cow::Owned(CapturedFrame {
+                data: self.rgba().into_owned(),
+                format: PixelFormat::Rgba8,
+                width: self.width,
+                height: self.height,
+                timestamp: self.timestamp,
+                cursor: self.cursor,
+            }),
+        }
+    }
+
     /// Convert to an image::RgbaImage for further processing
     pub fn to_rgba_image(&self) -> Option<image::RgbaImage> {
-        image::RgbaImage::from_raw(self.width, self.height, self.data.clone())
+        image::RgbaImage::from_raw(self.width, self.height, self.rgba().into_owned())
     }
 
     /// Get a grayscale version of the frame for OCR
     pub fn to_grayscale(&self) -> Vec<u8> {
-        self.data
-            .chunks_exact(4)
-            .map(|rgba| {
-                // Luminance formula: 0.299*R + 0.587*G + 0.114*B
-                let r = rgba[0] as f32;
-                let g = rgba[1] as f32;
-                let b = rgba[2] as f32;
-                (0.299 * r + 0.587 * g + 0.114 * b) as u8
+        match self.format {
+            PixelFormat::Rgba8 => self
+                .data
+                .chunks_exact(4)
+                .map(|rgba| luminance(rgba[0], rgba[1], rgba[2]))
+                .collect(),
+            PixelFormat::Gray8 => self.data.clone(),
+        }
+    }
+
+    /// Convert to an image::GrayImage for template matching
+    pub fn to_gray_image(&self) -> Option<image::GrayImage> {
+        image::GrayImage::from_raw(self.width, self.height, self.to_grayscale())
+    }
+
+    /// Get the age of this frame
+    pub fn age(&self) -> std::time::Duration {
+        self.timestamp.elapsed()
+    }
+}
+
+/// Luminance formula: 0.299*R + 0.587*G + 0.114*B
+fn luminance(r: u8, g: u8, b: u8) -> u8 {
+    (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) as u8
+}
+
+/// Windows' SDR reference white in scRGB, where 1.0 is 80 nits
+const SCRGB_NITS: f32 = 80.0;
+
+/// Maps HDR (linear scRGB half-float) channel values to 8-bit sRGB
+///
+/// UI text and HUDs are drawn at the display's SDR white level, so that level
+/// maps to white and everything below it looks as it would in SDR; brighter
+/// highlights clip to white. Every half-float value is mapped once up front,
+/// so a frame costs a table lookup per channel.
+#[derive(Clone)]
+pub struct HdrToneMap {
+    /// SDR white level in nits
+    white_nits: f32,
+    /// 8-bit value for each half-float bit pattern
+    table: Box<[u8]>,
+}
+
+impl HdrToneMap {
+    /// Tone map for a display whose SDR content is shown at `white_nits`
+    /// (Windows' "SDR content brightness", 80 to 480 nits)
+    pub fn new(white_nits: f32) -> Self {
+        let white_nits = white_nits.max(1.0);
+        let scale = SCRGB_NITS / white_nits;
+        let table = (0..=u16::MAX)
+            .map(|bits| {
+                let value = half::f16::from_bits(bits).to_f32() * scale;
+                // NaN and negative (out of sRGB gamut) values end up black
+                let value = if value.is_nan() {
+                    0.0
+                } else {
+                    value.clamp(0.0, 1.0)
+                };
+                (srgb_encode(value) * 255.0).round() as u8
             })
-            .collect()
+            .collect();
+        Self { white_nits, table }
+    }
+
+    /// SDR white level the map was made for, in nits
+    pub fn white_nits(&self) -> f32 {
+        self.white_nits
+    }
+
+    /// 8-bit sRGB value of a half-float channel value
+    pub fn map(&self, bits: u16) -> u8 {
+        self.table[bits as usize]
+    }
+}
+
+impl std::fmt::Debug for HdrToneMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HdrToneMap")
+            .field("white_nits", &self.white_nits)
+            .finish_non_exhaustive()
+    }
+}
+
+/// sRGB transfer function of a linear value in `0..=1`
+fn srgb_encode(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// A captured frame kept on the GPU as a BGRA Direct3D 11 texture
+///
+/// Cloning shares the texture.
+#[derive(Debug, Clone)]
+pub struct GpuFrame {
+    texture: ID3D11Texture2D,
+    context: ID3D11DeviceContext,
+    /// Frame width in pixels
+    pub width: u32,
+    /// Frame height in pixels
+    pub height: u32,
+    /// When the frame was captured
+    pub timestamp: Instant,
+    /// Mouse cursor position in frame pixels, if it was over the frame
+    pub cursor: Option<(u32, u32)>,
+}
+
+impl GpuFrame {
+    /// Copy the `width` x `height` pixels at (`x`, `y`) of a capture texture
+    ///
+    /// The copy stays on the GPU. The capture's own texture is reused for the
+    /// next frame, so it can't be kept.
+    pub(super) fn copy_of(
+        texture: &ID3D11Texture2D,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Self> {
+        // SAFETY: plain Direct3D 11 calls on live interfaces; the descriptor
+        // and box outlive the calls that read them
+        unsafe {
+            let device: ID3D11Device = texture.GetDevice()?;
+            let context = device.GetImmediateContext()?;
+            // Regions are downloaded from other threads than the capture's
+            let _ = context
+                .cast::<ID3D11Multithread>()?
+                .SetMultithreadProtected(true);
+
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            texture.GetDesc(&mut desc);
+            let copy_desc = D3D11_TEXTURE2D_DESC {
+                Width: width,
+                Height: height,
+                MipLevels: 1,
+                ArraySize: 1,
+                Usage: D3D11_USAGE_DEFAULT,
+                BindFlags: 0,
+                CPUAccessFlags: 0,
+                MiscFlags: 0,
+                ..desc
+            };
+            let mut copy = None;
+            device.CreateTexture2D(&copy_desc, None, Some(&mut copy))?;
+            let copy = copy.context("Direct3D returned no texture")?;
+
+            let area = D3D11_BOX {
+                left: x,
+                top: y,
+                front: 0,
+                right: x + width,
+                bottom: y + height,
+                back: 1,
+            };
+            context.CopySubresourceRegion(&copy, 0, 0, 0, 0, texture, 0, Some(&area));
+
+            Ok(Self {
+                texture: copy,
+                context,
+                width,
+                height,
+                timestamp: Instant::now(),
+                cursor: None,
+            })
+        }
+    }
+
+    /// Download a region as an RGBA frame
+    pub fn download_region(&self, x: u32, y: u32, w: u32, h: u32) -> Result<CapturedFrame> {
+        if w == 0 || h == 0 || x + w > self.width || y + h > self.height {
+            anyhow::bail!(
+                "Region {}x{} at ({}, {}) is outside the {}x{} frame",
+                w,
+                h,
+                x,
+                y,
+                self.width,
+                self.height
+            );
+        }
+
+        // SAFETY: the staging texture is mapped for reading only while the
+        // mapped rows are copied, and unmapped before it's dropped
+        let data = unsafe {
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            self.texture.GetDesc(&mut desc);
+            let staging_desc = D3D11_TEXTURE2D_DESC {
+                Width: w,
+                Height: h,
+                Usage: D3D11_USAGE_STAGING,
+                CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                ..desc
+            };
+            let device: ID3D11Device = self.texture.GetDevice()?;
+            let mut staging = None;
+            device.CreateTexture2D(&staging_desc, None, Some(&mut staging))?;
+            let staging = staging.context("Direct3D returned no texture")?;
+
+            let area = D3D11_BOX {
+                left: x,
+                top: y,
+                front: 0,
+                right: x + w,
+                bottom: y + h,
+                back: 1,
+            };
+            self.context
+                .CopySubresourceRegion(&staging, 0, 0, 0, 0, &self.texture, 0, Some(&area));
+
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context
+                .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))?;
+            let rows = std::slice::from_raw_parts(
+                mapped.pData as *const u8,
+                (mapped.RowPitch * h) as usize,
+            );
+            let data = bgra_rows_to_rgba(rows, mapped.RowPitch as usize, w, h);
+            self.context.Unmap(&staging, 0);
+            data
+        };
+
+        Ok(CapturedFrame {
+            data,
+            format: PixelFormat::Rgba8,
+            width: w,
+            height: h,
+            timestamp: self.timestamp,
+            cursor: cursor_in_region(self.cursor, x, y, w, h),
+        })
+    }
+
+    /// Download the whole frame
+    pub fn download(&self) -> Result<CapturedFrame> {
+        self.download_region(0, 0, self.width, self.height)
     }
 
     /// Get the age of this frame
@@ -106,6 +522,87 @@ impl CapturedFrame {
     }
 }
 
+/// A cursor position relative to a region, if the region contains it
+pub(super) fn cursor_in_region(
+    cursor: Option<(u32, u32)>,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+) -> Option<(u32, u32)> {
+    let (cx, cy) = cursor?;
+    let inside = cx >= x && cy >= y && cx - x < w && cy - y < h;
+    inside.then(|| (cx - x, cy - y))
+}
+
+/// Tightly packed RGBA pixels from BGRA rows `row_pitch` bytes apart
+fn bgra_rows_to_rgba(rows: &[u8], row_pitch: usize, width: u32, height: u32) -> Vec<u8> {
+    let row_bytes = width as usize * 4;
+    let mut data = Vec::with_capacity(row_bytes * height as usize);
+    for row in rows.chunks(row_pitch).take(height as usize) {
+        data.extend_from_slice(&row[..row_bytes]);
+    }
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    data
+}
+
+/// A frame the vision layer reads regions of: downloaded, or still on the GPU
+#[derive(Debug, Clone, Copy)]
+pub enum FrameSource<'a> {
+    /// Downloaded frame
+    Cpu(&'a CapturedFrame),
+    /// Frame kept on the GPU
+    Gpu(&'a GpuFrame),
+}
+
+impl<'a> FrameSource<'a> {
+    /// Frame dimensions as (width, height)
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self {
+            FrameSource::Cpu(frame) => (frame.width, frame.height),
+            FrameSource::Gpu(frame) => (frame.width, frame.height),
+        }
+    }
+
+    /// When the frame was captured
+    pub fn timestamp(&self) -> Instant {
+        match self {
+            FrameSource::Cpu(frame) => frame.timestamp,
+            FrameSource::Gpu(frame) => frame.timestamp,
+        }
+    }
+
+    /// Get the age of this frame
+    pub fn age(&self) -> std::time::Duration {
+        self.timestamp().elapsed()
+    }
+
+    /// A frame holding a region, and where the region is in it
+    ///
+    /// A downloaded frame is used as is; of a GPU frame, only the region
+    /// (cut to the frame) is downloaded.
+    pub fn region(
+        &self,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> Result<(Cow<'a, CapturedFrame>, u32, u32)> {
+        match *self {
+            FrameSource::Cpu(frame) => Ok((Cow::Borrowed(frame), x, y)),
+            FrameSource::Gpu(frame) => {
+                let x = x.min(frame.width);
+                let y = y.min(frame.height);
+                let w = w.min(frame.width - x);
+                let h = h.min(frame.height - y);
+                Ok((Cow::Owned(frame.download_region(x, y, w, h)?), 0, 0))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +669,82 @@ mod tests {
         assert_eq!(frame.data[10], 255); // B
     }
 
+    #[test]
+    fn test_hdr_tone_map() {
+        let tone_map = HdrToneMap::new(200.0);
+        let f16 = |v: f32| half::f16::from_f32(v).to_bits();
+
+        // SDR white (200 nits = 2.5 in scRGB) is white, black stays black
+        assert_eq!(tone_map.map(f16(0.0)), 0);
+        assert_eq!(tone_map.map(f16(-0.5)), 0);
+        assert_eq!(tone_map.map(f16(f32::NAN)), 0);
+        assert_eq!(tone_map.map(f16(2.5)), 255);
+        // Below white it's as in SDR: 18% gray is about 118
+        let gray = tone_map.map(f16(2.5 * 0.18));
+        assert!((116..=120).contains(&gray), "gray {}", gray);
+        // Highlights clip to white
+        assert_eq!(tone_map.map(f16(10.0)), 255);
+        assert_eq!(tone_map.map(f16(f32::INFINITY)), 255);
+    }
+
+    #[test]
+    fn test_from_hdr() {
+        let tone_map = HdrToneMap::new(80.0);
+        // One 2x1 row padded to 24 bytes: white, then pure red
+        let mut rows = Vec::new();
+        for value in [1.0f32, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0] {
+            rows.extend_from_slice(&half::f16::from_f32(value).to_le_bytes());
+        }
+        rows.resize(24, 0);
+
+        let frame = CapturedFrame::from_hdr(&rows, 24, 2, 1, &tone_map);
+        assert_eq!(frame.dimensions(), (2, 1));
+        assert_eq!(frame.data.len(), 8);
+        assert_eq!(frame.data, [255, 255, 255, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_gray_from_bgra() {
+        // One 2x2 frame in rows padded to 12 bytes: white, red / blue, black
+        let mut rows = Vec::new();
+        rows.extend_from_slice(&[255, 255, 255, 255, 0, 0, 255, 255, 0, 0, 0, 0]);
+        rows.extend_from_slice(&[255, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 0]);
+
+        let frame = CapturedFrame::gray_from_bgra(&rows, 12, 2, 2);
+        assert_eq!(frame.format, PixelFormat::Gray8);
+        assert_eq!(frame.data, [255, 76, 29, 0]);
+        assert_eq!(frame.stride(), 2);
+        assert_eq!(frame.to_grayscale(), frame.data);
+        assert_eq!(&frame.rgba()[4..8], &[76, 76, 76, 255]);
+
+        let region = frame.extract_region(1, 0, 1, 2).unwrap();
+        assert_eq!(region.data, [76, 0]);
+        let scaled = frame.scaled(0.5);
+        assert_eq!(scaled.data, [90]);
+    }
+
+    #[test]
+    fn test_scaled_averages_pixels() {
+        // 4x2: a black and a white 2x2 block side by side
+        let mut data = Vec::new();
+        for _ in 0..2 {
+            for value in [0u8, 0, 255, 255] {
+                data.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        let frame = CapturedFrame::new(data, 4, 2);
+
+        let half = frame.clone().scaled(0.5);
+        assert_eq!(half.dimensions(), (2, 1));
+        assert_eq!(half.data, [0, 0, 0, 255, 255, 255, 255, 255]);
+
+        let quarter = frame.clone().scaled(0.25);
+        assert_eq!(quarter.dimensions(), (1, 1));
+        assert_eq!(quarter.data, [128, 128, 128, 255]);
+
+        assert_eq!(frame.clone().scaled(1.0).data, frame.data);
+    }
+
     #[test]
     fn test_extract_region_valid() {
         // Create a 4x4 frame
@@ -215,6 +788,19 @@ mod tests {
         assert_eq!(region.data, vec![255, 255, 255, 255]);
     }
 
+    #[test]
+    fn test_cursor_follows_region_and_scale() {
+        let mut frame = CapturedFrame::new(vec![0; 8 * 4 * 4], 8, 4);
+        frame.cursor = Some((5, 3));
+
+        assert_eq!(
+            frame.extract_region(4, 2, 4, 2).unwrap().cursor,
+            Some((1, 1))
+        );
+        assert_eq!(frame.extract_region(0, 0, 4, 4).unwrap().cursor, None);
+        assert_eq!(frame.clone().scaled(0.5).cursor, Some((2, 1)));
+    }
+
     #[test]
     fn test_to_grayscale() {
         let frame = create_test_frame();
@@ -246,6 +832,20 @@ mod tests {
         assert_eq!(img.height(), 2);
     }
 
+    #[test]
+    fn test_bgra_rows_to_rgba() {
+        // 2x2 BGRA with 4 bytes of row padding
+        let rows = [
+            0, 0, 255, 255, 0, 255, 0, 255, 9, 9, 9, 9, //
+            255, 0, 0, 255, 1, 2, 3, 255, 9, 9, 9, 9,
+        ];
+        let data = bgra_rows_to_rgba(&rows, 12, 2, 2);
+        assert_eq!(
+            data,
+            [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 3, 2, 1, 255]
+        );
+    }
+
     #[test]
     fn test_age() {
         let frame = create_test_frame();
@@ -3,15 +3,36 @@
 //!
 //! Uses Windows Graphics Capture API for safe, anti-cheat compliant screen capture.
 //! This is a read-only operation that captures pixels without any game interaction.
+//!
+//! Next to its main target, a capture can run extra sources (a map tool on a
+//! second monitor, say). Each has its own frame channel and frame rate and is
+//! read by its ID.
+//!
+//! The replay buffer keeps the last seconds of frames to save on request.
+//!
+//! In snapshot mode, for turn-based and menu-heavy games, a capture sends one
+//! frame every few seconds instead of streaming.
 
+pub mod client_area;
+pub mod clipboard;
+pub mod composite;
+pub mod cursor;
+pub mod demo;
+pub mod diff;
+pub mod elevation;
 pub mod frame;
-pub use frame::CapturedFrame;
+pub mod process_watcher;
+pub mod replay;
+pub use composite::CompositeLayout;
+pub use frame::{CapturedFrame, FrameSource, GpuFrame, HdrToneMap, PixelFormat};
 
 use anyhow::{Context, Result};
-use crossbeam_channel::{bounded, Receiver, Sender};
-use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use tracing::{debug, error, info, warn};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 use windows_capture::{
     capture::{Context as CaptureContext, GraphicsCaptureApiHandler},
     frame::Frame,
@@ -24,6 +45,13 @@ use windows_capture::{
     window::Window,
 };
 
+use crate::config::{
+    CaptureSettings, CompositeSource, ExtraCaptureSource, FrameColorFormat, SecondaryWindowMode,
+};
+use crate::shared::channels::{latest_broadcast, BroadcastReceiver, BroadcastSender};
+use cursor::CursorArea;
+use frame::cursor_in_region;
+
 /// Screen capture configuration
 #[derive(Debug, Clone)]
 pub struct CaptureConfig {
@@ -35,17 +63,103 @@ pub struct CaptureConfig {
     pub capture_cursor: bool,
     /// Whether to draw border around captured window
     pub draw_border: bool,
+    /// Keep frames on the GPU as [`GpuFrame`]s, downloading only the regions
+    /// that are read (not for composite captures, which combine frames on
+    /// the CPU)
+    pub gpu_frames: bool,
+    /// Pixel format frames are captured in. Frames aren't kept on the GPU
+    /// for HDR captures.
+    pub color_format: CaptureColorFormat,
+    /// Factor frames are shrunk by in the capture thread before they're sent
+    /// on (1.0 = native size). Frames kept on the GPU aren't shrunk.
+    pub scale: f32,
+    /// Crop window captures to the client area, without title bar and borders
+    pub client_area_only: bool,
+    /// Whether window captures take in the game's other windows
+    pub secondary_windows: SecondaryWindowMode,
+}
+
+/// Pixel format frames are captured in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureColorFormat {
+    /// 8-bit BGRA, right for SDR games and the cheapest to convert
+    Bgra8,
+    /// Half-float scRGB for games running in HDR, tone-mapped to SDR for a
+    /// display showing SDR content at `white_nits`. Twice the bytes of
+    /// `Bgra8` per pixel.
+    Rgba16F { white_nits: f32 },
+    /// 8-bit BGRA converted to one byte of luminance per pixel as frames
+    /// arrive: a quarter of the memory, for runs that only match templates
+    /// and recognize screens, which work in grayscale anyway
+    Gray8,
+}
+
+impl CaptureColorFormat {
+    /// Format the capture settings ask for
+    pub fn from_settings(settings: &CaptureSettings) -> Self {
+        match settings.color_format {
+            FrameColorFormat::Bgra8 => CaptureColorFormat::Bgra8,
+            FrameColorFormat::Rgba16f => CaptureColorFormat::Rgba16F {
+                white_nits: settings.hdr_white_nits,
+            },
+            FrameColorFormat::Gray8 => CaptureColorFormat::Gray8,
+        }
+    }
+
+    fn windows_format(self) -> ColorFormat {
+        match self {
+            CaptureColorFormat::Bgra8 | CaptureColorFormat::Gray8 => ColorFormat::Bgra8,
+            CaptureColorFormat::Rgba16F { .. } => ColorFormat::Rgba16F,
+        }
+    }
+
+    fn tone_map(self) -> Option<HdrToneMap> {
+        match self {
+            CaptureColorFormat::Bgra8 | CaptureColorFormat::Gray8 => None,
+            CaptureColorFormat::Rgba16F { white_nits } => Some(HdrToneMap::new(white_nits)),
+        }
+    }
 }
 
 /// What to capture
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CaptureTarget {
     /// Capture a specific window by title (partial match)
     Window(String),
+    /// Capture the main window of a process (by process ID)
+    Process(u32),
+    /// Capture the main window of a program by executable name, e.g. `game.exe`
+    Executable(String),
     /// Capture primary monitor
     PrimaryMonitor,
     /// Capture monitor by index
     MonitorIndex(usize),
+    /// Capture several windows or monitor regions side by side in one frame
+    Composite(Vec<CompositeSource>),
+    /// The built-in demo game instead of a real one
+    Demo,
+}
+
+impl CaptureTarget {
+    /// Short description for status text and logs
+    pub fn label(&self) -> String {
+        match self {
+            CaptureTarget::Window(title) => title.clone(),
+            CaptureTarget::Process(pid) => format!("Process {}", pid),
+            CaptureTarget::Executable(executable) => executable.clone(),
+            CaptureTarget::PrimaryMonitor => "Primary Monitor".to_string(),
+            CaptureTarget::MonitorIndex(i) => format!("Monitor {}", i),
+            CaptureTarget::Composite(sources) => format!(
+                "Composite: {}",
+                sources
+                    .iter()
+                    .map(|s| s.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            ),
+            CaptureTarget::Demo => "Demo game".to_string(),
+        }
+    }
 }
 
 impl Default for CaptureConfig {
@@ -55,24 +169,115 @@ impl Default for CaptureConfig {
             max_fps: 30,
             capture_cursor: false,
             draw_border: false,
+            gpu_frames: false,
+            color_format: CaptureColorFormat::Bgra8,
+            scale: 1.0,
+            client_area_only: false,
+            secondary_windows: SecondaryWindowMode::Default,
         }
     }
 }
 
+/// What reads captured frames
+///
+/// Each consumer gets every frame it keeps up with, so a preview reading
+/// frames doesn't take them from the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameConsumer {
+    /// The main loop: game state, rules, replay and the frame rate
+    Pipeline,
+    /// Zone OCR
+    ZoneOcr,
+    /// Screen recognition
+    ScreenRecognition,
+    /// Preview on the Capture page
+    CapturePreview,
+    /// Preview on the Vision page
+    VisionPreview,
+}
+
+impl FrameConsumer {
+    /// Every consumer, in receiver order
+    const ALL: [FrameConsumer; 5] = [
+        FrameConsumer::Pipeline,
+        FrameConsumer::ZoneOcr,
+        FrameConsumer::ScreenRecognition,
+        FrameConsumer::CapturePreview,
+        FrameConsumer::VisionPreview,
+    ];
+}
+
+/// One receiver per consumer, in [`FrameConsumer::ALL`] order
+fn subscribe_all<T>(first: BroadcastReceiver<T>) -> Vec<BroadcastReceiver<T>> {
+    let mut receivers: Vec<_> = FrameConsumer::ALL[1..]
+        .iter()
+        .map(|_| first.subscribe())
+        .collect();
+    receivers.insert(0, first);
+    receivers
+}
+
 /// Screen capture manager using Windows Graphics Capture API
 pub struct ScreenCapture {
     config: CaptureConfig,
     running: Arc<AtomicBool>,
-    frame_receiver: Option<Receiver<CapturedFrame>>,
+    /// Frame receivers by consumer (empty while stopped)
+    frame_receivers: Vec<BroadcastReceiver<CapturedFrame>>,
+    /// Frames kept on the GPU, when capturing with `gpu_frames`
+    gpu_receivers: Vec<BroadcastReceiver<GpuFrame>>,
+    /// Whether the capture thread should run at below-normal priority
+    low_priority: Arc<AtomicBool>,
+    /// Priority the capture thread runs at (`NO_PRIORITY` until it started)
+    thread_priority: Arc<AtomicI32>,
+    /// Frame rate limit below `max_fps` (0 for none)
+    fps_limit: Arc<AtomicU32>,
+    /// Milliseconds between frames in snapshot mode (0 when streaming)
+    snapshot_interval_ms: Arc<AtomicU64>,
+    /// Set when the captured window or monitor went away
+    source_closed: Arc<AtomicBool>,
+    /// Set when Windows refused to capture the target (an elevated window)
+    access_denied: Arc<AtomicBool>,
+    /// Where the sources are in the frames of a composite capture
+    composite_layout: Arc<RwLock<Option<CompositeLayout>>>,
+    /// Extra sources captured next to the main target
+    sources: Vec<SourceCapture>,
 }
 
+/// An extra source and its capture
+struct SourceCapture {
+    id: String,
+    target: CaptureTarget,
+    capture: ScreenCapture,
+}
+
+/// Thread priority value before the capture thread reported one
+const NO_PRIORITY: i32 = i32::MIN;
+
+/// How long a window capture waits for its window to come back
+///
+/// Games recreate their window when switching display modes (alt-enter) and
+/// get a new one when restarted; capture re-attaches to it in the meantime.
+const REATTACH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time between looks for the window while re-attaching
+const REATTACH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 impl ScreenCapture {
     /// Create a new screen capture instance
     pub fn new(config: CaptureConfig) -> Result<Self> {
         Ok(Self {
             config,
             running: Arc::new(AtomicBool::new(false)),
-            frame_receiver: None,
+            frame_receivers: Vec::new(),
+            gpu_receivers: Vec::new(),
+            low_priority: Arc::new(AtomicBool::new(false)),
+            thread_priority: Arc::new(AtomicI32::new(NO_PRIORITY)),
+            fps_limit: Arc::new(AtomicU32::new(0)),
+            snapshot_interval_ms: Arc::new(AtomicU64::new(0)),
+            source_closed: Arc::new(AtomicBool::new(false)),
+            access_denied: Arc::new(AtomicBool::new(false)),
+            composite_layout: Arc::new(RwLock::new(None)),
+            sources: Vec::new(),
         })
     }
 
@@ -106,16 +311,78 @@ impl ScreenCapture {
             return Ok(());
         }
 
-        let (tx, rx) = bounded::<CapturedFrame>(2); // Small buffer to avoid memory buildup
-        self.frame_receiver = Some(rx);
+        // Latest-wins: a frame a consumer didn't pick up in time is replaced
+        // by the next, without holding up the other consumers
+        let (tx, rx) = latest_broadcast::<CapturedFrame>();
+        self.frame_receivers = subscribe_all(rx);
+        let gpu_frames = self.config.gpu_frames
+            && self.config.color_format == CaptureColorFormat::Bgra8
+            && !matches!(
+                self.config.target,
+                CaptureTarget::Composite(_) | CaptureTarget::Demo
+            );
+        let gpu_tx = gpu_frames.then(|| {
+            let (tx, rx) = latest_broadcast::<GpuFrame>();
+            self.gpu_receivers = subscribe_all(rx);
+            tx
+        });
         self.running.store(true, Ordering::SeqCst);
 
         let config = self.config.clone();
         let running = self.running.clone();
+        let fps_limit = self.fps_limit.clone();
+        let snapshot_interval_ms = self.snapshot_interval_ms.clone();
+        let source_closed = self.source_closed.clone();
+        source_closed.store(false, Ordering::SeqCst);
+        let access_denied = self.access_denied.clone();
+        access_denied.store(false, Ordering::SeqCst);
+        let priority = CapturePriority {
+            low: self.low_priority.clone(),
+            current: self.thread_priority.clone(),
+        };
+        let composite_layout = self.composite_layout.clone();
 
         std::thread::spawn(move || {
-            if let Err(e) = run_capture(config, tx, running.clone()) {
-                error!("Capture error: {}", e);
+            let result = match config.target {
+                CaptureTarget::Composite(ref sources) => {
+                    let sources = sources.clone();
+                    let shared = composite::CompositeShared {
+                        running: running.clone(),
+                        source_closed,
+                        fps_limit,
+                        snapshot_interval_ms,
+                        low_priority: priority.low,
+                        layout: composite_layout,
+                    };
+                    composite::run_composite(config, sources, tx, shared)
+                }
+                CaptureTarget::Demo => demo::run_demo(
+                    config.max_fps,
+                    tx,
+                    running.clone(),
+                    FrameRate {
+                        fps_limit,
+                        snapshot_interval_ms,
+                    },
+                ),
+                _ => run_capture(
+                    config,
+                    tx,
+                    gpu_tx,
+                    running.clone(),
+                    source_closed,
+                    FrameRate {
+                        fps_limit,
+                        snapshot_interval_ms,
+                    },
+                    priority,
+                ),
+            };
+            if let Err(e) = result {
+                if elevation::is_access_denied(&e) {
+                    access_denied.store(true, Ordering::SeqCst);
+                }
+                error!("Capture error: {:#}", e);
             }
             running.store(false, Ordering::SeqCst);
         });
@@ -124,43 +391,316 @@ impl ScreenCapture {
         Ok(())
     }
 
-    /// Stop capturing
+    /// Stop capturing, including the extra sources
     pub fn stop(&mut self) -> Result<()> {
         self.running.store(false, Ordering::SeqCst);
-        self.frame_receiver = None;
+        self.frame_receivers.clear();
+        self.gpu_receivers.clear();
+        for source in self.sources.drain(..) {
+            let mut capture = source.capture;
+            let _ = capture.stop();
+        }
         info!("Screen capture stopped");
         Ok(())
     }
 
+    /// Start capturing an extra source with its own frames and frame rate
+    ///
+    /// A source with the same ID is replaced.
+    pub fn add_source(&mut self, id: &str, target: CaptureTarget, max_fps: u32) -> Result<()> {
+        self.remove_source(id);
+
+        let mut capture = ScreenCapture::new(CaptureConfig {
+            target: target.clone(),
+            max_fps: max_fps.max(1),
+            gpu_frames: false,
+            ..self.config.clone()
+        })?;
+        capture
+            .start()
+            .with_context(|| format!("Failed to start source '{}'", id))?;
+        info!("Capture source '{}' started: {}", id, target.label());
+
+        self.sources.push(SourceCapture {
+            id: id.to_string(),
+            target,
+            capture,
+        });
+        Ok(())
+    }
+
+    /// Stop capturing an extra source, returning whether it was captured
+    pub fn remove_source(&mut self, id: &str) -> bool {
+        let Some(index) = self.sources.iter().position(|s| s.id == id) else {
+            return false;
+        };
+        let mut source = self.sources.remove(index);
+        let _ = source.capture.stop();
+        info!("Capture source '{}' stopped", id);
+        true
+    }
+
+    /// Capture the configured extra sources
+    ///
+    /// Sources no longer configured are stopped, new or changed ones
+    /// started. Every source is tried; the first failure is returned.
+    pub fn sync_sources(&mut self, sources: &[ExtraCaptureSource]) -> Result<()> {
+        let stale: Vec<String> = self
+            .sources
+            .iter()
+            .filter(|s| !sources.iter().any(|c| c.id == s.id))
+            .map(|s| s.id.clone())
+            .collect();
+        for id in stale {
+            self.remove_source(&id);
+        }
+
+        let mut result = Ok(());
+        for source in sources {
+            let target = composite::source_target(&source.target);
+            let current = self.sources.iter().find(|s| s.id == source.id);
+            if current.is_some_and(|s| {
+                s.target == target && s.capture.config.max_fps == source.max_fps.max(1)
+            }) {
+                continue;
+            }
+            if let Err(e) = self.add_source(&source.id, target, source.max_fps) {
+                warn!("{:#}", e);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// IDs of the extra sources
+    pub fn source_ids(&self) -> Vec<String> {
+        self.sources.iter().map(|s| s.id.clone()).collect()
+    }
+
+    /// Whether an extra source is still capturing (its window may have closed)
+    pub fn source_running(&self, id: &str) -> bool {
+        self.source(id).is_some_and(ScreenCapture::is_running)
+    }
+
+    /// Try to get the next frame of an extra source without blocking
+    pub fn try_next_source_frame(&self, id: &str) -> Option<Arc<CapturedFrame>> {
+        self.source(id)?.try_next_frame()
+    }
+
+    /// Capture fewer frames of an extra source than its `max_fps`
+    pub fn set_source_fps_limit(&self, id: &str, limit: Option<u32>) {
+        if let Some(capture) = self.source(id) {
+            capture.set_fps_limit(limit);
+        }
+    }
+
+    fn source(&self, id: &str) -> Option<&ScreenCapture> {
+        self.sources.iter().find(|s| s.id == id).map(|s| &s.capture)
+    }
+
     /// Check if capture is running
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
 
-    /// Get the next captured frame (blocks until available or capture stops)
-    pub fn next_frame(&self) -> Option<CapturedFrame> {
-        self.frame_receiver.as_ref()?.recv().ok()
+    /// Get the pipeline's next captured frame (blocks until available or
+    /// capture stops)
+    ///
+    /// Frames kept on the GPU are downloaded whole.
+    pub fn next_frame(&self) -> Option<Arc<CapturedFrame>> {
+        let index = FrameConsumer::Pipeline as usize;
+        match self.gpu_receivers.get(index) {
+            Some(rx) => download_frame(&rx.recv()?),
+            None => self.frame_receivers.get(index)?.recv(),
+        }
+    }
+
+    /// Try to get the pipeline's next captured frame without blocking
+    ///
+    /// Frames kept on the GPU are downloaded whole.
+    pub fn try_next_frame(&self) -> Option<Arc<CapturedFrame>> {
+        self.try_next_frame_for(FrameConsumer::Pipeline)
+    }
+
+    /// Try to get a consumer's next captured frame without blocking
+    ///
+    /// Frames kept on the GPU are downloaded whole.
+    pub fn try_next_frame_for(&self, consumer: FrameConsumer) -> Option<Arc<CapturedFrame>> {
+        let index = consumer as usize;
+        // Frames the GPU couldn't keep arrive downloaded
+        if let Some(frame) = self.frame_receivers.get(index)?.try_recv() {
+            return Some(frame);
+        }
+        download_frame(&self.gpu_receivers.get(index)?.try_recv()?)
+    }
+
+    /// The newest captured frame, whether or not a consumer had it already
+    ///
+    /// For tools that need one frame now (capturing a template) rather than
+    /// every frame. A frame kept on the GPU is downloaded whole.
+    pub fn latest_frame(&self) -> Option<Arc<CapturedFrame>> {
+        let index = FrameConsumer::Pipeline as usize;
+        match self.gpu_receivers.get(index).and_then(|rx| rx.latest()) {
+            Some(frame) => download_frame(&frame),
+            None => self.frame_receivers.get(index)?.latest(),
+        }
+    }
+
+    /// Try to get the pipeline's next frame kept on the GPU without blocking
+    ///
+    /// Only when capturing with `gpu_frames`.
+    pub fn try_next_gpu_frame(&self) -> Option<Arc<GpuFrame>> {
+        self.gpu_receivers
+            .get(FrameConsumer::Pipeline as usize)?
+            .try_recv()
+    }
+
+    /// Whether frames are kept on the GPU
+    pub fn keeps_gpu_frames(&self) -> bool {
+        !self.gpu_receivers.is_empty()
+    }
+
+    /// Run the capture thread at below-normal priority (applied on the next frame)
+    pub fn set_low_priority(&self, low: bool) {
+        self.low_priority.store(low, Ordering::Relaxed);
+    }
+
+    /// Priority of the capture thread, once it captured a frame
+    pub fn thread_priority(&self) -> Option<i32> {
+        Some(self.thread_priority.load(Ordering::Relaxed)).filter(|&p| p != NO_PRIORITY)
+    }
+
+    /// Capture fewer frames than `max_fps` (takes effect on the next frame)
+    pub fn set_fps_limit(&self, limit: Option<u32>) {
+        self.fps_limit
+            .store(limit.map_or(0, |fps| fps.max(1)), Ordering::Relaxed);
+    }
+
+    /// Send one frame every `interval` instead of streaming, or stream again
+    /// with None (takes effect on the next frame)
+    pub fn set_snapshot_interval(&self, interval: Option<Duration>) {
+        let ms = interval.map_or(0, |interval| (interval.as_millis() as u64).max(1));
+        self.snapshot_interval_ms.store(ms, Ordering::Relaxed);
+    }
+
+    /// Time between frames in snapshot mode (None when streaming)
+    pub fn snapshot_interval(&self) -> Option<Duration> {
+        match self.snapshot_interval_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Whether capture ended because the window (e.g. the game) closed
+    pub fn source_closed(&self) -> bool {
+        self.source_closed.load(Ordering::SeqCst)
     }
 
-    /// Try to get the next captured frame without blocking
-    pub fn try_next_frame(&self) -> Option<CapturedFrame> {
-        self.frame_receiver.as_ref()?.try_recv().ok()
+    /// Whether capture failed because the window runs as administrator
+    pub fn access_denied(&self) -> bool {
+        self.access_denied.load(Ordering::SeqCst)
     }
+
+    /// Where the sources are in the frames, for a composite capture
+    pub fn composite_layout(&self) -> Option<CompositeLayout> {
+        self.composite_layout.read().clone()
+    }
+
+    /// Frames replaced by a newer one before the pipeline picked them up
+    pub fn dropped_frames(&self) -> u64 {
+        let index = FrameConsumer::Pipeline as usize;
+        self.frame_receivers.get(index).map_or(0, |rx| rx.dropped())
+            + self.gpu_receivers.get(index).map_or(0, |rx| rx.dropped())
+    }
+}
+
+/// Download a whole frame kept on the GPU
+fn download_frame(frame: &GpuFrame) -> Option<Arc<CapturedFrame>> {
+    frame
+        .download()
+        .map(Arc::new)
+        .map_err(|e| warn!("Failed to download frame: {:#}", e))
+        .ok()
+}
+
+/// Requested and current priority of the capture thread
+#[derive(Clone)]
+struct CapturePriority {
+    low: Arc<AtomicBool>,
+    current: Arc<AtomicI32>,
+}
+
+/// Limits a capture's frame rate is slowed down by at runtime
+#[derive(Clone)]
+struct FrameRate {
+    fps_limit: Arc<AtomicU32>,
+    snapshot_interval_ms: Arc<AtomicU64>,
+}
+
+impl FrameRate {
+    /// Time between frames of a capture at `max_fps`, slowed down by the fps
+    /// limit and snapshot mode
+    fn interval(&self, max_fps: u32) -> Duration {
+        frame_interval(
+            max_fps,
+            self.fps_limit.load(Ordering::Relaxed),
+            self.snapshot_interval_ms.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Time between frames at `max_fps`, at most `fps_limit` (0 for none) and at
+/// least `snapshot_ms` apart (0 when streaming)
+fn frame_interval(max_fps: u32, fps_limit: u32, snapshot_ms: u64) -> Duration {
+    let fps = match fps_limit {
+        0 => max_fps,
+        limit => limit.min(max_fps),
+    };
+    (Duration::from_secs(1) / fps.max(1)).max(Duration::from_millis(snapshot_ms))
 }
 
 /// Flags passed to the capture handler
 struct CaptureFlags {
-    frame_sender: Sender<CapturedFrame>,
+    frame_sender: Arc<BroadcastSender<CapturedFrame>>,
+    gpu_sender: Option<Arc<BroadcastSender<GpuFrame>>>,
     running: Arc<AtomicBool>,
-    frame_interval_ms: u64,
+    closed: Arc<AtomicBool>,
+    max_fps: u32,
+    frame_rate: FrameRate,
+    priority: CapturePriority,
+    tone_map: Option<HdrToneMap>,
+    grayscale: bool,
+    scale: f32,
+    cursor_area: CursorArea,
+    client_area_only: bool,
 }
 
 /// Internal capture handler for windows-capture
 struct CaptureHandler {
-    frame_sender: Sender<CapturedFrame>,
+    frame_sender: Arc<BroadcastSender<CapturedFrame>>,
+    /// Where frames kept on the GPU go (None = download every frame)
+    gpu_sender: Option<Arc<BroadcastSender<GpuFrame>>>,
     running: Arc<AtomicBool>,
-    frame_interval_ms: u64,
+    /// Set when the captured window or monitor went away
+    closed: Arc<AtomicBool>,
+    max_fps: u32,
+    frame_rate: FrameRate,
     last_frame_time: std::time::Instant,
+    priority: CapturePriority,
+    /// Low priority setting last applied to this thread
+    applied_low_priority: Option<bool>,
+    /// Tone map of an HDR capture (None = 8-bit BGRA frames)
+    tone_map: Option<HdrToneMap>,
+    /// Convert 8-bit frames to grayscale
+    grayscale: bool,
+    /// Factor frames are shrunk by before they're sent
+    scale: f32,
+    /// Window or monitor the cursor position is taken relative to
+    cursor_area: CursorArea,
+    /// Crop frames of a window to its client area
+    client_area_only: bool,
 }
 
 impl GraphicsCaptureApiHandler for CaptureHandler {
@@ -171,9 +711,19 @@ impl GraphicsCaptureApiHandler for CaptureHandler {
         let flags = ctx.flags;
         Ok(Self {
             frame_sender: flags.frame_sender,
+            gpu_sender: flags.gpu_sender,
             running: flags.running,
-            frame_interval_ms: flags.frame_interval_ms,
+            closed: flags.closed,
+            max_fps: flags.max_fps,
+            frame_rate: flags.frame_rate,
             last_frame_time: std::time::Instant::now(),
+            priority: flags.priority,
+            applied_low_priority: None,
+            tone_map: flags.tone_map,
+            grayscale: flags.grayscale,
+            scale: flags.scale,
+            cursor_area: flags.cursor_area,
+            client_area_only: flags.client_area_only,
         })
     }
 
@@ -188,9 +738,20 @@ impl GraphicsCaptureApiHandler for CaptureHandler {
             return Ok(());
         }
 
-        // Rate limiting
-        let elapsed = self.last_frame_time.elapsed().as_millis() as u64;
-        if elapsed < self.frame_interval_ms {
+        // Frames arrive on the capture thread, so its priority is set here
+        let low_priority = self.priority.low.load(Ordering::Relaxed);
+        if self.applied_low_priority != Some(low_priority) {
+            if let Err(e) = crate::qos::set_current_thread_low_priority(low_priority) {
+                warn!("Capture thread priority unchanged: {:#}", e);
+            }
+            self.applied_low_priority = Some(low_priority);
+            self.priority
+                .current
+                .store(crate::qos::current_thread_priority(), Ordering::Relaxed);
+        }
+
+        // Rate limiting, slowed down further by the fps limit and snapshot mode
+        if self.last_frame_time.elapsed() < self.frame_rate.interval(self.max_fps) {
             return Ok(());
         }
         self.last_frame_time = std::time::Instant::now();
@@ -198,37 +759,87 @@ impl GraphicsCaptureApiHandler for CaptureHandler {
         // Get frame buffer
         let width = frame.width();
         let height = frame.height();
+        let cursor = self.cursor_area.cursor_in_frame(width, height);
+        // Title bar and borders of a windowed game, cut off as (x, y, w, h)
+        let client_area = match self.cursor_area {
+            CursorArea::Window(hwnd) if self.client_area_only => {
+                client_area::client_area_in_frame(hwnd, width, height)
+            }
+            _ => None,
+        };
+
+        // Keep the frame on the GPU; only regions that are read get downloaded
+        if let Some(ref gpu_sender) = self.gpu_sender {
+            let (x, y, w, h) = client_area.unwrap_or((0, 0, width, height));
+            // SAFETY: the texture is only copied from while the frame is current
+            let texture = unsafe { frame.as_raw_texture() };
+            match GpuFrame::copy_of(texture, x, y, w, h) {
+                Ok(mut gpu_frame) => {
+                    gpu_frame.timestamp = self.last_frame_time;
+                    gpu_frame.cursor = cursor_in_region(cursor, x, y, w, h);
+                    gpu_sender.send(gpu_frame);
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!("Can't keep frames on the GPU, downloading them: {:#}", e);
+                    self.gpu_sender = None;
+                }
+            }
+        }
 
         // Convert frame to RGBA buffer
         let mut buffer = frame.buffer().context("Failed to get frame buffer")?;
-        let data = buffer.as_raw_buffer().to_vec();
-
-        // Create captured frame (windows-capture uses BGRA, we'll convert later if needed)
-        let captured = CapturedFrame::new_bgra(data, width, height);
 
-        // Send frame (non-blocking, drop if receiver is full)
-        if self.frame_sender.try_send(captured).is_err() {
-            debug!("Frame dropped (receiver full)");
+        // Create captured frame (windows-capture uses BGRA, or half-float RGBA
+        // for HDR), stamped with when it arrived rather than after the conversion
+        let row_pitch = buffer.row_pitch() as usize;
+        let mut captured = match self.tone_map {
+            Some(ref tone_map) => {
+                CapturedFrame::from_hdr(buffer.as_raw_buffer(), row_pitch, width, height, tone_map)
+            }
+            None if self.grayscale => {
+                CapturedFrame::gray_from_bgra(buffer.as_raw_buffer(), row_pitch, width, height)
+            }
+            None => CapturedFrame::new_bgra(buffer.as_raw_buffer().to_vec(), width, height),
+        };
+        captured.timestamp = self.last_frame_time;
+        captured.cursor = cursor;
+        if let Some((x, y, w, h)) = client_area {
+            if let Some(cropped) = captured.extract_region(x, y, w, h) {
+                captured = cropped;
+            }
+        }
+        if self.scale < 1.0 {
+            captured = captured.scaled(self.scale);
         }
 
+        // Send frame (non-blocking, replaces a frame not picked up yet)
+        self.frame_sender.send(captured);
+
         Ok(())
     }
 
     fn on_closed(&mut self) -> Result<(), Self::Error> {
         info!("Capture source closed");
-        self.running.store(false, Ordering::SeqCst);
+        self.closed.store(true, Ordering::SeqCst);
         Ok(())
     }
 }
 
 /// Run the capture loop (blocking)
+///
+/// When the window of a window capture closes, capture looks for it again
+/// for [`REATTACH_TIMEOUT`] and carries on with the new one. Only then does
+/// it end with `source_closed` set.
 fn run_capture(
     config: CaptureConfig,
-    tx: Sender<CapturedFrame>,
+    tx: BroadcastSender<CapturedFrame>,
+    gpu_tx: Option<BroadcastSender<GpuFrame>>,
     running: Arc<AtomicBool>,
+    source_closed: Arc<AtomicBool>,
+    frame_rate: FrameRate,
+    priority: CapturePriority,
 ) -> Result<()> {
-    let frame_interval_ms = 1000 / config.max_fps.max(1) as u64;
-
     let cursor_settings = if config.capture_cursor {
         CursorCaptureSettings::WithCursor
     } else {
@@ -241,43 +852,95 @@ fn run_capture(
         DrawBorderSettings::WithoutBorder
     };
 
-    let flags = CaptureFlags {
-        frame_sender: tx,
-        running,
-        frame_interval_ms,
+    // Only window captures have secondary windows
+    let secondary_window_settings = match config.secondary_windows {
+        SecondaryWindowMode::Default => SecondaryWindowSettings::Default,
+        SecondaryWindowMode::Include => SecondaryWindowSettings::Include,
+        SecondaryWindowMode::Exclude => SecondaryWindowSettings::Exclude,
+    };
+
+    // HDR frames come as half-float scRGB and are tone-mapped on arrival
+    let tone_map = config.color_format.tone_map();
+    let color_format = config.color_format.windows_format();
+
+    // Shared by every capture session, so frames keep flowing after a re-attach
+    let frame_sender = Arc::new(tx);
+    let gpu_sender = gpu_tx.map(Arc::new);
+    let closed = Arc::new(AtomicBool::new(false));
+    let flags = |cursor_area| CaptureFlags {
+        frame_sender: frame_sender.clone(),
+        gpu_sender: gpu_sender.clone(),
+        running: running.clone(),
+        closed: closed.clone(),
+        max_fps: config.max_fps,
+        frame_rate: frame_rate.clone(),
+        priority: priority.clone(),
+        tone_map: tone_map.clone(),
+        grayscale: config.color_format == CaptureColorFormat::Gray8,
+        scale: config.scale,
+        cursor_area,
+        client_area_only: config.client_area_only,
     };
 
     match config.target {
-        CaptureTarget::Window(title) => {
-            let windows = Window::enumerate().context("Failed to enumerate windows")?;
-            let window = windows
-                .into_iter()
-                .find(|w| {
-                    w.title()
-                        .map(|t| t.to_lowercase().contains(&title.to_lowercase()))
-                        .unwrap_or(false)
-                })
-                .context(format!("Window '{}' not found", title))?;
+        CaptureTarget::Window(_) | CaptureTarget::Process(_) | CaptureTarget::Executable(_) => {
+            let mut window = find_window(&config.target)?;
+            let targets = reattach_targets(&config.target);
 
-            info!("Capturing window: {:?}", window.title());
+            loop {
+                // Capture of an elevated window fails or stays blank unless we're elevated too
+                if let Ok(pid) = window.process_id() {
+                    if elevation::process_is_elevated(pid) && !elevation::is_elevated() {
+                        return Err(elevation::access_denied_error()).context(format!(
+                            "Window '{}' belongs to a program running as administrator",
+                            window.title().unwrap_or_default()
+                        ));
+                    }
+                }
 
-            let settings = Settings::new(
-                window,
-                cursor_settings,
-                border_settings,
-                SecondaryWindowSettings::Default,
-                MinimumUpdateIntervalSettings::Default,
-                DirtyRegionSettings::Default,
-                ColorFormat::Bgra8,
-                flags,
-            );
+                info!("Capturing window: {:?}", window.title());
+                closed.store(false, Ordering::SeqCst);
+
+                let settings = Settings::new(
+                    window,
+                    cursor_settings,
+                    border_settings,
+                    secondary_window_settings,
+                    MinimumUpdateIntervalSettings::Default,
+                    DirtyRegionSettings::Default,
+                    color_format,
+                    flags(CursorArea::Window(window.as_raw_hwnd() as usize)),
+                );
+
+                CaptureHandler::start(settings).context("Failed to start window capture")?;
+
+                if !running.load(Ordering::SeqCst) || !closed.load(Ordering::SeqCst) {
+                    break;
+                }
 
-            CaptureHandler::start(settings).context("Failed to start window capture")?;
+                info!(
+                    "Captured window closed, waiting up to {:?} for it to come back",
+                    REATTACH_TIMEOUT
+                );
+                match wait_for_window(&targets, &running) {
+                    Some(found) => {
+                        info!("Re-attaching capture to {:?}", found.title());
+                        window = found;
+                    }
+                    None => {
+                        if running.load(Ordering::SeqCst) {
+                            source_closed.store(true, Ordering::SeqCst);
+                        }
+                        break;
+                    }
+                }
+            }
         }
         CaptureTarget::PrimaryMonitor => {
             let monitor = Monitor::primary().context("Failed to get primary monitor")?;
             info!("Capturing primary monitor: {:?}", monitor.name());
 
+            let cursor_area = CursorArea::Monitor(monitor.as_raw_hmonitor() as usize);
             let settings = Settings::new(
                 monitor,
                 cursor_settings,
@@ -285,11 +948,12 @@ fn run_capture(
                 SecondaryWindowSettings::Default,
                 MinimumUpdateIntervalSettings::Default,
                 DirtyRegionSettings::Default,
-                ColorFormat::Bgra8,
-                flags,
+                color_format,
+                flags(cursor_area),
             );
 
             CaptureHandler::start(settings).context("Failed to start monitor capture")?;
+            source_closed.store(closed.load(Ordering::SeqCst), Ordering::SeqCst);
         }
         CaptureTarget::MonitorIndex(idx) => {
             let monitors = Monitor::enumerate().context("Failed to enumerate monitors")?;
@@ -300,6 +964,7 @@ fn run_capture(
 
             info!("Capturing monitor {}: {:?}", idx, monitor.name());
 
+            let cursor_area = CursorArea::Monitor(monitor.as_raw_hmonitor() as usize);
             let settings = Settings::new(
                 monitor,
                 cursor_settings,
@@ -307,17 +972,131 @@ fn run_capture(
                 SecondaryWindowSettings::Default,
                 MinimumUpdateIntervalSettings::Default,
                 DirtyRegionSettings::Default,
-                ColorFormat::Bgra8,
-                flags,
+                color_format,
+                flags(cursor_area),
             );
 
             CaptureHandler::start(settings).context("Failed to start monitor capture")?;
+            source_closed.store(closed.load(Ordering::SeqCst), Ordering::SeqCst);
+        }
+        CaptureTarget::Composite(_) => {
+            anyhow::bail!("Composite captures run their sources separately");
+        }
+        CaptureTarget::Demo => {
+            anyhow::bail!("The demo game draws its own frames");
         }
     }
 
     Ok(())
 }
 
+/// Find the window of a window, process or executable target
+///
+/// A process's main window is its largest titled one.
+fn find_window(target: &CaptureTarget) -> Result<Window> {
+    let windows = Window::enumerate().context("Failed to enumerate windows")?;
+
+    match target {
+        CaptureTarget::Window(title) => {
+            let needle = title.to_lowercase();
+            windows
+                .into_iter()
+                .find(|w| w.title().is_ok_and(|t| t.to_lowercase().contains(&needle)))
+                .with_context(|| format!("Window '{}' not found", title))
+        }
+        CaptureTarget::Process(pid) => main_window(
+            windows
+                .into_iter()
+                .filter(|w| w.process_id().ok() == Some(*pid)),
+        )
+        .with_context(|| format!("No window of process {} found", pid)),
+        CaptureTarget::Executable(executable) => {
+            // Listed from a process snapshot, which works for elevated games too
+            let pids: HashSet<u32> = process_watcher::running_processes()?
+                .into_iter()
+                .filter(|(_, name)| process_watcher::is_executable(name, executable))
+                .map(|(pid, _)| pid)
+                .collect();
+            main_window(
+                windows
+                    .into_iter()
+                    .filter(|w| w.process_id().is_ok_and(|pid| pids.contains(&pid))),
+            )
+            .with_context(|| format!("No window of {} found", executable))
+        }
+        _ => anyhow::bail!("{} isn't a window", target.label()),
+    }
+}
+
+/// Largest window with a title
+fn main_window(windows: impl Iterator<Item = Window>) -> Option<Window> {
+    windows
+        .filter(|w| w.title().is_ok_and(|t| !t.is_empty()))
+        .max_by_key(|w| {
+            w.rect().map_or(0, |r| {
+                i64::from(r.right - r.left) * i64::from(r.bottom - r.top)
+            })
+        })
+}
+
+/// Where to look for a window target's window after it closed
+///
+/// A process's window is looked for in the same process first, then in a
+/// new instance of its program, in case the game was restarted.
+fn reattach_targets(target: &CaptureTarget) -> Vec<CaptureTarget> {
+    let mut targets = vec![target.clone()];
+    if let CaptureTarget::Process(pid) = target {
+        let executable = process_watcher::running_processes()
+            .ok()
+            .and_then(|processes| processes.into_iter().find(|(id, _)| id == pid))
+            .map(|(_, executable)| executable);
+        targets.extend(executable.map(CaptureTarget::Executable));
+    }
+    targets
+}
+
+/// Wait for one of the targets' windows to appear, while capture is running
+fn wait_for_window(targets: &[CaptureTarget], running: &AtomicBool) -> Option<Window> {
+    let deadline = Instant::now() + REATTACH_TIMEOUT;
+    while running.load(Ordering::SeqCst) && Instant::now() < deadline {
+        if let Some(window) = targets.iter().find_map(|target| find_window(target).ok()) {
+            return Some(window);
+        }
+        std::thread::sleep(REATTACH_POLL_INTERVAL);
+    }
+    None
+}
+
+/// Executable name of the program showing a window (by title, partial match)
+pub fn executable_of_window(title: &str) -> Option<String> {
+    let pid = find_window(&CaptureTarget::Window(title.to_string()))
+        .ok()?
+        .process_id()
+        .ok()?;
+    process_watcher::running_processes()
+        .ok()?
+        .into_iter()
+        .find(|(id, _)| *id == pid)
+        .map(|(_, executable)| executable)
+}
+
+/// Index of the monitor showing a window (by title, partial match)
+///
+/// The index is the position in [`ScreenCapture::list_monitors`].
+pub fn monitor_of_window(title: &str) -> Option<usize> {
+    let title = title.to_lowercase();
+    let window = Window::enumerate().ok()?.into_iter().find(|w| {
+        w.title()
+            .map(|t| t.to_lowercase().contains(&title))
+            .unwrap_or(false)
+    })?;
+    let monitor = window.monitor()?;
+    Monitor::enumerate()
+        .ok()?
+        .into_iter()
+        .position(|m| m == monitor)
+}
+
 /// Capture a single frame from the given target and return it.
 ///
 /// This creates a temporary capture session, grabs the first frame,
@@ -328,6 +1107,11 @@ pub fn capture_frame_once(target: &CaptureTarget) -> Result<CapturedFrame> {
         max_fps: 30,
         capture_cursor: false,
         draw_border: false,
+        gpu_frames: false,
+        color_format: CaptureColorFormat::Bgra8,
+        scale: 1.0,
+        client_area_only: false,
+        secondary_windows: SecondaryWindowMode::Default,
     };
 
     let mut capture = ScreenCapture::new(config)?;
@@ -339,7 +1123,7 @@ pub fn capture_frame_once(target: &CaptureTarget) -> Result<CapturedFrame> {
     loop {
         if let Some(frame) = capture.try_next_frame() {
             let _ = capture.stop();
-            return Ok(frame);
+            return Ok(Arc::unwrap_or_clone(frame));
         }
         if start.elapsed() > timeout {
             let _ = capture.stop();
@@ -0,0 +1,325 @@
+//! Game launch detection
+//!
+//! A background thread lists the running processes every couple of seconds
+//! and reports executables of profiles set to auto-start when they appear.
+//! Games already running when watching begins count as launched, so starting
+//! GamersToolKit after the game works the same way.
+
+use anyhow::{Context, Result};
+use crossbeam_channel::{unbounded, Receiver};
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use windows::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible,
+};
+
+/// Time between process list checks
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Executables of a profile that auto-starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedGame {
+    /// Profile to activate
+    pub profile_id: String,
+    /// Executable names, e.g. "game.exe"
+    pub executables: Vec<String>,
+}
+
+/// A watched executable that started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameLaunch {
+    /// Profile the executable belongs to
+    pub profile_id: String,
+    /// Process ID of the game
+    pub pid: u32,
+    /// Executable name as Windows reports it
+    pub executable: String,
+}
+
+/// Watches for games of auto-start profiles, and of the active profile, to launch
+pub struct ProcessWatcher {
+    watched: Arc<Mutex<Vec<WatchedGame>>>,
+    launches: Receiver<GameLaunch>,
+    stop: Arc<AtomicBool>,
+}
+
+impl ProcessWatcher {
+    /// Start watching on a background thread
+    ///
+    /// `wake` is called after a launch is reported, so an idle UI can pick it up.
+    pub fn start(wake: impl Fn() + Send + 'static) -> Self {
+        let watched: Arc<Mutex<Vec<WatchedGame>>> = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, launches) = unbounded();
+
+        let thread_watched = Arc::clone(&watched);
+        let thread_stop = Arc::clone(&stop);
+        std::thread::Builder::new()
+            .name("process-watcher".to_string())
+            .spawn(move || {
+                let mut known = HashSet::new();
+                let mut watching = Vec::new();
+                while !thread_stop.load(Ordering::Relaxed) {
+                    let games = thread_watched.lock().clone();
+                    if games.is_empty() {
+                        // Games running when watching starts again count as launched
+                        known.clear();
+                        watching.clear();
+                    } else {
+                        match running_processes() {
+                            Ok(processes) => {
+                                let found =
+                                    find_new_launches(&known, &watching, &processes, &games);
+                                known = processes.iter().map(|(pid, _)| *pid).collect();
+                                watching = games;
+                                for launch in found {
+                                    tracing::info!(
+                                        "Detected {} (pid {}) for profile '{}'",
+                                        launch.executable,
+                                        launch.pid,
+                                        launch.profile_id
+                                    );
+                                    if sender.send(launch).is_err() {
+                                        return;
+                                    }
+                                    wake();
+                                }
+                            }
+                            Err(e) => tracing::debug!("Failed to list processes: {:#}", e),
+                        }
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            })
+            .expect("failed to spawn process watcher thread");
+
+        Self {
+            watched,
+            launches,
+            stop,
+        }
+    }
+
+    /// Replace the games to watch for
+    pub fn set_watched(&self, games: Vec<WatchedGame>) {
+        *self.watched.lock() = games;
+    }
+
+    /// Take the next reported launch
+    pub fn try_recv(&self) -> Option<GameLaunch> {
+        self.launches.try_recv().ok()
+    }
+}
+
+impl Drop for ProcessWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Watched executables among processes that weren't running before
+///
+/// Executable names compare case-insensitively, and a profile may list a
+/// full path, of which only the file name counts.
+pub fn find_launches(
+    known: &HashSet<u32>,
+    processes: &[(u32, String)],
+    games: &[WatchedGame],
+) -> Vec<GameLaunch> {
+    processes
+        .iter()
+        .filter(|(pid, _)| !known.contains(pid))
+        .filter_map(|(pid, executable)| {
+            games
+                .iter()
+                .find(|game| {
+                    game.executables
+                        .iter()
+                        .any(|exe| is_executable(executable, exe))
+                })
+                .map(|game| GameLaunch {
+                    profile_id: game.profile_id.clone(),
+                    pid: *pid,
+                    executable: executable.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Launches since the last check, when `watching` was watched and `known`
+/// were running
+///
+/// Games added to the watch since then count as launched if they're already
+/// running, like every game does when watching starts.
+pub fn find_new_launches(
+    known: &HashSet<u32>,
+    watching: &[WatchedGame],
+    processes: &[(u32, String)],
+    games: &[WatchedGame],
+) -> Vec<GameLaunch> {
+    let added: Vec<WatchedGame> = games
+        .iter()
+        .filter(|game| !watching.contains(game))
+        .cloned()
+        .collect();
+    let mut launches = find_launches(known, processes, games);
+    launches.extend(
+        find_launches(&HashSet::new(), processes, &added)
+            .into_iter()
+            .filter(|launch| known.contains(&launch.pid)),
+    );
+    launches
+}
+
+/// File name of an executable given with or without its path
+fn exe_file_name(exe: &str) -> &str {
+    let exe = exe.trim();
+    exe.rsplit(['\\', '/']).next().unwrap_or(exe)
+}
+
+/// Whether a process's executable name is the given executable
+///
+/// Names compare case-insensitively; the given executable may include its path.
+pub fn is_executable(name: &str, executable: &str) -> bool {
+    exe_file_name(executable).eq_ignore_ascii_case(name)
+}
+
+/// Process IDs and executable names of the running processes
+pub fn running_processes() -> Result<Vec<(u32, String)>> {
+    let mut processes = Vec::new();
+
+    // SAFETY: the snapshot handle is closed below, and the entry's size is set
+    // before it's passed in as the API requires
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .context("Failed to snapshot processes")?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut next = Process32FirstW(snapshot, &mut entry);
+        while next.is_ok() {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            processes.push((
+                entry.th32ProcessID,
+                String::from_utf16_lossy(&entry.szExeFile[..len]),
+            ));
+            next = Process32NextW(snapshot, &mut entry);
+        }
+
+        let _ = CloseHandle(snapshot);
+    }
+
+    Ok(processes)
+}
+
+/// Title of the first visible, titled window of a process
+pub fn main_window_title(pid: u32) -> Option<String> {
+    struct Search {
+        pid: u32,
+        title: Option<String>,
+    }
+
+    unsafe extern "system" fn enum_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+
+        let mut window_pid = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut window_pid));
+        if window_pid != search.pid || !IsWindowVisible(hwnd).as_bool() {
+            return BOOL(1);
+        }
+
+        let mut buffer = [0u16; 512];
+        let len = GetWindowTextW(hwnd, &mut buffer);
+        if len <= 0 {
+            return BOOL(1);
+        }
+        search.title = Some(String::from_utf16_lossy(&buffer[..len as usize]));
+        BOOL(0) // Stop enumeration
+    }
+
+    let mut search = Search { pid, title: None };
+    // SAFETY: `search` outlives the enumeration, which runs synchronously
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_callback),
+            LPARAM(&mut search as *mut Search as isize),
+        );
+    }
+    search.title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_launches() {
+        let games = [WatchedGame {
+            profile_id: "tarkov".to_string(),
+            executables: vec!["C:\\Games\\EscapeFromTarkov.exe".to_string()],
+        }];
+        let processes = [
+            (4, "System".to_string()),
+            (1200, "explorer.exe".to_string()),
+            (5400, "escapefromtarkov.exe".to_string()),
+        ];
+
+        let launches = find_launches(&HashSet::new(), &processes, &games);
+        assert_eq!(
+            launches,
+            [GameLaunch {
+                profile_id: "tarkov".to_string(),
+                pid: 5400,
+                executable: "escapefromtarkov.exe".to_string(),
+            }]
+        );
+
+        // Already seen on the previous check
+        let known: HashSet<u32> = processes.iter().map(|(pid, _)| *pid).collect();
+        assert!(find_launches(&known, &processes, &games).is_empty());
+    }
+
+    #[test]
+    fn test_added_game_already_running_launches() {
+        let tarkov = WatchedGame {
+            profile_id: "tarkov".to_string(),
+            executables: vec!["EscapeFromTarkov.exe".to_string()],
+        };
+        let valorant = WatchedGame {
+            profile_id: "valorant".to_string(),
+            executables: vec!["VALORANT.exe".to_string()],
+        };
+        let processes = [
+            (5400, "EscapeFromTarkov.exe".to_string()),
+            (6100, "VALORANT.exe".to_string()),
+        ];
+        let known: HashSet<u32> = processes.iter().map(|(pid, _)| *pid).collect();
+
+        let games = [tarkov.clone(), valorant];
+        let launches = find_new_launches(&known, &[tarkov], &processes, &games);
+        assert_eq!(launches.len(), 1);
+        assert_eq!(launches[0].profile_id, "valorant");
+
+        assert!(find_new_launches(&known, &games, &processes, &games).is_empty());
+    }
+
+    #[test]
+    fn test_is_executable() {
+        assert!(is_executable("game.exe", "Game.exe"));
+        assert!(is_executable("game.exe", "C:\\Games\\Game.exe"));
+        assert!(!is_executable("game.exe", "launcher.exe"));
+    }
+}
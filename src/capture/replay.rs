@@ -0,0 +1,328 @@
+//! Replay buffer
+//!
+//! Keeps the last seconds of captured frames in memory, shrunk and thinned
+//! to a few per second, so what happened right before a death can be saved
+//! and looked at afterwards. Saved as an MP4 video (through windows-capture's
+//! Media Foundation encoder) or as a folder of PNG images.
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use windows_capture::encoder::{
+    AudioSettingsBuilder, ContainerSettingsBuilder, VideoEncoder, VideoSettingsBuilder,
+    VideoSettingsSubType,
+};
+
+use super::CapturedFrame;
+use crate::config::{ReplayFormat, ReplaySettings};
+
+/// The last seconds of capture, oldest frame first
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    frames: VecDeque<CapturedFrame>,
+    /// How much capture is kept
+    length: Duration,
+    /// Least time between kept frames
+    interval: Duration,
+    /// Frames are shrunk to at most this width
+    max_width: u32,
+}
+
+impl ReplayBuffer {
+    /// Create an empty buffer
+    pub fn new(settings: &ReplaySettings) -> Self {
+        let mut buffer = Self {
+            frames: VecDeque::new(),
+            length: Duration::ZERO,
+            interval: Duration::ZERO,
+            max_width: settings.max_width,
+        };
+        buffer.configure(settings);
+        buffer
+    }
+
+    /// Apply changed settings, keeping what fits them
+    pub fn configure(&mut self, settings: &ReplaySettings) {
+        self.length = Duration::from_secs(u64::from(settings.seconds));
+        self.interval = Duration::from_secs(1) / settings.fps.max(1);
+        if settings.max_width != self.max_width {
+            self.max_width = settings.max_width;
+            self.frames.clear();
+        }
+        self.trim();
+    }
+
+    /// Whether a frame captured at `timestamp` would be kept
+    pub fn wants(&self, timestamp: Instant) -> bool {
+        self.frames.back().map_or(true, |last| {
+            timestamp.saturating_duration_since(last.timestamp) >= self.interval
+        })
+    }
+
+    /// Keep a shrunk copy of a frame, if it's due
+    ///
+    /// Returns whether the frame was kept.
+    pub fn push(&mut self, frame: &CapturedFrame) -> bool {
+        if frame.width < 2 || frame.height < 2 || !self.wants(frame.timestamp) {
+            return false;
+        }
+        let frame = downscale(frame, self.max_width);
+        // A video has one size, so a resized target starts the replay over
+        if self
+            .frames
+            .back()
+            .is_some_and(|last| last.dimensions() != frame.dimensions())
+        {
+            self.frames.clear();
+        }
+        self.frames.push_back(frame);
+        self.trim();
+        true
+    }
+
+    /// Drop frames older than the buffer's length
+    fn trim(&mut self) {
+        let Some(newest) = self.frames.back().map(|frame| frame.timestamp) else {
+            return;
+        };
+        while self
+            .frames
+            .front()
+            .is_some_and(|frame| newest.saturating_duration_since(frame.timestamp) > self.length)
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Copy of the frames, for saving them off the UI thread
+    pub fn frames(&self) -> Vec<CapturedFrame> {
+        self.frames.iter().cloned().collect()
+    }
+
+    /// Number of frames kept
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames are kept
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time between the oldest and the newest frame
+    pub fn span(&self) -> Duration {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(oldest), Some(newest)) => {
+                newest.timestamp.saturating_duration_since(oldest.timestamp)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Memory the frames take
+    pub fn memory_bytes(&self) -> usize {
+        self.frames.iter().map(|frame| frame.data.len()).sum()
+    }
+
+    /// Forget every frame, e.g. when capturing something else
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// Shrink a frame to at most `max_width`, keeping its aspect ratio
+///
+/// Nearest-neighbour, which is plenty for looking back at what happened.
+/// Both sides come out even, as video encoders need.
+pub fn downscale(frame: &CapturedFrame, max_width: u32) -> CapturedFrame {
+    let width = frame.width.min(max_width.max(2)) & !1;
+    let height =
+        ((u64::from(frame.height) * u64::from(width) / u64::from(frame.width)) as u32).max(2) & !1;
+
+    let bytes_per_pixel = frame.format.bytes_per_pixel();
+    let mut data = Vec::with_capacity(width as usize * height as usize * bytes_per_pixel);
+    for y in 0..height {
+        let source_y = (u64::from(y) * u64::from(frame.height) / u64::from(height)) as usize;
+        let row = source_y * frame.stride() as usize;
+        for x in 0..width {
+            let source_x = (u64::from(x) * u64::from(frame.width) / u64::from(width)) as usize;
+            let start = row + source_x * bytes_per_pixel;
+            data.extend_from_slice(&frame.data[start..start + bytes_per_pixel]);
+        }
+    }
+
+    CapturedFrame {
+        data,
+        format: frame.format,
+        width,
+        height,
+        timestamp: frame.timestamp,
+        cursor: frame.cursor.map(|(x, y)| {
+            (
+                (u64::from(x) * u64::from(width) / u64::from(frame.width)) as u32,
+                (u64::from(y) * u64::from(height) / u64::from(frame.height)) as u32,
+            )
+        }),
+    }
+}
+
+/// Save replay frames into `dir`, returning the video file or image folder
+///
+/// `fps` is the rate the frames were kept at; the video follows their
+/// capture times either way.
+pub fn save_replay(
+    frames: &[CapturedFrame],
+    format: ReplayFormat,
+    fps: u32,
+    dir: &Path,
+) -> Result<PathBuf> {
+    if frames.is_empty() {
+        anyhow::bail!("The replay buffer is empty");
+    }
+    std::fs::create_dir_all(dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match format {
+        ReplayFormat::Mp4 => {
+            let path = dir.join(format!("replay-{}.mp4", timestamp));
+            save_video(frames, fps, &path)?;
+            Ok(path)
+        }
+        ReplayFormat::PngSequence => {
+            let path = dir.join(format!("replay-{}", timestamp));
+            save_images(frames, &path)?;
+            Ok(path)
+        }
+    }
+}
+
+/// One numbered PNG per frame
+fn save_images(frames: &[CapturedFrame], dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for (index, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame-{:04}.png", index + 1));
+        frame
+            .to_rgba_image()
+            .ok_or_else(|| anyhow!("Frame data does not match its size"))?
+            .save(&path)
+            .with_context(|| format!("Failed to save replay frame {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// H.264 video of the frames, timed by when they were captured
+fn save_video(frames: &[CapturedFrame], fps: u32, path: &Path) -> Result<()> {
+    let (width, height) = frames[0].dimensions();
+    let mut encoder = VideoEncoder::new(
+        VideoSettingsBuilder::new(width, height)
+            .sub_type(VideoSettingsSubType::H264)
+            .frame_rate(fps.max(1))
+            .bitrate(4_000_000),
+        AudioSettingsBuilder::default().disabled(true),
+        ContainerSettingsBuilder::default(),
+        path,
+    )
+    .map_err(|e| anyhow!("Failed to start the video encoder: {}", e))?;
+
+    let first = frames[0].timestamp;
+    let mut bgra = Vec::new();
+    for frame in frames {
+        rgba_to_bgra(&frame.rgba(), &mut bgra);
+        // In 100-nanosecond units
+        let timestamp = (frame.timestamp.saturating_duration_since(first).as_nanos() / 100) as i64;
+        encoder
+            .send_frame_buffer(&bgra, timestamp)
+            .map_err(|e| anyhow!("Failed to encode a replay frame: {}", e))?;
+    }
+    encoder
+        .finish()
+        .map_err(|e| anyhow!("Failed to finish the replay video: {}", e))
+}
+
+/// RGBA pixels in the BGRA order the encoder takes
+fn rgba_to_bgra(rgba: &[u8], bgra: &mut Vec<u8>) {
+    bgra.clear();
+    bgra.extend_from_slice(rgba);
+    for pixel in bgra.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::PixelFormat;
+
+    fn settings(seconds: u32, fps: u32) -> ReplaySettings {
+        ReplaySettings {
+            enabled: true,
+            seconds,
+            fps,
+            max_width: 4,
+            format: ReplayFormat::Mp4,
+        }
+    }
+
+    fn frame_at(timestamp: Instant) -> CapturedFrame {
+        CapturedFrame {
+            data: vec![0; 8 * 6 * 4],
+            format: PixelFormat::Rgba8,
+            width: 8,
+            height: 6,
+            timestamp,
+            cursor: None,
+        }
+    }
+
+    #[test]
+    fn test_downscale() {
+        // 4x2, each pixel's red channel is its index
+        let data = (0..8u8).flat_map(|i| [i, 0, 0, 255]).collect();
+        let frame = CapturedFrame::new(data, 4, 2);
+
+        let small = downscale(&frame, 2);
+        assert_eq!(small.dimensions(), (2, 2));
+        assert_eq!(
+            small.data,
+            [0, 0, 0, 255, 2, 0, 0, 255, 4, 0, 0, 255, 6, 0, 0, 255]
+        );
+        // Smaller frames keep their size, made even
+        assert_eq!(
+            downscale(&CapturedFrame::new(vec![0; 5 * 3 * 4], 5, 3), 640).dimensions(),
+            (4, 2)
+        );
+    }
+
+    #[test]
+    fn test_buffer_keeps_last_seconds() {
+        let start = Instant::now();
+        let mut buffer = ReplayBuffer::new(&settings(2, 4));
+
+        // 10 frames a second for 5 seconds
+        let kept = (0..50)
+            .filter(|i| buffer.push(&frame_at(start + Duration::from_millis(i * 100))))
+            .count();
+        assert!((16..=25).contains(&kept), "kept {}", kept);
+        assert!(buffer.span() <= Duration::from_secs(2));
+        assert!(buffer.span() >= Duration::from_millis(1500));
+        assert_eq!(buffer.frames()[0].dimensions(), (4, 2));
+
+        // A resized target starts over
+        let mut resized = frame_at(start + Duration::from_secs(6));
+        resized.data = vec![0; 8 * 8 * 4];
+        resized.height = 8;
+        assert!(buffer.push(&resized));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_rgba_to_bgra() {
+        let mut bgra = Vec::new();
+        rgba_to_bgra(&[1, 2, 3, 4, 5, 6, 7, 8], &mut bgra);
+        assert_eq!(bgra, [3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+}
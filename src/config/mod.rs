@@ -4,18 +4,27 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+mod validation;
+
+pub use validation::{load_config_with_mode, ConfigIssue, ConfigLoad, ConfigParseMode};
 
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     /// General settings
+    #[serde(default)]
     pub general: GeneralConfig,
     /// Capture settings
+    #[serde(default)]
     pub capture: CaptureSettings,
     /// Overlay settings
+    #[serde(default)]
     pub overlay: OverlaySettings,
     /// Performance settings
+    #[serde(default)]
     pub performance: PerformanceConfig,
     /// Vision/OCR settings
     #[serde(default)]
@@ -23,10 +32,32 @@ pub struct AppConfig {
     /// Dashboard UI settings
     #[serde(default)]
     pub dashboard: DashboardSettings,
+    /// Opt-in telemetry settings
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+    /// Playtime reminders and limits
+    #[serde(default)]
+    pub wellness: WellnessSettings,
+    /// Goals tracked over each capture session
+    #[serde(default)]
+    pub session_goals: Vec<SessionGoal>,
+    /// Pace nudges comparing the session with previous ones
+    #[serde(default)]
+    pub coach: CoachSettings,
+    /// Times when only critical tips are shown
+    #[serde(default)]
+    pub quiet_hours: QuietHoursSettings,
+    /// Proxy, certificates and mirrors for downloads and telemetry
+    #[serde(default)]
+    pub network: NetworkSettings,
+    /// Whole subsystems that can be switched off
+    #[serde(default)]
+    pub features: FeatureFlags,
 }
 
 /// General application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GeneralConfig {
     /// Start minimized to tray
     pub start_minimized: bool,
@@ -48,30 +79,281 @@ impl Default for GeneralConfig {
 
 /// Capture-related settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CaptureSettings {
     /// Target window title (partial match) or empty for primary monitor
     pub target_window: Option<String>,
+    /// Program whose main window is captured instead of matching `target_window`
+    /// by title (e.g. "game.exe"), so a recreated window or restarted game is
+    /// picked up again
+    pub target_executable: Option<String>,
     /// Maximum capture FPS
     pub max_fps: u32,
     /// Capture cursor in frames
     pub capture_cursor: bool,
     /// Draw border around captured window
     pub draw_border: bool,
+    /// What to do with the overlay when the captured game closes
+    pub on_game_exit: GameExitAction,
+    /// Windows and monitor regions captured together, side by side
+    pub composite_sources: Vec<CompositeSource>,
+    /// Capture the composite sources instead of `target_window`
+    pub use_composite: bool,
+    /// Capture the built-in demo game instead of a real one
+    pub use_demo: bool,
+    /// Windows and monitors captured next to the main target, each with its
+    /// own frames
+    pub extra_sources: Vec<ExtraCaptureSource>,
+    /// Keep frames on the GPU and download only the regions that are read,
+    /// instead of every whole frame (saves memory bandwidth at 4K)
+    pub gpu_frames: bool,
+    /// Pixel format frames are captured in
+    pub color_format: FrameColorFormat,
+    /// Windows' SDR content brightness in nits, which HDR frames are
+    /// tone-mapped to white at
+    pub hdr_white_nits: f32,
+    /// Factor frames are shrunk by as they're captured (0.5 = half the width
+    /// and height), for big HUD text that reads as well at a lower resolution
+    pub capture_scale: f32,
+    /// Crop window captures to the client area (no title bar or borders), so
+    /// zones line up between windowed and borderless modes
+    pub client_area_only: bool,
+    /// Whether window captures take in the game's other windows
+    pub secondary_windows: SecondaryWindowMode,
+    /// Last seconds of frames kept in memory to save on request
+    pub replay: ReplaySettings,
+}
+
+/// Smallest capture scale offered
+pub const MIN_CAPTURE_SCALE: f32 = 0.25;
+
+/// Replay buffer: the last seconds of capture, shrunk, kept in memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplaySettings {
+    /// Keep a replay buffer while capturing
+    pub enabled: bool,
+    /// Seconds of capture kept
+    pub seconds: u32,
+    /// Frames kept per second
+    pub fps: u32,
+    /// Frames wider than this are shrunk to it (pixels)
+    pub max_width: u32,
+    /// How replays are saved
+    pub format: ReplayFormat,
+}
+
+impl Default for ReplaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seconds: 15,
+            fps: 10,
+            max_width: 640,
+            format: ReplayFormat::default(),
+        }
+    }
+}
+
+/// File format replays are saved in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayFormat {
+    /// H.264 video
+    #[default]
+    Mp4,
+    /// A folder with one PNG image per frame
+    PngSequence,
+}
+
+impl ReplayFormat {
+    /// All formats, for pickers
+    pub const ALL: [ReplayFormat; 2] = [ReplayFormat::Mp4, ReplayFormat::PngSequence];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReplayFormat::Mp4 => "MP4 video",
+            ReplayFormat::PngSequence => "PNG images",
+        }
+    }
+}
+
+/// Window or monitor captured alongside the main target (e.g. a map tool on
+/// a second monitor)
+///
+/// Its frames aren't combined with the main target's; zones that name the
+/// source are read from its frames instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtraCaptureSource {
+    /// Name zones subscribe to the source by, e.g. "map"
+    pub id: String,
+    /// What to capture
+    pub target: CompositeTarget,
+    /// Maximum frames per second captured from the source
+    #[serde(default = "default_extra_source_fps")]
+    pub max_fps: u32,
+}
+
+fn default_extra_source_fps() -> u32 {
+    5
+}
+
+/// One window or monitor region of a composite capture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompositeSource {
+    /// Name zones refer to the source by, e.g. "game" or "map"
+    pub id: String,
+    /// What to capture
+    pub target: CompositeTarget,
+    /// Part to keep (x, y, width, height as 0.0-1.0), all of it if None
+    #[serde(default)]
+    pub region: Option<(f32, f32, f32, f32)>,
+}
+
+/// Window or monitor of a composite source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositeTarget {
+    /// Window by title (partial match)
+    Window(String),
+    /// Monitor by index
+    Monitor(usize),
+}
+
+impl CompositeTarget {
+    /// Display name
+    pub fn label(&self) -> String {
+        match self {
+            CompositeTarget::Window(title) => title.clone(),
+            CompositeTarget::Monitor(index) => format!("Monitor {}", index),
+        }
+    }
+}
+
+/// Pixel format frames are captured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameColorFormat {
+    /// 8-bit color, right for SDR games
+    #[default]
+    Bgra8,
+    /// HDR, tone-mapped to SDR, for games running in HDR whose 8-bit
+    /// capture comes out washed out
+    Rgba16f,
+    /// Grayscale, for a quarter of the memory when only templates and
+    /// screens are matched
+    Gray8,
+}
+
+impl FrameColorFormat {
+    /// All formats, for pickers
+    pub const ALL: [FrameColorFormat; 3] = [
+        FrameColorFormat::Bgra8,
+        FrameColorFormat::Rgba16f,
+        FrameColorFormat::Gray8,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            FrameColorFormat::Bgra8 => "Standard (8-bit)",
+            FrameColorFormat::Rgba16f => "HDR (16-bit float)",
+            FrameColorFormat::Gray8 => "Grayscale (8-bit)",
+        }
+    }
+}
+
+/// Whether a window capture takes in the other windows of the game, such as
+/// dialogs and detached tool windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecondaryWindowMode {
+    /// Whatever Windows does by default
+    #[default]
+    Default,
+    /// Draw the game's other windows into the frame over the main one
+    Include,
+    /// Only the main window
+    Exclude,
+}
+
+impl SecondaryWindowMode {
+    /// All modes, for pickers
+    pub const ALL: [SecondaryWindowMode; 3] = [
+        SecondaryWindowMode::Default,
+        SecondaryWindowMode::Include,
+        SecondaryWindowMode::Exclude,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SecondaryWindowMode::Default => "Windows default",
+            SecondaryWindowMode::Include => "Include",
+            SecondaryWindowMode::Exclude => "Exclude",
+        }
+    }
+}
+
+/// What happens to the overlay when the captured game closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameExitAction {
+    /// Keep the overlay and show a summary tip of the session on the desktop
+    #[default]
+    ShowSummary,
+    /// Stop the overlay
+    StopOverlay,
+    /// Leave the overlay as it is
+    KeepOverlay,
+}
+
+impl GameExitAction {
+    /// All actions, for pickers
+    pub const ALL: [GameExitAction; 3] = [
+        GameExitAction::ShowSummary,
+        GameExitAction::StopOverlay,
+        GameExitAction::KeepOverlay,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameExitAction::ShowSummary => "Show a summary",
+            GameExitAction::StopOverlay => "Stop the overlay",
+            GameExitAction::KeepOverlay => "Keep the overlay",
+        }
+    }
 }
 
 impl Default for CaptureSettings {
     fn default() -> Self {
         Self {
             target_window: None,
+            target_executable: None,
             max_fps: 30,
             capture_cursor: false,
             draw_border: false,
+            on_game_exit: GameExitAction::default(),
+            composite_sources: Vec::new(),
+            use_composite: false,
+            use_demo: false,
+            extra_sources: Vec::new(),
+            gpu_frames: false,
+            color_format: FrameColorFormat::Bgra8,
+            hdr_white_nits: 200.0,
+            capture_scale: 1.0,
+            client_area_only: false,
+            secondary_windows: SecondaryWindowMode::default(),
+            replay: ReplaySettings::default(),
         }
     }
 }
 
 /// Overlay-related settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OverlaySettings {
     /// Overlay enabled
     pub enabled: bool,
@@ -86,6 +368,18 @@ pub struct OverlaySettings {
     /// Hotkey to enter zone selection mode (e.g., "Ctrl+Shift+Z")
     #[serde(default = "default_zone_selection_hotkey")]
     pub zone_selection_hotkey: Option<String>,
+    /// Hotkey to OCR the whole screen into a copyable text panel (e.g., "Ctrl+Shift+R")
+    #[serde(default = "default_read_screen_hotkey")]
+    pub read_screen_hotkey: Option<String>,
+    /// Hotkey to tick the next item of the pinned checklist (e.g., "Ctrl+Shift+C")
+    #[serde(default = "default_checklist_hotkey")]
+    pub checklist_hotkey: Option<String>,
+    /// Hotkey to save the replay buffer (e.g., "Ctrl+Shift+S")
+    #[serde(default = "default_replay_hotkey")]
+    pub replay_hotkey: Option<String>,
+    /// Hotkey to save a screenshot of the next frame (e.g., "Ctrl+Shift+P")
+    #[serde(default = "default_screenshot_hotkey")]
+    pub screenshot_hotkey: Option<String>,
     /// Position offset from anchor corner (x, y)
     #[serde(default = "default_overlay_offset")]
     pub offset: (i32, i32),
@@ -107,6 +401,58 @@ pub struct OverlaySettings {
     /// Whether clicks pass through the overlay
     #[serde(default = "default_click_through")]
     pub click_through: bool,
+    /// Test tips saved in the tip designer
+    #[serde(default)]
+    pub tip_favorites: Vec<TipDesign>,
+    /// Where tips go by priority when the overlay isn't running
+    #[serde(default)]
+    pub tip_fallback: TipFallbackRoutes,
+    /// Move the overlay to another monitor while the game runs in exclusive
+    /// full screen, which hides it
+    #[serde(default = "default_second_monitor_mode")]
+    pub second_monitor_mode: bool,
+}
+
+/// A test tip composed in the Overlay view's tip designer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TipDesign {
+    /// Name the design is saved under as a favorite
+    pub name: String,
+    /// Message (may use `{name}` placeholders of profile variables)
+    pub message: String,
+    /// Priority level (higher = more important)
+    pub priority: u32,
+    /// How long the tip shows in milliseconds (None = until dismissed)
+    pub duration_ms: Option<u64>,
+    /// Symbol shown before the message (empty = none)
+    pub icon: String,
+    /// Whether the tip plays a sound
+    pub play_sound: bool,
+}
+
+impl Default for TipDesign {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            message: "This is a test tip from GamersToolKit!".to_string(),
+            priority: 50,
+            duration_ms: Some(default_tip_duration()),
+            icon: String::new(),
+            play_sound: false,
+        }
+    }
+}
+
+impl TipDesign {
+    /// Message as shown, with the icon in front
+    pub fn full_message(&self) -> String {
+        if self.icon.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} {}", self.icon, self.message)
+        }
+    }
 }
 
 /// Anchor corner for overlay positioning
@@ -120,6 +466,54 @@ pub enum OverlayAnchor {
     BottomRight,
 }
 
+/// What happens to a tip the overlay can't show (overlay disabled, failed
+/// to start, or hidden behind a game in exclusive full screen)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TipFallback {
+    /// Drop the tip
+    #[default]
+    Drop,
+    /// Show the tip as a Windows toast notification
+    Toast,
+}
+
+impl TipFallback {
+    /// All fallbacks, in display order
+    pub const ALL: [TipFallback; 2] = [TipFallback::Drop, TipFallback::Toast];
+
+    /// Get the display name for this fallback
+    pub fn name(&self) -> &'static str {
+        match self {
+            TipFallback::Drop => "Drop",
+            TipFallback::Toast => "Windows toast",
+        }
+    }
+}
+
+/// Fallback of tips the overlay can't show, per priority band
+/// (the bands tips are styled by: 0-25, 26-50, 51-75, 76+)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TipFallbackRoutes {
+    pub low: TipFallback,
+    pub medium: TipFallback,
+    pub high: TipFallback,
+    pub critical: TipFallback,
+}
+
+impl TipFallbackRoutes {
+    /// Fallback of a tip with the given priority
+    pub fn route(&self, priority: u32) -> TipFallback {
+        match priority {
+            0..=25 => self.low,
+            26..=50 => self.medium,
+            51..=75 => self.high,
+            _ => self.critical,
+        }
+    }
+}
+
 fn default_overlay_offset() -> (i32, i32) {
     (20, 20)
 }
@@ -140,10 +534,30 @@ fn default_click_through() -> bool {
     true
 }
 
+fn default_second_monitor_mode() -> bool {
+    true
+}
+
 fn default_zone_selection_hotkey() -> Option<String> {
     Some("Ctrl+Shift+Z".to_string())
 }
 
+fn default_read_screen_hotkey() -> Option<String> {
+    Some("Ctrl+Shift+R".to_string())
+}
+
+fn default_checklist_hotkey() -> Option<String> {
+    Some("Ctrl+Shift+C".to_string())
+}
+
+fn default_replay_hotkey() -> Option<String> {
+    Some("Ctrl+Shift+S".to_string())
+}
+
+fn default_screenshot_hotkey() -> Option<String> {
+    Some("Ctrl+Shift+P".to_string())
+}
+
 impl Default for OverlaySettings {
     fn default() -> Self {
         Self {
@@ -153,6 +567,10 @@ impl Default for OverlaySettings {
             sound_volume: 0.7,
             toggle_hotkey: Some("F9".to_string()),
             zone_selection_hotkey: default_zone_selection_hotkey(),
+            read_screen_hotkey: default_read_screen_hotkey(),
+            checklist_hotkey: default_checklist_hotkey(),
+            replay_hotkey: default_replay_hotkey(),
+            screenshot_hotkey: default_screenshot_hotkey(),
             offset: default_overlay_offset(),
             anchor: OverlayAnchor::default(),
             max_tips: default_max_tips(),
@@ -160,6 +578,9 @@ impl Default for OverlaySettings {
             max_width: default_max_width(),
             monitor_index: Some(0),
             click_through: default_click_through(),
+            tip_favorites: Vec::new(),
+            tip_fallback: TipFallbackRoutes::default(),
+            second_monitor_mode: default_second_monitor_mode(),
         }
     }
 }
@@ -189,23 +610,96 @@ pub fn save_window_state(state: &WindowState, path: &std::path::Path) -> Result<
     Ok(())
 }
 
+/// Per-view dashboard state for persistence
+///
+/// Where the user left off in each view. Scroll offsets, open sections and
+/// panel widths are keyed by a stable name such as `screens.tree`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct UiState {
+    /// Screen selected in the screen tree
+    pub selected_screen_id: Option<String>,
+    /// Profile selected in the profiles view
+    pub selected_profile_id: Option<String>,
+    /// Expanded settings section, as in links (e.g. `quiet-hours`)
+    pub settings_section: Option<String>,
+    /// Vertical scroll offsets
+    pub scroll_offsets: BTreeMap<String, f32>,
+    /// Whether collapsible sections are open
+    pub open_sections: BTreeMap<String, bool>,
+    /// Widths of resizable panels
+    pub panel_widths: BTreeMap<String, f32>,
+}
+
+/// Load per-view UI state from file
+pub fn load_ui_state(path: &std::path::Path) -> Option<UiState> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+}
+
+/// Save per-view UI state to file
+pub fn save_ui_state(state: &UiState, path: &std::path::Path) -> Result<()> {
+    let content = toml::to_string_pretty(state)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
 /// Performance-related settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PerformanceConfig {
     /// Maximum CPU usage percentage
     pub max_cpu_percent: u32,
+    /// Lower the capture frame rate while over `max_cpu_percent`
+    pub adaptive_capture_fps: bool,
     /// Maximum memory usage in MB
     pub max_memory_mb: u32,
     /// Reduce activity when game is in menu/pause
     pub idle_optimization: bool,
+    /// Pause zone OCR and screen rules after this many minutes without input
+    /// or change on screen (0 = never)
+    pub afk_pause_minutes: u32,
+    /// Run capture and vision at below-normal thread priority
+    pub low_priority_workers: bool,
+    /// Switch to Windows' efficiency mode while a full-screen app is in front
+    pub efficiency_mode_fullscreen: bool,
+    /// Switch to the low-power preset below while on battery
+    pub battery_saver: bool,
+    /// Capture frame rate limit on battery
+    pub battery_max_fps: u32,
+    /// Time between zone OCR passes on battery (milliseconds)
+    pub battery_ocr_interval_ms: u32,
+    /// Turn off screen recognition (template matching) on battery
+    pub battery_disable_template_matching: bool,
+    /// Load PaddleOCR in the background at startup when it's the OCR backend
+    pub warm_up_models: bool,
+    /// Unload PaddleOCR after this many minutes unused (0 = keep it loaded)
+    pub model_idle_unload_minutes: u32,
+    /// Drop tips whose screen data is older than this (milliseconds, 0 = never)
+    pub tip_latency_budget_ms: u32,
+    /// Skip OCR of zones whose pixels didn't change since they were last read
+    pub skip_unchanged_zones: bool,
 }
 
 impl Default for PerformanceConfig {
     fn default() -> Self {
         Self {
             max_cpu_percent: 10,
+            adaptive_capture_fps: true,
             max_memory_mb: 512,
             idle_optimization: true,
+            afk_pause_minutes: 5,
+            low_priority_workers: false,
+            efficiency_mode_fullscreen: false,
+            battery_saver: true,
+            battery_max_fps: 10,
+            battery_ocr_interval_ms: 1000,
+            battery_disable_template_matching: true,
+            warm_up_models: true,
+            model_idle_unload_minutes: 15,
+            tip_latency_budget_ms: 1500,
+            skip_unchanged_zones: true,
         }
     }
 }
@@ -232,6 +726,16 @@ pub struct VisionSettings {
     /// Image preprocessing settings for OCR
     #[serde(default)]
     pub preprocessing: OcrPreprocessing,
+    /// Match screen templates on the GPU (falls back to the CPU when unavailable)
+    #[serde(default)]
+    pub use_gpu: bool,
+    /// GPU for PaddleOCR (DirectML) and template matching, by adapter name
+    /// (`None` = Windows' default)
+    #[serde(default)]
+    pub gpu_adapter: Option<String>,
+    /// PaddleOCR accuracy vs speed
+    #[serde(default)]
+    pub quality: crate::vision::OcrQuality,
 }
 
 /// Image preprocessing options for OCR
@@ -255,6 +759,9 @@ pub struct OcrPreprocessing {
     /// Upscale factor for small text (1 = no scaling, 2-4 recommended for small text)
     #[serde(default = "default_scale")]
     pub scale: u32,
+    /// Binarization threshold on luminance (0 = off); pixels above become white
+    #[serde(default)]
+    pub threshold: u8,
 }
 
 fn default_contrast() -> f32 {
@@ -274,6 +781,7 @@ impl Default for OcrPreprocessing {
             sharpen: 0.0,
             invert: false,
             scale: 1,
+            threshold: 0,
         }
     }
 }
@@ -295,6 +803,9 @@ impl Default for VisionSettings {
             show_bounding_boxes: default_show_bounding_boxes(),
             auto_run_ocr: false,
             preprocessing: OcrPreprocessing::default(),
+            use_gpu: false,
+            gpu_adapter: None,
+            quality: crate::vision::OcrQuality::default(),
         }
     }
 }
@@ -308,6 +819,9 @@ pub struct DashboardSettings {
     /// Last active profile ID
     #[serde(default)]
     pub active_profile_id: Option<String>,
+    /// App version whose release notes were last shown
+    #[serde(default)]
+    pub last_seen_version: Option<String>,
 }
 
 /// Dashboard view for persistence (mirrors DashboardView enum)
@@ -320,6 +834,7 @@ pub enum DashboardViewSetting {
     Overlay,
     Vision,
     Profiles,
+    Data,
     Settings,
 }
 
@@ -328,15 +843,183 @@ impl Default for DashboardSettings {
         Self {
             last_view: DashboardViewSetting::default(),
             active_profile_id: Some("default".to_string()),
+            last_seen_version: None,
+        }
+    }
+}
+
+/// Opt-in anonymous telemetry settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    /// Send anonymous usage counts and crash reports (off unless the user opts in)
+    pub enabled: bool,
+    /// Random install ID generated on opt-in
+    pub install_id: Option<String>,
+    /// Endpoint reports are posted to (kept locally when unset)
+    pub endpoint: Option<String>,
+}
+
+/// Proxy and certificates for outgoing HTTP requests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// Proxy for all requests, e.g. "http://proxy.corp:8080"
+    /// (`None` = the HTTPS_PROXY/HTTP_PROXY environment variables)
+    pub proxy_url: Option<String>,
+    /// PEM file with extra root certificates, e.g. a corporate CA
+    pub ca_bundle: Option<PathBuf>,
+    /// Base URLs of model mirrors laid out like the Hugging Face repo, tried
+    /// in order before Hugging Face itself
+    pub model_mirrors: Vec<String>,
+}
+
+/// Playtime reminders and limits (all off by default)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WellnessSettings {
+    /// Remind to take a break every this many minutes of play (0 = off)
+    pub break_reminder_minutes: u32,
+    /// Show a banner on the overlay that can't be dismissed after this many
+    /// minutes of continuous play, until a break is taken (0 = off)
+    pub overlay_nag_minutes: u32,
+}
+
+/// Pace nudges of coach mode (off by default)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoachSettings {
+    /// Compare the session's pace with previous sessions on the overlay
+    /// every this many minutes while capturing (0 = off)
+    pub nudge_minutes: u32,
+    /// Difference from the average, in percent, worth a nudge
+    pub nudge_threshold_percent: u32,
+}
+
+impl Default for CoachSettings {
+    fn default() -> Self {
+        Self {
+            nudge_minutes: 0,
+            nudge_threshold_percent: 20,
+        }
+    }
+}
+
+/// Subsystems that can be switched off entirely (all on by default)
+///
+/// For low-end machines, and for finding out which part uses the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeatureFlags {
+    /// Overlay window (tips follow the overlay fallback when off)
+    pub overlay: bool,
+    /// PaddleOCR and its ONNX models (Windows OCR only when off)
+    pub ai: bool,
+    /// Windows notifications and watching for games to launch
+    pub integrations: bool,
+    /// MCP server (`--mcp`)
+    pub server: bool,
+    /// Screen recognition by template matching
+    pub template_matching: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            overlay: true,
+            ai: true,
+            integrations: true,
+            server: true,
+            template_matching: true,
+        }
+    }
+}
+
+impl FeatureFlags {
+    /// Names of the subsystems switched off, for status text
+    pub fn disabled(&self) -> Vec<&'static str> {
+        [
+            (self.overlay, "overlay"),
+            (self.ai, "AI models"),
+            (self.integrations, "integrations"),
+            (self.server, "MCP server"),
+            (self.template_matching, "template matching"),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| !enabled)
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
+/// A goal for a capture session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionGoal {
+    /// Unique identifier
+    pub id: String,
+    /// What the goal is, e.g. "Dungeon clears"
+    pub name: String,
+    /// How the goal is measured
+    pub kind: SessionGoalKind,
+}
+
+/// How a session goal is measured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionGoalKind {
+    /// Play at most this many minutes
+    MaxPlaytime { minutes: u32 },
+    /// A live value (a watch variable such as `zone.Level`) reaches a number
+    Reach { variable: String, target: f64 },
+    /// A counter ticked from the dashboard reaches a count
+    Count { target: u32 },
+}
+
+impl SessionGoalKind {
+    /// Display name of this kind of goal
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionGoalKind::MaxPlaytime { .. } => "Play at most",
+            SessionGoalKind::Reach { .. } => "Reach",
+            SessionGoalKind::Count { .. } => "Count to",
+        }
+    }
+}
+
+/// Times when only critical tips are shown
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursSettings {
+    /// Keep quiet on the schedule below
+    pub enabled: bool,
+    /// Hour (local time, 0-23) quiet hours start
+    pub start_hour: u8,
+    /// Hour (local time, 0-23) quiet hours end; before the start = the next day
+    pub end_hour: u8,
+    /// Days (Monday first) on which quiet hours start
+    pub days: [bool; 7],
+    /// Also keep quiet while Windows reports presentation mode or quiet time
+    pub follow_windows: bool,
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_hour: 22,
+            end_hour: 8,
+            days: [true; 7],
+            follow_windows: false,
         }
     }
 }
 
 /// Load configuration from file
+///
+/// Any unknown key or invalid value is an error; use
+/// [`load_config_with_mode`] to get the issues alongside the config instead.
 pub fn load_config(path: &Path) -> Result<AppConfig> {
-    let content = std::fs::read_to_string(path)?;
-    let config: AppConfig = toml::from_str(&content)?;
-    Ok(config)
+    Ok(load_config_with_mode(path, ConfigParseMode::Strict)?.config)
 }
 
 /// Save configuration to file
@@ -418,6 +1101,93 @@ mod tests {
         assert!((parsed.overlay.opacity - 0.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_composite_sources_roundtrip() {
+        let mut config = AppConfig::default();
+        config.capture.use_composite = true;
+        config.capture.composite_sources = vec![
+            CompositeSource {
+                id: "game".to_string(),
+                target: CompositeTarget::Window("Flight Simulator".to_string()),
+                region: None,
+            },
+            CompositeSource {
+                id: "map".to_string(),
+                target: CompositeTarget::Monitor(1),
+                region: Some((0.5, 0.0, 0.5, 1.0)),
+            },
+        ];
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&toml_str).unwrap();
+
+        assert!(parsed.capture.use_composite);
+        assert_eq!(
+            parsed.capture.composite_sources,
+            config.capture.composite_sources
+        );
+    }
+
+    #[test]
+    fn test_feature_flags() {
+        assert!(AppConfig::default().features.disabled().is_empty());
+
+        let parsed: AppConfig = toml::from_str(
+            r#"
+            [features]
+            overlay = false
+            template_matching = false
+            "#,
+        )
+        .unwrap();
+        assert!(!parsed.features.overlay);
+        assert!(parsed.features.ai);
+        assert_eq!(parsed.features.disabled(), ["overlay", "template matching"]);
+    }
+
+    #[test]
+    fn test_tip_design() {
+        let parsed: OverlaySettings = toml::from_str(
+            r#"
+            [[tip_favorites]]
+            name = "Low health"
+            message = "Heal up"
+            icon = "⚠"
+            "#,
+        )
+        .unwrap();
+
+        let design = &parsed.tip_favorites[0];
+        assert_eq!(design.priority, 50);
+        assert_eq!(design.duration_ms, Some(5000));
+        assert_eq!(design.full_message(), "⚠ Heal up");
+        assert_eq!(
+            TipDesign::default().full_message(),
+            TipDesign::default().message
+        );
+    }
+
+    #[test]
+    fn test_extra_sources_default_fps() {
+        let parsed: CaptureSettings = toml::from_str(
+            r#"
+            [[extra_sources]]
+            id = "map"
+            target = { monitor = 1 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.extra_sources,
+            [ExtraCaptureSource {
+                id: "map".to_string(),
+                target: CompositeTarget::Monitor(1),
+                max_fps: 5,
+            }]
+        );
+    }
+
     #[test]
     fn test_save_and_load_config() {
         let config = AppConfig::default();
@@ -436,6 +1206,31 @@ mod tests {
         assert_eq!(config.capture.max_fps, loaded.capture.max_fps);
     }
 
+    #[test]
+    fn test_ui_state_roundtrip() {
+        let mut state = UiState {
+            selected_screen_id: Some("shop".to_string()),
+            settings_section: Some("quiet-hours".to_string()),
+            ..Default::default()
+        };
+        state
+            .scroll_offsets
+            .insert("screens.tree".to_string(), 120.0);
+        state
+            .open_sections
+            .insert("profiles.variables".to_string(), true);
+        state.panel_widths.insert("screens.tree".to_string(), 310.0);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        save_ui_state(&state, temp_file.path()).unwrap();
+        assert_eq!(load_ui_state(temp_file.path()), Some(state));
+
+        // Older or partial files fill in the rest
+        let partial: UiState = toml::from_str("selected_profile_id = \"p1\"").unwrap();
+        assert_eq!(partial.selected_profile_id.as_deref(), Some("p1"));
+        assert!(partial.scroll_offsets.is_empty());
+    }
+
     #[test]
     fn test_load_config_file_not_found() {
         let result = load_config(Path::new("/nonexistent/path/config.toml"));
@@ -455,13 +1250,42 @@ mod tests {
     fn test_capture_settings_clone() {
         let settings = CaptureSettings {
             target_window: Some("Test".to_string()),
+            target_executable: None,
             max_fps: 60,
             capture_cursor: true,
             draw_border: true,
+            on_game_exit: GameExitAction::StopOverlay,
+            composite_sources: Vec::new(),
+            use_composite: false,
+            use_demo: false,
+            extra_sources: Vec::new(),
+            gpu_frames: false,
+            color_format: FrameColorFormat::Bgra8,
+            hdr_white_nits: 200.0,
+            capture_scale: 0.5,
+            client_area_only: false,
+            secondary_windows: SecondaryWindowMode::Include,
+            replay: ReplaySettings::default(),
         };
 
         let cloned = settings.clone();
         assert_eq!(settings.target_window, cloned.target_window);
         assert_eq!(settings.max_fps, cloned.max_fps);
     }
+
+    #[test]
+    fn test_tip_fallback_routes() {
+        let routes = TipFallbackRoutes {
+            critical: TipFallback::Toast,
+            ..Default::default()
+        };
+
+        assert_eq!(routes.route(75), TipFallback::Drop);
+        assert_eq!(routes.route(76), TipFallback::Toast);
+        assert_eq!(routes.route(u32::MAX), TipFallback::Toast);
+
+        let parsed: TipFallbackRoutes = toml::from_str("high = \"toast\"").unwrap();
+        assert_eq!(parsed.route(60), TipFallback::Toast);
+        assert_eq!(parsed.route(10), TipFallback::Drop);
+    }
 }
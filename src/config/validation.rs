@@ -0,0 +1,352 @@
+//! Config validation
+//!
+//! Parses config.toml while collecting diagnostics for unknown keys and invalid
+//! values, so a single bad entry no longer discards the whole file.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+use super::AppConfig;
+
+/// How strictly to treat problems in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigParseMode {
+    /// Any unknown key or invalid value is an error
+    Strict,
+    /// Unknown keys are ignored and invalid values fall back to their defaults
+    #[default]
+    Lenient,
+}
+
+/// Kind of problem found in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigIssueKind {
+    /// The file is not valid TOML
+    Syntax,
+    /// A key that the application does not know about
+    UnknownField,
+    /// A known key with a value of the wrong type or out of range
+    InvalidValue,
+}
+
+impl ConfigIssueKind {
+    /// Get the display name for this kind
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigIssueKind::Syntax => "Syntax error",
+            ConfigIssueKind::UnknownField => "Unknown key",
+            ConfigIssueKind::InvalidValue => "Invalid value",
+        }
+    }
+}
+
+/// A problem found while parsing the config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Kind of problem
+    pub kind: ConfigIssueKind,
+    /// Dotted key path (e.g. "overlay.opacity"), empty for syntax errors
+    pub key: String,
+    /// 1-based line number, if it could be located
+    pub line: Option<usize>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        if self.key.is_empty() {
+            write!(f, "{}: {}", self.kind.name(), self.message)
+        } else {
+            write!(f, "{} `{}`: {}", self.kind.name(), self.key, self.message)
+        }
+    }
+}
+
+/// Result of parsing a config file
+#[derive(Debug, Clone)]
+pub struct ConfigLoad {
+    /// The parsed config (with defaults substituted for invalid values)
+    pub config: AppConfig,
+    /// Problems found while parsing
+    pub issues: Vec<ConfigIssue>,
+}
+
+/// Parse config file contents, collecting diagnostics
+///
+/// In strict mode any issue is returned as an error. In lenient mode invalid
+/// values are replaced by their defaults, unknown keys are ignored, and the
+/// issues are returned alongside the config.
+pub fn parse_config(content: &str, mode: ConfigParseMode) -> Result<ConfigLoad> {
+    let mut issues = Vec::new();
+
+    let mut table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(e) => {
+            let line = e.span().map(|span| line_of_offset(content, span.start));
+            issues.push(ConfigIssue {
+                kind: ConfigIssueKind::Syntax,
+                key: String::new(),
+                line,
+                message: e.message().to_string(),
+            });
+            return finish(AppConfig::default(), issues, mode);
+        }
+    };
+
+    migrate_legacy_keys(&mut table, content, &mut issues);
+
+    // Drop invalid values one at a time until the rest deserializes
+    loop {
+        let value = toml::Value::Table(table.clone());
+        let err = match serde_path_to_error::deserialize::<_, AppConfig>(value) {
+            Ok(_) => break,
+            Err(err) => err,
+        };
+
+        let mut key_path = Vec::new();
+        for segment in err.path().iter() {
+            match segment {
+                serde_path_to_error::Segment::Map { key } => key_path.push(key.clone()),
+                _ => break,
+            }
+        }
+
+        let message = err.into_inner().message().to_string();
+        if !remove_key(&mut table, &key_path) {
+            // Nothing left to remove, give up on the file
+            issues.push(ConfigIssue {
+                kind: ConfigIssueKind::InvalidValue,
+                key: key_path.join("."),
+                line: find_key_line(content, &key_path),
+                message,
+            });
+            return finish(AppConfig::default(), issues, mode);
+        }
+
+        issues.push(ConfigIssue {
+            kind: ConfigIssueKind::InvalidValue,
+            key: key_path.join("."),
+            line: find_key_line(content, &key_path),
+            message: format!("{} (using default)", message),
+        });
+    }
+
+    let mut unknown = Vec::new();
+    let config = AppConfig::deserialize(serde_ignored::Deserializer::new(
+        toml::Value::Table(table),
+        &mut |path| unknown.push(path.to_string()),
+    ))?;
+
+    for key in unknown {
+        let key_path: Vec<String> = key.split('.').map(str::to_string).collect();
+        issues.push(ConfigIssue {
+            kind: ConfigIssueKind::UnknownField,
+            line: find_key_line(content, &key_path),
+            key,
+            message: "not a recognized setting (ignored)".to_string(),
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+
+    finish(config, issues, mode)
+}
+
+/// Rewrite keys of older versions in their current form
+///
+/// `capture.hdr` and `capture.grayscale` became `capture.color_format`. Both
+/// set at once is reported and HDR is used.
+fn migrate_legacy_keys(table: &mut toml::Table, content: &str, issues: &mut Vec<ConfigIssue>) {
+    let Some(toml::Value::Table(capture)) = table.get_mut("capture") else {
+        return;
+    };
+    let (hdr, grayscale) = (capture.remove("hdr"), capture.remove("grayscale"));
+    if hdr.is_none() && grayscale.is_none() {
+        return;
+    }
+    let hdr = hdr.and_then(|v| v.as_bool()).unwrap_or(false);
+    let grayscale = grayscale.and_then(|v| v.as_bool()).unwrap_or(false);
+
+    if hdr && grayscale {
+        let key_path = ["capture".to_string(), "grayscale".to_string()];
+        issues.push(ConfigIssue {
+            kind: ConfigIssueKind::InvalidValue,
+            key: key_path.join("."),
+            line: find_key_line(content, &key_path),
+            message: "conflicts with `capture.hdr` (using HDR)".to_string(),
+        });
+    }
+    if capture.contains_key("color_format") {
+        return;
+    }
+    let format = if hdr {
+        "rgba16f"
+    } else if grayscale {
+        "gray8"
+    } else {
+        "bgra8"
+    };
+    capture.insert("color_format".to_string(), format.into());
+}
+
+/// Load and parse a config file, collecting diagnostics
+pub fn load_config_with_mode(path: &Path, mode: ConfigParseMode) -> Result<ConfigLoad> {
+    let content = std::fs::read_to_string(path)?;
+    parse_config(&content, mode)
+}
+
+fn finish(
+    config: AppConfig,
+    issues: Vec<ConfigIssue>,
+    mode: ConfigParseMode,
+) -> Result<ConfigLoad> {
+    if mode == ConfigParseMode::Strict && !issues.is_empty() {
+        let details: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+        bail!("Invalid config:\n  {}", details.join("\n  "));
+    }
+    Ok(ConfigLoad { config, issues })
+}
+
+/// Remove a key from a nested table, returning whether anything was removed
+fn remove_key(table: &mut toml::Table, key_path: &[String]) -> bool {
+    match key_path {
+        [] => false,
+        [key] => table.remove(key).is_some(),
+        [first, rest @ ..] => match table.get_mut(first) {
+            Some(toml::Value::Table(inner)) => remove_key(inner, rest),
+            _ => false,
+        },
+    }
+}
+
+/// 1-based line number of a byte offset
+fn line_of_offset(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].lines().count().max(1)
+}
+
+/// Find the line on which a dotted key is assigned
+///
+/// Handles keys under `[section]` headers and dotted keys at the top level.
+fn find_key_line(content: &str, key_path: &[String]) -> Option<usize> {
+    if key_path.is_empty() {
+        return None;
+    }
+    let dotted = key_path.join(".");
+    let mut current = String::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !trimmed.starts_with("[[") {
+            current = trimmed
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            continue;
+        }
+
+        let Some((lhs, _)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let lhs: String = lhs
+            .split('.')
+            .map(|part| part.trim().trim_matches('"'))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let full = if current.is_empty() {
+            lhs
+        } else {
+            format!("{}.{}", current, lhs)
+        };
+        if full == dotted {
+            return Some(index + 1);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        let content = toml::to_string_pretty(&AppConfig::default()).unwrap();
+        let load = parse_config(&content, ConfigParseMode::Strict).unwrap();
+        assert!(load.issues.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_value_keeps_rest_of_file() {
+        let content = "[capture]\nmax_fps = 60\n\n[overlay]\nopacity = \"high\"\n";
+        let load = parse_config(content, ConfigParseMode::Lenient).unwrap();
+
+        assert_eq!(load.config.capture.max_fps, 60);
+        assert!((load.config.overlay.opacity - 0.9).abs() < 0.01);
+        assert_eq!(load.issues.len(), 1);
+        assert_eq!(load.issues[0].kind, ConfigIssueKind::InvalidValue);
+        assert_eq!(load.issues[0].key, "overlay.opacity");
+        assert_eq!(load.issues[0].line, Some(5));
+    }
+
+    #[test]
+    fn test_unknown_field_reported_with_line() {
+        let content = "[general]\nstart_minimised = true\n";
+        let load = parse_config(content, ConfigParseMode::Lenient).unwrap();
+
+        assert_eq!(load.issues.len(), 1);
+        assert_eq!(load.issues[0].kind, ConfigIssueKind::UnknownField);
+        assert_eq!(load.issues[0].key, "general.start_minimised");
+        assert_eq!(load.issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_issues() {
+        let content = "[general]\nstart_minimised = true\n";
+        assert!(parse_config(content, ConfigParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn test_legacy_color_format_keys_migrate() {
+        use crate::config::FrameColorFormat;
+
+        let content = "[capture]\nhdr = true\nhdr_white_nits = 240.0\n";
+        let load = parse_config(content, ConfigParseMode::Strict).unwrap();
+        assert_eq!(load.config.capture.color_format, FrameColorFormat::Rgba16f);
+        assert!((load.config.capture.hdr_white_nits - 240.0).abs() < 0.01);
+
+        let content = "[capture]\nhdr = false\ngrayscale = true\n";
+        let load = parse_config(content, ConfigParseMode::Strict).unwrap();
+        assert_eq!(load.config.capture.color_format, FrameColorFormat::Gray8);
+
+        // Both at once can't be honored
+        let content = "[capture]\nhdr = true\ngrayscale = true\n";
+        assert!(parse_config(content, ConfigParseMode::Strict).is_err());
+        let load = parse_config(content, ConfigParseMode::Lenient).unwrap();
+        assert_eq!(load.config.capture.color_format, FrameColorFormat::Rgba16f);
+        assert_eq!(load.issues.len(), 1);
+        assert_eq!(load.issues[0].key, "capture.grayscale");
+        assert_eq!(load.issues[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_syntax_error_falls_back_in_lenient_mode() {
+        let content = "[capture]\nmax_fps = \n";
+        let load = parse_config(content, ConfigParseMode::Lenient).unwrap();
+
+        assert_eq!(load.issues.len(), 1);
+        assert_eq!(load.issues[0].kind, ConfigIssueKind::Syntax);
+        assert_eq!(load.issues[0].line, Some(2));
+        assert_eq!(load.config.capture.max_fps, 30);
+    }
+}
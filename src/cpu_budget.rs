@@ -0,0 +1,200 @@
+//! CPU budget
+//!
+//! Enforces the maximum CPU usage from the performance settings. The
+//! process's CPU time is sampled every few seconds; while usage is over the
+//! budget (a busy fight means more frames to read) the capture frame rate is
+//! lowered step by step, and once usage has dropped well below the budget it
+//! is raised back towards `max_fps`.
+
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::FILETIME;
+use windows::Win32::System::Threading::{GetCurrentProcess, GetProcessTimes};
+
+/// Lowest frame rate the budget lowers capture to
+const MIN_FPS: u32 = 2;
+/// Factor the frame rate is lowered by while over budget
+const LOWER_FACTOR: f32 = 0.75;
+/// Factor the frame rate is raised by while well under budget
+const RAISE_FACTOR: f32 = 1.25;
+/// Share of the budget usage must be under before the frame rate is raised,
+/// so it doesn't go back and forth around the limit
+const RAISE_BELOW: f32 = 0.7;
+
+/// Measured CPU usage and the frame rate limit it led to
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuBudgetStatus {
+    /// CPU usage of the process in percent of all cores, once measured
+    pub cpu_percent: Option<f32>,
+    /// Capture frame rate limit keeping usage under budget, if one is needed
+    pub fps_limit: Option<u32>,
+}
+
+/// Samples the process's CPU usage and adapts the capture frame rate limit
+#[derive(Debug, Clone, Default)]
+pub struct CpuBudget {
+    /// When the last sample was taken and the process's CPU time then
+    last_sample: Option<(Instant, Duration)>,
+    status: CpuBudgetStatus,
+}
+
+impl CpuBudget {
+    /// Take a sample and adapt the frame rate limit to `max_cpu_percent`
+    ///
+    /// The first sample only measures; usage is known from the second on.
+    pub fn sample(&mut self, max_cpu_percent: u32, max_fps: u32) -> CpuBudgetStatus {
+        let Some(cpu_time) = process_cpu_time() else {
+            return self.status;
+        };
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get() as u32);
+        self.sample_at(Instant::now(), cpu_time, cores, max_cpu_percent, max_fps)
+    }
+
+    fn sample_at(
+        &mut self,
+        now: Instant,
+        cpu_time: Duration,
+        cores: u32,
+        max_cpu_percent: u32,
+        max_fps: u32,
+    ) -> CpuBudgetStatus {
+        let Some((then, then_cpu_time)) = self.last_sample.replace((now, cpu_time)) else {
+            return self.status;
+        };
+        let wall = now.duration_since(then);
+        if wall.is_zero() {
+            return self.status;
+        }
+
+        let cpu_percent = cpu_time.saturating_sub(then_cpu_time).as_secs_f32()
+            / wall.as_secs_f32()
+            / cores.max(1) as f32
+            * 100.0;
+        self.status = CpuBudgetStatus {
+            cpu_percent: Some(cpu_percent),
+            fps_limit: next_limit(self.status.fps_limit, cpu_percent, max_cpu_percent, max_fps),
+        };
+        self.status
+    }
+
+    /// Drop the frame rate limit, e.g. when the budget is turned off
+    pub fn reset(&mut self) {
+        self.status.fps_limit = None;
+    }
+
+    /// Latest usage and frame rate limit
+    pub fn status(&self) -> CpuBudgetStatus {
+        self.status
+    }
+}
+
+/// Frame rate limit after a sample of `cpu_percent`
+fn next_limit(
+    current: Option<u32>,
+    cpu_percent: f32,
+    max_cpu_percent: u32,
+    max_fps: u32,
+) -> Option<u32> {
+    let fps = current.unwrap_or(max_fps).min(max_fps).max(1);
+    let budget = max_cpu_percent as f32;
+
+    if cpu_percent > budget {
+        Some(((fps as f32 * LOWER_FACTOR) as u32).clamp(MIN_FPS.min(fps), fps))
+    } else if current.is_some() && cpu_percent < budget * RAISE_BELOW {
+        let raised = ((fps as f32 * RAISE_FACTOR) as u32).max(fps + 1);
+        (raised < max_fps).then_some(raised)
+    } else {
+        current.map(|_| fps)
+    }
+}
+
+/// CPU time (kernel and user) the process has used so far
+fn process_cpu_time() -> Option<Duration> {
+    let mut creation = FILETIME::default();
+    let mut exit = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    // SAFETY: the pseudo handle of the current process is always valid and
+    // GetProcessTimes only fills in the structs it's given
+    match unsafe {
+        GetProcessTimes(
+            GetCurrentProcess(),
+            &mut creation,
+            &mut exit,
+            &mut kernel,
+            &mut user,
+        )
+    } {
+        Ok(()) => Some(filetime_duration(kernel) + filetime_duration(user)),
+        Err(e) => {
+            tracing::debug!("Process CPU time unavailable: {}", e);
+            None
+        }
+    }
+}
+
+/// Duration of a `FILETIME` interval (100 ns units)
+fn filetime_duration(time: FILETIME) -> Duration {
+    let ticks = (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64;
+    Duration::from_nanos(ticks * 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_follows_usage() {
+        // Over budget: lowered step by step, but not below the minimum
+        assert_eq!(next_limit(None, 15.0, 10, 60), Some(45));
+        assert_eq!(next_limit(Some(45), 12.0, 10, 60), Some(33));
+        assert_eq!(next_limit(Some(2), 30.0, 10, 60), Some(2));
+
+        // Near the budget: kept where it is
+        assert_eq!(next_limit(Some(33), 9.0, 10, 60), Some(33));
+        assert_eq!(next_limit(None, 9.0, 10, 60), None);
+
+        // Well under budget: raised until the limit is gone
+        assert_eq!(next_limit(Some(33), 5.0, 10, 60), Some(41));
+        assert_eq!(next_limit(Some(2), 5.0, 10, 60), Some(3));
+        assert_eq!(next_limit(Some(50), 5.0, 10, 60), None);
+
+        // A lower max_fps caps the current limit
+        assert_eq!(next_limit(Some(45), 9.0, 10, 30), Some(30));
+    }
+
+    #[test]
+    fn test_sampled_usage() {
+        let mut budget = CpuBudget::default();
+        let start = Instant::now();
+
+        // The first sample only measures
+        let status = budget.sample_at(start, Duration::from_secs(10), 4, 10, 60);
+        assert_eq!(status, CpuBudgetStatus::default());
+
+        // 0.6 s of CPU time in 2 s on 4 cores is 7.5%, within budget
+        let status = budget.sample_at(
+            start + Duration::from_secs(2),
+            Duration::from_millis(10_600),
+            4,
+            10,
+            60,
+        );
+        assert!((status.cpu_percent.unwrap() - 7.5).abs() < 0.01);
+        assert_eq!(status.fps_limit, None);
+
+        // 2 s of CPU time in 2 s on 4 cores is 25%
+        let status = budget.sample_at(
+            start + Duration::from_secs(4),
+            Duration::from_millis(12_600),
+            4,
+            10,
+            60,
+        );
+        assert!((status.cpu_percent.unwrap() - 25.0).abs() < 0.01);
+        assert_eq!(status.fps_limit, Some(45));
+
+        budget.reset();
+        assert_eq!(budget.status().fps_limit, None);
+    }
+}
@@ -3,26 +3,83 @@
 
 use eframe::egui;
 use parking_lot::{Mutex, RwLock};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::analysis::afk::{self, AfkDetector};
+use crate::analysis::checklist::{self, ChecklistContext};
+use crate::analysis::coach;
+use crate::analysis::goals::SessionGoals;
+use crate::analysis::latency;
+use crate::analysis::phases::{self, PhaseTracker};
+use crate::analysis::quiet_hours;
+use crate::analysis::rules::{GameState, Rule, ScreenContext};
+use crate::analysis::stepper::{RuleStep, StepArtifacts, ZoneStep};
+use crate::analysis::template::fill_template;
+use crate::analysis::watch::{self, WatchValues};
+use crate::analysis::wellness::{format_playtime, PlaySession, WellnessEvent};
 use crate::analysis::Tip;
-use crate::capture::{CaptureTarget, ScreenCapture};
-use crate::config::WindowState;
-use crate::dashboard::components::render_sidebar;
-use crate::dashboard::state::ZoneOcrResult;
-use crate::dashboard::state::{AutoConfigureStep, DashboardState, DashboardView};
+use crate::capture::process_watcher::{self, GameLaunch, ProcessWatcher, WatchedGame};
+use crate::capture::replay::{self, ReplayBuffer};
+use crate::capture::{composite, elevation};
+use crate::capture::{
+    CaptureColorFormat, CaptureConfig, CaptureTarget, FrameConsumer, FrameSource, GpuFrame,
+    ScreenCapture,
+};
+use crate::config::{GameExitAction, TipFallback, UiState, WindowState, MIN_CAPTURE_SCALE};
+use crate::cpu_budget::CpuBudget;
+use crate::dashboard::changelog;
+use crate::dashboard::components::remembered;
+use crate::dashboard::components::{
+    render_issue_reporter, render_pipeline_debug, render_reference_panel, render_screen_text,
+    render_sidebar, render_validation_report, render_watch_panel, render_whats_new,
+    ScreenTextAction, ValidationAction, WhatsNewAction,
+};
+use crate::dashboard::state::{AutoConfigureStep, DashboardState, DashboardView, ScreenTextState};
+use crate::dashboard::state::{
+    OcrResultDisplay, SampleAction, SettingsSection, ValidationState, VisionViewState,
+    ZoneOcrResult,
+};
 use crate::dashboard::theme;
 use crate::dashboard::views::{
-    render_capture_view, render_home_view, render_overlay_view, render_profiles_view,
-    render_screens_view, render_settings_view, render_vision_view,
+    render_capture_view, render_data_view, render_home_view, render_overlay_view,
+    render_profiles_view, render_screens_view, render_settings_view, render_vision_view,
 };
 use crate::hotkey::HotkeyManager;
-use crate::overlay::{OverlayManager, ZoneSelectionResult};
-use crate::shared::SharedAppState;
-use crate::storage::profiles::{ContentType, GameProfile};
-use crate::vision::{ModelManager, ModelType, ScreenRecognizer, VisionPipeline};
+use crate::overlay::{toast, ChecklistDisplay, OverlayManager, ZoneSelectionResult};
+use crate::power;
+use crate::qos::{self, QosStatus};
+use crate::shared::state::FullscreenGame;
+use crate::shared::{AppError, SharedAppState};
+use crate::storage::backups::{BackupReason, ProfileBackup};
+use crate::storage::database::{Database, TipOrigin, ZoneReading};
+use crate::storage::labels;
+use crate::storage::profiles::{
+    Checklist, GameProfile, LabeledRegion, ProfileSections, RecognitionDictionary,
+};
+use crate::storage::rule_library::{effective_rules, LibraryRule};
+use crate::storage::screenshots;
+use crate::storage::share::{unique_id, SharedItem};
+use crate::storage::snapshot;
+use crate::storage::writer::{SaveTarget, StorageWriter};
+use crate::telemetry::Feature;
+use crate::vision::backend_compare::BackendComparison;
+use crate::vision::batch::BatchRun;
+use crate::vision::calibration;
+use crate::vision::color_trigger;
+use crate::vision::ensemble::{self, Reading};
+use crate::vision::gpu_adapters::{self, GpuAdapter};
+use crate::vision::scene::SceneAnalyzer;
+use crate::vision::screen_recognition::text_similarity;
+use crate::vision::scroll_stitch;
+use crate::vision::template_cache::TemplateCache;
+use crate::vision::validation::{validate_sample, SampleValidation};
+use crate::vision::zone_stats::ZoneOcrStats;
+use crate::vision::zone_text::{filter_zone_text, is_new_value, zone_reading};
+use crate::vision::{
+    ModelIntegrity, ModelManager, ModelType, ModelVariant, ScreenRecognizer, VisionPipeline,
+};
 use std::thread::JoinHandle;
 
 /// The main dashboard application
@@ -37,6 +94,15 @@ pub struct DashboardApp {
     capture_manager: Arc<Mutex<Option<ScreenCapture>>>,
     /// Frame counter for FPS calculation
     frame_counter: FrameCounter,
+    /// Latest frame kept on the GPU, when capture keeps frames there
+    latest_gpu_frame: Option<Arc<GpuFrame>>,
+    /// Last whole download of a GPU frame (for triggers, scene and screenshots)
+    last_frame_download: Option<Instant>,
+    /// The last seconds of capture, kept to save on request
+    replay: ReplayBuffer,
+    /// Screenshot to save of the next captured frame, with the file to save
+    /// it to (None inside = a new timestamped file)
+    pending_screenshot: Option<Option<PathBuf>>,
     /// Overlay manager
     overlay_manager: Option<Arc<OverlayManager>>,
     /// Overlay thread handle
@@ -49,18 +115,36 @@ pub struct DashboardApp {
     config_dir: Option<PathBuf>,
     /// Last time config was auto-saved
     last_auto_save: Instant,
+    /// Whether config.toml as loaded was kept as config.toml.bak
+    config_backed_up: bool,
     /// Whether there are pending changes to save
     pending_save: bool,
+    /// Writes config and profile saves off the UI thread
+    storage_writer: StorageWriter,
     /// Last saved window state (for change detection)
     last_window_state: Option<WindowState>,
     /// Last time window state was saved
     last_window_save: Instant,
+    /// Per-view UI state loaded at startup, handed to egui on the first frame
+    pending_ui_state: Option<UiState>,
+    /// Last saved per-view UI state (for change detection)
+    last_ui_state: Option<UiState>,
+    /// Last time per-view UI state was saved
+    last_ui_save: Instant,
     /// Vision pipeline for OCR
     vision_pipeline: Option<VisionPipeline>,
     /// Model manager for downloading OCR models
     model_manager: Option<ModelManager>,
     /// Profiles directory path for saving
     profiles_dir: Option<PathBuf>,
+    /// Directory for profile backups taken before destructive changes
+    backups_dir: Option<PathBuf>,
+    /// Rules library directory
+    rules_dir: Option<PathBuf>,
+    /// Database for sessions, events and zone readings
+    database: Option<Database>,
+    /// Database ID of this dashboard session
+    session_id: Option<i64>,
     /// Currently active profile
     active_profile: Option<GameProfile>,
     /// Last time profile labels were auto-saved
@@ -73,6 +157,77 @@ pub struct DashboardApp {
     screen_recognizer: ScreenRecognizer,
     /// Last time screen recognition was run
     last_screen_check: Instant,
+    /// Scene brightness tracker (dark flag with hysteresis)
+    scene_analyzer: SceneAnalyzer,
+    /// Unix time the app started (for checklists reset on app start)
+    session_start: u64,
+    /// Last time checklists were reset and auto-checked
+    last_checklist_check: Instant,
+    /// Checklist last shown on the overlay (for change detection)
+    last_synced_checklist: Option<ChecklistDisplay>,
+    /// Game phase of the current match
+    phase_tracker: PhaseTracker,
+    /// Last time the game phase was checked
+    last_phase_check: Instant,
+    /// Last time watched variables were refreshed
+    last_watch_check: Instant,
+    /// Last time OCR text was matched against labeled regions and their
+    /// values read
+    last_label_check: Instant,
+    /// Continuous play session (for break reminders)
+    play_session: PlaySession,
+    /// Last time playtime was tracked
+    last_wellness_check: Instant,
+    /// Capture time not yet added to the playtime stats
+    unrecorded_playtime: Duration,
+    /// Playtime banner last shown on the overlay (for change detection)
+    last_synced_nag: Option<String>,
+    /// Last time quiet hours were checked
+    last_quiet_check: Option<Instant>,
+    /// Last time thread and process priorities were checked
+    last_qos_check: Option<Instant>,
+    /// Low priority setting applied to this (vision) thread
+    applied_low_priority: Option<bool>,
+    /// Whether the process is in efficiency mode
+    efficiency_mode: bool,
+    /// Last time the power source was checked
+    last_power_check: Option<Instant>,
+    /// CPU usage sampling, lowering the capture frame rate when over budget
+    cpu_budget: CpuBudget,
+    /// Last time CPU usage was sampled
+    last_cpu_check: Option<Instant>,
+    /// Whether the player is away, from input and the captured picture
+    afk_detector: AfkDetector,
+    /// Last time the session goals took in live values
+    last_goal_check: Option<Instant>,
+    /// Last coach mode pace nudge, or when capture started (None while idle)
+    last_coach_nudge: Option<Instant>,
+    /// Last time the player was checked for being away
+    last_afk_check: Option<Instant>,
+    /// Last zone OCR pass (throttled while saving power)
+    last_zone_ocr: Option<Instant>,
+    /// Last check that the extra sources captured match the configured ones
+    /// and the active profile's secondary windows
+    last_source_check: Option<Instant>,
+    /// Extra sources the capture was last synced to
+    synced_sources: Vec<crate::config::ExtraCaptureSource>,
+    /// Last time both OCR backends read the previewed frame
+    last_backend_compare: Option<Instant>,
+    /// Last time the tip designer's repeating tip was sent
+    last_repeated_test_tip: Option<Instant>,
+    /// Whether the startup PaddleOCR warm-up was considered
+    warm_up_checked: bool,
+    /// Last time PaddleOCR was checked for being idle
+    last_idle_model_check: Option<Instant>,
+    /// Watches for games of auto-start profiles (started once one exists)
+    process_watcher: Option<ProcessWatcher>,
+    /// Games the watcher was last given
+    watched_games: Vec<WatchedGame>,
+    /// Launched game waiting for its window, and when it was detected
+    pending_game_launch: Option<(GameLaunch, Instant)>,
+    /// When capture started and how many tips were shown before, for the
+    /// summary when the game closes
+    capture_session_start: Option<(Instant, usize)>,
 }
 
 /// Helper for calculating FPS
@@ -95,28 +250,38 @@ impl Default for FrameCounter {
 impl DashboardApp {
     /// Create a new dashboard application
     pub fn new(shared_state: Arc<RwLock<SharedAppState>>) -> Self {
-        // Initialize hotkey manager
-        let hotkey_manager = match HotkeyManager::new(shared_state.clone()) {
-            Ok(mut manager) => {
-                if let Err(e) = manager.register_toggle_hotkey() {
-                    tracing::warn!("Failed to register toggle hotkey: {}", e);
-                }
-                if let Err(e) = manager.register_zone_selection_hotkey() {
-                    tracing::warn!("Failed to register zone selection hotkey: {}", e);
-                }
-                Some(manager)
-            }
-            Err(e) => {
-                tracing::warn!("Failed to create hotkey manager: {}", e);
-                None
-            }
+        let safe_mode = shared_state.read().runtime.safe_mode;
+
+        // Initialize hotkey manager (skipped in safe mode, hotkeys can start the overlay)
+        let hotkey_manager = if safe_mode {
+            None
+        } else {
+            Self::create_hotkey_manager(&shared_state)
         };
 
+        // Check system capabilities in the background
+        Self::list_gpu_adapters(&shared_state);
+        Self::start_capability_checks(&shared_state);
+
         // Get config directory for saving
         let config_dir = crate::storage::get_config_dir().ok();
 
         // Get profiles directory
         let profiles_dir = crate::storage::get_profiles_dir().ok();
+        let backups_dir = crate::storage::get_backups_dir().ok();
+        let rules_dir = crate::storage::get_rules_dir().ok();
+
+        // Open the database and record this session
+        let database = match crate::storage::get_database_path().and_then(|p| Database::open(&p)) {
+            Ok(db) => Some(db),
+            Err(e) => {
+                tracing::warn!("Failed to open database: {}", e);
+                None
+            }
+        };
+        let session_id = database
+            .as_ref()
+            .and_then(|db| db.start_session("dashboard").ok());
 
         // Initialize model manager
         let model_manager = ModelManager::new().ok();
@@ -143,17 +308,36 @@ impl DashboardApp {
             }
         }
 
+        // Load the rules library profiles attach rules from
+        if let Some(ref dir) = rules_dir {
+            match crate::storage::rule_library::load_library(dir) {
+                Ok(rules) => {
+                    tracing::info!("Loaded {} library rules from disk", rules.len());
+                    shared_state.write().rule_library = rules;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load rules library: {}", e);
+                }
+            }
+        }
+
         // Load or create profile based on saved active_profile_id
-        let (active_profile, initial_zones) = Self::load_profile_by_id(
-            &profiles_dir,
-            dashboard_settings.active_profile_id.as_deref(),
-        );
+        let (active_profile, initial_zones) = if safe_mode {
+            tracing::info!("Safe mode: skipping profile auto-activation");
+            (None, vec![])
+        } else {
+            Self::load_profile_by_id(
+                &profiles_dir,
+                dashboard_settings.active_profile_id.as_deref(),
+            )
+        };
 
         // Initialize screen recognizer and load screens from profile
         let mut screen_recognizer = ScreenRecognizer::new();
         if let Some(ref profile) = active_profile {
+            screen_recognizer.set_dictionary(profile.dictionary.clone());
             if !profile.screens.is_empty() {
-                screen_recognizer.load_screens(profile.screens.clone());
+                load_profile_screens(&mut screen_recognizer, profile);
                 tracing::info!(
                     "Loaded {} screens for recognition from profile '{}'",
                     profile.screens.len(),
@@ -177,9 +361,65 @@ impl DashboardApp {
         dashboard_state.vision.show_bounding_boxes = vision_settings.show_bounding_boxes;
         dashboard_state.vision.auto_run_ocr = vision_settings.auto_run_ocr;
         dashboard_state.vision.preprocessing = vision_settings.preprocessing.clone();
+        dashboard_state.vision.ocr_quality = vision_settings.quality;
 
-        // Load zones from profile into vision state
+        // Load zones and labeled regions from profile into vision state
         dashboard_state.vision.ocr_zones = initial_zones;
+        dashboard_state.vision.session_id = session_id;
+        if let Some(ref profile) = active_profile {
+            Self::load_labeled_regions(
+                &mut dashboard_state.vision,
+                profile.labeled_regions.clone(),
+            );
+        }
+
+        // Catch models left truncated or damaged since they were downloaded
+        if let Some(ref manager) = model_manager {
+            dashboard_state.vision.corrupted_models = Self::corrupted_models(manager);
+        }
+
+        // Show release notes for versions the user has not seen yet
+        if dashboard_settings.last_seen_version.as_deref() != Some(env!("CARGO_PKG_VERSION")) {
+            match changelog::releases() {
+                Ok(releases) => {
+                    let unseen = changelog::unseen_releases(
+                        releases,
+                        dashboard_settings.last_seen_version.as_deref(),
+                        env!("CARGO_PKG_VERSION"),
+                    );
+                    if !unseen.is_empty() {
+                        dashboard_state.whats_new = Some(unseen);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load changelog: {}", e),
+            }
+        }
+
+        shared_state.write().runtime.recent_screenshots = crate::storage::get_screenshots_dir()
+            .map(|dir| screenshots::list_screenshots(&dir, screenshots::RECENT_SCREENSHOTS))
+            .unwrap_or_default();
+
+        // Restore where each view was left last session
+        let ui_state = config_dir
+            .as_ref()
+            .and_then(|dir| crate::config::load_ui_state(&dir.join("ui_state.toml")));
+        if let Some(ref ui_state) = ui_state {
+            let state = shared_state.read();
+            dashboard_state.profiles.selected_profile_id = ui_state
+                .selected_profile_id
+                .clone()
+                .filter(|id| state.profiles.iter().any(|p| &p.id == id));
+            dashboard_state.screens.selected_screen_id =
+                ui_state.selected_screen_id.clone().filter(|id| {
+                    state
+                        .active_profile()
+                        .is_some_and(|p| p.screens.iter().any(|s| &s.id == id))
+                });
+            dashboard_state.settings.expanded_section = ui_state
+                .settings_section
+                .as_deref()
+                .and_then(SettingsSection::from_key);
+        }
 
         tracing::info!(
             "Restored settings: view={:?}, backend={:?}, granularity={:?}",
@@ -188,30 +428,221 @@ impl DashboardApp {
             dashboard_state.vision.ocr_granularity
         );
 
+        let replay = ReplayBuffer::new(&shared_state.read().config.capture.replay);
+
         Self {
             shared_state,
             dashboard_state,
             theme_applied: false,
             capture_manager: Arc::new(Mutex::new(None)),
             frame_counter: FrameCounter::default(),
+            latest_gpu_frame: None,
+            last_frame_download: None,
+            replay,
+            pending_screenshot: None,
             overlay_manager: None,
             overlay_handle: None,
             last_synced_overlay_config: None,
             hotkey_manager,
             config_dir,
+            config_backed_up: false,
             last_auto_save: Instant::now(),
             pending_save: false,
+            storage_writer: StorageWriter::spawn(),
             last_window_state: None,
             last_window_save: Instant::now(),
+            pending_ui_state: ui_state.clone(),
+            last_ui_state: ui_state,
+            last_ui_save: Instant::now(),
             vision_pipeline: None,
             model_manager,
             profiles_dir,
+            backups_dir,
+            rules_dir,
+            database,
+            session_id,
             active_profile,
             last_profile_save: Instant::now(),
             last_synced_vision: Some(vision_settings),
             last_synced_view: Some(DashboardView::from_setting(dashboard_settings.last_view)),
             screen_recognizer,
             last_screen_check: Instant::now(),
+            scene_analyzer: SceneAnalyzer::default(),
+            session_start: checklist::unix_time(),
+            last_checklist_check: Instant::now(),
+            last_synced_checklist: None,
+            phase_tracker: PhaseTracker::default(),
+            last_phase_check: Instant::now(),
+            last_watch_check: Instant::now(),
+            last_label_check: Instant::now(),
+            play_session: PlaySession::default(),
+            last_wellness_check: Instant::now(),
+            unrecorded_playtime: Duration::ZERO,
+            last_synced_nag: None,
+            last_quiet_check: None,
+            last_qos_check: None,
+            applied_low_priority: None,
+            efficiency_mode: false,
+            last_power_check: None,
+            cpu_budget: CpuBudget::default(),
+            last_cpu_check: None,
+            afk_detector: AfkDetector::default(),
+            last_goal_check: None,
+            last_coach_nudge: None,
+            last_afk_check: None,
+            last_zone_ocr: None,
+            last_source_check: None,
+            synced_sources: Vec::new(),
+            last_backend_compare: None,
+            last_repeated_test_tip: None,
+            warm_up_checked: false,
+            last_idle_model_check: None,
+            process_watcher: None,
+            watched_games: Vec::new(),
+            pending_game_launch: None,
+            capture_session_start: None,
+        }
+    }
+
+    /// Open the "What's New" panel when requested from the home view
+    fn process_whats_new_request(&mut self) {
+        if !std::mem::take(&mut self.dashboard_state.home.open_whats_new) {
+            return;
+        }
+
+        match changelog::releases() {
+            Ok(releases) => self.dashboard_state.whats_new = Some(releases),
+            Err(e) => self.report_error(AppError::Storage(e.to_string())),
+        }
+    }
+
+    /// Close the "What's New" panel, following a deep link if one was clicked
+    fn close_whats_new(&mut self, action: WhatsNewAction) {
+        self.dashboard_state.whats_new = None;
+
+        if let WhatsNewAction::Open(link) = action {
+            self.dashboard_state.current_view = link.view;
+            if link.section.is_some() {
+                self.dashboard_state.settings.expanded_section = link.section;
+            }
+        }
+
+        let mut state = self.shared_state.write();
+        let current = env!("CARGO_PKG_VERSION");
+        if state.config.dashboard.last_seen_version.as_deref() != Some(current) {
+            state.config.dashboard.last_seen_version = Some(current.to_string());
+            self.pending_save = true;
+        }
+    }
+
+    /// Run the system capability checks on a background thread
+    fn start_capability_checks(shared_state: &Arc<RwLock<SharedAppState>>) {
+        let overlay_running = {
+            let mut state = shared_state.write();
+            state.runtime.capability_checks = None;
+            state.runtime.is_overlay_running
+        };
+
+        let shared_state = shared_state.clone();
+        std::thread::spawn(move || {
+            let checks = crate::diagnostics::run_all_checks(overlay_running);
+            for check in &checks {
+                tracing::info!(
+                    "{} check: {:?} - {}",
+                    check.name,
+                    check.status,
+                    check.detail
+                );
+            }
+            shared_state.write().runtime.capability_checks = Some(checks);
+        });
+    }
+
+    /// List the GPUs for the settings, before anything loads on one
+    fn list_gpu_adapters(shared_state: &Arc<RwLock<SharedAppState>>) {
+        let adapters = gpu_adapters::list_adapters().unwrap_or_else(|e| {
+            tracing::warn!("Failed to list GPUs: {:#}", e);
+            Vec::new()
+        });
+        for adapter in &adapters {
+            tracing::info!("GPU {}: {}", adapter.index, adapter.label());
+        }
+        shared_state.write().runtime.gpu_adapters = adapters;
+    }
+
+    /// GPU chosen in the settings, if it's present
+    fn selected_gpu_adapter(shared_state: &Arc<RwLock<SharedAppState>>) -> Option<GpuAdapter> {
+        let shared = shared_state.read();
+        let name = shared.config.vision.gpu_adapter.as_deref()?;
+        gpu_adapters::find_adapter(&shared.runtime.gpu_adapters, name).cloned()
+    }
+
+    /// Create a vision pipeline that loads PaddleOCR on the chosen GPU
+    fn create_vision_pipeline(
+        shared_state: &Arc<RwLock<SharedAppState>>,
+    ) -> anyhow::Result<VisionPipeline> {
+        let mut pipeline = VisionPipeline::new()?;
+        pipeline.set_gpu_device_id(Self::selected_gpu_adapter(shared_state).map(|a| a.index));
+        pipeline.set_quality(shared_state.read().config.vision.quality);
+        Ok(pipeline)
+    }
+
+    /// Follow the GPU chosen in the settings
+    ///
+    /// Template matching moves over on its next match; a loaded PaddleOCR
+    /// keeps its GPU until it loads again.
+    fn sync_gpu_adapter(&mut self) {
+        let adapter = Self::selected_gpu_adapter(&self.shared_state);
+        if let Some(ref mut pipeline) = self.vision_pipeline {
+            let device_id = adapter.as_ref().map(|a| a.index);
+            if pipeline.gpu_device_id() != device_id {
+                pipeline.set_gpu_device_id(device_id);
+            }
+        }
+        self.screen_recognizer.set_gpu_adapter(adapter);
+    }
+
+    /// Follow the PaddleOCR quality chosen in the vision view
+    fn sync_ocr_quality(&mut self) {
+        let quality = self.dashboard_state.vision.ocr_quality;
+        if let Some(ref mut pipeline) = self.vision_pipeline {
+            if pipeline.set_quality(quality) {
+                self.dashboard_state.vision.ocr_initialized = false;
+            }
+        }
+    }
+
+    /// Re-run the capability checks if requested from the UI
+    fn process_capability_checks(&mut self) {
+        if std::mem::take(&mut self.shared_state.write().runtime.recheck_capabilities) {
+            Self::list_gpu_adapters(&self.shared_state);
+            Self::start_capability_checks(&self.shared_state);
+        }
+    }
+
+    /// Create the hotkey manager and register the configured hotkeys
+    fn create_hotkey_manager(shared_state: &Arc<RwLock<SharedAppState>>) -> Option<HotkeyManager> {
+        match HotkeyManager::new(shared_state.clone()) {
+            Ok(mut manager) => {
+                for (event, e) in manager.register_all() {
+                    shared_state
+                        .write()
+                        .runtime
+                        .set_error(AppError::Overlay(format!(
+                            "Failed to register {} hotkey: {}",
+                            event.name().to_lowercase(),
+                            e
+                        )));
+                }
+                Some(manager)
+            }
+            Err(e) => {
+                shared_state
+                    .write()
+                    .runtime
+                    .set_error(AppError::Overlay(format!("Hotkeys unavailable: {}", e)));
+                None
+            }
         }
     }
 
@@ -278,10 +709,20 @@ impl DashboardApp {
             ocr_regions: vec![],
             templates: vec![],
             rules: vec![],
+            library_rules: vec![],
             labeled_regions: vec![],
             screens: vec![],
             screen_recognition_enabled: false,
             screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
         };
 
         let default_path = dir.join("default.json");
@@ -317,9 +758,17 @@ impl DashboardApp {
         let profile_name = profile.name.clone();
         let profile_id_owned = profile.id.clone();
 
-        // Update dashboard state with new zones
+        // Update dashboard state with new zones and labels
         self.dashboard_state.vision.ocr_zones = zones;
+        Self::load_labeled_regions(
+            &mut self.dashboard_state.vision,
+            profile.labeled_regions.clone(),
+        );
         self.dashboard_state.vision.zone_ocr_results.clear();
+        self.dashboard_state.vision.zone_ocr_stats.clear();
+        self.dashboard_state.vision.zone_samples.clear();
+        self.dashboard_state.vision.zone_scroll_logs.clear();
+        self.dashboard_state.vision.zone_recorded_values.clear();
         self.dashboard_state.vision.zones_dirty = false;
 
         // Update active profile reference
@@ -339,6 +788,10 @@ impl DashboardApp {
         // Reload screens for screen recognition
         self.reload_screens_from_profile();
 
+        // Phases of the previous profile don't apply
+        self.start_match();
+
+        self.record_event("profile", &format!("Activated profile '{}'", profile_name));
         tracing::info!(
             "Activated profile '{}' with {} zones",
             profile_name,
@@ -364,9 +817,14 @@ impl DashboardApp {
             state.config.dashboard.active_profile_id = None;
         }
 
-        // Clear zones from vision state
+        // Clear zones and labels from vision state
         self.dashboard_state.vision.ocr_zones.clear();
+        Self::load_labeled_regions(&mut self.dashboard_state.vision, vec![]);
         self.dashboard_state.vision.zone_ocr_results.clear();
+        self.dashboard_state.vision.zone_ocr_stats.clear();
+        self.dashboard_state.vision.zone_samples.clear();
+        self.dashboard_state.vision.zone_scroll_logs.clear();
+        self.dashboard_state.vision.zone_recorded_values.clear();
         self.dashboard_state.vision.zones_dirty = false;
 
         // Mark config for save
@@ -381,25 +839,217 @@ impl DashboardApp {
         }
     }
 
+    /// Snapshot a profile's saved file before a destructive change
+    fn backup_profile(&self, profile_id: &str, reason: BackupReason) {
+        let (Some(ref profiles_dir), Some(ref backups_dir)) =
+            (&self.profiles_dir, &self.backups_dir)
+        else {
+            return;
+        };
+        // The file on disk is backed up, so queued saves are written first
+        self.storage_writer.flush();
+
+        match crate::storage::backups::backup_profile(profiles_dir, backups_dir, profile_id, reason)
+        {
+            Ok(Some(path)) => {
+                tracing::info!("Backed up profile '{}' to {:?}", profile_id, path);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to back up profile '{}': {}",
+                    profile_id, e
+                )));
+            }
+        }
+    }
+
+    /// Back up the active profile if zones were deleted since it was last saved
+    fn backup_if_zones_removed(&self) {
+        let Some(ref profile) = self.active_profile else {
+            return;
+        };
+
+        let zones = &self.dashboard_state.vision.ocr_zones;
+        let removed = profile
+            .ocr_regions
+            .iter()
+            .any(|saved| !zones.iter().any(|zone| zone.id == saved.id));
+        if removed {
+            self.backup_profile(&profile.id, BackupReason::DeleteZone);
+        }
+    }
+
+    /// Back up the active profile before saving fewer labeled regions
+    fn backup_if_labels_removed(&self) {
+        let Some(ref profile) = self.active_profile else {
+            return;
+        };
+
+        if self.dashboard_state.vision.labeled_regions.len() < profile.labeled_regions.len() {
+            self.backup_profile(&profile.id, BackupReason::DeleteLabels);
+        }
+    }
+
+    /// Put a profile's labeled regions into the vision state
+    ///
+    /// Labels not tracked yet start counting unused sessions from this one.
+    fn load_labeled_regions(vision: &mut VisionViewState, mut regions: Vec<LabeledRegion>) {
+        let mut stamped = false;
+        if let Some(session_id) = vision.session_id {
+            for region in regions
+                .iter_mut()
+                .filter(|r| r.last_matched_session.is_none())
+            {
+                region.last_matched_session = Some(session_id);
+                stamped = true;
+            }
+        }
+
+        vision.labeled_regions = regions;
+        vision.selected_labels.clear();
+        vision.label_values.clear();
+        vision.relocated_labels.clear();
+        vision.labels_dirty = stamped;
+    }
+
     /// Save current zones to the active profile (helper method)
     fn save_current_zones_to_profile(&mut self) {
+        self.backup_if_zones_removed();
+        self.backup_if_labels_removed();
+
         if let (Some(ref mut profile), Some(ref profiles_dir)) =
             (&mut self.active_profile, &self.profiles_dir)
         {
-            // Update profile with current zones
+            // Update profile with current zones and labels
             profile.ocr_regions = self.dashboard_state.vision.ocr_zones.clone();
+            profile.labeled_regions = self.dashboard_state.vision.labeled_regions.clone();
 
             // Save to disk
             let profile_path = profiles_dir.join(format!("{}.json", profile.id));
-            if let Err(e) = crate::storage::profiles::save_profile(profile, &profile_path) {
-                tracing::error!("Failed to save zones to profile '{}': {}", profile.name, e);
+            self.storage_writer.save_profile(profile, profile_path);
+            tracing::debug!(
+                "Saving {} zones to profile '{}' before switching",
+                profile.ocr_regions.len(),
+                profile.name
+            );
+        }
+    }
+
+    /// Shut down subsystems switched off in the `[features]` settings
+    fn apply_feature_flags(&mut self) {
+        let features = self.shared_state.read().config.features;
+
+        if !features.overlay && self.overlay_manager.is_some() {
+            self.stop_overlay();
+        }
+        let vision = &mut self.dashboard_state.vision;
+        if !features.ai && vision.selected_backend == crate::vision::OcrBackend::PaddleOcr {
+            tracing::info!("AI models are turned off, switching to Windows OCR");
+            vision.selected_backend = crate::vision::OcrBackend::WindowsOcr;
+        }
+        if !features.integrations {
+            // Dropping the watcher stops its thread
+            self.process_watcher = None;
+            self.watched_games.clear();
+            self.pending_game_launch = None;
+        }
+    }
+
+    /// Start capture when the game of an auto-start profile, or of the
+    /// active profile, launches
+    ///
+    /// Activates the profile, waits for the game's window and captures it,
+    /// then starts the overlay if the profile asks for it. The game is only
+    /// the capture target for the session; the saved target is left alone.
+    /// Capture already running is kept unless the profile auto-starts, so a
+    /// window picked by hand sticks.
+    fn process_game_launches(&mut self, ctx: &egui::Context) {
+        const WINDOW_TIMEOUT: Duration = Duration::from_secs(60);
+
+        let Some(games) = watched_games(&self.shared_state.read()) else {
+            return;
+        };
+        if games.is_empty() && self.process_watcher.is_none() {
+            return;
+        }
+
+        let watcher = self.process_watcher.get_or_insert_with(|| {
+            let ctx = ctx.clone();
+            ProcessWatcher::start(move || ctx.request_repaint())
+        });
+        if games != self.watched_games {
+            watcher.set_watched(games.clone());
+            self.watched_games = games;
+        }
+        if let Some(launch) = watcher.try_recv() {
+            self.pending_game_launch = Some((launch, Instant::now()));
+        }
+
+        let Some((ref launch, detected)) = self.pending_game_launch else {
+            return;
+        };
+        let Some(title) = process_watcher::main_window_title(launch.pid) else {
+            if detected.elapsed() > WINDOW_TIMEOUT {
+                tracing::warn!(
+                    "{} has no window after {:?}, not starting capture",
+                    launch.executable,
+                    WINDOW_TIMEOUT
+                );
+                self.pending_game_launch = None;
             } else {
-                tracing::debug!(
-                    "Saved {} zones to profile '{}' before switching",
-                    profile.ocr_regions.len(),
-                    profile.name
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
+            return;
+        };
+        let Some((launch, _)) = self.pending_game_launch.take() else {
+            return;
+        };
+
+        let auto_start = {
+            let state = self.shared_state.read();
+            state
+                .profiles
+                .iter()
+                .find(|p| p.id == launch.profile_id)
+                .map(|p| p.auto_start)
+        };
+        let Some(auto_start) = auto_start else {
+            return;
+        };
+        if !auto_start.capture {
+            let active = self.active_profile.as_ref().map(|p| &p.id) == Some(&launch.profile_id);
+            if !active || self.is_capturing() {
+                return;
+            }
+        }
+
+        if self.active_profile.as_ref().map(|p| &p.id) != Some(&launch.profile_id) {
+            self.activate_profile(&launch.profile_id);
+        }
+
+        if self.is_capturing() {
+            self.stop_capture();
+        }
+        // By process, so capture follows the game's window when it's recreated
+        self.shared_state.write().capture_config.target = CaptureTarget::Process(launch.pid);
+        match self.start_capture() {
+            Ok(()) => {
+                self.record_event(
+                    "capture",
+                    &format!("Auto-started for {} ('{}')", launch.executable, title),
                 );
             }
+            Err(e) => {
+                self.report_error(AppError::Capture(e));
+                return;
+            }
+        }
+
+        if auto_start.overlay && self.shared_state.read().config.features.overlay {
+            if let Err(e) = self.start_overlay() {
+                self.report_error(AppError::Overlay(e));
+            }
         }
     }
 
@@ -407,42 +1057,251 @@ impl DashboardApp {
     pub fn start_capture(&mut self) -> Result<(), String> {
         let config = {
             let state = self.shared_state.read();
-            state.capture_config.clone()
+            CaptureConfig {
+                gpu_frames: state.config.capture.gpu_frames,
+                color_format: CaptureColorFormat::from_settings(&state.config.capture),
+                scale: state
+                    .config
+                    .capture
+                    .capture_scale
+                    .clamp(MIN_CAPTURE_SCALE, 1.0),
+                client_area_only: state.config.capture.client_area_only,
+                secondary_windows: state.config.capture.secondary_windows,
+                ..state.capture_config.clone()
+            }
         };
 
-        let target_name = match &config.target {
-            CaptureTarget::Window(name) => name.clone(),
-            CaptureTarget::PrimaryMonitor => "Primary Monitor".to_string(),
-            CaptureTarget::MonitorIndex(i) => format!("Monitor {}", i),
-        };
+        let target_name = config.target.label();
 
         match ScreenCapture::new(config) {
             Ok(mut capture) => {
+                capture.set_fps_limit(capture_fps_limit(&self.shared_state.read()));
                 if let Err(e) = capture.start() {
                     return Err(format!("Failed to start capture: {}", e));
                 }
+                // The main target is captured even if an extra source fails
+                let extra_sources = self.shared_state.read().capture_sources();
+                let source_error = capture.sync_sources(&extra_sources).err();
+                self.synced_sources = extra_sources;
                 *self.capture_manager.lock() = Some(capture);
+                self.record_event("capture", &format!("Capture started: {}", target_name));
 
                 let mut state = self.shared_state.write();
                 state.runtime.is_capturing = true;
                 state.runtime.current_capture_target = Some(target_name);
                 state.runtime.clear_error();
+                state.runtime.capture_access_denied = None;
                 self.frame_counter = FrameCounter::default();
+                self.replay.clear();
+                self.capture_session_start = Some((Instant::now(), state.runtime.tips_displayed));
+                state.runtime.session_goals =
+                    SessionGoals::start(&state.config.session_goals, Instant::now());
+                drop(state);
+                if let Some(e) = source_error {
+                    self.report_error(AppError::Capture(format!("{:#}", e)));
+                }
+                self.start_match();
                 Ok(())
             }
             Err(e) => Err(format!("Failed to create capture: {}", e)),
         }
     }
 
+    /// Restart as administrator when asked to, so elevated games can be captured
+    fn process_relaunch_request(&mut self, ctx: &egui::Context) {
+        let requested = std::mem::take(&mut self.shared_state.write().runtime.relaunch_elevated);
+        if !requested {
+            return;
+        }
+
+        match elevation::relaunch_elevated() {
+            Ok(()) => {
+                tracing::info!("Restarting as administrator");
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            Err(e) => self.report_error(AppError::Capture(format!("{:#}", e))),
+        }
+    }
+
+    /// Wrap up when the captured game closes
+    ///
+    /// Capture goes back to idle, the playtime is recorded and the session
+    /// logged, then the overlay shows a summary tip or stops, as configured.
+    fn process_game_exit(&mut self) {
+        let closed = self
+            .capture_manager
+            .lock()
+            .as_ref()
+            .is_some_and(|capture| capture.source_closed());
+        if !closed {
+            return;
+        }
+
+        let (target, action, tips_displayed) = {
+            let state = self.shared_state.read();
+            (
+                state
+                    .runtime
+                    .current_capture_target
+                    .clone()
+                    .unwrap_or_default(),
+                state.config.capture.on_game_exit,
+                state.runtime.tips_displayed,
+            )
+        };
+        self.stop_capture();
+
+        // The capture time until now goes into today's playtime
+        self.record_playtime();
+
+        let (played, tips) = self.capture_session_start.take().map_or(
+            (Duration::ZERO, 0),
+            |(started, tips_before)| {
+                (
+                    started.elapsed(),
+                    tips_displayed.saturating_sub(tips_before),
+                )
+            },
+        );
+        let summary = format!(
+            "Game ended: {} closed after {}, {} tip(s) shown",
+            target,
+            format_playtime(played),
+            tips
+        );
+        tracing::info!("{}", summary);
+        self.record_event("capture", &summary);
+
+        match action {
+            GameExitAction::ShowSummary => {
+                self.dispatch_tip(Tip {
+                    id: "game_exit_summary".to_string(),
+                    message: summary,
+                    priority: 40,
+                    duration_ms: Some(10_000),
+                    ..Default::default()
+                });
+            }
+            GameExitAction::StopOverlay => {
+                if self.overlay_manager.is_some() {
+                    self.stop_overlay();
+                }
+            }
+            GameExitAction::KeepOverlay => {}
+        }
+    }
+
     /// Stop screen capture
     pub fn stop_capture(&mut self) {
         if let Some(mut capture) = self.capture_manager.lock().take() {
             let _ = capture.stop();
+            self.record_event("capture", "Capture stopped");
         }
+        self.latest_gpu_frame = None;
 
-        let mut state = self.shared_state.write();
-        state.runtime.is_capturing = false;
-        state.runtime.capture_fps = 0.0;
+        let goals = {
+            let mut state = self.shared_state.write();
+            state.runtime.is_capturing = false;
+            state.runtime.capture_fps = 0.0;
+            state.runtime.dropped_frames = 0;
+            state.runtime.composite_layout = None;
+            state.runtime.cursor = None;
+            state.runtime.session_goals.take()
+        };
+        if let Some(goals) = goals {
+            self.finish_session_goals(goals);
+        }
+    }
+
+    /// Score the session's goals into the session log and a tip
+    fn finish_session_goals(&mut self, mut goals: SessionGoals) {
+        goals.update(&self.watch_values());
+        let now = Instant::now();
+        let scorecard = goals.scorecard(now);
+        tracing::info!("{}", scorecard);
+        self.record_event("goals", &scorecard);
+
+        let all_met = goals.met(now) == goals.goals.len();
+        self.dispatch_tip(Tip {
+            id: "session_scorecard".to_string(),
+            message: scorecard,
+            priority: if all_met { 30 } else { 45 },
+            duration_ms: Some(15_000),
+            ..Default::default()
+        });
+    }
+
+    /// Nudge on the overlay when a zone's pace is off the player's average
+    fn process_coach_nudges(&mut self) {
+        let (capturing, settings) = {
+            let state = self.shared_state.read();
+            (state.runtime.is_capturing, state.config.coach.clone())
+        };
+        if !capturing || settings.nudge_minutes == 0 {
+            self.last_coach_nudge = None;
+            return;
+        }
+        let interval = Duration::from_secs(settings.nudge_minutes as u64 * 60);
+        match self.last_coach_nudge {
+            Some(last) if last.elapsed() >= interval => {}
+            Some(_) => return,
+            None => {
+                self.last_coach_nudge = Some(Instant::now());
+                return;
+            }
+        }
+        self.last_coach_nudge = Some(Instant::now());
+
+        let (Some(db), Some(session_id)) = (&self.database, self.session_id) else {
+            return;
+        };
+        let sessions = match db.recent_session_readings(coach::AVERAGE_SESSIONS + 1) {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                tracing::warn!("Coach mode can't read past sessions: {:#}", e);
+                return;
+            }
+        };
+        // Nothing read yet this session
+        if sessions.first().map(|s| s.session_id) != Some(session_id) {
+            return;
+        }
+
+        // The zone furthest off its average
+        let threshold = settings.nudge_threshold_percent as f64 / 100.0;
+        let nudge = coach::compare(&sessions)
+            .into_iter()
+            .filter_map(|pace| Some((pace.deviation()?.abs(), pace.nudge(threshold)?)))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some((_, message)) = nudge {
+            tracing::info!("Coach: {}", message);
+            self.dispatch_tip(Tip {
+                id: "coach_pace".to_string(),
+                message,
+                priority: 20,
+                duration_ms: Some(8_000),
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Take in the live values the session's goals wait for
+    fn process_session_goals(&mut self) {
+        const GOAL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        if self.shared_state.read().runtime.session_goals.is_none()
+            || self
+                .last_goal_check
+                .is_some_and(|checked| checked.elapsed() < GOAL_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_goal_check = Some(Instant::now());
+
+        let values = self.watch_values();
+        if let Some(ref mut goals) = self.shared_state.write().runtime.session_goals {
+            goals.update(&values);
+        }
     }
 
     /// Update capture FPS by polling for frames
@@ -451,16 +1310,68 @@ impl DashboardApp {
         if let Some(ref capture) = *capture_guard {
             // Try to get frames without blocking to calculate FPS
             let mut latest_frame = None;
+            let replay = self.shared_state.read().config.capture.replay.clone();
+            if capture.keeps_gpu_frames() {
+                // Frames stay on the GPU; the whole frame is downloaded only
+                // every so often (as often as the replay buffer keeps them)
+                let replay_interval = Duration::from_secs(1) / replay.fps.max(1);
+                let download_interval = match Duration::from_millis(500) {
+                    interval if replay.enabled => interval.min(replay_interval),
+                    interval => interval,
+                };
+                let mut arrived = false;
+                while let Some(frame) = capture.try_next_gpu_frame() {
+                    self.frame_counter.frames_this_second += 1;
+                    self.latest_gpu_frame = Some(frame);
+                    arrived = true;
+                }
+                let due = self.pending_screenshot.is_some()
+                    || self
+                        .last_frame_download
+                        .map_or(true, |last| last.elapsed() >= download_interval);
+                if let Some(frame) = self.latest_gpu_frame.as_ref().filter(|_| arrived && due) {
+                    self.last_frame_download = Some(Instant::now());
+                    match frame.download() {
+                        Ok(frame) => latest_frame = Some(Arc::new(frame)),
+                        Err(e) => tracing::warn!("Failed to download frame: {:#}", e),
+                    }
+                }
+            }
+            // Frames the GPU couldn't keep arrive downloaded
             while let Some(frame) = capture.try_next_frame() {
                 self.frame_counter.frames_this_second += 1;
                 latest_frame = Some(frame);
             }
             // Store the most recent frame for MCP screenshot tool
             if let Some(frame) = latest_frame {
-                self.shared_state.write().runtime.last_captured_frame =
-                    Some(std::sync::Arc::new(frame));
-            }
-
+                if let Some(path) = self.pending_screenshot.take() {
+                    save_screenshot_in_background(&self.shared_state, frame.clone(), path);
+                }
+                self.update_color_triggers(&frame);
+                let scene = self.scene_analyzer.analyze(&frame);
+                if replay.enabled && self.replay.push(&frame) {
+                    self.shared_state.write().runtime.replay_buffered =
+                        (self.replay.span().as_secs_f32(), self.replay.memory_bytes());
+                }
+
+                let layout = capture.composite_layout();
+                let mut shared = self.shared_state.write();
+                if shared.runtime.composite_layout != layout {
+                    shared.runtime.composite_layout = layout;
+                }
+                if scene.dark != shared.runtime.scene.dark {
+                    tracing::info!(
+                        "Scene turned {} (brightness {:.0}%)",
+                        if scene.dark { "dark" } else { "light" },
+                        scene.brightness * 100.0
+                    );
+                }
+                shared.runtime.scene = scene;
+                shared.runtime.cursor = frame.cursor;
+                shared.runtime.latency.frame_pickup.record(frame.age());
+                shared.runtime.last_captured_frame = Some(frame);
+            }
+
             // Update FPS every second
             let elapsed = self.frame_counter.last_fps_update.elapsed();
             if elapsed.as_secs_f32() >= 1.0 {
@@ -472,14 +1383,198 @@ impl DashboardApp {
                 // Update shared state
                 let mut state = self.shared_state.write();
                 state.runtime.capture_fps = self.frame_counter.current_fps;
+                state.runtime.dropped_frames = capture.dropped_frames();
             }
 
             // Check if capture is still running
             if !capture.is_running() {
+                let access_denied = capture.access_denied();
                 drop(capture_guard);
                 self.stop_capture();
+                if access_denied {
+                    let target = {
+                        let mut state = self.shared_state.write();
+                        state.runtime.capture_access_denied =
+                            state.runtime.current_capture_target.clone();
+                        state.runtime.current_capture_target.clone()
+                    };
+                    self.report_error(AppError::Capture(format!(
+                        "Can't capture '{}': it runs as administrator",
+                        target.unwrap_or_default()
+                    )));
+                } else {
+                    self.report_error(AppError::Capture(
+                        "Capture stopped unexpectedly (the target may have closed)".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Record an error in the shared error log
+    fn report_error(&self, error: AppError) {
+        self.record_event("error", &format!("{}: {}", error.category().name(), error));
+        self.shared_state.write().runtime.set_error(error);
+    }
+
+    /// Record an event in the database
+    fn record_event(&self, kind: &str, message: &str) {
+        if let (Some(ref db), Some(session_id)) = (&self.database, self.session_id) {
+            if let Err(e) = db.record_event(session_id, kind, message) {
+                tracing::warn!("Failed to record event: {}", e);
+            }
+        }
+    }
+
+    /// Record where on screen a tip that was shown came from, for the heatmap
+    fn record_tip_origin(&self, tip_id: &str, source: String, bounds: (f32, f32, f32, f32)) {
+        if let (Some(ref db), Some(session_id)) = (&self.database, self.session_id) {
+            let origin = TipOrigin {
+                tip_id: tip_id.to_string(),
+                source,
+                bounds,
+            };
+            if let Err(e) = db.record_tip_origin(session_id, &origin) {
+                tracing::warn!("Failed to record tip origin: {}", e);
+            }
+        }
+    }
+
+    /// Region of the frame a watched zone or color trigger variable is read
+    /// from, as fractions of the frame
+    fn variable_bounds(&self, variable: &str) -> Option<(f32, f32, f32, f32)> {
+        let profile = self.active_profile.as_ref()?;
+        if let Some(zone) = profile
+            .ocr_regions
+            .iter()
+            .find(|zone| watch::variable_name("zone", &zone.name, &zone.id) == variable)
+        {
+            return Some(zone.bounds);
+        }
+        let trigger = profile.color_triggers.iter().find(|trigger| {
+            watch::variable_name("trigger", &trigger.name, &trigger.id) == variable
+        })?;
+        let vision = &self.dashboard_state.vision;
+        let (width, height) = (
+            trigger.patch_size as f32 / vision.last_frame_width.max(1) as f32,
+            trigger.patch_size as f32 / vision.last_frame_height.max(1) as f32,
+        );
+        let (x, y) = trigger.position;
+        Some((x - width / 2.0, y - height / 2.0, width, height))
+    }
+
+    /// Check the active profile's color triggers against a frame
+    fn update_color_triggers(&self, frame: &crate::capture::CapturedFrame) {
+        let active = match self.active_profile {
+            Some(ref profile) if !profile.color_triggers.is_empty() => {
+                color_trigger::active_triggers(&profile.color_triggers, frame)
+            }
+            _ => Default::default(),
+        };
+
+        let previous = {
+            let mut shared = self.shared_state.write();
+            if shared.runtime.active_color_triggers == active {
+                return;
+            }
+            std::mem::replace(&mut shared.runtime.active_color_triggers, active.clone())
+        };
+
+        let Some(ref profile) = self.active_profile else {
+            return;
+        };
+        for trigger in &profile.color_triggers {
+            let state = match (previous.contains(&trigger.id), active.contains(&trigger.id)) {
+                (false, true) => "on",
+                (true, false) => "off",
+                _ => continue,
+            };
+            tracing::info!("Color trigger '{}' {}", trigger.name, state);
+            if state == "on" && trigger.save_replay {
+                self.shared_state.write().runtime.save_replay =
+                    Some(format!("color trigger '{}'", trigger.name));
+            }
+        }
+    }
+
+    /// Save the replay buffer when asked, and report finished saves
+    fn process_replay(&mut self) {
+        let (request, saved, settings) = {
+            let mut state = self.shared_state.write();
+            (
+                state.runtime.save_replay.take(),
+                state.runtime.saved_replay.take(),
+                state.config.capture.replay.clone(),
+            )
+        };
+
+        self.replay.configure(&settings);
+        if !settings.enabled && !self.replay.is_empty() {
+            self.replay.clear();
+            self.shared_state.write().runtime.replay_buffered = (0.0, 0);
+        }
+
+        match saved {
+            Some(Ok(path)) => {
+                self.record_event("replay", &format!("Replay saved to {}", path.display()));
+                self.dispatch_tip(Tip {
+                    id: "replay_saved".to_string(),
+                    message: "Replay saved".to_string(),
+                    priority: 50,
+                    duration_ms: Some(3_000),
+                    ..Default::default()
+                });
+            }
+            Some(Err(e)) => {
+                self.report_error(AppError::Capture(format!("Failed to save replay: {}", e)));
             }
+            None => {}
         }
+
+        let Some(reason) = request else {
+            return;
+        };
+        if self.shared_state.read().runtime.saving_replay {
+            tracing::debug!("Replay requested by {} while one is being saved", reason);
+            return;
+        }
+        if self.replay.is_empty() {
+            self.report_error(AppError::Capture(
+                "No replay to save: turn on the replay buffer in Settings and start capture"
+                    .to_string(),
+            ));
+            return;
+        }
+        let dir = match crate::storage::get_replays_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to create the replays folder: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        self.record_event("replay", &format!("Saving replay ({})", reason));
+        self.shared_state.write().runtime.saving_replay = true;
+        let frames = self.replay.frames();
+        let shared_state = self.shared_state.clone();
+        std::thread::spawn(move || {
+            let result = replay::save_replay(&frames, settings.format, settings.fps, &dir)
+                .map_err(|e| format!("{:#}", e));
+            let mut state = shared_state.write();
+            state.runtime.saving_replay = false;
+            if let Ok(ref path) = result {
+                state.runtime.last_replay = Some(path.clone());
+            }
+            state.runtime.saved_replay = Some(result);
+        });
+    }
+
+    /// Count a feature use for opt-in telemetry
+    fn record_usage(&self, feature: Feature) {
+        self.shared_state.write().runtime.usage.record(feature);
     }
 
     /// Check if capture is running
@@ -501,6 +1596,9 @@ impl DashboardApp {
         if self.overlay_manager.is_some() {
             return Ok(()); // Already running
         }
+        if !self.shared_state.read().config.features.overlay {
+            return Err("The overlay is turned off in Settings > Features".to_string());
+        }
 
         let overlay_config = self.shared_state.read().overlay_config.clone();
 
@@ -508,6 +1606,8 @@ impl DashboardApp {
             Ok(manager) => {
                 let manager = Arc::new(manager);
                 self.overlay_manager = Some(manager.clone());
+                self.last_synced_checklist = None;
+                self.last_synced_nag = None;
 
                 // Start overlay in a background thread
                 let handle = std::thread::spawn(move || {
@@ -517,6 +1617,7 @@ impl DashboardApp {
                 });
 
                 self.overlay_handle = Some(handle);
+                self.record_event("overlay", "Overlay started");
 
                 // Update runtime state
                 let mut state = self.shared_state.write();
@@ -536,11 +1637,13 @@ impl DashboardApp {
         // The overlay thread will stop when the manager is dropped
         if let Some(handle) = self.overlay_handle.take() {
             let _ = handle.join();
+            self.record_event("overlay", "Overlay stopped");
         }
 
         let mut state = self.shared_state.write();
         state.runtime.is_overlay_running = false;
         state.runtime.overlay_visible = false;
+        state.runtime.overlay_queue_depth = 0;
     }
 
     /// Check if overlay is running
@@ -602,31 +1705,96 @@ impl DashboardApp {
             return;
         }
 
+        // Never overwrite the user's config with safe mode defaults implicitly
+        if self.shared_state.read().runtime.safe_mode {
+            self.pending_save = false;
+            return;
+        }
+
         // Only save if enough time has passed since last change
         if self.last_auto_save.elapsed() < AUTO_SAVE_DELAY {
             return;
         }
 
-        if let Some(ref config_dir) = self.config_dir {
+        if let Some(config_dir) = self.config_dir.clone() {
             let config_path = config_dir.join("config.toml");
-            let state = self.shared_state.read();
-            if let Err(e) = crate::config::save_config(&state.config, &config_path) {
-                tracing::error!("Failed to auto-save config: {}", e);
-            } else {
-                tracing::debug!("Auto-saved configuration");
-                self.pending_save = false;
+            if let Err(e) = self.back_up_loaded_config(&config_dir) {
+                // Retried after the debounce delay
                 self.last_auto_save = Instant::now();
+                self.report_error(AppError::Storage(format!(
+                    "Failed to back up config before saving: {}",
+                    e
+                )));
+                return;
+            }
+            self.storage_writer
+                .save_config(&self.shared_state.read().config, config_path);
+            self.pending_save = false;
+            self.last_auto_save = Instant::now();
+        }
+    }
+
+    /// Keep config.toml as config.toml.bak before it's first overwritten, if
+    /// loading it dropped unknown keys or invalid values
+    ///
+    /// Saving writes only what was loaded, so without the backup those
+    /// entries would be gone for good.
+    fn back_up_loaded_config(&mut self, config_dir: &Path) -> anyhow::Result<()> {
+        if self.config_backed_up || self.shared_state.read().runtime.config_issues.is_empty() {
+            return Ok(());
+        }
+        let config_path = config_dir.join("config.toml");
+        let backup_path = config_dir.join("config.toml.bak");
+        if config_path.exists() {
+            std::fs::copy(&config_path, &backup_path)?;
+            tracing::info!(
+                "Kept config with dropped entries as {:?} before saving",
+                backup_path
+            );
+        }
+        self.config_backed_up = true;
+        Ok(())
+    }
+
+    /// Report saves the storage writer finished, marking failed ones to be
+    /// saved again after the debounce delay
+    fn process_save_results(&mut self) {
+        while let Some(result) = self.storage_writer.try_result() {
+            let Some(error) = result.error else {
+                tracing::debug!("Saved {:?}", result.path);
+                continue;
+            };
+            match result.target {
+                SaveTarget::Config => {
+                    self.report_error(AppError::Storage(format!(
+                        "Failed to auto-save config: {}",
+                        error
+                    )));
+                    self.pending_save = true;
+                    self.last_auto_save = Instant::now();
+                }
+                SaveTarget::Profile { id, name } => {
+                    self.report_error(AppError::Storage(format!(
+                        "Failed to save profile '{}': {}",
+                        name, error
+                    )));
+                    if self.active_profile.as_ref().is_some_and(|p| p.id == id) {
+                        self.dashboard_state.vision.zones_dirty = true;
+                        self.last_profile_save = Instant::now();
+                    }
+                }
             }
         }
     }
 
-    /// Auto-save profile zones if they've been modified (debounced)
+    /// Auto-save profile zones and labels if they've been modified (debounced)
     fn auto_save_profile_zones(&mut self) {
         const ZONE_SAVE_DELAY: Duration = Duration::from_secs(2);
 
         let zones_dirty = self.dashboard_state.vision.zones_dirty;
+        let labels_dirty = self.dashboard_state.vision.labels_dirty;
 
-        if !zones_dirty {
+        if !zones_dirty && !labels_dirty {
             return;
         }
 
@@ -635,24 +1803,40 @@ impl DashboardApp {
             return;
         }
 
+        self.backup_if_zones_removed();
+        self.backup_if_labels_removed();
+
         if let (Some(ref mut profile), Some(ref profiles_dir)) =
             (&mut self.active_profile, &self.profiles_dir)
         {
-            // Update profile with current zones from vision state
-            profile.ocr_regions = self.dashboard_state.vision.ocr_zones.clone();
+            // Update profile with current zones and labels from vision state
+            let vision = &self.dashboard_state.vision;
+            let sections = ProfileSections {
+                id: profile.id.clone(),
+                name: profile.name.clone(),
+                ocr_regions: zones_dirty.then(|| vision.ocr_zones.clone()),
+                labeled_regions: labels_dirty.then(|| vision.labeled_regions.clone()),
+            };
+            sections.apply(profile);
 
+            // Only the changed sections are written to a profile saved before,
+            // leaving its screens and template images alone
             let profile_path = profiles_dir.join(format!("{}.json", profile.id));
-            if let Err(e) = crate::storage::profiles::save_profile(profile, &profile_path) {
-                tracing::error!("Failed to save profile: {}", e);
+            if profile_path.exists() {
+                self.storage_writer
+                    .save_profile_sections(sections, profile_path);
             } else {
-                tracing::info!(
-                    "Saved {} zones to profile '{}'",
-                    profile.ocr_regions.len(),
-                    profile.name
-                );
-                self.dashboard_state.vision.zones_dirty = false;
-                self.last_profile_save = Instant::now();
+                self.storage_writer.save_profile(profile, profile_path);
             }
+            tracing::info!(
+                "Saving {} zones and {} labels to profile '{}'",
+                profile.ocr_regions.len(),
+                profile.labeled_regions.len(),
+                profile.name
+            );
+            self.dashboard_state.vision.zones_dirty = false;
+            self.dashboard_state.vision.labels_dirty = false;
+            self.last_profile_save = Instant::now();
         }
     }
 
@@ -669,6 +1853,17 @@ impl DashboardApp {
         };
 
         if let Some(screens) = updated_screens {
+            // Back up before saving if a screen was deleted
+            if let Some(ref profile) = self.active_profile {
+                let removed = profile
+                    .screens
+                    .iter()
+                    .any(|saved| !screens.iter().any(|screen| screen.id == saved.id));
+                if removed {
+                    self.backup_profile(&profile.id, BackupReason::DeleteScreen);
+                }
+            }
+
             // Update local profile
             if let Some(ref mut profile) = self.active_profile {
                 profile.screens = screens.clone();
@@ -676,15 +1871,12 @@ impl DashboardApp {
                 // Save profile to disk
                 if let Some(ref profiles_dir) = self.profiles_dir {
                     let profile_path = profiles_dir.join(format!("{}.json", profile.id));
-                    if let Err(e) = crate::storage::profiles::save_profile(profile, &profile_path) {
-                        tracing::error!("Failed to save profile screens: {}", e);
-                    } else {
-                        tracing::info!(
-                            "Saved {} screens to profile '{}'",
-                            screens.len(),
-                            profile.name
-                        );
-                    }
+                    self.storage_writer.save_profile(profile, profile_path);
+                    tracing::info!(
+                        "Saving {} screens to profile '{}'",
+                        screens.len(),
+                        profile.name
+                    );
                 }
 
                 // Reload screens into recognizer
@@ -695,141 +1887,1235 @@ impl DashboardApp {
         self.dashboard_state.screens.screens_dirty = false;
     }
 
-    /// Save window state periodically (debounced, only when changed)
-    fn save_window_state(&mut self, ctx: &egui::Context) {
-        const WINDOW_SAVE_INTERVAL: Duration = Duration::from_secs(5);
-
-        // Only check periodically
-        if self.last_window_save.elapsed() < WINDOW_SAVE_INTERVAL {
+    /// Save color triggers edited in shared state to the profile
+    fn sync_profile_color_triggers(&mut self) {
+        if !self.dashboard_state.vision.color_triggers_dirty {
             return;
         }
+        self.dashboard_state.vision.color_triggers_dirty = false;
 
-        self.last_window_save = Instant::now();
-
-        // Get current window state from egui - extract values inside the closure
-        let (outer_rect, maximized) = ctx.input(|i| {
-            let vp = i.viewport();
-            (vp.outer_rect, vp.maximized.unwrap_or(false))
-        });
-
-        let current_state = WindowState {
-            position: outer_rect.map(|r| (r.left() as i32, r.top() as i32)),
-            size: outer_rect.map(|r| (r.width(), r.height())),
-            maximized,
+        let triggers = {
+            let shared = self.shared_state.read();
+            shared.active_profile().map(|p| p.color_triggers.clone())
         };
-
-        // Only save if state changed
-        let should_save = match &self.last_window_state {
-            Some(last) => {
-                last.position != current_state.position
-                    || last.size != current_state.size
-                    || last.maximized != current_state.maximized
-            }
-            None => true,
+        let (Some(triggers), Some(ref mut profile)) = (triggers, &mut self.active_profile) else {
+            return;
         };
+        profile.color_triggers = triggers;
 
-        if should_save {
-            if let Some(ref config_dir) = self.config_dir {
-                let state_path = config_dir.join("window_state.toml");
-                if let Err(e) = crate::config::save_window_state(&current_state, &state_path) {
-                    tracing::error!("Failed to save window state: {}", e);
-                } else {
-                    tracing::debug!("Saved window state");
-                    self.last_window_state = Some(current_state);
-                }
-            }
+        if let Some(ref profiles_dir) = self.profiles_dir {
+            let profile_path = profiles_dir.join(format!("{}.json", profile.id));
+            self.storage_writer.save_profile(profile, profile_path);
         }
     }
 
-    /// Sync dashboard and vision state to config (for auto-save)
-    /// Returns true if any changes were detected
-    fn sync_dashboard_state_to_config(&mut self) {
-        // Build current vision settings from dashboard state
-        let current_vision = crate::config::VisionSettings {
-            backend: self.dashboard_state.vision.selected_backend,
-            granularity: match self.dashboard_state.vision.ocr_granularity {
-                crate::dashboard::state::OcrGranularity::Word => {
-                    crate::vision::OcrGranularity::Word
-                }
-                crate::dashboard::state::OcrGranularity::Line => {
-                    crate::vision::OcrGranularity::Line
-                }
-            },
-            match_threshold: self.dashboard_state.vision.match_threshold,
-            show_bounding_boxes: self.dashboard_state.vision.show_bounding_boxes,
-            auto_run_ocr: self.dashboard_state.vision.auto_run_ocr,
-            preprocessing: self.dashboard_state.vision.preprocessing.clone(),
+    /// Save checklists edited in shared state to the profile
+    fn sync_profile_checklists(&mut self) {
+        if !std::mem::take(&mut self.dashboard_state.home.checklists_dirty) {
+            return;
+        }
+        let checklists = {
+            let shared = self.shared_state.read();
+            shared.active_profile().map(|p| p.checklists.clone())
         };
+        if let Some(checklists) = checklists {
+            self.save_checklists(checklists);
+        }
+    }
 
-        let current_view = self.dashboard_state.current_view;
+    /// Reset checklists on schedule and check items whose condition shows on screen
+    fn process_checklists(&mut self) {
+        const CHECKLIST_INTERVAL: Duration = Duration::from_millis(500);
 
-        // Check for vision settings changes
-        let vision_changed = match &self.last_synced_vision {
-            Some(last) => {
-                last.backend != current_vision.backend
-                    || last.granularity != current_vision.granularity
-                    || (last.match_threshold - current_vision.match_threshold).abs() > 0.001
-                    || last.show_bounding_boxes != current_vision.show_bounding_boxes
-                    || last.preprocessing != current_vision.preprocessing
-            }
-            None => true,
-        };
+        if self.last_checklist_check.elapsed() < CHECKLIST_INTERVAL {
+            return;
+        }
+        self.last_checklist_check = Instant::now();
 
-        // Check for view changes
-        let view_changed = match &self.last_synced_view {
-            Some(last) => *last != current_view,
-            None => true,
+        let Some(mut checklists) = self.active_profile.as_ref().map(|p| p.checklists.clone())
+        else {
+            return;
         };
 
-        if vision_changed || view_changed {
-            let mut state = self.shared_state.write();
+        let reset =
+            checklist::apply_resets(&mut checklists, checklist::unix_time(), self.session_start);
+        let checked = {
+            let shared = self.shared_state.read();
+            let context = screen_conditions(&shared, &self.dashboard_state.vision);
+            checklist::auto_check(&mut checklists, &context)
+        };
 
-            // Sync vision settings
-            state.config.vision = current_vision.clone();
+        for name in &reset {
+            tracing::info!("Checklist '{}' reset", name);
+        }
+        for text in &checked {
+            tracing::info!("Checklist item '{}' checked", text);
+        }
+        if !reset.is_empty() || !checked.is_empty() {
+            self.save_checklists(checklists);
+        }
 
-            // Sync dashboard settings
-            state.config.dashboard.last_view = current_view.to_setting();
+        self.sync_overlay_checklist();
+    }
 
-            // Sync active profile ID
-            if let Some(ref profile) = self.active_profile {
-                state.config.dashboard.active_profile_id = Some(profile.id.clone());
-            }
+    /// Start a new match from the first game phase
+    fn start_match(&mut self) {
+        self.phase_tracker.new_match();
+        let mut shared = self.shared_state.write();
+        shared.runtime.current_phase = None;
+        shared.runtime.match_started_at = Some(self.phase_tracker.match_start());
+    }
 
-            drop(state);
+    /// Move the match on to the latest game phase reached
+    fn process_phases(&mut self) {
+        const PHASE_INTERVAL: Duration = Duration::from_millis(500);
 
-            // Update tracking state
-            self.last_synced_vision = Some(current_vision);
-            self.last_synced_view = Some(current_view);
+        if std::mem::take(&mut self.dashboard_state.profiles.new_match) {
+            tracing::info!("New match started");
+            self.start_match();
+        }
 
-            // Mark for auto-save
-            self.pending_save = true;
-            self.last_auto_save = Instant::now();
+        if self.last_phase_check.elapsed() < PHASE_INTERVAL {
+            return;
+        }
+        self.last_phase_check = Instant::now();
 
-            tracing::debug!("Dashboard state changed, marked for save");
+        let Some(ref profile) = self.active_profile else {
+            return;
+        };
+        let changed = {
+            let shared = self.shared_state.read();
+            let context = screen_conditions(&shared, &self.dashboard_state.vision);
+            self.phase_tracker.update(&profile.phases, &context)
+        };
+        if !changed {
+            return;
         }
+
+        let current = self.phase_tracker.current().map(str::to_string);
+        let name = current
+            .as_ref()
+            .and_then(|id| profile.phases.iter().find(|phase| phase.id == *id))
+            .map_or_else(|| "none".to_string(), |phase| phase.name.clone());
+        tracing::info!("Game phase: {}", name);
+        self.shared_state.write().runtime.current_phase = current;
+        self.record_event("phase", &format!("Entered phase '{}'", name));
+
+        // The pinned checklist may belong to another phase
+        self.sync_overlay_checklist();
     }
-}
 
-impl eframe::App for DashboardApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Apply theme once
-        if !self.theme_applied {
-            theme::apply_theme(ctx);
-            self.theme_applied = true;
+    /// Note the labeled regions whose text was read this session, and log
+    /// and announce changes of the values read at them
+    fn process_label_matches(&mut self) {
+        const LABEL_MATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+        if self.last_label_check.elapsed() < LABEL_MATCH_INTERVAL
+            || self.shared_state.read().runtime.afk
+        {
+            return;
         }
+        self.last_label_check = Instant::now();
 
-        // Poll for hotkey events
-        self.poll_hotkeys();
+        let vision = &mut self.dashboard_state.vision;
+        if vision.labeled_regions.is_empty() {
+            return;
+        }
 
-        // Process commands from UI
-        self.process_capture_commands();
-        self.process_overlay_commands();
-        self.process_profile_commands();
-        self.process_test_tip();
-        self.process_vision_commands();
-        self.process_zone_commands();
-        self.process_auto_configure();
+        // Zone readings placed in the frame, then full-frame detections
+        let (width, height) = (
+            vision.last_frame_width as f32,
+            vision.last_frame_height as f32,
+        );
+        let detections: Vec<(&str, labels::Bounds)> = vision
+            .ocr_zones
+            .iter()
+            .filter(|zone| zone.source.is_none())
+            .filter_map(|zone| {
+                let result = vision.zone_ocr_results.get(&zone.id)?;
+                let (x, y, w, h) = zone.bounds;
+                let bounds = (
+                    (x * width) as u32,
+                    (y * height) as u32,
+                    (w * width) as u32,
+                    (h * height) as u32,
+                );
+                (!result.occluded).then_some((result.text.as_str(), bounds))
+            })
+            .chain(
+                vision
+                    .last_ocr_results
+                    .iter()
+                    .map(|r| (r.text.as_str(), r.bounds)),
+            )
+            .collect();
+
+        // A region whose text moved out of its bounds (the UI shifted) is
+        // re-bound to wherever its text is found now
+        let threshold = vision.match_threshold;
+        let mut changes = Vec::new();
+        let mut relocations = Vec::new();
+        for region in vision.labeled_regions.iter_mut() {
+            let value = match labels::read_value(region.bounds, &detections) {
+                Some(value) => value,
+                None => {
+                    let Some(bounds) = labels::relocate(region, &detections, threshold) else {
+                        continue;
+                    };
+                    relocations.push((region.label.clone(), region.bounds, bounds));
+                    region.bounds = bounds;
+                    vision.relocated_labels.insert(region.label.clone());
+                    vision.labels_dirty = true;
+                    match labels::read_value(bounds, &detections) {
+                        Some(value) => value,
+                        None => continue,
+                    }
+                }
+            };
+            if let Some(previous) = vision.label_values.update(&region.label, value) {
+                let (x, y, w, h) = region.bounds;
+                changes.push((
+                    region.label.clone(),
+                    previous,
+                    value.to_string(),
+                    region.notify_on_change,
+                    (
+                        x as f32 / width.max(1.0),
+                        y as f32 / height.max(1.0),
+                        w as f32 / width.max(1.0),
+                        h as f32 / height.max(1.0),
+                    ),
+                ));
+            }
+        }
+
+        if let Some(session_id) = vision.session_id {
+            let texts: Vec<&str> = vision
+                .zone_ocr_results
+                .values()
+                .filter(|result| !result.occluded)
+                .map(|result| result.text.as_str())
+                .chain(vision.last_ocr_results.iter().map(|r| r.text.as_str()))
+                .collect();
+            for region in vision
+                .labeled_regions
+                .iter_mut()
+                .filter(|r| r.last_matched_session != Some(session_id))
+            {
+                if texts
+                    .iter()
+                    .any(|text| text_similarity(text, &region.matched_text) >= threshold)
+                {
+                    region.last_matched_session = Some(session_id);
+                    vision.labels_dirty = true;
+                }
+            }
+        }
+
+        for (label, from, to) in relocations {
+            let message = format!(
+                "'{}' moved from {:?} to {:?} (re-bound by its text)",
+                label, from, to
+            );
+            tracing::info!("Label {}", message);
+            self.record_event("label", &message);
+        }
+
+        for (label, previous, value, notify, bounds) in changes {
+            self.record_event(
+                "label",
+                &format!("'{}' changed from '{}' to '{}'", label, previous, value),
+            );
+            if notify {
+                let id = format!("label_changed_{}", label);
+                let shown = self.dispatch_tip(Tip {
+                    id: id.clone(),
+                    message: format!("{}: {}", label, value),
+                    priority: 50,
+                    duration_ms: Some(5_000),
+                    ..Default::default()
+                });
+                if shown {
+                    self.record_tip_origin(&id, format!("label {}", label), bounds);
+                }
+            }
+        }
+    }
+
+    /// Refresh watched variables and raise the alerts of conditions that started to hold
+    fn process_watches(&mut self) {
+        const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+        if self.last_watch_check.elapsed() < WATCH_INTERVAL {
+            return;
+        }
+        self.last_watch_check = Instant::now();
+
+        // Values are only gathered while someone looks or a condition waits
+        let state = &self.dashboard_state.watch;
+        if !state.open
+            && !self.dashboard_state.reference.open
+            && state.watches.iter().all(|w| w.condition.is_none())
+        {
+            return;
+        }
+
+        let values = self.watch_values();
+        let mut hits = Vec::new();
+        let mut freeze = false;
+        for watched in &mut self.dashboard_state.watch.watches {
+            let previous = watched.value.clone();
+            if !watched.update(&values) {
+                continue;
+            }
+            let condition = watched.condition.as_ref().map_or(String::new(), |c| {
+                format!("{} {}", c.op.label(), c.operand.trim())
+            });
+            hits.push((
+                watched.variable.clone(),
+                format!(
+                    "{} {}: {} (was {})",
+                    watched.variable,
+                    condition,
+                    watched.value.as_deref().unwrap_or("-"),
+                    previous.as_deref().unwrap_or("-")
+                ),
+            ));
+            freeze |= watched.break_on_hit;
+        }
+        self.dashboard_state.watch.values = values;
+
+        for (variable, message) in hits {
+            tracing::info!("Watch alert: {}", message);
+            let shown = self.dispatch_tip(Tip {
+                id: "watch_alert".to_string(),
+                message: format!("Watch: {}", message),
+                priority: 50,
+                duration_ms: Some(5_000),
+                ..Default::default()
+            });
+            // Only variables read from a place on screen have an origin
+            if let Some(bounds) = self.variable_bounds(&variable).filter(|_| shown) {
+                self.record_tip_origin("watch_alert", format!("watch {}", variable), bounds);
+            }
+            let hits = &mut self.dashboard_state.watch.hits;
+            hits.push_front(crate::dashboard::state::WatchHit {
+                message,
+                at: Instant::now(),
+            });
+            hits.truncate(watch::MAX_HITS);
+        }
+
+        // Like a breakpoint: hold the frame the condition started to hold on
+        let vision = &mut self.dashboard_state.vision;
+        if freeze && vision.frozen_frame.is_none() && !vision.showing_clipboard_image {
+            if let Some(ref data) = vision.last_frame_data {
+                tracing::info!("Watch alert froze the vision preview");
+                vision.frozen_ocr_inputs = None;
+                vision.frozen_frame = Some(crate::capture::CapturedFrame::new(
+                    data.clone(),
+                    vision.last_frame_width,
+                    vision.last_frame_height,
+                ));
+                self.dashboard_state.watch.open = true;
+            }
+        }
+    }
+
+    /// Current value of every variable the watch window offers
+    fn watch_values(&self) -> WatchValues {
+        let mut values = WatchValues::new();
+        let shared = self.shared_state.read();
+        let runtime = &shared.runtime;
+
+        for result in self.dashboard_state.vision.zone_ocr_results.values() {
+            let name = watch::variable_name("zone", &result.zone_name, &result.zone_id);
+            values.insert(name, result.text.clone());
+        }
+        if let Some(ref screen) = runtime.current_screen {
+            values.insert("screen".to_string(), screen.screen_name.clone());
+            values.insert(
+                "screen.confidence".to_string(),
+                format!("{:.2}", screen.confidence),
+            );
+        }
+
+        if let Some(ref profile) = self.active_profile {
+            if let Some(phase) = self
+                .phase_tracker
+                .current()
+                .and_then(|id| profile.phases.iter().find(|phase| phase.id == id))
+            {
+                values.insert("phase".to_string(), phase.name.clone());
+            }
+            for trigger in &profile.color_triggers {
+                let name = watch::variable_name("trigger", &trigger.name, &trigger.id);
+                let active = runtime.active_color_triggers.contains(&trigger.id);
+                values.insert(name, active.to_string());
+            }
+            for variable in &profile.variables {
+                values.insert(format!("var.{}", variable.name), variable.value.clone());
+            }
+        }
+
+        values.insert("scene.dark".to_string(), runtime.scene.dark.to_string());
+        values.insert(
+            "scene.brightness".to_string(),
+            format!("{:.2}", runtime.scene.brightness),
+        );
+        if let Some((x, y)) = runtime.cursor {
+            values.insert("cursor.x".to_string(), x.to_string());
+            values.insert("cursor.y".to_string(), y.to_string());
+        }
+        values.insert(
+            "capture.running".to_string(),
+            runtime.is_capturing.to_string(),
+        );
+        values.insert(
+            "capture.fps".to_string(),
+            format!("{:.1}", runtime.capture_fps),
+        );
+        values.insert(
+            "quiet_hours".to_string(),
+            runtime.quiet_hours_active.to_string(),
+        );
+        if let Some(started) = runtime.match_started_at {
+            values.insert(
+                "timer.match".to_string(),
+                started.elapsed().as_secs().to_string(),
+            );
+        }
+        values.insert(
+            "timer.playtime".to_string(),
+            self.play_session
+                .played(Instant::now())
+                .as_secs()
+                .to_string(),
+        );
+
+        values
+    }
+
+    /// Check the next item of the pinned checklist (checklist hotkey)
+    fn tick_checklist(&mut self) {
+        let Some(mut checklists) = self.active_profile.as_ref().map(|p| p.checklists.clone())
+        else {
+            return;
+        };
+        let Some(pinned) = checklists.iter_mut().find(|c| c.pinned) else {
+            tracing::info!("No pinned checklist to tick");
+            return;
+        };
+
+        match checklist::tick_next(pinned) {
+            Some(text) => {
+                tracing::info!("Checklist item '{}' ticked", text);
+                self.save_checklists(checklists);
+                self.sync_overlay_checklist();
+            }
+            None => tracing::info!("Checklist '{}' is already done", pinned.name),
+        }
+    }
+
+    /// Store checklists in the active profile and save it
+    fn save_checklists(&mut self, checklists: Vec<Checklist>) {
+        let Some(ref mut profile) = self.active_profile else {
+            return;
+        };
+        profile.checklists = checklists.clone();
+        if let Some(shared_profile) = self
+            .shared_state
+            .write()
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == profile.id)
+        {
+            shared_profile.checklists = checklists;
+        }
+
+        if let Some(ref profiles_dir) = self.profiles_dir {
+            let profile_path = profiles_dir.join(format!("{}.json", profile.id));
+            self.storage_writer.save_profile(profile, profile_path);
+        }
+    }
+
+    /// Show the pinned checklist on the overlay (only when changed)
+    fn sync_overlay_checklist(&mut self) {
+        let Some(ref manager) = self.overlay_manager else {
+            return;
+        };
+        let display = self
+            .active_profile
+            .as_ref()
+            .and_then(|p| checklist::pinned(&p.checklists))
+            .filter(|c| phases::is_active(&c.phases, self.phase_tracker.current()))
+            .map(|c| ChecklistDisplay {
+                name: c.name.clone(),
+                items: c
+                    .items
+                    .iter()
+                    .map(|item| (item.text.clone(), item.done))
+                    .collect(),
+            });
+
+        if display != self.last_synced_checklist {
+            manager.update_checklist(display.clone());
+            self.last_synced_checklist = display;
+        }
+    }
+
+    /// Update whether quiet hours are on (checked every few seconds)
+    fn process_quiet_hours(&mut self) {
+        const QUIET_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+        if self
+            .last_quiet_check
+            .is_some_and(|checked| checked.elapsed() < QUIET_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_quiet_check = Some(Instant::now());
+
+        let settings = self.shared_state.read().config.quiet_hours.clone();
+        let quiet = quiet_hours::is_quiet_now(&settings);
+
+        let mut shared = self.shared_state.write();
+        if quiet != shared.runtime.quiet_hours_active {
+            tracing::info!("Quiet hours {}", if quiet { "started" } else { "ended" });
+            shared.runtime.quiet_hours_active = quiet;
+        }
+    }
+
+    /// Apply the thread priority and efficiency mode settings
+    ///
+    /// Vision runs on the dashboard thread, so its priority is set here; the
+    /// capture thread applies its own on the next frame.
+    fn process_qos(&mut self) {
+        const QOS_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+        if self
+            .last_qos_check
+            .is_some_and(|checked| checked.elapsed() < QOS_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_qos_check = Some(Instant::now());
+
+        let settings = self.shared_state.read().config.performance.clone();
+
+        if self.applied_low_priority != Some(settings.low_priority_workers) {
+            if let Err(e) = qos::set_current_thread_low_priority(settings.low_priority_workers) {
+                tracing::warn!("Vision thread priority unchanged: {:#}", e);
+            }
+            self.applied_low_priority = Some(settings.low_priority_workers);
+        }
+
+        let fullscreen_app = qos::fullscreen_app_active();
+        let efficiency_mode = settings.efficiency_mode_fullscreen && fullscreen_app;
+        if efficiency_mode != self.efficiency_mode {
+            match qos::set_efficiency_mode(efficiency_mode) {
+                Ok(()) => {
+                    tracing::info!(
+                        "Efficiency mode {}",
+                        if efficiency_mode { "on" } else { "off" }
+                    );
+                    self.efficiency_mode = efficiency_mode;
+                }
+                Err(e) => tracing::warn!("Efficiency mode unchanged: {:#}", e),
+            }
+        }
+
+        let capture_priority = self.capture_manager.lock().as_ref().and_then(|capture| {
+            capture.set_low_priority(settings.low_priority_workers);
+            capture.thread_priority()
+        });
+
+        let exclusive_fullscreen = fullscreen_app && qos::exclusive_fullscreen_active();
+        self.update_fullscreen_game(exclusive_fullscreen);
+        let mut shared = self.shared_state.write();
+        if exclusive_fullscreen != shared.runtime.qos.exclusive_fullscreen
+            && self.overlay_manager.is_some()
+        {
+            if exclusive_fullscreen {
+                tracing::warn!(
+                    "The app in front runs in exclusive full screen; the overlay is hidden \
+                     there, and tips follow second monitor mode or the overlay fallback settings"
+                );
+            } else {
+                tracing::info!("Exclusive full screen ended; tips show on the overlay again");
+            }
+        }
+        shared.runtime.qos = QosStatus {
+            capture_priority,
+            vision_priority: qos::current_thread_priority(),
+            efficiency_mode: self.efficiency_mode,
+            fullscreen_app,
+            exclusive_fullscreen,
+        };
+    }
+
+    /// Track whether the game is the app in exclusive full screen, and pick
+    /// the monitor second monitor mode moves the overlay to meanwhile
+    fn update_fullscreen_game(&mut self, exclusive_fullscreen: bool) {
+        let window = exclusive_fullscreen
+            .then(qos::foreground_window)
+            .flatten()
+            .filter(|window| self.is_game_process(window.pid));
+        let (current, second_monitor_mode) = {
+            let shared = self.shared_state.read();
+            (
+                shared.runtime.fullscreen_game,
+                shared.config.overlay.second_monitor_mode,
+            )
+        };
+
+        let fullscreen_game = match (window, current) {
+            (Some(_), Some(game)) => Some(game),
+            (Some(window), None) => {
+                let monitors = crate::overlay::system_monitors();
+                let (monitor, other) = window.monitor_position.map_or((None, None), |position| {
+                    crate::overlay::monitor_beside(&monitors, position)
+                });
+                let tips_monitor = other.filter(|_| second_monitor_mode);
+                match tips_monitor {
+                    Some(index) => tracing::info!(
+                        "The game runs in exclusive full screen; tips move to monitor {}",
+                        index + 1
+                    ),
+                    None => tracing::info!("The game runs in exclusive full screen"),
+                }
+                Some(FullscreenGame {
+                    monitor,
+                    tips_monitor,
+                })
+            }
+            (None, _) => None,
+        };
+        self.shared_state.write().runtime.fullscreen_game = fullscreen_game;
+    }
+
+    /// Whether a process is the game: the captured process, or one running an
+    /// executable of the active profile (any process when neither is known)
+    fn is_game_process(&self, pid: u32) -> bool {
+        if let CaptureTarget::Process(target) = self.shared_state.read().capture_config.target {
+            return target == pid;
+        }
+        let executables = self
+            .active_profile
+            .as_ref()
+            .map(|profile| profile.executables.as_slice())
+            .unwrap_or_default();
+        if executables.is_empty() {
+            return true;
+        }
+        process_watcher::running_processes()
+            .ok()
+            .and_then(|processes| processes.into_iter().find(|(id, _)| *id == pid))
+            .is_some_and(|(_, name)| {
+                executables
+                    .iter()
+                    .any(|exe| process_watcher::is_executable(&name, exe))
+            })
+    }
+
+    /// Switch the low-power preset on or off with the power source
+    fn process_power(&mut self) {
+        const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+        if self
+            .last_power_check
+            .is_some_and(|checked| checked.elapsed() < POWER_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_power_check = Some(Instant::now());
+
+        let settings = self.shared_state.read().config.performance.clone();
+        let status = power::current_status(&settings);
+
+        let fps_limit = {
+            let mut shared = self.shared_state.write();
+            if status.power_saving != shared.runtime.power.power_saving {
+                tracing::info!(
+                    "Low-power preset {}",
+                    if status.power_saving {
+                        "on (running on battery)"
+                    } else {
+                        "off"
+                    }
+                );
+            }
+            shared.runtime.power = status;
+            capture_fps_limit(&shared)
+        };
+
+        if let Some(ref capture) = *self.capture_manager.lock() {
+            capture.set_fps_limit(fps_limit);
+        }
+    }
+
+    /// Capture in snapshot mode while the active profile asks for it
+    fn process_snapshot_capture(&mut self) {
+        let interval = self
+            .active_profile
+            .as_ref()
+            .and_then(|profile| profile.snapshot_capture.interval());
+        let Some(ref capture) = *self.capture_manager.lock() else {
+            return;
+        };
+        if capture.snapshot_interval() == interval {
+            return;
+        }
+        match interval {
+            Some(interval) => tracing::info!(
+                "Snapshot capture: one frame every {:.1}s",
+                interval.as_secs_f32()
+            ),
+            None => tracing::info!("Capture streaming again"),
+        }
+        capture.set_snapshot_interval(interval);
+    }
+
+    /// Capture the active profile's secondary windows, following profile
+    /// switches and edits while capturing
+    fn process_capture_sources(&mut self) {
+        const SOURCE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        if self
+            .last_source_check
+            .is_some_and(|checked| checked.elapsed() < SOURCE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_source_check = Some(Instant::now());
+
+        let sources = self.shared_state.read().capture_sources();
+        if sources == self.synced_sources {
+            return;
+        }
+        let result = match *self.capture_manager.lock() {
+            Some(ref mut capture) => capture.sync_sources(&sources),
+            None => return,
+        };
+        self.synced_sources = sources;
+        if let Err(e) = result {
+            self.report_error(AppError::Capture(format!("{:#}", e)));
+        }
+    }
+
+    /// Lower the capture frame rate while the process uses more CPU than
+    /// the performance settings allow, and raise it back once it doesn't
+    fn process_cpu_budget(&mut self) {
+        const CPU_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+        if self
+            .last_cpu_check
+            .is_some_and(|checked| checked.elapsed() < CPU_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_cpu_check = Some(Instant::now());
+
+        let (settings, max_fps) = {
+            let state = self.shared_state.read();
+            (
+                state.config.performance.clone(),
+                state.config.capture.max_fps,
+            )
+        };
+        let mut status = self
+            .cpu_budget
+            .sample(settings.max_cpu_percent, max_fps.max(1));
+        if !settings.adaptive_capture_fps {
+            self.cpu_budget.reset();
+            status.fps_limit = None;
+        }
+
+        let fps_limit = {
+            let mut shared = self.shared_state.write();
+            if status.fps_limit != shared.runtime.cpu_budget.fps_limit {
+                match status.fps_limit {
+                    Some(fps) => tracing::info!(
+                        "Capture limited to {} FPS to stay under {}% CPU",
+                        fps,
+                        settings.max_cpu_percent
+                    ),
+                    None => tracing::info!("Capture frame rate back to {} FPS", max_fps),
+                }
+            }
+            shared.runtime.cpu_budget = status;
+            capture_fps_limit(&shared)
+        };
+
+        if let Some(ref capture) = *self.capture_manager.lock() {
+            capture.set_fps_limit(fps_limit);
+        }
+    }
+
+    /// Pause zone OCR and screen rules while the player is away, and resume
+    /// them on input or a change on screen
+    fn process_afk(&mut self) {
+        const AFK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        if self
+            .last_afk_check
+            .is_some_and(|checked| checked.elapsed() < AFK_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_afk_check = Some(Instant::now());
+
+        let (capturing, frame, pause_minutes) = {
+            let state = self.shared_state.read();
+            (
+                state.runtime.is_capturing,
+                state.runtime.last_captured_frame.clone(),
+                state.config.performance.afk_pause_minutes,
+            )
+        };
+
+        let afk = if capturing && pause_minutes > 0 {
+            if let Some(frame) = frame {
+                self.afk_detector.observe(&frame);
+            }
+            self.afk_detector.is_afk(
+                afk::input_idle(),
+                Duration::from_secs(pause_minutes as u64 * 60),
+            )
+        } else {
+            self.afk_detector.reset();
+            false
+        };
+
+        let mut shared = self.shared_state.write();
+        if afk != shared.runtime.afk {
+            if afk {
+                tracing::info!(
+                    "Analysis paused (AFK): no input or change on screen for {} min",
+                    pause_minutes
+                );
+            } else {
+                tracing::info!("Analysis resumed");
+            }
+            shared.runtime.afk = afk;
+        }
+    }
+
+    /// Warm PaddleOCR up at startup and unload it once it sits idle
+    fn process_model_residency(&mut self) {
+        use crate::vision::OcrBackend;
+
+        const IDLE_MODEL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+        let settings = self.shared_state.read().config.performance.clone();
+
+        if !std::mem::replace(&mut self.warm_up_checked, true)
+            && settings.warm_up_models
+            && self.dashboard_state.vision.selected_backend == OcrBackend::PaddleOcr
+            && self.dashboard_state.vision.corrupted_models.is_empty()
+        {
+            if self.vision_pipeline.is_none() {
+                match Self::create_vision_pipeline(&self.shared_state) {
+                    Ok(pipeline) => self.vision_pipeline = Some(pipeline),
+                    Err(e) => tracing::warn!("PaddleOCR warm-up skipped: {:#}", e),
+                }
+            }
+            if let Some(ref mut pipeline) = self.vision_pipeline {
+                pipeline.warm_up_paddle();
+            }
+        }
+
+        let Some(ref mut pipeline) = self.vision_pipeline else {
+            return;
+        };
+
+        match pipeline.poll_warm_up() {
+            Some(Ok(())) => self.dashboard_state.vision.ocr_initialized = true,
+            Some(Err(e)) => tracing::warn!("PaddleOCR warm-up failed: {:#}", e),
+            None => {}
+        }
+
+        if settings.model_idle_unload_minutes == 0
+            || self
+                .last_idle_model_check
+                .is_some_and(|checked| checked.elapsed() < IDLE_MODEL_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_idle_model_check = Some(Instant::now());
+
+        let idle = Duration::from_secs(settings.model_idle_unload_minutes as u64 * 60);
+        if pipeline.unload_idle_paddle(idle) {
+            self.dashboard_state.vision.ocr_initialized = false;
+        }
+    }
+
+    /// Send a tip to the overlay unless quiet hours hold it back
+    ///
+    /// Returns false if the tip wasn't shown.
+    fn dispatch_tip(&self, tip: Tip) -> bool {
+        let (quiet, budget_ms, fallback, exclusive_fullscreen, integrations) = {
+            let state = self.shared_state.read();
+            // In second monitor mode the overlay shows next to the game
+            let overlay_hidden = state.runtime.qos.exclusive_fullscreen
+                && state
+                    .runtime
+                    .fullscreen_game
+                    .map_or(true, |game| game.tips_monitor.is_none());
+            (
+                state.runtime.quiet_hours_active,
+                state.config.performance.tip_latency_budget_ms,
+                state.config.overlay.tip_fallback.route(tip.priority),
+                overlay_hidden,
+                state.config.features.integrations,
+            )
+        };
+        if !quiet_hours::allows(&tip, quiet) {
+            tracing::debug!("Quiet hours: held back tip '{}'", tip.message);
+            return false;
+        }
+        // Without the overlay (disabled, failed to start, or hidden behind an
+        // exclusive full screen game) the tip's priority picks where it goes.
+        // Tips without a fallback still go to a hidden overlay, which shows
+        // them once the game is left.
+        let to_overlay = self.overlay_manager.is_some()
+            && !(exclusive_fullscreen && fallback == TipFallback::Toast);
+        // Notifications are an integration that can be switched off
+        if !to_overlay && (fallback == TipFallback::Drop || !integrations) {
+            return false;
+        }
+
+        // A tip about what was on screen too long ago would be misleading
+        if let Some(captured_at) = tip.captured_at {
+            let age = captured_at.elapsed();
+            let mut state = self.shared_state.write();
+            if latency::is_stale(age, budget_ms) {
+                tracing::debug!(
+                    "Dropped stale tip '{}' ({} ms after capture)",
+                    tip.message,
+                    age.as_millis()
+                );
+                state.runtime.latency.stale_tips += 1;
+                return false;
+            }
+            state.runtime.latency.tip.record(age);
+        }
+
+        match self.overlay_manager {
+            Some(ref manager) if to_overlay => manager.show_tip(tip),
+            _ => {
+                if let Err(e) = toast::show_tip(&tip) {
+                    tracing::warn!("Failed to show tip '{}' as a toast: {:#}", tip.message, e);
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Show the tips queued by MCP tools once the overlay is running
+    fn process_pending_tips(&mut self) {
+        if self.overlay_manager.is_none() {
+            return;
+        }
+        let tips = std::mem::take(&mut self.shared_state.write().runtime.pending_tips);
+        let shown = tips
+            .into_iter()
+            .filter(|tip| self.dispatch_tip(tip.clone()))
+            .count();
+        if shown > 0 {
+            self.shared_state.write().runtime.tips_displayed += shown;
+        }
+    }
+
+    /// Track playtime, remind to take breaks and nag past the limit
+    fn process_wellness(&mut self) {
+        const PLAYTIME_RECORD_INTERVAL: Duration = Duration::from_secs(60);
+
+        let now = Instant::now();
+        let (capturing, settings) = {
+            let shared = self.shared_state.read();
+            (shared.runtime.is_capturing, shared.config.wellness.clone())
+        };
+
+        if capturing {
+            self.unrecorded_playtime += now.saturating_duration_since(self.last_wellness_check);
+        }
+        self.last_wellness_check = now;
+        if self.unrecorded_playtime >= PLAYTIME_RECORD_INTERVAL
+            || (!capturing && self.unrecorded_playtime.as_secs() > 0)
+        {
+            self.record_playtime();
+        }
+
+        for event in self.play_session.update(capturing, now, &settings) {
+            match event {
+                WellnessEvent::BreakReminder { played } => {
+                    let message = format!(
+                        "You've been playing for {}. Time for a short break?",
+                        format_playtime(played)
+                    );
+                    tracing::info!("{}", message);
+                    self.dispatch_tip(Tip {
+                        id: "wellness_break".to_string(),
+                        message,
+                        priority: 60,
+                        duration_ms: Some(15_000),
+                        play_sound: true,
+                        ..Default::default()
+                    });
+                }
+                WellnessEvent::NagStarted { played } => {
+                    self.record_event(
+                        "wellness",
+                        &format!("Playtime limit reached after {}", format_playtime(played)),
+                    );
+                }
+                WellnessEvent::NagEnded => {
+                    tracing::info!("Playtime limit banner cleared");
+                }
+            }
+        }
+
+        let nag = self.play_session.is_nagging().then(|| {
+            format!(
+                "Playing for {} without a break. Time to stop for a while.",
+                format_playtime(self.play_session.played(now))
+            )
+        });
+        if let Some(ref manager) = self.overlay_manager {
+            if nag != self.last_synced_nag {
+                manager.update_wellness_nag(nag.clone());
+                self.last_synced_nag = nag;
+            }
+        }
+    }
+
+    /// Add the capture time counted so far to today's playtime stats
+    fn record_playtime(&mut self) {
+        let seconds = self.unrecorded_playtime.as_secs();
+        if seconds == 0 {
+            return;
+        }
+        self.unrecorded_playtime -= Duration::from_secs(seconds);
+
+        let (profile_id, profile_name) = self
+            .active_profile
+            .as_ref()
+            .map_or(("", "No profile"), |p| (p.id.as_str(), p.name.as_str()));
+        if let Some(ref db) = self.database {
+            if let Err(e) = db.add_playtime(profile_id, profile_name, seconds) {
+                tracing::warn!("Failed to record playtime: {}", e);
+            }
+        }
+    }
+
+    /// Save window state periodically (debounced, only when changed)
+    fn save_window_state(&mut self, ctx: &egui::Context) {
+        const WINDOW_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+        // Only check periodically
+        if self.last_window_save.elapsed() < WINDOW_SAVE_INTERVAL {
+            return;
+        }
+
+        self.last_window_save = Instant::now();
+
+        // Get current window state from egui - extract values inside the closure
+        let (outer_rect, maximized) = ctx.input(|i| {
+            let vp = i.viewport();
+            (vp.outer_rect, vp.maximized.unwrap_or(false))
+        });
+
+        let current_state = WindowState {
+            position: outer_rect.map(|r| (r.left() as i32, r.top() as i32)),
+            size: outer_rect.map(|r| (r.width(), r.height())),
+            maximized,
+        };
+
+        // Only save if state changed
+        let should_save = match &self.last_window_state {
+            Some(last) => {
+                last.position != current_state.position
+                    || last.size != current_state.size
+                    || last.maximized != current_state.maximized
+            }
+            None => true,
+        };
+
+        if should_save {
+            if let Some(ref config_dir) = self.config_dir {
+                let state_path = config_dir.join("window_state.toml");
+                if let Err(e) = crate::config::save_window_state(&current_state, &state_path) {
+                    tracing::error!("Failed to save window state: {}", e);
+                } else {
+                    tracing::debug!("Saved window state");
+                    self.last_window_state = Some(current_state);
+                }
+            }
+        }
+    }
+
+    /// Save per-view UI state periodically (debounced, only when changed)
+    fn save_ui_state(&mut self, ctx: &egui::Context) {
+        const UI_STATE_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+        if self.last_ui_save.elapsed() < UI_STATE_SAVE_INTERVAL {
+            return;
+        }
+
+        self.last_ui_save = Instant::now();
+
+        let current_state = UiState {
+            selected_screen_id: self.dashboard_state.screens.selected_screen_id.clone(),
+            selected_profile_id: self.dashboard_state.profiles.selected_profile_id.clone(),
+            settings_section: self
+                .dashboard_state
+                .settings
+                .expanded_section
+                .map(|section| section.key().to_string()),
+            ..remembered::ui_state(ctx)
+        };
+
+        if self.last_ui_state.as_ref() == Some(&current_state) {
+            return;
+        }
+
+        if let Some(ref config_dir) = self.config_dir {
+            let state_path = config_dir.join("ui_state.toml");
+            if let Err(e) = crate::config::save_ui_state(&current_state, &state_path) {
+                tracing::error!("Failed to save UI state: {}", e);
+            } else {
+                tracing::debug!("Saved UI state");
+                self.last_ui_state = Some(current_state);
+            }
+        }
+    }
+
+    /// Sync dashboard and vision state to config (for auto-save)
+    /// Returns true if any changes were detected
+    fn sync_dashboard_state_to_config(&mut self) {
+        // Build current vision settings from dashboard state
+        let current_vision = crate::config::VisionSettings {
+            backend: self.dashboard_state.vision.selected_backend,
+            granularity: match self.dashboard_state.vision.ocr_granularity {
+                crate::dashboard::state::OcrGranularity::Word => {
+                    crate::vision::OcrGranularity::Word
+                }
+                crate::dashboard::state::OcrGranularity::Line => {
+                    crate::vision::OcrGranularity::Line
+                }
+            },
+            match_threshold: self.dashboard_state.vision.match_threshold,
+            show_bounding_boxes: self.dashboard_state.vision.show_bounding_boxes,
+            auto_run_ocr: self.dashboard_state.vision.auto_run_ocr,
+            preprocessing: self.dashboard_state.vision.preprocessing.clone(),
+            // Edited in the settings view rather than the dashboard
+            use_gpu: self.shared_state.read().config.vision.use_gpu,
+            gpu_adapter: self.shared_state.read().config.vision.gpu_adapter.clone(),
+            quality: self.dashboard_state.vision.ocr_quality,
+        };
+
+        let current_view = self.dashboard_state.current_view;
+
+        // Check for vision settings changes
+        let vision_changed = match &self.last_synced_vision {
+            Some(last) => {
+                last.backend != current_vision.backend
+                    || last.granularity != current_vision.granularity
+                    || (last.match_threshold - current_vision.match_threshold).abs() > 0.001
+                    || last.show_bounding_boxes != current_vision.show_bounding_boxes
+                    || last.preprocessing != current_vision.preprocessing
+                    || last.quality != current_vision.quality
+            }
+            None => true,
+        };
+
+        // Check for view changes
+        let view_changed = match &self.last_synced_view {
+            Some(last) => *last != current_view,
+            None => true,
+        };
+
+        if vision_changed || view_changed {
+            let mut state = self.shared_state.write();
+
+            // Sync vision settings
+            state.config.vision = current_vision.clone();
+
+            // Sync dashboard settings
+            state.config.dashboard.last_view = current_view.to_setting();
+
+            // Sync active profile ID
+            if let Some(ref profile) = self.active_profile {
+                state.config.dashboard.active_profile_id = Some(profile.id.clone());
+            }
+
+            drop(state);
+
+            // Update tracking state
+            self.last_synced_vision = Some(current_vision);
+            self.last_synced_view = Some(current_view);
+
+            // Mark for auto-save
+            self.pending_save = true;
+            self.last_auto_save = Instant::now();
+
+            tracing::debug!("Dashboard state changed, marked for save");
+        }
+    }
+}
+
+impl eframe::App for DashboardApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Apply theme once
+        if !self.theme_applied {
+            theme::apply_theme(ctx);
+            self.theme_applied = true;
+        }
+
+        // Scroll positions, open sections and panel widths from last session
+        if let Some(ui_state) = self.pending_ui_state.take() {
+            remembered::set_ui_state(ctx, ui_state);
+        }
+
+        // Poll for hotkey events
+        self.poll_hotkeys(ctx);
+
+        // Process commands from UI
+        self.process_capture_commands();
+        self.process_replay();
+        self.process_screenshots();
+        self.process_snapshot_export();
+        self.process_game_exit();
+        self.process_relaunch_request(ctx);
+        self.process_overlay_commands();
+        self.process_profile_commands();
+        self.process_config_rewrite();
+        self.process_capability_checks();
+        self.process_whats_new_request();
+        self.process_quiet_hours();
+        self.process_qos();
+        self.process_power();
+        self.process_snapshot_capture();
+        self.process_capture_sources();
+        self.process_cpu_budget();
+        self.process_afk();
+        self.process_model_residency();
+        self.apply_feature_flags();
+        self.process_game_launches(ctx);
+        self.process_test_tip();
+        self.process_pending_tips();
+        self.process_vision_commands();
+        self.process_clipboard_ocr();
+        self.process_backend_compare();
+        self.process_batch_ocr();
+        self.process_sample_frames();
+        self.process_validation();
+        self.process_zone_commands();
+        self.process_auto_configure();
         self.process_screen_recognition();
+        self.process_pipeline_step();
 
         // Sync overlay config changes to running overlay
         self.sync_overlay_config();
@@ -845,26 +3131,59 @@ impl eframe::App for DashboardApp {
 
         // Auto-save settings if needed
         self.auto_save_settings();
+        self.process_save_results();
 
         // Auto-save profile labels if needed
         self.auto_save_profile_zones();
 
         // Sync profile screens if modified
         self.sync_profile_screens();
+        self.sync_profile_color_triggers();
+
+        // OCR confidences follow the active profile's calibration
+        self.sync_pipeline_calibration();
+        self.sync_gpu_adapter();
+        self.sync_ocr_quality();
+
+        // Save edited checklists, then reset and auto-check them
+        self.sync_profile_checklists();
+        self.process_phases();
+        self.process_checklists();
 
-        // Save window state periodically
+        // Watched variables and their alerts
+        self.process_watches();
+
+        // Labeled regions whose text was read
+        self.process_label_matches();
+
+        // Playtime stats, break reminders and limits
+        self.process_wellness();
+        self.process_session_goals();
+        self.process_coach_nudges();
+
+        // Save window and per-view UI state periodically
         self.save_window_state(ctx);
+        self.save_ui_state(ctx);
 
         // Check if settings view has unsaved changes and mark for auto-save
         if self.dashboard_state.settings.has_unsaved_changes {
             self.pending_save = true;
             self.dashboard_state.settings.has_unsaved_changes = false;
         }
+        if self.dashboard_state.overlay.favorites_changed {
+            self.pending_save = true;
+            self.dashboard_state.overlay.favorites_changed = false;
+        }
+        if self.dashboard_state.home.config_changed {
+            self.pending_save = true;
+            self.dashboard_state.home.config_changed = false;
+        }
 
         // Request continuous repaint when capturing or when there are pending saves
         if self.is_capturing()
             || self.pending_save
             || self.dashboard_state.vision.zones_dirty
+            || self.dashboard_state.vision.labels_dirty
             || self.dashboard_state.screens.screens_dirty
         {
             ctx.request_repaint();
@@ -895,6 +3214,8 @@ impl eframe::App for DashboardApp {
                         );
                     }
                     DashboardView::Overlay => {
+                        self.dashboard_state.overlay.snapshot =
+                            self.overlay_manager.as_ref().map(|m| m.snapshot());
                         render_overlay_view(
                             ui,
                             &mut self.dashboard_state.overlay,
@@ -919,6 +3240,9 @@ impl eframe::App for DashboardApp {
                             &self.shared_state,
                         );
                     }
+                    DashboardView::Data => {
+                        render_data_view(ui, &mut self.dashboard_state.data);
+                    }
                     DashboardView::Settings => {
                         render_settings_view(
                             ui,
@@ -929,15 +3253,110 @@ impl eframe::App for DashboardApp {
                 }
             });
         });
+
+        // Release notes panel
+        if let Some(ref releases) = self.dashboard_state.whats_new {
+            if let Some(action) = render_whats_new(ctx, releases) {
+                self.close_whats_new(action);
+            }
+        }
+
+        // Screen text panel
+        if let Some(ref mut screen_text) = self.dashboard_state.screen_text {
+            match render_screen_text(ctx, screen_text) {
+                Some(ScreenTextAction::Close) => self.dashboard_state.screen_text = None,
+                Some(ScreenTextAction::ReadAgain) => {
+                    self.read_screen();
+                }
+                None => {}
+            }
+        }
+
+        // Watched variables
+        if std::mem::take(&mut self.dashboard_state.vision.open_watch) {
+            self.dashboard_state.watch.open = true;
+        }
+        if self.dashboard_state.watch.open {
+            render_watch_panel(ctx, &mut self.dashboard_state.watch);
+        }
+
+        // Paused pipeline and its stepped frame
+        if std::mem::take(&mut self.dashboard_state.vision.open_pipeline_debug) {
+            self.dashboard_state.pipeline_debug.open = true;
+        }
+        if self.dashboard_state.pipeline_debug.open {
+            render_pipeline_debug(ctx, &mut self.dashboard_state.pipeline_debug);
+        }
+
+        // Script reference
+        if std::mem::take(&mut self.dashboard_state.vision.open_reference)
+            | std::mem::take(&mut self.dashboard_state.profiles.open_reference)
+        {
+            self.dashboard_state.reference.open = true;
+        }
+        if self.dashboard_state.reference.open {
+            render_reference_panel(
+                ctx,
+                &mut self.dashboard_state.reference,
+                &self.dashboard_state.watch.values,
+            );
+        }
+
+        // Problem report
+        if std::mem::take(&mut self.dashboard_state.settings.open_issue_report) {
+            self.dashboard_state.issue_report.open = true;
+        }
+        if self.dashboard_state.issue_report.open {
+            render_issue_reporter(
+                ctx,
+                &mut self.dashboard_state.issue_report,
+                &self.shared_state,
+            );
+        }
+
+        // Profile validation report
+        if let Some(ref validation) = self.dashboard_state.validation {
+            match render_validation_report(ctx, validation) {
+                Some(ValidationAction::Close) => self.dashboard_state.validation = None,
+                Some(ValidationAction::RunAgain) => {
+                    let samples = validation.samples.clone();
+                    let backend = self.dashboard_state.vision.selected_backend;
+                    self.dashboard_state.validation = Some(ValidationState::new(samples, backend));
+                }
+                Some(ValidationAction::AcceptResults) => self.accept_validation_results(),
+                Some(ValidationAction::Calibrate) => self.calibrate_confidence(),
+                None => {}
+            }
+        }
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.record_playtime();
+
+        // Goals of a session still being captured are scored into the log
+        let goals = self.shared_state.write().runtime.session_goals.take();
+        if let Some(goals) = goals {
+            self.record_event("goals", &goals.scorecard(Instant::now()));
+        }
+
+        if let (Some(ref db), Some(session_id)) = (&self.database, self.session_id) {
+            if let Err(e) = db.end_session(session_id) {
+                tracing::warn!("Failed to record session end: {}", e);
+            }
+        }
+
+        // Finish queued saves so the ones below are written last
+        self.storage_writer.flush();
+
         // Save any pending config changes
-        if self.pending_save {
-            if let Some(ref config_dir) = self.config_dir {
+        if self.pending_save && !self.shared_state.read().runtime.safe_mode {
+            if let Some(config_dir) = self.config_dir.clone() {
                 let config_path = config_dir.join("config.toml");
-                let state = self.shared_state.read();
-                if let Err(e) = crate::config::save_config(&state.config, &config_path) {
+                if let Err(e) = self.back_up_loaded_config(&config_dir) {
+                    tracing::error!("Failed to back up config, not saving on exit: {}", e);
+                } else if let Err(e) =
+                    crate::config::save_config(&self.shared_state.read().config, &config_path)
+                {
                     tracing::error!("Failed to save config on exit: {}", e);
                 } else {
                     tracing::info!("Saved configuration on exit");
@@ -947,6 +3366,7 @@ impl eframe::App for DashboardApp {
 
         // Save any pending zone changes
         if self.dashboard_state.vision.zones_dirty {
+            self.backup_if_zones_removed();
             if let (Some(ref mut profile), Some(ref profiles_dir)) =
                 (&mut self.active_profile, &self.profiles_dir)
             {
@@ -981,17 +3401,129 @@ impl DashboardApp {
 
         if let Some(cmd) = command {
             match cmd {
-                CaptureCommand::Start => {
-                    if let Err(e) = self.start_capture() {
-                        let mut state = self.shared_state.write();
-                        state.runtime.set_error(e);
-                    }
-                }
+                CaptureCommand::Start => match self.start_capture() {
+                    Ok(()) => self.record_usage(Feature::CaptureStarted),
+                    Err(e) => self.report_error(AppError::Capture(e)),
+                },
                 CaptureCommand::Stop => {
                     self.stop_capture();
                 }
+                CaptureCommand::Screenshot { target, path } => {
+                    self.take_screenshot(target, path);
+                }
+            }
+        }
+    }
+
+    /// Save a screenshot: of the next frame of the running capture, or of a
+    /// single frame grabbed from another target (or the selected one while
+    /// capture is stopped)
+    fn take_screenshot(&mut self, target: Option<CaptureTarget>, path: Option<PathBuf>) {
+        let selected = self.shared_state.read().capture_config.target.clone();
+        if self.is_capturing() && target.as_ref().map_or(true, |t| *t == selected) {
+            self.pending_screenshot = Some(path);
+            return;
+        }
+
+        let target = target.unwrap_or(selected);
+        let shared_state = self.shared_state.clone();
+        std::thread::spawn(move || match crate::capture::capture_frame_once(&target) {
+            Ok(frame) => save_screenshot_in_background(&shared_state, Arc::new(frame), path),
+            Err(e) => shared_state.write().runtime.saved_screenshot = Some(Err(format!("{:#}", e))),
+        });
+    }
+
+    /// Report screenshots that were just saved
+    fn process_screenshots(&mut self) {
+        let Some(saved) = self.shared_state.write().runtime.saved_screenshot.take() else {
+            return;
+        };
+        match saved {
+            Ok(path) => {
+                self.record_event(
+                    "screenshot",
+                    &format!("Screenshot saved to {}", path.display()),
+                );
+                {
+                    let recent = &mut self.shared_state.write().runtime.recent_screenshots;
+                    recent.retain(|p| *p != path);
+                    recent.insert(0, path);
+                    recent.truncate(screenshots::RECENT_SCREENSHOTS);
+                }
+                self.dispatch_tip(Tip {
+                    id: "screenshot_saved".to_string(),
+                    message: "Screenshot saved".to_string(),
+                    priority: 50,
+                    duration_ms: Some(2_000),
+                    ..Default::default()
+                });
+            }
+            Err(e) => {
+                self.report_error(AppError::Capture(format!(
+                    "Failed to take screenshot: {}",
+                    e
+                )));
+            }
+        }
+    }
+
+    /// Export a snapshot when asked and report the export that finished
+    fn process_snapshot_export(&mut self) {
+        let (requested, saved) = {
+            let mut state = self.shared_state.write();
+            let requested = std::mem::take(&mut state.runtime.export_snapshot);
+            (requested, state.runtime.saved_snapshot.take())
+        };
+
+        match saved {
+            Some(Ok(path)) => {
+                self.record_event("snapshot", &format!("Snapshot saved to {}", path.display()));
+                let mut state = self.shared_state.write();
+                state.runtime.exporting_snapshot = false;
+                state.runtime.last_snapshot = Some(path);
             }
+            Some(Err(e)) => {
+                self.shared_state.write().runtime.exporting_snapshot = false;
+                self.report_error(AppError::Storage(format!(
+                    "Failed to export snapshot: {}",
+                    e
+                )));
+            }
+            None => {}
+        }
+
+        if !requested {
+            return;
         }
+        let events = match self.database {
+            Some(ref db) => db
+                .recent_events(snapshot::SNAPSHOT_EVENTS)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read events for snapshot: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+        let (config, profile) = {
+            let mut state = self.shared_state.write();
+            state.runtime.exporting_snapshot = true;
+            (state.config.clone(), state.active_profile().cloned())
+        };
+
+        let shared_state = self.shared_state.clone();
+        std::thread::spawn(move || {
+            let result = crate::storage::get_samples_dir()
+                .and_then(|samples_dir| {
+                    snapshot::Snapshot::capture(&config, profile.as_ref(), &samples_dir, events)
+                })
+                .and_then(|snapshot| {
+                    let path = snapshot::snapshot_path(&crate::storage::get_snapshots_dir()?);
+                    snapshot.save(&path)?;
+                    Ok(path)
+                })
+                .map_err(|e| format!("{:#}", e));
+            shared_state.write().runtime.saved_snapshot = Some(result);
+        });
     }
 
     /// Process overlay commands from the UI
@@ -1005,12 +3537,10 @@ impl DashboardApp {
 
         if let Some(cmd) = command {
             match cmd {
-                OverlayCommand::Start => {
-                    if let Err(e) = self.start_overlay() {
-                        let mut state = self.shared_state.write();
-                        state.runtime.set_error(e);
-                    }
-                }
+                OverlayCommand::Start => match self.start_overlay() {
+                    Ok(()) => self.record_usage(Feature::OverlayStarted),
+                    Err(e) => self.report_error(AppError::Overlay(e)),
+                },
                 OverlayCommand::Stop => {
                     self.stop_overlay();
                 }
@@ -1024,14 +3554,72 @@ impl DashboardApp {
 
     /// Check if overlay thread has stopped unexpectedly
     fn check_overlay_status(&mut self) {
+        if let Some(ref manager) = self.overlay_manager {
+            let dropped = manager.dropped_events();
+            let queued = manager.queued_events();
+            let mut state = self.shared_state.write();
+            state.runtime.overlay_queue_depth = queued;
+            if dropped > state.runtime.dropped_overlay_events {
+                tracing::warn!(
+                    "Overlay fell behind: {} tips and commands dropped",
+                    dropped - state.runtime.dropped_overlay_events
+                );
+            }
+            state.runtime.dropped_overlay_events = dropped;
+        }
+
         if self.overlay_manager.is_some() && !self.is_overlay_running() {
             // Overlay thread has stopped
             self.overlay_manager = None;
             self.overlay_handle = None;
 
-            let mut state = self.shared_state.write();
-            state.runtime.is_overlay_running = false;
-            state.runtime.overlay_visible = false;
+            let mut state = self.shared_state.write();
+            state.runtime.is_overlay_running = false;
+            state.runtime.overlay_visible = false;
+            state.runtime.overlay_queue_depth = 0;
+        }
+    }
+
+    /// Rewrite config.toml from the loaded config, dropping unknown keys and invalid values
+    ///
+    /// The original file is kept as config.toml.bak.
+    fn process_config_rewrite(&mut self) {
+        if !std::mem::take(&mut self.shared_state.write().runtime.rewrite_config) {
+            return;
+        }
+
+        let Some(ref config_dir) = self.config_dir else {
+            return;
+        };
+        let config_path = config_dir.join("config.toml");
+        let backup_path = config_dir.join("config.toml.bak");
+        self.storage_writer.flush();
+
+        // An earlier save already kept the file as loaded
+        let result = if config_path.exists() && !self.config_backed_up {
+            std::fs::copy(&config_path, &backup_path)
+                .map(|_| ())
+                .map_err(anyhow::Error::from)
+        } else {
+            Ok(())
+        }
+        .and_then(|_| {
+            let state = self.shared_state.read();
+            crate::config::save_config(&state.config, &config_path)
+        });
+
+        match result {
+            Ok(()) => {
+                self.config_backed_up = true;
+                tracing::info!("Rewrote config, original saved to {:?}", backup_path);
+                self.shared_state.write().runtime.config_issues.clear();
+            }
+            Err(e) => {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to rewrite config: {}",
+                    e
+                )));
+            }
         }
     }
 
@@ -1050,22 +3638,23 @@ impl DashboardApp {
                     self.deactivate_profile();
                 }
                 ProfileAction::Create(profile) => {
+                    // Importing over an existing profile replaces it
+                    self.backup_profile(&profile.id, BackupReason::Import);
+
                     // Save profile to disk
                     if let Some(ref profiles_dir) = self.profiles_dir {
                         let profile_path = profiles_dir.join(format!("{}.json", profile.id));
-                        if let Err(e) =
-                            crate::storage::profiles::save_profile(&profile, &profile_path)
-                        {
-                            tracing::error!("Failed to save new profile '{}': {}", profile.name, e);
-                        } else {
-                            tracing::info!("Created and saved profile '{}' to disk", profile.name);
-                        }
+                        self.storage_writer.save_profile(&profile, profile_path);
+                        tracing::info!("Created profile '{}'", profile.name);
                     }
                     // Add to shared state
                     let mut state = self.shared_state.write();
+                    state.runtime.usage.record(Feature::ProfileCreated);
                     state.add_profile(profile);
                 }
                 ProfileAction::Delete(profile_id) => {
+                    self.backup_profile(&profile_id, BackupReason::DeleteProfile);
+
                     // Remove from shared state first
                     {
                         let mut state = self.shared_state.write();
@@ -1076,11 +3665,10 @@ impl DashboardApp {
                         if let Err(e) =
                             crate::storage::profiles::delete_profile(profiles_dir, &profile_id)
                         {
-                            tracing::error!(
+                            self.report_error(AppError::Storage(format!(
                                 "Failed to delete profile '{}' from disk: {}",
-                                profile_id,
-                                e
-                            );
+                                profile_id, e
+                            )));
                         } else {
                             tracing::info!("Deleted profile '{}' from disk", profile_id);
                         }
@@ -1090,147 +3678,927 @@ impl DashboardApp {
                         self.active_profile = None;
                         self.dashboard_state.vision.ocr_zones.clear();
                         self.dashboard_state.vision.zone_ocr_results.clear();
+                        self.dashboard_state.vision.zone_ocr_stats.clear();
+                        self.dashboard_state.vision.zone_samples.clear();
+                        self.dashboard_state.vision.zone_scroll_logs.clear();
+                        self.dashboard_state.vision.zone_recorded_values.clear();
                         self.reload_screens_from_profile();
                     }
                 }
+                ProfileAction::ImportShared(profile_id, item) => {
+                    self.import_shared_item(&profile_id, item);
+                }
+                ProfileAction::RestoreBackup(backup) => {
+                    self.restore_backup(&backup);
+                }
+                ProfileAction::UpdateDictionary(profile_id, dictionary) => {
+                    self.update_profile_dictionary(&profile_id, dictionary);
+                }
+                ProfileAction::UpdateVariables(profile_id, variables) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Updated {} variable(s) of profile '{}'",
+                            variables.len(),
+                            profile.name
+                        );
+                        profile.variables = variables;
+                    });
+                }
+                ProfileAction::UpdateAutoStart(profile_id, auto_start) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Auto-start of profile '{}': {:?}",
+                            profile.name,
+                            auto_start
+                        );
+                        profile.auto_start = auto_start;
+                    });
+                }
+                ProfileAction::UpdateSnapshotCapture(profile_id, snapshot_capture) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Snapshot capture of profile '{}': {:?}",
+                            profile.name,
+                            snapshot_capture
+                        );
+                        profile.snapshot_capture = snapshot_capture;
+                    });
+                }
+                ProfileAction::UpdateSecondaryWindows(profile_id, windows) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Updated {} secondary window(s) of profile '{}'",
+                            windows.len(),
+                            profile.name
+                        );
+                        profile.secondary_windows = windows;
+                    });
+                }
+                ProfileAction::UpdatePhases(profile_id, phases) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Updated {} phase(s) of profile '{}'",
+                            phases.len(),
+                            profile.name
+                        );
+                        profile.phases = phases;
+                    });
+                }
+                ProfileAction::UpdateLibraryRules(profile_id, references) => {
+                    self.update_profile(&profile_id, |profile| {
+                        tracing::info!(
+                            "Attached {} library rule(s) to profile '{}'",
+                            references.len(),
+                            profile.name
+                        );
+                        profile.library_rules = references;
+                    });
+                }
+                ProfileAction::SaveLibraryRule(rule) => {
+                    let name = rule.name.clone();
+                    if self.save_library_rule(rule) {
+                        self.dashboard_state.profiles.library_status =
+                            Some(format!("Saved '{}' to the library", name));
+                    }
+                }
+                ProfileAction::DeleteLibraryRule(rule_id) => {
+                    self.delete_library_rule(&rule_id);
+                }
+            }
+        }
+    }
+
+    /// Restore a profile backup, replacing (and backing up) the current version
+    fn restore_backup(&mut self, backup: &ProfileBackup) {
+        let profile = match backup.load() {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.report_error(AppError::Storage(e.to_string()));
+                return;
+            }
+        };
+
+        let is_active = self
+            .active_profile
+            .as_ref()
+            .is_some_and(|p| p.id == profile.id);
+        if is_active {
+            // Flush unsaved zone edits so they are part of the backup below
+            self.save_current_zones_to_profile();
+        }
+        self.backup_profile(&profile.id, BackupReason::Restore);
+
+        if let Some(ref profiles_dir) = self.profiles_dir {
+            let profile_path = profiles_dir.join(format!("{}.json", profile.id));
+            if let Err(e) = crate::storage::profiles::save_profile(&profile, &profile_path) {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to restore profile '{}': {}",
+                    profile.name, e
+                )));
+                return;
+            }
+        }
+        tracing::info!("Restored profile '{}' from {:?}", profile.name, backup.path);
+
+        let profile_id = profile.id.clone();
+        self.shared_state.write().add_profile(profile);
+        if is_active {
+            // Clear first so activation does not save the old zones over the restored file
+            self.active_profile = None;
+            self.activate_profile(&profile_id);
+        }
+    }
+
+    /// Add a zone, screen or rule from a share code to a profile and save it
+    fn import_shared_item(&mut self, profile_id: &str, mut item: SharedItem) {
+        // A library rule goes into the library, under a new ID if another
+        // rule already has its ID
+        if let SharedItem::LibraryRule(ref mut rule) = item {
+            let library = self.shared_state.read().rule_library.clone();
+            if library.iter().any(|r| r.id == rule.id && r != rule) {
+                let existing: Vec<&str> = library.iter().map(|r| r.id.as_str()).collect();
+                rule.id = unique_id(&rule.id, &existing);
+            }
+            if !self.save_library_rule(rule.clone()) {
+                return;
+            }
+        }
+
+        let kind = item.kind_name();
+        let updated = self.update_profile(profile_id, |profile| {
+            let id = item.add_to(profile);
+            tracing::info!("Imported {} '{}' into profile '{}'", kind, id, profile.name);
+        });
+        if updated {
+            self.record_usage(Feature::ShareCodeImported);
+        }
+    }
+
+    /// Add or replace a rule in the rules library and save it
+    ///
+    /// Returns false if it couldn't be saved.
+    fn save_library_rule(&mut self, rule: LibraryRule) -> bool {
+        if let Some(ref rules_dir) = self.rules_dir {
+            if let Err(e) = crate::storage::rule_library::save_library_rule(&rule, rules_dir) {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to save library rule '{}': {}",
+                    rule.name, e
+                )));
+                return false;
+            }
+        }
+        tracing::info!("Saved library rule '{}'", rule.name);
+
+        let mut state = self.shared_state.write();
+        let library = &mut state.rule_library;
+        library.retain(|r| r.id != rule.id);
+        library.push(rule);
+        library.sort_by_key(|r| r.name.to_lowercase());
+        true
+    }
+
+    /// Delete a rule from the library and detach it from every profile
+    fn delete_library_rule(&mut self, rule_id: &str) {
+        if let Some(ref rules_dir) = self.rules_dir {
+            if let Err(e) = crate::storage::rule_library::delete_library_rule(rules_dir, rule_id) {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to delete library rule '{}': {}",
+                    rule_id, e
+                )));
+                return;
+            }
+        }
+
+        let attached_to: Vec<String> = {
+            let mut state = self.shared_state.write();
+            state.rule_library.retain(|r| r.id != rule_id);
+            state
+                .profiles
+                .iter()
+                .filter(|p| p.library_rules.iter().any(|r| r.rule_id == rule_id))
+                .map(|p| p.id.clone())
+                .collect()
+        };
+        for profile_id in attached_to {
+            self.update_profile(&profile_id, |profile| {
+                profile.library_rules.retain(|r| r.rule_id != rule_id);
+            });
+        }
+        tracing::info!("Deleted library rule '{}'", rule_id);
+    }
+
+    /// Replace a profile's recognition dictionary
+    fn update_profile_dictionary(&mut self, profile_id: &str, dictionary: RecognitionDictionary) {
+        self.update_profile(profile_id, |profile| {
+            tracing::info!(
+                "Updated dictionary of profile '{}': {} substitution(s), {} word(s)",
+                profile.name,
+                dictionary.substitutions.len(),
+                dictionary.vocabulary.len()
+            );
+            profile.dictionary = dictionary;
+        });
+    }
+
+    /// Apply a change to a profile, save it and refresh the active profile
+    ///
+    /// Returns false if the profile doesn't exist.
+    fn update_profile(&mut self, profile_id: &str, update: impl FnOnce(&mut GameProfile)) -> bool {
+        let is_active = self
+            .active_profile
+            .as_ref()
+            .is_some_and(|p| p.id == profile_id);
+
+        let profile = if is_active {
+            self.active_profile.clone().map(|mut profile| {
+                // Zones of the active profile live in the vision state
+                profile.ocr_regions = self.dashboard_state.vision.ocr_zones.clone();
+                profile
+            })
+        } else {
+            let state = self.shared_state.read();
+            state.profiles.iter().find(|p| p.id == profile_id).cloned()
+        };
+        let Some(mut profile) = profile else {
+            tracing::warn!("Cannot update unknown profile '{}'", profile_id);
+            return false;
+        };
+
+        update(&mut profile);
+
+        if let Some(ref profiles_dir) = self.profiles_dir {
+            let profile_path = profiles_dir.join(format!("{}.json", profile.id));
+            self.storage_writer.save_profile(&profile, profile_path);
+        }
+
+        if is_active {
+            self.dashboard_state.vision.ocr_zones = profile.ocr_regions.clone();
+            self.active_profile = Some(profile.clone());
+            self.reload_screens_from_profile();
+        }
+        self.shared_state.write().add_profile(profile);
+        true
+    }
+
+    /// Sync overlay config from shared state to the running overlay (only when changed)
+    fn sync_overlay_config(&mut self) {
+        if let Some(manager) = &self.overlay_manager {
+            let config = {
+                let shared = self.shared_state.read();
+                let mut config = shared.overlay_config.clone();
+                // Second monitor mode: off the monitor of a full screen game
+                if let Some(index) = shared.runtime.fullscreen_game.and_then(|g| g.tips_monitor) {
+                    config.monitor_index = Some(index);
+                }
+                config
+            };
+
+            // Only sync if config has changed
+            let should_sync = match &self.last_synced_overlay_config {
+                Some(last) => last != &config,
+                None => true,
+            };
+
+            if should_sync {
+                manager.set_config(config.clone());
+                self.last_synced_overlay_config = Some(config);
+            }
+        }
+    }
+
+    /// Send the test tip requested by the dashboard, or the tip designer's
+    /// repeating tip when it's due
+    fn process_test_tip(&mut self) {
+        let mut design = self.shared_state.write().runtime.send_test_tip.take();
+
+        let overlay_state = &self.dashboard_state.overlay;
+        if design.is_none() && overlay_state.repeat_test_tip {
+            let interval =
+                Duration::from_secs(u64::from(overlay_state.repeat_interval_secs.max(1)));
+            if self
+                .last_repeated_test_tip
+                .map_or(true, |last| last.elapsed() >= interval)
+            {
+                self.last_repeated_test_tip = Some(Instant::now());
+                design = Some(overlay_state.tip_design.clone());
+            }
+        }
+
+        let Some(design) = design else {
+            return;
+        };
+
+        // Auto-start overlay if not running so the test tip can be seen
+        if self.overlay_manager.is_none() {
+            tracing::info!("Auto-starting overlay for test tip");
+            if let Err(e) = self.start_overlay() {
+                // Don't retry every frame
+                self.dashboard_state.overlay.repeat_test_tip = false;
+                self.report_error(AppError::Overlay(format!(
+                    "Failed to start overlay for test tip: {}",
+                    e
+                )));
+                return;
+            }
+            // Small sleep to ensure the overlay thread has initialized before sending tips
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        // Placeholders are filled from the active profile's variables
+        let variables = self
+            .shared_state
+            .read()
+            .active_profile()
+            .map(|profile| profile.variable_values())
+            .unwrap_or_default();
+
+        let tip = Tip {
+            id: format!(
+                "test_{}",
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis()
+            ),
+            message: fill_template(&design.full_message(), &variables),
+            priority: design.priority,
+            duration_ms: design.duration_ms,
+            play_sound: design.play_sound,
+            ..Default::default()
+        };
+        if self.dispatch_tip(tip) {
+            self.shared_state.write().runtime.tips_displayed += 1;
+        }
+    }
+
+    /// Poll for global hotkey events
+    fn poll_hotkeys(&mut self, ctx: &egui::Context) {
+        use crate::hotkey::HotkeyEvent;
+
+        let event = if let Some(ref hotkey_manager) = self.hotkey_manager {
+            hotkey_manager.poll_events()
+        } else {
+            HotkeyEvent::None
+        };
+
+        match event {
+            HotkeyEvent::None => {}
+            HotkeyEvent::ToggleOverlay => {
+                // Auto-start overlay if it's not running
+                if self.overlay_manager.is_none() {
+                    tracing::info!("Auto-starting overlay on toggle hotkey");
+                    if let Err(e) = self.start_overlay() {
+                        self.report_error(AppError::Overlay(e));
+                    }
+                }
+            }
+            HotkeyEvent::EnterZoneSelection => {
+                // Request zone selection mode
+                self.dashboard_state.vision.pending_zone_selection_mode = true;
+            }
+            HotkeyEvent::ReadScreen => {
+                if self.read_screen() {
+                    // Bring the dashboard forward so the panel is visible
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+            }
+            HotkeyEvent::TickChecklist => self.tick_checklist(),
+            HotkeyEvent::SaveReplay => {
+                self.shared_state.write().runtime.save_replay = Some("hotkey".to_string());
+            }
+            HotkeyEvent::Screenshot => self.take_screenshot(None, None),
+        }
+    }
+
+    /// The vision pipeline, created and with the selected backend initialized
+    fn ready_pipeline(&mut self) -> anyhow::Result<&mut VisionPipeline> {
+        let backend = self.dashboard_state.vision.selected_backend;
+        if self.vision_pipeline.is_none() {
+            self.vision_pipeline = Some(Self::create_vision_pipeline(&self.shared_state)?);
+        }
+
+        let pipeline = self
+            .vision_pipeline
+            .as_mut()
+            .expect("vision pipeline was just created");
+        pipeline.set_backend(backend);
+        if !pipeline.is_backend_ready(backend) {
+            pipeline.init_backend(backend)?;
+        }
+        Ok(pipeline)
+    }
+
+    /// Start a requested batch OCR run and process its next image
+    ///
+    /// One image is read per frame so the dashboard stays responsive during
+    /// long runs.
+    fn process_batch_ocr(&mut self) {
+        let batch = &mut self.dashboard_state.data.batch;
+        if std::mem::take(&mut batch.pending_start) {
+            let folder = std::path::PathBuf::from(batch.folder.trim());
+            let output = match batch.output.trim() {
+                "" => folder.join(crate::vision::batch::DEFAULT_OUTPUT_NAME),
+                path => std::path::PathBuf::from(path),
+            };
+            batch.status = None;
+            match BatchRun::start(&folder, &output, &self.dashboard_state.vision.ocr_zones) {
+                Ok(run) => {
+                    tracing::info!("Batch OCR of {} images in {:?}", run.total(), folder);
+                    batch.run = Some(run);
+                    batch.error = None;
+                }
+                Err(e) => batch.error = Some(format!("{:#}", e)),
+            }
+        }
+
+        let Some(mut run) = self.dashboard_state.data.batch.run.take() else {
+            return;
+        };
+
+        let preprocessing = self.dashboard_state.vision.preprocessing.clone();
+        let dictionary = self.active_profile.as_ref().map(|p| p.dictionary.clone());
+        let result = self
+            .ready_pipeline()
+            .and_then(|pipeline| run.process_next(pipeline, &preprocessing, dictionary.as_ref()));
+
+        let batch = &mut self.dashboard_state.data.batch;
+        match result {
+            Ok(()) if run.is_done() => {
+                let mut status = format!(
+                    "Wrote {} rows to {}",
+                    run.total() - run.failed(),
+                    run.output().display()
+                );
+                if run.failed() > 0 {
+                    status.push_str(&format!(" ({} images could not be opened)", run.failed()));
+                }
+                batch.status = Some(status);
+            }
+            Ok(()) => batch.run = Some(run),
+            Err(e) => batch.error = Some(format!("Batch OCR failed: {:#}", e)),
+        }
+    }
+
+    /// Keep the sample frame list in sync with the active profile and run the
+    /// Vision view's sample library actions
+    fn process_sample_frames(&mut self) {
+        use crate::storage::samples::{delete_sample, list_samples, save_sample};
+
+        let profile_id = self.active_profile.as_ref().map(|p| p.id.clone());
+        let vision = &mut self.dashboard_state.vision;
+
+        if vision.sample_frames_profile != profile_id {
+            vision.sample_frames = match (&profile_id, crate::storage::get_samples_dir()) {
+                (Some(id), Ok(dir)) => list_samples(&dir, id),
+                _ => Vec::new(),
+            };
+            vision.sample_frames_profile = profile_id.clone();
+            vision.shown_sample = None;
+        }
+
+        let Some(action) = vision.pending_sample_action.take() else {
+            return;
+        };
+        let Some(profile_id) = profile_id else {
+            return;
+        };
+        let samples_dir = match crate::storage::get_samples_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                vision.last_error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+
+        match action {
+            SampleAction::Save(name) => {
+                let Some(ref data) = vision.last_frame_data else {
+                    return;
+                };
+                let frame = crate::capture::CapturedFrame::new(
+                    data.clone(),
+                    vision.last_frame_width,
+                    vision.last_frame_height,
+                );
+                match save_sample(&samples_dir, &profile_id, &name, &frame) {
+                    Ok(sample) => {
+                        tracing::info!("Saved sample frame '{}' to {:?}", sample.name, sample.path);
+                        vision.sample_frames = list_samples(&samples_dir, &profile_id);
+                    }
+                    Err(e) => vision.last_error = Some(format!("{:#}", e)),
+                }
+            }
+            SampleAction::Show(index) => {
+                let Some(sample) = vision.sample_frames.get(index) else {
+                    return;
+                };
+                match sample.load() {
+                    Ok(frame) => {
+                        vision.shown_sample = Some(sample.name.clone());
+                        vision.showing_clipboard_image = false;
+                        vision.frozen_frame = Some(frame.clone());
+                        vision.frozen_ocr_inputs = None;
+                        vision.last_ocr_results.clear();
+                        vision.zone_ocr_results.clear();
+                        vision.pending_preview = Some(frame);
+                    }
+                    Err(e) => vision.last_error = Some(format!("{:#}", e)),
+                }
+            }
+            SampleAction::Delete(index) => {
+                if index >= vision.sample_frames.len() {
+                    return;
+                }
+                let sample = vision.sample_frames.remove(index);
+                if let Err(e) = delete_sample(&sample) {
+                    vision.last_error = Some(format!("{:#}", e));
+                }
+                if vision.shown_sample.as_deref() == Some(sample.name.as_str()) {
+                    vision.shown_sample = None;
+                }
+                vision.sample_frames = list_samples(&samples_dir, &profile_id);
+            }
+            SampleAction::Validate => {
+                let samples = vision.sample_frames.clone();
+                let backend = vision.selected_backend;
+                self.dashboard_state.validation = Some(ValidationState::new(samples, backend));
+            }
+        }
+    }
+
+    /// Validate the next sample of a running profile validation
+    ///
+    /// Uses the zones and preprocessing as currently edited, and the active
+    /// profile's screens. A new sample's results are kept as its expectation
+    /// when they pass.
+    fn process_validation(&mut self) {
+        let Some(ref validation) = self.dashboard_state.validation else {
+            return;
+        };
+        let Some(sample) = validation.samples.get(validation.results.len()).cloned() else {
+            return;
+        };
+
+        let zones = self.dashboard_state.vision.ocr_zones.clone();
+        let preprocessing = self.dashboard_state.vision.preprocessing.clone();
+        let (screens, dictionary) = self
+            .active_profile
+            .as_ref()
+            .map(|p| (p.screens.clone(), p.dictionary.clone()))
+            .unwrap_or_default();
+        let expectation = sample.load_expectation();
+
+        let result = match sample.load() {
+            Ok(frame) => match self.ready_pipeline() {
+                Ok(pipeline) => validate_sample(
+                    pipeline,
+                    &frame,
+                    &sample.name,
+                    &zones,
+                    &screens,
+                    &dictionary,
+                    &preprocessing,
+                    expectation.as_ref(),
+                ),
+                Err(e) => {
+                    SampleValidation::load_failed(&sample.name, format!("OCR unavailable: {:#}", e))
+                }
+            },
+            Err(e) => SampleValidation::load_failed(&sample.name, format!("{:#}", e)),
+        };
+
+        if result.new_baseline && result.passed() {
+            if let Err(e) = sample.save_expectation(&result.expectation()) {
+                tracing::warn!("Failed to store results of sample '{}': {}", sample.name, e);
+            }
+        }
+
+        if let Some(ref mut validation) = self.dashboard_state.validation {
+            validation.results.push(result);
+            if validation.is_done() {
+                let failed = validation.results.iter().filter(|r| !r.passed()).count();
+                tracing::info!(
+                    "Validated profile against {} samples: {} failed",
+                    validation.results.len(),
+                    failed
+                );
             }
         }
     }
 
-    /// Sync overlay config from shared state to the running overlay (only when changed)
-    fn sync_overlay_config(&mut self) {
-        if let Some(manager) = &self.overlay_manager {
-            let config = self.shared_state.read().overlay_config.clone();
-
-            // Only sync if config has changed
-            let should_sync = match &self.last_synced_overlay_config {
-                Some(last) => last != &config,
-                None => true,
-            };
+    /// Store the validation results as every sample's expected results and
+    /// validate again
+    fn accept_validation_results(&mut self) {
+        let Some(ref validation) = self.dashboard_state.validation else {
+            return;
+        };
 
-            if should_sync {
-                manager.set_config(config.clone());
-                self.last_synced_overlay_config = Some(config);
+        for (sample, result) in validation.samples.iter().zip(&validation.results) {
+            if result.error.is_some() {
+                continue;
+            }
+            if let Err(e) = sample.save_expectation(&result.expectation()) {
+                self.report_error(AppError::Storage(format!(
+                    "Failed to store results of sample '{}': {}",
+                    sample.name, e
+                )));
             }
         }
+
+        let samples = validation.samples.clone();
+        let backend = self.dashboard_state.vision.selected_backend;
+        self.dashboard_state.validation = Some(ValidationState::new(samples, backend));
     }
 
-    /// Process test tip request
-    fn process_test_tip(&mut self) {
-        let (should_send, custom_message) = {
-            let mut state = self.shared_state.write();
-            let send = state.runtime.send_test_tip;
-            let custom_message = state.runtime.test_tip_message.take();
-            state.runtime.send_test_tip = false;
-            (send, custom_message)
+    /// Fit the validated backend's confidence calibration to the labeled
+    /// readings of the validation and store it in the active profile
+    fn calibrate_confidence(&mut self) {
+        let Some(ref mut validation) = self.dashboard_state.validation else {
+            return;
+        };
+        let readings: Vec<(f32, bool)> = validation
+            .results
+            .iter()
+            .flat_map(|r| r.labeled_readings())
+            .collect();
+        let Some(calibration) = calibration::fit(validation.backend, &readings) else {
+            validation.calibration_note = Some(format!(
+                "Need at least {} labeled readings to calibrate",
+                calibration::MIN_SAMPLES
+            ));
+            return;
+        };
+        let Some(ref mut profile) = self.active_profile else {
+            validation.calibration_note = Some("No active profile to calibrate".to_string());
+            return;
         };
 
-        if should_send {
-            // Auto-start overlay if not running so the test tip can be seen
-            if self.overlay_manager.is_none() {
-                tracing::info!("Auto-starting overlay for test tip");
-                if let Err(e) = self.start_overlay() {
-                    tracing::error!("Failed to start overlay for test tip: {}", e);
-                    let mut state = self.shared_state.write();
-                    state
-                        .runtime
-                        .set_error(format!("Failed to start overlay: {}", e));
-                    return;
-                }
-                // Small sleep to ensure the overlay thread has initialized before sending tips
-                std::thread::sleep(std::time::Duration::from_millis(100));
+        let right = readings.iter().filter(|(_, right)| *right).count();
+        validation.calibration_note = Some(format!(
+            "Calibrated from {} readings ({:.0}% right)",
+            readings.len(),
+            right as f32 * 100.0 / readings.len() as f32
+        ));
+        tracing::info!(
+            "Calibrated {:?} confidence of profile '{}' from {} readings",
+            calibration.backend,
+            profile.name,
+            readings.len()
+        );
+
+        profile
+            .confidence_calibration
+            .retain(|c| c.backend != calibration.backend);
+        profile.confidence_calibration.push(calibration);
+        let calibrations = profile.confidence_calibration.clone();
+        if let Some(shared_profile) = self
+            .shared_state
+            .write()
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == profile.id)
+        {
+            shared_profile.confidence_calibration = calibrations;
+        }
+
+        if let Some(ref profiles_dir) = self.profiles_dir {
+            let profile_path = profiles_dir.join(format!("{}.json", profile.id));
+            self.storage_writer.save_profile(profile, profile_path);
+        }
+    }
+
+    /// Give the vision pipeline the active profile's confidence calibration
+    /// (only when changed)
+    fn sync_pipeline_calibration(&mut self) {
+        let Some(ref mut pipeline) = self.vision_pipeline else {
+            return;
+        };
+        let calibration = self
+            .active_profile
+            .as_ref()
+            .map_or(&[][..], |p| p.confidence_calibration.as_slice());
+        if pipeline.calibration() != calibration {
+            pipeline.set_calibration(calibration.to_vec());
+        }
+    }
+
+    /// OCR an image pasted to the clipboard and show it in the Vision view
+    ///
+    /// The whole image is read with the global preprocessing, and every enabled
+    /// zone is read from it as if it were a captured frame. Live zone OCR pauses
+    /// until the user returns to the live preview.
+    fn process_clipboard_ocr(&mut self) {
+        if !std::mem::take(&mut self.dashboard_state.vision.pending_clipboard_ocr) {
+            return;
+        }
+
+        let frame = match crate::capture::clipboard::frame_from_clipboard() {
+            Ok(frame) => frame,
+            Err(e) => {
+                self.dashboard_state.vision.last_error = Some(e.to_string());
+                return;
             }
+        };
+        tracing::info!("OCR of clipboard image ({}x{})", frame.width, frame.height);
+
+        let preprocessing =
+            Some(self.dashboard_state.vision.preprocessing.clone()).filter(|pp| pp.enabled);
+        let result = self.ready_pipeline().and_then(|pipeline| {
+            pipeline.process_region_with_preprocessing(
+                &frame,
+                0,
+                0,
+                frame.width,
+                frame.height,
+                preprocessing.as_ref(),
+            )
+        });
 
-            if let Some(manager) = &self.overlay_manager {
-                // Get candidate screens from active profile
-                let candidate_screens: Vec<String> = {
-                    let state = self.shared_state.read();
-                    if let Some(profile) = state.active_profile() {
-                        profile.screens.iter().map(|s| s.name.clone()).collect()
-                    } else {
-                        Vec::new()
-                    }
-                };
+        let vision_state = &mut self.dashboard_state.vision;
+        match result {
+            Ok(result) => {
+                vision_state.last_ocr_results = result
+                    .text_regions
+                    .into_iter()
+                    .map(|region| OcrResultDisplay {
+                        text: region.text,
+                        bounds: region.bounds,
+                        confidence: region.confidence,
+                    })
+                    .collect();
+                vision_state.last_processing_time_ms = result.processing_time_ms;
+                vision_state.last_error = None;
+            }
+            Err(e) => {
+                vision_state.last_error = Some(format!("Clipboard OCR failed: {}", e));
+                return;
+            }
+        }
 
-                // Use the custom message from the dashboard if provided, otherwise default to a generic success tip
-                let (base_msg, priority) = if let Some((msg, prio)) = custom_message {
-                    (msg, prio)
-                } else {
-                    ("This is a test tip from GamersToolKit!".to_string(), 50)
-                };
+        vision_state.last_frame_data = Some(frame.data.clone());
+        vision_state.last_frame_width = frame.width;
+        vision_state.last_frame_height = frame.height;
+        vision_state.showing_clipboard_image = true;
+        vision_state.frozen_frame = None;
+        vision_state.frozen_ocr_inputs = None;
+        vision_state.shown_sample = None;
+        vision_state.zone_ocr_results.clear();
 
-                if candidate_screens.is_empty() {
-                    let tip = Tip {
-                        id: format!(
-                            "test_{}",
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_millis()
-                        ),
-                        message: base_msg,
-                        priority,
-                        duration_ms: Some(5000),
-                        play_sound: false,
-                    };
-                    manager.show_tip(tip);
-                } else {
-                    for (i, screen_name) in candidate_screens.iter().enumerate() {
-                        let tip = Tip {
-                            id: format!(
-                                "test_{}_{}",
-                                i,
-                                std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_millis()
-                            ),
-                            message: format!("[{}]: {}", screen_name, base_msg),
-                            priority,
-                            duration_ms: Some(5000),
-                            play_sound: false,
-                        };
-                        manager.show_tip(tip);
-                    }
-                }
+        self.ocr_zones_in_frame(FrameSource::Cpu(&frame), None, None);
 
-                // Update tips displayed count
-                let mut state = self.shared_state.write();
-                state.runtime.tips_displayed += std::cmp::max(1, candidate_screens.len());
+        self.dashboard_state.vision.pending_preview = Some(frame);
+        self.dashboard_state.current_view = DashboardView::Vision;
+    }
+
+    /// Read the previewed frame with both OCR backends for the Vision view's
+    /// side-by-side comparison
+    ///
+    /// A live preview is compared again every few seconds; a frozen frame or
+    /// clipboard image only when asked.
+    fn process_backend_compare(&mut self) {
+        const COMPARE_INTERVAL: Duration = Duration::from_secs(3);
+
+        let vision_state = &mut self.dashboard_state.vision;
+        if !vision_state.compare_backends {
+            vision_state.backend_comparison = None;
+            vision_state.backend_compare_error = None;
+            return;
+        }
+        let live = vision_state.frozen_frame.is_none() && !vision_state.showing_clipboard_image;
+        let due = std::mem::take(&mut vision_state.pending_backend_compare)
+            || (vision_state.backend_comparison.is_none()
+                && vision_state.backend_compare_error.is_none())
+            || (live
+                && self
+                    .last_backend_compare
+                    .map_or(true, |last| last.elapsed() >= COMPARE_INTERVAL));
+        if !due || vision_state.is_processing {
+            return;
+        }
+        let Some(data) = vision_state.last_frame_data.clone() else {
+            return;
+        };
+        let frame = crate::capture::CapturedFrame::new(
+            data,
+            vision_state.last_frame_width,
+            vision_state.last_frame_height,
+        );
+        let preprocessing = Some(vision_state.preprocessing.clone()).filter(|pp| pp.enabled);
+
+        self.last_backend_compare = Some(Instant::now());
+        let result = self.compare_backends(&frame, preprocessing.as_ref());
+        let vision_state = &mut self.dashboard_state.vision;
+        match result {
+            Ok(comparison) => {
+                vision_state.backend_comparison = Some(comparison);
+                vision_state.backend_compare_error = None;
+            }
+            Err(e) => {
+                tracing::debug!("OCR backend comparison failed: {:#}", e);
+                vision_state.backend_comparison = None;
+                vision_state.backend_compare_error = Some(format!("{:#}", e));
             }
         }
     }
 
-    /// Poll for global hotkey events
-    fn poll_hotkeys(&mut self) {
-        use crate::hotkey::HotkeyEvent;
+    /// A frame read by the selected OCR backend and then by the other one
+    fn compare_backends(
+        &mut self,
+        frame: &crate::capture::CapturedFrame,
+        preprocessing: Option<&crate::config::OcrPreprocessing>,
+    ) -> anyhow::Result<BackendComparison> {
+        if !self.shared_state.read().config.features.ai {
+            anyhow::bail!("AI models are turned off in Settings > Features");
+        }
+        let pipeline = self.ready_pipeline()?;
+        let primary = pipeline.backend();
+        let secondary = primary.other();
+        if !pipeline.is_backend_ready(secondary) {
+            init_ensemble_backend(pipeline, secondary)?;
+        }
 
-        let event = if let Some(ref hotkey_manager) = self.hotkey_manager {
-            hotkey_manager.poll_events()
-        } else {
-            HotkeyEvent::None
+        let (width, height) = frame.dimensions();
+        let first = pipeline.process_region_with_preprocessing(
+            frame,
+            0,
+            0,
+            width,
+            height,
+            preprocessing,
+        )?;
+        pipeline.set_backend(secondary);
+        let second =
+            pipeline.process_region_with_preprocessing(frame, 0, 0, width, height, preprocessing);
+        pipeline.set_backend(primary);
+        let second = second?;
+
+        Ok(BackendComparison::new(
+            (
+                primary,
+                first.text_regions.as_slice(),
+                first.processing_time_ms,
+            ),
+            (
+                secondary,
+                second.text_regions.as_slice(),
+                second.processing_time_ms,
+            ),
+        ))
+    }
+
+    /// OCR the latest captured frame line by line into the screen text panel
+    ///
+    /// Returns whether the panel was opened.
+    fn read_screen(&mut self) -> bool {
+        let frame = self.shared_state.read().runtime.last_captured_frame.clone();
+        let Some(frame) = frame else {
+            self.report_error(AppError::Ocr(
+                "Start capturing a window or screen to read its text".to_string(),
+            ));
+            return false;
         };
 
-        match event {
-            HotkeyEvent::None => {}
-            HotkeyEvent::ToggleOverlay => {
-                // Auto-start overlay if it's not running
-                if self.overlay_manager.is_none() {
-                    tracing::info!("Auto-starting overlay on toggle hotkey");
-                    if let Err(e) = self.start_overlay() {
-                        tracing::error!("Failed to auto-start overlay: {}", e);
-                        let mut state = self.shared_state.write();
-                        state.runtime.set_error(e);
-                    }
-                }
+        let result = self.ready_pipeline().and_then(|pipeline| {
+            pipeline.process_with_granularity(&frame, crate::vision::OcrGranularity::Line)
+        });
+
+        match result {
+            Ok(result) => {
+                let mut lines: Vec<_> = result
+                    .text_regions
+                    .into_iter()
+                    .filter(|line| !line.text.trim().is_empty())
+                    .collect();
+                lines.sort_by_key(|line| (line.bounds.1, line.bounds.0));
+                tracing::info!("Read {} lines of screen text", lines.len());
+
+                self.dashboard_state.screen_text = Some(ScreenTextState {
+                    lines,
+                    search: String::new(),
+                    read_at: Instant::now(),
+                });
+                true
             }
-            HotkeyEvent::EnterZoneSelection => {
-                // Request zone selection mode
-                self.dashboard_state.vision.pending_zone_selection_mode = true;
+            Err(e) => {
+                self.report_error(AppError::Ocr(format!("Failed to read screen text: {}", e)));
+                false
             }
         }
     }
 
+    /// Downloaded models that fail verification
+    fn corrupted_models(manager: &ModelManager) -> Vec<(ModelType, String)> {
+        manager
+            .verify_models()
+            .into_iter()
+            .filter_map(|(model_type, integrity)| match integrity {
+                ModelIntegrity::Corrupted(reason) => Some((model_type, reason)),
+                ModelIntegrity::Ok | ModelIntegrity::Missing => None,
+            })
+            .collect()
+    }
+
     /// Process vision/OCR commands from the UI
     fn process_vision_commands(&mut self) {
         use crate::vision::OcrBackend;
@@ -1242,9 +4610,38 @@ impl DashboardApp {
             vision_state.detection_model_ready = manager.is_model_available(ModelType::Detection);
             vision_state.recognition_model_ready =
                 manager.is_model_available(ModelType::Recognition);
-            vision_state.models_ready = manager.are_models_ready();
+            vision_state.models_ready =
+                manager.are_models_ready() && vision_state.corrupted_models.is_empty();
+            vision_state.quantized_models_ready =
+                manager.is_variant_available(ModelVariant::Quantized);
+        }
+
+        // Composite capture sources zones can be placed in
+        {
+            let state = self.shared_state.read();
+            let capture = &state.config.capture;
+            vision_state.composite_sources = capture
+                .composite_sources
+                .iter()
+                .map(|s| s.id.clone())
+                .chain(state.capture_sources().into_iter().map(|s| s.id))
+                .collect();
+            vision_state.composite_layout = state.runtime.composite_layout.clone();
         }
 
+        // Game phases zones can be limited to
+        vision_state.phases = self
+            .active_profile
+            .as_ref()
+            .map(|profile| {
+                profile
+                    .phases
+                    .iter()
+                    .map(|phase| (phase.id.clone(), phase.name.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         // Update OCR initialized status based on backend
         if let Some(ref pipeline) = self.vision_pipeline {
             vision_state.ocr_initialized =
@@ -1268,12 +4665,21 @@ impl DashboardApp {
                     Ok(()) => {
                         vision_state.is_downloading = false;
                         vision_state.download_progress = 1.0;
+                        vision_state.corrupted_models = Self::corrupted_models(manager);
+                        // Ensemble zones can use PaddleOCR now
+                        vision_state.ensemble_error = None;
                         tracing::info!("OCR models downloaded successfully");
                     }
                     Err(e) => {
                         vision_state.is_downloading = false;
                         vision_state.last_error = Some(format!("Download failed: {}", e));
-                        tracing::error!("Failed to download OCR models: {}", e);
+                        self.shared_state
+                            .write()
+                            .runtime
+                            .set_error(AppError::Model(format!(
+                                "Failed to download OCR models: {}",
+                                e
+                            )));
                     }
                 }
             } else {
@@ -1282,6 +4688,31 @@ impl DashboardApp {
             }
         }
 
+        // Handle re-download of a corrupted model
+        if let Some(model_type) = vision_state.pending_repair.take() {
+            vision_state.last_error = None;
+
+            if let Some(ref manager) = self.model_manager {
+                match manager.redownload_model(model_type) {
+                    Ok(()) => {
+                        vision_state.corrupted_models = Self::corrupted_models(manager);
+                        tracing::info!("Re-downloaded {:?} model", model_type);
+                    }
+                    Err(e) => {
+                        vision_state.last_error = Some(format!("Re-download failed: {}", e));
+                        self.shared_state
+                            .write()
+                            .runtime
+                            .set_error(AppError::Model(format!(
+                                "Failed to re-download the {} model: {}",
+                                model_type.display_name(),
+                                e
+                            )));
+                    }
+                }
+            }
+        }
+
         // Handle OCR initialization request - based on selected backend
         if vision_state.pending_init {
             vision_state.pending_init = false;
@@ -1289,14 +4720,32 @@ impl DashboardApp {
 
             let selected_backend = vision_state.selected_backend;
 
+            // A corrupted model would only fail inside ONNX Runtime
+            if selected_backend == OcrBackend::PaddleOcr {
+                if let Some((model_type, reason)) = vision_state.corrupted_models.first() {
+                    vision_state.last_error = Some(format!(
+                        "{} model is corrupted ({}), re-download it",
+                        model_type.display_name(),
+                        reason
+                    ));
+                    return;
+                }
+            }
+
             // Create or update pipeline with selected backend
             let mut pipeline = match self.vision_pipeline.take() {
                 Some(p) => p,
-                None => match VisionPipeline::new() {
+                None => match Self::create_vision_pipeline(&self.shared_state) {
                     Ok(p) => p,
                     Err(e) => {
                         vision_state.last_error = Some(format!("Pipeline creation failed: {}", e));
-                        tracing::error!("Failed to create vision pipeline: {}", e);
+                        self.shared_state
+                            .write()
+                            .runtime
+                            .set_error(AppError::Model(format!(
+                                "Failed to create vision pipeline: {}",
+                                e
+                            )));
                         return;
                     }
                 },
@@ -1308,18 +4757,27 @@ impl DashboardApp {
             // Initialize the selected backend
             if let Err(e) = pipeline.init_ocr() {
                 vision_state.last_error = Some(format!("OCR init failed: {}", e));
-                tracing::error!("Failed to initialize {:?} OCR: {}", selected_backend, e);
+                self.shared_state
+                    .write()
+                    .runtime
+                    .set_error(AppError::Ocr(format!(
+                        "Failed to initialize {:?} OCR: {}",
+                        selected_backend, e
+                    )));
             } else {
-                match selected_backend {
+                let feature = match selected_backend {
                     OcrBackend::WindowsOcr => {
                         vision_state.windows_ocr_initialized = true;
                         tracing::info!("Windows OCR engine initialized successfully");
+                        Feature::WindowsOcr
                     }
                     OcrBackend::PaddleOcr => {
                         vision_state.ocr_initialized = true;
                         tracing::info!("PaddleOCR engine initialized successfully");
+                        Feature::PaddleOcr
                     }
-                }
+                };
+                self.shared_state.write().runtime.usage.record(feature);
             }
 
             self.vision_pipeline = Some(pipeline);
@@ -1525,6 +4983,62 @@ impl DashboardApp {
             }
         }
 
+        // Handle request to enter full screen template capture mode
+        if let Some(screen_id) = self.dashboard_state.screens.pending_full_capture.clone() {
+            self.dashboard_state.screens.pending_full_capture = None;
+
+            // Auto-start capture if not running (needed to get frame data for the template)
+            if !self.is_capturing() {
+                tracing::info!("Auto-starting capture for template capture");
+                if let Err(e) = self.start_capture() {
+                    tracing::error!("Failed to start capture for template capture: {}", e);
+                    self.dashboard_state.screens.error_message =
+                        Some(format!("Failed to start capture: {}", e));
+                    return;
+                }
+                // Give capture time to start and get first frame
+                std::thread::sleep(std::time::Duration::from_millis(200));
+            }
+
+            // Auto-start overlay if not running
+            if self.overlay_manager.is_none() {
+                tracing::info!("Auto-starting overlay for template capture");
+                if let Err(e) = self.start_overlay() {
+                    tracing::error!("Failed to start overlay for template capture: {}", e);
+                    self.dashboard_state.screens.error_message =
+                        Some(format!("Failed to start overlay: {}", e));
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+
+            if let Some(ref manager) = self.overlay_manager {
+                // Bring captured window to front so user can see it
+                {
+                    let shared = self.shared_state.read();
+                    if let Some(ref window_title) = shared.config.capture.target_window {
+                        crate::capture::bring_window_to_front(window_title);
+                    }
+                }
+
+                let capture_size = {
+                    let w = self.dashboard_state.vision.last_frame_width;
+                    let h = self.dashboard_state.vision.last_frame_height;
+                    if w > 0 && h > 0 {
+                        Some((w, h))
+                    } else {
+                        None
+                    }
+                };
+
+                manager.enter_full_screen_capture_mode(screen_id, capture_size);
+                tracing::info!("Requested full screen capture mode");
+            } else {
+                self.dashboard_state.screens.error_message =
+                    Some("Overlay failed to start".to_string());
+            }
+        }
+
         let vision_state = &mut self.dashboard_state.vision;
 
         // Poll for zone selection results from overlay
@@ -1568,7 +5082,7 @@ impl DashboardApp {
                         vision_state.zone_selection.repositioning_zone_index = None;
                         self.dashboard_state.screens.pending_anchor_capture = None;
                         self.dashboard_state.screens.pending_text_anchor_capture = None;
-                        self.dashboard_state.screens.pending_full_capture = false;
+                        self.dashboard_state.screens.pending_full_capture = None;
                         tracing::info!("Selection cancelled");
                     }
                     ZoneSelectionResult::VisualAnchorCaptured { screen_id, bounds } => {
@@ -1582,15 +5096,14 @@ impl DashboardApp {
                             bounds.3
                         );
 
-                        // Get a fresh frame from capture manager (don't rely on Vision view state)
-                        // Try multiple times with small delays since try_next_frame only returns
-                        // if there's already a frame in the channel buffer
+                        // Get the newest frame from capture manager (don't rely on Vision view state)
+                        // Try multiple times with small delays in case no frame arrived yet
                         let mut frame = None;
                         for attempt in 0..10 {
                             {
                                 let capture_guard = self.capture_manager.lock();
                                 if let Some(ref capture) = *capture_guard {
-                                    frame = capture.try_next_frame();
+                                    frame = capture.latest_frame();
                                 }
                             }
                             if frame.is_some() {
@@ -1614,7 +5127,7 @@ impl DashboardApp {
 
                                 // Extract region and encode as PNG
                                 if let Some(png_data) = extract_region_as_png(
-                                    &frame.data,
+                                    &frame.rgba(),
                                     width,
                                     height,
                                     px,
@@ -1633,9 +5146,13 @@ impl DashboardApp {
                                         anchor_type: crate::storage::profiles::AnchorType::Visual,
                                         bounds,
                                         template_data: Some(png_data),
+                                        template_file: None,
                                         expected_text: None,
                                         text_similarity: 0.8,
                                         required: true,
+                                        weight: 1.0,
+                                        match_strategy:
+                                            crate::storage::profiles::MatchStrategy::Ncc,
                                     };
 
                                     // Add to screen
@@ -1687,7 +5204,7 @@ impl DashboardApp {
                         // Ensure OCR pipeline is initialized
                         let selected_backend = self.dashboard_state.vision.selected_backend;
                         if self.vision_pipeline.is_none() {
-                            match VisionPipeline::new() {
+                            match Self::create_vision_pipeline(&self.shared_state) {
                                 Ok(p) => {
                                     self.vision_pipeline = Some(p);
                                     tracing::info!("Created vision pipeline for text anchor OCR");
@@ -1722,7 +5239,7 @@ impl DashboardApp {
                             let frame = {
                                 let capture_guard = self.capture_manager.lock();
                                 if let Some(ref capture) = *capture_guard {
-                                    capture.try_next_frame()
+                                    capture.latest_frame()
                                 } else {
                                     None
                                 }
@@ -1808,8 +5325,75 @@ impl DashboardApp {
                     ZoneSelectionResult::FullScreenCaptured { screen_id } => {
                         // Handle full screen template capture
                         tracing::info!("Full screen template captured for screen {}", screen_id);
-                        // TODO: Store current frame as template for screen definition
-                        self.dashboard_state.screens.pending_full_capture = false;
+
+                        // Get the newest frame from capture manager, retrying until
+                        // one arrived
+                        let mut frame = None;
+                        for attempt in 0..10 {
+                            {
+                                let capture_guard = self.capture_manager.lock();
+                                if let Some(ref capture) = *capture_guard {
+                                    frame = capture.latest_frame();
+                                }
+                            }
+                            if frame.is_some() {
+                                break;
+                            }
+                            if attempt < 9 {
+                                std::thread::sleep(std::time::Duration::from_millis(50));
+                            }
+                        }
+
+                        let template = frame.and_then(|frame| {
+                            let png_data = extract_region_as_png(
+                                &frame.rgba(),
+                                frame.width,
+                                frame.height,
+                                0,
+                                0,
+                                frame.width,
+                                frame.height,
+                            )?;
+                            Some(crate::storage::profiles::ScreenTemplate {
+                                image_data: png_data,
+                                image_file: None,
+                                width: frame.width,
+                                height: frame.height,
+                                captured_at: std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .unwrap_or_default()
+                                    .as_secs()
+                                    .to_string(),
+                            })
+                        });
+
+                        if let Some(template) = template {
+                            let (width, height) = (template.width, template.height);
+                            let mut shared = self.shared_state.write();
+                            let active_id = shared.active_profile_id.clone();
+                            if let Some(screen) = shared
+                                .profiles
+                                .iter_mut()
+                                .find(|p| active_id.as_ref() == Some(&p.id))
+                                .and_then(|p| p.screens.iter_mut().find(|s| s.id == screen_id))
+                            {
+                                screen.add_full_template(template);
+                                tracing::info!(
+                                    "Added {}x{} template to screen {}",
+                                    width,
+                                    height,
+                                    screen_id
+                                );
+                            }
+                        } else {
+                            tracing::warn!("No frame available for template capture");
+                            self.dashboard_state.screens.error_message = Some(
+                                "No capture frame available. Make sure capture is running."
+                                    .to_string(),
+                            );
+                        }
+
+                        self.dashboard_state.screens.pending_full_capture = None;
                         self.dashboard_state.screens.screens_dirty = true;
                     }
                 }
@@ -1838,8 +5422,9 @@ impl DashboardApp {
             return;
         }
 
-        // Don't process while another OCR is running
-        if vision_state.is_processing {
+        // Don't process while another OCR is running, or replace the
+        // readings of a pasted clipboard image
+        if vision_state.is_processing || vision_state.showing_clipboard_image {
             return;
         }
 
@@ -1853,13 +5438,19 @@ impl DashboardApp {
         if !backend_ready {
             // Create pipeline if needed
             if self.vision_pipeline.is_none() {
-                match VisionPipeline::new() {
+                match Self::create_vision_pipeline(&self.shared_state) {
                     Ok(p) => {
                         self.vision_pipeline = Some(p);
                         tracing::info!("Created vision pipeline for zone OCR");
                     }
                     Err(e) => {
-                        tracing::debug!("Failed to create vision pipeline: {}", e);
+                        self.shared_state
+                            .write()
+                            .runtime
+                            .set_error(AppError::Ocr(format!(
+                                "Failed to create vision pipeline for zone OCR: {}",
+                                e
+                            )));
                         return;
                     }
                 }
@@ -1869,7 +5460,20 @@ impl DashboardApp {
             if let Some(ref mut pipeline) = self.vision_pipeline {
                 pipeline.set_backend(selected_backend);
                 if let Err(e) = pipeline.init_ocr() {
-                    tracing::debug!("Failed to initialize OCR for zone processing: {}", e);
+                    let error = if selected_backend == OcrBackend::PaddleOcr {
+                        // Degrade to Windows OCR rather than leaving zones without results
+                        vision_state.selected_backend = OcrBackend::WindowsOcr;
+                        AppError::Ocr(format!(
+                            "PaddleOCR unavailable, falling back to Windows OCR: {}",
+                            e
+                        ))
+                    } else {
+                        AppError::Ocr(format!(
+                            "Failed to initialize OCR for zone processing: {}",
+                            e
+                        ))
+                    };
+                    self.shared_state.write().runtime.set_error(error);
                     return;
                 }
 
@@ -1886,19 +5490,122 @@ impl DashboardApp {
             }
         }
 
-        // Get a fresh frame from capture manager (zone OCR runs independently of Vision view)
-        let frame = {
+        // A frozen frame is read again only when zones or preprocessing change
+        if let Some(ref frozen) = vision_state.frozen_frame {
+            let inputs = (
+                vision_state.ocr_zones.clone(),
+                vision_state.preprocessing.clone(),
+            );
+            if vision_state.frozen_ocr_inputs.as_ref() == Some(&inputs) {
+                return;
+            }
+            vision_state.frozen_ocr_inputs = Some(inputs);
+            let frame = frozen.clone();
+            self.ocr_zones_in_frame(FrameSource::Cpu(&frame), None, None);
+            return;
+        }
+
+        // A paused pipeline only reads the frames it's stepped on
+        if self.dashboard_state.pipeline_debug.stepper.is_paused() {
+            return;
+        }
+
+        // Nothing is read while the player is away
+        if self.shared_state.read().runtime.afk {
+            return;
+        }
+
+        // On battery, read the zones only every so often
+        let ocr_interval = {
+            let shared = self.shared_state.read();
+            shared
+                .runtime
+                .power
+                .ocr_interval(&shared.config.performance)
+        };
+        if let Some(interval) = ocr_interval {
+            if self
+                .last_zone_ocr
+                .is_some_and(|last| last.elapsed() < interval)
+            {
+                return;
+            }
+        }
+
+        // Get a fresh frame from capture manager (zone OCR runs independently of Vision view),
+        // and one of each extra source a zone subscribed to
+        let (frame, source_frames) = {
             let capture_guard = self.capture_manager.lock();
             if let Some(ref capture) = *capture_guard {
-                capture.try_next_frame()
+                let zones = &self.dashboard_state.vision.ocr_zones;
+                let source_frames: Vec<_> = capture
+                    .source_ids()
+                    .into_iter()
+                    .filter(|id| {
+                        zones
+                            .iter()
+                            .any(|z| z.enabled && z.source.as_ref() == Some(id))
+                    })
+                    .filter_map(|id| capture.try_next_source_frame(&id).map(|f| (id, f)))
+                    .collect();
+                (
+                    capture.try_next_frame_for(FrameConsumer::ZoneOcr),
+                    source_frames,
+                )
             } else {
-                None
+                (None, Vec::new())
             }
         };
 
-        let Some(frame) = frame else {
+        // A frame kept on the GPU is read once; only the zones are downloaded
+        let gpu_frame = self
+            .latest_gpu_frame
+            .clone()
+            .filter(|f| self.last_zone_ocr.map_or(true, |last| f.timestamp > last));
+
+        if frame.is_none() && gpu_frame.is_none() && source_frames.is_empty() {
             return;
-        };
+        }
+
+        // Zones whose pixels didn't change are read again only once they or
+        // the preprocessing change
+        let vision_state = &mut self.dashboard_state.vision;
+        let inputs = (
+            vision_state.ocr_zones.clone(),
+            vision_state.preprocessing.clone(),
+        );
+        if vision_state.live_ocr_inputs.as_ref() != Some(&inputs) {
+            vision_state.live_ocr_inputs = Some(inputs);
+            if let Some(ref mut pipeline) = self.vision_pipeline {
+                pipeline.reset_region_diff();
+            }
+        }
+
+        self.last_zone_ocr = Some(Instant::now());
+        if let Some(frame) = frame {
+            self.ocr_zones_in_frame(FrameSource::Cpu(&frame), None, None);
+        } else if let Some(frame) = gpu_frame {
+            self.ocr_zones_in_frame(FrameSource::Gpu(&frame), None, None);
+        }
+        for (id, frame) in source_frames {
+            self.ocr_zones_in_frame(FrameSource::Cpu(&frame), Some(&id), None);
+        }
+    }
+
+    /// Run OCR for every enabled zone of a frame and record the readings
+    ///
+    /// `source` is the extra capture source the frame came from, whose frames
+    /// hold only the zones subscribed to it; `None` for the main target. Of a
+    /// frame kept on the GPU, only the zones are downloaded. When stepping
+    /// the paused pipeline, `step` collects what each zone produced.
+    fn ocr_zones_in_frame(
+        &mut self,
+        frame: FrameSource,
+        source: Option<&str>,
+        mut step: Option<&mut Vec<ZoneStep>>,
+    ) {
+        let vision_state = &mut self.dashboard_state.vision;
+        let selected_backend = vision_state.selected_backend;
 
         let Some(ref mut pipeline) = self.vision_pipeline else {
             return;
@@ -1907,27 +5614,158 @@ impl DashboardApp {
         // Ensure backend is synced with user selection
         pipeline.set_backend(selected_backend);
 
-        let frame_width = frame.width;
-        let frame_height = frame.height;
+        let (frame_width, frame_height) = frame.dimensions();
 
         if frame_width == 0 || frame_height == 0 {
             return;
         }
 
+        // Ensemble zones also need the other backend to vote
+        let secondary_backend = selected_backend.other();
+        let wants_ensemble = self.shared_state.read().config.features.ai
+            && vision_state
+                .ocr_zones
+                .iter()
+                .any(|z| z.enabled && z.ensemble);
+        let ensemble_ready = wants_ensemble
+            && vision_state.ensemble_error.is_none()
+            && (pipeline.is_backend_ready(secondary_backend)
+                || match init_ensemble_backend(pipeline, secondary_backend) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!("Ensemble OCR unavailable: {}", e);
+                        vision_state.ensemble_error = Some(e.to_string());
+                        false
+                    }
+                });
+
+        let dictionary = self.active_profile.as_ref().map(|p| &p.dictionary);
+
+        // Readings of a frozen frame are for tuning and stepped ones for
+        // debugging; neither is recorded
+        let recording = vision_state.frozen_frame.is_none() && step.is_none();
+
+        // Live zones whose pixels didn't change since they were read keep
+        // their reading instead of being read again
+        let skip_unchanged = recording
+            && !vision_state.showing_clipboard_image
+            && self
+                .shared_state
+                .read()
+                .config
+                .performance
+                .skip_unchanged_zones;
+
+        // Zones under a popup screen would read the popup instead of the game
+        let popup = self
+            .shared_state
+            .read()
+            .runtime
+            .current_screen
+            .as_ref()
+            .and_then(|current| {
+                self.active_profile
+                    .as_ref()?
+                    .screens
+                    .iter()
+                    .find(|screen| screen.id == current.screen_id && screen.popup_bounds.is_some())
+                    .cloned()
+            });
+
+        // Zones of a composite capture source are placed within that source
+        let layout = self.shared_state.read().runtime.composite_layout.clone();
+
+        // Zones subscribed to an extra source are read from its own frames
+        let extra_sources = self
+            .capture_manager
+            .lock()
+            .as_ref()
+            .map(|capture| capture.source_ids())
+            .unwrap_or_default();
+
         // Process each enabled zone
+        let pass_started = Instant::now();
+        let mut zones_read = 0;
+        let mut zones_unchanged = 0;
         for zone in &vision_state.ocr_zones {
             if !zone.enabled {
                 continue;
             }
+            if !phases::is_active(&zone.phases, self.phase_tracker.current()) {
+                if let Some(step) = step.as_deref_mut() {
+                    step.push(ZoneStep::skipped(
+                        &zone.id,
+                        &zone.name,
+                        "Not in the current phase",
+                    ));
+                }
+                continue;
+            }
+            let bounds = match source {
+                Some(source) if zone.source.as_deref() == Some(source) => Some(zone.bounds),
+                Some(_) => None,
+                None if zone
+                    .source
+                    .as_ref()
+                    .is_some_and(|id| extra_sources.contains(id)) =>
+                {
+                    None
+                }
+                None => {
+                    composite::frame_bounds(layout.as_ref(), zone.source.as_deref(), zone.bounds)
+                }
+            };
+            let Some(bounds) = bounds else {
+                if let (None, Some(step)) = (source, step.as_deref_mut()) {
+                    step.push(ZoneStep::skipped(
+                        &zone.id,
+                        &zone.name,
+                        "Its capture source isn't in this frame",
+                    ));
+                }
+                continue;
+            };
 
             // Convert normalized bounds to pixel coordinates
-            let x = (zone.bounds.0 * frame_width as f32) as u32;
-            let y = (zone.bounds.1 * frame_height as f32) as u32;
-            let w = (zone.bounds.2 * frame_width as f32) as u32;
-            let h = (zone.bounds.3 * frame_height as f32) as u32;
+            let x = (bounds.0 * frame_width as f32) as u32;
+            let y = (bounds.1 * frame_height as f32) as u32;
+            let w = (bounds.2 * frame_width as f32) as u32;
+            let h = (bounds.3 * frame_height as f32) as u32;
 
             // Ensure minimum size
             if w < 5 || h < 5 {
+                if let Some(step) = step.as_deref_mut() {
+                    step.push(ZoneStep::skipped(
+                        &zone.id,
+                        &zone.name,
+                        "Smaller than 5x5 pixels",
+                    ));
+                }
+                continue;
+            }
+
+            // Keep the last reading, marked occluded, until the popup closes
+            // (popups are on the main target)
+            if source.is_none() && popup.as_ref().is_some_and(|popup| popup.occludes(bounds)) {
+                vision_state
+                    .zone_ocr_results
+                    .entry(zone.id.clone())
+                    .or_insert_with(|| ZoneOcrResult {
+                        zone_id: zone.id.clone(),
+                        zone_name: zone.name.clone(),
+                        text: String::new(),
+                        agreement: None,
+                        occluded: true,
+                        last_updated: Instant::now(),
+                    })
+                    .occluded = true;
+                if let Some(step) = step.as_deref_mut() {
+                    step.push(ZoneStep::skipped(
+                        &zone.id,
+                        &zone.name,
+                        "Covered by a popup",
+                    ));
+                }
                 continue;
             }
 
@@ -1949,8 +5787,42 @@ impl DashboardApp {
                 frame_height
             );
 
+            // Only the zone's region of a GPU frame is downloaded
+            let (region_frame, rx, ry) = match frame.region(x, y, w, h) {
+                Ok(region) => region,
+                Err(e) => {
+                    tracing::warn!("Zone '{}': failed to download region: {:#}", zone.name, e);
+                    if let Some(step) = step.as_deref_mut() {
+                        step.push(ZoneStep::skipped(
+                            &zone.id,
+                            &zone.name,
+                            format!("Failed to download region: {:#}", e),
+                        ));
+                    }
+                    continue;
+                }
+            };
+
+            if skip_unchanged && !pipeline.region_changed(&zone.id, &region_frame, rx, ry, w, h) {
+                if let Some(result) = vision_state.zone_ocr_results.get_mut(&zone.id) {
+                    result.last_updated = Instant::now();
+                }
+                zones_unchanged += 1;
+                continue;
+            }
+
             // Run OCR on the zone region with preprocessing
-            match pipeline.process_region_with_preprocessing(&frame, x, y, w, h, preprocessing) {
+            pipeline.set_allowed_chars(zone.allowed_chars.as_deref().filter(|a| !a.is_empty()));
+            let prepared = crate::vision::prepare_region(
+                &region_frame,
+                rx,
+                ry,
+                w,
+                h,
+                preprocessing,
+                Some(&zone.geometry),
+            );
+            match pipeline.process_prepared_region(&prepared, rx, ry, w) {
                 Ok(result) => {
                     tracing::info!(
                         "Zone '{}': OCR returned {} text regions",
@@ -1961,39 +5833,185 @@ impl DashboardApp {
                         tracing::info!("  Region {}: '{}' (conf: {:.2})", i, r.text, r.confidence);
                     }
 
-                    // Combine all detected text
-                    let raw_text: String = result
-                        .text_regions
-                        .iter()
-                        .map(|r| r.text.as_str())
-                        .collect::<Vec<_>>()
-                        .join(" ");
+                    let mut reading = zone_reading(&result, zone, dictionary);
+                    let mut agreement = None;
+
+                    // Let the other backend vote on ensemble zones (scrolling
+                    // zones are stitched from the primary backend's lines)
+                    if zone.ensemble && !zone.scrolling && ensemble_ready {
+                        pipeline.set_backend(secondary_backend);
+                        let secondary = pipeline.process_prepared_region(&prepared, rx, ry, w);
+                        pipeline.set_backend(selected_backend);
+
+                        match secondary {
+                            Ok(secondary) => {
+                                let voted = ensemble::reconcile(
+                                    reading,
+                                    zone_reading(&secondary, zone, dictionary),
+                                    &zone.content_type,
+                                );
+                                tracing::info!(
+                                    "Zone '{}': ensemble {:?} on '{}'",
+                                    zone.name,
+                                    voted.agreement,
+                                    voted.text
+                                );
+                                agreement = Some(voted.agreement);
+                                reading = Reading {
+                                    text: voted.text,
+                                    confidence: voted.confidence,
+                                };
+                            }
+                            Err(e) => {
+                                tracing::debug!(
+                                    "Zone '{}': ensemble {:?} OCR failed: {}",
+                                    zone.name,
+                                    secondary_backend,
+                                    e
+                                );
+                            }
+                        }
+                    }
+
+                    let Reading { text, confidence } = reading;
+
+                    // Track OCR quality for the zone's confidence heatmap
+                    if recording {
+                        let now = Instant::now();
+                        vision_state
+                            .zone_ocr_stats
+                            .entry(zone.id.clone())
+                            .or_insert_with(|| ZoneOcrStats::new(now))
+                            .record(now, confidence, text.trim().is_empty());
+                    }
+
+                    // Scrolling zones record only the lines that scrolled into
+                    // view; other zones record their value when it changes
+                    // (numbers by more than the zone's epsilon)
+                    let entries = if zone.scrolling {
+                        let lines = scroll_stitch::group_lines(&result.text_regions)
+                            .iter()
+                            .map(|line| filter_zone_text(line, zone, dictionary))
+                            .filter(|line| !line.trim().is_empty())
+                            .collect();
+                        let new_lines = vision_state
+                            .zone_scroll_logs
+                            .entry(zone.id.clone())
+                            .or_default()
+                            .push(lines);
+                        if !new_lines.is_empty() {
+                            tracing::info!(
+                                "Zone '{}': {} new scrolled lines",
+                                zone.name,
+                                new_lines.len()
+                            );
+                        }
+                        new_lines
+                    } else {
+                        let value_changed = vision_state
+                            .zone_recorded_values
+                            .get(&zone.id)
+                            .map_or(true, |previous| is_new_value(previous, &text, zone));
+                        if value_changed {
+                            vision_state
+                                .zone_recorded_values
+                                .insert(zone.id.clone(), text.clone());
+                            vec![text.clone()]
+                        } else {
+                            Vec::new()
+                        }
+                    };
+                    let database = self.database.as_ref().filter(|_| recording);
+                    if let (Some(db), Some(session_id)) = (database, self.session_id) {
+                        for entry in &entries {
+                            let reading = ZoneReading {
+                                profile_id: self.active_profile.as_ref().map(|p| p.id.as_str()),
+                                zone_id: &zone.id,
+                                zone_name: &zone.name,
+                                text: entry,
+                                confidence,
+                            };
+                            if let Err(e) = db.record_zone_reading(session_id, &reading) {
+                                tracing::warn!("Failed to record zone reading: {}", e);
+                            }
+                        }
+                    }
 
-                    // Filter text based on content type
-                    let text = filter_text_by_content_type(&raw_text, &zone.content_type);
+                    if let Some(step) = step.as_deref_mut() {
+                        step.push(ZoneStep {
+                            zone_id: zone.id.clone(),
+                            zone_name: zone.name.clone(),
+                            crop: Some(prepared),
+                            regions: result.text_regions.clone(),
+                            text: text.clone(),
+                            confidence,
+                            skipped: None,
+                        });
+                    }
 
                     // Update zone result
+                    zones_read += 1;
                     vision_state.zone_ocr_results.insert(
                         zone.id.clone(),
                         ZoneOcrResult {
                             zone_id: zone.id.clone(),
                             zone_name: zone.name.clone(),
                             text,
+                            agreement,
+                            occluded: false,
                             last_updated: Instant::now(),
                         },
                     );
                 }
                 Err(e) => {
-                    tracing::info!("Zone OCR failed for '{}': {}", zone.name, e);
+                    if recording {
+                        let now = Instant::now();
+                        vision_state
+                            .zone_ocr_stats
+                            .entry(zone.id.clone())
+                            .or_insert_with(|| ZoneOcrStats::new(now))
+                            .record(now, None, true);
+                    }
+                    if let Some(step) = step.as_deref_mut() {
+                        step.push(ZoneStep::skipped(
+                            &zone.id,
+                            &zone.name,
+                            format!("OCR failed: {}", e),
+                        ));
+                    }
+
+                    self.shared_state
+                        .write()
+                        .runtime
+                        .set_error(AppError::Ocr(format!(
+                            "Zone OCR failed for '{}': {}",
+                            zone.name, e
+                        )));
                 }
             }
         }
+
+        pipeline.set_allowed_chars(None);
+
+        // Unchanged zones' readings hold for this frame too
+        if recording && zones_read + zones_unchanged > 0 {
+            let mut state = self.shared_state.write();
+            if zones_read > 0 {
+                state.runtime.latency.zone_ocr.record(frame.age());
+                state
+                    .runtime
+                    .latency
+                    .zone_ocr_time
+                    .record(pass_started.elapsed());
+            }
+            state.runtime.readings_captured_at = Some(frame.timestamp());
+        }
     }
 
-    /// Process auto-configure for a zone
-    /// This iterates through different OCR settings until text is detected
+    /// Process auto-tune for a zone
+    /// Tests one preprocessing candidate per frame and applies the best-scoring one
     fn process_auto_configure(&mut self) {
-        use crate::config::OcrPreprocessing;
+        use crate::vision::auto_tune;
         use crate::vision::OcrBackend;
 
         // Check if auto-configure is active
@@ -2034,7 +6052,7 @@ impl DashboardApp {
         if !backend_ready {
             // Try to initialize
             if self.vision_pipeline.is_none() {
-                match VisionPipeline::new() {
+                match Self::create_vision_pipeline(&self.shared_state) {
                     Ok(p) => self.vision_pipeline = Some(p),
                     Err(e) => {
                         if let Some(ref mut ac) =
@@ -2123,7 +6141,7 @@ impl DashboardApp {
             return;
         }
 
-        // Get current settings to test from auto_configure state
+        // Next candidate to test, plus the saved samples to score it against
         let ac = self
             .dashboard_state
             .vision
@@ -2131,104 +6149,76 @@ impl DashboardApp {
             .auto_configure
             .as_ref()
             .unwrap();
-        let test_preprocessing = if ac.current_preprocessing_enabled {
-            Some(OcrPreprocessing {
-                enabled: true,
-                grayscale: ac.current_grayscale,
-                contrast: ac.current_contrast,
-                sharpen: 0.0,
-                invert: ac.current_invert,
-                scale: ac.current_scale,
-            })
-        } else {
-            Some(OcrPreprocessing {
-                enabled: false,
-                grayscale: false,
-                contrast: 1.0,
-                sharpen: 0.0,
-                invert: false,
-                scale: ac.current_scale,
-            })
+        let Some(candidate) = ac.candidates.get(ac.current_combination).cloned() else {
+            return;
         };
+        let samples = ac.samples.clone();
+
+        // Score the candidate on the current frame and every sample; the
+        // candidate's score is the mean over all of them
+        let mut total_score = 0.0;
+        let mut total_confidence = 0.0;
+        let mut current_text = String::new();
+        let mut runs = 0;
+
+        pipeline.set_allowed_chars(zone.allowed_chars.as_deref().filter(|a| !a.is_empty()));
+        let dictionary = self.active_profile.as_ref().map(|p| &p.dictionary);
+        let regions = std::iter::once((&frame, x, y, w, h))
+            .chain(samples.iter().map(|s| (s, 0, 0, s.width, s.height)));
+        for (image, rx, ry, rw, rh) in regions {
+            runs += 1;
+            let result = pipeline.process_region_with_geometry(
+                image,
+                rx,
+                ry,
+                rw,
+                rh,
+                Some(&candidate),
+                Some(&zone.geometry),
+            );
 
-        // Update status message
-        let status = format!(
-            "Testing: scale={}x, pp={}, gray={}, inv={}, contrast={:.1}",
-            ac.current_scale,
-            if ac.current_preprocessing_enabled {
-                "on"
-            } else {
-                "off"
-            },
-            if ac.current_grayscale { "Y" } else { "N" },
-            if ac.current_invert { "Y" } else { "N" },
-            ac.current_contrast
-        );
-
-        // Run OCR with current settings
-        let result = pipeline.process_region_with_preprocessing(
-            &frame,
-            x,
-            y,
-            w,
-            h,
-            test_preprocessing.as_ref(),
-        );
-
-        match result {
-            Ok(ocr_result) => {
-                // Check if we got text
-                let combined_text: String = ocr_result
-                    .text_regions
-                    .iter()
-                    .map(|r| r.text.as_str())
-                    .collect::<Vec<_>>()
-                    .join(" ");
-
-                // Calculate average confidence
-                let avg_confidence = if ocr_result.text_regions.is_empty() {
-                    0.0
-                } else {
-                    ocr_result
+            match result {
+                Ok(ocr_result) => {
+                    let combined_text: String = ocr_result
                         .text_regions
                         .iter()
-                        .map(|r| r.confidence)
-                        .sum::<f32>()
-                        / ocr_result.text_regions.len() as f32
-                };
-
-                let filtered_text = filter_text_by_content_type(&combined_text, &zone.content_type);
+                        .map(|r| r.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
 
-                if !filtered_text.trim().is_empty() {
-                    // Found text - check if this is better than our best so far
-                    let ac = self
-                        .dashboard_state
-                        .vision
-                        .zone_selection
-                        .auto_configure
-                        .as_mut()
-                        .unwrap();
+                    let avg_confidence = if ocr_result.text_regions.is_empty() {
+                        0.0
+                    } else {
+                        ocr_result
+                            .text_regions
+                            .iter()
+                            .map(|r| r.confidence)
+                            .sum::<f32>()
+                            / ocr_result.text_regions.len() as f32
+                    };
 
-                    if avg_confidence > ac.best_confidence {
-                        tracing::info!(
-                            "Auto-configure found better config for '{}': text='{}', confidence={:.2}, settings: {:?}",
-                            zone.name,
-                            filtered_text,
-                            avg_confidence,
-                            test_preprocessing
-                        );
-                        ac.best_confidence = avg_confidence;
-                        ac.best_text = filtered_text;
-                        ac.best_preprocessing = test_preprocessing;
+                    let filtered_text = filter_zone_text(&combined_text, &zone, dictionary);
+                    total_score += auto_tune::score_reading(
+                        &filtered_text,
+                        avg_confidence,
+                        &zone.content_type,
+                    );
+                    total_confidence += avg_confidence;
+                    if runs == 1 {
+                        current_text = filtered_text;
                     }
                 }
-            }
-            Err(e) => {
-                tracing::debug!("Auto-configure OCR failed: {}", e);
+                Err(e) => {
+                    tracing::debug!("Auto-tune OCR failed: {}", e);
+                }
             }
         }
 
-        // Advance to next configuration
+        pipeline.set_allowed_chars(None);
+
+        let score = total_score / runs as f32;
+        let confidence = total_confidence / runs as f32;
+
         let ac = self
             .dashboard_state
             .vision
@@ -2236,59 +6226,33 @@ impl DashboardApp {
             .auto_configure
             .as_mut()
             .unwrap();
-        ac.status_message = status;
-        ac.current_step = AutoConfigureStep::Testing;
-        ac.current_combination += 1;
-
-        // Advance settings: scale -> preprocessing -> grayscale -> invert -> contrast
-        // Contrast values: 1.0, 1.5, 2.0
-        let contrast_values = [1.0, 1.5, 2.0];
-
-        // Find current contrast index
-        let current_contrast_idx = contrast_values
-            .iter()
-            .position(|&c| (c - ac.current_contrast).abs() < 0.01)
-            .unwrap_or(0);
-
-        // Try next contrast
-        if current_contrast_idx + 1 < contrast_values.len() {
-            ac.current_contrast = contrast_values[current_contrast_idx + 1];
-            return;
-        }
-
-        // Reset contrast, try next invert
-        ac.current_contrast = 1.0;
-        if !ac.current_invert {
-            ac.current_invert = true;
-            return;
-        }
 
-        // Reset invert, try next grayscale
-        ac.current_invert = false;
-        if !ac.current_grayscale {
-            ac.current_grayscale = true;
-            return;
-        }
-
-        // Reset grayscale, try next preprocessing state
-        ac.current_grayscale = false;
-        if !ac.current_preprocessing_enabled {
-            ac.current_preprocessing_enabled = true;
-            return;
+        if score > ac.best_score {
+            tracing::info!(
+                "Auto-tune found better config for '{}': text='{}', score={:.2}, settings: {}",
+                zone.name,
+                current_text,
+                score,
+                auto_tune::describe(&candidate)
+            );
+            ac.best_score = score;
+            ac.best_confidence = confidence;
+            ac.best_text = current_text;
+            ac.best_preprocessing = Some(candidate.clone());
         }
 
-        // Reset preprocessing, try next scale
-        ac.current_preprocessing_enabled = false;
-        if ac.current_scale < 4 {
-            ac.current_scale += 1;
+        // Advance to next configuration
+        ac.status_message = format!("Testing: {}", auto_tune::describe(&candidate));
+        ac.current_step = AutoConfigureStep::Testing;
+        ac.current_combination += 1;
+        if ac.current_combination < ac.candidates.len() {
             return;
         }
 
         // All combinations exhausted - apply best configuration if found
         if ac.best_preprocessing.is_some() {
             let best_pp = ac.best_preprocessing.clone();
-            let best_text = ac.best_text.clone();
-            let best_conf = ac.best_confidence;
+            let best_score = ac.best_score;
 
             // Apply the best settings to the zone
             if let Some(zone) = self.dashboard_state.vision.ocr_zones.get_mut(zone_idx) {
@@ -2298,12 +6262,16 @@ impl DashboardApp {
 
             ac.current_step = AutoConfigureStep::Completed;
             ac.success = true;
-            ac.status_message = format!("Best: '{}' ({:.0}%)", best_text, best_conf * 100.0);
+            ac.status_message = format!(
+                "Best: '{}' (score {:.0}%)",
+                ac.best_text,
+                best_score * 100.0
+            );
 
             tracing::info!(
-                "Auto-configure completed for zone {}: applied best config with confidence {:.2}",
+                "Auto-tune completed for zone {}: applied best config with score {:.2}",
                 zone_idx,
-                best_conf
+                best_score
             );
         } else {
             ac.current_step = AutoConfigureStep::Completed;
@@ -2315,16 +6283,7 @@ impl DashboardApp {
     /// Process screen recognition to detect current game screen
     fn process_screen_recognition(&mut self) {
         // Check if screen recognition is enabled for the active profile
-        let (enabled, check_interval_ms) = {
-            let shared = self.shared_state.read();
-            match shared.active_profile() {
-                Some(profile) => (
-                    profile.screen_recognition_enabled,
-                    profile.screen_check_interval_ms,
-                ),
-                None => (false, 500),
-            }
-        };
+        let (enabled, check_interval_ms) = screen_recognition_settings(&self.shared_state.read());
 
         if !enabled {
             // Clear screen state if recognition was disabled
@@ -2335,6 +6294,14 @@ impl DashboardApp {
             return;
         }
 
+        // The last recognized screen stays while the player is away or the
+        // pipeline is paused
+        if self.shared_state.read().runtime.afk
+            || self.dashboard_state.pipeline_debug.stepper.is_paused()
+        {
+            return;
+        }
+
         // Check if enough time has passed since last check
         let elapsed = self.last_screen_check.elapsed();
         if elapsed.as_millis() < check_interval_ms as u128 {
@@ -2343,13 +6310,19 @@ impl DashboardApp {
 
         self.last_screen_check = Instant::now();
 
-        // Get a frame from capture
+        // Get a frame from capture (a frame kept on the GPU is downloaded whole)
         let frame = {
             let capture_guard = self.capture_manager.lock();
-            if let Some(ref capture) = *capture_guard {
-                capture.try_next_frame()
-            } else {
-                None
+            match (&*capture_guard, &self.latest_gpu_frame) {
+                (Some(_), Some(gpu_frame)) => gpu_frame
+                    .download()
+                    .map(Arc::new)
+                    .map_err(|e| tracing::warn!("Failed to download frame: {:#}", e))
+                    .ok(),
+                (Some(capture), None) => {
+                    capture.try_next_frame_for(FrameConsumer::ScreenRecognition)
+                }
+                (None, _) => None,
             }
         };
 
@@ -2361,17 +6334,20 @@ impl DashboardApp {
             return;
         }
 
+        let use_gpu = self.shared_state.read().config.vision.use_gpu;
+        self.screen_recognizer.set_use_gpu(use_gpu);
+        self.screen_recognizer
+            .set_benchmark(self.dashboard_state.screens.benchmark_matching);
+
         // Run screen recognition
         // Note: For now we don't provide an OCR function for text anchors
         // This could be added later by integrating with the vision pipeline
         let result = self
             .screen_recognizer
-            .recognize::<fn(u32, u32, u32, u32) -> Option<String>>(
-                &frame.data,
-                frame.width,
-                frame.height,
-                None, // No OCR function for text anchors yet
-            );
+            .recognize_frame::<fn(u32, u32, u32, u32) -> Option<String>>(&frame, None);
+        self.dashboard_state.screens.match_benchmark = self.screen_recognizer.last_benchmark();
+        self.dashboard_state.screens.gpu_error =
+            self.screen_recognizer.gpu_error().map(String::from);
 
         // Update shared state with result
         let mut shared = self.shared_state.write();
@@ -2393,11 +6369,132 @@ impl DashboardApp {
         shared.runtime.last_screen_check_ms = elapsed.as_millis() as u64;
     }
 
+    /// Run the paused pipeline once on the next frame and keep what each
+    /// stage produced for the pipeline step window
+    fn process_pipeline_step(&mut self) {
+        if !self.dashboard_state.pipeline_debug.stepper.wants_step() {
+            return;
+        }
+
+        // Wait for a manual OCR to finish; a pasted image keeps its readings
+        let vision = &self.dashboard_state.vision;
+        if vision.is_processing || vision.showing_clipboard_image {
+            return;
+        }
+
+        // A frame kept on the GPU is downloaded whole, as for screen recognition
+        let frame = {
+            let capture_guard = self.capture_manager.lock();
+            match (&*capture_guard, &self.latest_gpu_frame) {
+                (Some(_), Some(gpu_frame)) => gpu_frame
+                    .download()
+                    .map(Arc::new)
+                    .map_err(|e| tracing::warn!("Failed to download frame: {:#}", e))
+                    .ok(),
+                (Some(capture), None) => capture.try_next_frame_for(FrameConsumer::ZoneOcr),
+                (None, _) => None,
+            }
+        };
+        let Some(frame) = frame.filter(|f| f.width > 0 && f.height > 0) else {
+            return;
+        };
+        let Some(step) = self.dashboard_state.pipeline_debug.stepper.take_step() else {
+            return;
+        };
+        let started = Instant::now();
+
+        let mut zones = Vec::new();
+        self.ocr_zones_in_frame(FrameSource::Cpu(&frame), None, Some(&mut zones));
+
+        // Every screen is scored; the best match becomes the current screen
+        let (recognition_enabled, _) = screen_recognition_settings(&self.shared_state.read());
+        let screens = if recognition_enabled {
+            self.screen_recognizer.clear_cache();
+            let screens = self.screen_recognizer.score_screens(&frame);
+            let result = self
+                .screen_recognizer
+                .recognize_frame::<fn(u32, u32, u32, u32) -> Option<String>>(&frame, None);
+            self.shared_state.write().runtime.update_screen(result);
+            screens
+        } else {
+            Vec::new()
+        };
+
+        // Rules are checked against what they'd see after this frame
+        let mut game_state = GameState {
+            phase: self.phase_tracker.current().map(str::to_string),
+            ..Default::default()
+        };
+        for result in self.dashboard_state.vision.zone_ocr_results.values() {
+            if result.occluded {
+                game_state.occluded_zones.insert(result.zone_id.clone());
+            }
+            game_state
+                .text_values
+                .insert(result.zone_id.clone(), result.text.clone());
+        }
+        for zone in &zones {
+            if let Some(confidence) = zone.confidence {
+                game_state
+                    .zone_confidences
+                    .insert(zone.zone_id.clone(), confidence);
+            }
+        }
+        let rules: Vec<RuleStep> = {
+            let shared = self.shared_state.read();
+            if let Some(ref screen) = shared.runtime.current_screen {
+                game_state.screen_context = ScreenContext::from_match(
+                    Some(screen.screen_id.clone()),
+                    Some(screen.screen_name.clone()),
+                    screen.confidence,
+                    false,
+                    None,
+                    None,
+                    screen.parent_chain.clone(),
+                );
+            }
+            self.active_profile
+                .as_ref()
+                .map(|profile| effective_rules(profile, &shared.rule_library))
+                .unwrap_or_default()
+                .iter()
+                .map(|definition| RuleStep::check(&Rule::from(definition), &game_state))
+                .collect()
+        };
+
+        tracing::debug!(
+            "Pipeline step {}: {} zones, {} screens, {} rules in {:?}",
+            step,
+            zones.len(),
+            screens.len(),
+            rules.len(),
+            started.elapsed()
+        );
+        let debug = &mut self.dashboard_state.pipeline_debug;
+        debug.crop_textures.clear();
+        debug.step = Some(StepArtifacts {
+            step,
+            at: Instant::now(),
+            frame_size: (frame.width, frame.height),
+            duration: started.elapsed(),
+            zones,
+            screens,
+            rules,
+        });
+    }
+
     /// Reload screens into the recognizer from the active profile
     fn reload_screens_from_profile(&mut self) {
+        let dictionary = self
+            .active_profile
+            .as_ref()
+            .map(|p| p.dictionary.clone())
+            .unwrap_or_default();
+        self.screen_recognizer.set_dictionary(dictionary);
+
         if let Some(ref profile) = self.active_profile {
             if !profile.screens.is_empty() {
-                self.screen_recognizer.load_screens(profile.screens.clone());
+                load_profile_screens(&mut self.screen_recognizer, profile);
                 tracing::info!(
                     "Reloaded {} screens for recognition from profile '{}'",
                     profile.screens.len(),
@@ -2424,67 +6521,141 @@ pub fn run_dashboard(shared_state: Arc<RwLock<SharedAppState>>) -> Result<(), ef
     )
 }
 
-/// Filter OCR text based on the expected content type
-/// This helps clean up OCR results by removing characters that don't match the expected type
-fn filter_text_by_content_type(text: &str, content_type: &ContentType) -> String {
-    match content_type {
-        ContentType::Text => {
-            // For text, just trim whitespace
-            text.trim().to_string()
-        }
-        ContentType::Number => {
-            // Keep only digits, decimal points, commas (for thousands), and minus sign
-            // Also handle common OCR mistakes: O->0, l/I->1, S->5, B->8
-            let cleaned: String = text
-                .chars()
-                .map(|c| match c {
-                    'O' | 'o' => '0',
-                    'l' | 'I' | '|' => '1',
-                    'S' | 's' => '5',
-                    'B' => '8',
-                    _ => c,
-                })
-                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',' || *c == '-')
-                .collect();
-            cleaned
-        }
-        ContentType::Percentage => {
-            // Keep digits, decimal points, and percent sign
-            let cleaned: String = text
-                .chars()
-                .map(|c| match c {
-                    'O' | 'o' => '0',
-                    'l' | 'I' | '|' => '1',
-                    'S' | 's' => '5',
-                    'B' => '8',
-                    _ => c,
-                })
-                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '%' || *c == '-')
-                .collect();
-            // Ensure % is at the end if present anywhere
-            if cleaned.contains('%') {
-                let without_percent: String = cleaned.chars().filter(|c| *c != '%').collect();
-                format!("{}%", without_percent)
-            } else {
-                cleaned
-            }
-        }
-        ContentType::Time => {
-            // Keep digits and colons for time formats like 12:34 or 1:23:45
-            let cleaned: String = text
-                .chars()
-                .map(|c| match c {
-                    'O' | 'o' => '0',
-                    'l' | 'I' | '|' => '1',
-                    _ => c,
-                })
-                .filter(|c| c.is_ascii_digit() || *c == ':')
-                .collect();
-            cleaned
+/// Games whose launch auto-starts their profile, plus the active profile's
+/// game, or None when launches aren't watched at all (integrations off, or
+/// safe mode, which never activates a profile on its own)
+fn watched_games(state: &SharedAppState) -> Option<Vec<WatchedGame>> {
+    if state.runtime.safe_mode || !state.config.features.integrations {
+        return None;
+    }
+    let games = state
+        .profiles
+        .iter()
+        .filter(|p| {
+            p.auto_start.capture || state.active_profile_id.as_deref() == Some(p.id.as_str())
+        })
+        .filter(|p| !p.executables.is_empty())
+        .map(|p| WatchedGame {
+            profile_id: p.id.clone(),
+            executables: p.executables.clone(),
+        })
+        .collect();
+    Some(games)
+}
+
+/// Capture frame rate limit in effect: the lower of the low-power preset's
+/// and the CPU budget's
+fn capture_fps_limit(state: &SharedAppState) -> Option<u32> {
+    [
+        state.runtime.power.fps_limit(&state.config.performance),
+        state.runtime.cpu_budget.fps_limit,
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+}
+
+/// Whether screen recognition runs for the active profile, and how often
+/// (ms)
+fn screen_recognition_settings(shared: &SharedAppState) -> (bool, u32) {
+    // The low-power preset can turn template matching off
+    let allowed = shared
+        .runtime
+        .power
+        .allows_template_matching(&shared.config.performance);
+    let allowed = allowed && shared.config.features.template_matching;
+    match shared.active_profile() {
+        Some(profile) => (
+            allowed && profile.screen_recognition_enabled,
+            profile.screen_check_interval_ms,
+        ),
+        None => (false, 500),
+    }
+}
+
+/// What's on screen right now, for checklist items and game phases
+fn screen_conditions<'a>(
+    shared: &'a SharedAppState,
+    vision: &'a crate::dashboard::state::VisionViewState,
+) -> ChecklistContext<'a> {
+    ChecklistContext {
+        screen_id: shared
+            .runtime
+            .current_screen
+            .as_ref()
+            .map(|m| m.screen_id.as_str()),
+        color_triggers: Some(&shared.runtime.active_color_triggers),
+        zone_texts: vision
+            .zone_ocr_results
+            .values()
+            .filter(|result| !result.occluded)
+            .map(|result| (result.zone_id.as_str(), result.text.as_str()))
+            .collect(),
+    }
+}
+
+/// Initialize the second backend for ensemble zones
+///
+/// PaddleOCR is only initialized when its models are already downloaded, so
+/// ensemble mode never triggers a download.
+fn init_ensemble_backend(
+    pipeline: &mut VisionPipeline,
+    backend: crate::vision::OcrBackend,
+) -> anyhow::Result<()> {
+    if backend == crate::vision::OcrBackend::PaddleOcr && !pipeline.are_models_ready() {
+        anyhow::bail!("PaddleOCR models are not downloaded");
+    }
+    pipeline.init_backend(backend)
+}
+
+/// Load a profile's screens into the recognizer, with templates decoded on
+/// an earlier run taken from the profile's template cache
+fn load_profile_screens(recognizer: &mut ScreenRecognizer, profile: &GameProfile) {
+    let modified = crate::storage::get_profiles_dir()
+        .and_then(|dir| Ok(std::fs::metadata(dir.join(format!("{}.json", profile.id)))?))
+        .and_then(|metadata| Ok(metadata.modified()?));
+    let cache_path = crate::storage::get_cache_dir()
+        .map(|dir| dir.join(format!("{}.templates", profile.id)))
+        .ok();
+    let cache = match (&cache_path, modified) {
+        (Some(path), Ok(modified)) => TemplateCache::load(path, modified),
+        // Profiles not saved yet have nothing to check a cache against
+        _ => TemplateCache::default(),
+    };
+    let cached = cache.len();
+
+    let started = Instant::now();
+    recognizer.set_template_cache(cache);
+    recognizer.load_screens(profile.screens.clone());
+    tracing::debug!(
+        "Loaded screens of '{}' in {:?} ({} templates cached)",
+        profile.name,
+        started.elapsed(),
+        cached
+    );
+
+    if let Some(path) = cache_path {
+        if let Err(e) = recognizer.template_cache_mut().save_if_changed(&path) {
+            tracing::warn!("Failed to save template cache: {:#}", e);
         }
     }
 }
 
+/// Encode and save a screenshot off the UI thread, for DashboardApp to report
+fn save_screenshot_in_background(
+    shared_state: &Arc<RwLock<SharedAppState>>,
+    frame: Arc<crate::capture::CapturedFrame>,
+    path: Option<PathBuf>,
+) {
+    let shared_state = shared_state.clone();
+    std::thread::spawn(move || {
+        let result = crate::storage::get_screenshots_dir()
+            .and_then(|dir| screenshots::save_screenshot(&dir, &frame, path.as_deref()))
+            .map_err(|e| format!("{:#}", e));
+        shared_state.write().runtime.saved_screenshot = Some(result);
+    });
+}
+
 /// Extract a region from BGRA frame data and encode as PNG
 fn extract_region_as_png(
     frame_data: &[u8],
@@ -2542,3 +6713,46 @@ fn extract_region_as_png(
 
     Some(png_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn test_safe_mode_watches_no_games() {
+        let mut profile = crate::storage::starter_packs::STARTER_PACKS[0]
+            .profile()
+            .unwrap();
+        profile.auto_start.capture = true;
+        profile.executables = vec!["game.exe".to_string()];
+
+        let mut state = SharedAppState::new(AppConfig::default());
+        state.config.features.integrations = true;
+        state.profiles = vec![profile];
+        assert_eq!(watched_games(&state).map(|games| games.len()), Some(1));
+
+        // No watcher is started, so nothing is ever handed to set_watched
+        state.runtime.safe_mode = true;
+        assert_eq!(watched_games(&state), None);
+    }
+
+    #[test]
+    fn test_active_profile_game_is_watched() {
+        let mut profile = crate::storage::starter_packs::STARTER_PACKS[0]
+            .profile()
+            .unwrap();
+        profile.executables = vec!["game.exe".to_string()];
+        let profile_id = profile.id.clone();
+
+        let mut state = SharedAppState::new(AppConfig::default());
+        state.config.features.integrations = true;
+        state.profiles = vec![profile];
+        assert_eq!(watched_games(&state), Some(Vec::new()));
+
+        state.active_profile_id = Some(profile_id.clone());
+        let games = watched_games(&state).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].profile_id, profile_id);
+    }
+}
@@ -0,0 +1,206 @@
+//! Bundled changelog for the "What's New" panel
+//!
+//! Release notes live in `assets/changelog.toml` and are embedded in the binary.
+//! Items can carry a deep link to the dashboard view or settings section that
+//! introduces the feature.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::cmp::Ordering;
+
+use super::state::{DashboardView, SettingsSection};
+
+/// Embedded changelog source
+const CHANGELOG: &str = include_str!("../../assets/changelog.toml");
+
+/// Release notes for one version
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    /// Version number
+    pub version: String,
+    /// Release date (free-form)
+    #[serde(default)]
+    pub date: String,
+    /// Notable changes
+    #[serde(default, rename = "item")]
+    pub items: Vec<ChangelogItem>,
+}
+
+/// A single changelog entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangelogItem {
+    /// Short title
+    pub title: String,
+    /// One or two sentence description
+    pub description: String,
+    /// Optional deep link (see `DeepLink::parse`)
+    #[serde(default)]
+    pub link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChangelogFile {
+    #[serde(default, rename = "release")]
+    releases: Vec<Release>,
+}
+
+/// A place in the dashboard a changelog item can jump to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeepLink {
+    /// View to open
+    pub view: DashboardView,
+    /// Settings section to expand (settings links only)
+    pub section: Option<SettingsSection>,
+}
+
+impl DeepLink {
+    /// Parse a link such as `vision` or `settings/telemetry`
+    pub fn parse(link: &str) -> Option<Self> {
+        let (view, section) = match link.split_once('/') {
+            Some((view, section)) => (view, Some(section)),
+            None => (link, None),
+        };
+
+        let view = match view {
+            "home" => DashboardView::Home,
+            "capture" => DashboardView::Capture,
+            "overlay" => DashboardView::Overlay,
+            "vision" => DashboardView::Vision,
+            "screens" => DashboardView::Screens,
+            "profiles" => DashboardView::Profiles,
+            "data" => DashboardView::Data,
+            "settings" => DashboardView::Settings,
+            _ => return None,
+        };
+
+        let section = match (view, section) {
+            (_, None) => None,
+            (DashboardView::Settings, Some(key)) => Some(SettingsSection::from_key(key)?),
+            _ => return None,
+        };
+
+        Some(Self { view, section })
+    }
+}
+
+/// All bundled releases, newest first
+pub fn releases() -> Result<Vec<Release>> {
+    parse_changelog(CHANGELOG)
+}
+
+fn parse_changelog(content: &str) -> Result<Vec<Release>> {
+    let file: ChangelogFile = toml::from_str(content).context("Invalid bundled changelog")?;
+    Ok(file.releases)
+}
+
+/// Releases the user has not seen yet
+///
+/// Returns releases newer than `last_seen` up to and including `current`. On a
+/// fresh install (`last_seen` is `None`) only the current release is returned.
+pub fn unseen_releases(
+    releases: Vec<Release>,
+    last_seen: Option<&str>,
+    current: &str,
+) -> Vec<Release> {
+    releases
+        .into_iter()
+        .filter(|release| compare_versions(&release.version, current) != Ordering::Greater)
+        .filter(|release| match last_seen {
+            Some(seen) => compare_versions(&release.version, seen) == Ordering::Greater,
+            None => compare_versions(&release.version, current) == Ordering::Equal,
+        })
+        .collect()
+}
+
+/// Compare dotted version numbers numerically (missing parts count as 0)
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+
+    for i in 0..a.len().max(b.len()) {
+        let ordering = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(version: &str) -> Release {
+        Release {
+            version: version.to_string(),
+            date: String::new(),
+            items: vec![],
+        }
+    }
+
+    fn versions(releases: &[Release]) -> Vec<&str> {
+        releases.iter().map(|r| r.version.as_str()).collect()
+    }
+
+    #[test]
+    fn test_bundled_changelog_is_valid() {
+        let releases = releases().unwrap();
+        assert!(!releases.is_empty());
+
+        for item in releases.iter().flat_map(|r| &r.items) {
+            if let Some(ref link) = item.link {
+                assert!(DeepLink::parse(link).is_some(), "bad link '{}'", link);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("0.2.0", "0.1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("0.10.0", "0.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("v1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0-beta", "1.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_unseen_releases() {
+        let all = || vec![release("0.3.0"), release("0.2.0"), release("0.1.0")];
+
+        assert_eq!(
+            versions(&unseen_releases(all(), Some("0.1.0"), "0.2.0")),
+            vec!["0.2.0"]
+        );
+        assert_eq!(
+            versions(&unseen_releases(all(), Some("0.1.0"), "0.3.0")),
+            vec!["0.3.0", "0.2.0"]
+        );
+        assert!(unseen_releases(all(), Some("0.3.0"), "0.3.0").is_empty());
+        assert_eq!(
+            versions(&unseen_releases(all(), None, "0.2.0")),
+            vec!["0.2.0"]
+        );
+    }
+
+    #[test]
+    fn test_deep_links() {
+        assert_eq!(
+            DeepLink::parse("settings/telemetry"),
+            Some(DeepLink {
+                view: DashboardView::Settings,
+                section: Some(SettingsSection::Telemetry),
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("settings/network").unwrap().section,
+            Some(SettingsSection::Network)
+        );
+        assert_eq!(DeepLink::parse("vision").unwrap().section, None);
+        assert!(DeepLink::parse("vision/telemetry").is_none());
+        assert!(DeepLink::parse("nowhere").is_none());
+    }
+}
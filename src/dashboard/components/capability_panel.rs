@@ -0,0 +1,78 @@
+//! System capability checks panel
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::dashboard::theme::ThemeColors;
+use crate::diagnostics::CheckStatus;
+use crate::shared::SharedAppState;
+
+/// Render the capability check results with remediation hints
+pub fn render_capability_panel(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let mut recheck = false;
+
+    ui.horizontal(|ui| {
+        ui.heading(RichText::new("System Checks").size(18.0));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let running = shared_state.read().runtime.capability_checks.is_none();
+            if ui
+                .add_enabled(!running, egui::Button::new("Run Again"))
+                .clicked()
+            {
+                recheck = true;
+            }
+        });
+    });
+
+    ui.add_space(12.0);
+
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            let state = shared_state.read();
+            let Some(ref checks) = state.runtime.capability_checks else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(RichText::new("Checking system...").color(ThemeColors::TEXT_MUTED));
+                });
+                return;
+            };
+
+            for check in checks {
+                let color = match check.status {
+                    CheckStatus::Ok => ThemeColors::ACCENT_SUCCESS,
+                    CheckStatus::Warning => ThemeColors::ACCENT_WARNING,
+                    CheckStatus::Error => ThemeColors::ACCENT_ERROR,
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("●").color(color));
+                    ui.label(RichText::new(check.name).strong());
+                    ui.add_space(8.0);
+                    ui.label(RichText::new(&check.detail).color(ThemeColors::TEXT_SECONDARY));
+                });
+
+                if let Some(hint) = check.hint {
+                    ui.horizontal(|ui| {
+                        ui.add_space(20.0);
+                        ui.label(
+                            RichText::new(hint)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    });
+                }
+
+                ui.add_space(4.0);
+            }
+        });
+
+    if recheck {
+        shared_state.write().runtime.recheck_capabilities = true;
+    }
+}
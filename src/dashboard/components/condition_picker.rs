@@ -0,0 +1,46 @@
+//! Condition picker component
+//!
+//! Picks what kind of condition a checklist item or watch waits for, or
+//! none. The caller edits the details of the picked kind.
+
+use egui::Ui;
+
+/// A condition that comes in kinds picked from a menu
+pub trait ConditionKind {
+    /// Menu labels of the kinds, in menu order
+    fn kind_labels() -> Vec<&'static str>;
+
+    /// Index of this condition's kind in the menu
+    fn kind(&self) -> usize;
+}
+
+/// Add a menu of condition kinds, with `none_label` for no condition
+///
+/// Picking another kind replaces the condition with `make(kind, previous)`,
+/// so values that still apply can carry over.
+pub fn add_condition_picker<T: ConditionKind>(
+    ui: &mut Ui,
+    id_salt: impl std::hash::Hash,
+    width: f32,
+    none_label: &str,
+    condition: &mut Option<T>,
+    mut make: impl FnMut(usize, Option<T>) -> T,
+) {
+    let labels = T::kind_labels();
+    let current = condition.as_ref().map(T::kind);
+
+    egui::ComboBox::from_id_salt(id_salt)
+        .width(width)
+        .selected_text(current.map_or(none_label, |kind| labels[kind]))
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(current.is_none(), none_label).clicked() {
+                *condition = None;
+            }
+            for (kind, label) in labels.iter().enumerate() {
+                let selected = current == Some(kind);
+                if ui.selectable_label(selected, *label).clicked() && !selected {
+                    *condition = Some(make(kind, condition.take()));
+                }
+            }
+        });
+}
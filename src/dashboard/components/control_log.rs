@@ -0,0 +1,76 @@
+//! Control log component listing commands from hotkeys and MCP
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::dashboard::theme::ThemeColors;
+use crate::shared::SharedAppState;
+
+/// Render the control log (nothing is shown before the first command)
+pub fn render_control_log(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let mut state = shared_state.write();
+    let log = &state.runtime.control_log;
+    if log.is_empty() {
+        return;
+    }
+
+    let mut clear = false;
+
+    ui.horizontal(|ui| {
+        ui.heading(RichText::new("Control Log").size(18.0));
+        ui.add_space(8.0);
+        let mut summary = format!("{} recent", log.len());
+        if log.refused() > 0 {
+            summary = format!("{}, {} rate limited", summary, log.refused());
+        }
+        ui.label(
+            RichText::new(summary)
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        )
+        .on_hover_text(
+            "Commands from hotkeys and MCP tools, to trace what changed the overlay. \
+             Commands arriving faster than their source's rate limit are refused.",
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Clear").clicked() {
+                clear = true;
+            }
+        });
+    });
+
+    ui.add_space(12.0);
+
+    egui::ScrollArea::vertical()
+        .id_salt("control_log_scroll")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            egui::Grid::new("control_log_grid")
+                .num_columns(4)
+                .spacing([24.0, 4.0])
+                .show(ui, |ui| {
+                    for entry in log.iter_recent() {
+                        ui.label(
+                            RichText::new(format!("{}s ago", entry.at.elapsed().as_secs()))
+                                .size(11.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.label(RichText::new(entry.source.name()).strong());
+                        ui.label(&entry.command);
+                        if entry.allowed {
+                            ui.label(RichText::new("Ran").color(ThemeColors::ACCENT_SUCCESS));
+                        } else {
+                            ui.label(
+                                RichText::new("Rate limited").color(ThemeColors::ACCENT_WARNING),
+                            );
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if clear {
+        state.runtime.control_log.clear();
+    }
+}
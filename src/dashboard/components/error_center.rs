@@ -0,0 +1,102 @@
+//! Error center component listing recent errors with suggested fixes
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::dashboard::theme::{color_with_alpha, ThemeColors};
+use crate::shared::SharedAppState;
+
+/// Render the error center (nothing is shown when there are no errors)
+pub fn render_error_center(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let mut state = shared_state.write();
+    if state.runtime.errors.is_empty() {
+        return;
+    }
+
+    let mut dismiss_index = None;
+    let mut clear_all = false;
+
+    ui.horizontal(|ui| {
+        ui.heading(RichText::new("Error Center").size(18.0));
+        ui.add_space(8.0);
+        ui.label(
+            RichText::new(format!("{} recent", state.runtime.errors.len()))
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Clear All").clicked() {
+                clear_all = true;
+            }
+        });
+    });
+
+    ui.add_space(12.0);
+
+    egui::ScrollArea::vertical()
+        .id_salt("error_center_scroll")
+        .max_height(300.0)
+        .show(ui, |ui| {
+            for (index, entry) in state.runtime.errors.iter_recent().enumerate() {
+                let category = entry.error.category();
+
+                egui::Frame::none()
+                    .fill(color_with_alpha(ThemeColors::ACCENT_ERROR, 38)) // ~0.15 alpha
+                    .rounding(egui::Rounding::same(6.0))
+                    .inner_margin(12.0)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(category.name())
+                                    .color(ThemeColors::ACCENT_ERROR)
+                                    .strong(),
+                            );
+                            ui.label(
+                                RichText::new(entry.error.message())
+                                    .color(ThemeColors::TEXT_PRIMARY),
+                            );
+
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui.small_button("Dismiss").clicked() {
+                                        dismiss_index = Some(index);
+                                    }
+                                    let mut when =
+                                        format!("{}s ago", entry.last_seen.elapsed().as_secs());
+                                    if entry.count > 1 {
+                                        when = format!("x{}  {}", entry.count, when);
+                                    }
+                                    ui.label(
+                                        RichText::new(when)
+                                            .size(11.0)
+                                            .color(ThemeColors::TEXT_MUTED),
+                                    );
+                                },
+                            );
+                        });
+
+                        ui.label(
+                            RichText::new(category.suggested_fix())
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_SECONDARY),
+                        );
+                    });
+
+                ui.add_space(8.0);
+            }
+        });
+
+    if clear_all {
+        state.runtime.errors.clear();
+        state.runtime.clear_error();
+    } else if let Some(index) = dismiss_index {
+        state.runtime.errors.dismiss(index);
+        if state.runtime.errors.is_empty() {
+            state.runtime.clear_error();
+        }
+    }
+}
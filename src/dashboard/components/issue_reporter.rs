@@ -0,0 +1,168 @@
+//! "Report a Problem" window
+//!
+//! Collects a title and description, shows the issue body that will be
+//! pre-filled with version, backend and system check results, and opens it as
+//! a new GitHub issue in the browser.
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::dashboard::issue_report::{issue_body, issue_url, IssueContext};
+use crate::dashboard::state::IssueReportState;
+use crate::dashboard::theme::ThemeColors;
+use crate::shared::SharedAppState;
+use crate::vision::OcrBackend;
+
+/// What the app knows about itself right now
+fn issue_context(state: &SharedAppState, attach_snapshot: bool) -> IssueContext {
+    let vision = &state.config.vision;
+    let backend = match vision.backend {
+        OcrBackend::WindowsOcr => "Windows OCR".to_string(),
+        OcrBackend::PaddleOcr => format!("PaddleOCR ({})", vision.quality.name()),
+    };
+    let gpu = match (vision.use_gpu, &vision.gpu_adapter) {
+        (_, Some(adapter)) => adapter.clone(),
+        (true, None) => "Windows default".to_string(),
+        (false, None) => "not used".to_string(),
+    };
+
+    IssueContext {
+        backend,
+        gpu,
+        capture: state
+            .runtime
+            .is_capturing
+            .then(|| state.capture_config.target.label()),
+        profile: state.active_profile().map(|p| p.name.clone()),
+        checks: state.runtime.capability_checks.clone(),
+        snapshot: state
+            .runtime
+            .last_snapshot
+            .clone()
+            .filter(|_| attach_snapshot),
+    }
+}
+
+/// Render the problem report window (if open)
+pub fn render_issue_reporter(
+    ctx: &egui::Context,
+    report: &mut IssueReportState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let mut open = report.open;
+    let mut submitted = false;
+
+    egui::Window::new("Report a Problem")
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(520.0, 480.0))
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(
+                    "Opens a new GitHub issue in your browser with the details below filled \
+                     in. Nothing is sent until you submit it there.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Title:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut report.title)
+                        .hint_text("e.g. Gold zone reads 0 at night")
+                        .desired_width(f32::INFINITY),
+                );
+            });
+            ui.add_space(4.0);
+            ui.label("What happened:");
+            ui.add(
+                egui::TextEdit::multiline(&mut report.description)
+                    .hint_text("What you did, what you expected and what you saw")
+                    .desired_rows(5)
+                    .desired_width(f32::INFINITY),
+            );
+
+            ui.add_space(8.0);
+            let (last_snapshot, exporting) = {
+                let state = shared_state.read();
+                (
+                    state.runtime.last_snapshot.clone(),
+                    state.runtime.exporting_snapshot,
+                )
+            };
+            ui.horizontal(|ui| {
+                ui.add_enabled(
+                    last_snapshot.is_some(),
+                    egui::Checkbox::new(&mut report.attach_snapshot, "Attach snapshot"),
+                );
+                if ui
+                    .add_enabled(!exporting, egui::Button::new("Export Snapshot"))
+                    .on_hover_text("Save config, active profile, recent samples and events")
+                    .clicked()
+                {
+                    shared_state.write().runtime.export_snapshot = true;
+                    report.attach_snapshot = true;
+                }
+            });
+            if let Some(ref path) = last_snapshot.filter(|_| report.attach_snapshot) {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Drag this file into the issue:")
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                    if let Some(dir) = path.parent() {
+                        let url =
+                            format!("file:///{}", dir.display().to_string().replace('\\', "/"));
+                        ui.hyperlink_to(
+                            path.file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default(),
+                            url,
+                        );
+                    }
+                });
+            }
+
+            let body = issue_body(
+                &report.description,
+                &issue_context(&shared_state.read(), report.attach_snapshot),
+            );
+            ui.add_space(8.0);
+            egui::CollapsingHeader::new("Issue preview")
+                .default_open(false)
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("issue_preview")
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new(&body).monospace().size(11.0));
+                        });
+                });
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let title = report.title.trim();
+                if ui
+                    .add_enabled(!title.is_empty(), egui::Button::new("Open on GitHub"))
+                    .clicked()
+                {
+                    ui.ctx()
+                        .open_url(egui::OpenUrl::new_tab(issue_url(title, &body)));
+                    submitted = true;
+                }
+                if ui.button("Copy Details").clicked() {
+                    ui.output_mut(|o| o.copied_text = body.clone());
+                }
+            });
+        });
+
+    report.open = open && !submitted;
+    if submitted {
+        *report = IssueReportState::default();
+    }
+}
@@ -1,8 +1,34 @@
 //! Reusable UI components for the dashboard
 
+pub mod capability_panel;
+pub mod condition_picker;
+pub mod control_log;
+pub mod error_center;
+pub mod issue_reporter;
+pub mod phase_picker;
+pub mod pipeline_debug;
+pub mod reference_panel;
+pub mod remembered;
+pub mod screen_text;
 pub mod scroll_slider;
 pub mod sidebar;
 pub mod status_card;
+pub mod validation_report;
+pub mod watch_panel;
+pub mod whats_new;
 
+pub use capability_panel::render_capability_panel;
+pub use condition_picker::{add_condition_picker, ConditionKind};
+pub use control_log::render_control_log;
+pub use error_center::render_error_center;
+pub use issue_reporter::render_issue_reporter;
+pub use phase_picker::add_phase_picker;
+pub use pipeline_debug::render_pipeline_debug;
+pub use reference_panel::render_reference_panel;
+pub use remembered::{remember_width, remembered_scroll, remembered_section, remembered_width};
+pub use screen_text::{render_screen_text, ScreenTextAction};
 pub use scroll_slider::add_scroll_slider;
 pub use sidebar::render_sidebar;
+pub use validation_report::{render_validation_report, ValidationAction};
+pub use watch_panel::render_watch_panel;
+pub use whats_new::{render_whats_new, WhatsNewAction};
@@ -0,0 +1,42 @@
+//! Game phase picker component
+//!
+//! Limits a zone or checklist to some of the profile's game phases.
+
+use egui::{RichText, Ui};
+
+use crate::dashboard::theme::ThemeColors;
+
+/// Add a checkbox per game phase and return whether the selection changed
+///
+/// `phases` are the profile's phases as (ID, name); `selected` holds the
+/// IDs checked, and nothing checked means every phase. Phases no longer in
+/// the profile stay selected but aren't shown.
+pub fn add_phase_picker(
+    ui: &mut Ui,
+    phases: &[(String, String)],
+    selected: &mut Vec<String>,
+) -> bool {
+    let mut changed = false;
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Active in:");
+        for (id, name) in phases {
+            let mut checked = selected.contains(id);
+            if ui.checkbox(&mut checked, name).changed() {
+                if checked {
+                    selected.push(id.clone());
+                } else {
+                    selected.retain(|phase| phase != id);
+                }
+                changed = true;
+            }
+        }
+        if !phases.iter().any(|(id, _)| selected.contains(id)) {
+            ui.label(
+                RichText::new("(every phase)")
+                    .size(11.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+        }
+    });
+    changed
+}
@@ -0,0 +1,321 @@
+//! Pipeline step window
+//!
+//! Pauses zone OCR and screen recognition and steps them one frame at a
+//! time, showing what each stage produced for the stepped frame. Closing
+//! the window lets the pipeline run again.
+
+use egui::RichText;
+
+use crate::analysis::rules::InputConfidence;
+use crate::analysis::stepper::{RuleStep, StepArtifacts, ZoneStep};
+use crate::dashboard::state::PipelineDebugState;
+use crate::dashboard::theme::ThemeColors;
+
+/// Tallest a zone crop is drawn
+const MAX_CROP_HEIGHT: f32 = 64.0;
+
+/// Render the pipeline step window (if open)
+pub fn render_pipeline_debug(ctx: &egui::Context, state: &mut PipelineDebugState) {
+    let mut open = state.open;
+
+    egui::Window::new("Pipeline Step")
+        .open(&mut open)
+        .collapsible(true)
+        .default_size(egui::vec2(620.0, 520.0))
+        .show(ctx, |ui| {
+            render_controls(ui, state);
+            ui.add_space(8.0);
+
+            let Some(ref step) = state.step else {
+                ui.label(
+                    RichText::new(
+                        "Nothing stepped yet. Step to run zone OCR and screen recognition once \
+                         on the next captured frame.",
+                    )
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+                );
+                return;
+            };
+
+            ui.label(
+                RichText::new(format!(
+                    "Step {}: {}x{} frame, {} ms, {:.0}s ago",
+                    step.step,
+                    step.frame_size.0,
+                    step.frame_size.1,
+                    step.duration.as_millis(),
+                    step.at.elapsed().as_secs_f32()
+                ))
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("pipeline_step")
+                .show(ui, |ui| {
+                    render_zones(ui, step, &mut state.crop_textures);
+                    ui.add_space(8.0);
+                    render_screens(ui, step);
+                    ui.add_space(8.0);
+                    render_rules(ui, &step.rules);
+                });
+        });
+
+    // A closed window doesn't leave the pipeline paused
+    if !open && state.stepper.is_paused() {
+        state.stepper.resume();
+    }
+    state.open = open;
+}
+
+/// Pause, step and resume buttons
+fn render_controls(ui: &mut egui::Ui, state: &mut PipelineDebugState) {
+    ui.horizontal(|ui| {
+        let paused = state.stepper.is_paused();
+        if paused {
+            if ui
+                .button("Resume")
+                .on_hover_text("Run the pipeline live again")
+                .clicked()
+            {
+                state.stepper.resume();
+            }
+        } else if ui
+            .button("Pause")
+            .on_hover_text("Hold zone OCR and screen recognition on their last readings")
+            .clicked()
+        {
+            state.stepper.pause();
+        }
+        if ui
+            .add_enabled(!state.stepper.wants_step(), egui::Button::new("Step"))
+            .on_hover_text("Run the pipeline once on the next frame")
+            .clicked()
+        {
+            state.stepper.request_step();
+        }
+
+        let (status, color) = if state.stepper.wants_step() {
+            ("Waiting for a frame...", ThemeColors::ACCENT_WARNING)
+        } else if paused {
+            ("Paused", ThemeColors::ACCENT_WARNING)
+        } else {
+            ("Running", ThemeColors::STATUS_RUNNING)
+        };
+        ui.label(RichText::new(status).color(color));
+    });
+}
+
+/// Zone crops as OCR got them, with what was read from them
+fn render_zones(
+    ui: &mut egui::Ui,
+    step: &StepArtifacts,
+    textures: &mut std::collections::HashMap<String, egui::TextureHandle>,
+) {
+    ui.label(RichText::new(format!("Zones ({})", step.zones.len())).strong());
+    if step.zones.is_empty() {
+        ui.label(
+            RichText::new("No enabled zones were read")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    for zone in &step.zones {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&zone.zone_name).strong());
+                if let Some(ref reason) = zone.skipped {
+                    ui.label(RichText::new(reason).color(ThemeColors::TEXT_MUTED));
+                    return;
+                }
+                ui.label(RichText::new(format!("\"{}\"", zone.text)).monospace());
+                if let Some(confidence) = zone.confidence {
+                    ui.label(
+                        RichText::new(format!("{:.0}%", confidence * 100.0))
+                            .color(confidence_color(confidence)),
+                    );
+                }
+            });
+            if zone.skipped.is_none() {
+                render_crop(ui, zone, textures);
+            }
+        });
+    }
+}
+
+/// A zone's crop and the text regions found in it
+fn render_crop(
+    ui: &mut egui::Ui,
+    zone: &ZoneStep,
+    textures: &mut std::collections::HashMap<String, egui::TextureHandle>,
+) {
+    ui.horizontal(|ui| {
+        if let Some((ref data, width, height)) = zone.crop {
+            let texture = textures.entry(zone.zone_id.clone()).or_insert_with(|| {
+                let image =
+                    egui::ColorImage::from_rgba_unmultiplied([width as _, height as _], data);
+                ui.ctx().load_texture(
+                    format!("pipeline_step_{}", zone.zone_id),
+                    image,
+                    egui::TextureOptions::NEAREST,
+                )
+            });
+            let scale = (MAX_CROP_HEIGHT / height.max(1) as f32).min(1.0);
+            ui.image((
+                texture.id(),
+                egui::vec2(width as f32 * scale, height as f32 * scale),
+            ))
+            .on_hover_text(format!("{}x{} after preprocessing", width, height));
+        }
+        ui.vertical(|ui| {
+            if zone.regions.is_empty() {
+                ui.label(
+                    RichText::new("No text regions")
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            for region in &zone.regions {
+                ui.label(
+                    RichText::new(format!(
+                        "'{}' {:.0}%",
+                        region.text,
+                        region.confidence * 100.0
+                    ))
+                    .size(11.0)
+                    .color(confidence_color(region.confidence)),
+                );
+            }
+        });
+    });
+}
+
+/// Match score of every screen
+fn render_screens(ui: &mut egui::Ui, step: &StepArtifacts) {
+    ui.label(RichText::new(format!("Screens ({})", step.screens.len())).strong());
+    if step.screens.is_empty() {
+        ui.label(
+            RichText::new("Screen recognition is off or the profile has no screens")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    egui::Grid::new("pipeline_step_screens")
+        .num_columns(4)
+        .spacing(egui::vec2(12.0, 4.0))
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Screen", "Score", "Needs", "Anchors"] {
+                ui.label(
+                    RichText::new(header)
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            ui.end_row();
+
+            for screen in &step.screens {
+                let name = RichText::new(&screen.screen_name);
+                ui.label(if screen.matched {
+                    name.strong().color(ThemeColors::ACCENT_SUCCESS)
+                } else {
+                    name
+                });
+                match screen.confidence {
+                    Some(confidence) => ui.label(format!("{:.0}%", confidence * 100.0)),
+                    None => ui.label(RichText::new("-").color(ThemeColors::TEXT_MUTED)),
+                };
+                ui.label(format!("{:.0}%", screen.threshold * 100.0));
+                let anchors = screen
+                    .anchors
+                    .iter()
+                    .map(|a| {
+                        format!(
+                            "{} {:.0}%{}",
+                            a.anchor_id,
+                            a.confidence * 100.0,
+                            if a.matched { "" } else { " (no)" }
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                ui.label(RichText::new(anchors).size(11.0));
+                ui.end_row();
+            }
+        });
+}
+
+/// How each rule's checks came out
+fn render_rules(ui: &mut egui::Ui, rules: &[RuleStep]) {
+    ui.label(RichText::new(format!("Rules ({})", rules.len())).strong());
+    if rules.is_empty() {
+        ui.label(
+            RichText::new("The active profile has no rules")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    egui::Grid::new("pipeline_step_rules")
+        .num_columns(3)
+        .spacing(egui::vec2(12.0, 4.0))
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Rule", "Inputs", "Outcome"] {
+                ui.label(
+                    RichText::new(header)
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            ui.end_row();
+
+            for rule in rules {
+                ui.label(&rule.name);
+                match rule.input_confidence {
+                    Some(confidence) => ui.label(
+                        RichText::new(format!("{:.0}%", confidence * 100.0))
+                            .color(confidence_color(confidence)),
+                    ),
+                    None => ui.label(RichText::new("-").color(ThemeColors::TEXT_MUTED)),
+                };
+                let (outcome, color) = if !rule.enabled {
+                    ("Disabled", ThemeColors::TEXT_MUTED)
+                } else if !rule.in_phase {
+                    ("Not in this phase", ThemeColors::TEXT_MUTED)
+                } else if rule.occluded {
+                    ("Waits: zone under a popup", ThemeColors::ACCENT_WARNING)
+                } else {
+                    match rule.confidence {
+                        InputConfidence::Confident => ("Runs", ThemeColors::ACCENT_SUCCESS),
+                        InputConfidence::Uncertain => {
+                            ("Runs, readings uncertain", ThemeColors::ACCENT_WARNING)
+                        }
+                        InputConfidence::Suppressed => {
+                            ("Below confidence floor", ThemeColors::ACCENT_ERROR)
+                        }
+                    }
+                };
+                ui.label(RichText::new(outcome).color(color));
+                ui.end_row();
+            }
+        });
+}
+
+/// Color of a confidence: green when sure, amber when doubtful, red when not
+fn confidence_color(confidence: f32) -> egui::Color32 {
+    if confidence >= 0.8 {
+        ThemeColors::ACCENT_SUCCESS
+    } else if confidence >= 0.5 {
+        ThemeColors::ACCENT_WARNING
+    } else {
+        ThemeColors::ACCENT_ERROR
+    }
+}
@@ -0,0 +1,150 @@
+//! Script reference window
+//!
+//! Lists the pipeline variables with their live values and the operators,
+//! functions and placeholders rule scripts and tip messages can use, so
+//! conditions can be written without leaving the dashboard.
+
+use egui::RichText;
+
+use crate::analysis::reference::{describe_variable, matches, Section, ENTRIES};
+use crate::analysis::watch::WatchValues;
+use crate::dashboard::state::ReferenceState;
+use crate::dashboard::theme::ThemeColors;
+
+/// Render the script reference window (if open)
+pub fn render_reference_panel(
+    ctx: &egui::Context,
+    state: &mut ReferenceState,
+    values: &WatchValues,
+) {
+    let mut open = state.open;
+
+    egui::Window::new("Script Reference")
+        .open(&mut open)
+        .collapsible(true)
+        .default_size(egui::vec2(520.0, 480.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.query)
+                        .hint_text("health, text, round...")
+                        .desired_width(220.0),
+                );
+                if !state.query.is_empty() && ui.small_button("Clear").clicked() {
+                    state.query.clear();
+                }
+            });
+            ui.label(
+                RichText::new("Click a name to copy it.")
+                    .size(11.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+            ui.add_space(8.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("reference_scroll")
+                .show(ui, |ui| {
+                    render_variables(ui, &state.query, values);
+                    for section in Section::ALL {
+                        render_section(ui, &state.query, section);
+                    }
+                });
+        });
+
+    state.open = open;
+}
+
+/// Pipeline variables and their current values
+fn render_variables(ui: &mut egui::Ui, query: &str, values: &WatchValues) {
+    let found: Vec<_> = values
+        .iter()
+        .filter(|(name, value)| matches(query, &[name, value, describe_variable(name)]))
+        .collect();
+
+    ui.label(RichText::new("Variables").strong());
+    if found.is_empty() {
+        ui.label(
+            RichText::new(if values.is_empty() {
+                "No values yet. Activate a profile and start capture."
+            } else {
+                "No matching variables"
+            })
+            .size(12.0)
+            .color(ThemeColors::TEXT_MUTED),
+        );
+    } else {
+        egui::Grid::new("reference_variables")
+            .num_columns(3)
+            .spacing(egui::vec2(12.0, 4.0))
+            .striped(true)
+            .show(ui, |ui| {
+                for (name, value) in found {
+                    copy_label(ui, name);
+                    ui.label(RichText::new(value).strong());
+                    ui.label(
+                        RichText::new(describe_variable(name))
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_SECONDARY),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+    ui.add_space(12.0);
+}
+
+/// Operators, functions or placeholders of one section
+fn render_section(ui: &mut egui::Ui, query: &str, section: Section) {
+    let found: Vec<_> = ENTRIES
+        .iter()
+        .filter(|entry| entry.section == section)
+        .filter(|entry| {
+            matches(
+                query,
+                &[
+                    section.label(),
+                    entry.signature,
+                    entry.description,
+                    entry.example,
+                ],
+            )
+        })
+        .collect();
+    if found.is_empty() {
+        return;
+    }
+
+    ui.label(RichText::new(section.label()).strong());
+    for entry in found {
+        ui.horizontal_wrapped(|ui| {
+            copy_label(ui, entry.signature);
+            ui.label(
+                RichText::new(entry.description)
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_SECONDARY),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            ui.label(
+                RichText::new(entry.example)
+                    .monospace()
+                    .size(11.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+        });
+    }
+    ui.add_space(12.0);
+}
+
+/// Monospace text that copies itself when clicked
+fn copy_label(ui: &mut egui::Ui, text: &str) {
+    if ui
+        .add(egui::Label::new(RichText::new(text).monospace()).sense(egui::Sense::click()))
+        .on_hover_text("Copy")
+        .clicked()
+    {
+        ui.output_mut(|o| o.copied_text = text.to_string());
+    }
+}
@@ -0,0 +1,105 @@
+//! Remembered UI state
+//!
+//! Scroll positions, open sections and panel widths that survive restarts.
+//! While the dashboard runs they're kept in egui's memory; DashboardApp
+//! saves them together with the selections to `ui_state.toml` and hands
+//! them back at startup.
+
+use egui::{CollapsingHeader, CollapsingResponse, Context, Id, ScrollArea, Ui};
+use std::collections::HashSet;
+
+use crate::config::UiState;
+
+fn state_id() -> Id {
+    Id::new("remembered_ui_state")
+}
+
+/// Keys of scroll areas whose saved offset was already applied
+fn restored_id() -> Id {
+    Id::new("remembered_scroll_restored")
+}
+
+fn with_state<R>(ctx: &Context, f: impl FnOnce(&mut UiState) -> R) -> R {
+    ctx.data_mut(|d| f(d.get_temp_mut_or_default::<UiState>(state_id())))
+}
+
+/// The remembered state
+pub fn ui_state(ctx: &Context) -> UiState {
+    with_state(ctx, |state| state.clone())
+}
+
+/// Replace the remembered state, e.g. with the one saved last session
+pub fn set_ui_state(ctx: &Context, state: UiState) {
+    ctx.data_mut(|d| d.insert_temp(state_id(), state));
+}
+
+/// Show a scroll area that opens where it was left last session
+///
+/// The saved offset is applied the first time the area is shown.
+pub fn remembered_scroll<R>(
+    ui: &mut Ui,
+    key: &str,
+    area: ScrollArea,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> R {
+    let ctx = ui.ctx().clone();
+    let first_show = ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<HashSet<String>>(restored_id())
+            .insert(key.to_string())
+    });
+    let saved = with_state(&ctx, |state| state.scroll_offsets.get(key).copied());
+
+    let area = match saved.filter(|_| first_show) {
+        Some(offset) => area.vertical_scroll_offset(offset),
+        None => area,
+    };
+    let output = area.show(ui, add_contents);
+
+    let offset = output.state.offset.y;
+    with_state(&ctx, |state| {
+        if state.scroll_offsets.get(key) != Some(&offset) {
+            state.scroll_offsets.insert(key.to_string(), offset);
+        }
+    });
+    output.inner
+}
+
+/// Show a collapsing section that stays open or closed across restarts
+///
+/// `default_open` applies until the section is first opened or closed.
+pub fn remembered_section<R>(
+    ui: &mut Ui,
+    key: &str,
+    header: CollapsingHeader,
+    default_open: bool,
+    add_body: impl FnOnce(&mut Ui) -> R,
+) -> CollapsingResponse<R> {
+    let ctx = ui.ctx().clone();
+    let was_open = with_state(&ctx, |state| state.open_sections.get(key).copied());
+
+    let response = header
+        .default_open(was_open.unwrap_or(default_open))
+        .show(ui, add_body);
+
+    let open = !response.fully_closed();
+    if was_open.unwrap_or(default_open) != open {
+        with_state(&ctx, |state| {
+            state.open_sections.insert(key.to_string(), open);
+        });
+    }
+    response
+}
+
+/// Width a resizable panel had last session, or `default`
+pub fn remembered_width(ctx: &Context, key: &str, default: f32) -> f32 {
+    with_state(ctx, |state| state.panel_widths.get(key).copied()).unwrap_or(default)
+}
+
+/// Note a resizable panel's current width
+pub fn remember_width(ctx: &Context, key: &str, width: f32) {
+    with_state(ctx, |state| {
+        if state.panel_widths.get(key) != Some(&width) {
+            state.panel_widths.insert(key.to_string(), width);
+        }
+    });
+}
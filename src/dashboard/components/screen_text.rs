@@ -0,0 +1,111 @@
+//! Screen text panel opened by the read screen hotkey
+//!
+//! Lists every line OCR found on the screen so seeds, codes or lore text can
+//! be searched and copied from games that don't allow selecting text.
+
+use egui::RichText;
+
+use crate::dashboard::state::ScreenTextState;
+use crate::dashboard::theme::ThemeColors;
+
+/// What the user did in the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenTextAction {
+    /// Panel closed
+    Close,
+    /// Read the current screen again
+    ReadAgain,
+}
+
+/// Render the screen text window
+pub fn render_screen_text(
+    ctx: &egui::Context,
+    state: &mut ScreenTextState,
+) -> Option<ScreenTextAction> {
+    let mut action = None;
+    let mut open = true;
+
+    egui::Window::new("Screen Text")
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(520.0, 420.0))
+        .show(ctx, |ui| {
+            let search = state.search.to_lowercase();
+            let matching: Vec<&str> = state
+                .lines
+                .iter()
+                .map(|line| line.text.as_str())
+                .filter(|text| search.is_empty() || text.to_lowercase().contains(&search))
+                .collect();
+
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.search)
+                        .hint_text("Filter lines")
+                        .desired_width(220.0),
+                );
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Read Again").clicked() {
+                        action = Some(ScreenTextAction::ReadAgain);
+                    }
+                    if ui
+                        .add_enabled(!matching.is_empty(), egui::Button::new("Copy All"))
+                        .on_hover_text("Copy the lines shown")
+                        .clicked()
+                    {
+                        let text = matching.join("\n");
+                        ui.output_mut(|o| o.copied_text = text);
+                    }
+                });
+            });
+
+            ui.label(
+                RichText::new(format!(
+                    "{} of {} lines, read {}s ago",
+                    matching.len(),
+                    state.lines.len(),
+                    state.read_at.elapsed().as_secs()
+                ))
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("screen_text_lines")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if matching.is_empty() {
+                        ui.label(
+                            RichText::new("No text found")
+                                .italics()
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    }
+
+                    for (i, text) in matching.iter().enumerate() {
+                        ui.push_id(i, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button("Copy")
+                                    .on_hover_text("Copy this line")
+                                    .clicked()
+                                {
+                                    ui.output_mut(|o| o.copied_text = text.to_string());
+                                }
+                                ui.add(egui::Label::new(RichText::new(*text).monospace()).wrap());
+                            });
+                        });
+                    }
+                });
+        });
+
+    if !open {
+        action = Some(ScreenTextAction::Close);
+    }
+    action
+}
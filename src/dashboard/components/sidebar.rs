@@ -42,6 +42,7 @@ pub fn render_sidebar(ui: &mut egui::Ui, current_view: &mut DashboardView) {
             DashboardView::Vision,
             DashboardView::Screens,
             DashboardView::Profiles,
+            DashboardView::Data,
             DashboardView::Settings,
         ] {
             let is_selected = *current_view == view;
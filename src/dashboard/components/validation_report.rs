@@ -0,0 +1,196 @@
+//! Profile validation report
+//!
+//! Shows which screens and zones of the active profile no longer read as
+//! expected on the saved sample frames.
+
+use egui::RichText;
+
+use crate::dashboard::state::ValidationState;
+use crate::dashboard::theme::ThemeColors;
+use crate::vision::calibration::MIN_SAMPLES;
+use crate::vision::validation::SampleValidation;
+use crate::vision::OcrBackend;
+
+/// What the user did in the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationAction {
+    /// Report closed
+    Close,
+    /// Validate every sample again
+    RunAgain,
+    /// Store the current results as every sample's expectation
+    AcceptResults,
+    /// Fit the backend's confidence calibration to the results
+    Calibrate,
+}
+
+/// Render the validation report window
+pub fn render_validation_report(
+    ctx: &egui::Context,
+    state: &ValidationState,
+) -> Option<ValidationAction> {
+    let mut action = None;
+    let mut open = true;
+
+    egui::Window::new("Profile Validation")
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(520.0, 440.0))
+        .show(ctx, |ui| {
+            let failed = state.results.iter().filter(|r| !r.passed()).count();
+
+            ui.horizontal(|ui| {
+                if state.is_done() {
+                    let (summary, color) = if failed == 0 {
+                        (
+                            format!("All {} samples pass", state.results.len()),
+                            ThemeColors::ACCENT_SUCCESS,
+                        )
+                    } else {
+                        (
+                            format!("{} of {} samples fail", failed, state.results.len()),
+                            ThemeColors::ACCENT_ERROR,
+                        )
+                    };
+                    ui.label(RichText::new(summary).strong().color(color));
+                } else {
+                    ui.spinner();
+                    ui.label(format!(
+                        "Validating {} of {} samples...",
+                        state.results.len() + 1,
+                        state.samples.len()
+                    ));
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.add_enabled_ui(state.is_done(), |ui| {
+                        if ui.button("Run Again").clicked() {
+                            action = Some(ValidationAction::RunAgain);
+                        }
+                        if ui
+                            .add_enabled(failed > 0, egui::Button::new("Accept Results"))
+                            .on_hover_text(
+                                "Expect these results from now on (after deliberately \
+                                 changing zones or screens)",
+                            )
+                            .clicked()
+                        {
+                            action = Some(ValidationAction::AcceptResults);
+                        }
+                    });
+                });
+            });
+
+            if state.is_done() {
+                let labeled: usize = state
+                    .results
+                    .iter()
+                    .map(|r| r.labeled_readings().count())
+                    .sum();
+                let backend = match state.backend {
+                    OcrBackend::WindowsOcr => "Windows OCR",
+                    OcrBackend::PaddleOcr => "PaddleOCR",
+                };
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{} labeled {} readings", labeled, backend))
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_SECONDARY),
+                    );
+                    if ui
+                        .add_enabled(
+                            labeled >= MIN_SAMPLES,
+                            egui::Button::new("Calibrate Confidence"),
+                        )
+                        .on_hover_text(format!(
+                            "Map {}'s confidences to how often these readings were right, \
+                             so the recognition threshold means the same for every backend \
+                             (needs {} readings from samples with accepted results)",
+                            backend, MIN_SAMPLES
+                        ))
+                        .clicked()
+                    {
+                        action = Some(ValidationAction::Calibrate);
+                    }
+                });
+                if let Some(ref note) = state.calibration_note {
+                    ui.label(
+                        RichText::new(note)
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                }
+            }
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("validation_results")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for result in &state.results {
+                        render_sample_result(ui, result);
+                    }
+                });
+        });
+
+    if !open {
+        action = Some(ValidationAction::Close);
+    }
+    action
+}
+
+/// Render one sample's screen and zone results
+fn render_sample_result(ui: &mut egui::Ui, result: &SampleValidation) {
+    let (status, color) = if result.passed() {
+        ("PASS", ThemeColors::ACCENT_SUCCESS)
+    } else {
+        ("FAIL", ThemeColors::ACCENT_ERROR)
+    };
+
+    egui::CollapsingHeader::new(
+        RichText::new(format!("{}  {}", status, result.sample)).color(color),
+    )
+    .id_salt(("validation_sample", &result.sample))
+    .default_open(!result.passed())
+    .show(ui, |ui| {
+        if let Some(ref error) = result.error {
+            ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+            return;
+        }
+
+        let screen = result.screen_name.as_deref().unwrap_or("(none)");
+        ui.label(format!("Screen: {}", screen));
+        if let Some(ref issue) = result.screen_issue {
+            ui.label(
+                RichText::new(issue)
+                    .size(12.0)
+                    .color(ThemeColors::ACCENT_ERROR),
+            );
+        }
+
+        for zone in &result.zones {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("{}:", zone.zone_name)).strong());
+                match zone.issue {
+                    Some(ref issue) => {
+                        ui.label(RichText::new(issue).color(ThemeColors::ACCENT_ERROR));
+                    }
+                    None => {
+                        ui.label(RichText::new(&zone.text).monospace());
+                    }
+                }
+            });
+        }
+
+        if result.new_baseline {
+            ui.label(
+                RichText::new("No accepted results yet")
+                    .size(12.0)
+                    .italics()
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+        }
+    });
+}
@@ -0,0 +1,219 @@
+//! Watch window
+//!
+//! Pins pipeline variables like a debugger's watch window. Each watch can
+//! carry a condition that alerts when it starts to hold, and optionally
+//! freezes the vision preview on that frame, like a breakpoint.
+
+use egui::RichText;
+
+use crate::analysis::watch::{Watch, WatchCondition, WatchOp};
+use crate::dashboard::components::{add_condition_picker, ConditionKind};
+use crate::dashboard::state::WatchState;
+use crate::dashboard::theme::ThemeColors;
+
+/// Render the watch window (if open)
+pub fn render_watch_panel(ctx: &egui::Context, state: &mut WatchState) {
+    let mut open = state.open;
+
+    egui::Window::new("Watch")
+        .open(&mut open)
+        .collapsible(true)
+        .default_size(egui::vec2(560.0, 360.0))
+        .show(ctx, |ui| {
+            render_add_watch(ui, state);
+            ui.add_space(8.0);
+
+            if state.watches.is_empty() {
+                ui.label(
+                    RichText::new(
+                        "Nothing watched yet. Add a zone, flag or timer above to follow its \
+                         value while playing.",
+                    )
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+                );
+            } else {
+                render_watches(ui, state);
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+            render_hits(ui, state);
+        });
+
+    state.open = open;
+}
+
+/// Pick a variable to watch
+fn render_add_watch(ui: &mut egui::Ui, state: &mut WatchState) {
+    let mut added = None;
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("watch_add_variable")
+            .width(220.0)
+            .selected_text("Add watch...")
+            .show_ui(ui, |ui| {
+                for (name, value) in &state.values {
+                    if state.watches.iter().any(|w| w.variable == *name) {
+                        continue;
+                    }
+                    if ui
+                        .selectable_label(false, name)
+                        .on_hover_text(value)
+                        .clicked()
+                    {
+                        added = Some(name.clone());
+                    }
+                }
+            });
+        ui.label(
+            RichText::new(format!("{} variables", state.values.len()))
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    });
+    if let Some(name) = added {
+        state.watches.push(Watch::new(name));
+    }
+}
+
+/// Table of watched variables, their values and conditions
+fn render_watches(ui: &mut egui::Ui, state: &mut WatchState) {
+    let mut removed = None;
+
+    egui::Grid::new("watch_grid")
+        .num_columns(5)
+        .spacing(egui::vec2(8.0, 4.0))
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Variable", "Value", "Alert when", "", "Hits"] {
+                ui.label(
+                    RichText::new(header)
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            ui.end_row();
+
+            for (index, watch) in state.watches.iter_mut().enumerate() {
+                ui.label(RichText::new(&watch.variable).monospace());
+                match watch.value {
+                    Some(ref value) => ui.label(RichText::new(value).strong()),
+                    None => ui.label(RichText::new("(unavailable)").color(ThemeColors::TEXT_MUTED)),
+                };
+
+                let before = watch.condition.clone();
+                ui.horizontal(|ui| {
+                    render_condition_editor(ui, index, &mut watch.condition);
+                });
+                if watch.condition != before {
+                    watch.rearm();
+                }
+
+                ui.add_enabled(
+                    watch.condition.is_some(),
+                    egui::Checkbox::new(&mut watch.break_on_hit, "Break"),
+                )
+                .on_hover_text("Freeze the vision preview on the frame the alert fired");
+
+                let hit_color = if watch.hits > 0 {
+                    ThemeColors::ACCENT_WARNING
+                } else {
+                    ThemeColors::TEXT_MUTED
+                };
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(watch.hits.to_string()).color(hit_color));
+                    if ui
+                        .small_button("X")
+                        .on_hover_text("Stop watching")
+                        .clicked()
+                    {
+                        removed = Some(index);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+    if let Some(index) = removed {
+        state.watches.remove(index);
+    }
+}
+
+/// Edit a watch's alert condition
+fn render_condition_editor(
+    ui: &mut egui::Ui,
+    index: usize,
+    condition: &mut Option<WatchCondition>,
+) {
+    add_condition_picker(
+        ui,
+        ("watch_op", index),
+        80.0,
+        "never",
+        condition,
+        |kind, previous| WatchCondition {
+            op: WatchOp::ALL[kind],
+            operand: previous.map(|c| c.operand).unwrap_or_default(),
+        },
+    );
+
+    if let Some(condition) = condition.as_mut().filter(|c| c.op.takes_operand()) {
+        let hint = match condition.op {
+            WatchOp::Above | WatchOp::Below => "30",
+            _ => "text",
+        };
+        ui.add(
+            egui::TextEdit::singleline(&mut condition.operand)
+                .hint_text(hint)
+                .desired_width(90.0),
+        );
+    }
+}
+
+impl ConditionKind for WatchCondition {
+    fn kind_labels() -> Vec<&'static str> {
+        WatchOp::ALL.iter().map(|op| op.label()).collect()
+    }
+
+    fn kind(&self) -> usize {
+        WatchOp::ALL
+            .iter()
+            .position(|op| *op == self.op)
+            .unwrap_or(0)
+    }
+}
+
+/// Recent alerts
+fn render_hits(ui: &mut egui::Ui, state: &mut WatchState) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Alerts").strong());
+        if !state.hits.is_empty() && ui.small_button("Clear").clicked() {
+            state.hits.clear();
+        }
+    });
+
+    if state.hits.is_empty() {
+        ui.label(
+            RichText::new("No alerts yet")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .id_salt("watch_hits")
+        .max_height(120.0)
+        .show(ui, |ui| {
+            for hit in &state.hits {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{}s ago", hit.at.elapsed().as_secs()))
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                    ui.label(&hit.message);
+                });
+            }
+        });
+}
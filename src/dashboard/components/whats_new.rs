@@ -0,0 +1,90 @@
+//! "What's New" panel shown after an update
+
+use egui::RichText;
+
+use crate::dashboard::changelog::{DeepLink, Release};
+use crate::dashboard::theme::ThemeColors;
+
+/// What the user did in the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhatsNewAction {
+    /// Panel dismissed
+    Close,
+    /// Jump to a view or settings section
+    Open(DeepLink),
+}
+
+/// Render the release notes window
+pub fn render_whats_new(ctx: &egui::Context, releases: &[Release]) -> Option<WhatsNewAction> {
+    let mut action = None;
+
+    egui::Window::new("What's New")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.set_min_width(460.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("whats_new")
+                .max_height(420.0)
+                .show(ui, |ui| {
+                    for release in releases {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(format!("Version {}", release.version))
+                                    .size(16.0)
+                                    .strong(),
+                            );
+                            if !release.date.is_empty() {
+                                ui.label(
+                                    RichText::new(&release.date)
+                                        .size(12.0)
+                                        .color(ThemeColors::TEXT_MUTED),
+                                );
+                            }
+                        });
+
+                        ui.add_space(8.0);
+
+                        for item in &release.items {
+                            ui.horizontal(|ui| {
+                                ui.vertical(|ui| {
+                                    ui.set_max_width(340.0);
+                                    ui.label(RichText::new(&item.title).strong());
+                                    ui.label(
+                                        RichText::new(&item.description)
+                                            .size(12.0)
+                                            .color(ThemeColors::TEXT_SECONDARY),
+                                    );
+                                });
+
+                                let link = item.link.as_deref().and_then(DeepLink::parse);
+                                if let Some(link) = link {
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if ui.button("Show me").clicked() {
+                                                action = Some(WhatsNewAction::Open(link));
+                                            }
+                                        },
+                                    );
+                                }
+                            });
+                            ui.add_space(8.0);
+                        }
+
+                        ui.add_space(8.0);
+                    }
+                });
+
+            ui.separator();
+            ui.add_space(4.0);
+
+            if ui.button("Got it").clicked() {
+                action = Some(WhatsNewAction::Close);
+            }
+        });
+
+    action
+}
@@ -0,0 +1,169 @@
+//! Problem reports
+//!
+//! Builds a GitHub issue pre-filled with what maintainers ask for anyway:
+//! version, OCR backend, system check results and the active profile. The
+//! issue is opened in the browser as a `/issues/new` link, so nothing is sent
+//! until the user submits it there.
+
+use std::path::PathBuf;
+
+use crate::diagnostics::{CapabilityCheck, CheckStatus};
+
+/// Page for opening a new issue
+pub const NEW_ISSUE_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "/issues/new");
+
+/// Longest link handed to the browser; GitHub rejects much longer ones
+const MAX_URL_LEN: usize = 8000;
+
+/// What the app knows about itself when the problem is reported
+#[derive(Debug, Clone, Default)]
+pub struct IssueContext {
+    /// OCR backend and its settings, e.g. "Windows OCR"
+    pub backend: String,
+    /// GPU used for OCR and template matching
+    pub gpu: String,
+    /// What is being captured, if anything
+    pub capture: Option<String>,
+    /// Active profile name
+    pub profile: Option<String>,
+    /// System check results (None = not run yet)
+    pub checks: Option<Vec<CapabilityCheck>>,
+    /// Snapshot to attach to the issue
+    pub snapshot: Option<PathBuf>,
+}
+
+/// Markdown body of the issue
+pub fn issue_body(description: &str, context: &IssueContext) -> String {
+    let mut body = String::from("### What happened\n\n");
+    let description = description.trim();
+    if description.is_empty() {
+        body.push_str("_Describe the problem and how to make it happen._\n");
+    } else {
+        body.push_str(description);
+        body.push('\n');
+    }
+
+    body.push_str("\n### Environment\n\n");
+    body.push_str(&format!("- Version: {}\n", env!("CARGO_PKG_VERSION")));
+    body.push_str(&format!("- OCR backend: {}\n", context.backend));
+    body.push_str(&format!("- GPU: {}\n", context.gpu));
+    body.push_str(&format!(
+        "- Capture: {}\n",
+        context.capture.as_deref().unwrap_or("not capturing")
+    ));
+    body.push_str(&format!(
+        "- Profile: {}\n",
+        context.profile.as_deref().unwrap_or("none")
+    ));
+
+    body.push_str("\n### System checks\n\n");
+    match context.checks {
+        Some(ref checks) => {
+            for check in checks {
+                let status = match check.status {
+                    CheckStatus::Ok => "OK",
+                    CheckStatus::Warning => "Warning",
+                    CheckStatus::Error => "Error",
+                };
+                body.push_str(&format!(
+                    "- {}: {} ({})\n",
+                    check.name, status, check.detail
+                ));
+            }
+        }
+        None => body.push_str("Not run yet\n"),
+    }
+
+    if let Some(ref snapshot) = context.snapshot {
+        let name = snapshot
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        body.push_str(&format!(
+            "\n### Snapshot\n\n`{}` is attached; load it with `--load-snapshot`.\n",
+            name
+        ));
+    }
+
+    body
+}
+
+/// Link opening a new issue with the title and body filled in
+///
+/// The body is cut short if the link would be too long for GitHub.
+pub fn issue_url(title: &str, body: &str) -> String {
+    let mut url = format!("{}?title={}&body=", NEW_ISSUE_URL, encode(title.trim()));
+    let mut buf = [0; 4];
+    for c in body.chars() {
+        let encoded = encode(c.encode_utf8(&mut buf));
+        if url.len() + encoded.len() > MAX_URL_LEN {
+            break;
+        }
+        url.push_str(&encoded);
+    }
+    url
+}
+
+/// Percent-encode text for a query string
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_body() {
+        let context = IssueContext {
+            backend: "Windows OCR".to_string(),
+            gpu: "Windows default".to_string(),
+            capture: None,
+            profile: Some("Test Game".to_string()),
+            checks: Some(vec![CapabilityCheck {
+                name: "Screen Capture",
+                status: CheckStatus::Warning,
+                detail: "No monitors".to_string(),
+                hint: None,
+            }]),
+            snapshot: Some(PathBuf::from("snapshots/snapshot-1.json")),
+        };
+
+        let body = issue_body("  Zone reads 0 ", &context);
+        assert!(body.starts_with("### What happened\n\nZone reads 0\n"));
+        assert!(body.contains("- Capture: not capturing\n"));
+        assert!(body.contains("- Profile: Test Game\n"));
+        assert!(body.contains("- Screen Capture: Warning (No monitors)\n"));
+        assert!(body.contains("`snapshot-1.json` is attached"));
+
+        let empty = issue_body("", &IssueContext::default());
+        assert!(empty.contains("_Describe the problem"));
+        assert!(empty.contains("Not run yet"));
+        assert!(!empty.contains("### Snapshot"));
+    }
+
+    #[test]
+    fn test_issue_url() {
+        let url = issue_url("OCR fails", "a b&c\né");
+        assert_eq!(
+            url,
+            format!(
+                "{}?title=OCR%20fails&body=a%20b%26c%0A%C3%A9",
+                NEW_ISSUE_URL
+            )
+        );
+
+        // Long bodies are cut short, on a character boundary
+        let url = issue_url("", &"é".repeat(MAX_URL_LEN));
+        assert!(url.len() <= MAX_URL_LEN);
+        assert!(url.ends_with("%C3%A9"));
+    }
+}
@@ -4,7 +4,9 @@
 //! game profiles, capture configuration, and overlay customization.
 
 pub mod app;
+pub mod changelog;
 pub mod components;
+pub mod issue_report;
 pub mod state;
 pub mod theme;
 pub mod views;
@@ -1,9 +1,24 @@
 #![allow(dead_code)]
 //! Dashboard view state management
 
-use crate::config::DashboardViewSetting;
-use crate::storage::profiles::{GameProfile, OcrRegion};
-use std::collections::HashMap;
+use crate::analysis::coach::ZonePace;
+use crate::config::{DashboardViewSetting, ExtraCaptureSource};
+use crate::dashboard::changelog::Release;
+use crate::storage::backups::ProfileBackup;
+use crate::storage::database::{PlaytimeStats, QueryResult, TipOrigin};
+use crate::storage::labels::LabelValues;
+use crate::storage::profiles::{
+    AutoStart, GamePhase, GameProfile, LabeledRegion, OcrRegion, ProfileVariable,
+    RecognitionDictionary, SnapshotCapture,
+};
+use crate::storage::rule_library::{LibraryRule, RuleReference};
+use crate::storage::samples::SampleFrame;
+use crate::storage::share::SharedItem;
+use crate::vision::batch::BatchRun;
+use crate::vision::validation::SampleValidation;
+use crate::vision::zone_stats::ZoneOcrStats;
+use crate::vision::OcrBackend;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::time::Instant;
 
 /// OCR result granularity - word-level or line-level
@@ -26,6 +41,7 @@ pub enum DashboardView {
     Vision,
     Screens,
     Profiles,
+    Data,
     Settings,
 }
 
@@ -39,6 +55,7 @@ impl DashboardView {
             DashboardView::Vision => "Vision",
             DashboardView::Screens => "Screens",
             DashboardView::Profiles => "Profiles",
+            DashboardView::Data => "Data",
             DashboardView::Settings => "Settings",
         }
     }
@@ -52,6 +69,7 @@ impl DashboardView {
             DashboardView::Vision => "V",
             DashboardView::Screens => "S",
             DashboardView::Profiles => "P",
+            DashboardView::Data => "D",
             DashboardView::Settings => "G", // "Gear" for settings
         }
     }
@@ -65,6 +83,7 @@ impl DashboardView {
             DashboardView::Vision => DashboardViewSetting::Vision,
             DashboardView::Screens => DashboardViewSetting::Vision, // Map to Vision for now
             DashboardView::Profiles => DashboardViewSetting::Profiles,
+            DashboardView::Data => DashboardViewSetting::Data,
             DashboardView::Settings => DashboardViewSetting::Settings,
         }
     }
@@ -77,6 +96,7 @@ impl DashboardView {
             DashboardViewSetting::Overlay => DashboardView::Overlay,
             DashboardViewSetting::Vision => DashboardView::Vision,
             DashboardViewSetting::Profiles => DashboardView::Profiles,
+            DashboardViewSetting::Data => DashboardView::Data,
             DashboardViewSetting::Settings => DashboardView::Settings,
         }
     }
@@ -99,8 +119,24 @@ pub struct DashboardState {
     pub screens: ScreensViewState,
     /// Profiles view state
     pub profiles: ProfilesViewState,
+    /// Data browser view state
+    pub data: DataViewState,
     /// Settings view state
     pub settings: SettingsViewState,
+    /// Releases shown in the "What's New" panel (None = hidden)
+    pub whats_new: Option<Vec<Release>>,
+    /// Text read by the read screen hotkey (None = hidden)
+    pub screen_text: Option<ScreenTextState>,
+    /// Profile validation against the sample library (report window)
+    pub validation: Option<ValidationState>,
+    /// Watched pipeline variables (debug window)
+    pub watch: WatchState,
+    /// Paused pipeline and its last stepped frame (debug window)
+    pub pipeline_debug: PipelineDebugState,
+    /// Script reference window
+    pub reference: ReferenceState,
+    /// "Report a Problem" window
+    pub issue_report: IssueReportState,
 }
 
 impl Default for DashboardState {
@@ -113,16 +149,145 @@ impl Default for DashboardState {
             vision: VisionViewState::default(),
             screens: ScreensViewState::default(),
             profiles: ProfilesViewState::default(),
+            data: DataViewState::default(),
             settings: SettingsViewState::default(),
+            whats_new: None,
+            screen_text: None,
+            validation: None,
+            watch: WatchState::default(),
+            pipeline_debug: PipelineDebugState::default(),
+            reference: ReferenceState::default(),
+            issue_report: IssueReportState::default(),
         }
     }
 }
 
+/// Pipeline variables pinned in the watch window, with ad-hoc alerts
+///
+/// Watches last for the session and aren't saved to the profile.
+#[derive(Debug, Default)]
+pub struct WatchState {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Pinned variables
+    pub watches: Vec<crate::analysis::watch::Watch>,
+    /// Every variable's current value (refreshed by DashboardApp)
+    pub values: crate::analysis::watch::WatchValues,
+    /// Recent alerts, newest first
+    pub hits: VecDeque<WatchHit>,
+}
+
+/// Pipeline paused and stepped frame by frame
+#[derive(Default)]
+pub struct PipelineDebugState {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Pause and step requests (processed by DashboardApp)
+    pub stepper: crate::analysis::stepper::PipelineStepper,
+    /// What the last step produced
+    pub step: Option<crate::analysis::stepper::StepArtifacts>,
+    /// Zone crops of the last step, by zone ID (created by the window)
+    pub crop_textures: HashMap<String, egui::TextureHandle>,
+}
+
+impl std::fmt::Debug for PipelineDebugState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PipelineDebugState")
+            .field("open", &self.open)
+            .field("stepper", &self.stepper)
+            .field("step", &self.step.as_ref().map(|s| s.step))
+            .field("crop_textures", &self.crop_textures.len())
+            .finish()
+    }
+}
+
+/// Searchable reference of what rule scripts and tip messages can use
+#[derive(Debug, Default)]
+pub struct ReferenceState {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Search text
+    pub query: String,
+}
+
+/// Problem report being written, opened as a GitHub issue
+#[derive(Debug, Default)]
+pub struct IssueReportState {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Issue title
+    pub title: String,
+    /// What went wrong, in the user's words
+    pub description: String,
+    /// Mention the last exported snapshot so it gets attached
+    pub attach_snapshot: bool,
+}
+
+/// An alert raised by a watch condition
+#[derive(Debug, Clone)]
+pub struct WatchHit {
+    /// What happened, e.g. "zone.health < 30 (was 25)"
+    pub message: String,
+    /// When the condition started to hold
+    pub at: Instant,
+}
+
+/// Everything read from the screen by the read screen hotkey
+#[derive(Debug, Clone)]
+pub struct ScreenTextState {
+    /// Detected lines, top to bottom
+    pub lines: Vec<crate::vision::TextRegion>,
+    /// Case-insensitive filter for the lines shown
+    pub search: String,
+    /// When the screen was read
+    pub read_at: Instant,
+}
+
+/// Profile validation run over the sample library
+///
+/// Samples are validated one per frame by DashboardApp.
+#[derive(Debug, Clone)]
+pub struct ValidationState {
+    /// Samples to validate
+    pub samples: Vec<SampleFrame>,
+    /// Results so far, in sample order
+    pub results: Vec<SampleValidation>,
+    /// OCR backend the samples are read with
+    pub backend: OcrBackend,
+    /// Outcome of fitting a confidence calibration to the results
+    pub calibration_note: Option<String>,
+}
+
+impl ValidationState {
+    /// Start validating the given samples
+    pub fn new(samples: Vec<SampleFrame>, backend: OcrBackend) -> Self {
+        Self {
+            samples,
+            results: Vec::new(),
+            backend,
+            calibration_note: None,
+        }
+    }
+
+    /// Whether every sample has been validated
+    pub fn is_done(&self) -> bool {
+        self.results.len() >= self.samples.len()
+    }
+}
+
 /// Home view state
 #[derive(Debug, Default)]
 pub struct HomeViewState {
     /// Quick actions expanded
     pub quick_actions_expanded: bool,
+    /// Request to open the "What's New" panel (processed by DashboardApp)
+    pub open_whats_new: bool,
+    /// Checklists were edited in shared state and need saving
+    pub checklists_dirty: bool,
+    /// Checklist whose items are being edited
+    pub editing_checklist: Option<String>,
+    /// Settings were changed from the dashboard and need saving
+    pub config_changed: bool,
 }
 
 /// Capture view state
@@ -171,14 +336,57 @@ impl std::fmt::Debug for CaptureViewState {
 }
 
 /// Overlay view state
-#[derive(Debug, Default)]
 pub struct OverlayViewState {
-    /// Preview tip text
-    pub preview_tip_text: String,
-    /// Preview tip priority
-    pub preview_tip_priority: u32,
+    /// Test tip being designed (also the preview's tip)
+    pub tip_design: crate::config::TipDesign,
+    /// Send the designed tip again and again
+    pub repeat_test_tip: bool,
+    /// Seconds between repeated test tips
+    pub repeat_interval_secs: u32,
+    /// Favorite tips were saved or deleted (DashboardApp saves the config)
+    pub favorites_changed: bool,
     /// Show tip preview
     pub show_preview: bool,
+    /// What the overlay shows (set by DashboardApp; None while it isn't running)
+    pub snapshot: Option<crate::overlay::OverlaySnapshot>,
+    /// Captured frame the preview is drawn over
+    pub screenshot: Option<egui::TextureHandle>,
+    /// When the frame in `screenshot` was captured
+    pub screenshot_taken: Option<Instant>,
+    /// Keep the screenshot following the capture
+    pub live_screenshot: bool,
+}
+
+impl Default for OverlayViewState {
+    fn default() -> Self {
+        Self {
+            tip_design: Default::default(),
+            repeat_test_tip: false,
+            repeat_interval_secs: 30,
+            favorites_changed: false,
+            show_preview: false,
+            snapshot: None,
+            screenshot: None,
+            screenshot_taken: None,
+            live_screenshot: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for OverlayViewState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayViewState")
+            .field("tip_design", &self.tip_design)
+            .field("repeat_test_tip", &self.repeat_test_tip)
+            .field("repeat_interval_secs", &self.repeat_interval_secs)
+            .field("favorites_changed", &self.favorites_changed)
+            .field("show_preview", &self.show_preview)
+            .field("snapshot", &self.snapshot)
+            .field("screenshot", &self.screenshot.as_ref().map(|_| "<texture>"))
+            .field("screenshot_taken", &self.screenshot_taken)
+            .field("live_screenshot", &self.live_screenshot)
+            .finish()
+    }
 }
 
 /// Vision/OCR view state
@@ -187,8 +395,28 @@ pub struct VisionViewState {
     pub selected_backend: crate::vision::OcrBackend,
     /// OCR result granularity (word vs line level)
     pub ocr_granularity: OcrGranularity,
+    /// PaddleOCR accuracy vs speed
+    pub ocr_quality: crate::vision::OcrQuality,
     /// Whether OCR models are ready (PaddleOCR)
     pub models_ready: bool,
+    /// Whether the int8 models for the fast quality are installed
+    pub quantized_models_ready: bool,
+    /// IDs of the configured composite and extra capture sources
+    pub composite_sources: Vec<String>,
+    /// Where the sources are in the current composite frame
+    pub composite_layout: Option<crate::capture::CompositeLayout>,
+    /// Game phases of the active profile zones can be limited to, as (ID, name)
+    pub phases: Vec<(String, String)>,
+    /// Downloaded models that failed verification, with the reason
+    pub corrupted_models: Vec<(crate::vision::ModelType, String)>,
+    /// Pending re-download of a corrupted model
+    pub pending_repair: Option<crate::vision::ModelType>,
+    /// Request to open the watch window (processed by DashboardApp)
+    pub open_watch: bool,
+    /// Request to open the pipeline step window (processed by DashboardApp)
+    pub open_pipeline_debug: bool,
+    /// Request to open the script reference (processed by DashboardApp)
+    pub open_reference: bool,
     /// Detection model loaded
     pub detection_model_ready: bool,
     /// Recognition model loaded
@@ -233,6 +461,37 @@ pub struct VisionViewState {
     pub last_frame_width: u32,
     /// Last frame height
     pub last_frame_height: u32,
+    /// Request to OCR the image on the clipboard
+    pub pending_clipboard_ocr: bool,
+    /// Clipboard image or sample frame waiting to be shown in the preview
+    pub pending_preview: Option<crate::capture::CapturedFrame>,
+    /// Whether the preview and zone results show a clipboard image instead of live capture
+    pub showing_clipboard_image: bool,
+    /// Frame pinned by "Freeze frame"; preview and zone OCR use it instead of live capture
+    pub frozen_frame: Option<crate::capture::CapturedFrame>,
+    /// Zones and preprocessing the frozen frame was last read with
+    pub frozen_ocr_inputs: Option<(Vec<OcrRegion>, crate::config::OcrPreprocessing)>,
+    /// Zones and preprocessing live frames were last read with; a change
+    /// reads zones again even where the pixels stayed the same
+    pub live_ocr_inputs: Option<(Vec<OcrRegion>, crate::config::OcrPreprocessing)>,
+    /// Sample frames saved for the active profile
+    pub sample_frames: Vec<SampleFrame>,
+    /// Profile the sample list was loaded for
+    pub sample_frames_profile: Option<String>,
+    /// Name for the next saved sample
+    pub new_sample_name: String,
+    /// Pending sample library action (processed by DashboardApp)
+    pub pending_sample_action: Option<SampleAction>,
+    /// Name of the sample frozen in the preview
+    pub shown_sample: Option<String>,
+    /// Whether both OCR backends read the previewed frame, side by side
+    pub compare_backends: bool,
+    /// Request to compare the backends on the previewed frame now
+    pub pending_backend_compare: bool,
+    /// Readings of both backends of the previewed frame
+    pub backend_comparison: Option<crate::vision::backend_compare::BackendComparison>,
+    /// Why the backends couldn't be compared
+    pub backend_compare_error: Option<String>,
 
     // Zone OCR state
     /// Zone selection state
@@ -241,6 +500,14 @@ pub struct VisionViewState {
     pub ocr_zones: Vec<OcrRegion>,
     /// Latest OCR results per zone
     pub zone_ocr_results: HashMap<String, ZoneOcrResult>,
+    /// OCR confidence and failure history per zone (this session)
+    pub zone_ocr_stats: HashMap<String, ZoneOcrStats>,
+    /// Saved zone images per zone, used by auto-tune alongside the current frame
+    pub zone_samples: HashMap<String, Vec<crate::capture::CapturedFrame>>,
+    /// Line stitching state of scrolling zones
+    pub zone_scroll_logs: HashMap<String, crate::vision::scroll_stitch::ScrollStitcher>,
+    /// Last value recorded per zone, for recording changes only
+    pub zone_recorded_values: HashMap<String, String>,
     /// Whether to show zone overlays in preview
     pub show_zone_overlays: bool,
     /// Request to enter zone selection mode (triggers overlay mode change)
@@ -249,12 +516,48 @@ pub struct VisionViewState {
     pub zones_dirty: bool,
     /// Error message for zone selection (e.g., overlay failed to start)
     pub zone_selection_error: Option<String>,
+    /// Why ensemble zones fell back to a single backend (cleared to retry)
+    pub ensemble_error: Option<String>,
+    /// Zone image shown in the perspective corner editor
+    pub geometry_texture: Option<egui::TextureHandle>,
+
+    // Color trigger state
+    /// Flag indicating the profile's color triggers were edited and need saving
+    pub color_triggers_dirty: bool,
+    /// Trigger whose spot and color the next click on the preview picks
+    pub picking_color_trigger: Option<String>,
+    /// Whether the preview shows the color under the cursor
+    pub eyedropper: bool,
+    /// Radius (pixels) averaged by the eyedropper, 0 = single pixel
+    pub eyedropper_radius: u32,
+    /// Pixel and color last clicked with the eyedropper
+    pub picked_color: Option<((u32, u32), [u8; 3])>,
+
+    // Labeled region state
+    /// Labeled regions of the current profile
+    pub labeled_regions: Vec<LabeledRegion>,
+    /// Flag indicating labeled regions have been modified and need saving
+    pub labels_dirty: bool,
+    /// Selected labeled regions (indices into `labeled_regions`)
+    pub selected_labels: BTreeSet<usize>,
+    /// Pattern the selected labels are renamed with
+    pub label_rename_pattern: String,
+    /// Sessions without a match after which a label counts as unused
+    pub label_stale_sessions: u32,
+    /// Current dashboard session (None = sessions aren't recorded)
+    pub session_id: Option<i64>,
+    /// Value last read at each labeled region
+    pub label_values: LabelValues,
+    /// Labels re-bound to new bounds this session, for review
+    pub relocated_labels: BTreeSet<String>,
 }
 
 impl std::fmt::Debug for VisionViewState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("VisionViewState")
+            .field("ocr_quality", &self.ocr_quality)
             .field("models_ready", &self.models_ready)
+            .field("corrupted_models", &self.corrupted_models)
             .field("ocr_initialized", &self.ocr_initialized)
             .field("is_downloading", &self.is_downloading)
             .field("is_processing", &self.is_processing)
@@ -264,12 +567,35 @@ impl std::fmt::Debug for VisionViewState {
     }
 }
 
+/// Sample library action requested from the Vision view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SampleAction {
+    /// Save the current preview frame under a name
+    Save(String),
+    /// Freeze the preview on a sample (index into `sample_frames`)
+    Show(usize),
+    /// Delete a sample (index into `sample_frames`)
+    Delete(usize),
+    /// Validate the profile against every sample
+    Validate,
+}
+
 impl Default for VisionViewState {
     fn default() -> Self {
         Self {
             selected_backend: crate::vision::OcrBackend::WindowsOcr, // Default to Windows OCR
             ocr_granularity: OcrGranularity::Word,                   // Default to word-level
+            ocr_quality: crate::vision::OcrQuality::default(),
             models_ready: false,
+            quantized_models_ready: false,
+            composite_sources: Vec::new(),
+            composite_layout: None,
+            phases: Vec::new(),
+            corrupted_models: Vec::new(),
+            pending_repair: None,
+            open_watch: false,
+            open_pipeline_debug: false,
+            open_reference: false,
             detection_model_ready: false,
             recognition_model_ready: false,
             ocr_initialized: false,
@@ -292,14 +618,48 @@ impl Default for VisionViewState {
             last_frame_data: None,
             last_frame_width: 0,
             last_frame_height: 0,
+            pending_clipboard_ocr: false,
+            pending_preview: None,
+            showing_clipboard_image: false,
+            frozen_frame: None,
+            frozen_ocr_inputs: None,
+            live_ocr_inputs: None,
+            sample_frames: Vec::new(),
+            sample_frames_profile: None,
+            new_sample_name: String::new(),
+            pending_sample_action: None,
+            shown_sample: None,
+            compare_backends: false,
+            pending_backend_compare: false,
+            backend_comparison: None,
+            backend_compare_error: None,
             // Zone OCR defaults
             zone_selection: ZoneSelectionState::default(),
             ocr_zones: Vec::new(),
             zone_ocr_results: HashMap::new(),
+            zone_ocr_stats: HashMap::new(),
+            zone_samples: HashMap::new(),
+            zone_scroll_logs: HashMap::new(),
+            zone_recorded_values: HashMap::new(),
             show_zone_overlays: true,
             pending_zone_selection_mode: false,
             zones_dirty: false,
             zone_selection_error: None,
+            ensemble_error: None,
+            geometry_texture: None,
+            color_triggers_dirty: false,
+            picking_color_trigger: None,
+            eyedropper: false,
+            eyedropper_radius: 0,
+            picked_color: None,
+            labeled_regions: Vec::new(),
+            labels_dirty: false,
+            selected_labels: BTreeSet::new(),
+            label_rename_pattern: String::new(),
+            label_stale_sessions: 10,
+            session_id: None,
+            label_values: LabelValues::default(),
+            relocated_labels: BTreeSet::new(),
         }
     }
 }
@@ -342,6 +702,8 @@ pub struct ZoneSelectionState {
     pub repositioning_zone_index: Option<usize>,
     /// Auto-configure state for a zone
     pub auto_configure: Option<AutoConfigureState>,
+    /// Whether auto-tune also scores the zone's saved sample frames
+    pub auto_tune_use_samples: bool,
 }
 
 /// State for auto-configure process
@@ -351,16 +713,10 @@ pub struct AutoConfigureState {
     pub zone_index: usize,
     /// Current step in the auto-configure process
     pub current_step: AutoConfigureStep,
-    /// Current scale being tested (1-4)
-    pub current_scale: u32,
-    /// Current preprocessing enabled state
-    pub current_preprocessing_enabled: bool,
-    /// Current grayscale setting
-    pub current_grayscale: bool,
-    /// Current invert setting
-    pub current_invert: bool,
-    /// Current contrast value
-    pub current_contrast: f32,
+    /// Preprocessing candidates to try, in order
+    pub candidates: Vec<crate::config::OcrPreprocessing>,
+    /// Saved zone images scored alongside the current frame
+    pub samples: Vec<crate::capture::CapturedFrame>,
     /// Total combinations to try
     pub total_combinations: usize,
     /// Current combination index
@@ -373,7 +729,9 @@ pub struct AutoConfigureState {
     pub error_message: Option<String>,
     /// Best configuration found so far (preprocessing settings)
     pub best_preprocessing: Option<crate::config::OcrPreprocessing>,
-    /// Best confidence score found
+    /// Best combined score (confidence plus pattern validity) found
+    pub best_score: f32,
+    /// Mean OCR confidence of the best configuration
     pub best_confidence: f32,
     /// Best text found
     pub best_text: String,
@@ -399,6 +757,10 @@ pub struct ZoneOcrResult {
     pub zone_name: String,
     /// Detected text
     pub text: String,
+    /// How the backends voted (ensemble zones only)
+    pub agreement: Option<crate::vision::ensemble::Agreement>,
+    /// A popup screen covers the zone; `text` is the last reading before it
+    pub occluded: bool,
     /// Last update timestamp
     pub last_updated: Instant,
 }
@@ -414,6 +776,28 @@ pub enum ProfileAction {
     Create(GameProfile),
     /// Delete a profile by ID
     Delete(String),
+    /// Add a shared zone, screen or rule to a profile
+    ImportShared(String, SharedItem),
+    /// Restore a profile from a backup
+    RestoreBackup(ProfileBackup),
+    /// Replace a profile's recognition dictionary
+    UpdateDictionary(String, RecognitionDictionary),
+    /// Replace a profile's user variables
+    UpdateVariables(String, Vec<ProfileVariable>),
+    /// Change what starts when a profile's game launches
+    UpdateAutoStart(String, AutoStart),
+    /// Change how often a profile's game is captured
+    UpdateSnapshotCapture(String, SnapshotCapture),
+    /// Replace the secondary windows captured for a profile
+    UpdateSecondaryWindows(String, Vec<ExtraCaptureSource>),
+    /// Replace a profile's game phases
+    UpdatePhases(String, Vec<GamePhase>),
+    /// Replace the library rules attached to a profile
+    UpdateLibraryRules(String, Vec<RuleReference>),
+    /// Add or replace a rule in the rules library
+    SaveLibraryRule(LibraryRule),
+    /// Delete a rule from the library, detaching it from every profile
+    DeleteLibraryRule(String),
 }
 
 /// Profiles view state
@@ -429,6 +813,34 @@ pub struct ProfilesViewState {
     pub show_create_dialog: bool,
     /// Show delete confirmation
     pub show_delete_confirm: bool,
+    /// Show starter pack picker
+    pub show_starter_packs: bool,
+    /// Show backup restore picker
+    pub show_backups: bool,
+    /// Backups listed in the restore picker (loaded when it opens)
+    pub backups: Vec<ProfileBackup>,
+    /// Dictionary being edited, with the ID of its profile
+    pub dictionary_draft: Option<(String, RecognitionDictionary)>,
+    /// Variables being edited, with the ID of their profile
+    pub variables_draft: Option<(String, Vec<ProfileVariable>)>,
+    /// Game phases being edited, with the ID of their profile
+    pub phases_draft: Option<(String, Vec<GamePhase>)>,
+    /// Request to start a new match from the first phase (processed by DashboardApp)
+    pub new_match: bool,
+    /// Request to open the script reference (processed by DashboardApp)
+    pub open_reference: bool,
+    /// Attached library rules being edited, with the ID of their profile
+    pub library_draft: Option<(String, Vec<RuleReference>)>,
+    /// Secondary windows being edited, with the ID of their profile
+    pub secondary_windows_draft: Option<(String, Vec<ExtraCaptureSource>)>,
+    /// Library rule being created or edited
+    pub library_rule_draft: Option<LibraryRule>,
+    /// Result of the last rules library change or copy
+    pub library_status: Option<String>,
+    /// Share code being pasted for import
+    pub share_code_input: String,
+    /// Result of the last share code import or copy
+    pub share_status: Option<String>,
     /// New profile name (for create dialog)
     pub new_profile_name: String,
     /// New profile executable
@@ -437,6 +849,62 @@ pub struct ProfilesViewState {
     pub pending_action: Option<ProfileAction>,
 }
 
+/// Data browser view state
+#[derive(Debug, Default)]
+pub struct DataViewState {
+    /// Browse tables or run SQL
+    pub mode: DataBrowserMode,
+    /// Table being browsed
+    pub table: String,
+    /// Text filter applied to all columns of the table
+    pub filter: String,
+    /// Ad-hoc SQL query
+    pub sql: String,
+    /// Last query result
+    pub result: Option<QueryResult>,
+    /// Last query error
+    pub error: Option<String>,
+    /// Batch OCR of a screenshots folder
+    pub batch: BatchOcrState,
+    /// Playtime per profile, or the error loading it (None until loaded)
+    pub playtime: Option<Result<Vec<PlaytimeStats>, String>>,
+    /// Pace of the latest session against previous ones, or the error
+    /// loading it (None until loaded)
+    pub coach: Option<Result<Vec<ZonePace>, String>>,
+    /// Where on screen recent tips came from, or the error loading them
+    /// (None until loaded)
+    pub tip_origins: Option<Result<Vec<TipOrigin>, String>>,
+}
+
+/// Data browser mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataBrowserMode {
+    #[default]
+    Tables,
+    Sql,
+    BatchOcr,
+    Playtime,
+    Coach,
+    TipHeatmap,
+}
+
+/// Batch OCR state (runs are advanced by DashboardApp, one image per frame)
+#[derive(Debug, Default)]
+pub struct BatchOcrState {
+    /// Folder of screenshots
+    pub folder: String,
+    /// CSV output path (empty: zone_values.csv in the folder)
+    pub output: String,
+    /// Start requested from the UI
+    pub pending_start: bool,
+    /// Run in progress
+    pub run: Option<BatchRun>,
+    /// Summary of the last finished run
+    pub status: Option<String>,
+    /// Error starting or running the batch
+    pub error: Option<String>,
+}
+
 /// Settings view state
 #[derive(Debug, Default)]
 pub struct SettingsViewState {
@@ -444,6 +912,12 @@ pub struct SettingsViewState {
     pub expanded_section: Option<SettingsSection>,
     /// Unsaved changes flag
     pub has_unsaved_changes: bool,
+    /// Telemetry payload preview (JSON), shown on request
+    pub telemetry_preview: Option<String>,
+    /// Problem with the network settings, checked when they change
+    pub network_error: Option<String>,
+    /// Request to open the "Report a Problem" window
+    pub open_issue_report: bool,
 }
 
 /// Settings sections
@@ -453,6 +927,46 @@ pub enum SettingsSection {
     Capture,
     Overlay,
     Performance,
+    Features,
+    Wellness,
+    QuietHours,
+    Telemetry,
+    Network,
+}
+
+impl SettingsSection {
+    /// Every section, in page order
+    pub const ALL: [SettingsSection; 9] = [
+        SettingsSection::General,
+        SettingsSection::Capture,
+        SettingsSection::Overlay,
+        SettingsSection::Performance,
+        SettingsSection::Features,
+        SettingsSection::Wellness,
+        SettingsSection::QuietHours,
+        SettingsSection::Telemetry,
+        SettingsSection::Network,
+    ];
+
+    /// Name used in links and the saved UI state, e.g. `quiet-hours`
+    pub fn key(self) -> &'static str {
+        match self {
+            SettingsSection::General => "general",
+            SettingsSection::Capture => "capture",
+            SettingsSection::Overlay => "overlay",
+            SettingsSection::Performance => "performance",
+            SettingsSection::Features => "features",
+            SettingsSection::Wellness => "wellness",
+            SettingsSection::QuietHours => "quiet-hours",
+            SettingsSection::Telemetry => "telemetry",
+            SettingsSection::Network => "network",
+        }
+    }
+
+    /// Section with the given key
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|section| section.key() == key)
+    }
 }
 
 /// Pending text anchor data: (screen_id, detected_text, bounds)
@@ -476,7 +990,7 @@ pub struct ScreensViewState {
     /// Whether screen recognition is running
     pub recognition_running: bool,
     /// Pending request to capture full screen template
-    pub pending_full_capture: bool,
+    pub pending_full_capture: Option<String>, // Screen ID to add template to
     /// Pending request to capture visual anchor
     pub pending_anchor_capture: Option<String>, // Screen ID to add anchor to
     /// Pending request to capture text anchor
@@ -497,6 +1011,12 @@ pub struct ScreensViewState {
     pub drop_target_screen_id: Option<String>,
     /// Whether to drop before (true) or after (false) the target
     pub drop_before_target: bool,
+    /// Time template matching on both the CPU and the GPU
+    pub benchmark_matching: bool,
+    /// Latest CPU/GPU timing while benchmarking
+    pub match_benchmark: Option<crate::vision::screen_recognition::MatchBenchmark>,
+    /// Why GPU template matching is unavailable
+    pub gpu_error: Option<String>,
 }
 
 impl std::fmt::Debug for ScreensViewState {
@@ -5,10 +5,11 @@ use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use std::time::Instant;
 
-use crate::capture::{CaptureTarget, ScreenCapture};
+use crate::capture::{self, elevation, CaptureTarget, FrameConsumer, ScreenCapture};
+use crate::config::{CompositeSource, CompositeTarget, ExtraCaptureSource};
 use crate::dashboard::state::CaptureViewState;
-use crate::dashboard::theme::ThemeColors;
-use crate::shared::{CaptureCommand, SharedAppState};
+use crate::dashboard::theme::{color_with_alpha, ThemeColors};
+use crate::shared::{AppError, CaptureCommand, SharedAppState};
 
 /// Render the capture view
 pub fn render_capture_view(
@@ -27,6 +28,12 @@ pub fn render_capture_view(
 
     ui.add_space(24.0);
 
+    let access_denied = shared_state.read().runtime.capture_access_denied.clone();
+    if let Some(window) = access_denied {
+        render_access_denied_banner(ui, &window, view_state, shared_state);
+        ui.add_space(16.0);
+    }
+
     // Refresh button and status
     ui.horizontal(|ui| {
         if ui.button("Refresh Sources").clicked() {
@@ -64,6 +71,27 @@ pub fn render_capture_view(
         if ui.selectable_label(monitor_selected, "Monitors").clicked() {
             view_state.target_type = 1;
         }
+        ui.add_space(8.0);
+        if ui
+            .selectable_label(view_state.target_type == 2, "Composite")
+            .clicked()
+        {
+            view_state.target_type = 2;
+        }
+        ui.add_space(8.0);
+        if ui
+            .selectable_label(view_state.target_type == 3, "Extra Sources")
+            .clicked()
+        {
+            view_state.target_type = 3;
+        }
+        ui.add_space(8.0);
+        if ui
+            .selectable_label(view_state.target_type == 4, "Demo")
+            .clicked()
+        {
+            view_state.target_type = 4;
+        }
     });
 
     ui.add_space(16.0);
@@ -92,7 +120,7 @@ pub fn render_capture_view(
     let preview_frame = if view_state.preview_enabled {
         let capture_guard = capture_manager.lock();
         if let Some(ref capture) = *capture_guard {
-            capture.try_next_frame()
+            capture.try_next_frame_for(FrameConsumer::CapturePreview)
         } else {
             None
         }
@@ -102,7 +130,7 @@ pub fn render_capture_view(
 
     if use_two_columns {
         ui.columns(2, |columns| {
-            render_source_list_column(&mut columns[0], view_state, shared_state);
+            render_source_list_column(&mut columns[0], view_state, shared_state, capture_manager);
             render_capture_settings_column(
                 &mut columns[1],
                 view_state,
@@ -111,17 +139,92 @@ pub fn render_capture_view(
             );
         });
     } else {
-        render_source_list_column(ui, view_state, shared_state);
+        render_source_list_column(ui, view_state, shared_state, capture_manager);
         ui.add_space(16.0);
         render_capture_settings_column(ui, view_state, shared_state, preview_frame);
     }
 }
 
+/// Explain why an elevated window can't be captured and offer ways around it
+fn render_access_denied_banner(
+    ui: &mut egui::Ui,
+    window: &str,
+    view_state: &mut CaptureViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    egui::Frame::none()
+        .fill(color_with_alpha(ThemeColors::ACCENT_WARNING, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.label(
+                RichText::new(format!("Can't capture \"{}\"", window))
+                    .color(ThemeColors::ACCENT_WARNING)
+                    .strong(),
+            );
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "This window belongs to a program running as administrator, and Windows \
+                     doesn't let GamersToolKit capture it unless it runs as administrator too. \
+                     Capture the monitor the game is on instead, or restart GamersToolKit \
+                     as administrator.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Capture Monitor Instead")
+                    .on_hover_text("Capture the whole monitor the window is on")
+                    .clicked()
+                {
+                    if view_state.available_monitors.is_empty() {
+                        refresh_sources(view_state);
+                    }
+                    view_state.target_type = 1;
+                    view_state.selected_monitor = Some(capture::monitor_of_window(window).unwrap_or(0));
+                    view_state.selected_window = None;
+                    apply_selection(view_state, shared_state);
+
+                    let mut state = shared_state.write();
+                    state.runtime.capture_access_denied = None;
+                    state.runtime.capture_command = Some(CaptureCommand::Start);
+                }
+
+                ui.add_space(8.0);
+
+                if ui
+                    .add_enabled(
+                        !elevation::is_elevated(),
+                        egui::Button::new("Restart as Administrator"),
+                    )
+                    .on_hover_text(
+                        "Windows asks for permission, then GamersToolKit restarts with \
+                         administrator rights",
+                    )
+                    .clicked()
+                {
+                    shared_state.write().runtime.relaunch_elevated = true;
+                }
+
+                if ui.button("Dismiss").clicked() {
+                    shared_state.write().runtime.capture_access_denied = None;
+                }
+            });
+        });
+}
+
 /// Render the source list column
 fn render_source_list_column(
     ui: &mut egui::Ui,
     view_state: &mut CaptureViewState,
     shared_state: &Arc<RwLock<SharedAppState>>,
+    capture_manager: &Arc<Mutex<Option<ScreenCapture>>>,
 ) {
     egui::Frame::none()
         .fill(ThemeColors::BG_MEDIUM)
@@ -130,12 +233,12 @@ fn render_source_list_column(
         .show(ui, |ui| {
             ui.set_max_height(400.0);
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                if view_state.target_type == 0 {
-                    render_window_list(ui, view_state, shared_state);
-                } else {
-                    render_monitor_list(ui, view_state, shared_state);
-                }
+            egui::ScrollArea::vertical().show(ui, |ui| match view_state.target_type {
+                0 => render_window_list(ui, view_state, shared_state),
+                1 => render_monitor_list(ui, view_state, shared_state),
+                2 => render_composite_list(ui, view_state, shared_state),
+                3 => render_extra_source_list(ui, view_state, shared_state, capture_manager),
+                _ => render_demo_game(ui, view_state, shared_state),
             });
         });
 }
@@ -145,7 +248,7 @@ fn render_capture_settings_column(
     ui: &mut egui::Ui,
     view_state: &mut CaptureViewState,
     shared_state: &Arc<RwLock<SharedAppState>>,
-    preview_frame: Option<crate::capture::frame::CapturedFrame>,
+    preview_frame: Option<Arc<crate::capture::frame::CapturedFrame>>,
 ) {
     egui::Frame::none()
         .fill(ThemeColors::BG_MEDIUM)
@@ -156,7 +259,13 @@ fn render_capture_settings_column(
             ui.add_space(12.0);
 
             // Current selection
-            let selection_text = get_current_selection_text(view_state);
+            let selection_text = if shared_state.read().config.capture.use_demo {
+                "Demo game".to_string()
+            } else if shared_state.read().config.capture.use_composite {
+                "Composite capture".to_string()
+            } else {
+                get_current_selection_text(view_state)
+            };
             ui.horizontal(|ui| {
                 ui.label(RichText::new("Selected:").color(ThemeColors::TEXT_MUTED));
                 ui.label(RichText::new(selection_text).strong());
@@ -187,7 +296,7 @@ fn render_capture_settings_column(
 
                             let color_image = egui::ColorImage::from_rgba_unmultiplied(
                                 [frame.width as usize, frame.height as usize],
-                                &frame.data,
+                                &frame.rgba(),
                             );
 
                             if needs_update {
@@ -274,15 +383,71 @@ fn render_capture_settings_column(
             // Show current FPS if capturing
             if is_capturing {
                 ui.add_space(4.0);
-                let fps = shared_state.read().runtime.capture_fps;
+                let (fps, dropped) = {
+                    let state = shared_state.read();
+                    (state.runtime.capture_fps, state.runtime.dropped_frames)
+                };
                 ui.label(
                     RichText::new(format!("Capturing at {:.1} FPS", fps))
                         .color(ThemeColors::ACCENT_SUCCESS),
                 );
+                if dropped > 0 {
+                    ui.label(
+                        RichText::new(format!("{} stale frames skipped", dropped))
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    )
+                    .on_hover_text(
+                        "Frames replaced by a newer one before they were processed; \
+                         processing always works on the latest frame",
+                    );
+                }
             }
+
+            render_replay_controls(ui, shared_state);
         });
 }
 
+/// Save the replay buffer and show what it holds
+fn render_replay_controls(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let state = shared_state.read();
+    if !state.config.capture.replay.enabled {
+        return;
+    }
+    let (seconds, bytes) = state.runtime.replay_buffered;
+    let saving = state.runtime.saving_replay;
+    let last_replay = state.runtime.last_replay.clone();
+    drop(state);
+
+    ui.add_space(12.0);
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!saving && bytes > 0, egui::Button::new("Save Replay"))
+            .on_hover_text("Save the last seconds of capture")
+            .clicked()
+        {
+            shared_state.write().runtime.save_replay = Some("dashboard".to_string());
+        }
+        let status = if saving {
+            "Saving...".to_string()
+        } else {
+            format!("{:.0} s buffered ({} MB)", seconds, bytes / 1_000_000)
+        };
+        ui.label(
+            RichText::new(status)
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    });
+    if let Some(path) = last_replay {
+        ui.label(
+            RichText::new(format!("Last saved: {}", path.display()))
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    }
+}
+
 /// Refresh available capture sources
 fn refresh_sources(view_state: &mut CaptureViewState) {
     // Get available windows
@@ -308,6 +473,8 @@ fn render_window_list(
         .filter(|(_, w)| filter.is_empty() || w.to_lowercase().contains(&filter))
         .collect();
 
+    render_follow_program(ui, view_state, shared_state);
+
     if filtered_windows.is_empty() {
         ui.label(RichText::new("No windows found").color(ThemeColors::TEXT_MUTED));
         return;
@@ -326,6 +493,42 @@ fn render_window_list(
     }
 }
 
+/// Toggle between capturing the selected window by title or by its program
+fn render_follow_program(
+    ui: &mut egui::Ui,
+    view_state: &CaptureViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let following = shared_state.read().config.capture.target_executable.clone();
+    let mut follow = following.is_some();
+
+    let response = ui
+        .add_enabled(
+            view_state.selected_window.is_some(),
+            egui::Checkbox::new(&mut follow, "Follow the program"),
+        )
+        .on_hover_text(
+            "Capture the selected window's program instead of matching its title, so \
+             capture keeps going when the game changes its title or is restarted",
+        )
+        .on_disabled_hover_text("Select a window first");
+    if let Some(executable) = following {
+        ui.label(
+            RichText::new(format!("Following {}", executable))
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    }
+    ui.add_space(4.0);
+
+    let selected_title = view_state
+        .selected_window
+        .and_then(|idx| view_state.available_windows.get(idx));
+    if let (true, Some(window_title)) = (response.changed(), selected_title) {
+        set_window_target(&mut shared_state.write(), window_title, follow);
+    }
+}
+
 /// Render the monitor list
 fn render_monitor_list(
     ui: &mut egui::Ui,
@@ -358,6 +561,278 @@ fn render_monitor_list(
     }
 }
 
+/// Render the sources of the composite capture
+fn render_composite_list(
+    ui: &mut egui::Ui,
+    view_state: &mut CaptureViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.label(
+        RichText::new(
+            "Capture several windows or monitor regions side by side in one frame, \
+             e.g. a flight sim and its moving map. Zones can be placed within a source \
+             in their settings.",
+        )
+        .size(12.0)
+        .color(ThemeColors::TEXT_SECONDARY),
+    );
+    ui.add_space(8.0);
+
+    let (mut sources, active) = {
+        let state = shared_state.read();
+        (
+            state.config.capture.composite_sources.clone(),
+            state.config.capture.use_composite,
+        )
+    };
+    let mut changed = false;
+    let mut remove = None;
+
+    for (i, source) in sources.iter_mut().enumerate() {
+        ui.push_id(i, |ui| {
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut source.id).desired_width(80.0))
+                    .on_hover_text("Name zones refer to this source by")
+                    .changed();
+                ui.label(RichText::new(source.target.label()).strong());
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+
+            let mut crop = source.region.is_some();
+            if ui
+                .checkbox(&mut crop, "Crop")
+                .on_hover_text("Keep only part of the source (fractions of its size)")
+                .changed()
+            {
+                source.region = crop.then_some((0.0, 0.0, 1.0, 1.0));
+                changed = true;
+            }
+            if let Some((x, y, w, h)) = source.region.as_mut() {
+                ui.horizontal(|ui| {
+                    for (label, value) in [("x", x), ("y", y), ("w", w), ("h", h)] {
+                        ui.label(label);
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(value)
+                                    .range(0.0..=1.0)
+                                    .speed(0.01)
+                                    .fixed_decimals(2),
+                            )
+                            .changed();
+                    }
+                });
+            }
+            ui.add_space(6.0);
+        });
+    }
+    if let Some(i) = remove {
+        sources.remove(i);
+        changed = true;
+    }
+
+    let mut added = None;
+    egui::ComboBox::from_id_salt("composite_add_source")
+        .selected_text("Add source...")
+        .show_ui(ui, |ui| {
+            for window in &view_state.available_windows {
+                if ui.selectable_label(false, window).clicked() {
+                    added = Some(CompositeTarget::Window(window.clone()));
+                }
+            }
+            ui.separator();
+            for (idx, monitor) in view_state.available_monitors.iter().enumerate() {
+                if ui.selectable_label(false, monitor).clicked() {
+                    added = Some(CompositeTarget::Monitor(idx));
+                }
+            }
+        });
+    if let Some(target) = added {
+        let id = (1..)
+            .map(|n| format!("source{}", n))
+            .find(|id| !sources.iter().any(|s| &s.id == id))
+            .unwrap_or_default();
+        sources.push(CompositeSource {
+            id,
+            target,
+            region: None,
+        });
+        changed = true;
+    }
+
+    if changed {
+        let mut state = shared_state.write();
+        state.config.capture.composite_sources = sources.clone();
+        if active {
+            state.capture_config.target = CaptureTarget::Composite(sources.clone());
+        }
+    }
+
+    ui.add_space(8.0);
+    if active {
+        ui.label(RichText::new("Composite capture selected").color(ThemeColors::ACCENT_SUCCESS));
+    } else if ui
+        .add_enabled(
+            !sources.is_empty(),
+            egui::Button::new("Use Composite Capture"),
+        )
+        .clicked()
+    {
+        view_state.selected_window = None;
+        view_state.selected_monitor = None;
+
+        let mut state = shared_state.write();
+        state.config.capture.use_composite = true;
+        state.config.capture.use_demo = false;
+        state.capture_config.target = CaptureTarget::Composite(sources);
+        state.runtime.current_capture_target = Some("Composite".to_string());
+    }
+}
+
+/// Describe the demo game and select it as the capture target
+fn render_demo_game(
+    ui: &mut egui::Ui,
+    view_state: &mut CaptureViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.label(
+        RichText::new(
+            "A built-in stand-in for a game: health, gold and a match timer that keep \
+             changing, a red screen edge when health runs low and a shop screen every \
+             minute. Use it to try out zones, screens, color triggers and the overlay \
+             without a game.",
+        )
+        .size(12.0)
+        .color(ThemeColors::TEXT_SECONDARY),
+    );
+    ui.add_space(8.0);
+
+    if shared_state.read().config.capture.use_demo {
+        ui.label(RichText::new("Demo game selected").color(ThemeColors::ACCENT_SUCCESS));
+    } else if ui.button("Use Demo Game").clicked() {
+        view_state.selected_window = None;
+        view_state.selected_monitor = None;
+
+        let mut state = shared_state.write();
+        state.config.capture.use_demo = true;
+        state.config.capture.use_composite = false;
+        state.capture_config.target = CaptureTarget::Demo;
+        state.runtime.current_capture_target = Some(CaptureTarget::Demo.label());
+    }
+}
+
+/// Render the extra sources captured next to the main target
+fn render_extra_source_list(
+    ui: &mut egui::Ui,
+    view_state: &CaptureViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+    capture_manager: &Arc<Mutex<Option<ScreenCapture>>>,
+) {
+    ui.label(
+        RichText::new(
+            "Capture more windows or monitors next to the main target, each at its own \
+             frame rate, e.g. a map tool on a second monitor. Zones read from a source \
+             when it's picked as their capture source.",
+        )
+        .size(12.0)
+        .color(ThemeColors::TEXT_SECONDARY),
+    );
+    ui.add_space(8.0);
+
+    let mut sources = shared_state.read().config.capture.extra_sources.clone();
+    let mut changed = false;
+    // Renames restart the source only once editing is done
+    let mut restart = false;
+    let mut remove = None;
+
+    {
+        let capture = capture_manager.lock();
+        for (i, source) in sources.iter_mut().enumerate() {
+            ui.push_id(i, |ui| {
+                ui.horizontal(|ui| {
+                    let id = ui
+                        .add(egui::TextEdit::singleline(&mut source.id).desired_width(80.0))
+                        .on_hover_text("Name zones subscribe to this source by");
+                    changed |= id.changed();
+                    restart |= id.lost_focus();
+                    ui.label(RichText::new(source.target.label()).strong());
+                    restart |= ui
+                        .add(
+                            egui::DragValue::new(&mut source.max_fps)
+                                .range(1..=60)
+                                .suffix(" fps"),
+                        )
+                        .on_hover_text("Frames per second captured from this source")
+                        .changed();
+                    if let Some(ref capture) = *capture {
+                        let (text, color) = if capture.source_running(&source.id) {
+                            ("capturing", ThemeColors::ACCENT_SUCCESS)
+                        } else {
+                            ("stopped", ThemeColors::TEXT_MUTED)
+                        };
+                        ui.label(RichText::new(text).size(11.0).color(color));
+                    }
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(i);
+                    }
+                });
+            });
+        }
+    }
+    if let Some(i) = remove {
+        sources.remove(i);
+        restart = true;
+    }
+
+    let mut added = None;
+    egui::ComboBox::from_id_salt("extra_add_source")
+        .selected_text("Add source...")
+        .show_ui(ui, |ui| {
+            for window in &view_state.available_windows {
+                if ui.selectable_label(false, window).clicked() {
+                    added = Some(CompositeTarget::Window(window.clone()));
+                }
+            }
+            ui.separator();
+            for (idx, monitor) in view_state.available_monitors.iter().enumerate() {
+                if ui.selectable_label(false, monitor).clicked() {
+                    added = Some(CompositeTarget::Monitor(idx));
+                }
+            }
+        });
+    if let Some(target) = added {
+        let id = (1..)
+            .map(|n| format!("extra{}", n))
+            .find(|id| !sources.iter().any(|s| &s.id == id))
+            .unwrap_or_default();
+        sources.push(ExtraCaptureSource {
+            id,
+            target,
+            max_fps: 5,
+        });
+        restart = true;
+    }
+
+    if changed || restart {
+        shared_state.write().config.capture.extra_sources = sources;
+    }
+    if restart {
+        // Running captures pick the change up right away, with the active
+        // profile's secondary windows
+        let sources = shared_state.read().capture_sources();
+        if let Some(ref mut capture) = *capture_manager.lock() {
+            if let Err(e) = capture.sync_sources(&sources) {
+                shared_state
+                    .write()
+                    .runtime
+                    .set_error(AppError::Capture(format!("{:#}", e)));
+            }
+        }
+    }
+}
+
 /// Get text describing the current selection
 fn get_current_selection_text(view_state: &CaptureViewState) -> String {
     if let Some(idx) = view_state.selected_window {
@@ -383,9 +858,9 @@ fn apply_selection(view_state: &CaptureViewState, shared_state: &Arc<RwLock<Shar
 
     if let Some(idx) = view_state.selected_window {
         if let Some(window_title) = view_state.available_windows.get(idx) {
-            state.capture_config.target = CaptureTarget::Window(window_title.clone());
-            state.config.capture.target_window = Some(window_title.clone());
-            state.runtime.current_capture_target = Some(window_title.clone());
+            // Keep following the program if that's how the last window was captured
+            let follow = state.config.capture.target_executable.is_some();
+            set_window_target(&mut state, window_title, follow);
         }
     } else if let Some(idx) = view_state.selected_monitor {
         if idx == 0 {
@@ -396,5 +871,26 @@ fn apply_selection(view_state: &CaptureViewState, shared_state: &Arc<RwLock<Shar
             state.runtime.current_capture_target = view_state.available_monitors.get(idx).cloned();
         }
         state.config.capture.target_window = None;
+        state.config.capture.target_executable = None;
+        state.config.capture.use_composite = false;
+        state.config.capture.use_demo = false;
     }
 }
+
+/// Capture a window by its title, or by its program when following it
+///
+/// Falls back to the title when the window's program can't be found.
+fn set_window_target(state: &mut SharedAppState, window_title: &str, follow: bool) {
+    let executable = follow
+        .then(|| capture::executable_of_window(window_title))
+        .flatten();
+    state.capture_config.target = match executable {
+        Some(ref executable) => CaptureTarget::Executable(executable.clone()),
+        None => CaptureTarget::Window(window_title.to_string()),
+    };
+    state.config.capture.target_window = Some(window_title.to_string());
+    state.config.capture.target_executable = executable;
+    state.config.capture.use_composite = false;
+    state.config.capture.use_demo = false;
+    state.runtime.current_capture_target = Some(window_title.to_string());
+}
@@ -0,0 +1,450 @@
+//! Coaching checklist widget
+//!
+//! Shows the active profile's checklists on the Home view. Items are ticked
+//! by clicking them, by the checklist hotkey, or by themselves when their
+//! condition shows on screen. "Edit" opens the items, their conditions and
+//! the reset schedule.
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::analysis::checklist::{reset, unix_time};
+use crate::dashboard::components::{add_condition_picker, add_phase_picker, ConditionKind};
+use crate::dashboard::state::HomeViewState;
+use crate::dashboard::theme::ThemeColors;
+use crate::shared::SharedAppState;
+use crate::storage::profiles::{
+    Checklist, ChecklistCondition, ChecklistItem, ChecklistReset, GameProfile,
+};
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Things on screen a condition can wait for, as (ID, name)
+pub(super) struct ConditionTargets {
+    pub screens: Vec<(String, String)>,
+    pub color_triggers: Vec<(String, String)>,
+    pub zones: Vec<(String, String)>,
+}
+
+impl ConditionTargets {
+    pub fn of(profile: &GameProfile) -> Self {
+        Self {
+            screens: profile
+                .screens
+                .iter()
+                .map(|s| (s.id.clone(), s.name.clone()))
+                .collect(),
+            color_triggers: profile
+                .color_triggers
+                .iter()
+                .map(|t| (t.id.clone(), t.name.clone()))
+                .collect(),
+            zones: profile
+                .ocr_regions
+                .iter()
+                .map(|z| (z.id.clone(), z.name.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Render the checklists of the active profile
+pub fn render_checklists(
+    ui: &mut egui::Ui,
+    state: &mut HomeViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let profile = shared_state.read().active_profile().map(|p| {
+        let phases: Vec<(String, String)> = p
+            .phases
+            .iter()
+            .map(|phase| (phase.id.clone(), phase.name.clone()))
+            .collect();
+        (p.checklists.clone(), ConditionTargets::of(p), phases)
+    });
+
+    ui.heading(RichText::new("Checklists").size(18.0));
+    ui.add_space(12.0);
+
+    let Some((checklists, targets, phases)) = profile else {
+        ui.label(
+            RichText::new("Activate a profile to add checklists")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    };
+
+    for checklist in &checklists {
+        render_checklist(ui, checklist, &targets, &phases, state, shared_state);
+        ui.add_space(8.0);
+    }
+
+    if ui
+        .button("+ Add Checklist")
+        .on_hover_text("A list to work through, e.g. before a raid or every day")
+        .clicked()
+    {
+        let checklist = Checklist {
+            id: Uuid::new_v4().to_string(),
+            name: format!("Checklist {}", checklists.len() + 1),
+            items: vec![],
+            reset: ChecklistReset::Manual,
+            last_reset: None,
+            pinned: checklists.is_empty(),
+            phases: vec![],
+        };
+        state.editing_checklist = Some(checklist.id.clone());
+        update_checklists(state, shared_state, |checklists| checklists.push(checklist));
+    }
+}
+
+/// Render one checklist, with its editor if open
+fn render_checklist(
+    ui: &mut egui::Ui,
+    checklist: &Checklist,
+    targets: &ConditionTargets,
+    phases: &[(String, String)],
+    state: &mut HomeViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let mut edited = checklist.clone();
+    let mut delete = false;
+    let editing = state.editing_checklist.as_deref() == Some(checklist.id.as_str());
+
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(8.0)
+        .inner_margin(12.0)
+        .show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                if editing {
+                    ui.add(egui::TextEdit::singleline(&mut edited.name).desired_width(160.0));
+                } else {
+                    ui.label(RichText::new(&checklist.name).size(15.0).strong());
+                }
+                let done = checklist.items.iter().filter(|i| i.done).count();
+                let color = if done == checklist.items.len() && done > 0 {
+                    ThemeColors::ACCENT_SUCCESS
+                } else {
+                    ThemeColors::TEXT_MUTED
+                };
+                ui.label(RichText::new(format!("{}/{}", done, checklist.items.len())).color(color));
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .small_button("X")
+                        .on_hover_text("Delete checklist")
+                        .clicked()
+                    {
+                        delete = true;
+                    }
+                    if ui.selectable_label(editing, "Edit").clicked() {
+                        state.editing_checklist = (!editing).then(|| checklist.id.clone());
+                    }
+                    if ui
+                        .button("Reset")
+                        .on_hover_text("Uncheck every item")
+                        .clicked()
+                    {
+                        reset(&mut edited, unix_time());
+                    }
+                    ui.checkbox(&mut edited.pinned, "Pinned").on_hover_text(
+                        "Show on the overlay; the checklist hotkey ticks its next item",
+                    );
+                });
+            });
+
+            if editing {
+                render_reset_editor(ui, &mut edited);
+                if !phases.is_empty() {
+                    add_phase_picker(ui, phases, &mut edited.phases);
+                }
+            }
+            ui.add_space(4.0);
+
+            let mut removed_item = None;
+            for (index, item) in edited.items.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if editing {
+                        ui.add(egui::TextEdit::singleline(&mut item.text).desired_width(180.0));
+                        render_condition_editor(ui, &item.id, &mut item.auto_check, targets);
+                        if ui.small_button("X").on_hover_text("Delete item").clicked() {
+                            removed_item = Some(index);
+                        }
+                    } else {
+                        ui.checkbox(&mut item.done, &item.text);
+                        if let Some(ref condition) = item.auto_check {
+                            ui.label(
+                                RichText::new("auto")
+                                    .size(11.0)
+                                    .color(ThemeColors::TEXT_MUTED),
+                            )
+                            .on_hover_text(describe_condition(condition, targets));
+                        }
+                    }
+                });
+            }
+            if let Some(index) = removed_item {
+                edited.items.remove(index);
+            }
+
+            if editing && ui.button("+ Add Item").clicked() {
+                edited.items.push(ChecklistItem {
+                    id: Uuid::new_v4().to_string(),
+                    text: format!("Item {}", edited.items.len() + 1),
+                    done: false,
+                    auto_check: None,
+                });
+            }
+        });
+
+    if delete {
+        update_checklists(state, shared_state, |checklists| {
+            checklists.retain(|c| c.id != checklist.id)
+        });
+    } else if edited != *checklist {
+        let pinned_now = edited.pinned && !checklist.pinned;
+        update_checklists(state, shared_state, |checklists| {
+            for c in checklists.iter_mut() {
+                if c.id == checklist.id {
+                    *c = edited.clone();
+                } else if pinned_now {
+                    // Only one checklist is pinned at a time
+                    c.pinned = false;
+                }
+            }
+        });
+    }
+}
+
+/// Edit when the checklist unchecks itself
+fn render_reset_editor(ui: &mut egui::Ui, checklist: &mut Checklist) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Reset:").color(ThemeColors::TEXT_MUTED));
+
+        let hour_utc = match checklist.reset {
+            ChecklistReset::Daily { hour_utc } | ChecklistReset::Weekly { hour_utc, .. } => {
+                hour_utc
+            }
+            _ => 0,
+        };
+        egui::ComboBox::from_id_salt(("checklist_reset", &checklist.id))
+            .selected_text(match checklist.reset {
+                ChecklistReset::Manual => "By hand",
+                ChecklistReset::SessionStart => "On app start",
+                ChecklistReset::Daily { .. } => "Daily",
+                ChecklistReset::Weekly { .. } => "Weekly",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut checklist.reset, ChecklistReset::Manual, "By hand");
+                ui.selectable_value(
+                    &mut checklist.reset,
+                    ChecklistReset::SessionStart,
+                    "On app start",
+                );
+                if ui
+                    .selectable_label(
+                        matches!(checklist.reset, ChecklistReset::Daily { .. }),
+                        "Daily",
+                    )
+                    .clicked()
+                {
+                    checklist.reset = ChecklistReset::Daily { hour_utc };
+                }
+                if ui
+                    .selectable_label(
+                        matches!(checklist.reset, ChecklistReset::Weekly { .. }),
+                        "Weekly",
+                    )
+                    .clicked()
+                {
+                    checklist.reset = ChecklistReset::Weekly {
+                        weekday: 0,
+                        hour_utc,
+                    };
+                }
+            });
+
+        if let ChecklistReset::Weekly {
+            ref mut weekday, ..
+        } = checklist.reset
+        {
+            egui::ComboBox::from_id_salt(("checklist_weekday", &checklist.id))
+                .selected_text(WEEKDAYS[(*weekday as usize).min(6)])
+                .show_ui(ui, |ui| {
+                    for (day, name) in (0u8..).zip(WEEKDAYS) {
+                        ui.selectable_value(weekday, day, name);
+                    }
+                });
+        }
+        if let ChecklistReset::Daily { ref mut hour_utc }
+        | ChecklistReset::Weekly {
+            ref mut hour_utc, ..
+        } = checklist.reset
+        {
+            ui.add(
+                egui::DragValue::new(hour_utc)
+                    .range(0..=23)
+                    .prefix("at ")
+                    .suffix(":00 UTC"),
+            )
+            .on_hover_text("Hour of the reset, in UTC like most game servers");
+        }
+    });
+}
+
+/// Edit what checks an item by itself
+fn render_condition_editor(
+    ui: &mut egui::Ui,
+    item_id: &str,
+    condition: &mut Option<ChecklistCondition>,
+    targets: &ConditionTargets,
+) {
+    let first =
+        |list: &[(String, String)]| list.first().map(|(id, _)| id.clone()).unwrap_or_default();
+
+    add_condition_picker(
+        ui,
+        ("checklist_condition", item_id),
+        110.0,
+        "By hand",
+        condition,
+        |kind, _| match kind {
+            0 => ChecklistCondition::Screen {
+                screen_id: first(&targets.screens),
+            },
+            1 => ChecklistCondition::ColorTrigger {
+                trigger_id: first(&targets.color_triggers),
+            },
+            _ => ChecklistCondition::ZoneText {
+                zone_id: first(&targets.zones),
+                text: String::new(),
+            },
+        },
+    );
+
+    if let Some(condition) = condition {
+        render_condition_target(ui, item_id, condition, targets);
+    }
+}
+
+impl ConditionKind for ChecklistCondition {
+    fn kind_labels() -> Vec<&'static str> {
+        vec!["Screen shown", "Color trigger", "Zone reads"]
+    }
+
+    fn kind(&self) -> usize {
+        match self {
+            ChecklistCondition::Screen { .. } => 0,
+            ChecklistCondition::ColorTrigger { .. } => 1,
+            ChecklistCondition::ZoneText { .. } => 2,
+        }
+    }
+}
+
+/// Edit the screen, trigger or zone (and text) a condition waits for
+pub(super) fn render_condition_target(
+    ui: &mut egui::Ui,
+    id: &str,
+    condition: &mut ChecklistCondition,
+    targets: &ConditionTargets,
+) {
+    match condition {
+        ChecklistCondition::Screen { screen_id } => {
+            target_combo(ui, ("checklist_screen", id), screen_id, &targets.screens);
+        }
+        ChecklistCondition::ColorTrigger { trigger_id } => {
+            target_combo(
+                ui,
+                ("checklist_trigger", id),
+                trigger_id,
+                &targets.color_triggers,
+            );
+        }
+        ChecklistCondition::ZoneText { zone_id, text } => {
+            target_combo(ui, ("checklist_zone", id), zone_id, &targets.zones);
+            ui.add(
+                egui::TextEdit::singleline(text)
+                    .hint_text("text, e.g. Flask bought")
+                    .desired_width(120.0),
+            );
+        }
+    }
+}
+
+/// Pick a screen, trigger or zone by name
+fn target_combo(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    selected: &mut String,
+    targets: &[(String, String)],
+) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .width(110.0)
+        .selected_text(target_name(selected, targets))
+        .show_ui(ui, |ui| {
+            for (id, name) in targets {
+                ui.selectable_value(selected, id.clone(), name);
+            }
+        });
+}
+
+/// Name of a screen, trigger or zone, or a placeholder if it's gone
+fn target_name<'a>(id: &str, targets: &'a [(String, String)]) -> &'a str {
+    targets
+        .iter()
+        .find(|(target_id, _)| target_id == id)
+        .map_or("(none)", |(_, name)| name.as_str())
+}
+
+/// Hover text explaining what checks an item
+fn describe_condition(condition: &ChecklistCondition, targets: &ConditionTargets) -> String {
+    match condition {
+        ChecklistCondition::Screen { screen_id } => format!(
+            "Checks itself when the '{}' screen shows",
+            target_name(screen_id, &targets.screens)
+        ),
+        ChecklistCondition::ColorTrigger { trigger_id } => format!(
+            "Checks itself when the '{}' color trigger fires",
+            target_name(trigger_id, &targets.color_triggers)
+        ),
+        ChecklistCondition::ZoneText { zone_id, text } => format!(
+            "Checks itself when the '{}' zone reads \"{}\"",
+            target_name(zone_id, &targets.zones),
+            text
+        ),
+    }
+}
+
+/// Edit the active profile's checklists and mark them for saving
+fn update_checklists<F>(
+    state: &mut HomeViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+    update: F,
+) where
+    F: FnOnce(&mut Vec<Checklist>),
+{
+    let mut shared = shared_state.write();
+    let active_id = shared.active_profile_id.clone();
+    if let Some(profile) = shared
+        .profiles
+        .iter_mut()
+        .find(|p| active_id.as_ref() == Some(&p.id))
+    {
+        update(&mut profile.checklists);
+        state.checklists_dirty = true;
+    }
+}
@@ -0,0 +1,246 @@
+//! Color trigger editor
+//!
+//! Lists the active profile's pixel-color triggers under the Vision preview.
+//! "Pick" arms an eyedropper: the next click on the preview stores the
+//! clicked spot and the color it shows.
+
+use egui::{Color32, RichText, Stroke};
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dashboard::components::remembered_section;
+use crate::dashboard::state::VisionViewState;
+use crate::dashboard::theme::ThemeColors;
+use crate::shared::SharedAppState;
+use crate::storage::profiles::ColorTrigger;
+use crate::vision::color_trigger::{patch_color, pixel_at};
+
+/// Render the color trigger list of the active profile
+pub fn render_color_triggers(
+    ui: &mut egui::Ui,
+    view_state: &mut VisionViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let (triggers, active) = {
+        let shared = shared_state.read();
+        (
+            shared.active_profile().map(|p| p.color_triggers.clone()),
+            shared.runtime.active_color_triggers.clone(),
+        )
+    };
+
+    remembered_section(
+        ui,
+        "vision.color_triggers",
+        egui::CollapsingHeader::new(
+            RichText::new(format!(
+                "Color Triggers ({})",
+                triggers.as_ref().map_or(0, Vec::len)
+            ))
+            .size(14.0),
+        )
+        .id_salt("color_triggers"),
+        false,
+        |ui| {
+            let Some(triggers) = triggers else {
+                ui.label(
+                    RichText::new("Activate a profile to add color triggers")
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+                return;
+            };
+
+            if view_state.picking_color_trigger.is_some() {
+                ui.label(
+                    RichText::new("Click the preview to pick the spot and color")
+                        .size(12.0)
+                        .color(ThemeColors::ACCENT_WARNING),
+                );
+            }
+
+            for trigger in &triggers {
+                render_trigger_row(ui, trigger, &active, view_state, shared_state);
+            }
+
+            if ui
+                .button("+ Add Trigger")
+                .on_hover_text("Watch a pixel for a color, e.g. the red of a low-health vignette")
+                .clicked()
+            {
+                let trigger = ColorTrigger {
+                    id: Uuid::new_v4().to_string(),
+                    name: format!("Trigger {}", triggers.len() + 1),
+                    position: (0.5, 0.5),
+                    patch_size: 3,
+                    color: [255, 0, 0],
+                    tolerance: 24,
+                    enabled: true,
+                    save_replay: false,
+                };
+                view_state.picking_color_trigger = Some(trigger.id.clone());
+                update_triggers(view_state, shared_state, |triggers| triggers.push(trigger));
+            }
+        },
+    );
+}
+
+/// Render one trigger's state and settings
+fn render_trigger_row(
+    ui: &mut egui::Ui,
+    trigger: &ColorTrigger,
+    active: &HashSet<String>,
+    view_state: &mut VisionViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let mut edited = trigger.clone();
+    let mut delete = false;
+
+    ui.horizontal(|ui| {
+        let (status, color) = if active.contains(&trigger.id) {
+            ("●", ThemeColors::ACCENT_SUCCESS)
+        } else {
+            ("○", ThemeColors::TEXT_MUTED)
+        };
+        ui.label(RichText::new(status).color(color))
+            .on_hover_text("Lit while the color is showing");
+
+        ui.checkbox(&mut edited.enabled, "");
+        ui.add(egui::TextEdit::singleline(&mut edited.name).desired_width(100.0));
+        ui.color_edit_button_srgb(&mut edited.color);
+        ui.add(
+            egui::DragValue::new(&mut edited.tolerance)
+                .range(0..=128)
+                .prefix("±"),
+        )
+        .on_hover_text("Largest difference per color channel still counted as a match");
+        ui.add(
+            egui::DragValue::new(&mut edited.patch_size)
+                .range(1..=15)
+                .suffix(" px"),
+        )
+        .on_hover_text("Side of the square of pixels averaged");
+
+        let picking = view_state.picking_color_trigger.as_deref() == Some(trigger.id.as_str());
+        if ui
+            .selectable_label(picking, "Pick")
+            .on_hover_text("Click the preview to pick the spot and color")
+            .clicked()
+        {
+            view_state.picking_color_trigger = (!picking).then(|| trigger.id.clone());
+        }
+
+        ui.toggle_value(&mut edited.save_replay, "Replay")
+            .on_hover_text("Save the replay buffer when the color starts showing");
+
+        if ui
+            .small_button("X")
+            .on_hover_text("Delete trigger")
+            .clicked()
+        {
+            delete = true;
+        }
+    });
+
+    if delete {
+        update_triggers(view_state, shared_state, |triggers| {
+            triggers.retain(|t| t.id != trigger.id)
+        });
+    } else if edited != *trigger {
+        update_triggers(view_state, shared_state, |triggers| {
+            if let Some(t) = triggers.iter_mut().find(|t| t.id == trigger.id) {
+                *t = edited;
+            }
+        });
+    }
+}
+
+/// Set the armed trigger's spot and color from a click on the preview
+///
+/// `position` is relative to the frame (0.0-1.0).
+pub fn pick_trigger_color(
+    view_state: &mut VisionViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+    position: (f32, f32),
+) {
+    let Some(id) = view_state.picking_color_trigger.take() else {
+        return;
+    };
+    let patch_size = shared_state
+        .read()
+        .active_profile()
+        .and_then(|p| p.color_triggers.iter().find(|t| t.id == id))
+        .map(|t| t.patch_size);
+
+    // The preview always shows the last frame, frozen or not
+    let (width, height) = (view_state.last_frame_width, view_state.last_frame_height);
+    let (Some(patch_size), Some(ref data), Some(pixel)) = (
+        patch_size,
+        &view_state.last_frame_data,
+        pixel_at(width, height, position),
+    ) else {
+        return;
+    };
+    let Some(color) = patch_color(data, width, height, pixel, patch_size) else {
+        return;
+    };
+
+    update_triggers(view_state, shared_state, |triggers| {
+        if let Some(trigger) = triggers.iter_mut().find(|t| t.id == id) {
+            trigger.position = position;
+            trigger.color = color;
+        }
+    });
+}
+
+/// Mark each trigger's spot on the preview, filled while it fires
+pub fn draw_color_trigger_markers(
+    ui: &egui::Ui,
+    triggers: &[ColorTrigger],
+    image_rect: egui::Rect,
+    active: &HashSet<String>,
+) {
+    let painter = ui.painter();
+    for trigger in triggers.iter().filter(|t| t.enabled) {
+        let center = image_rect.min
+            + egui::vec2(
+                trigger.position.0 * image_rect.width(),
+                trigger.position.1 * image_rect.height(),
+            );
+        let [r, g, b] = trigger.color;
+        let color = Color32::from_rgb(r, g, b);
+        if active.contains(&trigger.id) {
+            painter.circle_filled(center, 5.0, color);
+        }
+        painter.circle_stroke(center, 6.0, Stroke::new(1.5, Color32::WHITE));
+        painter.text(
+            center + egui::vec2(9.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            &trigger.name,
+            egui::FontId::proportional(11.0),
+            Color32::WHITE,
+        );
+    }
+}
+
+/// Edit the active profile's triggers and mark them for saving
+fn update_triggers<F>(
+    view_state: &mut VisionViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+    update: F,
+) where
+    F: FnOnce(&mut Vec<ColorTrigger>),
+{
+    let mut shared = shared_state.write();
+    let active_id = shared.active_profile_id.clone();
+    if let Some(profile) = shared
+        .profiles
+        .iter_mut()
+        .find(|p| active_id.as_ref() == Some(&p.id))
+    {
+        update(&mut profile.color_triggers);
+        view_state.color_triggers_dirty = true;
+    }
+}
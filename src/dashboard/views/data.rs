@@ -0,0 +1,625 @@
+//! Data view - Browse the app's SQLite database
+
+use egui::RichText;
+
+use crate::analysis::coach::{self, format_rate, ZonePace};
+use crate::analysis::heatmap::{self, Heatmap};
+use crate::analysis::wellness::format_playtime;
+use crate::dashboard::state::{BatchOcrState, DataBrowserMode, DataViewState};
+use crate::dashboard::theme::{color_with_alpha, ThemeColors};
+use crate::storage::database::{
+    browse_table, open_read_only, playtime_stats, recent_session_readings, run_read_only_query,
+    tip_origins, PlaytimeStats, QueryResult, TipOrigin, BROWSABLE_TABLES, PLAYTIME_DAYS,
+};
+use crate::vision::batch::DEFAULT_OUTPUT_NAME;
+
+/// Maximum rows loaded per query
+const ROW_LIMIT: usize = 500;
+
+/// Cells longer than this are shortened (full value on hover)
+const MAX_CELL_CHARS: usize = 60;
+
+/// Latest tips shown on the tip heatmap
+const HEATMAP_TIPS: usize = 5_000;
+
+/// Cells of the tip heatmap across and down
+const HEATMAP_COLUMNS: usize = 32;
+const HEATMAP_ROWS: usize = 18;
+
+/// Render the data browser view
+pub fn render_data_view(ui: &mut egui::Ui, view_state: &mut DataViewState) {
+    ui.heading(RichText::new("Data").size(24.0).strong());
+    ui.add_space(8.0);
+    ui.label(
+        RichText::new("Browse recorded sessions, events, zone readings and playtime")
+            .size(14.0)
+            .color(ThemeColors::TEXT_SECONDARY),
+    );
+
+    ui.add_space(24.0);
+
+    // Load the first table when the view is first opened
+    if view_state.table.is_empty() {
+        view_state.table = BROWSABLE_TABLES[0].to_string();
+        run_query(view_state);
+    }
+
+    // Mode tabs
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut view_state.mode, DataBrowserMode::Tables, "Tables");
+        ui.selectable_value(&mut view_state.mode, DataBrowserMode::Sql, "SQL");
+        ui.selectable_value(&mut view_state.mode, DataBrowserMode::BatchOcr, "Batch OCR");
+        ui.selectable_value(&mut view_state.mode, DataBrowserMode::Playtime, "Playtime");
+        ui.selectable_value(&mut view_state.mode, DataBrowserMode::Coach, "Coach");
+        ui.selectable_value(
+            &mut view_state.mode,
+            DataBrowserMode::TipHeatmap,
+            "Tip Heatmap",
+        );
+    });
+
+    ui.add_space(12.0);
+
+    match view_state.mode {
+        DataBrowserMode::BatchOcr => {
+            render_batch_ocr(ui, &mut view_state.batch);
+            return;
+        }
+        DataBrowserMode::Playtime => {
+            render_playtime(ui, view_state);
+            return;
+        }
+        DataBrowserMode::Coach => {
+            render_coach(ui, view_state);
+            return;
+        }
+        DataBrowserMode::TipHeatmap => {
+            render_tip_heatmap(ui, view_state);
+            return;
+        }
+        DataBrowserMode::Tables | DataBrowserMode::Sql => {}
+    }
+
+    let mut run = false;
+
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            match view_state.mode {
+                DataBrowserMode::Tables => {
+                    ui.horizontal(|ui| {
+                        ui.label("Table:");
+                        egui::ComboBox::from_id_salt("data_table")
+                            .selected_text(&view_state.table)
+                            .show_ui(ui, |ui| {
+                                for table in BROWSABLE_TABLES {
+                                    if ui
+                                        .selectable_label(view_state.table == *table, *table)
+                                        .clicked()
+                                    {
+                                        view_state.table = table.to_string();
+                                        run = true;
+                                    }
+                                }
+                            });
+
+                        ui.add_space(16.0);
+                        ui.label("Filter:");
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut view_state.filter)
+                                .hint_text("Text in any column...")
+                                .desired_width(200.0),
+                        );
+                        if response.changed() {
+                            run = true;
+                        }
+
+                        ui.add_space(8.0);
+                        if ui.button("Refresh").clicked() {
+                            run = true;
+                        }
+                    });
+                }
+                DataBrowserMode::Sql => {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut view_state.sql)
+                            .code_editor()
+                            .hint_text(
+                                "SELECT zone_name, COUNT(*) FROM zone_readings GROUP BY zone_name",
+                            )
+                            .desired_rows(4)
+                            .desired_width(f32::INFINITY),
+                    );
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                !view_state.sql.trim().is_empty(),
+                                egui::Button::new("Run Query"),
+                            )
+                            .clicked()
+                        {
+                            run = true;
+                        }
+                        ui.label(
+                            RichText::new("Read-only: statements that modify data are rejected")
+                                .size(11.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    });
+                }
+                // Rendered by their own render functions
+                DataBrowserMode::BatchOcr
+                | DataBrowserMode::Playtime
+                | DataBrowserMode::Coach
+                | DataBrowserMode::TipHeatmap => {}
+            }
+        });
+
+    if run {
+        run_query(view_state);
+    }
+
+    ui.add_space(16.0);
+
+    if let Some(ref error) = view_state.error {
+        egui::Frame::none()
+            .fill(color_with_alpha(ThemeColors::ACCENT_ERROR, 38)) // ~0.15 alpha
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(12.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+            });
+        return;
+    }
+
+    if let Some(ref result) = view_state.result {
+        render_result(ui, result);
+    }
+}
+
+/// Run the current table browse or SQL query and store the result
+fn run_query(view_state: &mut DataViewState) {
+    let result = crate::storage::get_database_path().and_then(|path| {
+        let conn = open_read_only(&path)?;
+        match view_state.mode {
+            DataBrowserMode::Tables
+            | DataBrowserMode::BatchOcr
+            | DataBrowserMode::Playtime
+            | DataBrowserMode::Coach
+            | DataBrowserMode::TipHeatmap => {
+                browse_table(&conn, &view_state.table, &view_state.filter, ROW_LIMIT)
+            }
+            DataBrowserMode::Sql => run_read_only_query(&conn, &view_state.sql, ROW_LIMIT),
+        }
+    });
+
+    match result {
+        Ok(result) => {
+            view_state.result = Some(result);
+            view_state.error = None;
+        }
+        Err(e) => {
+            view_state.result = None;
+            view_state.error = Some(format!("{:#}", e));
+        }
+    }
+}
+
+/// Render the playtime of each profile over the last week
+fn render_playtime(ui: &mut egui::Ui, view_state: &mut DataViewState) {
+    if view_state.playtime.is_none() {
+        load_playtime(view_state);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new("Time spent capturing, per profile and day (local time)")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        if ui.button("Refresh").clicked() {
+            load_playtime(view_state);
+        }
+    });
+    ui.add_space(8.0);
+
+    let stats = match view_state.playtime {
+        Some(Ok(ref stats)) => stats,
+        Some(Err(ref error)) => {
+            ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+            return;
+        }
+        None => return,
+    };
+    if stats.is_empty() {
+        ui.label(
+            RichText::new("No playtime recorded in the last week")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            egui::Grid::new("playtime_grid")
+                .striped(true)
+                .spacing(egui::vec2(16.0, 6.0))
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Profile").strong());
+                    for days_ago in (0..PLAYTIME_DAYS).rev() {
+                        let label = match days_ago {
+                            0 => "Today".to_string(),
+                            1 => "Yesterday".to_string(),
+                            n => format!("{}d ago", n),
+                        };
+                        ui.label(RichText::new(label).strong());
+                    }
+                    ui.label(RichText::new("Week").strong());
+                    ui.end_row();
+
+                    for profile in stats {
+                        ui.label(&profile.profile_name)
+                            .on_hover_text(&profile.profile_id);
+                        for &seconds in &profile.daily_seconds {
+                            let text = format_playtime(std::time::Duration::from_secs(seconds));
+                            let color = if seconds == 0 {
+                                ThemeColors::TEXT_MUTED
+                            } else {
+                                ThemeColors::TEXT_PRIMARY
+                            };
+                            ui.label(RichText::new(text).color(color));
+                        }
+                        ui.label(
+                            RichText::new(format_playtime(std::time::Duration::from_secs(
+                                profile.total(),
+                            )))
+                            .strong(),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Load the playtime stats from the database
+fn load_playtime(view_state: &mut DataViewState) {
+    let result: anyhow::Result<Vec<PlaytimeStats>> = crate::storage::get_database_path()
+        .and_then(|path| playtime_stats(&open_read_only(&path)?));
+    view_state.playtime = Some(result.map_err(|e| format!("{:#}", e)));
+}
+
+/// Render the pace of each numeric zone in the latest session against the
+/// average of previous sessions
+fn render_coach(ui: &mut egui::Ui, view_state: &mut DataViewState) {
+    if view_state.coach.is_none() {
+        load_coach(view_state);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!(
+                "Change per hour of numeric zones in the latest session, against the last {} \
+                 sessions",
+                coach::AVERAGE_SESSIONS
+            ))
+            .size(12.0)
+            .color(ThemeColors::TEXT_MUTED),
+        );
+        if ui.button("Refresh").clicked() {
+            load_coach(view_state);
+        }
+    });
+    ui.add_space(8.0);
+
+    let paces = match view_state.coach {
+        Some(Ok(ref paces)) => paces,
+        Some(Err(ref error)) => {
+            ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+            return;
+        }
+        None => return,
+    };
+    if paces.is_empty() {
+        ui.label(
+            RichText::new(
+                "No pace yet: numeric zones need readings over at least 5 minutes of a session",
+            )
+            .size(12.0)
+            .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            egui::Grid::new("coach_grid")
+                .striped(true)
+                .spacing(egui::vec2(16.0, 6.0))
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Zone").strong());
+                    ui.label(RichText::new("Latest session").strong());
+                    ui.label(RichText::new("Average").strong());
+                    ui.label(RichText::new("Difference").strong());
+                    ui.end_row();
+
+                    for pace in paces {
+                        ui.label(&pace.zone_name).on_hover_text(&pace.zone_id);
+                        ui.label(format_rate(pace.current));
+                        match pace.average {
+                            Some(average) => ui
+                                .label(format_rate(average))
+                                .on_hover_text(format!("Over {} previous sessions", pace.sessions)),
+                            None => ui.label(RichText::new("-").color(ThemeColors::TEXT_MUTED)),
+                        };
+                        match pace.deviation() {
+                            Some(deviation) => {
+                                // Off by a lot either way; whether more is
+                                // better depends on the zone
+                                let color = if deviation.abs() >= 0.2 {
+                                    ThemeColors::ACCENT_WARNING
+                                } else {
+                                    ThemeColors::TEXT_PRIMARY
+                                };
+                                let arrow = if deviation < 0.0 { "▼" } else { "▲" };
+                                ui.label(
+                                    RichText::new(format!(
+                                        "{} {:.0}%",
+                                        arrow,
+                                        deviation.abs() * 100.0
+                                    ))
+                                    .color(color),
+                                );
+                            }
+                            None => {
+                                ui.label(RichText::new("-").color(ThemeColors::TEXT_MUTED));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Load the latest sessions' readings and compare their paces
+fn load_coach(view_state: &mut DataViewState) {
+    let result: anyhow::Result<Vec<ZonePace>> =
+        crate::storage::get_database_path().and_then(|path| {
+            let sessions =
+                recent_session_readings(&open_read_only(&path)?, coach::AVERAGE_SESSIONS + 1)?;
+            Ok(coach::compare(&sessions))
+        });
+    view_state.coach = Some(result.map_err(|e| format!("{:#}", e)));
+}
+
+/// Render where on screen recent tips came from, and how many each source raised
+fn render_tip_heatmap(ui: &mut egui::Ui, view_state: &mut DataViewState) {
+    if view_state.tip_origins.is_none() {
+        load_tip_origins(view_state);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!(
+                "Regions of the frame the last {} tips shown were raised from (label changes \
+                 and watch alerts on zones and color triggers)",
+                HEATMAP_TIPS
+            ))
+            .size(12.0)
+            .color(ThemeColors::TEXT_MUTED),
+        );
+        if ui.button("Refresh").clicked() {
+            load_tip_origins(view_state);
+        }
+    });
+    ui.add_space(8.0);
+
+    let origins = match view_state.tip_origins {
+        Some(Ok(ref origins)) => origins,
+        Some(Err(ref error)) => {
+            ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+            return;
+        }
+        None => return,
+    };
+    if origins.is_empty() {
+        ui.label(
+            RichText::new("No tips from a place on screen recorded yet")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    // The frame as a 16:9 grid, hotter where more tips came from
+    let heatmap = Heatmap::new(origins, HEATMAP_COLUMNS, HEATMAP_ROWS);
+    let max = heatmap.max().max(1);
+    let width = ui.available_width().min(640.0);
+    let size = egui::vec2(width, width * 9.0 / 16.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, ThemeColors::BG_DARK);
+    let cell = egui::vec2(
+        rect.width() / HEATMAP_COLUMNS as f32,
+        rect.height() / HEATMAP_ROWS as f32,
+    );
+    for row in 0..HEATMAP_ROWS {
+        for column in 0..HEATMAP_COLUMNS {
+            let count = heatmap.get(column, row);
+            if count == 0 {
+                continue;
+            }
+            let min = rect.min + egui::vec2(column as f32 * cell.x, row as f32 * cell.y);
+            let alpha = (40 + 215 * count / max) as u8;
+            painter.rect_filled(
+                egui::Rect::from_min_size(min, cell),
+                0.0,
+                color_with_alpha(ThemeColors::ACCENT_ERROR, alpha),
+            );
+        }
+    }
+    painter.rect_stroke(rect, 4.0, egui::Stroke::new(1.0, ThemeColors::BORDER));
+    if let Some(pointer) = response.hover_pos() {
+        let column = (((pointer.x - rect.min.x) / cell.x) as usize).min(HEATMAP_COLUMNS - 1);
+        let row = (((pointer.y - rect.min.y) / cell.y) as usize).min(HEATMAP_ROWS - 1);
+        response.on_hover_text(format!("{} tips", heatmap.get(column, row)));
+    }
+
+    ui.add_space(12.0);
+    egui::Grid::new("tip_sources_grid")
+        .striped(true)
+        .spacing(egui::vec2(16.0, 6.0))
+        .show(ui, |ui| {
+            ui.label(RichText::new("Source").strong());
+            ui.label(RichText::new("Tips").strong());
+            ui.end_row();
+            for (source, count) in heatmap::by_source(origins) {
+                ui.label(source);
+                ui.label(count.to_string());
+                ui.end_row();
+            }
+        });
+}
+
+/// Load the latest tip origins from the database
+fn load_tip_origins(view_state: &mut DataViewState) {
+    let result: anyhow::Result<Vec<TipOrigin>> = crate::storage::get_database_path()
+        .and_then(|path| tip_origins(&open_read_only(&path)?, HEATMAP_TIPS));
+    view_state.tip_origins = Some(result.map_err(|e| format!("{:#}", e)));
+}
+
+/// Render a query result as a table
+fn render_result(ui: &mut egui::Ui, result: &QueryResult) {
+    let summary = if result.truncated {
+        format!("Showing the first {} rows", result.rows.len())
+    } else {
+        format!("{} row(s)", result.rows.len())
+    };
+    ui.label(
+        RichText::new(summary)
+            .size(12.0)
+            .color(ThemeColors::TEXT_MUTED),
+    );
+
+    ui.add_space(8.0);
+
+    egui::ScrollArea::both()
+        .id_salt("data_result")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("data_result_grid")
+                .striped(true)
+                .spacing(egui::vec2(16.0, 4.0))
+                .show(ui, |ui| {
+                    for column in &result.columns {
+                        ui.label(RichText::new(column).strong());
+                    }
+                    ui.end_row();
+
+                    for row in &result.rows {
+                        for value in row {
+                            if value.chars().count() > MAX_CELL_CHARS {
+                                let short: String = value.chars().take(MAX_CELL_CHARS).collect();
+                                ui.label(format!("{}…", short)).on_hover_text(value);
+                            } else {
+                                ui.label(value);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Render the batch OCR card: pick a folder, run the active profile's zones
+/// over every image in it and write the readings to a CSV
+fn render_batch_ocr(ui: &mut egui::Ui, state: &mut BatchOcrState) {
+    egui::Frame::none()
+        .fill(ThemeColors::BG_MEDIUM)
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(
+                RichText::new(
+                    "Reads the active profile's enabled zones from every image in a folder \
+                     and writes one CSV row per image.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+            ui.add_space(8.0);
+
+            let running = state.run.is_some();
+            egui::Grid::new("batch_ocr_paths")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 6.0))
+                .show(ui, |ui| {
+                    ui.label("Folder:");
+                    ui.add_enabled(
+                        !running,
+                        egui::TextEdit::singleline(&mut state.folder)
+                            .hint_text("C:\\Users\\me\\Pictures\\Screenshots")
+                            .desired_width(360.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Output:");
+                    ui.add_enabled(
+                        !running,
+                        egui::TextEdit::singleline(&mut state.output)
+                            .hint_text(format!("{} in the folder", DEFAULT_OUTPUT_NAME))
+                            .desired_width(360.0),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if running {
+                    if ui.button("Cancel").clicked() {
+                        state.run = None;
+                        state.status = Some("Batch cancelled".to_string());
+                    }
+                } else if ui
+                    .add_enabled(!state.folder.trim().is_empty(), egui::Button::new("Start"))
+                    .clicked()
+                {
+                    state.pending_start = true;
+                }
+            });
+
+            if let Some(ref run) = state.run {
+                ui.add_space(8.0);
+                let progress = run.processed() as f32 / run.total().max(1) as f32;
+                ui.add(egui::ProgressBar::new(progress).text(format!(
+                    "{} / {} images",
+                    run.processed(),
+                    run.total()
+                )));
+            }
+        });
+
+    ui.add_space(16.0);
+
+    if let Some(ref error) = state.error {
+        ui.label(RichText::new(error).color(ThemeColors::ACCENT_ERROR));
+    } else if let Some(ref status) = state.status {
+        ui.label(RichText::new(status).color(ThemeColors::TEXT_SECONDARY));
+    }
+}
@@ -0,0 +1,178 @@
+//! Session goals widget
+//!
+//! Shows the goals of the capture session on the Home view: their progress
+//! while capturing, with a button to tick counters, and the goals for the
+//! next session otherwise. The scorecard is shown as a tip when capture stops.
+
+use egui::RichText;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
+
+use crate::config::{SessionGoal, SessionGoalKind};
+use crate::dashboard::state::HomeViewState;
+use crate::dashboard::theme::ThemeColors;
+use crate::shared::SharedAppState;
+
+/// Kinds of goal offered when adding or changing one, with their defaults
+fn goal_kinds() -> [SessionGoalKind; 3] {
+    [
+        SessionGoalKind::MaxPlaytime { minutes: 120 },
+        SessionGoalKind::Reach {
+            variable: String::new(),
+            target: 1.0,
+        },
+        SessionGoalKind::Count { target: 1 },
+    ]
+}
+
+/// Render the session goals
+pub fn render_session_goals(
+    ui: &mut egui::Ui,
+    state: &mut HomeViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.heading(RichText::new("Session Goals").size(18.0));
+    ui.add_space(12.0);
+
+    let mut shared = shared_state.write();
+    let capturing = shared.runtime.is_capturing;
+    if let Some(ref mut goals) = shared.runtime.session_goals {
+        let played = goals.played(Instant::now());
+        let mut tick = None;
+        egui::Grid::new("session_goals_progress")
+            .num_columns(3)
+            .spacing([16.0, 6.0])
+            .show(ui, |ui| {
+                for progress in &goals.goals {
+                    let met = progress.is_met(played);
+                    ui.label(RichText::new(&progress.goal.name).strong());
+                    ui.add(
+                        egui::ProgressBar::new(progress.fraction(played))
+                            .desired_width(200.0)
+                            .fill(if met {
+                                ThemeColors::ACCENT_SUCCESS
+                            } else {
+                                ThemeColors::ACCENT_PRIMARY
+                            })
+                            .text(progress.describe(played)),
+                    );
+                    if matches!(progress.goal.kind, SessionGoalKind::Count { .. }) {
+                        if ui.small_button("+1").clicked() {
+                            tick = Some(progress.goal.id.clone());
+                        }
+                    } else {
+                        ui.label("");
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(id) = tick {
+            goals.increment(&id);
+        }
+        return;
+    }
+
+    if capturing {
+        ui.label(
+            RichText::new("No goals this session. Goals set here apply from the next capture.")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        ui.add_space(8.0);
+    }
+
+    let goals = &mut shared.config.session_goals;
+    let mut remove = None;
+    let mut changed = false;
+    egui::Grid::new("session_goals_edit")
+        .num_columns(4)
+        .spacing([8.0, 6.0])
+        .show(ui, |ui| {
+            for (index, goal) in goals.iter_mut().enumerate() {
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut goal.name).desired_width(140.0))
+                    .changed();
+
+                egui::ComboBox::from_id_salt(("session_goal_kind", &goal.id))
+                    .selected_text(goal.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in goal_kinds() {
+                            let selected =
+                                std::mem::discriminant(&kind) == std::mem::discriminant(&goal.kind);
+                            if ui.selectable_label(selected, kind.name()).clicked() && !selected {
+                                goal.kind = kind;
+                                changed = true;
+                            }
+                        }
+                    });
+
+                ui.horizontal(|ui| match goal.kind {
+                    SessionGoalKind::MaxPlaytime { ref mut minutes } => {
+                        changed |= ui
+                            .add(egui::DragValue::new(minutes).range(1..=1440).suffix(" min"))
+                            .changed();
+                    }
+                    SessionGoalKind::Reach {
+                        ref mut variable,
+                        ref mut target,
+                    } => {
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(variable)
+                                    .hint_text("zone.Level")
+                                    .desired_width(120.0),
+                            )
+                            .on_hover_text(
+                                "A variable of the watch window, e.g. the reading of a zone",
+                            )
+                            .changed();
+                        ui.label("≥");
+                        changed |= ui.add(egui::DragValue::new(target)).changed();
+                    }
+                    SessionGoalKind::Count { ref mut target } => {
+                        changed |= ui
+                            .add(egui::DragValue::new(target).range(1..=1000))
+                            .changed();
+                    }
+                });
+
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(index) = remove {
+        goals.remove(index);
+        changed = true;
+    }
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        if ui.button("Add Goal").clicked() {
+            goals.push(SessionGoal {
+                id: Uuid::new_v4().to_string(),
+                name: format!("Goal {}", goals.len() + 1),
+                kind: SessionGoalKind::Count { target: 1 },
+            });
+            changed = true;
+        }
+        if goals.is_empty() {
+            ui.label(
+                RichText::new(
+                    "Set goals such as a playtime limit, a level to reach or a number of \
+                     dungeon clears; they're scored when capture stops.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+            );
+        }
+    });
+
+    if changed {
+        state.config_changed = true;
+    }
+}
@@ -4,20 +4,52 @@ use egui::RichText;
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+use crate::config::TipFallback;
 use crate::dashboard::components::status_card::{CardStatus, StatusCard};
+use crate::dashboard::components::{
+    render_capability_panel, render_control_log, render_error_center,
+};
 use crate::dashboard::state::HomeViewState;
 use crate::dashboard::theme::{color_with_alpha, ThemeColors};
+use crate::dashboard::views::checklists::render_checklists;
+use crate::dashboard::views::goals::render_session_goals;
+use crate::power::PowerStatus;
 use crate::shared::{CaptureCommand, OverlayCommand, SharedAppState};
 
 /// Render the home view
 pub fn render_home_view(
     ui: &mut egui::Ui,
-    _state: &mut HomeViewState,
+    state: &mut HomeViewState,
     shared_state: &Arc<RwLock<SharedAppState>>,
 ) {
-    let app_state = shared_state.read();
-
-    ui.heading(RichText::new("Dashboard").size(24.0).strong());
+    ui.horizontal(|ui| {
+        ui.heading(RichText::new("Dashboard").size(24.0).strong());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("What's New").clicked() {
+                state.open_whats_new = true;
+            }
+            let (power, afk, disabled) = {
+                let state = shared_state.read();
+                (
+                    state.runtime.power,
+                    state.runtime.afk,
+                    state.config.features.disabled(),
+                )
+            };
+            if power.on_battery {
+                ui.add_space(8.0);
+                render_power_chip(ui, power);
+            }
+            if afk {
+                ui.add_space(8.0);
+                render_afk_chip(ui);
+            }
+            if !disabled.is_empty() {
+                ui.add_space(8.0);
+                render_features_chip(ui, &disabled);
+            }
+        });
+    });
     ui.add_space(8.0);
     ui.label(
         RichText::new("Monitor and control your GamersToolKit instance")
@@ -27,6 +59,22 @@ pub fn render_home_view(
 
     ui.add_space(24.0);
 
+    if shared_state.read().runtime.safe_mode {
+        render_safe_mode_banner(ui, shared_state);
+        ui.add_space(24.0);
+    }
+
+    let overlay_hidden = {
+        let runtime = &shared_state.read().runtime;
+        runtime.is_overlay_running && runtime.qos.exclusive_fullscreen
+    };
+    if overlay_hidden {
+        render_exclusive_fullscreen_banner(ui, state, shared_state);
+        ui.add_space(24.0);
+    }
+
+    let app_state = shared_state.read();
+
     // Status cards row
     ui.horizontal(|ui| {
         // Capture status
@@ -124,7 +172,13 @@ pub fn render_home_view(
         ui.add_space(12.0);
 
         // Start/Stop Overlay button
-        let is_overlay_running = shared_state.read().runtime.is_overlay_running;
+        let (is_overlay_running, overlay_allowed) = {
+            let state = shared_state.read();
+            (
+                state.runtime.is_overlay_running,
+                state.config.features.overlay,
+            )
+        };
         let overlay_btn_text = if is_overlay_running {
             "Stop Overlay"
         } else {
@@ -137,11 +191,13 @@ pub fn render_home_view(
         };
 
         if ui
-            .add(
+            .add_enabled(
+                overlay_allowed || is_overlay_running,
                 egui::Button::new(RichText::new(overlay_btn_text).color(egui::Color32::WHITE))
                     .fill(overlay_btn_color)
                     .min_size(egui::vec2(120.0, 36.0)),
             )
+            .on_disabled_hover_text("The overlay is turned off in Settings > Features")
             .clicked()
         {
             let mut state = shared_state.write();
@@ -181,12 +237,42 @@ pub fn render_home_view(
                 .clicked()
         {
             let mut state = shared_state.write();
-            state.runtime.send_test_tip = true;
+            state.runtime.send_test_tip = Some(Default::default());
+        }
+
+        if ui
+            .add(egui::Button::new("Take Screenshot").min_size(egui::vec2(120.0, 36.0)))
+            .on_hover_text("Save the next frame as a PNG")
+            .clicked()
+        {
+            shared_state.write().runtime.capture_command = Some(CaptureCommand::Screenshot {
+                target: None,
+                path: None,
+            });
         }
     });
 
     ui.add_space(32.0);
 
+    // Goals of the capture session
+    render_session_goals(ui, state, shared_state);
+
+    ui.add_space(32.0);
+
+    // Coaching checklists of the active profile
+    render_checklists(ui, state, shared_state);
+
+    ui.add_space(32.0);
+
+    render_recent_screenshots(ui, shared_state);
+
+    ui.add_space(32.0);
+
+    // System capability checks
+    render_capability_panel(ui, shared_state);
+
+    ui.add_space(32.0);
+
     // Current configuration summary
     ui.heading(RichText::new("Configuration Summary").size(18.0));
     ui.add_space(12.0);
@@ -218,20 +304,280 @@ pub fn render_home_view(
             ui.label(RichText::new("Tips Displayed:").color(ThemeColors::TEXT_MUTED));
             ui.label(format!("{}", app_state.runtime.tips_displayed));
             ui.end_row();
+
+            if app_state.runtime.dropped_overlay_events > 0 {
+                ui.label(RichText::new("Overlay Dropped:").color(ThemeColors::TEXT_MUTED));
+                ui.label(
+                    RichText::new(format!(
+                        "{} tips/commands",
+                        app_state.runtime.dropped_overlay_events
+                    ))
+                    .color(ThemeColors::ACCENT_WARNING),
+                );
+                ui.end_row();
+            }
         });
 
-    // Error display
-    if let Some(error) = &app_state.runtime.last_error {
-        ui.add_space(24.0);
-        egui::Frame::none()
-            .fill(color_with_alpha(ThemeColors::ACCENT_ERROR, 51)) // ~0.2 alpha
-            .rounding(egui::Rounding::same(6.0))
-            .inner_margin(12.0)
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    ui.label(RichText::new("Error:").color(ThemeColors::ACCENT_ERROR).strong());
-                    ui.label(RichText::new(error).color(ThemeColors::TEXT_PRIMARY));
+    drop(app_state);
+
+    // Recent errors with suggested fixes
+    ui.add_space(24.0);
+    render_error_center(ui, shared_state);
+
+    // Commands from hotkeys and MCP, to trace what changed the overlay
+    ui.add_space(24.0);
+    render_control_log(ui, shared_state);
+}
+
+/// Latest screenshots, each opening in the default image viewer
+fn render_recent_screenshots(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    ui.heading(RichText::new("Screenshots").size(18.0));
+    ui.add_space(12.0);
+
+    let state = shared_state.read();
+    let recent = &state.runtime.recent_screenshots;
+    if recent.is_empty() {
+        let text = match state.config.overlay.screenshot_hotkey {
+            Some(ref hotkey) => format!(
+                "No screenshots yet. Press {} or Take Screenshot to save the next frame.",
+                hotkey
+            ),
+            None => "No screenshots yet. Take Screenshot saves the next frame.".to_string(),
+        };
+        ui.label(
+            RichText::new(text)
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    for path in recent {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let url = format!("file:///{}", path.display().to_string().replace('\\', "/"));
+        ui.hyperlink_to(name, url)
+            .on_hover_text(path.display().to_string());
+    }
+}
+
+/// Render the chip showing the laptop runs on battery
+fn render_power_chip(ui: &mut egui::Ui, power: PowerStatus) {
+    let color = if power.power_saving {
+        ThemeColors::ACCENT_WARNING
+    } else {
+        ThemeColors::TEXT_SECONDARY
+    };
+    let mut text = match power.battery_percent {
+        Some(percent) => format!("On battery {}%", percent),
+        None => "On battery".to_string(),
+    };
+    if power.power_saving {
+        text.push_str(" · Low power");
+    }
+
+    egui::Frame::none()
+        .fill(color_with_alpha(color, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(10.0))
+        .inner_margin(egui::Margin::symmetric(10.0, 4.0))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).size(12.0).color(color));
+        })
+        .response
+        .on_hover_text(if power.power_saving {
+            "Capture frame rate, zone OCR and screen recognition are reduced to save \
+             battery. Change this in Settings > Performance."
+        } else {
+            "The low-power preset is off in Settings > Performance."
+        });
+}
+
+/// Render the notice that the game in front hides the overlay, with the
+/// option to send important tips as Windows notifications meanwhile
+///
+/// In second monitor mode it says where the tips went instead.
+fn render_exclusive_fullscreen_banner(
+    ui: &mut egui::Ui,
+    state: &mut HomeViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let (routes, tips_monitor) = {
+        let shared = shared_state.read();
+        (
+            shared.config.overlay.tip_fallback,
+            shared.runtime.fullscreen_game.and_then(|g| g.tips_monitor),
+        )
+    };
+    if let Some(index) = tips_monitor {
+        render_second_monitor_banner(ui, index);
+        return;
+    }
+    let toasts =
+        [routes.low, routes.medium, routes.high, routes.critical].contains(&TipFallback::Toast);
+
+    egui::Frame::none()
+        .fill(color_with_alpha(ThemeColors::ACCENT_WARNING, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Overlay hidden by exclusive full screen")
+                        .color(ThemeColors::ACCENT_WARNING)
+                        .strong(),
+                );
+                if !toasts {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .button("Send Important Tips as Notifications")
+                            .on_hover_text(
+                                "Show high and critical priority tips as Windows toast \
+                                 notifications while the overlay can't be seen. Change this \
+                                 in Settings > Overlay.",
+                            )
+                            .clicked()
+                        {
+                            let mut shared = shared_state.write();
+                            let routes = &mut shared.config.overlay.tip_fallback;
+                            routes.high = TipFallback::Toast;
+                            routes.critical = TipFallback::Toast;
+                            state.config_changed = true;
+                        }
+                    });
+                }
+            });
+
+            ui.add_space(4.0);
+            let fallback = if toasts {
+                "Meanwhile tips are sent as Windows notifications as set in Settings > Overlay."
+            } else {
+                "Meanwhile tips are only shown once you leave the game."
+            };
+            ui.label(
+                RichText::new(format!(
+                    "The game in front runs in exclusive full screen, which nothing can be \
+                     drawn over. Set its display mode to borderless or windowed full screen \
+                     in the game's video settings to see tips in game. {}",
+                    fallback
+                ))
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+        });
+}
+
+/// Render the notice that tips moved to another monitor while the game runs
+/// in exclusive full screen
+fn render_second_monitor_banner(ui: &mut egui::Ui, monitor_index: usize) {
+    egui::Frame::none()
+        .fill(color_with_alpha(ThemeColors::ACCENT_PRIMARY, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(
+                RichText::new(format!("Tips moved to monitor {}", monitor_index + 1))
+                    .color(ThemeColors::ACCENT_PRIMARY)
+                    .strong(),
+            );
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "The game runs in exclusive full screen, which nothing can be drawn over, \
+                     so the overlay shows on another monitor until you leave the game. Set its \
+                     display mode to borderless or windowed full screen to see tips in game.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+        });
+}
+
+/// Render the chip showing subsystems are turned off
+fn render_features_chip(ui: &mut egui::Ui, disabled: &[&str]) {
+    let color = ThemeColors::TEXT_SECONDARY;
+    egui::Frame::none()
+        .fill(color_with_alpha(color, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(10.0))
+        .inner_margin(egui::Margin::symmetric(10.0, 4.0))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(format!("{} off", disabled.len()))
+                    .size(12.0)
+                    .color(color),
+            );
+        })
+        .response
+        .on_hover_text(format!(
+            "Turned off in Settings > Features: {}",
+            disabled.join(", ")
+        ));
+}
+
+/// Render the chip showing analysis is paused while the player is away
+fn render_afk_chip(ui: &mut egui::Ui) {
+    let color = ThemeColors::TEXT_SECONDARY;
+    egui::Frame::none()
+        .fill(color_with_alpha(color, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(10.0))
+        .inner_margin(egui::Margin::symmetric(10.0, 4.0))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new("Analysis paused (AFK)")
+                    .size(12.0)
+                    .color(color),
+            );
+        })
+        .response
+        .on_hover_text(
+            "No input and no change on screen for a while, so zone OCR and screen \
+             recognition are paused. They resume on any input or change. Change this in \
+             Settings > Performance.",
+        );
+}
+
+/// Render the safe mode notice with the option to keep the current settings
+fn render_safe_mode_banner(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    egui::Frame::none()
+        .fill(color_with_alpha(ThemeColors::ACCENT_WARNING, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Safe Mode")
+                        .color(ThemeColors::ACCENT_WARNING)
+                        .strong(),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button("Save Current Settings")
+                        .on_hover_text(
+                            "Replace config.toml with the current settings. \
+                             The original is kept as config.toml.bak.",
+                        )
+                        .clicked()
+                    {
+                        shared_state.write().runtime.rewrite_config = true;
+                    }
                 });
             });
-    }
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "Started with default settings, no hotkeys and no active profile. \
+                     Fix or delete the problem profile in Profiles, then restart normally. \
+                     Settings changes are not saved unless you save them here.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+        });
 }
@@ -0,0 +1,236 @@
+//! Labeled region list
+//!
+//! Lists the active profile's labeled regions under the Vision preview, with
+//! bulk tools for keeping a long list tidy: multi-select, renaming from a
+//! pattern, merging duplicate labels and deleting labels whose text hasn't
+//! been read for a number of sessions. Each row shows the value currently
+//! read at the region, and can ask for a tip when it changes. Edits are
+//! saved with the profile's zones.
+
+use egui::RichText;
+
+use crate::dashboard::components::remembered_section;
+use crate::dashboard::state::VisionViewState;
+use crate::dashboard::theme::ThemeColors;
+use crate::storage::labels::{duplicate_count, is_stale, merge_duplicates, rename_selected};
+
+/// Render the labeled region list of the active profile
+pub fn render_labeled_regions(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    remembered_section(
+        ui,
+        "vision.labeled_regions",
+        egui::CollapsingHeader::new(
+            RichText::new(format!(
+                "Labeled Regions ({})",
+                view_state.labeled_regions.len()
+            ))
+            .size(14.0),
+        )
+        .id_salt("labeled_regions"),
+        false,
+        |ui| {
+            if view_state.labeled_regions.is_empty() {
+                ui.label(
+                    RichText::new("The active profile has no labeled regions")
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+                return;
+            }
+
+            render_selection_bar(ui, view_state);
+            render_label_list(ui, view_state);
+            ui.add_space(4.0);
+            render_rename(ui, view_state);
+            render_maintenance(ui, view_state);
+        },
+    );
+}
+
+/// Select all or none, and the selection size
+fn render_selection_bar(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    ui.horizontal(|ui| {
+        if ui.small_button("All").clicked() {
+            view_state.selected_labels = (0..view_state.labeled_regions.len()).collect();
+        }
+        if ui.small_button("None").clicked() {
+            view_state.selected_labels.clear();
+        }
+        ui.label(
+            RichText::new(format!("{} selected", view_state.selected_labels.len()))
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    });
+}
+
+/// One row per region: selection, label, matched text, current value and
+/// when it was read
+fn render_label_list(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    egui::ScrollArea::vertical()
+        .id_salt("labeled_region_list")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            for (index, region) in view_state.labeled_regions.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let mut selected = view_state.selected_labels.contains(&index);
+                    if ui.checkbox(&mut selected, "").changed() {
+                        if selected {
+                            view_state.selected_labels.insert(index);
+                        } else {
+                            view_state.selected_labels.remove(&index);
+                        }
+                    }
+                    ui.label(RichText::new(&region.label).strong());
+                    ui.label(
+                        RichText::new(&region.matched_text)
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_SECONDARY),
+                    );
+                    if view_state.relocated_labels.contains(&region.label) {
+                        ui.label(
+                            RichText::new("moved")
+                                .size(11.0)
+                                .color(ThemeColors::ACCENT_WARNING),
+                        )
+                        .on_hover_text(
+                            "The text was found elsewhere on screen and the region followed \
+                             it. The move is in the session log.",
+                        );
+                    }
+                    if let Some(value) = view_state.label_values.get(&region.label) {
+                        ui.label(
+                            RichText::new(format!("now {}", value))
+                                .size(12.0)
+                                .color(ThemeColors::ACCENT_PRIMARY),
+                        );
+                    }
+                    if ui
+                        .toggle_value(&mut region.notify_on_change, "Notify")
+                        .on_hover_text("Show a tip when the value read here changes")
+                        .changed()
+                    {
+                        view_state.labels_dirty = true;
+                    }
+
+                    let read = match (view_state.session_id, region.last_matched_session) {
+                        (Some(current), Some(last)) if current == last => {
+                            "read this session".to_string()
+                        }
+                        (Some(current), Some(last)) => {
+                            format!("last read {} sessions ago", current - last)
+                        }
+                        _ => String::new(),
+                    };
+                    ui.label(
+                        RichText::new(read)
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                });
+            }
+        });
+}
+
+/// Rename or delete the selection
+fn render_rename(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    let has_selection = !view_state.selected_labels.is_empty();
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut view_state.label_rename_pattern)
+                .hint_text("{label} ({text})")
+                .desired_width(160.0),
+        );
+        let can_rename = has_selection && !view_state.label_rename_pattern.trim().is_empty();
+        if ui
+            .add_enabled(can_rename, egui::Button::new("Rename"))
+            .on_hover_text(
+                "Rename the selected labels. {label} is the current label, {text} the \
+                 matched text and {n} the position in the selection.",
+            )
+            .clicked()
+        {
+            let renamed = rename_selected(
+                &mut view_state.labeled_regions,
+                &view_state.selected_labels,
+                &view_state.label_rename_pattern,
+            );
+            if renamed > 0 {
+                view_state.labels_dirty = true;
+            }
+        }
+
+        if ui
+            .add_enabled(has_selection, egui::Button::new("Delete"))
+            .on_hover_text("Delete the selected labels")
+            .clicked()
+        {
+            let selected = std::mem::take(&mut view_state.selected_labels);
+            let mut index = 0;
+            view_state.labeled_regions.retain(|_| {
+                let keep = !selected.contains(&index);
+                index += 1;
+                keep
+            });
+            view_state.labels_dirty = true;
+        }
+    });
+}
+
+/// Merge duplicates and delete labels that stopped matching
+fn render_maintenance(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    let duplicates = duplicate_count(&view_state.labeled_regions);
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                duplicates > 0,
+                egui::Button::new(format!("Merge Duplicates ({})", duplicates)),
+            )
+            .on_hover_text(
+                "Keep one region per label (case and spacing ignored), with the text of \
+                 the most confident one",
+            )
+            .clicked()
+        {
+            merge_duplicates(&mut view_state.labeled_regions);
+            view_state.selected_labels.clear();
+            view_state.labels_dirty = true;
+        }
+    });
+
+    let Some(session_id) = view_state.session_id else {
+        ui.label(
+            RichText::new("Sessions aren't being recorded, so unused labels can't be found")
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    };
+
+    let sessions = view_state.label_stale_sessions;
+    let stale = view_state
+        .labeled_regions
+        .iter()
+        .filter(|region| is_stale(region, session_id, sessions))
+        .count();
+    ui.horizontal(|ui| {
+        ui.label("Not read for");
+        ui.add(
+            egui::DragValue::new(&mut view_state.label_stale_sessions)
+                .range(1..=100)
+                .suffix(" sessions"),
+        );
+        if ui
+            .add_enabled(stale > 0, egui::Button::new(format!("Delete {}", stale)))
+            .on_hover_text("Delete labels whose text hasn't been read for this many sessions")
+            .clicked()
+        {
+            view_state
+                .labeled_regions
+                .retain(|region| !is_stale(region, session_id, sessions));
+            view_state.selected_labels.clear();
+            view_state.labels_dirty = true;
+        }
+    });
+}
@@ -1,7 +1,12 @@
 //! Dashboard views
 
 pub mod capture;
+pub mod checklists;
+pub mod color_triggers;
+pub mod data;
+pub mod goals;
 pub mod home;
+pub mod labeled_regions;
 pub mod overlay;
 pub mod profiles;
 pub mod screens;
@@ -10,6 +15,7 @@ pub mod vision;
 pub mod zone_ocr;
 
 pub use capture::render_capture_view;
+pub use data::render_data_view;
 pub use home::render_home_view;
 pub use overlay::render_overlay_view;
 pub use profiles::render_profiles_view;
@@ -4,11 +4,17 @@ use egui::RichText;
 use parking_lot::RwLock;
 use std::cell::Cell;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::dashboard::components::add_scroll_slider;
+use crate::analysis::Tip;
+use crate::dashboard::components::{add_scroll_slider, remembered_scroll};
 use crate::dashboard::state::OverlayViewState;
-use crate::dashboard::theme::{color_with_alpha, ThemeColors};
-use crate::overlay::OverlayAnchor;
+use crate::dashboard::theme::ThemeColors;
+use crate::overlay::widgets::PriorityStyles;
+use crate::overlay::{
+    anchor_align, draw_checklist, draw_tip_stack, draw_wellness_nag, OverlayAnchor,
+    OverlaySnapshot, CHECKLIST_PLACEMENT, WELLNESS_NAG_PLACEMENT,
+};
 use crate::shared::SharedAppState;
 
 /// Render the overlay view
@@ -27,7 +33,19 @@ pub fn render_overlay_view(
 
     ui.add_space(24.0);
 
-    egui::ScrollArea::vertical().show(ui, |ui| {
+    if !shared_state.read().config.features.overlay {
+        ui.label(
+            RichText::new(
+                "The overlay is turned off in Settings > Features. Tips go where the fallback \
+                 for their priority sends them.",
+            )
+            .size(12.0)
+            .color(ThemeColors::ACCENT_WARNING),
+        );
+        ui.add_space(16.0);
+    }
+
+    remembered_scroll(ui, "overlay.page", egui::ScrollArea::vertical(), |ui| {
         // Use columns for side-by-side layout that stacks on narrow screens
         let available_width = ui.available_width();
         let use_two_columns = available_width > 700.0;
@@ -233,6 +251,90 @@ fn render_settings_column(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedApp
 
             ui.add_space(8.0);
 
+            ui.horizontal(|ui| {
+                ui.label("Read screen text:");
+                ui.add_space(8.0);
+                let hotkey_text = state
+                    .config
+                    .overlay
+                    .read_screen_hotkey
+                    .as_deref()
+                    .unwrap_or("Not set");
+                ui.label(RichText::new(hotkey_text).strong().monospace());
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Press this key to OCR the whole screen into a copyable text panel")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Tick checklist item:");
+                ui.add_space(8.0);
+                let hotkey_text = state
+                    .config
+                    .overlay
+                    .checklist_hotkey
+                    .as_deref()
+                    .unwrap_or("Not set");
+                ui.label(RichText::new(hotkey_text).strong().monospace());
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Press this key to check the next item of the pinned checklist")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Save replay:");
+                ui.add_space(8.0);
+                let hotkey_text = state
+                    .config
+                    .overlay
+                    .replay_hotkey
+                    .as_deref()
+                    .unwrap_or("Not set");
+                ui.label(RichText::new(hotkey_text).strong().monospace());
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Press this key to save the last seconds of capture")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Screenshot:");
+                ui.add_space(8.0);
+                let hotkey_text = state
+                    .config
+                    .overlay
+                    .screenshot_hotkey
+                    .as_deref()
+                    .unwrap_or("Not set");
+                ui.label(RichText::new(hotkey_text).strong().monospace());
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Press this key to save the next captured frame as a PNG")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(8.0);
+
             // Visibility status indicator
             ui.horizontal(|ui| {
                 ui.label("Currently visible:");
@@ -281,113 +383,344 @@ fn render_preview_column(
             ui.heading(RichText::new("Preview").size(16.0));
             ui.add_space(12.0);
 
-            // Monitor preview area
-            egui::Frame::none()
-                .fill(ThemeColors::BG_DARK)
-                .rounding(egui::Rounding::same(6.0))
-                .stroke(egui::Stroke::new(1.0, ThemeColors::BORDER))
-                .show(ui, |ui| {
-                    let available_size = ui.available_size();
-                    let preview_size = egui::vec2(available_size.x.min(280.0), 160.0);
-                    ui.set_min_size(preview_size);
-
-                    // Draw a visual representation of overlay position
-                    let rect = ui.available_rect_before_wrap();
-                    let painter = ui.painter();
-
-                    // Draw "monitor" outline
-                    painter.rect_stroke(
-                        rect.shrink(4.0),
-                        egui::Rounding::same(4.0),
-                        egui::Stroke::new(1.0, ThemeColors::TEXT_MUTED),
-                    );
-
-                    // Draw overlay position indicator
-                    let state = shared_state.read();
-                    let anchor = state.overlay_config.anchor;
-                    let offset = state.overlay_config.offset;
-
-                    let tip_size = egui::vec2(80.0, 30.0);
-                    let scale = 0.15; // Scale offset for preview
-
-                    let tip_pos = match anchor {
-                        OverlayAnchor::TopLeft => {
-                            rect.left_top()
-                                + egui::vec2(
-                                    8.0 + offset.0 as f32 * scale,
-                                    8.0 + offset.1 as f32 * scale,
-                                )
-                        }
-                        OverlayAnchor::TopRight => {
-                            rect.right_top()
-                                + egui::vec2(
-                                    -8.0 - tip_size.x - offset.0 as f32 * scale,
-                                    8.0 + offset.1 as f32 * scale,
-                                )
-                        }
-                        OverlayAnchor::BottomLeft => {
-                            rect.left_bottom()
-                                + egui::vec2(
-                                    8.0 + offset.0 as f32 * scale,
-                                    -8.0 - tip_size.y - offset.1 as f32 * scale,
-                                )
-                        }
-                        OverlayAnchor::BottomRight => {
-                            rect.right_bottom()
-                                + egui::vec2(
-                                    -8.0 - tip_size.x - offset.0 as f32 * scale,
-                                    -8.0 - tip_size.y - offset.1 as f32 * scale,
-                                )
-                        }
-                    };
-
-                    let tip_rect = egui::Rect::from_min_size(tip_pos, tip_size);
-                    let opacity = state.overlay_config.opacity;
-
-                    let alpha = (opacity * 255.0) as u8;
-                    painter.rect_filled(
-                        tip_rect,
-                        egui::Rounding::same(4.0),
-                        color_with_alpha(ThemeColors::ACCENT_PRIMARY, alpha),
-                    );
-
-                    painter.text(
-                        tip_rect.center(),
-                        egui::Align2::CENTER_CENTER,
-                        "Tip",
-                        egui::FontId::proportional(10.0),
-                        color_with_alpha(egui::Color32::WHITE, alpha),
-                    );
-                });
+            render_live_preview(ui, view_state, shared_state);
 
             ui.add_space(16.0);
 
-            // Test tip section
-            ui.heading(RichText::new("Test Tip").size(14.0));
-            ui.add_space(8.0);
+            render_tip_designer(ui, view_state, shared_state);
+        });
+}
 
-            ui.label("Message:");
-            ui.text_edit_singleline(&mut view_state.preview_tip_text);
+/// Symbols a designed tip can start with
+const TIP_ICONS: [&str; 6] = ["⚠", "ℹ", "★", "✔", "❤", "⏰"];
 
-            ui.add_space(4.0);
-            ui.label("Priority:");
-            let mut priority = view_state.preview_tip_priority as f32;
-            add_scroll_slider(ui, &mut priority, 0.0..=100.0, Some(5.0), None, None);
-            view_state.preview_tip_priority = priority as u32;
+/// Compose a test tip, send it once or again and again, and keep favorites
+fn render_tip_designer(
+    ui: &mut egui::Ui,
+    view_state: &mut OverlayViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.heading(RichText::new("Tip Designer").size(14.0));
+    ui.add_space(8.0);
 
-            ui.add_space(8.0);
+    let design = &mut view_state.tip_design;
+    ui.label("Message:");
+    ui.add(egui::TextEdit::singleline(&mut design.message).hint_text("Buy boots at {gold} gold"))
+        .on_hover_text("{name} is replaced by the profile variable `name`");
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label("Icon:");
+        ui.selectable_value(&mut design.icon, String::new(), "None");
+        for icon in TIP_ICONS {
+            ui.selectable_value(&mut design.icon, icon.to_string(), icon);
+        }
+    });
 
-            if ui.button("Send Test Tip").clicked() {
-                let mut state = shared_state.write();
-                state.runtime.send_test_tip = true;
+    ui.add_space(4.0);
+    ui.label("Priority:");
+    let mut priority = design.priority as f32;
+    add_scroll_slider(ui, &mut priority, 0.0..=100.0, Some(5.0), None, None);
+    design.priority = priority as u32;
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label("Duration:");
+        let mut until_dismissed = design.duration_ms.is_none();
+        if ui
+            .checkbox(&mut until_dismissed, "Until dismissed")
+            .changed()
+        {
+            design.duration_ms = (!until_dismissed).then_some(5000);
+        }
+    });
+    if let Some(ref mut duration_ms) = design.duration_ms {
+        let mut seconds = *duration_ms as f32 / 1000.0;
+        add_scroll_slider(ui, &mut seconds, 1.0..=30.0, Some(1.0), Some(" s"), None);
+        *duration_ms = (seconds * 1000.0) as u64;
+    }
 
-                let message = if view_state.preview_tip_text.is_empty() {
-                    "This is a test tip from GamersToolKit!".to_string()
-                } else {
-                    view_state.preview_tip_text.clone()
-                };
+    ui.add_space(4.0);
+    ui.checkbox(&mut design.play_sound, "Play sound");
 
-                state.runtime.test_tip_message = Some((message, view_state.preview_tip_priority));
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        if ui.button("Send Once").clicked() {
+            shared_state.write().runtime.send_test_tip = Some(view_state.tip_design.clone());
+        }
+        ui.add_space(8.0);
+        ui.checkbox(&mut view_state.repeat_test_tip, "Repeat every")
+            .on_hover_text("Keep sending the tip, e.g. for a demo");
+        ui.add(
+            egui::DragValue::new(&mut view_state.repeat_interval_secs)
+                .range(2..=600)
+                .suffix(" s"),
+        );
+    });
+
+    ui.add_space(12.0);
+    render_tip_favorites(ui, view_state, shared_state);
+}
+
+/// Saved tips: load one into the designer, save the design, delete
+fn render_tip_favorites(
+    ui: &mut egui::Ui,
+    view_state: &mut OverlayViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.label(RichText::new("Favorites").strong());
+    let mut state = shared_state.write();
+    let favorites = &mut state.config.overlay.tip_favorites;
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut view_state.tip_design.name)
+                .hint_text("Name")
+                .desired_width(140.0),
+        );
+        let name = view_state.tip_design.name.trim().to_string();
+        if ui
+            .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+            .on_hover_text("Save the tip as a favorite (replacing one of the same name)")
+            .clicked()
+        {
+            let design = crate::config::TipDesign {
+                name: name.clone(),
+                ..view_state.tip_design.clone()
+            };
+            match favorites.iter_mut().find(|f| f.name == name) {
+                Some(favorite) => *favorite = design,
+                None => favorites.push(design),
+            }
+            view_state.favorites_changed = true;
+        }
+    });
+
+    if favorites.is_empty() {
+        ui.label(
+            RichText::new("No favorites yet")
+                .size(11.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    }
+
+    let mut delete = None;
+    for (index, favorite) in favorites.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui
+                .button(&favorite.name)
+                .on_hover_text(favorite.full_message())
+                .clicked()
+            {
+                view_state.tip_design = favorite.clone();
+            }
+            if ui.small_button("Delete").clicked() {
+                delete = Some(index);
             }
         });
+    }
+    if let Some(index) = delete {
+        favorites.remove(index);
+        view_state.favorites_changed = true;
+    }
+}
+
+/// How often the screenshot follows the capture
+const SCREENSHOT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Render the overlay over a screenshot of the game, as the player sees it
+///
+/// Shows what the overlay shows right now; while it isn't running, the test
+/// tip stands in so styles and anchors can still be tuned.
+fn render_live_preview(
+    ui: &mut egui::Ui,
+    view_state: &mut OverlayViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let (config, frame) = {
+        let state = shared_state.read();
+        (
+            state.overlay_config.clone(),
+            state.runtime.last_captured_frame.clone(),
+        )
+    };
+
+    let take_screenshot = ui
+        .horizontal(|ui| {
+            ui.checkbox(&mut view_state.live_screenshot, "Follow capture")
+                .on_hover_text("Keep the screenshot up to date while capturing");
+            ui.add_enabled(frame.is_some(), egui::Button::new("Take Screenshot"))
+                .clicked()
+        })
+        .inner;
+    if let Some(frame) = frame {
+        let due = view_state.screenshot.is_none()
+            || (view_state.live_screenshot
+                && view_state
+                    .screenshot_taken
+                    .map_or(true, |taken| taken.elapsed() >= SCREENSHOT_INTERVAL));
+        if view_state.screenshot_taken != Some(frame.timestamp) && (take_screenshot || due) {
+            let size = [frame.width as usize, frame.height as usize];
+            let image = egui::ColorImage::from_rgba_unmultiplied(size, &frame.rgba());
+            match view_state.screenshot {
+                Some(ref mut texture) if texture.size() == size => {
+                    texture.set(image, egui::TextureOptions::LINEAR);
+                }
+                _ => {
+                    view_state.screenshot = Some(ui.ctx().load_texture(
+                        "overlay_preview",
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    ));
+                }
+            }
+            view_state.screenshot_taken = Some(frame.timestamp);
+        }
+    }
+    ui.add_space(8.0);
+
+    // The screenshot stands for the overlay's monitor
+    let screen_size = view_state
+        .screenshot
+        .as_ref()
+        .map_or(egui::vec2(1920.0, 1080.0), |texture| texture.size_vec2());
+    let scale = ui.available_width() / screen_size.x;
+    let (screen, _) = ui.allocate_exact_size(screen_size * scale, egui::Sense::hover());
+    let painter = ui.painter_at(screen);
+    match view_state.screenshot {
+        Some(ref texture) => {
+            painter.image(
+                texture.id(),
+                screen,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        None => {
+            painter.rect_filled(screen, egui::Rounding::same(4.0), ThemeColors::BG_DARK);
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "Start capture to preview over the game",
+                egui::FontId::proportional(12.0),
+                ThemeColors::TEXT_MUTED,
+            );
+        }
+    }
+    painter.rect_stroke(
+        screen,
+        egui::Rounding::same(4.0),
+        egui::Stroke::new(1.0, ThemeColors::BORDER),
+    );
+
+    let sample;
+    let snapshot = match view_state.snapshot {
+        Some(ref snapshot) => snapshot,
+        None => {
+            sample = OverlaySnapshot {
+                tips: vec![test_tip(view_state)],
+                ..Default::default()
+            };
+            &sample
+        }
+    };
+
+    // Same order and rules as the overlay itself
+    if config.enabled {
+        if let Some(ref message) = snapshot.wellness_nag {
+            place_scaled(
+                ui,
+                screen,
+                "preview_nag",
+                WELLNESS_NAG_PLACEMENT,
+                scale,
+                |ui| {
+                    draw_wellness_nag(ui, message, scale);
+                },
+            );
+        }
+    }
+    if config.enabled && config.visible {
+        if let Some(ref checklist) = snapshot.checklist {
+            place_scaled(
+                ui,
+                screen,
+                "preview_checklist",
+                CHECKLIST_PLACEMENT,
+                scale,
+                |ui| draw_checklist(ui, checklist, scale),
+            );
+        }
+        if !snapshot.tips.is_empty() {
+            let offset = egui::vec2(config.offset.0 as f32, config.offset.1 as f32);
+            let placement = (anchor_align(config.anchor), offset);
+            place_scaled(ui, screen, "preview_tips", placement, scale, |ui| {
+                draw_tip_stack(
+                    ui,
+                    &snapshot.tips,
+                    &config,
+                    &PriorityStyles::default(),
+                    scale,
+                );
+            });
+        }
+    }
+
+    let status = match view_state.snapshot {
+        _ if !config.enabled => "Overlay disabled".to_string(),
+        _ if !config.visible => "Overlay hidden (toggle hotkey)".to_string(),
+        None => "Overlay not running: showing the designed tip".to_string(),
+        Some(ref snapshot) => format!("{} tip(s) on the overlay", snapshot.tips.len()),
+    };
+    ui.add_space(4.0);
+    ui.label(
+        RichText::new(status)
+            .size(11.0)
+            .color(ThemeColors::TEXT_MUTED),
+    );
+    ui.ctx().request_repaint_after(Duration::from_millis(250));
+}
+
+/// The designed tip, as the preview shows it
+fn test_tip(view_state: &OverlayViewState) -> Tip {
+    let design = &view_state.tip_design;
+    Tip {
+        id: "test_tip".to_string(),
+        message: design.full_message(),
+        priority: design.priority,
+        duration_ms: design.duration_ms,
+        play_sound: design.play_sound,
+        ..Default::default()
+    }
+}
+
+/// Lay out overlay content where an `egui::Area` anchored this way would put
+/// it on a screen shrunk by `scale`
+///
+/// Like an area, the content is placed using its size from the previous frame.
+fn place_scaled(
+    ui: &mut egui::Ui,
+    screen: egui::Rect,
+    id_salt: &str,
+    (align, offset): (egui::Align2, egui::Vec2),
+    scale: f32,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    let id = ui.id().with(id_salt);
+    let size: egui::Vec2 = ui.ctx().data(|d| d.get_temp(id)).unwrap_or_default();
+    let min = align.align_size_within_rect(size, screen).min + offset * scale;
+    let mut child = ui.new_child(
+        egui::UiBuilder::new()
+            .id_salt(id_salt)
+            .max_rect(egui::Rect::from_min_size(min, screen.size())),
+    );
+    child.set_clip_rect(screen.intersect(ui.clip_rect()));
+    add_contents(&mut child);
+
+    let used = child.min_rect().size();
+    if used != size {
+        ui.ctx().data_mut(|d| d.insert_temp(id, used));
+        ui.ctx().request_repaint();
+    }
 }
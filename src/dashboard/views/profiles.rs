@@ -3,11 +3,24 @@
 use egui::RichText;
 use parking_lot::RwLock;
 use std::sync::Arc;
+use uuid::Uuid;
 
+use crate::config::{CompositeTarget, ExtraCaptureSource};
+use crate::dashboard::components::{remembered_scroll, remembered_section};
 use crate::dashboard::state::{ProfileAction, ProfilesViewState};
 use crate::dashboard::theme::{color_with_alpha, ThemeColors};
-use crate::shared::SharedAppState;
-use crate::storage::profiles::GameProfile;
+use crate::dashboard::views::checklists::{render_condition_target, ConditionTargets};
+use crate::shared::{AppError, SharedAppState};
+use crate::storage::backups::list_backups;
+use crate::storage::profiles::{
+    ChecklistCondition, GamePhase, GameProfile, PhaseStart, ProfileVariable, RecognitionDictionary,
+    SubstitutionRule, MIN_SNAPSHOT_INTERVAL_MS,
+};
+use crate::storage::rule_library::{
+    effective_rules, parameter_name, LibraryRule, RuleParameter, RuleReference,
+};
+use crate::storage::share::SharedItem;
+use crate::storage::starter_packs::STARTER_PACKS;
 
 /// Render the profiles view
 pub fn render_profiles_view(
@@ -50,6 +63,21 @@ pub fn render_profiles_view(
             view_state.new_profile_name.clear();
             view_state.new_profile_executable.clear();
         }
+
+        ui.add_space(8.0);
+
+        if ui.button("Starter Packs").clicked() {
+            view_state.show_starter_packs = true;
+        }
+
+        ui.add_space(8.0);
+
+        if ui.button("Restore…").clicked() {
+            view_state.backups = crate::storage::get_backups_dir()
+                .map(|dir| list_backups(&dir))
+                .unwrap_or_default();
+            view_state.show_backups = true;
+        }
     });
 
     ui.add_space(16.0);
@@ -86,9 +114,11 @@ pub fn render_profiles_view(
                                 );
                                 ui.add_space(8.0);
                                 ui.label(
-                                    RichText::new("Create your first game profile to get started")
-                                        .size(12.0)
-                                        .color(ThemeColors::TEXT_MUTED),
+                                    RichText::new(
+                                        "Create your first game profile or install a starter pack",
+                                    )
+                                    .size(12.0)
+                                    .color(ThemeColors::TEXT_MUTED),
                                 );
                             });
                         } else {
@@ -99,7 +129,7 @@ pub fn render_profiles_view(
                         }
                     });
                 } else {
-                    egui::ScrollArea::vertical().show(ui, |ui| {
+                    remembered_scroll(ui, "profiles.list", egui::ScrollArea::vertical(), |ui| {
                         for profile in filtered_profiles {
                             let is_selected =
                                 view_state.selected_profile_id.as_ref() == Some(&profile.id);
@@ -146,6 +176,16 @@ pub fn render_profiles_view(
     if view_state.show_delete_confirm {
         render_delete_confirm_dialog(ui, view_state, shared_state);
     }
+
+    // Starter pack picker
+    if view_state.show_starter_packs {
+        render_starter_packs_dialog(ui, view_state, shared_state);
+    }
+
+    // Backup restore picker
+    if view_state.show_backups {
+        render_backups_dialog(ui, view_state, shared_state);
+    }
 }
 
 /// Render a profile card in the list
@@ -187,7 +227,7 @@ fn render_profile_card(
                     );
 
                     let exe_count = profile.executables.len();
-                    let rule_count = profile.rules.len();
+                    let rule_count = profile.rules.len() + profile.library_rules.len();
                     ui.label(
                         RichText::new(format!("{} exe(s), {} rule(s)", exe_count, rule_count))
                             .size(11.0)
@@ -203,6 +243,163 @@ fn render_profile_card(
     }
 }
 
+/// Render the auto-start options of a profile
+fn render_auto_start(ui: &mut egui::Ui, profile: &GameProfile, view_state: &mut ProfilesViewState) {
+    let mut auto_start = profile.auto_start;
+    ui.add_enabled_ui(!profile.executables.is_empty(), |ui| {
+        ui.checkbox(
+            &mut auto_start.capture,
+            "Start capture when the game launches",
+        )
+        .on_disabled_hover_text("Add the game's executable first");
+        ui.add_enabled_ui(auto_start.capture, |ui| {
+            ui.checkbox(&mut auto_start.overlay, "Also start the overlay");
+        });
+    });
+    ui.label(
+        RichText::new("Activates this profile and captures the game's window")
+            .size(11.0)
+            .color(ThemeColors::TEXT_MUTED),
+    );
+
+    if auto_start != profile.auto_start {
+        view_state.pending_action = Some(ProfileAction::UpdateAutoStart(
+            profile.id.clone(),
+            auto_start,
+        ));
+    }
+}
+
+/// Render the snapshot capture options of a profile
+fn render_snapshot_capture(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+) {
+    let mut snapshot = profile.snapshot_capture;
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut snapshot.enabled, "Snapshot mode: capture every");
+        ui.add_enabled(
+            snapshot.enabled,
+            egui::DragValue::new(&mut snapshot.interval_ms)
+                .range(MIN_SNAPSHOT_INTERVAL_MS..=60_000)
+                .speed(50)
+                .suffix(" ms"),
+        );
+    });
+    ui.label(
+        RichText::new(
+            "For turn-based and menu-heavy games: one frame at a time instead of a stream",
+        )
+        .size(11.0)
+        .color(ThemeColors::TEXT_MUTED),
+    );
+
+    if snapshot != profile.snapshot_capture {
+        view_state.pending_action = Some(ProfileAction::UpdateSnapshotCapture(
+            profile.id.clone(),
+            snapshot,
+        ));
+    }
+}
+
+/// Edit the detached tool windows of the game captured for a profile
+fn render_secondary_windows(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+) {
+    let draft = reset_draft(
+        &mut view_state.secondary_windows_draft,
+        &profile.id,
+        &profile.secondary_windows,
+    );
+
+    ui.label(RichText::new("Secondary Windows").strong());
+    ui.label(
+        RichText::new(
+            "Tool windows the game opens next to its main one (a map, a chat). Each is \
+             captured while the profile is active, and zones read it when it's picked as \
+             their capture source.",
+        )
+        .size(11.0)
+        .color(ThemeColors::TEXT_MUTED),
+    );
+
+    let mut remove = None;
+    for (i, window) in draft.iter_mut().enumerate() {
+        ui.push_id(("secondary_window", i), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut window.id)
+                        .hint_text("Name")
+                        .desired_width(80.0),
+                )
+                .on_hover_text("Name zones pick the window by");
+                if let CompositeTarget::Window(ref mut title) = window.target {
+                    ui.add(
+                        egui::TextEdit::singleline(title)
+                            .hint_text("Window title")
+                            .desired_width(160.0),
+                    )
+                    .on_hover_text("Title of the window, or part of it");
+                }
+                ui.add(
+                    egui::DragValue::new(&mut window.max_fps)
+                        .range(1..=60)
+                        .suffix(" fps"),
+                );
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+        });
+    }
+    if let Some(i) = remove {
+        draft.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        if ui.small_button("Add Window").clicked() {
+            let id = (1..)
+                .map(|n| format!("window{}", n))
+                .find(|id| !draft.iter().any(|w| &w.id == id))
+                .unwrap_or_default();
+            draft.push(ExtraCaptureSource {
+                id,
+                target: CompositeTarget::Window(String::new()),
+                max_fps: 5,
+            });
+        }
+
+        let cleaned: Vec<ExtraCaptureSource> = draft
+            .iter()
+            .filter(|w| !w.id.trim().is_empty() && !w.target.label().trim().is_empty())
+            .map(|w| ExtraCaptureSource {
+                id: w.id.trim().to_string(),
+                ..w.clone()
+            })
+            .collect();
+        let has_changes = *draft != profile.secondary_windows;
+        if ui
+            .add_enabled(has_changes, egui::Button::new("Save"))
+            .clicked()
+        {
+            *draft = cleaned.clone();
+            view_state.pending_action = Some(ProfileAction::UpdateSecondaryWindows(
+                profile.id.clone(),
+                cleaned,
+            ));
+        }
+        if ui
+            .add_enabled(has_changes, egui::Button::new("Revert"))
+            .clicked()
+        {
+            *draft = profile.secondary_windows.clone();
+        }
+    });
+}
+
 /// Render profile details panel
 fn render_profile_details(
     ui: &mut egui::Ui,
@@ -237,6 +434,13 @@ fn render_profile_details(
         );
     }
 
+    ui.add_space(8.0);
+    render_auto_start(ui, profile, view_state);
+    ui.add_space(8.0);
+    render_snapshot_capture(ui, profile, view_state);
+    ui.add_space(8.0);
+    render_secondary_windows(ui, profile, view_state);
+
     ui.add_space(16.0);
 
     // OCR Regions
@@ -253,7 +457,11 @@ fn render_profile_details(
     // Rules
     ui.label(RichText::new("Rules").strong());
     ui.add_space(4.0);
-    for rule in &profile.rules {
+    let own_rules = profile.rules.len();
+    for (i, rule) in effective_rules(profile, &state.rule_library)
+        .iter()
+        .enumerate()
+    {
         ui.horizontal(|ui| {
             let status_color = if rule.enabled {
                 ThemeColors::ACCENT_SUCCESS
@@ -266,9 +474,31 @@ fn render_profile_details(
                     .size(10.0),
             );
             ui.label(&rule.name);
+            if i >= own_rules {
+                ui.label(
+                    RichText::new("library")
+                        .size(11.0)
+                        .color(ThemeColors::ACCENT_PRIMARY),
+                );
+            } else if state.rule_library.iter().any(|r| r.script == rule.script) {
+                ui.label(
+                    RichText::new("in library")
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            } else if ui
+                .small_button("Add to Library")
+                .on_hover_text("Copy the rule to the rules library so other profiles can use it")
+                .clicked()
+            {
+                view_state.pending_action = Some(ProfileAction::SaveLibraryRule(LibraryRule {
+                    id: Uuid::new_v4().to_string(),
+                    ..LibraryRule::from_rule(rule)
+                }));
+            }
         });
     }
-    if profile.rules.is_empty() {
+    if profile.rules.is_empty() && profile.library_rules.is_empty() {
         ui.label(
             RichText::new("No rules defined")
                 .size(12.0)
@@ -276,6 +506,31 @@ fn render_profile_details(
         );
     }
 
+    ui.add_space(8.0);
+
+    // Rules shared between profiles
+    render_library_section(ui, profile, view_state, state);
+
+    ui.add_space(16.0);
+
+    // User-entered variables
+    render_variables_section(ui, profile, view_state);
+
+    ui.add_space(8.0);
+
+    // Match phases
+    render_phases_section(ui, profile, view_state, state);
+
+    ui.add_space(8.0);
+
+    // OCR corrections
+    render_dictionary_section(ui, profile, view_state);
+
+    ui.add_space(8.0);
+
+    // Share codes
+    render_share_section(ui, profile, view_state);
+
     ui.add_space(24.0);
 
     // Action buttons
@@ -335,121 +590,1303 @@ fn render_no_selection(ui: &mut egui::Ui) {
     });
 }
 
-/// Render the create profile dialog
-fn render_create_dialog(
+/// Render the editable variables of a profile
+fn render_variables_section(
     ui: &mut egui::Ui,
+    profile: &GameProfile,
     view_state: &mut ProfilesViewState,
-    _shared_state: &Arc<RwLock<SharedAppState>>,
 ) {
-    egui::Window::new("Create New Profile")
-        .collapsible(false)
-        .resizable(false)
-        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
-        .show(ui.ctx(), |ui| {
-            ui.set_min_width(300.0);
-
-            ui.horizontal(|ui| {
-                ui.label("Profile Name:");
-                ui.text_edit_singleline(&mut view_state.new_profile_name);
-            });
-
-            ui.add_space(8.0);
+    remembered_section(
+        ui,
+        "profiles.variables",
+        egui::CollapsingHeader::new(RichText::new("Variables").strong())
+            .id_salt(("profile_variables", &profile.id)),
+        false,
+        |ui| {
+            let draft = reset_draft(
+                &mut view_state.variables_draft,
+                &profile.id,
+                &profile.variables,
+            );
 
-            ui.horizontal(|ui| {
-                ui.label("Executable:");
-                ui.text_edit_singleline(&mut view_state.new_profile_executable);
-            });
+            ui.label(
+                RichText::new(
+                    "Facts the screen never shows, such as your character level. Rules \
+                     read them by name and tip messages fill in {name}.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+            );
+            ui.add_space(4.0);
 
-            ui.add_space(16.0);
+            let mut to_remove = None;
+            egui::Grid::new(("profile_variables_grid", &profile.id))
+                .num_columns(4)
+                .spacing(egui::vec2(8.0, 4.0))
+                .show(ui, |ui| {
+                    for header in ["Name", "Value", "Choices"] {
+                        ui.label(
+                            RichText::new(header)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    }
+                    ui.end_row();
 
-            ui.horizontal(|ui| {
-                if ui.button("Cancel").clicked() {
-                    view_state.show_create_dialog = false;
-                }
+                    for (i, variable) in draft.iter_mut().enumerate() {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut variable.name)
+                                .hint_text("level")
+                                .desired_width(110.0),
+                        );
 
-                ui.add_space(8.0);
+                        let choices: Vec<String> = variable
+                            .choices
+                            .iter()
+                            .map(|c| c.trim().to_string())
+                            .filter(|c| !c.is_empty())
+                            .collect();
+                        if choices.is_empty() {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut variable.value)
+                                    .desired_width(110.0),
+                            );
+                        } else {
+                            egui::ComboBox::from_id_salt(("variable_value", &profile.id, i))
+                                .width(110.0)
+                                .selected_text(variable.value.as_str())
+                                .show_ui(ui, |ui| {
+                                    for choice in choices {
+                                        ui.selectable_value(
+                                            &mut variable.value,
+                                            choice.clone(),
+                                            choice,
+                                        );
+                                    }
+                                });
+                        }
 
-                let can_create = !view_state.new_profile_name.is_empty();
-                ui.add_enabled_ui(can_create, |ui| {
-                    if ui
-                        .add(
-                            egui::Button::new(RichText::new("Create").color(egui::Color32::WHITE))
-                                .fill(ThemeColors::ACCENT_PRIMARY),
-                        )
-                        .clicked()
-                    {
-                        let new_profile = GameProfile {
-                            id: format!(
-                                "profile_{}",
-                                std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs()
-                            ),
-                            name: view_state.new_profile_name.clone(),
-                            executables: if view_state.new_profile_executable.is_empty() {
-                                vec![]
-                            } else {
-                                vec![view_state.new_profile_executable.clone()]
-                            },
-                            version: "1.0.0".to_string(),
-                            ocr_regions: vec![],
-                            templates: vec![],
-                            rules: vec![],
-                            labeled_regions: vec![],
-                            screens: vec![],
-                            screen_recognition_enabled: false,
-                            screen_check_interval_ms: 500,
-                        };
+                        let mut choices = variable.choices.join(",");
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut choices)
+                                    .hint_text("Easy, Normal, Hard")
+                                    .desired_width(140.0),
+                            )
+                            .on_hover_text("Comma-separated values to pick from (empty = any text)")
+                            .changed()
+                        {
+                            variable.choices = choices.split(',').map(str::to_string).collect();
+                        }
 
-                        // Queue profile creation action (will be saved to disk by DashboardApp)
-                        view_state.selected_profile_id = Some(new_profile.id.clone());
-                        view_state.pending_action = Some(ProfileAction::Create(new_profile));
-                        view_state.show_create_dialog = false;
+                        if ui.small_button("X").clicked() {
+                            to_remove = Some(i);
+                        }
+                        ui.end_row();
                     }
                 });
+            if let Some(i) = to_remove {
+                draft.remove(i);
+            }
+            if ui.small_button("+ Add Variable").clicked() {
+                draft.push(ProfileVariable::default());
+            }
+
+            ui.add_space(8.0);
+
+            let cleaned = cleaned_variables(draft);
+            let has_changes = cleaned != profile.variables;
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Save Variables"))
+                    .clicked()
+                {
+                    *draft = cleaned.clone();
+                    view_state.pending_action =
+                        Some(ProfileAction::UpdateVariables(profile.id.clone(), cleaned));
+                }
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Revert"))
+                    .clicked()
+                {
+                    *draft = profile.variables.clone();
+                }
             });
-        });
+        },
+    );
 }
 
-/// Render the delete confirmation dialog
-fn render_delete_confirm_dialog(
+/// Drop unnamed variables and blank choices, trimming names and choices
+///
+/// Spaces in names become underscores so rules and templates can refer to
+/// them; a later variable with the same name replaces an earlier one.
+fn cleaned_variables(draft: &[ProfileVariable]) -> Vec<ProfileVariable> {
+    let mut cleaned: Vec<ProfileVariable> = Vec::new();
+    for variable in draft {
+        let name = variable
+            .name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_");
+        if name.is_empty() {
+            continue;
+        }
+        let variable = ProfileVariable {
+            name,
+            value: variable.value.trim().to_string(),
+            choices: variable
+                .choices
+                .iter()
+                .map(|choice| choice.trim())
+                .filter(|choice| !choice.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        match cleaned.iter_mut().find(|v| v.name == variable.name) {
+            Some(existing) => *existing = variable,
+            None => cleaned.push(variable),
+        }
+    }
+    cleaned
+}
+
+/// Render the editable game phases of a profile
+fn render_phases_section(
     ui: &mut egui::Ui,
+    profile: &GameProfile,
     view_state: &mut ProfilesViewState,
-    _shared_state: &Arc<RwLock<SharedAppState>>,
+    state: &SharedAppState,
 ) {
-    egui::Window::new("Confirm Delete")
-        .collapsible(false)
-        .resizable(false)
-        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
-        .show(ui.ctx(), |ui| {
-            ui.label("Are you sure you want to delete this profile?");
+    remembered_section(
+        ui,
+        "profiles.phases",
+        egui::CollapsingHeader::new(RichText::new("Game Phases").strong())
+            .id_salt(("profile_phases", &profile.id)),
+        false,
+        |ui| {
+            let draft = reset_draft(&mut view_state.phases_draft, &profile.id, &profile.phases);
+
             ui.label(
-                RichText::new("This action cannot be undone.").color(ThemeColors::ACCENT_WARNING),
+                RichText::new(
+                    "Stages of a match, reached in this order. Zones and checklists can be \
+                     limited to some phases so the overlay only shows what matters.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
             );
+            ui.add_space(4.0);
 
-            ui.add_space(16.0);
+            // Progress of the current match
+            if state.active_profile_id.as_ref() == Some(&profile.id) && !profile.phases.is_empty() {
+                ui.horizontal(|ui| {
+                    let phase = state
+                        .runtime
+                        .current_phase
+                        .as_ref()
+                        .and_then(|id| profile.phases.iter().find(|phase| phase.id == *id))
+                        .map_or("None yet", |phase| phase.name.as_str());
+                    ui.label(RichText::new("Current phase:").color(ThemeColors::TEXT_MUTED));
+                    ui.label(RichText::new(phase).strong());
+                    if let Some(started) = state.runtime.match_started_at {
+                        let secs = started.elapsed().as_secs();
+                        ui.label(
+                            RichText::new(format!("{}:{:02} into the match", secs / 60, secs % 60))
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    }
+                    if ui
+                        .small_button("New Match")
+                        .on_hover_text("Start over from the first phase")
+                        .clicked()
+                    {
+                        view_state.new_match = true;
+                    }
+                });
+                ui.add_space(4.0);
+            }
 
-            ui.horizontal(|ui| {
-                if ui.button("Cancel").clicked() {
-                    view_state.show_delete_confirm = false;
-                }
+            let targets = ConditionTargets::of(profile);
+            let mut to_remove = None;
+            egui::Grid::new(("profile_phases_grid", &profile.id))
+                .num_columns(3)
+                .spacing(egui::vec2(8.0, 4.0))
+                .show(ui, |ui| {
+                    for header in ["Name", "Starts"] {
+                        ui.label(
+                            RichText::new(header)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    }
+                    ui.end_row();
 
-                ui.add_space(8.0);
+                    for (i, phase) in draft.iter_mut().enumerate() {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut phase.name)
+                                .hint_text("Late game")
+                                .desired_width(110.0),
+                        );
+                        ui.horizontal(|ui| {
+                            render_phase_start(ui, phase, &targets);
+                        });
+                        if ui.small_button("X").clicked() {
+                            to_remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = to_remove {
+                draft.remove(i);
+            }
+            if ui.small_button("+ Add Phase").clicked() {
+                draft.push(GamePhase {
+                    id: Uuid::new_v4().to_string(),
+                    name: format!("Phase {}", draft.len() + 1),
+                    start: if draft.is_empty() {
+                        PhaseStart::MatchStart
+                    } else {
+                        PhaseStart::Elapsed { minutes: 15 }
+                    },
+                });
+            }
+
+            ui.add_space(8.0);
 
+            let cleaned = cleaned_phases(draft);
+            let has_changes = cleaned != profile.phases;
+            ui.horizontal(|ui| {
                 if ui
-                    .add(
-                        egui::Button::new(RichText::new("Delete").color(egui::Color32::WHITE))
-                            .fill(ThemeColors::ACCENT_ERROR),
-                    )
+                    .add_enabled(has_changes, egui::Button::new("Save Phases"))
                     .clicked()
                 {
-                    if let Some(profile_id) = view_state.selected_profile_id.take() {
-                        // Queue profile deletion action (will be deleted from disk by DashboardApp)
-                        view_state.pending_action = Some(ProfileAction::Delete(profile_id));
-                    }
-                    view_state.show_delete_confirm = false;
+                    *draft = cleaned.clone();
+                    view_state.pending_action =
+                        Some(ProfileAction::UpdatePhases(profile.id.clone(), cleaned));
+                }
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Revert"))
+                    .clicked()
+                {
+                    *draft = profile.phases.clone();
                 }
             });
-        });
+        },
+    );
+}
+
+/// Edit what moves the match into a phase
+fn render_phase_start(ui: &mut egui::Ui, phase: &mut GamePhase, targets: &ConditionTargets) {
+    let first =
+        |list: &[(String, String)]| list.first().map(|(id, _)| id.clone()).unwrap_or_default();
+    let seen = |condition| PhaseStart::Seen { condition };
+
+    egui::ComboBox::from_id_salt(("phase_start", &phase.id))
+        .width(110.0)
+        .selected_text(match phase.start {
+            PhaseStart::MatchStart => "At match start",
+            PhaseStart::Elapsed { .. } => "After time",
+            PhaseStart::Seen {
+                condition: ChecklistCondition::Screen { .. },
+            } => "Screen shown",
+            PhaseStart::Seen {
+                condition: ChecklistCondition::ColorTrigger { .. },
+            } => "Color trigger",
+            PhaseStart::Seen {
+                condition: ChecklistCondition::ZoneText { .. },
+            } => "Zone reads",
+        })
+        .show_ui(ui, |ui| {
+            let start = &mut phase.start;
+            if ui
+                .selectable_label(*start == PhaseStart::MatchStart, "At match start")
+                .clicked()
+            {
+                *start = PhaseStart::MatchStart;
+            }
+            if ui
+                .selectable_label(matches!(start, PhaseStart::Elapsed { .. }), "After time")
+                .clicked()
+            {
+                *start = PhaseStart::Elapsed { minutes: 15 };
+            }
+            if ui
+                .selectable_label(
+                    matches!(
+                        start,
+                        PhaseStart::Seen {
+                            condition: ChecklistCondition::Screen { .. }
+                        }
+                    ),
+                    "Screen shown",
+                )
+                .clicked()
+            {
+                *start = seen(ChecklistCondition::Screen {
+                    screen_id: first(&targets.screens),
+                });
+            }
+            if ui
+                .selectable_label(
+                    matches!(
+                        start,
+                        PhaseStart::Seen {
+                            condition: ChecklistCondition::ColorTrigger { .. }
+                        }
+                    ),
+                    "Color trigger",
+                )
+                .clicked()
+            {
+                *start = seen(ChecklistCondition::ColorTrigger {
+                    trigger_id: first(&targets.color_triggers),
+                });
+            }
+            if ui
+                .selectable_label(
+                    matches!(
+                        start,
+                        PhaseStart::Seen {
+                            condition: ChecklistCondition::ZoneText { .. }
+                        }
+                    ),
+                    "Zone reads",
+                )
+                .clicked()
+            {
+                *start = seen(ChecklistCondition::ZoneText {
+                    zone_id: first(&targets.zones),
+                    text: String::new(),
+                });
+            }
+        });
+
+    match phase.start {
+        PhaseStart::MatchStart => {}
+        PhaseStart::Elapsed { ref mut minutes } => {
+            ui.add(egui::DragValue::new(minutes).range(0..=180).suffix(" min"))
+                .on_hover_text("Match time, counted from when capture starts or New Match");
+        }
+        PhaseStart::Seen { ref mut condition } => {
+            render_condition_target(ui, &phase.id, condition, targets)
+        }
+    }
+}
+
+/// Drop unnamed phases, trimming names
+fn cleaned_phases(draft: &[GamePhase]) -> Vec<GamePhase> {
+    draft
+        .iter()
+        .filter(|phase| !phase.name.trim().is_empty())
+        .map(|phase| GamePhase {
+            name: phase.name.trim().to_string(),
+            ..phase.clone()
+        })
+        .collect()
+}
+
+/// Render the library rules attached to a profile and the library itself
+fn render_library_section(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+    state: &SharedAppState,
+) {
+    remembered_section(
+        ui,
+        "profiles.rules_library",
+        egui::CollapsingHeader::new(RichText::new("Rules Library").strong())
+            .id_salt(("profile_rule_library", &profile.id)),
+        false,
+        |ui| {
+            ui.label(
+                RichText::new(
+                    "Rules shared between profiles. Attach one here and set its parameters \
+                     for this game; fixing it in the library fixes it everywhere.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+            );
+            ui.add_space(4.0);
+
+            render_attached_rules(ui, profile, view_state, state);
+
+            ui.add_space(8.0);
+            ui.separator();
+            render_library_rules(ui, view_state, state);
+
+            if let Some(ref status) = view_state.library_status {
+                ui.label(
+                    RichText::new(status)
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_SECONDARY),
+                );
+            }
+        },
+    );
+}
+
+/// Edit which library rules a profile uses and their parameters
+fn render_attached_rules(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+    state: &SharedAppState,
+) {
+    let draft = reset_draft(
+        &mut view_state.library_draft,
+        &profile.id,
+        &profile.library_rules,
+    );
+
+    let mut to_remove = None;
+
+    for (i, reference) in draft.iter_mut().enumerate() {
+        let rule = state
+            .rule_library
+            .iter()
+            .find(|rule| rule.id == reference.rule_id);
+        ui.horizontal(|ui| {
+            match rule {
+                Some(rule) => ui.checkbox(&mut reference.enabled, &rule.name),
+                None => ui.label(
+                    RichText::new(format!("Missing from library: {}", reference.rule_id))
+                        .color(ThemeColors::ACCENT_WARNING),
+                ),
+            };
+            if ui.small_button("Detach").clicked() {
+                to_remove = Some(i);
+            }
+        });
+        let Some(rule) = rule else {
+            continue;
+        };
+
+        ui.indent(("attached_rule", i), |ui| {
+            if !rule.parameters.is_empty() {
+                egui::Grid::new(("attached_rule_parameters", &profile.id, i))
+                    .num_columns(2)
+                    .spacing(egui::vec2(8.0, 4.0))
+                    .show(ui, |ui| {
+                        for parameter in &rule.parameters {
+                            let label = ui.label(&parameter.name);
+                            if !parameter.description.is_empty() {
+                                label.on_hover_text(&parameter.description);
+                            }
+                            // An empty field uses the library default
+                            let mut value = reference
+                                .overrides
+                                .get(&parameter.name)
+                                .cloned()
+                                .unwrap_or_default();
+                            let edit = ui.add(
+                                egui::TextEdit::singleline(&mut value)
+                                    .hint_text(&parameter.default)
+                                    .desired_width(140.0),
+                            );
+                            if edit.changed() {
+                                if value.trim().is_empty() {
+                                    reference.overrides.remove(&parameter.name);
+                                } else {
+                                    reference.overrides.insert(parameter.name.clone(), value);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            }
+        });
+    }
+    if let Some(i) = to_remove {
+        draft.remove(i);
+    }
+
+    let mut attached = None;
+    egui::ComboBox::from_id_salt(("attach_library_rule", &profile.id))
+        .width(200.0)
+        .selected_text("Attach rule...")
+        .show_ui(ui, |ui| {
+            for rule in &state.rule_library {
+                if draft.iter().any(|reference| reference.rule_id == rule.id) {
+                    continue;
+                }
+                if ui.selectable_label(false, &rule.name).clicked() {
+                    attached = Some(rule.id.clone());
+                }
+            }
+        });
+    if let Some(rule_id) = attached {
+        draft.push(RuleReference::new(rule_id));
+    }
+
+    ui.add_space(8.0);
+
+    let has_changes = *draft != profile.library_rules;
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(has_changes, egui::Button::new("Save Attached Rules"))
+            .clicked()
+        {
+            view_state.pending_action = Some(ProfileAction::UpdateLibraryRules(
+                profile.id.clone(),
+                draft.clone(),
+            ));
+        }
+        if ui
+            .add_enabled(has_changes, egui::Button::new("Revert"))
+            .clicked()
+        {
+            *draft = profile.library_rules.clone();
+        }
+    });
+}
+
+/// List, share and edit the rules in the library
+fn render_library_rules(
+    ui: &mut egui::Ui,
+    view_state: &mut ProfilesViewState,
+    state: &SharedAppState,
+) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Library").strong());
+        if ui.small_button("+ New Rule").clicked() {
+            view_state.library_rule_draft = Some(LibraryRule {
+                id: Uuid::new_v4().to_string(),
+                name: String::new(),
+                description: String::new(),
+                script: String::new(),
+                parameters: vec![],
+            });
+        }
+    });
+    ui.label(
+        RichText::new("Library rules are shared with the Import field under Share.")
+            .size(11.0)
+            .color(ThemeColors::TEXT_MUTED),
+    );
+    ui.add_space(4.0);
+
+    if state.rule_library.is_empty() {
+        ui.label(
+            RichText::new("No library rules yet")
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    }
+
+    for rule in &state.rule_library {
+        ui.horizontal(|ui| {
+            let name = ui.label(&rule.name);
+            if !rule.description.is_empty() {
+                name.on_hover_text(&rule.description);
+            }
+            if !rule.parameters.is_empty() {
+                ui.label(
+                    RichText::new(format!("{} parameter(s)", rule.parameters.len()))
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("Delete").clicked() {
+                    view_state.pending_action =
+                        Some(ProfileAction::DeleteLibraryRule(rule.id.clone()));
+                }
+                if ui.small_button("Copy Code").clicked() {
+                    let item = SharedItem::LibraryRule(rule.clone());
+                    view_state.library_status = Some(match item.to_share_code() {
+                        Ok(code) => {
+                            ui.output_mut(|o| o.copied_text = code);
+                            format!("Copied share code for '{}'", rule.name)
+                        }
+                        Err(e) => format!("Failed to create share code: {}", e),
+                    });
+                }
+                if ui.small_button("Edit").clicked() {
+                    view_state.library_rule_draft = Some(rule.clone());
+                }
+            });
+        });
+    }
+
+    if view_state.library_rule_draft.is_some() {
+        ui.add_space(8.0);
+        render_library_rule_editor(ui, view_state);
+    }
+}
+
+/// Edit a library rule's script and parameters
+fn render_library_rule_editor(ui: &mut egui::Ui, view_state: &mut ProfilesViewState) {
+    let Some(rule) = view_state.library_rule_draft.as_mut() else {
+        return;
+    };
+
+    let mut close = false;
+    egui::Frame::none()
+        .fill(ThemeColors::BG_DARK)
+        .rounding(egui::Rounding::same(6.0))
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            egui::Grid::new("library_rule_editor")
+                .num_columns(2)
+                .spacing(egui::vec2(8.0, 4.0))
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut rule.name)
+                            .hint_text("Stretch reminder")
+                            .desired_width(220.0),
+                    );
+                    ui.end_row();
+                    ui.label("Description:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut rule.description)
+                            .hint_text("Optional")
+                            .desired_width(220.0),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Parameters")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+            let mut to_remove = None;
+            egui::Grid::new("library_rule_parameters")
+                .num_columns(4)
+                .spacing(egui::vec2(8.0, 4.0))
+                .show(ui, |ui| {
+                    for (i, parameter) in rule.parameters.iter_mut().enumerate() {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut parameter.name)
+                                .hint_text("minutes")
+                                .desired_width(90.0),
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(&mut parameter.default)
+                                .hint_text("Default")
+                                .desired_width(90.0),
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(&mut parameter.description)
+                                .hint_text("What it controls")
+                                .desired_width(140.0),
+                        );
+                        if ui.small_button("X").clicked() {
+                            to_remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = to_remove {
+                rule.parameters.remove(i);
+            }
+            if ui.small_button("+ Add Parameter").clicked() {
+                rule.parameters.push(RuleParameter::default());
+            }
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Script (parameters are available as constants)")
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+                if ui
+                    .small_button("Reference")
+                    .on_hover_text("Variables and functions scripts can use")
+                    .clicked()
+                {
+                    view_state.open_reference = true;
+                }
+            });
+            ui.add(
+                egui::TextEdit::multiline(&mut rule.script)
+                    .code_editor()
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY),
+            );
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!rule.name.trim().is_empty(), egui::Button::new("Save Rule"))
+                    .clicked()
+                {
+                    view_state.pending_action =
+                        Some(ProfileAction::SaveLibraryRule(cleaned_library_rule(rule)));
+                    close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    close = true;
+                }
+            });
+        });
+    if close {
+        view_state.library_rule_draft = None;
+    }
+}
+
+/// Trim the name and make parameter names usable in the script, dropping unnamed ones
+fn cleaned_library_rule(rule: &LibraryRule) -> LibraryRule {
+    LibraryRule {
+        name: rule.name.trim().to_string(),
+        description: rule.description.trim().to_string(),
+        parameters: rule
+            .parameters
+            .iter()
+            .filter(|parameter| !parameter.name.trim().is_empty())
+            .map(|parameter| RuleParameter {
+                name: parameter_name(&parameter.name),
+                default: parameter.default.trim().to_string(),
+                description: parameter.description.trim().to_string(),
+            })
+            .collect(),
+        ..rule.clone()
+    }
+}
+
+/// Render the editable OCR substitution table and vocabulary of a profile
+fn render_dictionary_section(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+) {
+    remembered_section(
+        ui,
+        "profiles.dictionary",
+        egui::CollapsingHeader::new(RichText::new("Recognition Dictionary").strong())
+            .id_salt(("profile_dictionary", &profile.id)),
+        false,
+        |ui| {
+            let draft = reset_draft(
+                &mut view_state.dictionary_draft,
+                &profile.id,
+                &profile.dictionary,
+            );
+
+            ui.label(
+                RichText::new(
+                    "Substitutions fix common misreads in raw OCR text. Text zones and \
+                     screen anchors that read close to a vocabulary word snap to it.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+            );
+
+            ui.add_space(8.0);
+            ui.label(RichText::new("Substitutions").size(13.0));
+            ui.add_space(4.0);
+
+            let mut to_remove = None;
+            egui::Grid::new(("dictionary_substitutions", &profile.id))
+                .num_columns(3)
+                .spacing(egui::vec2(8.0, 4.0))
+                .show(ui, |ui| {
+                    ui.label(
+                        RichText::new("OCR reads")
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                    ui.label(
+                        RichText::new("Replace with")
+                            .size(12.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+                    ui.end_row();
+
+                    for (i, rule) in draft.substitutions.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut rule.from).desired_width(140.0));
+                        ui.add(egui::TextEdit::singleline(&mut rule.to).desired_width(140.0));
+                        if ui.small_button("X").clicked() {
+                            to_remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = to_remove {
+                draft.substitutions.remove(i);
+            }
+            if ui.small_button("+ Add Substitution").clicked() {
+                draft.substitutions.push(SubstitutionRule::default());
+            }
+
+            ui.add_space(8.0);
+            ui.label(RichText::new("Vocabulary").size(13.0));
+            ui.add_space(4.0);
+
+            let mut vocabulary = draft.vocabulary.join("\n");
+            if ui
+                .add(
+                    egui::TextEdit::multiline(&mut vocabulary)
+                        .hint_text("One word or name per line")
+                        .desired_rows(4)
+                        .desired_width(300.0),
+                )
+                .changed()
+            {
+                draft.vocabulary = vocabulary.split('\n').map(str::to_string).collect();
+            }
+
+            ui.add_space(8.0);
+
+            let cleaned = cleaned_dictionary(draft);
+            let has_changes = cleaned != profile.dictionary;
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Save Dictionary"))
+                    .clicked()
+                {
+                    *draft = cleaned.clone();
+                    view_state.pending_action =
+                        Some(ProfileAction::UpdateDictionary(profile.id.clone(), cleaned));
+                }
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Revert"))
+                    .clicked()
+                {
+                    *draft = profile.dictionary.clone();
+                }
+            });
+        },
+    );
+}
+
+/// A draft of a profile's setting, started afresh from the saved value
+/// when another profile is selected
+fn reset_draft<'a, T: Clone>(
+    draft: &'a mut Option<(String, T)>,
+    profile_id: &str,
+    saved: &T,
+) -> &'a mut T {
+    if draft.as_ref().is_some_and(|(id, _)| id != profile_id) {
+        *draft = None;
+    }
+    &mut draft
+        .get_or_insert_with(|| (profile_id.to_string(), saved.clone()))
+        .1
+}
+
+/// Drop blank substitutions and vocabulary lines, trimming vocabulary words
+fn cleaned_dictionary(draft: &RecognitionDictionary) -> RecognitionDictionary {
+    RecognitionDictionary {
+        substitutions: draft
+            .substitutions
+            .iter()
+            .filter(|rule| !rule.from.is_empty())
+            .cloned()
+            .collect(),
+        vocabulary: draft
+            .vocabulary
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// Render share code copy buttons and the import field for a profile
+fn render_share_section(
+    ui: &mut egui::Ui,
+    profile: &GameProfile,
+    view_state: &mut ProfilesViewState,
+) {
+    remembered_section(
+        ui,
+        "profiles.share",
+        egui::CollapsingHeader::new(RichText::new("Share").strong())
+            .id_salt(("profile_share", &profile.id)),
+        false,
+        |ui| {
+            let mut to_copy = None;
+
+            for zone in &profile.ocr_regions {
+                let name = if zone.name.is_empty() {
+                    &zone.id
+                } else {
+                    &zone.name
+                };
+                if share_row(ui, "Zone", name) {
+                    to_copy = Some(SharedItem::Zone(zone.clone()));
+                }
+            }
+            for screen in &profile.screens {
+                if share_row(ui, "Screen", &screen.name) {
+                    to_copy = Some(SharedItem::Screen(screen.clone()));
+                }
+            }
+            for rule in &profile.rules {
+                if share_row(ui, "Rule", &rule.name) {
+                    to_copy = Some(SharedItem::Rule(rule.clone()));
+                }
+            }
+
+            if let Some(item) = to_copy {
+                view_state.share_status = Some(match item.to_share_code() {
+                    Ok(code) => {
+                        ui.output_mut(|o| o.copied_text = code);
+                        format!(
+                            "Copied share code for {} '{}'",
+                            item.kind_name(),
+                            item.name()
+                        )
+                    }
+                    Err(e) => format!("Failed to create share code: {}", e),
+                });
+            }
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut view_state.share_code_input)
+                        .hint_text("Paste a share code (GTK1...)")
+                        .desired_width(220.0),
+                );
+                let can_import = !view_state.share_code_input.trim().is_empty();
+                if ui
+                    .add_enabled(can_import, egui::Button::new("Import"))
+                    .clicked()
+                {
+                    view_state.share_status = Some(
+                        match SharedItem::from_share_code(&view_state.share_code_input) {
+                            Ok(item) => {
+                                let status =
+                                    format!("Added {} '{}'", item.kind_name(), item.name());
+                                view_state.pending_action =
+                                    Some(ProfileAction::ImportShared(profile.id.clone(), item));
+                                view_state.share_code_input.clear();
+                                status
+                            }
+                            Err(e) => e.to_string(),
+                        },
+                    );
+                }
+            });
+
+            if let Some(ref status) = view_state.share_status {
+                ui.label(
+                    RichText::new(status)
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_SECONDARY),
+                );
+            }
+        },
+    );
+}
+
+/// Render a row with a copy button, returning whether it was clicked
+fn share_row(ui: &mut egui::Ui, kind: &str, name: &str) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(kind)
+                .size(12.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        ui.label(name);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.small_button("Copy Code").clicked()
+        })
+        .inner
+    })
+    .inner
+}
+
+/// Render the create profile dialog
+fn render_create_dialog(
+    ui: &mut egui::Ui,
+    view_state: &mut ProfilesViewState,
+    _shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    egui::Window::new("Create New Profile")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ui.ctx(), |ui| {
+            ui.set_min_width(300.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Profile Name:");
+                ui.text_edit_singleline(&mut view_state.new_profile_name);
+            });
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Executable:");
+                ui.text_edit_singleline(&mut view_state.new_profile_executable);
+            });
+
+            ui.add_space(16.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Cancel").clicked() {
+                    view_state.show_create_dialog = false;
+                }
+
+                ui.add_space(8.0);
+
+                let can_create = !view_state.new_profile_name.is_empty();
+                ui.add_enabled_ui(can_create, |ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(RichText::new("Create").color(egui::Color32::WHITE))
+                                .fill(ThemeColors::ACCENT_PRIMARY),
+                        )
+                        .clicked()
+                    {
+                        let new_profile = GameProfile {
+                            id: format!(
+                                "profile_{}",
+                                std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .unwrap()
+                                    .as_secs()
+                            ),
+                            name: view_state.new_profile_name.clone(),
+                            executables: if view_state.new_profile_executable.is_empty() {
+                                vec![]
+                            } else {
+                                vec![view_state.new_profile_executable.clone()]
+                            },
+                            version: "1.0.0".to_string(),
+                            ocr_regions: vec![],
+                            templates: vec![],
+                            rules: vec![],
+                            library_rules: vec![],
+                            labeled_regions: vec![],
+                            screens: vec![],
+                            screen_recognition_enabled: false,
+                            screen_check_interval_ms: 500,
+                            dictionary: Default::default(),
+                            color_triggers: vec![],
+                            variables: vec![],
+                            checklists: vec![],
+                            confidence_calibration: vec![],
+                            auto_start: Default::default(),
+                            snapshot_capture: Default::default(),
+                            secondary_windows: vec![],
+                            phases: vec![],
+                        };
+
+                        // Queue profile creation action (will be saved to disk by DashboardApp)
+                        view_state.selected_profile_id = Some(new_profile.id.clone());
+                        view_state.pending_action = Some(ProfileAction::Create(new_profile));
+                        view_state.show_create_dialog = false;
+                    }
+                });
+            });
+        });
+}
+
+/// Render the delete confirmation dialog
+fn render_delete_confirm_dialog(
+    ui: &mut egui::Ui,
+    view_state: &mut ProfilesViewState,
+    _shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    egui::Window::new("Confirm Delete")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ui.ctx(), |ui| {
+            ui.label("Are you sure you want to delete this profile?");
+            ui.label(
+                RichText::new("This action cannot be undone.").color(ThemeColors::ACCENT_WARNING),
+            );
+
+            ui.add_space(16.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Cancel").clicked() {
+                    view_state.show_delete_confirm = false;
+                }
+
+                ui.add_space(8.0);
+
+                if ui
+                    .add(
+                        egui::Button::new(RichText::new("Delete").color(egui::Color32::WHITE))
+                            .fill(ThemeColors::ACCENT_ERROR),
+                    )
+                    .clicked()
+                {
+                    if let Some(profile_id) = view_state.selected_profile_id.take() {
+                        // Queue profile deletion action (will be deleted from disk by DashboardApp)
+                        view_state.pending_action = Some(ProfileAction::Delete(profile_id));
+                    }
+                    view_state.show_delete_confirm = false;
+                }
+            });
+        });
+}
+
+/// Render the starter pack picker
+fn render_starter_packs_dialog(
+    ui: &mut egui::Ui,
+    view_state: &mut ProfilesViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    egui::Window::new("Starter Packs")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ui.ctx(), |ui| {
+            ui.set_min_width(420.0);
+
+            ui.label(
+                RichText::new(
+                    "Ready-made profiles with screens, zones and example rules. \
+                     Zone positions assume a 16:9 display and may need adjusting.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+
+            ui.add_space(12.0);
+
+            for pack in STARTER_PACKS {
+                let installed = shared_state.read().profiles.iter().any(|p| p.id == pack.id);
+
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label(RichText::new(pack.name).strong());
+                        ui.label(
+                            RichText::new(pack.description)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                    });
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if installed {
+                            ui.add_enabled(false, egui::Button::new("Installed"));
+                        } else if ui.button("Install").clicked() {
+                            match pack.profile() {
+                                Ok(profile) => {
+                                    // Saved to disk by DashboardApp like any new profile
+                                    view_state.selected_profile_id = Some(profile.id.clone());
+                                    view_state.pending_action =
+                                        Some(ProfileAction::Create(profile));
+                                }
+                                Err(e) => {
+                                    shared_state
+                                        .write()
+                                        .runtime
+                                        .set_error(AppError::Storage(e.to_string()));
+                                }
+                            }
+                        }
+                    });
+                });
+
+                ui.add_space(8.0);
+            }
+
+            ui.add_space(8.0);
+
+            if ui.button("Close").clicked() {
+                view_state.show_starter_packs = false;
+            }
+        });
+}
+
+/// Render the backup restore picker
+fn render_backups_dialog(
+    ui: &mut egui::Ui,
+    view_state: &mut ProfilesViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    egui::Window::new("Restore Profile Backup")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ui.ctx(), |ui| {
+            ui.set_min_width(460.0);
+
+            ui.label(
+                RichText::new(
+                    "Profiles are backed up automatically before screens, zones or \
+                     profiles are deleted and before imports. Restoring also backs up \
+                     the current version first.",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+
+            ui.add_space(12.0);
+
+            if view_state.backups.is_empty() {
+                ui.label(RichText::new("No backups yet").color(ThemeColors::TEXT_MUTED));
+            }
+
+            let now_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("profile_backups")
+                .max_height(360.0)
+                .show(ui, |ui| {
+                    for backup in &view_state.backups {
+                        let profile_name = shared_state
+                            .read()
+                            .profiles
+                            .iter()
+                            .find(|p| p.id == backup.profile_id)
+                            .map(|p| p.name.clone());
+
+                        ui.horizontal(|ui| {
+                            ui.vertical(|ui| {
+                                let title = profile_name
+                                    .unwrap_or_else(|| format!("{} (deleted)", backup.profile_id));
+                                ui.label(RichText::new(title).strong());
+                                ui.label(
+                                    RichText::new(format!(
+                                        "{} · {}",
+                                        backup.reason.description(),
+                                        format_age(now_ms.saturating_sub(backup.timestamp_ms))
+                                    ))
+                                    .size(12.0)
+                                    .color(ThemeColors::TEXT_MUTED),
+                                );
+                            });
+
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui.button("Restore").clicked() {
+                                        view_state.selected_profile_id =
+                                            Some(backup.profile_id.clone());
+                                        view_state.pending_action =
+                                            Some(ProfileAction::RestoreBackup(backup.clone()));
+                                    }
+                                },
+                            );
+                        });
+
+                        ui.add_space(8.0);
+                    }
+                });
+
+            ui.add_space(8.0);
+
+            if ui.button("Close").clicked() || view_state.pending_action.is_some() {
+                view_state.show_backups = false;
+            }
+        });
+}
+
+/// Format an age in milliseconds as "5 min ago" and similar
+fn format_age(age_ms: u64) -> String {
+    let secs = age_ms / 1000;
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", secs / 60),
+        3600..=86399 => format!("{} h ago", secs / 3600),
+        _ => format!("{} days ago", secs / 86400),
+    }
 }
@@ -7,10 +7,13 @@ use egui::{RichText, ScrollArea};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+use crate::dashboard::components::{remember_width, remembered_scroll, remembered_width};
 use crate::dashboard::state::{DashboardState, ScreensViewState};
 use crate::dashboard::theme::ThemeColors;
 use crate::shared::SharedAppState;
-use crate::storage::profiles::{AnchorType, ScreenAnchor, ScreenDefinition, ScreenMatchMode};
+use crate::storage::profiles::{
+    AnchorType, MatchStrategy, ScreenAnchor, ScreenDefinition, ScreenMatchMode,
+};
 
 /// Render the screens view
 pub fn render_screens_view(
@@ -30,6 +33,7 @@ pub fn render_screens_view(
             .active_profile()
             .map(|p| p.screen_recognition_enabled)
             .unwrap_or(false);
+        let template_matching = shared.config.features.template_matching;
         drop(shared);
 
         let toggle_text = if is_enabled { "Enabled" } else { "Disabled" };
@@ -54,6 +58,14 @@ pub fn render_screens_view(
                 view_state.screens_dirty = true;
             }
         }
+
+        if !template_matching {
+            ui.label(
+                RichText::new("Template matching is turned off in Settings > Features")
+                    .size(12.0)
+                    .color(ThemeColors::ACCENT_WARNING),
+            );
+        }
     });
 
     ui.add_space(8.0);
@@ -79,6 +91,8 @@ pub fn render_screens_view(
         }
     }
 
+    render_match_benchmark(ui, view_state);
+
     ui.add_space(16.0);
     ui.separator();
     ui.add_space(8.0);
@@ -86,13 +100,16 @@ pub fn render_screens_view(
     // Main content area - split into tree and details
     let available_height = ui.available_height();
 
-    ui.horizontal(|ui| {
-        // Left panel: Screen hierarchy tree
-        ui.vertical(|ui| {
-            ui.set_min_width(250.0);
-            ui.set_max_width(300.0);
-            ui.set_min_height(available_height);
-
+    // Left panel: Screen hierarchy tree (resizable)
+    let tree = egui::SidePanel::left("screen_tree_panel")
+        .resizable(true)
+        .frame(egui::Frame::none().inner_margin(egui::Margin {
+            right: 8.0,
+            ..Default::default()
+        }))
+        .default_width(remembered_width(ui.ctx(), "screens.tree", 280.0))
+        .width_range(220.0..=520.0)
+        .show_inside(ui, |ui| {
             ui.horizontal(|ui| {
                 ui.label(
                     RichText::new("Screens")
@@ -122,31 +139,29 @@ pub fn render_screens_view(
 
             ui.add_space(8.0);
 
-            ScrollArea::vertical()
-                .id_salt("screen_tree")
-                .show(ui, |ui| {
-                    render_screen_tree(ui, view_state, shared_state);
-                });
+            remembered_scroll(
+                ui,
+                "screens.tree",
+                ScrollArea::vertical().id_salt("screen_tree"),
+                |ui| render_screen_tree(ui, view_state, shared_state),
+            );
         });
+    remember_width(ui.ctx(), "screens.tree", tree.response.rect.width());
 
-        ui.separator();
+    // Right panel: Selected screen details
+    ui.vertical(|ui| {
+        ui.set_min_width(350.0);
+        ui.set_min_height(available_height);
 
-        // Right panel: Selected screen details
-        ui.vertical(|ui| {
-            ui.set_min_width(350.0);
-            ui.set_min_height(available_height);
-
-            if let Some(ref screen_id) = view_state.selected_screen_id.clone() {
-                render_screen_details(ui, screen_id, view_state, shared_state);
-            } else {
-                ui.centered_and_justified(|ui| {
-                    ui.label(
-                        RichText::new("Select a screen to view details")
-                            .color(ThemeColors::TEXT_MUTED),
-                    );
-                });
-            }
-        });
+        if let Some(ref screen_id) = view_state.selected_screen_id.clone() {
+            render_screen_details(ui, screen_id, view_state, shared_state);
+        } else {
+            ui.centered_and_justified(|ui| {
+                ui.label(
+                    RichText::new("Select a screen to view details").color(ThemeColors::TEXT_MUTED),
+                );
+            });
+        }
     });
 
     // Add screen dialog
@@ -604,12 +619,97 @@ fn render_screen_details(
                     }
                 });
             ui.end_row();
+
+            // How many required anchors must match
+            if screen.match_mode == ScreenMatchMode::Anchors {
+                ui.label(RichText::new("Required:").color(ThemeColors::TEXT_SECONDARY));
+                ui.horizontal(|ui| {
+                    let required_total =
+                        screen.anchors.iter().filter(|a| a.required).count() as u32;
+                    let mut all = screen.min_required_anchors.is_none();
+                    if ui
+                        .checkbox(&mut all, "All")
+                        .on_hover_text(
+                            "Uncheck to recognize the screen when only some required \
+                             anchors match, e.g. while a popup covers one",
+                        )
+                        .changed()
+                    {
+                        let min_required = (!all).then(|| required_total.saturating_sub(1).max(1));
+                        update_screen_field(shared_state, screen_id, |s| {
+                            s.min_required_anchors = min_required
+                        });
+                        view_state.screens_dirty = true;
+                    }
+
+                    if let Some(mut min_required) = screen.min_required_anchors {
+                        if ui
+                            .add(egui::DragValue::new(&mut min_required).range(0..=required_total))
+                            .changed()
+                        {
+                            update_screen_field(shared_state, screen_id, |s| {
+                                s.min_required_anchors = Some(min_required)
+                            });
+                            view_state.screens_dirty = true;
+                        }
+                        ui.label(format!("of {} must match", required_total));
+                    }
+                });
+                ui.end_row();
+            }
+
+            // Area the screen covers when it pops up over the game
+            ui.label(RichText::new("Popup:").color(ThemeColors::TEXT_SECONDARY));
+            ui.horizontal(|ui| {
+                let mut is_popup = screen.popup_bounds.is_some();
+                if ui
+                    .checkbox(&mut is_popup, "")
+                    .on_hover_text(
+                        "Zones under this area keep their last value, shown as occluded, \
+                         while this screen is recognized",
+                    )
+                    .changed()
+                {
+                    let bounds = is_popup.then(|| anchors_bounding_box(&screen.anchors));
+                    update_screen_field(shared_state, screen_id, |s| s.popup_bounds = bounds);
+                    view_state.screens_dirty = true;
+                }
+
+                if let Some((x, y, w, h)) = screen.popup_bounds {
+                    let mut percent = [x * 100.0, y * 100.0, w * 100.0, h * 100.0];
+                    let mut changed = false;
+                    for (value, label) in percent.iter_mut().zip(["x", "y", "w", "h"]) {
+                        ui.label(label);
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(value)
+                                    .range(0.0..=100.0)
+                                    .speed(0.5)
+                                    .suffix("%"),
+                            )
+                            .changed();
+                    }
+                    if changed {
+                        let [x, y, w, h] = percent.map(|v| v / 100.0);
+                        update_screen_field(shared_state, screen_id, |s| {
+                            s.popup_bounds = Some((x, y, w, h))
+                        });
+                        view_state.screens_dirty = true;
+                    }
+                }
+            });
+            ui.end_row();
         });
 
     ui.add_space(16.0);
     ui.separator();
     ui.add_space(8.0);
 
+    if screen.match_mode == ScreenMatchMode::FullScreenshot {
+        render_full_templates(ui, &screen, view_state, shared_state);
+        ui.add_space(16.0);
+    }
+
     // Anchors section
     ui.horizontal(|ui| {
         ui.label(
@@ -724,6 +824,99 @@ where
     }
 }
 
+/// Render the CPU/GPU template matching benchmark toggle and timings
+fn render_match_benchmark(ui: &mut egui::Ui, view_state: &mut ScreensViewState) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut view_state.benchmark_matching, "Benchmark matching")
+            .on_hover_text("Time template matching on both the CPU and the GPU");
+
+        if !view_state.benchmark_matching {
+            return;
+        }
+        match view_state.match_benchmark {
+            Some(benchmark) => {
+                let gpu = match benchmark.gpu {
+                    Some(time) => format!("{:.1} ms", time.as_secs_f64() * 1000.0),
+                    None => "unavailable".to_string(),
+                };
+                ui.label(
+                    RichText::new(format!(
+                        "{} templates: CPU {:.1} ms, GPU {}",
+                        benchmark.pairs,
+                        benchmark.cpu.as_secs_f64() * 1000.0,
+                        gpu
+                    ))
+                    .color(ThemeColors::TEXT_MUTED),
+                );
+            }
+            None => {
+                ui.label(
+                    RichText::new("Waiting for recognition...").color(ThemeColors::TEXT_MUTED),
+                );
+            }
+        }
+        if let Some(ref error) = view_state.gpu_error {
+            ui.label(RichText::new("GPU error").color(ThemeColors::ACCENT_WARNING))
+                .on_hover_text(error);
+        }
+    });
+}
+
+/// Render the screen's full templates, one per captured resolution
+fn render_full_templates(
+    ui: &mut egui::Ui,
+    screen: &ScreenDefinition,
+    view_state: &mut ScreensViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new("Templates")
+                .color(ThemeColors::TEXT_PRIMARY)
+                .strong(),
+        );
+        ui.label(
+            RichText::new(format!("({})", screen.full_templates().count()))
+                .color(ThemeColors::TEXT_MUTED),
+        );
+    });
+
+    ui.add_space(4.0);
+
+    let mut template_to_remove = None;
+    for (idx, template) in screen.full_templates().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!("{}x{}", template.width, template.height))
+                    .color(ThemeColors::TEXT_PRIMARY),
+            );
+            if ui
+                .small_button("X")
+                .on_hover_text("Delete template")
+                .clicked()
+            {
+                template_to_remove = Some(idx);
+            }
+        });
+    }
+
+    if let Some(idx) = template_to_remove {
+        update_screen_field(shared_state, &screen.id, |s| s.remove_full_template(idx));
+        view_state.screens_dirty = true;
+    }
+
+    if ui
+        .button("Capture Template")
+        .on_hover_text(
+            "Capture the current frame; one template is kept per resolution and the \
+             closest one to the game's resolution is used",
+        )
+        .clicked()
+    {
+        view_state.pending_full_capture = Some(screen.id.clone());
+    }
+}
+
 /// Render anchor item with edit controls, returns anchor ID if delete was clicked
 fn render_anchor_item_with_controls(
     ui: &mut egui::Ui,
@@ -764,6 +957,27 @@ fn render_anchor_item_with_controls(
             }
         }
 
+        // Weight in the screen's confidence
+        let mut weight = anchor.weight;
+        if ui
+            .add(
+                egui::DragValue::new(&mut weight)
+                    .range(0.1..=10.0)
+                    .speed(0.05)
+                    .fixed_decimals(1)
+                    .prefix("Weight "),
+            )
+            .on_hover_text("How much this anchor counts in the screen's confidence")
+            .changed()
+        {
+            update_screen_field(shared_state, screen_id, |s| {
+                if let Some(a) = s.anchors.iter_mut().find(|a| a.id == anchor.id) {
+                    a.weight = weight;
+                }
+            });
+            view_state.screens_dirty = true;
+        }
+
         // Show expected text for text anchors
         if anchor.anchor_type == AnchorType::Text {
             if let Some(ref expected) = anchor.expected_text {
@@ -775,6 +989,31 @@ fn render_anchor_item_with_controls(
             }
         }
 
+        // How visual anchors are compared with the frame
+        if anchor.anchor_type == AnchorType::Visual {
+            let mut strategy = anchor.match_strategy;
+            egui::ComboBox::from_id_salt(("anchor_match_strategy", &anchor.id))
+                .width(80.0)
+                .selected_text(match strategy {
+                    MatchStrategy::Ncc => "Pixels",
+                    MatchStrategy::Features => "Features",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut strategy, MatchStrategy::Ncc, "Pixels")
+                        .on_hover_text("Compare pixels directly (fast, exact size only)");
+                    ui.selectable_value(&mut strategy, MatchStrategy::Features, "Features")
+                        .on_hover_text("Match keypoints (slower, tolerates scaling and rotation)");
+                });
+            if strategy != anchor.match_strategy {
+                update_screen_field(shared_state, screen_id, |s| {
+                    if let Some(a) = s.anchors.iter_mut().find(|a| a.id == anchor.id) {
+                        a.match_strategy = strategy;
+                    }
+                });
+                view_state.screens_dirty = true;
+            }
+        }
+
         // Delete button
         if ui
             .small_button("X")
@@ -878,12 +1117,15 @@ fn render_add_screen_dialog(
                         match_mode: view_state.new_screen_match_mode.clone(),
                         anchors: vec![],
                         full_template: None,
+                        extra_templates: vec![],
                         match_threshold: 0.8,
                         enabled: true,
                         priority: 10,
                         ocr_zone_overrides: vec![],
                         rules_to_trigger: vec![],
                         show_notification: true,
+                        min_required_anchors: None,
+                        popup_bounds: None,
                     };
 
                     let mut shared = shared_state.write();
@@ -905,6 +1147,22 @@ fn render_add_screen_dialog(
         });
 }
 
+/// Smallest area containing every anchor (the whole screen without anchors)
+fn anchors_bounding_box(anchors: &[ScreenAnchor]) -> (f32, f32, f32, f32) {
+    if anchors.is_empty() {
+        return (0.0, 0.0, 1.0, 1.0);
+    }
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (1.0f32, 1.0f32, 0.0f32, 0.0f32);
+    for anchor in anchors {
+        let (x, y, w, h) = anchor.bounds;
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x + w);
+        max_y = max_y.max(y + h);
+    }
+    (min_x, min_y, max_x - min_x, max_y - min_y)
+}
+
 /// Render the delete confirmation dialog
 fn render_delete_confirm_dialog(
     ui: &mut egui::Ui,
@@ -1040,9 +1298,12 @@ fn render_text_anchor_dialog(
                             anchor_type: AnchorType::Text,
                             bounds,
                             template_data: None,
+                            template_file: None,
                             expected_text: Some(view_state.editing_text_anchor_text.clone()),
                             text_similarity: 0.8,
                             required: true,
+                            weight: 1.0,
+                            match_strategy: MatchStrategy::Ncc,
                         };
 
                         // Add to screen
@@ -5,10 +5,77 @@ use parking_lot::RwLock;
 use std::cell::Cell;
 use std::sync::Arc;
 
-use crate::dashboard::components::add_scroll_slider;
+use crate::analysis::latency::LatencySamples;
+use crate::config::{
+    FrameColorFormat, GameExitAction, ReplayFormat, SecondaryWindowMode, TipFallback,
+    MIN_CAPTURE_SCALE,
+};
+use crate::dashboard::components::{add_scroll_slider, remembered_scroll};
 use crate::dashboard::state::{SettingsSection, SettingsViewState};
-use crate::dashboard::theme::ThemeColors;
+use crate::dashboard::theme::{color_with_alpha, ThemeColors};
+use crate::qos;
 use crate::shared::SharedAppState;
+use crate::vision::gpu_adapters;
+
+/// Render the banner listing problems found in config.toml (if any)
+fn render_config_issues_banner(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let mut state = shared_state.write();
+    if state.runtime.config_issues.is_empty() {
+        return;
+    }
+
+    egui::Frame::none()
+        .fill(color_with_alpha(ThemeColors::ACCENT_WARNING, 38)) // ~0.15 alpha
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(16.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Config issues ({})",
+                        state.runtime.config_issues.len()
+                    ))
+                    .color(ThemeColors::ACCENT_WARNING)
+                    .strong(),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button("Fix & Rewrite")
+                        .on_hover_text(
+                            "Rewrite config.toml with the settings below, dropping the \
+                             problem entries. The original is kept as config.toml.bak.",
+                        )
+                        .clicked()
+                    {
+                        state.runtime.rewrite_config = true;
+                    }
+                });
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new(
+                    "These entries in config.toml were ignored and defaults were used instead:",
+                )
+                .size(12.0)
+                .color(ThemeColors::TEXT_SECONDARY),
+            );
+            ui.add_space(8.0);
+
+            for issue in &state.runtime.config_issues {
+                ui.label(
+                    RichText::new(issue.to_string())
+                        .monospace()
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_PRIMARY),
+                );
+            }
+        });
+
+    ui.add_space(16.0);
+}
 
 /// Render the settings view
 pub fn render_settings_view(
@@ -26,10 +93,12 @@ pub fn render_settings_view(
 
     ui.add_space(24.0);
 
+    render_config_issues_banner(ui, shared_state);
+
     // Track changes using Cell to avoid borrow issues
     let changed = Cell::new(false);
 
-    egui::ScrollArea::vertical().show(ui, |ui| {
+    remembered_scroll(ui, "settings.page", egui::ScrollArea::vertical(), |ui| {
         // General Settings
         let is_general_expanded = view_state.expanded_section == Some(SettingsSection::General);
         egui::Frame::none()
@@ -97,6 +166,42 @@ pub fn render_settings_view(
                             changed.set(true);
                         }
                     });
+
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        let exporting = state.runtime.exporting_snapshot;
+                        if ui
+                            .add_enabled(!exporting, egui::Button::new("Export Snapshot"))
+                            .on_hover_text(
+                                "Save config, active profile, recent samples and events \
+                                 to one file",
+                            )
+                            .clicked()
+                        {
+                            state.runtime.export_snapshot = true;
+                        }
+                        if ui.button("Report a Problem").clicked() {
+                            view_state.open_issue_report = true;
+                        }
+                        if exporting {
+                            ui.label(
+                                RichText::new("Exporting...")
+                                    .size(11.0)
+                                    .color(ThemeColors::TEXT_MUTED),
+                            );
+                        }
+                    });
+                    let snapshot_note = match state.runtime.last_snapshot {
+                        Some(ref path) => format!("Last exported: {}", path.display()),
+                        None => "Attach a snapshot to bug reports so they can be reproduced \
+                                 with --load-snapshot."
+                            .to_string(),
+                    };
+                    ui.label(
+                        RichText::new(snapshot_note)
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
                 }
             });
 
@@ -178,6 +283,170 @@ pub fn render_settings_view(
                         }
                     });
 
+                    ui.horizontal(|ui| {
+                        ui.label("Crop to game area:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.capture.client_area_only, "")
+                            .on_hover_text(
+                                "Cut the title bar and borders off a windowed game, so zones \
+                                 line up with the same game running borderless. Applies the \
+                                 next time capture starts.",
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Other game windows:");
+                        ui.add_space(8.0);
+                        let secondary_windows = &mut state.config.capture.secondary_windows;
+                        egui::ComboBox::from_id_salt("capture_secondary_windows")
+                            .selected_text(secondary_windows.name())
+                            .show_ui(ui, |ui| {
+                                for mode in SecondaryWindowMode::ALL {
+                                    if ui
+                                        .selectable_value(secondary_windows, mode, mode.name())
+                                        .changed()
+                                    {
+                                        changed.set(true);
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "Whether a window capture also shows the game's dialogs and \
+                                 detached tool windows on top of it. To read a tool window \
+                                 on its own, add it as a secondary window of the profile. \
+                                 Applies the next time capture starts.",
+                            );
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Keep frames on the GPU:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.capture.gpu_frames, "")
+                            .on_hover_text(
+                                "Download only the zones that are read instead of every whole \
+                                 frame, which saves memory bandwidth at high resolutions. \
+                                 Applies the next time capture starts; not for composite \
+                                 captures.",
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Color format:");
+                        ui.add_space(8.0);
+                        let color_format = &mut state.config.capture.color_format;
+                        egui::ComboBox::from_id_salt("capture_color_format")
+                            .selected_text(color_format.name())
+                            .show_ui(ui, |ui| {
+                                for format in FrameColorFormat::ALL {
+                                    if ui
+                                        .selectable_value(color_format, format, format.name())
+                                        .changed()
+                                    {
+                                        changed.set(true);
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "HDR is for games running in HDR, whose 8-bit captures come \
+                                 out washed out and read badly: frames are captured at twice \
+                                 the size and tone-mapped back to normal colors. Grayscale \
+                                 keeps a quarter of the memory per frame, for recognizing \
+                                 screens and matching templates; color triggers only see \
+                                 brightness then. Standard is right for everything else. \
+                                 Applies the next time capture starts; HDR and grayscale \
+                                 frames aren't kept on the GPU.",
+                            );
+                    });
+
+                    let hdr = state.config.capture.color_format == FrameColorFormat::Rgba16f;
+                    ui.add_enabled_ui(hdr, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("SDR content brightness:");
+                            ui.add_space(8.0);
+                            if add_scroll_slider(
+                                ui,
+                                &mut state.config.capture.hdr_white_nits,
+                                80.0..=480.0,
+                                Some(20.0),
+                                Some(" nits"),
+                                Some(0),
+                            )
+                            .on_hover_text(
+                                "Match Windows' HDR setting of the same name, so HUD white \
+                                 comes out white",
+                            )
+                            .changed()
+                            {
+                                changed.set(true);
+                            }
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Capture scale:");
+                        ui.add_space(8.0);
+                        if add_scroll_slider(
+                            ui,
+                            &mut state.config.capture.capture_scale,
+                            MIN_CAPTURE_SCALE..=1.0,
+                            Some(0.05),
+                            Some("x"),
+                            Some(2),
+                        )
+                        .on_hover_text(
+                            "Shrink frames as they're captured, e.g. 0.5x for a 4K game whose \
+                             HUD text is big enough to read at half size: half the memory \
+                             and less time reading each frame. Zones follow; labeled regions \
+                             found at another scale need finding again. Applies the next \
+                             time capture starts; frames kept on the GPU aren't shrunk.",
+                        )
+                        .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("When the game closes:");
+                        ui.add_space(8.0);
+                        let on_game_exit = &mut state.config.capture.on_game_exit;
+                        egui::ComboBox::from_id_salt("on_game_exit")
+                            .selected_text(on_game_exit.name())
+                            .show_ui(ui, |ui| {
+                                for action in GameExitAction::ALL {
+                                    if ui
+                                        .selectable_value(on_game_exit, action, action.name())
+                                        .changed()
+                                    {
+                                        changed.set(true);
+                                    }
+                                }
+                            });
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Capture stops and the session is logged either way; the summary \
+                             tip shows the playtime and tips shown.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    render_replay_settings(ui, &mut state.config.capture.replay, &changed);
+
                     let mut target = state
                         .config
                         .capture
@@ -195,6 +464,8 @@ pub fn render_settings_view(
                             } else {
                                 Some(target)
                             };
+                            // A typed title is matched by title
+                            state.config.capture.target_executable = None;
                             changed.set(true);
                         }
                     });
@@ -288,6 +559,60 @@ pub fn render_settings_view(
                             }
                         });
                     });
+
+                    ui.add_space(8.0);
+                    ui.label("When the overlay can't show a tip:");
+                    let mut state = shared_state.write();
+                    let routes = &mut state.config.overlay.tip_fallback;
+                    egui::Grid::new("tip_fallback_grid")
+                        .num_columns(2)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            for (band, fallback) in [
+                                ("Low priority", &mut routes.low),
+                                ("Medium priority", &mut routes.medium),
+                                ("High priority", &mut routes.high),
+                                ("Critical priority", &mut routes.critical),
+                            ] {
+                                ui.label(band);
+                                egui::ComboBox::from_id_salt(("tip_fallback", band))
+                                    .selected_text(fallback.name())
+                                    .show_ui(ui, |ui| {
+                                        for option in TipFallback::ALL {
+                                            if ui
+                                                .selectable_value(fallback, option, option.name())
+                                                .changed()
+                                            {
+                                                changed.set(true);
+                                            }
+                                        }
+                                    });
+                                ui.end_row();
+                            }
+                        });
+                    ui.label(
+                        RichText::new(
+                            "Applies while the overlay is disabled, failed to start, or is \
+                             hidden by a game in exclusive full screen.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Second monitor mode:");
+                        if ui
+                            .checkbox(&mut state.config.overlay.second_monitor_mode, "")
+                            .on_hover_text(
+                                "While the game runs in exclusive full screen, move the \
+                                 overlay to another monitor so tips stay visible",
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
                 }
             });
 
@@ -340,6 +665,29 @@ pub fn render_settings_view(
                         }
                     });
 
+                    ui.horizontal(|ui| {
+                        ui.label("Adapt capture FPS:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.adaptive_capture_fps, "")
+                            .on_hover_text(
+                                "Lower the capture frame rate while GamersToolKit uses more CPU \
+                                 than the limit above, and raise it back once it doesn't",
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "CPU usage counts all cores, as in Task Manager. Without adapting, \
+                             the limit is only shown, not kept.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
                     ui.horizontal(|ui| {
                         ui.label("Max memory:");
                         ui.add_space(8.0);
@@ -374,37 +722,1119 @@ pub fn render_settings_view(
                             .size(11.0)
                             .color(ThemeColors::TEXT_MUTED),
                     );
-                }
-            });
 
-        ui.add_space(24.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Pause when AFK after:");
+                        ui.add_space(8.0);
+                        let mut minutes = state.config.performance.afk_pause_minutes as f32;
+                        if add_scroll_slider(
+                            ui,
+                            &mut minutes,
+                            0.0..=60.0,
+                            Some(1.0),
+                            Some(" min"),
+                            None,
+                        )
+                        .changed()
+                        {
+                            state.config.performance.afk_pause_minutes = minutes as u32;
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Zone OCR and screen recognition pause while neither your input nor \
+                             the picture changes, and resume as soon as either does. Capture \
+                             keeps running. 0 never pauses.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
 
-        // Reset button and auto-save indicator
-        ui.horizontal(|ui| {
-            if ui
-                .add(egui::Button::new("Reset to Defaults").min_size(egui::vec2(120.0, 36.0)))
-                .clicked()
-            {
-                let mut state = shared_state.write();
-                state.config = crate::config::AppConfig::default();
-                state.overlay_config.opacity = state.config.overlay.opacity;
-                state.overlay_config.enabled = state.config.overlay.enabled;
-                changed.set(true);
-            }
+                    ui.horizontal(|ui| {
+                        ui.label("GPU template matching:");
+                        ui.add_space(8.0);
+                        if ui.checkbox(&mut state.config.vision.use_gpu, "").changed() {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Compare screen templates on the GPU; uses the CPU when no GPU \
+                             is available",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
 
-            ui.add_space(16.0);
+                    ui.horizontal(|ui| {
+                        ui.label("GPU:");
+                        ui.add_space(8.0);
+                        let state = &mut *state;
+                        let adapters = &state.runtime.gpu_adapters;
+                        let selected = &mut state.config.vision.gpu_adapter;
+                        let selected_text = match selected.as_deref() {
+                            None => "Automatic".to_string(),
+                            Some(name) => match gpu_adapters::find_adapter(adapters, name) {
+                                Some(adapter) => adapter.label(),
+                                None => format!("{} (not found)", name),
+                            },
+                        };
+                        egui::ComboBox::from_id_salt("gpu_adapter")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                if ui.selectable_value(selected, None, "Automatic").changed() {
+                                    changed.set(true);
+                                }
+                                for adapter in adapters {
+                                    if ui
+                                        .selectable_value(
+                                            selected,
+                                            Some(adapter.name.clone()),
+                                            adapter.label(),
+                                        )
+                                        .changed()
+                                    {
+                                        changed.set(true);
+                                    }
+                                }
+                            });
+                    });
+                    ui.label(
+                        RichText::new(
+                            "GPU for PaddleOCR (DirectML) and template matching; pick the one \
+                             the game runs on. PaddleOCR switches the next time it loads",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
 
-            // Auto-save indicator
-            ui.label(
-                RichText::new("Settings are saved automatically")
-                    .size(12.0)
-                    .color(ThemeColors::TEXT_MUTED),
-            );
-        });
-    });
+                    ui.add_space(8.0);
 
-    // Apply changes - triggers auto-save in the dashboard app
-    if changed.get() {
-        view_state.has_unsaved_changes = true;
+                    ui.horizontal(|ui| {
+                        ui.label("Low priority capture and vision:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.low_priority_workers, "")
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Run capture and OCR below normal priority so the game gets the \
+                             CPU first; the dashboard may respond a little slower",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.horizontal(|ui| {
+                        ui.label("Efficiency mode in full screen:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.efficiency_mode_fullscreen, "")
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Switch to Windows' efficiency mode while a full-screen game is \
+                             in front; the overlay may update less smoothly",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Skip unchanged zones:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.skip_unchanged_zones, "")
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Read a zone again only when its pixels changed; most HUD values \
+                             stay the same for seconds at a time",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.horizontal(|ui| {
+                        ui.label("Load PaddleOCR at startup:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.warm_up_models, "")
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Load the OCR models in the background when PaddleOCR is the \
+                             backend, so the first reading in game doesn't stall",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.horizontal(|ui| {
+                        ui.label("Unload PaddleOCR after idle:");
+                        ui.add_space(8.0);
+                        let mut minutes = state.config.performance.model_idle_unload_minutes as f32;
+                        if add_scroll_slider(
+                            ui,
+                            &mut minutes,
+                            0.0..=120.0,
+                            Some(5.0),
+                            Some(" min"),
+                            None,
+                        )
+                        .changed()
+                        {
+                            state.config.performance.model_idle_unload_minutes = minutes as u32;
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new("Free the models' memory when unused; 0 keeps them loaded")
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Save power on battery:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.performance.battery_saver, "")
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Switch to the low-power preset below while a laptop runs on \
+                             battery",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_enabled_ui(state.config.performance.battery_saver, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Capture FPS on battery:");
+                            ui.add_space(8.0);
+                            let mut fps = state.config.performance.battery_max_fps as f32;
+                            if add_scroll_slider(ui, &mut fps, 1.0..=30.0, Some(1.0), None, None)
+                                .changed()
+                            {
+                                state.config.performance.battery_max_fps = fps as u32;
+                                changed.set(true);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Zone OCR interval on battery:");
+                            ui.add_space(8.0);
+                            let mut interval =
+                                state.config.performance.battery_ocr_interval_ms as f32;
+                            if add_scroll_slider(
+                                ui,
+                                &mut interval,
+                                100.0..=5000.0,
+                                Some(100.0),
+                                Some(" ms"),
+                                None,
+                            )
+                            .changed()
+                            {
+                                state.config.performance.battery_ocr_interval_ms = interval as u32;
+                                changed.set(true);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Pause screen recognition on battery:");
+                            ui.add_space(8.0);
+                            if ui
+                                .checkbox(
+                                    &mut state.config.performance.battery_disable_template_matching,
+                                    "",
+                                )
+                                .changed()
+                            {
+                                changed.set(true);
+                            }
+                        });
+                    });
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Drop tips older than:");
+                        ui.add_space(8.0);
+                        let mut budget = state.config.performance.tip_latency_budget_ms as f32;
+                        if add_scroll_slider(
+                            ui,
+                            &mut budget,
+                            0.0..=5000.0,
+                            Some(250.0),
+                            Some(" ms"),
+                            None,
+                        )
+                        .changed()
+                        {
+                            state.config.performance.tip_latency_budget_ms = budget as u32;
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Tips about what was on screen longer ago than this (counted from \
+                             capture) are dropped instead of shown late; 0 shows them all",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    let runtime = &state.runtime;
+                    let latency = &runtime.latency;
+                    egui::Grid::new("latency_status")
+                        .num_columns(2)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            for (name, value) in [
+                                ("Frame pickup:", format_latency(&latency.frame_pickup)),
+                                ("Zone readings:", format_latency(&latency.zone_ocr)),
+                                ("Zone OCR time:", format_latency(&latency.zone_ocr_time)),
+                                ("Capture to tip:", format_latency(&latency.tip)),
+                                ("Stale tips dropped:", latency.stale_tips.to_string()),
+                                ("Frames dropped:", runtime.dropped_frames.to_string()),
+                                (
+                                    "Overlay queue:",
+                                    format!(
+                                        "{} waiting, {} dropped",
+                                        runtime.overlay_queue_depth, runtime.dropped_overlay_events
+                                    ),
+                                ),
+                            ] {
+                                ui.label(
+                                    RichText::new(name)
+                                        .size(12.0)
+                                        .color(ThemeColors::TEXT_MUTED),
+                                );
+                                ui.label(RichText::new(value).size(12.0));
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    let qos = state.runtime.qos;
+                    let power = state.runtime.power;
+                    let power_source = match (power.on_battery, power.power_saving) {
+                        (false, _) => "plugged in",
+                        (true, false) => "battery",
+                        (true, true) => "battery (low power)",
+                    };
+                    let capture = qos
+                        .capture_priority
+                        .map_or("not capturing", qos::thread_priority_name);
+                    let process = if qos.efficiency_mode {
+                        "Efficiency mode".to_string()
+                    } else {
+                        qos::process_priority_name().to_string()
+                    };
+                    let cpu_budget = state.runtime.cpu_budget;
+                    let cpu_usage = match (cpu_budget.cpu_percent, cpu_budget.fps_limit) {
+                        (None, _) => "measuring".to_string(),
+                        (Some(cpu), None) => format!("{:.1}%", cpu),
+                        (Some(cpu), Some(fps)) => {
+                            format!("{:.1}% (capture limited to {} FPS)", cpu, fps)
+                        }
+                    };
+                    egui::Grid::new("qos_status")
+                        .num_columns(2)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            for (name, value) in [
+                                ("Capture thread:", capture),
+                                (
+                                    "Vision thread:",
+                                    qos::thread_priority_name(qos.vision_priority),
+                                ),
+                                ("Process:", process.as_str()),
+                                (
+                                    "Full-screen app:",
+                                    match (qos.fullscreen_app, qos.exclusive_fullscreen) {
+                                        (_, true) => "exclusive, in front",
+                                        (true, false) => "in front",
+                                        (false, false) => "none",
+                                    },
+                                ),
+                                ("Power source:", power_source),
+                                ("CPU usage:", cpu_usage.as_str()),
+                            ] {
+                                ui.label(
+                                    RichText::new(name)
+                                        .size(12.0)
+                                        .color(ThemeColors::TEXT_MUTED),
+                                );
+                                ui.label(RichText::new(value).size(12.0));
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+        ui.add_space(16.0);
+
+        // Feature Flags
+        let is_features_expanded = view_state.expanded_section == Some(SettingsSection::Features);
+        egui::Frame::none()
+            .fill(ThemeColors::BG_MEDIUM)
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                let header_response = ui
+                    .horizontal(|ui| {
+                        let arrow = if is_features_expanded { "v" } else { ">" };
+                        ui.label(
+                            RichText::new(arrow)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.add_space(8.0);
+                        ui.heading(RichText::new("Features").size(16.0));
+                    })
+                    .response;
+
+                if header_response.interact(egui::Sense::click()).clicked() {
+                    view_state.expanded_section = if is_features_expanded {
+                        None
+                    } else {
+                        Some(SettingsSection::Features)
+                    };
+                }
+
+                if is_features_expanded {
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+
+                    ui.label(
+                        RichText::new(
+                            "Switch off whole subsystems on slower machines, or to find out \
+                             which one uses the CPU.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+                    ui.add_space(8.0);
+
+                    let mut state = shared_state.write();
+                    let features = &mut state.config.features;
+                    for (enabled, name, note) in [
+                        (
+                            &mut features.overlay,
+                            "Overlay:",
+                            "Tips go where the fallback in Overlay settings sends them",
+                        ),
+                        (
+                            &mut features.ai,
+                            "AI models:",
+                            "PaddleOCR and ensemble zones; Windows OCR is used when off",
+                        ),
+                        (
+                            &mut features.integrations,
+                            "Integrations:",
+                            "Windows notifications and watching for games to launch",
+                        ),
+                        (
+                            &mut features.server,
+                            "MCP server:",
+                            "Lets AI assistants connect with --mcp (applies on next start)",
+                        ),
+                        (
+                            &mut features.template_matching,
+                            "Template matching:",
+                            "Screen recognition",
+                        ),
+                    ] {
+                        ui.horizontal(|ui| {
+                            ui.label(name);
+                            ui.add_space(8.0);
+                            if ui.checkbox(enabled, "").changed() {
+                                changed.set(true);
+                            }
+                            ui.label(
+                                RichText::new(note)
+                                    .size(11.0)
+                                    .color(ThemeColors::TEXT_MUTED),
+                            );
+                        });
+                    }
+                }
+            });
+
+        ui.add_space(16.0);
+
+        // Wellness Settings
+        let is_wellness_expanded = view_state.expanded_section == Some(SettingsSection::Wellness);
+        egui::Frame::none()
+            .fill(ThemeColors::BG_MEDIUM)
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                let header_response = ui
+                    .horizontal(|ui| {
+                        let arrow = if is_wellness_expanded { "v" } else { ">" };
+                        ui.label(
+                            RichText::new(arrow)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.add_space(8.0);
+                        ui.heading(RichText::new("Wellness").size(16.0));
+                    })
+                    .response;
+
+                if header_response.interact(egui::Sense::click()).clicked() {
+                    view_state.expanded_section = if is_wellness_expanded {
+                        None
+                    } else {
+                        Some(SettingsSection::Wellness)
+                    };
+                }
+
+                if is_wellness_expanded {
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+
+                    let mut state = shared_state.write();
+
+                    ui.horizontal(|ui| {
+                        ui.label("Break reminder every:");
+                        ui.add_space(8.0);
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut state.config.wellness.break_reminder_minutes,
+                                )
+                                .range(0..=600)
+                                .suffix(" min"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new("Shows a tip on the overlay while capturing (0 = off)")
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Playtime limit:");
+                        ui.add_space(8.0);
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut state.config.wellness.overlay_nag_minutes,
+                                )
+                                .range(0..=1440)
+                                .suffix(" min"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "After this much play without a break, a banner stays on the \
+                             overlay until capture stops (0 = off). Pauses shorter than 5 \
+                             minutes don't count as a break.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Pace nudge every:");
+                        ui.add_space(8.0);
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut state.config.coach.nudge_minutes)
+                                    .range(0..=240)
+                                    .suffix(" min"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                        ui.add_space(16.0);
+                        ui.label("when off by:");
+                        ui.add_space(8.0);
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut state.config.coach.nudge_threshold_percent,
+                                )
+                                .range(5..=100)
+                                .suffix("%"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Coach mode: tells on the overlay when a zone's pace (gold, kills) \
+                             is off your average over previous sessions (0 = off). Compare \
+                             sessions in Data > Coach.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+                }
+            });
+
+        ui.add_space(16.0);
+
+        // Quiet Hours Settings
+        let is_quiet_expanded = view_state.expanded_section == Some(SettingsSection::QuietHours);
+        egui::Frame::none()
+            .fill(ThemeColors::BG_MEDIUM)
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                let header_response = ui
+                    .horizontal(|ui| {
+                        let arrow = if is_quiet_expanded { "v" } else { ">" };
+                        ui.label(
+                            RichText::new(arrow)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.add_space(8.0);
+                        ui.heading(RichText::new("Quiet Hours").size(16.0));
+                    })
+                    .response;
+
+                if header_response.interact(egui::Sense::click()).clicked() {
+                    view_state.expanded_section = if is_quiet_expanded {
+                        None
+                    } else {
+                        Some(SettingsSection::QuietHours)
+                    };
+                }
+
+                if is_quiet_expanded {
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+
+                    let mut state = shared_state.write();
+                    let quiet_now = state.runtime.quiet_hours_active;
+                    let quiet = &mut state.config.quiet_hours;
+
+                    ui.horizontal(|ui| {
+                        ui.label("Quiet hours:");
+                        ui.add_space(8.0);
+                        if ui.checkbox(&mut quiet.enabled, "").changed() {
+                            changed.set(true);
+                        }
+                        ui.add_space(16.0);
+                        ui.label("from");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut quiet.start_hour)
+                                    .range(0..=23)
+                                    .suffix(":00"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                        ui.label("to");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut quiet.end_hour)
+                                    .range(0..=23)
+                                    .suffix(":00"),
+                            )
+                            .changed()
+                        {
+                            changed.set(true);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("On:");
+                        ui.add_space(8.0);
+                        for (day, name) in quiet
+                            .days
+                            .iter_mut()
+                            .zip(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"])
+                        {
+                            if ui.toggle_value(day, name).changed() {
+                                changed.set(true);
+                            }
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Only critical tips are shown during quiet hours (local time). \
+                             Hours that end before they start run past midnight.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Follow Windows do-not-disturb:");
+                        ui.add_space(8.0);
+                        if ui.checkbox(&mut quiet.follow_windows, "").changed() {
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Also keep quiet while Windows is in presentation mode or quiet time",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    let (status, color) = if quiet_now {
+                        (
+                            "Quiet now: only critical tips are shown",
+                            ThemeColors::ACCENT_WARNING,
+                        )
+                    } else {
+                        ("Not quiet: all tips are shown", ThemeColors::TEXT_SECONDARY)
+                    };
+                    ui.label(RichText::new(status).size(12.0).color(color));
+                }
+            });
+
+        ui.add_space(16.0);
+
+        // Telemetry Settings
+        let is_telemetry_expanded = view_state.expanded_section == Some(SettingsSection::Telemetry);
+        egui::Frame::none()
+            .fill(ThemeColors::BG_MEDIUM)
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                let header_response = ui
+                    .horizontal(|ui| {
+                        let arrow = if is_telemetry_expanded { "v" } else { ">" };
+                        ui.label(
+                            RichText::new(arrow)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.add_space(8.0);
+                        ui.heading(RichText::new("Telemetry").size(16.0));
+                    })
+                    .response;
+
+                if header_response.interact(egui::Sense::click()).clicked() {
+                    view_state.expanded_section = if is_telemetry_expanded {
+                        None
+                    } else {
+                        Some(SettingsSection::Telemetry)
+                    };
+                }
+
+                if is_telemetry_expanded {
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+
+                    let mut state = shared_state.write();
+
+                    ui.horizontal(|ui| {
+                        ui.label("Share anonymous usage and crash reports:");
+                        ui.add_space(8.0);
+                        if ui
+                            .checkbox(&mut state.config.telemetry.enabled, "")
+                            .changed()
+                        {
+                            let enabled = state.config.telemetry.enabled;
+                            if enabled && state.config.telemetry.install_id.is_none() {
+                                state.config.telemetry.install_id =
+                                    Some(uuid::Uuid::new_v4().to_string());
+                            }
+                            crate::telemetry::set_enabled(enabled);
+                            view_state.telemetry_preview = None;
+                            changed.set(true);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Counts which features are used (no screen contents, text or \
+                             file names) and reports crashes, with user names taken out of any paths in \
+                             them. Off unless you turn it on.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    let destination = match state.config.telemetry.endpoint {
+                        Some(ref endpoint) => format!("Reports are sent to {}", endpoint),
+                        None => "No endpoint configured, reports stay on this computer".to_string(),
+                    };
+                    ui.label(
+                        RichText::new(destination)
+                            .size(11.0)
+                            .color(ThemeColors::TEXT_SECONDARY),
+                    );
+
+                    ui.add_space(8.0);
+
+                    let telemetry_dir = crate::storage::get_data_dir()
+                        .ok()
+                        .map(|dir| crate::telemetry::telemetry_dir(&dir));
+
+                    ui.horizontal(|ui| {
+                        let preview_label = if view_state.telemetry_preview.is_some() {
+                            "Hide Payload"
+                        } else {
+                            "Preview Payload"
+                        };
+                        if ui.button(preview_label).clicked() {
+                            view_state.telemetry_preview =
+                                match (&view_state.telemetry_preview, &telemetry_dir) {
+                                    (None, Some(dir)) => {
+                                        let payload = crate::telemetry::build_payload(
+                                            &state.config.telemetry,
+                                            dir,
+                                            &state.runtime.usage,
+                                        );
+                                        serde_json::to_string_pretty(&payload).ok()
+                                    }
+                                    _ => None,
+                                };
+                        }
+
+                        if ui.button("Delete Pending Data").clicked() {
+                            if let Some(ref dir) = telemetry_dir {
+                                if let Err(e) = crate::telemetry::clear_pending(dir) {
+                                    state.runtime.set_error(crate::shared::AppError::Storage(
+                                        format!("Failed to delete telemetry data: {}", e),
+                                    ));
+                                }
+                            }
+                            state.runtime.usage = Default::default();
+                            view_state.telemetry_preview = None;
+                        }
+                    });
+
+                    if let Some(ref preview) = view_state.telemetry_preview {
+                        ui.add_space(8.0);
+                        egui::ScrollArea::vertical()
+                            .id_salt("telemetry_preview")
+                            .max_height(240.0)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new(preview).monospace().size(11.0));
+                            });
+                    }
+                }
+            });
+
+        ui.add_space(16.0);
+
+        // Network Settings
+        let is_network_expanded = view_state.expanded_section == Some(SettingsSection::Network);
+        egui::Frame::none()
+            .fill(ThemeColors::BG_MEDIUM)
+            .rounding(egui::Rounding::same(8.0))
+            .inner_margin(16.0)
+            .show(ui, |ui| {
+                let header_response = ui
+                    .horizontal(|ui| {
+                        let arrow = if is_network_expanded { "v" } else { ">" };
+                        ui.label(
+                            RichText::new(arrow)
+                                .size(12.0)
+                                .color(ThemeColors::TEXT_MUTED),
+                        );
+                        ui.add_space(8.0);
+                        ui.heading(RichText::new("Network").size(16.0));
+                    })
+                    .response;
+
+                if header_response.interact(egui::Sense::click()).clicked() {
+                    view_state.expanded_section = if is_network_expanded {
+                        None
+                    } else {
+                        Some(SettingsSection::Network)
+                    };
+                }
+
+                if is_network_expanded {
+                    ui.add_space(16.0);
+                    ui.separator();
+                    ui.add_space(12.0);
+
+                    let mut state = shared_state.write();
+                    let network = &mut state.config.network;
+                    let mut network_changed = false;
+
+                    ui.horizontal(|ui| {
+                        ui.label("Proxy:");
+                        ui.add_space(8.0);
+                        let mut proxy_url = network.proxy_url.clone().unwrap_or_default();
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut proxy_url)
+                                    .hint_text("http://proxy:8080")
+                                    .desired_width(240.0),
+                            )
+                            .changed()
+                        {
+                            network.proxy_url = (!proxy_url.trim().is_empty()).then_some(proxy_url);
+                            network_changed = true;
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "Used for model downloads and telemetry. Leave empty to use the \
+                             HTTPS_PROXY environment variable.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("CA bundle:");
+                        ui.add_space(8.0);
+                        let mut ca_bundle = network
+                            .ca_bundle
+                            .as_ref()
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_default();
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut ca_bundle)
+                                    .hint_text("C:\\certs\\corporate-ca.pem")
+                                    .desired_width(240.0),
+                            )
+                            .changed()
+                        {
+                            network.ca_bundle = (!ca_bundle.trim().is_empty())
+                                .then(|| std::path::PathBuf::from(ca_bundle.trim()));
+                            network_changed = true;
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "PEM file with extra root certificates, for networks that \
+                             inspect HTTPS traffic.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    ui.add_space(8.0);
+                    ui.label("Model mirrors:");
+                    let mut mirrors = network.model_mirrors.join("\n");
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut mirrors)
+                                .hint_text("https://mirror.example.com/paddleocr-onnx")
+                                .desired_rows(2)
+                                .desired_width(360.0),
+                        )
+                        .changed()
+                    {
+                        // Blank lines are kept while editing and skipped when downloading
+                        network.model_mirrors = mirrors.split('\n').map(str::to_string).collect();
+                        network_changed = true;
+                    }
+                    ui.label(
+                        RichText::new(
+                            "One base URL per line, laid out like the Hugging Face repo. \
+                             Tried in order before Hugging Face when downloading models.",
+                        )
+                        .size(11.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                    );
+
+                    if network_changed {
+                        view_state.network_error = crate::network::validate(network)
+                            .err()
+                            .map(|e| format!("{:#}", e));
+                        crate::network::set_settings(network.clone());
+                        changed.set(true);
+                    }
+
+                    ui.add_space(8.0);
+                    let (status, color) = match view_state.network_error {
+                        Some(ref error) => (error.clone(), ThemeColors::ACCENT_ERROR),
+                        None => (
+                            crate::network::proxy_description(network),
+                            ThemeColors::TEXT_SECONDARY,
+                        ),
+                    };
+                    ui.label(RichText::new(status).size(12.0).color(color));
+                }
+            });
+
+        ui.add_space(24.0);
+
+        // Reset button and auto-save indicator
+        ui.horizontal(|ui| {
+            if ui
+                .add(egui::Button::new("Reset to Defaults").min_size(egui::vec2(120.0, 36.0)))
+                .clicked()
+            {
+                let mut state = shared_state.write();
+                state.config = crate::config::AppConfig::default();
+                state.overlay_config.opacity = state.config.overlay.opacity;
+                state.overlay_config.enabled = state.config.overlay.enabled;
+                crate::telemetry::set_enabled(state.config.telemetry.enabled);
+                crate::network::set_settings(state.config.network.clone());
+                view_state.telemetry_preview = None;
+                view_state.network_error = None;
+                changed.set(true);
+            }
+
+            ui.add_space(16.0);
+
+            // Auto-save indicator
+            ui.label(
+                RichText::new("Settings are saved automatically")
+                    .size(12.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+        });
+    });
+
+    // Apply changes - triggers auto-save in the dashboard app
+    if changed.get() {
+        view_state.has_unsaved_changes = true;
+    }
+}
+
+/// Replay buffer length, frame rate, size and format
+fn render_replay_settings(
+    ui: &mut egui::Ui,
+    replay: &mut crate::config::ReplaySettings,
+    changed: &Cell<bool>,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Replay buffer:");
+        ui.add_space(8.0);
+        if ui
+            .checkbox(&mut replay.enabled, "")
+            .on_hover_text(
+                "Keep the last seconds of capture in memory, to save with the replay \
+                 hotkey, from the Capture view or when a color trigger fires",
+            )
+            .changed()
+        {
+            changed.set(true);
+        }
+    });
+    if !replay.enabled {
+        return;
+    }
+
+    let sliders: [(&str, &mut u32, std::ops::RangeInclusive<f32>, f64, &str); 3] = [
+        ("Length:", &mut replay.seconds, 5.0..=120.0, 5.0, " s"),
+        (
+            "Frames per second:",
+            &mut replay.fps,
+            1.0..=30.0,
+            1.0,
+            " fps",
+        ),
+        (
+            "Width:",
+            &mut replay.max_width,
+            320.0..=1920.0,
+            160.0,
+            " px",
+        ),
+    ];
+    for (label, value, range, step, suffix) in sliders {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            ui.add_space(8.0);
+            let mut number = *value as f32;
+            if add_scroll_slider(ui, &mut number, range, Some(step), Some(suffix), None).changed() {
+                *value = number as u32;
+                changed.set(true);
+            }
+        });
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Save as:");
+        ui.add_space(8.0);
+        egui::ComboBox::from_id_salt("replay_format")
+            .selected_text(replay.format.name())
+            .show_ui(ui, |ui| {
+                for format in ReplayFormat::ALL {
+                    if ui
+                        .selectable_value(&mut replay.format, format, format.name())
+                        .changed()
+                    {
+                        changed.set(true);
+                    }
+                }
+            });
+    });
+
+    // 16:9 frames, 4 bytes a pixel
+    let frame_bytes = u64::from(replay.max_width) * u64::from(replay.max_width) * 9 / 16 * 4;
+    let total = frame_bytes * u64::from(replay.seconds) * u64::from(replay.fps);
+    ui.label(
+        RichText::new(format!(
+            "Takes about {} MB of memory while capturing",
+            total / 1_000_000
+        ))
+        .size(11.0)
+        .color(ThemeColors::TEXT_MUTED),
+    );
+}
+
+/// Average and 95th percentile of a latency stage, for display
+fn format_latency(samples: &LatencySamples) -> String {
+    match (samples.average(), samples.percentile(95)) {
+        (Some(average), Some(p95)) => {
+            format!("{} ms avg, {} ms p95", average.as_millis(), p95.as_millis())
+        }
+        _ => "No data yet".to_string(),
     }
 }
@@ -4,13 +4,52 @@ use egui::RichText;
 use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 
-use crate::capture::ScreenCapture;
-use crate::dashboard::components::add_scroll_slider;
-use crate::dashboard::state::{OcrGranularity, VisionViewState};
+use crate::capture::{FrameConsumer, ScreenCapture};
+use crate::dashboard::components::{add_scroll_slider, remembered_section};
+use crate::dashboard::state::{OcrGranularity, SampleAction, VisionViewState};
 use crate::dashboard::theme::ThemeColors;
+use crate::dashboard::views::color_triggers::{
+    draw_color_trigger_markers, pick_trigger_color, render_color_triggers,
+};
+use crate::dashboard::views::labeled_regions::render_labeled_regions;
 use crate::dashboard::views::zone_ocr::{draw_zone_overlays, render_zone_ocr_panel};
 use crate::shared::SharedAppState;
-use crate::vision::OcrBackend;
+use crate::vision::backend_compare::{BackendComparison, RegionDiff};
+use crate::vision::color_trigger::{patch_color, pixel_at};
+use crate::vision::{OcrBackend, OcrQuality};
+
+/// Display name of an OCR backend
+fn backend_name(backend: OcrBackend) -> &'static str {
+    match backend {
+        OcrBackend::WindowsOcr => "Windows OCR",
+        OcrBackend::PaddleOcr => "PaddleOCR",
+    }
+}
+
+/// Render the PaddleOCR quality vs speed slider
+fn render_quality_slider(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    let mut level = OcrQuality::ALL
+        .iter()
+        .position(|q| *q == view_state.ocr_quality)
+        .unwrap_or(1);
+    let slider = egui::Slider::new(&mut level, 0..=OcrQuality::ALL.len() - 1)
+        .show_value(false)
+        .text(RichText::new(view_state.ocr_quality.name()).color(ThemeColors::TEXT_SECONDARY));
+    let response = ui.add(slider).on_hover_text(
+        "Quality vs speed: lower detection resolution is faster but misses small text",
+    );
+    if response.changed() {
+        view_state.ocr_quality = OcrQuality::ALL[level];
+    }
+
+    if view_state.ocr_quality == OcrQuality::Fast && !view_state.quantized_models_ready {
+        ui.label(RichText::new("(standard models)").color(ThemeColors::TEXT_MUTED))
+            .on_hover_text(
+                "Put det.int8.onnx and rec.int8.onnx in the models folder to use \
+                 the quantized models",
+            );
+    }
+}
 
 /// Render the vision/OCR view
 pub fn render_vision_view(
@@ -19,11 +58,16 @@ pub fn render_vision_view(
     shared_state: &Arc<RwLock<SharedAppState>>,
     capture_manager: &Arc<Mutex<Option<ScreenCapture>>>,
 ) {
-    // Get a frame for OCR if capturing
-    let preview_frame = {
+    // Get a frame for OCR if capturing (a pasted clipboard image or a frozen
+    // frame holds the preview)
+    let preview_frame = if let Some(frame) = view_state.pending_preview.take() {
+        Some(Arc::new(frame))
+    } else if view_state.showing_clipboard_image || view_state.frozen_frame.is_some() {
+        None
+    } else {
         let capture_guard = capture_manager.lock();
         if let Some(ref capture) = *capture_guard {
-            capture.try_next_frame()
+            capture.try_next_frame_for(FrameConsumer::VisionPreview)
         } else {
             None
         }
@@ -33,7 +77,35 @@ pub fn render_vision_view(
     ui.horizontal(|ui| {
         ui.heading(RichText::new("Vision / OCR").size(24.0).strong());
         ui.add_space(24.0);
-        render_ocr_backend_inline(ui, view_state);
+        let ai_enabled = shared_state.read().config.features.ai;
+        render_ocr_backend_inline(ui, view_state, ai_enabled);
+        ui.add_space(12.0);
+        if ui
+            .button("Watch")
+            .on_hover_text(
+                "Pin zone values, flags and timers, and alert when they meet a condition",
+            )
+            .clicked()
+        {
+            view_state.open_watch = true;
+        }
+        if ui
+            .button("Step")
+            .on_hover_text(
+                "Pause zone OCR and screen recognition and step them frame by frame, \
+                 with the crops, match scores and rule checks of each frame",
+            )
+            .clicked()
+        {
+            view_state.open_pipeline_debug = true;
+        }
+        if ui
+            .button("Reference")
+            .on_hover_text("Variables, functions and placeholders for rules and tips")
+            .clicked()
+        {
+            view_state.open_reference = true;
+        }
     });
     ui.add_space(12.0);
 
@@ -69,24 +141,28 @@ pub fn render_vision_view(
 }
 
 /// Render inline OCR backend selector and status
-fn render_ocr_backend_inline(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+fn render_ocr_backend_inline(
+    ui: &mut egui::Ui,
+    view_state: &mut VisionViewState,
+    ai_enabled: bool,
+) {
     // Backend selector
     egui::ComboBox::from_id_salt("ocr_backend")
-        .selected_text(match view_state.selected_backend {
-            OcrBackend::WindowsOcr => "Windows OCR",
-            OcrBackend::PaddleOcr => "PaddleOCR",
-        })
+        .selected_text(backend_name(view_state.selected_backend))
         .show_ui(ui, |ui| {
             ui.selectable_value(
                 &mut view_state.selected_backend,
                 OcrBackend::WindowsOcr,
                 "Windows OCR (Recommended)",
             );
-            ui.selectable_value(
-                &mut view_state.selected_backend,
-                OcrBackend::PaddleOcr,
-                "PaddleOCR (ONNX)",
-            );
+            ui.add_enabled_ui(ai_enabled, |ui| {
+                ui.selectable_value(
+                    &mut view_state.selected_backend,
+                    OcrBackend::PaddleOcr,
+                    "PaddleOCR (ONNX)",
+                )
+                .on_disabled_hover_text("AI models are turned off in Settings > Features");
+            });
         });
 
     ui.add_space(8.0);
@@ -113,6 +189,12 @@ fn render_ocr_backend_inline(ui: &mut egui::Ui, view_state: &mut VisionViewState
 
     ui.add_space(8.0);
 
+    // Quality vs speed (PaddleOCR only)
+    if view_state.selected_backend == OcrBackend::PaddleOcr {
+        render_quality_slider(ui, view_state);
+        ui.add_space(8.0);
+    }
+
     // Status indicator
     let (status_text, status_color, needs_init) = match view_state.selected_backend {
         OcrBackend::WindowsOcr => {
@@ -123,7 +205,9 @@ fn render_ocr_backend_inline(ui: &mut egui::Ui, view_state: &mut VisionViewState
             }
         }
         OcrBackend::PaddleOcr => {
-            if view_state.ocr_initialized {
+            if !view_state.corrupted_models.is_empty() {
+                ("Model corrupted", ThemeColors::ACCENT_ERROR, false)
+            } else if view_state.ocr_initialized {
                 ("Ready", ThemeColors::ACCENT_SUCCESS, false)
             } else if view_state.models_ready {
                 ("Models ready", ThemeColors::ACCENT_WARNING, true)
@@ -143,12 +227,30 @@ fn render_ocr_backend_inline(ui: &mut egui::Ui, view_state: &mut VisionViewState
     // Download button for PaddleOCR
     if view_state.selected_backend == OcrBackend::PaddleOcr
         && !view_state.models_ready
+        && view_state.corrupted_models.is_empty()
         && !view_state.is_downloading
         && ui.small_button("Download Models").clicked()
     {
         view_state.pending_download = true;
     }
 
+    // Re-download only the models that failed verification
+    if view_state.selected_backend == OcrBackend::PaddleOcr && !view_state.is_downloading {
+        for (model_type, reason) in &view_state.corrupted_models {
+            if ui
+                .small_button(format!("Re-download {}", model_type.display_name()))
+                .on_hover_text(format!(
+                    "{} is corrupted: {}",
+                    model_type.filename(),
+                    reason
+                ))
+                .clicked()
+            {
+                view_state.pending_repair = Some(*model_type);
+            }
+        }
+    }
+
     if view_state.is_downloading {
         ui.spinner();
     }
@@ -163,12 +265,378 @@ fn render_ocr_backend_inline(ui: &mut egui::Ui, view_state: &mut VisionViewState
     }
 }
 
+/// Color of a region by how the other backend read it
+fn diff_color(diff: RegionDiff) -> egui::Color32 {
+    match diff {
+        RegionDiff::Same => ThemeColors::TEXT_SECONDARY,
+        RegionDiff::Different => ThemeColors::ACCENT_WARNING,
+        RegionDiff::Missing => ThemeColors::ACCENT_ERROR,
+    }
+}
+
+/// Outline the regions the OCR backends read differently: amber where they
+/// read different text, red where only one of them found text
+fn draw_comparison_boxes(
+    ui: &egui::Ui,
+    comparison: &BackendComparison,
+    image_rect: egui::Rect,
+    scale: egui::Vec2,
+) {
+    for region in comparison.sides.iter().flat_map(|side| &side.regions) {
+        if region.diff == RegionDiff::Same {
+            continue;
+        }
+        let (x, y, w, h) = region.bounds;
+        let box_rect = egui::Rect::from_min_size(
+            image_rect.min + egui::vec2(x as f32 * scale.x, y as f32 * scale.y),
+            egui::vec2(w as f32 * scale.x, h as f32 * scale.y),
+        );
+        ui.painter().rect_stroke(
+            box_rect,
+            egui::Rounding::ZERO,
+            egui::Stroke::new(2.0, diff_color(region.diff)),
+        );
+    }
+}
+
+/// Render both OCR backends' readings of the previewed frame side by side
+fn render_backend_comparison(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    if !view_state.compare_backends {
+        return;
+    }
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Backend comparison").size(14.0).strong());
+        if let Some(ref comparison) = view_state.backend_comparison {
+            ui.label(
+                RichText::new(format!("{:.0}% read alike", comparison.agreement() * 100.0))
+                    .size(13.0)
+                    .color(ThemeColors::TEXT_MUTED),
+            );
+        }
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .small_button("Compare again")
+                .on_hover_text("Read the previewed frame with both backends now")
+                .clicked()
+            {
+                view_state.pending_backend_compare = true;
+            }
+        });
+    });
+
+    if let Some(ref error) = view_state.backend_compare_error {
+        ui.label(
+            RichText::new(error)
+                .size(13.0)
+                .color(ThemeColors::ACCENT_ERROR),
+        );
+        return;
+    }
+    let Some(ref comparison) = view_state.backend_comparison else {
+        ui.label(
+            RichText::new("Reading the frame with both backends...")
+                .size(13.0)
+                .color(ThemeColors::TEXT_MUTED),
+        );
+        return;
+    };
+
+    ui.columns(2, |columns| {
+        for (ui, side) in columns.iter_mut().zip(&comparison.sides) {
+            ui.label(
+                RichText::new(format!(
+                    "{}: {} regions, {} differ, {}ms",
+                    backend_name(side.backend),
+                    side.regions.len(),
+                    side.differences(),
+                    side.processing_time_ms
+                ))
+                .size(13.0)
+                .strong(),
+            );
+            egui::ScrollArea::vertical()
+                .id_salt(("backend_comparison", backend_name(side.backend)))
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for region in &side.regions {
+                        let label = ui.label(
+                            RichText::new(&region.text)
+                                .size(13.0)
+                                .color(diff_color(region.diff)),
+                        );
+                        match (region.diff, &region.other_text) {
+                            (RegionDiff::Different, Some(other)) => {
+                                label.on_hover_text(format!("The other backend read: {}", other));
+                            }
+                            (RegionDiff::Missing, _) => {
+                                label.on_hover_text("The other backend found no text here");
+                            }
+                            _ => {}
+                        }
+                    }
+                });
+        }
+    });
+}
+
+/// Render the "Freeze" checkbox that pins the current frame for tuning
+fn render_freeze_toggle(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    let mut frozen = view_state.frozen_frame.is_some();
+    let can_freeze = !view_state.showing_clipboard_image && view_state.last_frame_data.is_some();
+    let response = ui
+        .add_enabled(
+            can_freeze || frozen,
+            egui::Checkbox::new(&mut frozen, "Freeze"),
+        )
+        .on_hover_text(
+            "Pin the current frame so zones and preprocessing can be tuned against a \
+             stable image. Zone readings aren't recorded while frozen.",
+        );
+    if !response.changed() {
+        return;
+    }
+
+    view_state.frozen_ocr_inputs = None;
+    view_state.frozen_frame = if frozen {
+        view_state.last_frame_data.as_ref().map(|data| {
+            crate::capture::CapturedFrame::new(
+                data.clone(),
+                view_state.last_frame_width,
+                view_state.last_frame_height,
+            )
+        })
+    } else {
+        view_state.shown_sample = None;
+        None
+    };
+}
+
+/// Render the latest frame's scene brightness and the dark flag
+fn render_scene_metrics(ui: &mut egui::Ui, shared_state: &Arc<RwLock<SharedAppState>>) {
+    let (is_capturing, scene) = {
+        let shared = shared_state.read();
+        (shared.runtime.is_capturing, shared.runtime.scene)
+    };
+    if !is_capturing {
+        return;
+    }
+
+    let (label, color) = if scene.dark {
+        ("Dark", ThemeColors::ACCENT_WARNING)
+    } else {
+        ("Light", ThemeColors::TEXT_MUTED)
+    };
+    ui.label(
+        RichText::new(format!("{} {:.0}%", label, scene.brightness * 100.0))
+            .size(14.0)
+            .color(color),
+    )
+    .on_hover_text(format!(
+        "Scene brightness {:.0}%, shadows {:.0}%, saturation {:.0}%\n\
+         Rules see these as scene.brightness, scene.shadows, scene.saturation and scene.dark",
+        scene.brightness * 100.0,
+        scene.shadows * 100.0,
+        scene.saturation * 100.0
+    ));
+}
+
+/// Render the eyedropper toggle, averaging radius and last picked color
+fn render_eyedropper_toggle(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    ui.checkbox(&mut view_state.eyedropper, "Eyedropper")
+        .on_hover_text("Show the color under the cursor on the preview; click to keep it");
+    if !view_state.eyedropper {
+        return;
+    }
+
+    ui.add(
+        egui::DragValue::new(&mut view_state.eyedropper_radius)
+            .range(0..=10)
+            .prefix("r ")
+            .suffix(" px"),
+    )
+    .on_hover_text("Average the pixels within this radius (0 = single pixel)");
+
+    if let Some(((x, y), color)) = view_state.picked_color {
+        color_swatch(ui, color);
+        let hex = color_hex(color);
+        ui.label(
+            RichText::new(format!("{} ({}, {})", hex, x, y))
+                .monospace()
+                .size(13.0),
+        );
+        if ui
+            .small_button("Copy")
+            .on_hover_text("Copy the color as hex")
+            .clicked()
+        {
+            ui.ctx().copy_text(hex);
+        }
+    }
+}
+
+/// Read the color under the cursor, and pick it on click
+///
+/// A click sets the color trigger waiting for a pick, or else keeps the
+/// color as the eyedropper's result.
+fn handle_eyedropper(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    image_rect: egui::Rect,
+    view_state: &mut VisionViewState,
+    shared_state: &Arc<RwLock<SharedAppState>>,
+) {
+    let Some(pointer) = response.hover_pos().filter(|p| image_rect.contains(*p)) else {
+        return;
+    };
+    ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+
+    let relative = (pointer - image_rect.min) / image_rect.size();
+    let position = (relative.x, relative.y);
+    if view_state.picking_color_trigger.is_some() {
+        if response.clicked() {
+            pick_trigger_color(view_state, shared_state, position);
+        }
+        return;
+    }
+
+    let (width, height) = (view_state.last_frame_width, view_state.last_frame_height);
+    let Some(ref data) = view_state.last_frame_data else {
+        return;
+    };
+    let Some(pixel) = pixel_at(width, height, position) else {
+        return;
+    };
+    let patch_size = view_state.eyedropper_radius * 2 + 1;
+    let Some(color) = patch_color(data, width, height, pixel, patch_size) else {
+        return;
+    };
+
+    response.clone().on_hover_ui_at_pointer(|ui| {
+        ui.horizontal(|ui| {
+            color_swatch(ui, color);
+            ui.label(
+                RichText::new(format!(
+                    "RGB {} {} {}  {}\n({}, {})",
+                    color[0],
+                    color[1],
+                    color[2],
+                    color_hex(color),
+                    pixel.0,
+                    pixel.1
+                ))
+                .monospace(),
+            );
+        });
+    });
+    if response.clicked() {
+        view_state.picked_color = Some((pixel, color));
+    }
+}
+
+/// Small square filled with a color
+fn color_swatch(ui: &mut egui::Ui, [r, g, b]: [u8; 3]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(14.0, 14.0), egui::Sense::hover());
+    ui.painter().rect_filled(
+        rect,
+        egui::Rounding::same(2.0),
+        egui::Color32::from_rgb(r, g, b),
+    );
+    ui.painter().rect_stroke(
+        rect,
+        egui::Rounding::same(2.0),
+        egui::Stroke::new(1.0, ThemeColors::TEXT_MUTED),
+    );
+}
+
+/// Color as `#RRGGBB`
+fn color_hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Render the profile's sample frame library
+fn render_sample_library(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
+    remembered_section(
+        ui,
+        "vision.samples",
+        egui::CollapsingHeader::new(
+            RichText::new(format!("Samples ({})", view_state.sample_frames.len())).size(14.0),
+        )
+        .id_salt("sample_frames"),
+        false,
+        |ui| {
+            if view_state.sample_frames_profile.is_none() {
+                ui.label(
+                    RichText::new("Activate a profile to save sample frames")
+                        .size(12.0)
+                        .color(ThemeColors::TEXT_MUTED),
+                );
+                return;
+            }
+
+            for (i, sample) in view_state.sample_frames.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let shown = view_state.shown_sample.as_deref() == Some(sample.name.as_str());
+                    if ui
+                        .selectable_label(shown, &sample.name)
+                        .on_hover_text("Freeze the preview on this sample")
+                        .clicked()
+                    {
+                        view_state.pending_sample_action = Some(SampleAction::Show(i));
+                    }
+                    if ui
+                        .small_button("X")
+                        .on_hover_text("Delete sample")
+                        .clicked()
+                    {
+                        view_state.pending_sample_action = Some(SampleAction::Delete(i));
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut view_state.new_sample_name)
+                        .hint_text("e.g. Night, 1440p, Inventory")
+                        .desired_width(180.0),
+                );
+                let can_save = view_state.last_frame_data.is_some()
+                    && !view_state.new_sample_name.trim().is_empty();
+                if ui
+                    .add_enabled(can_save, egui::Button::new("Save Frame"))
+                    .on_hover_text("Save the frame shown in the preview to the profile's samples")
+                    .clicked()
+                {
+                    let name = std::mem::take(&mut view_state.new_sample_name);
+                    view_state.pending_sample_action = Some(SampleAction::Save(name));
+                }
+            });
+
+            ui.add_space(4.0);
+            if ui
+                .add_enabled(
+                    !view_state.sample_frames.is_empty(),
+                    egui::Button::new("Validate Profile"),
+                )
+                .on_hover_text(
+                    "Run screen recognition and every zone on each sample and report what \
+                 no longer reads as expected (e.g. after a game patch)",
+                )
+                .clicked()
+            {
+                view_state.pending_sample_action = Some(SampleAction::Validate);
+            }
+        },
+    );
+}
+
 /// Render the preview panel with height constraint
 fn render_preview_panel(
     ui: &mut egui::Ui,
     view_state: &mut VisionViewState,
     shared_state: &Arc<RwLock<SharedAppState>>,
-    preview_frame: Option<crate::capture::frame::CapturedFrame>,
+    preview_frame: Option<Arc<crate::capture::frame::CapturedFrame>>,
     max_height: f32,
 ) {
     egui::Frame::none()
@@ -210,14 +678,14 @@ fn render_preview_panel(
 
                     // Update texture if a new frame arrived
                     if let Some(frame) = preview_frame {
-                        view_state.last_frame_data = Some(frame.data.clone());
-                        view_state.last_frame_width = frame.width;
-                        view_state.last_frame_height = frame.height;
-
+                        let rgba = frame.rgba();
                         let color_image = egui::ColorImage::from_rgba_unmultiplied(
                             [frame.width as usize, frame.height as usize],
-                            &frame.data,
+                            &rgba,
                         );
+                        view_state.last_frame_data = Some(rgba.into_owned());
+                        view_state.last_frame_width = frame.width;
+                        view_state.last_frame_height = frame.height;
 
                         let needs_update = view_state
                             .preview_frame_size
@@ -239,18 +707,29 @@ fn render_preview_panel(
                     }
 
                     // Display the texture if we have one (persists between frames)
-                    if let Some(ref texture) = view_state.preview_texture {
+                    if let Some(texture) = view_state.preview_texture.clone() {
                         let tex_size = texture.size_vec2();
                         let scale = (preview_size.x / tex_size.x).min(preview_size.y / tex_size.y);
                         let scaled_size = tex_size * scale;
                         let offset_x = (preview_size.x - scaled_size.x) / 2.0;
                         let offset_y = (preview_size.y - scaled_size.y) / 2.0;
 
-                        let (rect, _) = ui.allocate_exact_size(preview_size, egui::Sense::hover());
+                        let picking =
+                            view_state.eyedropper || view_state.picking_color_trigger.is_some();
+                        let sense = if picking {
+                            egui::Sense::click()
+                        } else {
+                            egui::Sense::hover()
+                        };
+                        let (rect, response) = ui.allocate_exact_size(preview_size, sense);
                         let image_rect = egui::Rect::from_min_size(
                             rect.min + egui::vec2(offset_x, offset_y),
                             scaled_size,
                         );
+
+                        if picking {
+                            handle_eyedropper(ui, &response, image_rect, view_state, shared_state);
+                        }
                         ui.painter().image(
                             texture.id(),
                             image_rect,
@@ -277,6 +756,17 @@ fn render_preview_panel(
                             }
                         }
 
+                        // Outline where the backends read differently
+                        if let Some(ref comparison) = view_state.backend_comparison {
+                            if view_state.last_frame_width > 0 {
+                                let scale = egui::vec2(
+                                    scaled_size.x / view_state.last_frame_width as f32,
+                                    scaled_size.y / view_state.last_frame_height as f32,
+                                );
+                                draw_comparison_boxes(ui, comparison, image_rect, scale);
+                            }
+                        }
+
                         // Draw zone overlays if enabled
                         if view_state.show_zone_overlays && !view_state.ocr_zones.is_empty() {
                             draw_zone_overlays(
@@ -286,6 +776,19 @@ fn render_preview_panel(
                                 &view_state.zone_ocr_results,
                             );
                         }
+
+                        // Mark color trigger spots
+                        let (triggers, active) = {
+                            let shared = shared_state.read();
+                            (
+                                shared
+                                    .active_profile()
+                                    .map(|p| p.color_triggers.clone())
+                                    .unwrap_or_default(),
+                                shared.runtime.active_color_triggers.clone(),
+                            )
+                        };
+                        draw_color_trigger_markers(ui, &triggers, image_rect, &active);
                     } else {
                         // No texture yet - show placeholder
                         ui.centered_and_justified(|ui| {
@@ -307,6 +810,14 @@ fn render_preview_panel(
             // Options row
             ui.horizontal(|ui| {
                 ui.checkbox(&mut view_state.show_bounding_boxes, "Boxes");
+                ui.checkbox(&mut view_state.compare_backends, "Compare")
+                    .on_hover_text(
+                        "Read the frame with Windows OCR and PaddleOCR and show both side by \
+                         side, outlining where they read differently",
+                    );
+                render_freeze_toggle(ui, view_state);
+                render_eyedropper_toggle(ui, view_state);
+                render_scene_metrics(ui, shared_state);
                 if view_state.last_processing_time_ms > 0 {
                     ui.label(
                         RichText::new(format!("{}ms", view_state.last_processing_time_ms))
@@ -314,13 +825,71 @@ fn render_preview_panel(
                             .color(ThemeColors::TEXT_MUTED),
                     );
                 }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if view_state.frozen_frame.is_some() {
+                        let label = match view_state.shown_sample {
+                            Some(ref name) => format!("Sample: {}", name),
+                            None => "Frozen".to_string(),
+                        };
+                        ui.label(
+                            RichText::new(label)
+                                .size(14.0)
+                                .color(ThemeColors::ACCENT_WARNING),
+                        );
+                    }
+                    if view_state.showing_clipboard_image {
+                        if ui
+                            .small_button("Back to Live")
+                            .on_hover_text("Resume the live preview and zone OCR")
+                            .clicked()
+                        {
+                            view_state.showing_clipboard_image = false;
+                            view_state.last_ocr_results.clear();
+                            view_state.zone_ocr_results.clear();
+                        }
+                        ui.label(
+                            RichText::new("Clipboard image")
+                                .size(14.0)
+                                .color(ThemeColors::ACCENT_WARNING),
+                        );
+                    }
+                    if ui
+                        .small_button("OCR Clipboard")
+                        .on_hover_text(
+                            "Read an image from the clipboard (e.g. a screenshot someone sent) \
+                             with the current zones and preprocessing",
+                        )
+                        .clicked()
+                    {
+                        view_state.pending_clipboard_ocr = true;
+                    }
+                });
             });
 
+            // Both backends' readings, when comparing
+            render_backend_comparison(ui, view_state);
+
+            // Sample frames of the active profile
+            ui.add_space(4.0);
+            render_sample_library(ui, view_state);
+
+            // Labeled regions of the active profile
+            ui.add_space(4.0);
+            render_labeled_regions(ui, view_state);
+
+            // Pixel-color triggers of the active profile
+            ui.add_space(4.0);
+            render_color_triggers(ui, view_state, shared_state);
+
             // Preprocessing controls (collapsible)
             ui.add_space(4.0);
-            egui::CollapsingHeader::new(RichText::new("Preprocessing").size(14.0))
-                .default_open(view_state.preprocessing.enabled)
-                .show(ui, |ui| {
+            remembered_section(
+                ui,
+                "vision.preprocessing",
+                egui::CollapsingHeader::new(RichText::new("Preprocessing").size(14.0)),
+                view_state.preprocessing.enabled,
+                |ui| {
                     // Track if any setting changed to trigger OCR re-run
                     let mut settings_changed = false;
 
@@ -399,6 +968,24 @@ fn render_preview_panel(
                                 "Upscale image before OCR (2-3x recommended for small text)",
                             );
                         });
+
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Threshold:").size(13.0));
+                            let threshold_slider = add_scroll_slider(
+                                ui,
+                                &mut view_state.preprocessing.threshold,
+                                0..=255,
+                                Some(8.0),
+                                None,
+                                None,
+                            );
+                            if threshold_slider.changed() {
+                                settings_changed = true;
+                            }
+                            threshold_slider.on_hover_text(
+                                "Binarize to black and white at this brightness (0 = off)",
+                            );
+                        });
                     });
 
                     // Auto-trigger OCR when preprocessing settings change
@@ -411,6 +998,7 @@ fn render_preview_panel(
                             view_state.pending_ocr_run = true;
                         }
                     }
-                });
+                },
+            );
         });
 }
@@ -5,11 +5,20 @@
 use egui::{Color32, RichText, Rounding, Stroke, Vec2};
 use uuid::Uuid;
 
-use crate::dashboard::components::add_scroll_slider;
+use crate::capture::composite;
+use crate::dashboard::components::{add_phase_picker, add_scroll_slider};
 use crate::dashboard::state::{
     AutoConfigureState, AutoConfigureStep, VisionViewState, ZoneOcrResult,
 };
-use crate::storage::profiles::{ContentType, OcrRegion};
+use crate::storage::profiles::{ContentType, OcrRegion, ZoneGeometry};
+use crate::vision::auto_tune::MAX_SAMPLES_PER_ZONE;
+use crate::vision::char_whitelist::PRESETS;
+use crate::vision::ensemble::Agreement;
+use crate::vision::scroll_stitch::ScrollStitcher;
+use crate::vision::zone_stats::{ZoneOcrStats, BUCKET_DURATION, MAX_BUCKETS};
+
+/// Stitched lines listed under a scrolling zone, newest first
+const RECENT_LOG_LINES_SHOWN: usize = 8;
 
 /// Render the zone OCR management panel
 pub fn render_zone_ocr_panel(ui: &mut egui::Ui, view_state: &mut VisionViewState, max_height: f32) {
@@ -86,12 +95,16 @@ pub fn render_zone_ocr_panel(ui: &mut egui::Ui, view_state: &mut VisionViewState
 
                     for (idx, zone) in view_state.ocr_zones.iter().enumerate() {
                         let ocr_result = view_state.zone_ocr_results.get(&zone.id);
+                        let ocr_stats = view_state.zone_ocr_stats.get(&zone.id);
+                        let scroll_log = view_state.zone_scroll_logs.get(&zone.id);
 
                         ui.push_id(idx, |ui| {
                             render_zone_item(
                                 ui,
                                 zone,
                                 ocr_result,
+                                ocr_stats,
+                                scroll_log,
                                 &mut zone_to_toggle,
                                 &mut zone_to_delete,
                                 &mut zone_to_configure,
@@ -117,6 +130,9 @@ pub fn render_zone_ocr_panel(ui: &mut egui::Ui, view_state: &mut VisionViewState
                         let zone_id = view_state.ocr_zones[idx].id.clone();
                         view_state.ocr_zones.remove(idx);
                         view_state.zone_ocr_results.remove(&zone_id);
+                        view_state.zone_ocr_stats.remove(&zone_id);
+                        view_state.zone_samples.remove(&zone_id);
+                        view_state.zone_scroll_logs.remove(&zone_id);
                         view_state.zones_dirty = true;
                     }
 
@@ -163,6 +179,8 @@ fn render_zone_item(
     ui: &mut egui::Ui,
     zone: &OcrRegion,
     ocr_result: Option<&ZoneOcrResult>,
+    ocr_stats: Option<&ZoneOcrStats>,
+    scroll_log: Option<&ScrollStitcher>,
     zone_to_toggle: &mut Option<usize>,
     zone_to_delete: &mut Option<usize>,
     zone_to_configure: &mut Option<usize>,
@@ -258,6 +276,37 @@ fn render_zone_item(
                             .color(Color32::from_rgb(100, 200, 255))
                             .monospace(),
                     );
+
+                    // Ensemble vote
+                    match result.agreement {
+                        Some(Agreement::Agreed) => {
+                            ui.label(RichText::new("✓").color(Color32::GREEN).small())
+                                .on_hover_text("Both OCR backends agree");
+                        }
+                        Some(Agreement::Disagreed) => {
+                            ui.label(
+                                RichText::new("≠")
+                                    .color(Color32::from_rgb(255, 200, 100))
+                                    .small(),
+                            )
+                            .on_hover_text(
+                                "OCR backends disagree; showing the more plausible reading",
+                            );
+                        }
+                        Some(Agreement::Single) | None => {}
+                    }
+
+                    if result.occluded {
+                        ui.label(
+                            RichText::new("Occluded")
+                                .small()
+                                .color(Color32::from_rgb(255, 200, 100)),
+                        )
+                        .on_hover_text(
+                            "A popup screen covers this zone; showing the last value \
+                             until it closes",
+                        );
+                    }
                 });
 
                 // Time since last update
@@ -272,6 +321,22 @@ fn render_zone_item(
                 ui.label(RichText::new("No value").italics().color(Color32::GRAY));
             }
 
+            // Latest stitched lines of scrolling zones
+            if let Some(log) = scroll_log.filter(|_| zone.scrolling) {
+                if log.recent().len() > 0 {
+                    ui.collapsing(format!("Log ({} lines)", log.recent().len()), |ui| {
+                        for line in log.recent().rev().take(RECENT_LOG_LINES_SHOWN) {
+                            ui.label(RichText::new(line).small().monospace());
+                        }
+                    });
+                }
+            }
+
+            // OCR quality over time
+            if let Some(stats) = ocr_stats {
+                render_zone_quality(ui, stats);
+            }
+
             // Show bounds info
             let bounds_text = format!(
                 "Region: {:.1}%, {:.1}% - {:.1}% x {:.1}%",
@@ -288,6 +353,68 @@ fn render_zone_item(
         });
 }
 
+/// Render a zone's OCR confidence heatmap and session failure rate
+fn render_zone_quality(ui: &mut egui::Ui, stats: &ZoneOcrStats) {
+    const CELL_WIDTH: f32 = 3.0;
+    const CELL_HEIGHT: f32 = 10.0;
+
+    ui.horizontal(|ui| {
+        let (rect, response) = ui.allocate_exact_size(
+            Vec2::new(CELL_WIDTH * MAX_BUCKETS as f32, CELL_HEIGHT),
+            egui::Sense::hover(),
+        );
+
+        // Right-align so the newest bucket is always at the right edge
+        let offset = MAX_BUCKETS - stats.buckets().count();
+        for (i, bucket) in stats.buckets().enumerate() {
+            let x = rect.left() + (offset + i) as f32 * CELL_WIDTH;
+            let cell = egui::Rect::from_min_size(
+                egui::pos2(x, rect.top()),
+                Vec2::new(CELL_WIDTH - 0.5, CELL_HEIGHT),
+            );
+            ui.painter()
+                .rect_filled(cell, 0.0, quality_color(bucket.quality()));
+        }
+
+        response.on_hover_text(format!(
+            "OCR quality over the last {} minutes ({} s per cell): \
+             green = confident reads, red = low confidence or no text",
+            MAX_BUCKETS as u64 * BUCKET_DURATION.as_secs() / 60,
+            BUCKET_DURATION.as_secs()
+        ));
+
+        let total = stats.total();
+        let confidence = total
+            .mean_confidence()
+            .map(|c| format!("{:.0}% conf", c * 100.0))
+            .unwrap_or_else(|| "no conf".to_string());
+        ui.label(
+            RichText::new(format!(
+                "{} · {:.0}% failed",
+                confidence,
+                total.failure_rate() * 100.0
+            ))
+            .small()
+            .color(quality_color(total.quality())),
+        );
+    });
+}
+
+/// Heatmap color for a quality value (red = poor, green = good, gray = no data)
+fn quality_color(quality: Option<f32>) -> Color32 {
+    let Some(quality) = quality else {
+        return Color32::from_gray(60);
+    };
+    let q = quality.clamp(0.0, 1.0);
+    if q < 0.5 {
+        // Red to yellow
+        Color32::from_rgb(220, (q * 2.0 * 200.0) as u8, 60)
+    } else {
+        // Yellow to green
+        Color32::from_rgb((220.0 * (1.0 - q) * 2.0) as u8, 200, 60)
+    }
+}
+
 /// Render the zone naming dialog
 fn render_zone_naming_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewState) {
     egui::Window::new("Name Zone")
@@ -366,6 +493,13 @@ fn render_zone_naming_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewState
                                     .clone(),
                                 enabled: true,
                                 preprocessing: None, // Use global settings by default
+                                ensemble: false,
+                                allowed_chars: None,
+                                geometry: Default::default(),
+                                scrolling: false,
+                                value_epsilon: 0.0,
+                                source: None,
+                                phases: vec![],
                             };
 
                             view_state.ocr_zones.push(new_zone);
@@ -474,6 +608,173 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
                     }
                 });
 
+                ui.add_space(4.0);
+
+                // Ensemble mode - apply immediately
+                if ui
+                    .checkbox(&mut zone.ensemble, "Ensemble OCR (both backends vote)")
+                    .on_hover_text(
+                        "Read this zone with Windows OCR and PaddleOCR. Matching readings \
+                         get a confidence boost; on a mismatch the reading that fits the \
+                         content type wins.",
+                    )
+                    .changed()
+                {
+                    // Retry the second backend if it failed before
+                    view_state.ensemble_error = None;
+                    view_state.zones_dirty = true;
+                }
+                if zone.ensemble {
+                    if let Some(ref err) = view_state.ensemble_error {
+                        ui.label(
+                            RichText::new(format!("Using one backend only: {}", err))
+                                .small()
+                                .color(Color32::from_rgb(255, 200, 100)),
+                        );
+                    }
+                }
+
+                ui.add_space(4.0);
+
+                // Scrolling log mode - apply immediately
+                if ui
+                    .checkbox(&mut zone.scrolling, "Scrolling log (record new lines only)")
+                    .on_hover_text(
+                        "For chat and combat logs: detect how far the text scrolled and \
+                         record only the lines that scrolled into view. Read by the \
+                         selected backend only.",
+                    )
+                    .changed()
+                {
+                    view_state.zones_dirty = true;
+                }
+
+                // Recording threshold for numeric zones - apply immediately
+                if matches!(
+                    zone.content_type,
+                    ContentType::Number | ContentType::Percentage
+                ) {
+                    ui.add_space(4.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Record changes above:");
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut zone.value_epsilon)
+                                    .range(0.0..=f64::MAX)
+                                    .speed(0.1),
+                            )
+                            .on_hover_text(
+                                "Smaller changes of the value aren't recorded, so a \
+                                 flickering digit doesn't fill the session with readings \
+                                 (0 = record every change)",
+                            )
+                            .changed()
+                        {
+                            view_state.zones_dirty = true;
+                        }
+                    });
+                }
+
+                ui.add_space(4.0);
+
+                // Character whitelist - apply immediately
+                ui.horizontal(|ui| {
+                    ui.label("Allowed characters:");
+                    let selected = match zone.allowed_chars.as_deref() {
+                        None => "Any",
+                        Some(chars) => PRESETS
+                            .iter()
+                            .find(|(_, preset)| *preset == chars)
+                            .map_or("Custom", |(label, _)| *label),
+                    };
+                    egui::ComboBox::from_id_salt("settings_allowed_chars")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(selected == "Any", "Any").clicked() {
+                                zone.allowed_chars = None;
+                                view_state.zones_dirty = true;
+                            }
+                            for (label, chars) in PRESETS {
+                                if ui.selectable_label(selected == *label, *label).clicked() {
+                                    zone.allowed_chars = Some(chars.to_string());
+                                    view_state.zones_dirty = true;
+                                }
+                            }
+                            if ui
+                                .selectable_label(selected == "Custom", "Custom")
+                                .clicked()
+                                && selected != "Custom"
+                            {
+                                zone.allowed_chars = Some(String::new());
+                                view_state.zones_dirty = true;
+                            }
+                        });
+                });
+                let is_custom = zone
+                    .allowed_chars
+                    .as_deref()
+                    .is_some_and(|chars| !PRESETS.iter().any(|(_, preset)| *preset == chars));
+                if let Some(chars) = zone.allowed_chars.as_mut().filter(|_| is_custom) {
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(chars)
+                                .hint_text("e.g. 0123456789:")
+                                .desired_width(200.0),
+                        )
+                        .on_hover_text(
+                            "Misreads like O/I/S are mapped to 0/1/5 when only digits are allowed",
+                        )
+                        .changed()
+                    {
+                        view_state.zones_dirty = true;
+                    }
+                }
+
+                // Composite capture source - apply immediately
+                if !view_state.composite_sources.is_empty() || zone.source.is_some() {
+                    ui.add_space(4.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Capture source:");
+                        let mut source = zone.source.clone();
+                        egui::ComboBox::from_id_salt("settings_capture_source")
+                            .selected_text(source.as_deref().unwrap_or("Whole frame"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut source, None, "Whole frame");
+                                for id in &view_state.composite_sources {
+                                    ui.selectable_value(&mut source, Some(id.clone()), id);
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "With a composite capture, place the zone within one of its \
+                                 sources so it stays put when another source changes size. \
+                                 Zones of an extra source are read from its own frames.",
+                            );
+                        if source != zone.source {
+                            // Keep the zone where it is if both sources are in the current frame
+                            let layout = view_state.composite_layout.as_ref();
+                            if let Some(bounds) =
+                                composite::frame_bounds(layout, zone.source.as_deref(), zone.bounds)
+                                    .and_then(|bounds| {
+                                        composite::source_bounds(layout, source.as_deref(), bounds)
+                                    })
+                            {
+                                zone.bounds = bounds;
+                            }
+                            zone.source = source;
+                            view_state.zones_dirty = true;
+                        }
+                    });
+                }
+
+                // Game phases - apply immediately
+                if !view_state.phases.is_empty() {
+                    ui.add_space(4.0);
+                    if add_phase_picker(ui, &view_state.phases, &mut zone.phases) {
+                        view_state.zones_dirty = true;
+                    }
+                }
+
                 ui.add_space(8.0);
                 ui.separator();
                 ui.add_space(8.0);
@@ -585,6 +886,24 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
                                         view_state.zones_dirty = true;
                                     }
                                 });
+
+                                // Threshold
+                                ui.horizontal(|ui| {
+                                    ui.label("Threshold:");
+                                    if add_scroll_slider(
+                                        ui,
+                                        &mut pp.threshold,
+                                        0..=255,
+                                        Some(8.0),
+                                        None,
+                                        None,
+                                    )
+                                    .on_hover_text("Binarize at this brightness (0 = off)")
+                                    .changed()
+                                    {
+                                        view_state.zones_dirty = true;
+                                    }
+                                });
                             });
                         });
                 });
@@ -602,16 +921,28 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
                 ui.separator();
                 ui.add_space(8.0);
 
-                // Auto Configure section
-                ui.label(RichText::new("Auto Configure").strong());
+                render_zone_geometry(ui, view_state, idx);
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(8.0);
+
+                // Auto-tune section
+                ui.label(RichText::new("Auto-tune").strong());
                 ui.add_space(4.0);
                 ui.label(
-                    RichText::new("Automatically find settings that produce OCR text.")
-                        .small()
-                        .color(Color32::GRAY),
+                    RichText::new(
+                        "Search preprocessing settings and apply the one that reads best, \
+                         scored by OCR confidence and how well the text fits the content type.",
+                    )
+                    .small()
+                    .color(Color32::GRAY),
                 );
                 ui.add_space(8.0);
 
+                render_zone_samples(ui, view_state, idx);
+                ui.add_space(8.0);
+
                 // Check if auto-configure is running for this zone
                 let is_auto_configuring = view_state
                     .zone_selection
@@ -651,7 +982,8 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
                                     ui.label(RichText::new("✓").color(Color32::GREEN));
                                     ui.label(
                                         RichText::new(format!(
-                                            "Best config found! (conf: {:.0}%)",
+                                            "Best config applied (score: {:.0}%, conf: {:.0}%)",
+                                            ac.best_score * 100.0,
                                             ac.best_confidence * 100.0
                                         ))
                                         .color(Color32::GREEN),
@@ -676,26 +1008,33 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
 
                     // Start button
                     if ui
-                        .button("Auto Configure")
-                        .on_hover_text("Tests all settings combinations and picks the best one")
+                        .button("Auto-tune")
+                        .on_hover_text("Tests preprocessing combinations and applies the best one")
                         .clicked()
                     {
-                        // Calculate total combinations: 4 scales * 2 preprocessing * 2 grayscale * 2 invert * 3 contrast = 96
-                        let total = 4 * 2 * 2 * 2 * 3;
+                        let candidates = crate::vision::auto_tune::candidate_settings();
+                        let samples = if view_state.zone_selection.auto_tune_use_samples {
+                            view_state
+                                .ocr_zones
+                                .get(idx)
+                                .and_then(|zone| view_state.zone_samples.get(&zone.id))
+                                .cloned()
+                                .unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        };
                         view_state.zone_selection.auto_configure = Some(AutoConfigureState {
                             zone_index: idx,
                             current_step: AutoConfigureStep::Starting,
-                            current_scale: 1,
-                            current_preprocessing_enabled: false,
-                            current_grayscale: false,
-                            current_invert: false,
-                            current_contrast: 1.0,
-                            total_combinations: total,
+                            total_combinations: candidates.len(),
+                            candidates,
+                            samples,
                             current_combination: 0,
                             status_message: "Starting...".to_string(),
                             success: false,
                             error_message: None,
                             best_preprocessing: None,
+                            best_score: 0.0,
                             best_confidence: 0.0,
                             best_text: String::new(),
                         });
@@ -737,6 +1076,205 @@ fn render_zone_settings_dialog(ui: &mut egui::Ui, view_state: &mut VisionViewSta
     }
 }
 
+/// Render the saved sample frames controls for auto-tune
+fn render_zone_samples(ui: &mut egui::Ui, view_state: &mut VisionViewState, idx: usize) {
+    let Some(zone) = view_state.ocr_zones.get(idx) else {
+        return;
+    };
+    let zone_id = zone.id.clone();
+    let bounds = zone.bounds;
+    let sample_count = view_state.zone_samples.get(&zone_id).map_or(0, Vec::len);
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "Sample frames: {}/{}",
+            sample_count, MAX_SAMPLES_PER_ZONE
+        ));
+
+        let can_add = view_state.last_frame_data.is_some() && sample_count < MAX_SAMPLES_PER_ZONE;
+        if ui
+            .add_enabled(can_add, egui::Button::new("Add Current Frame"))
+            .on_hover_text("Save this zone's image from the current frame for auto-tune")
+            .clicked()
+        {
+            if let Some(sample) = current_zone_image(view_state, bounds) {
+                view_state
+                    .zone_samples
+                    .entry(zone_id.clone())
+                    .or_default()
+                    .push(sample);
+            }
+        }
+
+        if sample_count > 0 && ui.button("Clear").clicked() {
+            view_state.zone_samples.remove(&zone_id);
+        }
+    });
+
+    ui.add_enabled_ui(sample_count > 0, |ui| {
+        ui.checkbox(
+            &mut view_state.zone_selection.auto_tune_use_samples,
+            "Include sample frames",
+        )
+        .on_hover_text("Score each setting on the saved samples as well as the current frame");
+    });
+}
+
+/// Crop a zone (normalized bounds) out of the last captured frame
+fn current_zone_image(
+    view_state: &VisionViewState,
+    bounds: (f32, f32, f32, f32),
+) -> Option<crate::capture::CapturedFrame> {
+    let data = view_state.last_frame_data.as_ref()?;
+    let frame_width = view_state.last_frame_width;
+    let frame_height = view_state.last_frame_height;
+
+    let x = (bounds.0 * frame_width as f32) as u32;
+    let y = (bounds.1 * frame_height as f32) as u32;
+    let w = ((bounds.2 * frame_width as f32) as u32).min(frame_width.saturating_sub(x));
+    let h = ((bounds.3 * frame_height as f32) as u32).min(frame_height.saturating_sub(y));
+    if w == 0 || h == 0 {
+        return None;
+    }
+
+    // Copy just the zone's rows rather than cloning the whole frame
+    let row_bytes = (w * 4) as usize;
+    let mut region = Vec::with_capacity(row_bytes * h as usize);
+    for row in y..y + h {
+        let start = ((row * frame_width + x) * 4) as usize;
+        region.extend_from_slice(data.get(start..start + row_bytes)?);
+    }
+    Some(crate::capture::CapturedFrame::new(region, w, h))
+}
+
+/// Render the rotation slider and perspective corner editor for a zone
+fn render_zone_geometry(ui: &mut egui::Ui, view_state: &mut VisionViewState, idx: usize) {
+    let Some(bounds) = view_state.ocr_zones.get(idx).map(|z| z.bounds) else {
+        return;
+    };
+    let perspective_enabled = view_state.ocr_zones[idx].geometry.perspective.is_some();
+    let zone_image = if perspective_enabled {
+        current_zone_image(view_state, bounds)
+    } else {
+        None
+    };
+    let zone = &mut view_state.ocr_zones[idx];
+
+    ui.label(RichText::new("Geometry").strong());
+    ui.add_space(4.0);
+
+    // Rotation - apply immediately
+    ui.horizontal(|ui| {
+        ui.label("Rotation:");
+        if add_scroll_slider(
+            ui,
+            &mut zone.geometry.rotation,
+            -45.0..=45.0,
+            Some(0.5),
+            Some("°"),
+            Some(1),
+        )
+        .on_hover_text("Rotate the zone clockwise to level angled text")
+        .changed()
+        {
+            view_state.zones_dirty = true;
+        }
+    });
+
+    // Perspective correction toggle - apply immediately
+    let mut use_perspective = perspective_enabled;
+    if ui
+        .checkbox(&mut use_perspective, "Perspective correction")
+        .on_hover_text("Drag the corners onto the edges of skewed text")
+        .changed()
+    {
+        zone.geometry.perspective = use_perspective.then_some(ZoneGeometry::IDENTITY_CORNERS);
+        view_state.zones_dirty = true;
+    }
+
+    let Some(corners) = zone.geometry.perspective.as_mut() else {
+        return;
+    };
+    let Some(image) = zone_image else {
+        ui.label(
+            RichText::new("Start capture to adjust the corners")
+                .italics()
+                .color(Color32::GRAY),
+        );
+        return;
+    };
+
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+        [image.width as usize, image.height as usize],
+        &image.rgba(),
+    );
+    let texture = match view_state.geometry_texture {
+        Some(ref mut texture) => {
+            texture.set(color_image, egui::TextureOptions::LINEAR);
+            texture
+        }
+        None => view_state.geometry_texture.insert(ui.ctx().load_texture(
+            "zone_geometry",
+            color_image,
+            egui::TextureOptions::LINEAR,
+        )),
+    };
+
+    // Fit the zone image into the editor, keeping its aspect ratio
+    let image_size = egui::vec2(image.width as f32, image.height as f32);
+    let scale = (300.0 / image_size.x).min(160.0 / image_size.y);
+    let (rect, _) = ui.allocate_exact_size(image_size * scale, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.image(
+        texture.id(),
+        rect,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        Color32::WHITE,
+    );
+
+    let to_screen = |(x, y): (f32, f32)| rect.min + egui::vec2(x * rect.width(), y * rect.height());
+    painter.add(egui::Shape::closed_line(
+        corners.iter().map(|&corner| to_screen(corner)).collect(),
+        Stroke::new(1.5, Color32::YELLOW),
+    ));
+
+    // Draggable corner handles (TL, TR, BR, BL)
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let center = to_screen(*corner);
+        let response = ui
+            .interact(
+                egui::Rect::from_center_size(center, Vec2::splat(14.0)),
+                ui.id().with(("zone_geometry_corner", i)),
+                egui::Sense::drag(),
+            )
+            .on_hover_cursor(egui::CursorIcon::Grab);
+
+        if response.dragged() {
+            let pos = center + response.drag_delta();
+            corner.0 = ((pos.x - rect.min.x) / rect.width()).clamp(0.0, 1.0);
+            corner.1 = ((pos.y - rect.min.y) / rect.height()).clamp(0.0, 1.0);
+            view_state.zones_dirty = true;
+        }
+
+        let fill = if response.hovered() || response.dragged() {
+            Color32::WHITE
+        } else {
+            Color32::YELLOW
+        };
+        painter.circle(
+            to_screen(*corner),
+            5.0,
+            fill,
+            Stroke::new(1.0, Color32::BLACK),
+        );
+    }
+
+    if ui.small_button("Reset corners").clicked() {
+        *corners = ZoneGeometry::IDENTITY_CORNERS;
+        view_state.zones_dirty = true;
+    }
+}
+
 /// Draw zone overlays on the preview image
 pub fn draw_zone_overlays(
     ui: &egui::Ui,
@@ -795,12 +1333,17 @@ pub fn draw_zone_overlays(
         // Draw current value if available
         if let Some(result) = zone_results.get(&zone.id) {
             let value_pos = zone_rect.left_top() + egui::vec2(2.0, 14.0);
+            let (value, color) = if result.occluded {
+                ("(occluded)", Color32::from_rgb(255, 200, 100))
+            } else {
+                (result.text.as_str(), Color32::from_rgb(100, 200, 255))
+            };
             painter.text(
                 value_pos,
                 egui::Align2::LEFT_TOP,
-                &result.text,
+                value,
                 egui::FontId::monospace(10.0),
-                Color32::from_rgb(100, 200, 255),
+                color,
             );
         }
     }
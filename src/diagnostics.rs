@@ -0,0 +1,201 @@
+#![allow(dead_code)]
+//! System capability checks
+//!
+//! Verifies up front that the Windows features the toolkit depends on are
+//! available, so problems show up as actionable diagnostics instead of
+//! failing later when capture, OCR or the overlay is first used.
+
+use ort::execution_providers::{DirectMLExecutionProvider, ExecutionProvider};
+use windows_capture::graphics_capture_api::GraphicsCaptureApi;
+use windows_capture::monitor::Monitor;
+
+use crate::vision::gpu_adapters;
+use crate::vision::windows_ocr::WindowsOcr;
+
+/// Outcome of a capability check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Fully available
+    Ok,
+    /// Usable, but degraded
+    Warning,
+    /// Not available
+    Error,
+}
+
+/// Result of checking a single capability
+#[derive(Debug, Clone)]
+pub struct CapabilityCheck {
+    /// Capability name
+    pub name: &'static str,
+    /// Outcome
+    pub status: CheckStatus,
+    /// What was found
+    pub detail: String,
+    /// How to fix it (for warnings and errors)
+    pub hint: Option<&'static str>,
+}
+
+impl CapabilityCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn with_hint(mut self, hint: &'static str) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+/// Run all capability checks
+///
+/// `overlay_running` reports the overlay's own state instead of probing for
+/// monitors.
+pub fn run_all_checks(overlay_running: bool) -> Vec<CapabilityCheck> {
+    vec![
+        check_graphics_capture(),
+        check_ocr_languages(),
+        check_gpu(),
+        check_overlay(overlay_running),
+    ]
+}
+
+/// Check that Windows Graphics Capture is supported and a monitor can be found
+fn check_graphics_capture() -> CapabilityCheck {
+    const NAME: &str = "Screen Capture";
+
+    match GraphicsCaptureApi::is_supported() {
+        Ok(true) => match Monitor::enumerate() {
+            Ok(monitors) if !monitors.is_empty() => CapabilityCheck::new(
+                NAME,
+                CheckStatus::Ok,
+                format!(
+                    "Windows Graphics Capture available, {} monitor(s)",
+                    monitors.len()
+                ),
+            ),
+            Ok(_) => CapabilityCheck::new(NAME, CheckStatus::Error, "No monitors found")
+                .with_hint("Check that a display is connected and the session is not locked."),
+            Err(e) => CapabilityCheck::new(
+                NAME,
+                CheckStatus::Error,
+                format!("Failed to enumerate monitors: {}", e),
+            )
+            .with_hint("Check that a display is connected and the session is not locked."),
+        },
+        Ok(false) => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Error,
+            "Windows Graphics Capture is not supported",
+        )
+        .with_hint("Screen capture requires Windows 10 version 1903 or later. Update Windows."),
+        Err(e) => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Error,
+            format!("Failed to query Windows Graphics Capture: {}", e),
+        )
+        .with_hint("Screen capture requires Windows 10 version 1903 or later. Update Windows."),
+    }
+}
+
+/// Check which Windows OCR languages are installed
+fn check_ocr_languages() -> CapabilityCheck {
+    const NAME: &str = "Windows OCR";
+    const INSTALL_HINT: &str = "Add a language with the Optical character recognition \
+                                feature in Settings > Time & language > Language & region, \
+                                or use PaddleOCR in the Vision view.";
+
+    match WindowsOcr::available_languages() {
+        Ok(languages) if languages.iter().any(|tag| tag.starts_with("en")) => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("Languages: {}", languages.join(", ")),
+        ),
+        Ok(languages) if !languages.is_empty() => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            format!(
+                "No English OCR language (installed: {})",
+                languages.join(", ")
+            ),
+        )
+        .with_hint(INSTALL_HINT),
+        Ok(_) => CapabilityCheck::new(NAME, CheckStatus::Error, "No OCR languages installed")
+            .with_hint(INSTALL_HINT),
+        Err(e) => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Error,
+            format!("Windows OCR unavailable: {}", e),
+        )
+        .with_hint(INSTALL_HINT),
+    }
+}
+
+/// Check for a hardware GPU and DirectML support (used by PaddleOCR)
+fn check_gpu() -> CapabilityCheck {
+    const NAME: &str = "GPU Acceleration";
+
+    let adapters = hardware_adapters();
+    let directml = DirectMLExecutionProvider::default()
+        .is_available()
+        .unwrap_or(false);
+
+    match adapters.first() {
+        Some(adapter) if directml => {
+            CapabilityCheck::new(NAME, CheckStatus::Ok, format!("{} (DirectML)", adapter))
+        }
+        Some(adapter) => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            format!(
+                "{} found, but DirectML is unavailable; PaddleOCR will use the CPU",
+                adapter
+            ),
+        )
+        .with_hint("Update your graphics driver. DirectML requires a DirectX 12 capable GPU."),
+        None => CapabilityCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            "No hardware GPU found; PaddleOCR will use the CPU",
+        )
+        .with_hint("Windows OCR does not need a GPU and is usually fast enough for game UI."),
+    }
+}
+
+/// Names of the hardware (non-software) display adapters
+fn hardware_adapters() -> Vec<String> {
+    gpu_adapters::list_adapters()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|adapter| adapter.name)
+        .collect()
+}
+
+/// Check that there's a monitor for the overlay to show on
+///
+/// Monitors are listed through Win32 rather than GLFW, so the probe can't
+/// race with an overlay started while the checks run.
+fn check_overlay(overlay_running: bool) -> CapabilityCheck {
+    const NAME: &str = "Overlay";
+
+    if overlay_running {
+        return CapabilityCheck::new(NAME, CheckStatus::Ok, "Overlay is running");
+    }
+
+    let monitors = crate::overlay::system_monitors();
+    if monitors.is_empty() {
+        CapabilityCheck::new(NAME, CheckStatus::Error, "No monitor found for the overlay")
+            .with_hint("Check that a display is connected and its graphics driver is installed.")
+    } else {
+        CapabilityCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("{} monitor(s) available for the overlay", monitors.len()),
+        )
+    }
+}
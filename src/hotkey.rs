@@ -1,4 +1,5 @@
-//! Global hotkey handling for overlay visibility toggle
+//! Global hotkey handling for overlay visibility, zone selection, screen reading
+//! and checklists
 
 use anyhow::{anyhow, Result};
 use global_hotkey::{
@@ -9,6 +10,9 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use crate::config::OverlaySettings;
+use crate::shared::control::ControlSource;
+
 /// Parses a hotkey string like "F9", "Ctrl+Shift+O", "Alt+F1" into a HotKey
 pub fn parse_hotkey(hotkey_str: &str) -> Result<HotKey> {
     let parts: Vec<&str> = hotkey_str.split('+').map(|s| s.trim()).collect();
@@ -135,13 +139,48 @@ pub enum HotkeyEvent {
     ToggleOverlay,
     /// Enter zone selection mode
     EnterZoneSelection,
+    /// OCR the whole screen into the screen text panel
+    ReadScreen,
+    /// Tick the next item of the pinned checklist
+    TickChecklist,
+    /// Save the replay buffer
+    SaveReplay,
+    /// Save a screenshot of the next frame
+    Screenshot,
 }
 
+impl HotkeyEvent {
+    /// What the hotkey does, for the control log
+    pub fn name(&self) -> &'static str {
+        match self {
+            HotkeyEvent::None => "None",
+            HotkeyEvent::ToggleOverlay => "Toggle overlay",
+            HotkeyEvent::EnterZoneSelection => "Zone selection",
+            HotkeyEvent::ReadScreen => "Read screen",
+            HotkeyEvent::TickChecklist => "Tick checklist item",
+            HotkeyEvent::SaveReplay => "Save replay",
+            HotkeyEvent::Screenshot => "Screenshot",
+        }
+    }
+}
+
+/// The hotkeys the manager registers, with the overlay setting each is read from
+const HOTKEYS: [(HotkeyEvent, fn(&OverlaySettings) -> &Option<String>); 6] = [
+    (HotkeyEvent::ToggleOverlay, |o| &o.toggle_hotkey),
+    (HotkeyEvent::EnterZoneSelection, |o| {
+        &o.zone_selection_hotkey
+    }),
+    (HotkeyEvent::ReadScreen, |o| &o.read_screen_hotkey),
+    (HotkeyEvent::TickChecklist, |o| &o.checklist_hotkey),
+    (HotkeyEvent::SaveReplay, |o| &o.replay_hotkey),
+    (HotkeyEvent::Screenshot, |o| &o.screenshot_hotkey),
+];
+
 /// Manages global hotkeys for the application
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
-    toggle_hotkey_id: Option<u32>,
-    zone_selection_hotkey_id: Option<u32>,
+    /// Registered hotkeys and the event each triggers
+    registered: Vec<(HotkeyEvent, HotKey)>,
     shared_state: Arc<RwLock<crate::shared::SharedAppState>>,
 }
 
@@ -153,21 +192,28 @@ impl HotkeyManager {
 
         Ok(Self {
             manager,
-            toggle_hotkey_id: None,
-            zone_selection_hotkey_id: None,
+            registered: Vec::new(),
             shared_state,
         })
     }
 
-    /// Register the overlay toggle hotkey from config
-    pub fn register_toggle_hotkey(&mut self) -> Result<()> {
+    /// Register every configured hotkey, returning the ones that failed
+    pub fn register_all(&mut self) -> Vec<(HotkeyEvent, anyhow::Error)> {
+        HOTKEYS
+            .iter()
+            .filter_map(|(event, _)| self.register(*event).err().map(|e| (*event, e)))
+            .collect()
+    }
+
+    /// Register the hotkey for an event from config
+    pub fn register(&mut self, event: HotkeyEvent) -> Result<()> {
         // Unregister existing hotkey if any
-        self.unregister_toggle_hotkey();
+        self.unregister(event);
 
-        let hotkey_str = {
-            let state = self.shared_state.read();
-            state.config.overlay.toggle_hotkey.clone()
+        let Some((_, setting)) = HOTKEYS.iter().find(|(e, _)| *e == event) else {
+            return Ok(());
         };
+        let hotkey_str = setting(&self.shared_state.read().config.overlay).clone();
 
         if let Some(ref hotkey_str) = hotkey_str {
             match parse_hotkey(hotkey_str) {
@@ -176,60 +222,19 @@ impl HotkeyManager {
                         .register(hotkey)
                         .map_err(|e| anyhow!("Failed to register hotkey: {:?}", e))?;
 
-                    self.toggle_hotkey_id = Some(hotkey.id());
-                    info!("Registered overlay toggle hotkey: {}", hotkey_str);
-                }
-                Err(e) => {
-                    warn!("Failed to parse hotkey '{}': {}", hotkey_str, e);
-                    return Err(e);
-                }
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Unregister the toggle hotkey
-    pub fn unregister_toggle_hotkey(&mut self) {
-        if let Some(_id) = self.toggle_hotkey_id.take() {
-            // Get the hotkey string to reconstruct the HotKey for unregistration
-            let hotkey_str = {
-                let state = self.shared_state.read();
-                state.config.overlay.toggle_hotkey.clone()
-            };
-
-            if let Some(ref hotkey_str) = hotkey_str {
-                if let Ok(hotkey) = parse_hotkey(hotkey_str) {
-                    let _ = self.manager.unregister(hotkey);
-                }
-            }
-        }
-    }
-
-    /// Register the zone selection hotkey from config
-    pub fn register_zone_selection_hotkey(&mut self) -> Result<()> {
-        // Unregister existing hotkey if any
-        self.unregister_zone_selection_hotkey();
-
-        let hotkey_str = {
-            let state = self.shared_state.read();
-            state.config.overlay.zone_selection_hotkey.clone()
-        };
-
-        if let Some(ref hotkey_str) = hotkey_str {
-            match parse_hotkey(hotkey_str) {
-                Ok(hotkey) => {
-                    self.manager.register(hotkey).map_err(|e| {
-                        anyhow!("Failed to register zone selection hotkey: {:?}", e)
-                    })?;
-
-                    self.zone_selection_hotkey_id = Some(hotkey.id());
-                    info!("Registered zone selection hotkey: {}", hotkey_str);
+                    self.registered.push((event, hotkey));
+                    info!(
+                        "Registered {} hotkey: {}",
+                        event.name().to_lowercase(),
+                        hotkey_str
+                    );
                 }
                 Err(e) => {
                     warn!(
-                        "Failed to parse zone selection hotkey '{}': {}",
-                        hotkey_str, e
+                        "Failed to parse {} hotkey '{}': {}",
+                        event.name().to_lowercase(),
+                        hotkey_str,
+                        e
                     );
                     return Err(e);
                 }
@@ -239,23 +244,18 @@ impl HotkeyManager {
         Ok(())
     }
 
-    /// Unregister the zone selection hotkey
-    pub fn unregister_zone_selection_hotkey(&mut self) {
-        if let Some(_id) = self.zone_selection_hotkey_id.take() {
-            let hotkey_str = {
-                let state = self.shared_state.read();
-                state.config.overlay.zone_selection_hotkey.clone()
-            };
-
-            if let Some(ref hotkey_str) = hotkey_str {
-                if let Ok(hotkey) = parse_hotkey(hotkey_str) {
-                    let _ = self.manager.unregister(hotkey);
-                }
-            }
+    /// Unregister the hotkey for an event
+    pub fn unregister(&mut self, event: HotkeyEvent) {
+        if let Some(index) = self.registered.iter().position(|(e, _)| *e == event) {
+            let (_, hotkey) = self.registered.remove(index);
+            let _ = self.manager.unregister(hotkey);
         }
     }
 
     /// Process pending hotkey events
+    ///
+    /// Presses are recorded in the control log; ones over the hotkey rate
+    /// limit are ignored.
     pub fn poll_events(&self) -> HotkeyEvent {
         if let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
             // Only process key press events, ignore key releases
@@ -263,9 +263,25 @@ impl HotkeyManager {
                 return HotkeyEvent::None;
             }
 
-            if Some(event.id) == self.toggle_hotkey_id {
+            let Some(&(hotkey_event, _)) = self
+                .registered
+                .iter()
+                .find(|(_, hotkey)| hotkey.id() == event.id)
+            else {
+                return HotkeyEvent::None;
+            };
+
+            let mut state = self.shared_state.write();
+            if !state
+                .runtime
+                .control_log
+                .admit(ControlSource::Hotkey, hotkey_event.name())
+            {
+                return HotkeyEvent::None;
+            }
+
+            if hotkey_event == HotkeyEvent::ToggleOverlay {
                 // Toggle overlay visibility
-                let mut state = self.shared_state.write();
                 state.overlay_config.visible = !state.overlay_config.visible;
                 state.runtime.overlay_visible = state.overlay_config.visible;
 
@@ -277,13 +293,8 @@ impl HotkeyManager {
                         "hidden"
                     }
                 );
-                return HotkeyEvent::ToggleOverlay;
-            }
-
-            if Some(event.id) == self.zone_selection_hotkey_id {
-                info!("Hotkey pressed: zone selection mode requested");
-                return HotkeyEvent::EnterZoneSelection;
             }
+            return hotkey_event;
         }
 
         HotkeyEvent::None
@@ -292,8 +303,9 @@ impl HotkeyManager {
 
 impl Drop for HotkeyManager {
     fn drop(&mut self) {
-        self.unregister_toggle_hotkey();
-        self.unregister_zone_selection_hotkey();
+        for (_, hotkey) in self.registered.drain(..) {
+            let _ = self.manager.unregister(hotkey);
+        }
     }
 }
 
@@ -330,4 +342,14 @@ mod tests {
         let result = parse_hotkey("");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_default_hotkeys_parse() {
+        let overlay = OverlaySettings::default();
+        for (event, setting) in HOTKEYS {
+            if let Some(hotkey_str) = setting(&overlay) {
+                assert!(parse_hotkey(hotkey_str).is_ok(), "{}", event.name());
+            }
+        }
+    }
 }
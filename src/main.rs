@@ -7,25 +7,33 @@ mod analysis;
 mod app;
 mod capture;
 mod config;
+mod cpu_budget;
 mod dashboard;
+mod diagnostics;
 mod hotkey;
 mod mcp;
+mod network;
 mod overlay;
+mod power;
+mod qos;
 mod shared;
 mod storage;
+mod telemetry;
 mod vision;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::{info, Level};
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 use crate::analysis::Tip;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ConfigLoad, ConfigParseMode};
 use crate::overlay::{list_monitors, OverlayManager};
 use crate::shared::SharedAppState;
+use crate::telemetry::Feature;
 
 /// GamersToolKit - Real-time game analysis overlay
 #[derive(Parser, Debug)]
@@ -55,6 +63,40 @@ struct Args {
     /// Send a custom test message via the overlay to the screen
     #[arg(long)]
     test_msg: Option<String>,
+
+    /// Refuse to start if config.toml has unknown keys or invalid values
+    #[arg(long)]
+    strict_config: bool,
+
+    /// Start with default settings, no overlay, no capture and no active profile
+    #[arg(long)]
+    safe_mode: bool,
+
+    /// Restore an exported snapshot into a temporary data directory and run from it
+    #[arg(long, value_name = "PATH")]
+    load_snapshot: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// One-shot commands that run instead of the dashboard
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run OCR on an image and print its text and the active profile's zone readings
+    Ocr {
+        /// Read the image currently on the clipboard
+        #[arg(long)]
+        clipboard: bool,
+    },
+    /// Run the active profile's zones over every image in a folder and write a CSV
+    Batch {
+        /// Folder of screenshots
+        folder: PathBuf,
+        /// CSV file to write (default: zone_values.csv in the folder)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -94,50 +136,278 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Run from a restored snapshot instead of the user's data
+    if let Some(ref path) = args.load_snapshot {
+        let dir = storage::snapshot::load_into_sandbox(path)?;
+        info!("Loaded snapshot {:?} into {:?}", path, dir);
+    }
+
+    // One-shot OCR commands
+    match args.command {
+        Some(Command::Ocr { clipboard }) => return run_ocr_command(clipboard),
+        Some(Command::Batch { folder, output }) => return run_batch_command(&folder, output),
+        None => {}
+    }
+
     if !args.mcp {
         info!("GamersToolKit starting...");
         info!("Read-only mode: Screen capture and analysis only");
         info!("No game memory access, no input injection");
     }
 
+    // Track sessions so repeated crashes drop into safe mode (MCP clients may
+    // kill the server at any time, so it doesn't count)
+    let data_dir = storage::get_data_dir().ok();
+    let session_dir = data_dir.as_ref().filter(|_| !args.mcp);
+    let crashes = match session_dir {
+        Some(dir) => storage::session::begin_session(dir).unwrap_or_else(|e| {
+            warn!("Failed to record session start: {}", e);
+            0
+        }),
+        None => 0,
+    };
+    let safe_mode = args.safe_mode || crashes >= storage::session::SAFE_MODE_CRASH_THRESHOLD;
+    if safe_mode {
+        if args.safe_mode {
+            warn!("Starting in safe mode");
+        } else {
+            warn!(
+                "Previous {} sessions did not shut down cleanly, starting in safe mode",
+                crashes
+            );
+        }
+    }
+
     // Load or create configuration
-    let config = load_or_create_config();
+    let parse_mode = if args.strict_config {
+        ConfigParseMode::Strict
+    } else {
+        ConfigParseMode::Lenient
+    };
+    let ConfigLoad { config, issues } = if safe_mode {
+        info!("Safe mode: using default configuration");
+        ConfigLoad {
+            config: AppConfig::default(),
+            issues: Vec::new(),
+        }
+    } else {
+        load_or_create_config(parse_mode)?
+    };
+
+    // Proxy and certificates for model downloads and telemetry
+    network::set_settings(config.network.clone());
+
+    // Opt-in telemetry: record crashes and send anything pending from earlier sessions
+    let telemetry_settings = config.telemetry.clone();
+    telemetry::set_enabled(telemetry_settings.enabled);
+    if let Some(ref dir) = data_dir {
+        let telemetry_dir = telemetry::telemetry_dir(dir);
+        telemetry::install_panic_hook(telemetry_dir.clone());
+        if telemetry_settings.enabled {
+            tokio::spawn(async move {
+                if let Err(e) = telemetry::send_pending(telemetry_settings, telemetry_dir).await {
+                    warn!("Failed to send telemetry: {}", e);
+                }
+            });
+        }
+    }
+
+    let config_features = config.features;
 
     // Create shared state
     let shared_state = Arc::new(RwLock::new(SharedAppState::new(config)));
+    {
+        let mut state = shared_state.write();
+        state.runtime.config_issues = issues;
+        state.runtime.safe_mode = safe_mode;
+        let feature = if args.mcp {
+            Feature::McpServer
+        } else if args.overlay_only || args.test_msg.is_some() {
+            Feature::OverlayOnly
+        } else {
+            Feature::Dashboard
+        };
+        state.runtime.usage.record(feature);
+        if safe_mode {
+            state.runtime.usage.record(Feature::SafeMode);
+        }
+    }
 
-    if args.mcp {
+    let result = if args.mcp {
+        if !config_features.server {
+            anyhow::bail!("The MCP server is turned off in the [features] settings");
+        }
         // Run as an MCP Server
         let server = mcp::McpServer::new(Arc::clone(&shared_state));
-        server.run().await?;
-    } else if args.overlay_only || args.test_msg.is_some() {
-        // Run in overlay-only mode (also handle --test-msg here)
-        run_overlay_only(args.monitor, shared_state, args.test_msg)?;
+        server.run().await
     } else {
-        // Run in dashboard mode (default)
-        run_with_dashboard(args.monitor, shared_state)?;
+        storage::session::finish_session(session_dir.map(PathBuf::as_path), || {
+            if args.overlay_only || args.test_msg.is_some() {
+                if !config_features.overlay {
+                    anyhow::bail!("The overlay is turned off in the [features] settings");
+                }
+                // Run in overlay-only mode (also handle --test-msg here)
+                run_overlay_only(args.monitor, Arc::clone(&shared_state), args.test_msg)
+            } else {
+                // Run in dashboard mode (default)
+                run_with_dashboard(args.monitor, Arc::clone(&shared_state))
+            }
+        })
+    };
+
+    if let Some(ref dir) = data_dir {
+        if telemetry::is_enabled() {
+            let usage = shared_state.read().runtime.usage.clone();
+            if let Err(e) = telemetry::save_usage(&telemetry::telemetry_dir(dir), &usage) {
+                warn!("Failed to save usage statistics: {}", e);
+            }
+        }
     }
 
     if !args.mcp {
         info!("GamersToolKit shutdown complete");
     }
 
-    Ok(())
+    result
 }
 
 /// Load configuration from file or create default
-fn load_or_create_config() -> AppConfig {
+///
+/// In lenient mode problems in the file are logged and returned alongside the
+/// config; in strict mode they abort startup.
+fn load_or_create_config(mode: ConfigParseMode) -> Result<ConfigLoad> {
     if let Ok(config_dir) = storage::get_config_dir() {
         let config_path = config_dir.join("config.toml");
         if config_path.exists() {
-            if let Ok(config) = config::load_config(&config_path) {
-                info!("Loaded configuration from {:?}", config_path);
-                return config;
+            match config::load_config_with_mode(&config_path, mode) {
+                Ok(load) => {
+                    for issue in &load.issues {
+                        warn!("config.toml {}", issue);
+                    }
+                    info!("Loaded configuration from {:?}", config_path);
+                    return Ok(load);
+                }
+                Err(e) if mode == ConfigParseMode::Strict => return Err(e),
+                Err(e) => warn!("Failed to read {:?}: {}", config_path, e),
             }
         }
     }
     info!("Using default configuration");
-    AppConfig::default()
+    Ok(ConfigLoad {
+        config: AppConfig::default(),
+        issues: Vec::new(),
+    })
+}
+
+/// Run OCR on an image and print the text and each zone's reading
+///
+/// Uses the saved vision settings and the active profile's zones, the same
+/// way the dashboard reads captured frames.
+fn run_ocr_command(clipboard: bool) -> Result<()> {
+    if !clipboard {
+        anyhow::bail!("No image source given, use `ocr --clipboard`");
+    }
+
+    let frame = capture::clipboard::frame_from_clipboard()?;
+    let ConfigLoad { config, .. } = load_or_create_config(ConfigParseMode::Lenient)?;
+    let vision_settings = &config.vision;
+    let mut pipeline = init_cli_pipeline(vision_settings)?;
+
+    let preprocessing = Some(&vision_settings.preprocessing).filter(|pp| pp.enabled);
+    let result = pipeline.process_region_with_preprocessing(
+        &frame,
+        0,
+        0,
+        frame.width,
+        frame.height,
+        preprocessing,
+    )?;
+
+    println!("Text:");
+    for region in &result.text_regions {
+        println!("  {}", region.text);
+    }
+
+    // Read the active profile's zones from the image
+    let Some(profile) = load_active_profile(&config) else {
+        return Ok(());
+    };
+    let zones: Vec<_> = profile
+        .ocr_regions
+        .iter()
+        .filter(|zone| zone.enabled)
+        .cloned()
+        .collect();
+    let readings = vision::batch::read_zones(
+        &mut pipeline,
+        &frame,
+        &zones,
+        &vision_settings.preprocessing,
+        Some(&profile.dictionary),
+    );
+
+    println!();
+    println!("Zones ({}):", profile.name);
+    for (zone, reading) in zones.iter().zip(readings) {
+        match reading {
+            Some(vision::ensemble::Reading {
+                text,
+                confidence: Some(confidence),
+            }) => println!("  {}: {} ({:.0}%)", zone.name, text, confidence * 100.0),
+            Some(reading) => println!("  {}: {}", zone.name, reading.text),
+            None => println!("  {}: (not read)", zone.name),
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the active profile's zones over every image in a folder into a CSV
+fn run_batch_command(folder: &Path, output: Option<PathBuf>) -> Result<()> {
+    let ConfigLoad { config, .. } = load_or_create_config(ConfigParseMode::Lenient)?;
+    let profile = load_active_profile(&config)
+        .ok_or_else(|| anyhow::anyhow!("No active profile; activate one in the dashboard"))?;
+    let output = output.unwrap_or_else(|| folder.join(vision::batch::DEFAULT_OUTPUT_NAME));
+
+    let mut pipeline = init_cli_pipeline(&config.vision)?;
+    let mut run = vision::batch::BatchRun::start(folder, &output, &profile.ocr_regions)?;
+    while !run.is_done() {
+        run.process_next(
+            &mut pipeline,
+            &config.vision.preprocessing,
+            Some(&profile.dictionary),
+        )?;
+        eprintln!("Processed {}/{}", run.processed(), run.total());
+    }
+
+    println!(
+        "Wrote {} rows to {}",
+        run.total() - run.failed(),
+        output.display()
+    );
+    if run.failed() > 0 {
+        println!("Skipped {} images that could not be opened", run.failed());
+    }
+    Ok(())
+}
+
+/// Create the vision pipeline with the configured OCR backend
+fn init_cli_pipeline(vision_settings: &config::VisionSettings) -> Result<vision::VisionPipeline> {
+    let mut pipeline = vision::VisionPipeline::new()?;
+    pipeline.set_backend(vision_settings.backend);
+    pipeline.init_ocr()?;
+    Ok(pipeline)
+}
+
+/// Load the profile that is active in the dashboard
+fn load_active_profile(config: &AppConfig) -> Option<storage::profiles::GameProfile> {
+    let id = config.dashboard.active_profile_id.as_deref()?;
+    let path = storage::get_profiles_dir()
+        .ok()?
+        .join(format!("{}.json", id));
+    storage::profiles::load_profile(&path)
+        .map_err(|e| warn!("Failed to load profile '{}': {}", id, e))
+        .ok()
 }
 
 /// Run in overlay-only mode
@@ -178,7 +448,7 @@ fn run_overlay_only(
                 message: msg,
                 priority: 100,
                 duration_ms: Some(8000),
-                play_sound: false,
+                ..Default::default()
             });
         } else {
             let _ = tip_sender.send(Tip {
@@ -186,7 +456,7 @@ fn run_overlay_only(
                 message: "GamersToolKit overlay is working!".to_string(),
                 priority: 50,
                 duration_ms: Some(5000),
-                play_sound: false,
+                ..Default::default()
             });
 
             std::thread::sleep(std::time::Duration::from_secs(3));
@@ -195,7 +465,7 @@ fn run_overlay_only(
                 message: "Press ESC or close the window to exit".to_string(),
                 priority: 25,
                 duration_ms: Some(8000),
-                play_sound: false,
+                ..Default::default()
             });
         }
     });
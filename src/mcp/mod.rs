@@ -1,3 +1,4 @@
+use crate::shared::control::ControlSource;
 use crate::shared::SharedAppState;
 use async_trait::async_trait;
 use parking_lot::RwLock;
@@ -102,9 +103,26 @@ impl ServerHandler for GamersToolKitHandler {
         params: CallToolRequestParams,
         _runtime: Arc<dyn McpServerTrait>,
     ) -> std::result::Result<CallToolResult, CallToolError> {
+        let name = params.name.clone();
         let tool_params: tools::GamersToolKitTools =
             tools::GamersToolKitTools::try_from(params).map_err(CallToolError::new)?;
 
+        if tool_params.is_control()
+            && !self
+                .shared_state
+                .write()
+                .runtime
+                .control_log
+                .admit(ControlSource::Mcp, name.as_str())
+        {
+            let (limit, window) = ControlSource::Mcp.rate_limit();
+            return Err(CallToolError::from_message(format!(
+                "Rate limited: at most {} changes per {} seconds",
+                limit,
+                window.as_secs()
+            )));
+        }
+
         match tool_params {
             tools::GamersToolKitTools::GetCurrentScreenTool(t) => {
                 t.call_tool(Arc::clone(&self.shared_state))
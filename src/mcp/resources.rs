@@ -167,6 +167,9 @@ impl RuntimeStatusResource {
         let status = json!({
             "is_capturing": rt.is_capturing,
             "capture_fps": rt.capture_fps,
+            "dropped_frames": rt.dropped_frames,
+            "dropped_overlay_events": rt.dropped_overlay_events,
+            "overlay_queue_depth": rt.overlay_queue_depth,
             "capture_target": rt.current_capture_target,
             "is_overlay_running": rt.is_overlay_running,
             "overlay_visible": rt.overlay_visible,
@@ -174,6 +177,13 @@ impl RuntimeStatusResource {
             "pending_tips_count": rt.pending_tips.len(),
             "current_screen": screen,
             "screen_just_changed": rt.screen_just_changed,
+            "scene": {
+                "brightness": rt.scene.brightness,
+                "shadows": rt.scene.shadows,
+                "saturation": rt.scene.saturation,
+                "dark": rt.scene.dark,
+            },
+            "cursor": rt.cursor,
             "last_error": rt.last_error,
             "last_ocr_results_count": rt.last_ocr_results.len(),
             "active_profile_id": state.active_profile_id,
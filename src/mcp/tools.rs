@@ -1,5 +1,6 @@
 #![allow(clippy::enum_variant_names)]
 
+use crate::analysis::template::fill_template;
 use crate::analysis::Tip;
 use crate::capture::{capture_frame_once, CaptureTarget};
 use crate::shared::SharedAppState;
@@ -91,12 +92,16 @@ impl GetActiveProfileTool {
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct SendOverlayTipTool {
-    /// The tip message to display
+    /// The tip message to display; `{name}` is replaced with the active profile's variable
     message: String,
 
     /// Priority from 0-100 (100 is highest). Default 50
     priority: Option<u32>,
 
+    /// Whether the tip reacts to the latest zone readings; such tips are dropped
+    /// when the readings are older than the latency budget. Default false
+    reacts_to_screen: Option<bool>,
+
     /// How long to display in milliseconds. Default 5000
     duration_ms: Option<u64>,
 }
@@ -109,12 +114,24 @@ impl SendOverlayTipTool {
         let priority = self.priority.unwrap_or(50);
         let duration_ms = self.duration_ms.unwrap_or(5000);
 
+        let (variables, readings_captured_at) = {
+            let state = shared_state.read();
+            (
+                state
+                    .active_profile()
+                    .map(|p| p.variable_values())
+                    .unwrap_or_default(),
+                state.runtime.readings_captured_at,
+            )
+        };
+
         let tip = Tip {
             id: uuid::Uuid::new_v4().to_string(),
-            message: self.message.clone(),
+            message: fill_template(&self.message, &variables),
             priority,
             duration_ms: Some(duration_ms),
             play_sound: false,
+            captured_at: readings_captured_at.filter(|_| self.reacts_to_screen.unwrap_or(false)),
         };
 
         tracing::info!("MCP send_overlay_tip: {}", tip.message);
@@ -163,6 +180,9 @@ impl GetRuntimeStatusTool {
         ok_json(json!({
             "is_capturing": rt.is_capturing,
             "capture_fps": rt.capture_fps,
+            "dropped_frames": rt.dropped_frames,
+            "dropped_overlay_events": rt.dropped_overlay_events,
+            "overlay_queue_depth": rt.overlay_queue_depth,
             "capture_target": rt.current_capture_target,
             "is_overlay_running": rt.is_overlay_running,
             "overlay_visible": rt.overlay_visible,
@@ -170,6 +190,13 @@ impl GetRuntimeStatusTool {
             "pending_tips_count": rt.pending_tips.len(),
             "current_screen": screen,
             "screen_just_changed": rt.screen_just_changed,
+            "scene": {
+                "brightness": rt.scene.brightness,
+                "shadows": rt.scene.shadows,
+                "saturation": rt.scene.saturation,
+                "dark": rt.scene.dark,
+            },
+            "cursor": rt.cursor,
             "last_error": rt.last_error,
             "last_ocr_results_count": rt.last_ocr_results.len(),
             "active_profile_id": state.active_profile_id,
@@ -205,7 +232,7 @@ impl ListProfilesTool {
                     "version": p.version,
                     "ocr_regions_count": p.ocr_regions.len(),
                     "screens_count": p.screens.len(),
-                    "rules_count": p.rules.len(),
+                    "rules_count": p.rules.len() + p.library_rules.len(),
                     "is_active": state.active_profile_id.as_deref() == Some(&p.id),
                 })
             })
@@ -290,10 +317,20 @@ impl CreateProfileTool {
             ocr_regions: vec![],
             templates: vec![],
             rules: vec![],
+            library_rules: vec![],
             labeled_regions: vec![],
             screens: vec![],
             screen_recognition_enabled: false,
             screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
         };
 
         // Save to disk
@@ -616,6 +653,13 @@ impl AddOcrRegionTool {
             content_type: ct,
             enabled: true,
             preprocessing: None,
+            ensemble: false,
+            allowed_chars: None,
+            geometry: Default::default(),
+            scrolling: false,
+            value_epsilon: 0.0,
+            source: None,
+            phases: vec![],
         };
 
         if let Some(profile) = state.profiles.iter_mut().find(|p| p.id == profile_id) {
@@ -786,12 +830,15 @@ impl AddScreenTool {
             match_mode: ScreenMatchMode::Anchors,
             anchors: vec![],
             full_template: None,
+            extra_templates: vec![],
             match_threshold: self.match_threshold.unwrap_or(0.8),
             enabled: true,
             priority: self.priority.unwrap_or(0),
             ocr_zone_overrides: vec![],
             rules_to_trigger: vec![],
             show_notification: self.show_notification.unwrap_or(true),
+            min_required_anchors: None,
+            popup_bounds: None,
         };
 
         if let Some(profile) = state.profiles.iter_mut().find(|p| p.id == profile_id) {
@@ -860,7 +907,9 @@ impl GetLastOcrResultsTool {
 )]
 #[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
 pub struct CaptureScreenshotTool {
-    /// Capture source: "primary_monitor", "monitor:0", "monitor:1", or a window title.
+    /// Capture source: "primary_monitor", "monitor:0", "monitor:1", "pid:1234" (main window of
+    /// a process), "exe:game.exe" (main window of a program), "demo" (the built-in demo
+    /// game), or a window title.
     /// If omitted, uses the current capture target when capture is running, otherwise primary monitor.
     source: Option<String>,
 
@@ -982,6 +1031,7 @@ impl CaptureScreenshotTool {
     fn parse_source(source: &str) -> CaptureTarget {
         match source.to_lowercase().as_str() {
             "primary_monitor" | "primary" => CaptureTarget::PrimaryMonitor,
+            "demo" => CaptureTarget::Demo,
             s if s.starts_with("monitor:") => {
                 if let Ok(idx) = s.trim_start_matches("monitor:").parse::<usize>() {
                     CaptureTarget::MonitorIndex(idx)
@@ -989,6 +1039,13 @@ impl CaptureScreenshotTool {
                     CaptureTarget::PrimaryMonitor
                 }
             }
+            s if s.starts_with("pid:") => match s.trim_start_matches("pid:").parse::<u32>() {
+                Ok(pid) => CaptureTarget::Process(pid),
+                Err(_) => CaptureTarget::PrimaryMonitor,
+            },
+            s if s.starts_with("exe:") => {
+                CaptureTarget::Executable(s.trim_start_matches("exe:").trim().to_string())
+            }
             title => CaptureTarget::Window(title.to_string()),
         }
     }
@@ -1022,3 +1079,21 @@ tool_box!(
         CaptureScreenshotTool
     ]
 );
+
+impl GamersToolKitTools {
+    /// Whether the tool changes something (a control command) rather than
+    /// only reading state; those are logged and rate limited
+    pub fn is_control(&self) -> bool {
+        matches!(
+            self,
+            GamersToolKitTools::SendOverlayTipTool(_)
+                | GamersToolKitTools::SetActiveProfileTool(_)
+                | GamersToolKitTools::CreateProfileTool(_)
+                | GamersToolKitTools::DeleteProfileTool(_)
+                | GamersToolKitTools::UpdateOverlayConfigTool(_)
+                | GamersToolKitTools::AddOcrRegionTool(_)
+                | GamersToolKitTools::RemoveOcrRegionTool(_)
+                | GamersToolKitTools::AddScreenTool(_)
+        )
+    }
+}
@@ -0,0 +1,148 @@
+//! HTTP client settings
+//!
+//! Model downloads and telemetry go through clients built here, so a proxy
+//! and extra root certificates configured in Settings apply to all of them.
+//! Without an explicit proxy, the standard `HTTPS_PROXY`, `HTTP_PROXY`,
+//! `ALL_PROXY` and `NO_PROXY` environment variables are honored.
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use std::time::Duration;
+
+use crate::config::NetworkSettings;
+
+/// Settings every new client is built with
+static SETTINGS: RwLock<Option<NetworkSettings>> = RwLock::new(None);
+
+/// Environment variables reqwest reads a proxy from, in order of precedence
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// Use these settings for clients created from now on
+pub fn set_settings(settings: NetworkSettings) {
+    *SETTINGS.write() = Some(settings);
+}
+
+/// Current network settings
+pub fn settings() -> NetworkSettings {
+    SETTINGS.read().clone().unwrap_or_default()
+}
+
+/// HTTP client with the current network settings
+pub fn http_client(timeout: Duration) -> Result<reqwest::Client> {
+    build_client(&settings(), timeout)
+}
+
+/// HTTP client with the given network settings
+pub fn build_client(settings: &NetworkSettings, timeout: Duration) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
+
+    if let Some(url) = proxy_url(settings) {
+        let proxy = reqwest::Proxy::all(url)
+            .with_context(|| format!("Invalid proxy URL: {}", url))?
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(ref path) = settings.ca_bundle {
+        let pem =
+            std::fs::read(path).with_context(|| format!("Failed to read CA bundle {:?}", path))?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA bundle {:?}", path))?;
+        if certificates.is_empty() {
+            anyhow::bail!("No certificates in CA bundle {:?}", path);
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    builder.build().context("Failed to create HTTP client")
+}
+
+/// Check the settings by building a client with them
+pub fn validate(settings: &NetworkSettings) -> Result<()> {
+    build_client(settings, Duration::from_secs(30)).map(|_| ())
+}
+
+/// Explicit proxy from the settings, ignoring a blank one
+fn proxy_url(settings: &NetworkSettings) -> Option<&str> {
+    settings
+        .proxy_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+}
+
+/// Proxy requests go through, and where it comes from, for display
+pub fn proxy_description(settings: &NetworkSettings) -> String {
+    if let Some(url) = proxy_url(settings) {
+        return format!("Using proxy {}", url);
+    }
+    PROXY_ENV_VARS
+        .iter()
+        .find_map(|var| {
+            std::env::var(var)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .map(|value| format!("Using proxy {} from {}", value, var))
+        })
+        .unwrap_or_else(|| "Connecting directly".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_proxy_settings() {
+        let blank = NetworkSettings {
+            proxy_url: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(proxy_url(&blank), None);
+        assert!(validate(&blank).is_ok());
+
+        let proxy = NetworkSettings {
+            proxy_url: Some("http://proxy.example.com:8080".to_string()),
+            ..Default::default()
+        };
+        assert!(validate(&proxy).is_ok());
+        assert_eq!(
+            proxy_description(&proxy),
+            "Using proxy http://proxy.example.com:8080"
+        );
+
+        let invalid = NetworkSettings {
+            proxy_url: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        assert!(validate(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_ca_bundle_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let missing = NetworkSettings {
+            ca_bundle: Some(dir.path().join("missing.pem")),
+            ..Default::default()
+        };
+        assert!(validate(&missing).is_err());
+
+        let empty: PathBuf = dir.path().join("empty.pem");
+        std::fs::write(&empty, "no certificates here").unwrap();
+        let empty = NetworkSettings {
+            ca_bundle: Some(empty),
+            ..Default::default()
+        };
+        assert!(validate(&empty).is_err());
+    }
+}
@@ -4,11 +4,12 @@
 //! Displays tips and alerts using egui_overlay with click passthrough.
 //! The overlay is a separate window that doesn't interact with the game.
 
+pub mod toast;
 pub mod widgets;
 pub mod zone_selection;
 
 use anyhow::Result;
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crossbeam_channel::Receiver;
 use egui::{Align2, Color32, FontId, RichText, Rounding, Vec2};
 use egui_overlay::egui_window_glfw_passthrough::glfw;
 use egui_overlay::{
@@ -18,10 +19,17 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::info;
+use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFOEXW, MONITORINFOF_PRIMARY,
+};
 
 use crate::analysis::Tip;
 use crate::overlay::widgets::{PriorityStyles, TipStyle};
 use crate::overlay::zone_selection::{render_zone_selection, ZoneSelectionOverlayState};
+use crate::shared::channels::{
+    event_queue, EventSender, COMMAND_QUEUE_CAPACITY, TIP_QUEUE_CAPACITY,
+};
 
 /// Mode for overlay interaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -100,6 +108,30 @@ pub enum ZoneCommand {
         /// Confidence level (0.0 - 1.0)
         confidence: f32,
     },
+    /// Update the pinned checklist display (None hides it)
+    UpdateChecklist { checklist: Option<ChecklistDisplay> },
+    /// Show or hide the playtime limit banner (shown even while hidden)
+    UpdateWellnessNag { message: Option<String> },
+}
+
+/// A checklist as shown on the overlay
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistDisplay {
+    /// Checklist name
+    pub name: String,
+    /// Item texts and whether they're done
+    pub items: Vec<(String, bool)>,
+}
+
+/// What the overlay shows at a moment, for the dashboard's preview
+#[derive(Debug, Clone, Default)]
+pub struct OverlaySnapshot {
+    /// Tips on screen, oldest first
+    pub tips: Vec<Tip>,
+    /// Pinned checklist
+    pub checklist: Option<ChecklistDisplay>,
+    /// Playtime limit banner
+    pub wellness_nag: Option<String>,
 }
 
 /// Results sent from overlay back to dashboard
@@ -235,6 +267,10 @@ pub struct OverlayState {
     current_screen_name: Option<String>,
     /// Current screen recognition confidence
     current_screen_confidence: f32,
+    /// Pinned checklist
+    checklist: Option<ChecklistDisplay>,
+    /// Playtime limit banner
+    wellness_nag: Option<String>,
 }
 
 impl OverlayState {
@@ -248,6 +284,8 @@ impl OverlayState {
             capture_screen_id: None,
             current_screen_name: None,
             current_screen_confidence: 0.0,
+            checklist: None,
+            wellness_nag: None,
         }
     }
 }
@@ -300,25 +338,101 @@ pub fn list_monitors() -> Vec<MonitorInfo> {
     monitors
 }
 
+/// List connected monitors through Win32
+///
+/// Uses the same indices as [`list_monitors`] (primary first) without
+/// initializing GLFW, which only the overlay thread may do while the overlay
+/// runs, so it's safe to call from any thread.
+pub fn system_monitors() -> Vec<MonitorInfo> {
+    unsafe extern "system" fn enum_callback(
+        monitor: HMONITOR,
+        _: HDC,
+        _: *mut RECT,
+        lparam: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(lparam.0 as *mut Vec<MonitorInfo>);
+
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(monitor, &mut info.monitorInfo).as_bool() {
+            let bounds = info.monitorInfo.rcMonitor;
+            let work = info.monitorInfo.rcWork;
+            let device = &info.szDevice;
+            let len = device.iter().position(|&c| c == 0).unwrap_or(device.len());
+            monitors.push(MonitorInfo {
+                index: 0,
+                name: Some(String::from_utf16_lossy(&device[..len])).filter(|n| !n.is_empty()),
+                position: (bounds.left, bounds.top),
+                work_area: (
+                    work.left,
+                    work.top,
+                    work.right - work.left,
+                    work.bottom - work.top,
+                ),
+                is_primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            });
+        }
+        BOOL(1)
+    }
+
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+    // SAFETY: `monitors` outlives the enumeration, which runs synchronously
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(enum_callback),
+            LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+        );
+    }
+
+    // GLFW lists the primary monitor first
+    monitors.sort_by_key(|monitor| !monitor.is_primary);
+    for (index, monitor) in monitors.iter_mut().enumerate() {
+        monitor.index = index;
+    }
+    monitors
+}
+
+/// Monitor a full screen game covers, found by its top-left corner, and the
+/// monitor the overlay can show on meanwhile (the primary unless it's the
+/// game's, else the next one)
+///
+/// Returns `(game, other)` indices; `other` is None with a single monitor.
+pub fn monitor_beside(
+    monitors: &[MonitorInfo],
+    game_position: (i32, i32),
+) -> (Option<usize>, Option<usize>) {
+    let game = monitors
+        .iter()
+        .find(|monitor| monitor.position == game_position)
+        .map(|monitor| monitor.index);
+    let other = monitors
+        .iter()
+        .find(|monitor| Some(monitor.index) != game)
+        .map(|monitor| monitor.index);
+    (game, other.filter(|_| game.is_some()))
+}
+
 /// Overlay window manager
 pub struct OverlayManager {
     state: Arc<RwLock<OverlayState>>,
-    tip_sender: Sender<Tip>,
+    tip_sender: EventSender<Tip>,
     tip_receiver: Receiver<Tip>,
     /// Channel for sending zone commands to overlay
-    zone_cmd_sender: Sender<ZoneCommand>,
+    zone_cmd_sender: EventSender<ZoneCommand>,
     zone_cmd_receiver: Receiver<ZoneCommand>,
     /// Channel for receiving zone selection results from overlay
-    zone_result_sender: Sender<ZoneSelectionResult>,
+    zone_result_sender: EventSender<ZoneSelectionResult>,
     zone_result_receiver: Receiver<ZoneSelectionResult>,
 }
 
 impl OverlayManager {
     /// Create a new overlay manager
     pub fn new(config: OverlayConfig) -> Result<Self> {
-        let (tip_sender, tip_receiver) = unbounded();
-        let (zone_cmd_sender, zone_cmd_receiver) = unbounded();
-        let (zone_result_sender, zone_result_receiver) = unbounded();
+        let (tip_sender, tip_receiver) = event_queue(TIP_QUEUE_CAPACITY);
+        let (zone_cmd_sender, zone_cmd_receiver) = event_queue(COMMAND_QUEUE_CAPACITY);
+        let (zone_result_sender, zone_result_receiver) = event_queue(COMMAND_QUEUE_CAPACITY);
         Ok(Self {
             state: Arc::new(RwLock::new(OverlayState::new(config))),
             tip_sender,
@@ -330,11 +444,38 @@ impl OverlayManager {
         })
     }
 
+    /// What the overlay shows right now (expired tips excluded)
+    pub fn snapshot(&self) -> OverlaySnapshot {
+        let state = self.state.read();
+        OverlaySnapshot {
+            tips: state
+                .tips
+                .iter()
+                .filter(|t| !t.is_expired())
+                .map(|t| t.tip.clone())
+                .collect(),
+            checklist: state.checklist.clone(),
+            wellness_nag: state.wellness_nag.clone(),
+        }
+    }
+
     /// Get a sender for adding tips from other threads
-    pub fn tip_sender(&self) -> Sender<Tip> {
+    pub fn tip_sender(&self) -> EventSender<Tip> {
         self.tip_sender.clone()
     }
 
+    /// Tips and commands dropped because the overlay fell behind
+    pub fn dropped_events(&self) -> u64 {
+        self.tip_sender.dropped()
+            + self.zone_cmd_sender.dropped()
+            + self.zone_result_sender.dropped()
+    }
+
+    /// Tips and commands waiting for the overlay to pick them up
+    pub fn queued_events(&self) -> usize {
+        self.tip_sender.len() + self.zone_cmd_sender.len() + self.zone_result_sender.len()
+    }
+
     /// Show a tip on the overlay
     pub fn show_tip(&self, tip: Tip) {
         let _ = self.tip_sender.send(tip);
@@ -469,6 +610,20 @@ impl OverlayManager {
         });
     }
 
+    /// Update the pinned checklist displayed on the overlay
+    pub fn update_checklist(&self, checklist: Option<ChecklistDisplay>) {
+        let _ = self
+            .zone_cmd_sender
+            .send(ZoneCommand::UpdateChecklist { checklist });
+    }
+
+    /// Show or hide the playtime limit banner on the overlay
+    pub fn update_wellness_nag(&self, message: Option<String>) {
+        let _ = self
+            .zone_cmd_sender
+            .send(ZoneCommand::UpdateWellnessNag { message });
+    }
+
     /// Run the overlay event loop (blocking)
     /// This should be called from the main thread
     pub fn run(&self) -> Result<()> {
@@ -507,7 +662,7 @@ struct OverlayApp {
     /// Receiver for zone selection commands
     zone_cmd_receiver: Receiver<ZoneCommand>,
     /// Sender for zone selection results
-    zone_result_sender: Sender<ZoneSelectionResult>,
+    zone_result_sender: EventSender<ZoneSelectionResult>,
     /// Whether we've positioned the window on the target monitor
     positioned: bool,
     /// Cached monitor bounds for the selected monitor (x, y, width, height)
@@ -611,6 +766,12 @@ impl EguiOverlay for OverlayApp {
                     state.current_screen_name = screen_name;
                     state.current_screen_confidence = confidence;
                 }
+                ZoneCommand::UpdateChecklist { checklist } => {
+                    state.checklist = checklist;
+                }
+                ZoneCommand::UpdateWellnessNag { message } => {
+                    state.wellness_nag = message;
+                }
             }
         }
 
@@ -889,42 +1050,42 @@ impl EguiOverlay for OverlayApp {
         // Get state for rendering
         let state = self.state.read();
 
-        if !state.config.enabled || !state.config.visible || state.tips.is_empty() {
-            // Request repaint to check for new tips or visibility changes
+        // The playtime limit banner ignores the visibility hotkey
+        if state.config.enabled {
+            if let Some(ref message) = state.wellness_nag {
+                render_wellness_nag(egui_ctx, message);
+            }
+        }
+
+        if !state.config.enabled || !state.config.visible {
+            // Request repaint to check for visibility changes
             egui_ctx.request_repaint_after(Duration::from_millis(100));
             return;
         }
 
-        // Determine anchor alignment
-        let anchor = match state.config.anchor {
-            OverlayAnchor::TopLeft => Align2::LEFT_TOP,
-            OverlayAnchor::TopRight => Align2::RIGHT_TOP,
-            OverlayAnchor::BottomLeft => Align2::LEFT_BOTTOM,
-            OverlayAnchor::BottomRight => Align2::RIGHT_BOTTOM,
-        };
-
-        let offset = Vec2::new(state.config.offset.0 as f32, state.config.offset.1 as f32);
+        if let Some(ref checklist) = state.checklist {
+            let (anchor, offset) = CHECKLIST_PLACEMENT;
+            render_checklist_widget(egui_ctx, checklist, anchor, offset);
+        }
 
-        let max_width = state.config.max_width;
+        if state.tips.is_empty() {
+            // Request repaint to check for new tips
+            egui_ctx.request_repaint_after(Duration::from_millis(100));
+            return;
+        }
 
         // Draw tips window
+        let offset = Vec2::new(state.config.offset.0 as f32, state.config.offset.1 as f32);
         egui::Area::new(egui::Id::new("tips_overlay"))
-            .anchor(anchor, offset)
+            .anchor(anchor_align(state.config.anchor), offset)
             .show(egui_ctx, |ui| {
-                egui::Frame::none()
-                    .fill(Color32::TRANSPARENT)
-                    .show(ui, |ui| {
-                        ui.set_max_width(max_width);
-
-                        for display_tip in state.tips.iter() {
-                            let style =
-                                get_style_for_priority(display_tip.tip.priority, &state.styles);
-                            let opacity = calculate_opacity(display_tip, state.config.opacity);
-
-                            draw_tip(ui, display_tip, style, opacity);
-                            ui.add_space(8.0);
-                        }
-                    });
+                draw_tip_stack(
+                    ui,
+                    state.tips.iter().map(|t| &t.tip),
+                    &state.config,
+                    &state.styles,
+                    1.0,
+                );
             });
 
         // Request continuous repaints while we have tips
@@ -932,6 +1093,42 @@ impl EguiOverlay for OverlayApp {
     }
 }
 
+/// Where the tip stack sits for an anchor corner
+pub fn anchor_align(anchor: OverlayAnchor) -> Align2 {
+    match anchor {
+        OverlayAnchor::TopLeft => Align2::LEFT_TOP,
+        OverlayAnchor::TopRight => Align2::RIGHT_TOP,
+        OverlayAnchor::BottomLeft => Align2::LEFT_BOTTOM,
+        OverlayAnchor::BottomRight => Align2::RIGHT_BOTTOM,
+    }
+}
+
+/// Draw the stack of tips, oldest first
+///
+/// `scale` shrinks everything for the dashboard's preview (1.0 on the
+/// overlay itself).
+pub fn draw_tip_stack<'a>(
+    ui: &mut egui::Ui,
+    tips: impl IntoIterator<Item = &'a Tip>,
+    config: &OverlayConfig,
+    styles: &PriorityStyles,
+    scale: f32,
+) {
+    egui::Frame::none()
+        .fill(Color32::TRANSPARENT)
+        .show(ui, |ui| {
+            ui.set_max_width(config.max_width * scale);
+
+            for tip in tips {
+                let style = get_style_for_priority(tip.priority, styles);
+                let opacity = calculate_opacity(config.opacity);
+
+                draw_tip(ui, tip, style, opacity, scale);
+                ui.add_space(8.0 * scale);
+            }
+        });
+}
+
 /// Get the appropriate style based on priority
 fn get_style_for_priority(priority: u32, styles: &PriorityStyles) -> &TipStyle {
     match priority {
@@ -943,7 +1140,7 @@ fn get_style_for_priority(priority: u32, styles: &PriorityStyles) -> &TipStyle {
 }
 
 /// Calculate opacity with fade-in/fade-out effects
-fn calculate_opacity(_tip: &DisplayTip, base_opacity: f32) -> f32 {
+fn calculate_opacity(base_opacity: f32) -> f32 {
     // Simplified calculation for debugging to ensure it stays visible
     if base_opacity > 0.8 {
         base_opacity
@@ -953,7 +1150,7 @@ fn calculate_opacity(_tip: &DisplayTip, base_opacity: f32) -> f32 {
 }
 
 /// Draw a single tip
-fn draw_tip(ui: &mut egui::Ui, display_tip: &DisplayTip, style: &TipStyle, opacity: f32) {
+fn draw_tip(ui: &mut egui::Ui, tip: &Tip, style: &TipStyle, opacity: f32, scale: f32) {
     let bg_color = Color32::from_rgba_unmultiplied(
         (style.background[0] * 255.0) as u8,
         (style.background[1] * 255.0) as u8,
@@ -970,13 +1167,13 @@ fn draw_tip(ui: &mut egui::Ui, display_tip: &DisplayTip, style: &TipStyle, opaci
 
     egui::Frame::none()
         .fill(bg_color)
-        .rounding(Rounding::same(style.corner_radius))
-        .inner_margin(style.padding)
+        .rounding(Rounding::same(style.corner_radius * scale))
+        .inner_margin(style.padding * scale)
         .show(ui, |ui| {
             ui.label(
-                RichText::new(&display_tip.tip.message)
+                RichText::new(&tip.message)
                     .color(text_color)
-                    .font(FontId::proportional(14.0)),
+                    .font(FontId::proportional(14.0 * scale)),
             );
         });
 }
@@ -1274,3 +1471,86 @@ pub fn render_screen_status_widget(
                 });
         });
 }
+
+/// Render the pinned checklist widget
+/// Shows each item with a tick once done
+pub fn render_checklist_widget(
+    ctx: &egui::Context,
+    checklist: &ChecklistDisplay,
+    anchor: Align2,
+    offset: Vec2,
+) {
+    egui::Area::new(egui::Id::new("checklist_widget"))
+        .anchor(anchor, offset)
+        .show(ctx, |ui| draw_checklist(ui, checklist, 1.0));
+}
+
+/// Where the pinned checklist sits: anchor and offset
+pub const CHECKLIST_PLACEMENT: (Align2, Vec2) = (Align2::LEFT_CENTER, Vec2::new(20.0, 0.0));
+
+/// Draw the pinned checklist, `scale` times its on-screen size
+pub fn draw_checklist(ui: &mut egui::Ui, checklist: &ChecklistDisplay, scale: f32) {
+    egui::Frame::none()
+        .fill(Color32::from_rgba_unmultiplied(20, 20, 30, 200))
+        .rounding(Rounding::same(6.0 * scale))
+        .inner_margin(8.0 * scale)
+        .show(ui, |ui| {
+            let done = checklist.items.iter().filter(|(_, done)| *done).count();
+            ui.label(
+                RichText::new(format!(
+                    "{} ({}/{})",
+                    checklist.name,
+                    done,
+                    checklist.items.len()
+                ))
+                .color(Color32::WHITE)
+                .font(FontId::proportional(13.0 * scale)),
+            );
+            ui.add_space(4.0 * scale);
+
+            for (text, done) in &checklist.items {
+                let (mark, color) = if *done {
+                    ("✔", Color32::from_rgb(100, 255, 100))
+                } else {
+                    ("○", Color32::from_rgb(180, 180, 180))
+                };
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(mark)
+                            .color(color)
+                            .font(FontId::proportional(12.0 * scale)),
+                    );
+                    let text = RichText::new(text)
+                        .color(color)
+                        .font(FontId::proportional(12.0 * scale));
+                    ui.label(if *done { text.strikethrough() } else { text });
+                });
+            }
+        });
+}
+
+/// Render the playtime limit banner at the top center of the screen
+pub fn render_wellness_nag(ctx: &egui::Context, message: &str) {
+    let (anchor, offset) = WELLNESS_NAG_PLACEMENT;
+    egui::Area::new(egui::Id::new("wellness_nag"))
+        .anchor(anchor, offset)
+        .show(ctx, |ui| draw_wellness_nag(ui, message, 1.0));
+}
+
+/// Where the playtime limit banner sits: anchor and offset
+pub const WELLNESS_NAG_PLACEMENT: (Align2, Vec2) = (Align2::CENTER_TOP, Vec2::new(0.0, 40.0));
+
+/// Draw the playtime limit banner, `scale` times its on-screen size
+pub fn draw_wellness_nag(ui: &mut egui::Ui, message: &str, scale: f32) {
+    egui::Frame::none()
+        .fill(Color32::from_rgba_unmultiplied(120, 30, 30, 230))
+        .rounding(Rounding::same(8.0 * scale))
+        .inner_margin(16.0 * scale)
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(message)
+                    .color(Color32::WHITE)
+                    .font(FontId::proportional(20.0 * scale)),
+            );
+        });
+}
@@ -0,0 +1,128 @@
+//! Windows toast notifications
+//!
+//! Tips the overlay can't show, because it's turned off, failed to start, or
+//! is hidden behind a game in exclusive full screen, can be sent to Windows
+//! as toast notifications instead. Which ones is set per priority band in
+//! the overlay settings.
+
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+use windows::core::{w, HSTRING};
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_SET_VALUE,
+    REG_OPTION_NON_VOLATILE, REG_SZ,
+};
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+
+use crate::analysis::Tip;
+
+/// Application user model ID toasts are shown under
+const APP_ID: &str = "GamersToolKit";
+
+/// Show a tip as a toast notification
+pub fn show_tip(tip: &Tip) -> Result<()> {
+    static REGISTERED: OnceLock<bool> = OnceLock::new();
+    if !REGISTERED.get_or_init(|| {
+        register_app_id()
+            .map_err(|e| tracing::warn!("Toasts may not show: {:#}", e))
+            .is_ok()
+    }) {
+        tracing::debug!("Showing toast without a registered app ID");
+    }
+
+    let xml = XmlDocument::new()?;
+    xml.LoadXml(&HSTRING::from(toast_xml(tip)))
+        .context("Invalid toast content")?;
+    let toast = ToastNotification::CreateToastNotification(&xml)?;
+    ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(APP_ID))?
+        .Show(&toast)
+        .context("Failed to show toast")
+}
+
+/// Register the app ID with a display name, which Windows needs to show
+/// toasts of an app that isn't installed as a package
+fn register_app_id() -> Result<()> {
+    let display_name: Vec<u8> = APP_ID
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(u16::to_le_bytes)
+        .collect();
+
+    let mut key = HKEY::default();
+    // SAFETY: the key is created, written with a NUL-terminated UTF-16
+    // string, and closed before it goes out of scope
+    unsafe {
+        RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Classes\\AppUserModelId\\GamersToolKit"),
+            0,
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_SET_VALUE,
+            None,
+            &mut key,
+            None,
+        )
+        .ok()
+        .context("Failed to create the app ID registry key")?;
+        let result = RegSetValueExW(key, w!("DisplayName"), 0, REG_SZ, Some(&display_name));
+        let _ = RegCloseKey(key);
+        result.ok().context("Failed to name the app ID")
+    }
+}
+
+/// Toast XML of a tip: the message, silent unless the tip plays a sound
+fn toast_xml(tip: &Tip) -> String {
+    let audio = if tip.play_sound {
+        ""
+    } else {
+        "<audio silent=\"true\"/>"
+    };
+    format!(
+        "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text></binding></visual>{}</toast>",
+        escape_xml(&tip.message),
+        audio
+    )
+}
+
+/// Escape text for XML content
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_xml() {
+        let tip = Tip {
+            id: "t".to_string(),
+            message: "HP < 20 & falling".to_string(),
+            priority: 90,
+            duration_ms: None,
+            ..Default::default()
+        };
+
+        let xml = toast_xml(&tip);
+        assert!(xml.contains("<text>HP &lt; 20 &amp; falling</text>"));
+        assert!(xml.contains("<audio silent=\"true\"/>"));
+        assert!(!toast_xml(&Tip {
+            play_sound: true,
+            ..tip
+        })
+        .contains("audio"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Battery-aware power profile
+//!
+//! On laptops running on battery, GamersToolKit switches to the low-power
+//! preset from the performance settings: a lower capture frame rate, zone
+//! OCR on an interval instead of every frame, and no template matching for
+//! screen recognition. Plugging back in restores the normal settings.
+
+use std::time::Duration;
+
+use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+use crate::config::PerformanceConfig;
+
+/// `ACLineStatus` when running on battery
+const AC_OFFLINE: u8 = 0;
+/// `BatteryFlag` bit set when the system has no battery
+const NO_SYSTEM_BATTERY: u8 = 128;
+/// `BatteryFlag` and `BatteryLifePercent` when the status is unknown
+const UNKNOWN: u8 = 255;
+
+/// Power source and whether the low-power preset is on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PowerStatus {
+    /// Running on battery
+    pub on_battery: bool,
+    /// Remaining battery charge, if known
+    pub battery_percent: Option<u8>,
+    /// Whether the low-power preset is in effect
+    pub power_saving: bool,
+}
+
+impl PowerStatus {
+    /// Status from the values `GetSystemPowerStatus` reports
+    pub fn from_system(
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_percent: u8,
+        settings: &PerformanceConfig,
+    ) -> Self {
+        let has_battery = battery_flag != UNKNOWN && battery_flag & NO_SYSTEM_BATTERY == 0;
+        let on_battery = has_battery && ac_line_status == AC_OFFLINE;
+        Self {
+            on_battery,
+            battery_percent: (has_battery && battery_percent <= 100).then_some(battery_percent),
+            power_saving: on_battery && settings.battery_saver,
+        }
+    }
+
+    /// Capture frame rate limit of the preset, if it's in effect
+    pub fn fps_limit(&self, settings: &PerformanceConfig) -> Option<u32> {
+        self.power_saving.then_some(settings.battery_max_fps.max(1))
+    }
+
+    /// Time between zone OCR passes under the preset, if it's in effect
+    pub fn ocr_interval(&self, settings: &PerformanceConfig) -> Option<Duration> {
+        self.power_saving
+            .then(|| Duration::from_millis(settings.battery_ocr_interval_ms as u64))
+    }
+
+    /// Whether screen recognition may match templates
+    pub fn allows_template_matching(&self, settings: &PerformanceConfig) -> bool {
+        !(self.power_saving && settings.battery_disable_template_matching)
+    }
+}
+
+/// Read the power source and decide whether to save power
+///
+/// Desktops and failed queries count as plugged in.
+pub fn current_status(settings: &PerformanceConfig) -> PowerStatus {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    // SAFETY: GetSystemPowerStatus only fills in the struct it's given
+    match unsafe { GetSystemPowerStatus(&mut status) } {
+        Ok(()) => PowerStatus::from_system(
+            status.ACLineStatus,
+            status.BatteryFlag,
+            status.BatteryLifePercent,
+            settings,
+        ),
+        Err(e) => {
+            tracing::debug!("Power status unavailable: {}", e);
+            PowerStatus::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_battery_detection() {
+        let settings = PerformanceConfig::default();
+
+        let status = PowerStatus::from_system(0, 0, 80, &settings);
+        assert!(status.on_battery);
+        assert!(status.power_saving);
+        assert_eq!(status.battery_percent, Some(80));
+
+        // Plugged in, desktop without a battery, unknown status
+        assert!(!PowerStatus::from_system(1, 8, 100, &settings).on_battery);
+        assert_eq!(
+            PowerStatus::from_system(0, NO_SYSTEM_BATTERY, UNKNOWN, &settings),
+            PowerStatus::default()
+        );
+        assert!(!PowerStatus::from_system(0, UNKNOWN, UNKNOWN, &settings).on_battery);
+
+        let disabled = PerformanceConfig {
+            battery_saver: false,
+            ..Default::default()
+        };
+        let status = PowerStatus::from_system(0, 2, 30, &disabled);
+        assert!(status.on_battery);
+        assert!(!status.power_saving);
+    }
+
+    #[test]
+    fn test_preset_only_on_battery() {
+        let settings = PerformanceConfig {
+            battery_max_fps: 5,
+            battery_ocr_interval_ms: 2000,
+            ..Default::default()
+        };
+
+        let plugged_in = PowerStatus::default();
+        assert_eq!(plugged_in.fps_limit(&settings), None);
+        assert_eq!(plugged_in.ocr_interval(&settings), None);
+        assert!(plugged_in.allows_template_matching(&settings));
+
+        let saving = PowerStatus::from_system(0, 0, 50, &settings);
+        assert_eq!(saving.fps_limit(&settings), Some(5));
+        assert_eq!(saving.ocr_interval(&settings), Some(Duration::from_secs(2)));
+        assert!(!saving.allows_template_matching(&settings));
+    }
+}
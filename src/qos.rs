@@ -0,0 +1,190 @@
+//! Thread and process priorities
+//!
+//! Keeps GamersToolKit from competing with the game for CPU time: capture and
+//! vision can run at below-normal thread priority, and while a full-screen
+//! app is in front the whole process can switch to Windows' efficiency mode
+//! (EcoQoS with idle priority, as Task Manager's "Efficiency mode" does).
+
+use anyhow::{Context, Result};
+use windows::Win32::Graphics::Gdi::{
+    GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONULL,
+};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetCurrentThread, GetPriorityClass, GetThreadPriority,
+    ProcessPowerThrottling, SetPriorityClass, SetProcessInformation, SetThreadPriority,
+    IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_STATE, THREAD_PRIORITY,
+    THREAD_PRIORITY_BELOW_NORMAL, THREAD_PRIORITY_NORMAL,
+};
+use windows::Win32::UI::Shell::{
+    SHQueryUserNotificationState, QUNS_BUSY, QUNS_RUNNING_D3D_FULL_SCREEN,
+};
+use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+/// Priorities currently in effect, for the performance panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QosStatus {
+    /// Priority of the capture thread (`None` while not capturing)
+    pub capture_priority: Option<i32>,
+    /// Priority of the dashboard thread, which runs vision
+    pub vision_priority: i32,
+    /// Whether the process is in efficiency mode
+    pub efficiency_mode: bool,
+    /// Whether a full-screen app is in front
+    pub fullscreen_app: bool,
+    /// Whether the app in front runs in exclusive full screen, where the
+    /// overlay can't draw on top of it
+    pub exclusive_fullscreen: bool,
+}
+
+/// Set the calling thread to below-normal priority, or back to normal
+pub fn set_current_thread_low_priority(low: bool) -> Result<()> {
+    let priority = if low {
+        THREAD_PRIORITY_BELOW_NORMAL
+    } else {
+        THREAD_PRIORITY_NORMAL
+    };
+    // SAFETY: the pseudo handle of the current thread is always valid
+    unsafe { SetThreadPriority(GetCurrentThread(), priority) }
+        .context("Failed to set thread priority")
+}
+
+/// Set the calling thread to a priority as returned by [`current_thread_priority`]
+pub fn set_current_thread_priority(priority: i32) -> Result<()> {
+    // SAFETY: the pseudo handle of the current thread is always valid
+    unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY(priority)) }
+        .context("Failed to set thread priority")
+}
+
+/// Threads vision may spread one frame's work over: half the cores, leaving
+/// the rest to the game
+pub fn analysis_threads() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, |n| n.get() / 2)
+        .max(1)
+}
+
+/// Priority of the calling thread
+pub fn current_thread_priority() -> i32 {
+    // SAFETY: the pseudo handle of the current thread is always valid
+    unsafe { GetThreadPriority(GetCurrentThread()) }
+}
+
+/// Display name of a thread priority
+pub fn thread_priority_name(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=-15 => "Idle",
+        -2 => "Lowest",
+        -1 => "Below normal",
+        0 => "Normal",
+        1 => "Above normal",
+        2 => "Highest",
+        15.. => "Time critical",
+        _ => "Custom",
+    }
+}
+
+/// Turn efficiency mode on or off for the whole process
+///
+/// Efficiency mode asks Windows to run the process on efficient cores at low
+/// clock speeds (EcoQoS) and lowers its priority class to idle.
+pub fn set_efficiency_mode(enabled: bool) -> Result<()> {
+    let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        StateMask: if enabled {
+            PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+        } else {
+            0
+        },
+    };
+    let class = if enabled {
+        IDLE_PRIORITY_CLASS
+    } else {
+        NORMAL_PRIORITY_CLASS
+    };
+
+    // SAFETY: the pseudo handle of the current process is always valid, and
+    // `state` outlives the call with its size passed alongside
+    unsafe {
+        SetProcessInformation(
+            GetCurrentProcess(),
+            ProcessPowerThrottling,
+            &state as *const _ as *const std::ffi::c_void,
+            std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+        )
+        .context("Failed to set power throttling")?;
+        SetPriorityClass(GetCurrentProcess(), class).context("Failed to set priority class")
+    }
+}
+
+/// Display name of the process's priority class
+pub fn process_priority_name() -> &'static str {
+    // SAFETY: the pseudo handle of the current process is always valid
+    match unsafe { GetPriorityClass(GetCurrentProcess()) } {
+        0x40 => "Idle",
+        0x4000 => "Below normal",
+        0x20 => "Normal",
+        0x8000 => "Above normal",
+        0x80 => "High",
+        0x100 => "Realtime",
+        _ => "Unknown",
+    }
+}
+
+/// Whether a full-screen app (usually the game) is in the foreground
+pub fn fullscreen_app_active() -> bool {
+    // SAFETY: SHQueryUserNotificationState has no preconditions
+    match unsafe { SHQueryUserNotificationState() } {
+        Ok(state) => state == QUNS_BUSY || state == QUNS_RUNNING_D3D_FULL_SCREEN,
+        Err(_) => false,
+    }
+}
+
+/// Whether the app in the foreground runs in exclusive (Direct3D) full screen
+///
+/// Borderless full screen counts as a full-screen app but not as exclusive;
+/// windows such as the overlay are only shown on top of the former.
+pub fn exclusive_fullscreen_active() -> bool {
+    // SAFETY: SHQueryUserNotificationState has no preconditions
+    matches!(
+        unsafe { SHQueryUserNotificationState() },
+        Ok(state) if state == QUNS_RUNNING_D3D_FULL_SCREEN
+    )
+}
+
+/// The window in the foreground
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForegroundWindow {
+    /// Process the window belongs to
+    pub pid: u32,
+    /// Top-left corner of its monitor on the virtual screen
+    pub monitor_position: Option<(i32, i32)>,
+}
+
+/// Process and monitor of the window in the foreground
+pub fn foreground_window() -> Option<ForegroundWindow> {
+    // SAFETY: GetForegroundWindow has no preconditions
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+    let mut pid = 0;
+    // SAFETY: the window handle came from the system and pid is a local
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    // SAFETY: GetMonitorInfoW writes into a local, for a monitor the system
+    // just returned
+    let found = unsafe {
+        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONULL);
+        !monitor.is_invalid() && GetMonitorInfoW(monitor, &mut info).as_bool()
+    };
+    Some(ForegroundWindow {
+        pid,
+        monitor_position: found.then_some((info.rcMonitor.left, info.rcMonitor.top)),
+    })
+}
@@ -0,0 +1,253 @@
+//! Backpressure for the pipeline channels
+//!
+//! Two kinds of channel connect capture, vision and the overlay:
+//! - Frames go through a latest-wins broadcast: a new frame replaces one
+//!   that wasn't picked up yet, so a slow consumer always gets the newest
+//!   frame instead of working through stale ones. Every consumer has its own
+//!   receiver, so reading a frame doesn't take it from the others.
+//! - Events (tips, overlay commands) go through a bounded queue that drops
+//!   its oldest event when full, so a stalled consumer can't grow memory.
+//!
+//! Both count what they drop for the runtime metrics.
+
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use parking_lot::{Condvar, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Capacity of the tip queue to the overlay
+pub const TIP_QUEUE_CAPACITY: usize = 64;
+
+/// Capacity of the command queues between dashboard and overlay
+pub const COMMAND_QUEUE_CAPACITY: usize = 256;
+
+struct BroadcastState<T> {
+    value: Option<Arc<T>>,
+    /// Number of values sent so far
+    sequence: u64,
+    closed: bool,
+}
+
+struct Broadcast<T> {
+    state: Mutex<BroadcastState<T>>,
+    ready: Condvar,
+}
+
+/// Create a latest-wins broadcast: every receiver sees the newest value,
+/// without taking it from the others
+pub fn latest_broadcast<T>() -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    let shared = Arc::new(Broadcast {
+        state: Mutex::new(BroadcastState {
+            value: None,
+            sequence: 0,
+            closed: false,
+        }),
+        ready: Condvar::new(),
+    });
+    (
+        BroadcastSender {
+            shared: Arc::clone(&shared),
+        },
+        BroadcastReceiver::new(shared),
+    )
+}
+
+/// Writing end of a latest-wins broadcast (closes it when dropped)
+pub struct BroadcastSender<T> {
+    shared: Arc<Broadcast<T>>,
+}
+
+impl<T> BroadcastSender<T> {
+    /// Publish a value, replacing the previous one
+    pub fn send(&self, value: T) {
+        let mut state = self.shared.state.lock();
+        state.value = Some(Arc::new(value));
+        state.sequence += 1;
+        drop(state);
+        self.shared.ready.notify_all();
+    }
+}
+
+impl<T> Drop for BroadcastSender<T> {
+    fn drop(&mut self) {
+        self.shared.state.lock().closed = true;
+        self.shared.ready.notify_all();
+    }
+}
+
+/// Reading end of a latest-wins broadcast, one per consumer
+pub struct BroadcastReceiver<T> {
+    shared: Arc<Broadcast<T>>,
+    /// Sequence number of the last value received
+    seen: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl<T> BroadcastReceiver<T> {
+    fn new(shared: Arc<Broadcast<T>>) -> Self {
+        Self {
+            shared,
+            seen: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Another receiver of the same values, starting with the current one
+    pub fn subscribe(&self) -> Self {
+        Self::new(Arc::clone(&self.shared))
+    }
+
+    /// Mark a value received, counting the ones this receiver missed
+    fn receive(&self, state: &BroadcastState<T>) -> Option<Arc<T>> {
+        let seen = self.seen.swap(state.sequence, Ordering::Relaxed);
+        if seen > 0 {
+            self.dropped
+                .fetch_add(state.sequence - seen - 1, Ordering::Relaxed);
+        }
+        state.value.clone()
+    }
+
+    /// The newest value, if this receiver hasn't had it yet
+    pub fn try_recv(&self) -> Option<Arc<T>> {
+        let state = self.shared.state.lock();
+        if state.sequence > self.seen.load(Ordering::Relaxed) {
+            self.receive(&state)
+        } else {
+            None
+        }
+    }
+
+    /// Wait for a value this receiver hasn't had (`None` once the sender is
+    /// gone)
+    pub fn recv(&self) -> Option<Arc<T>> {
+        let mut state = self.shared.state.lock();
+        loop {
+            if state.sequence > self.seen.load(Ordering::Relaxed) {
+                return self.receive(&state);
+            }
+            if state.closed {
+                return None;
+            }
+            self.shared.ready.wait(&mut state);
+        }
+    }
+
+    /// The newest value, whether or not it was received already
+    pub fn latest(&self) -> Option<Arc<T>> {
+        self.shared.state.lock().value.clone()
+    }
+
+    /// Values this receiver missed because newer ones replaced them
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Create a bounded event queue that drops its oldest event when full
+pub fn event_queue<T>(capacity: usize) -> (EventSender<T>, Receiver<T>) {
+    let (sender, receiver) = bounded(capacity);
+    (
+        EventSender {
+            sender,
+            receiver: receiver.clone(),
+            dropped: Arc::new(AtomicU64::new(0)),
+        },
+        receiver,
+    )
+}
+
+/// Sending end of a bounded event queue
+pub struct EventSender<T> {
+    sender: Sender<T>,
+    /// Used to drop the oldest event when the queue is full
+    receiver: Receiver<T>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            receiver: self.receiver.clone(),
+            dropped: Arc::clone(&self.dropped),
+        }
+    }
+}
+
+impl<T> EventSender<T> {
+    /// Queue an event, dropping the oldest one if the queue is full
+    ///
+    /// Returns whether an event was dropped.
+    pub fn send(&self, event: T) -> bool {
+        let mut event = event;
+        let mut dropped = false;
+        loop {
+            match self.sender.try_send(event) {
+                Ok(()) => return dropped,
+                Err(TrySendError::Full(rejected)) => {
+                    if self.receiver.try_recv().is_ok() {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        dropped = true;
+                    }
+                    event = rejected;
+                }
+                // Unreachable while this sender holds a receiver
+                Err(TrySendError::Disconnected(_)) => return dropped,
+            }
+        }
+    }
+
+    /// Events dropped because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Events waiting to be received
+    pub fn len(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Whether no events are waiting
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_broadcast_reaches_every_receiver() {
+        let (sender, preview) = latest_broadcast();
+        let ocr = preview.subscribe();
+        sender.send(1);
+        assert_eq!(preview.try_recv().as_deref(), Some(&1));
+        assert_eq!(preview.try_recv(), None);
+        assert_eq!(ocr.latest().as_deref(), Some(&1));
+
+        // A receiver that falls behind gets the newest value and counts the rest
+        sender.send(2);
+        sender.send(3);
+        assert_eq!(ocr.try_recv().as_deref(), Some(&3));
+        assert_eq!(ocr.dropped(), 0);
+        assert_eq!(preview.try_recv().as_deref(), Some(&3));
+        assert_eq!(preview.dropped(), 1);
+
+        sender.send(4);
+        drop(sender);
+        assert_eq!(ocr.recv().as_deref(), Some(&4));
+        assert_eq!(ocr.recv(), None);
+    }
+
+    #[test]
+    fn test_event_queue_drops_oldest() {
+        let (sender, receiver) = event_queue(2);
+        assert!(!sender.send("a"));
+        assert!(!sender.send("b"));
+        assert!(sender.send("c"));
+        assert_eq!(sender.clone().dropped(), 1);
+        assert_eq!(sender.len(), 2);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), ["b", "c"]);
+    }
+}
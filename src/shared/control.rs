@@ -0,0 +1,183 @@
+#![allow(dead_code)]
+//! Audit log of commands from outside the dashboard
+//!
+//! Hotkeys and MCP tool calls change what the overlay shows without the
+//! dashboard being touched. Each such command is recorded with where it came
+//! from, so unexpected overlay behavior can be traced to its trigger, and a
+//! source sending commands faster than its rate limit has the extra ones
+//! refused (a stuck key, a runaway assistant).
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Maximum number of commands retained in the control log
+const MAX_CONTROL_ENTRIES: usize = 200;
+
+/// Where an external command came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlSource {
+    /// Global hotkey
+    Hotkey,
+    /// MCP tool call (an AI assistant)
+    Mcp,
+}
+
+impl ControlSource {
+    /// All sources, in display order
+    pub const ALL: [ControlSource; 2] = [ControlSource::Hotkey, ControlSource::Mcp];
+
+    /// Get the display name for this source
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlSource::Hotkey => "Hotkey",
+            ControlSource::Mcp => "MCP",
+        }
+    }
+
+    /// Commands accepted from this source per time window
+    pub fn rate_limit(&self) -> (usize, Duration) {
+        match self {
+            // A person pressing keys; more is a held or stuck key
+            ControlSource::Hotkey => (5, Duration::from_secs(1)),
+            ControlSource::Mcp => (30, Duration::from_secs(60)),
+        }
+    }
+}
+
+/// A command recorded in the control log
+#[derive(Debug, Clone)]
+pub struct ControlEntry {
+    /// Where the command came from
+    pub source: ControlSource,
+    /// What was asked for, e.g. "Toggle overlay" or an MCP tool name
+    pub command: String,
+    /// When the command arrived
+    pub at: Instant,
+    /// Whether it ran (false = refused by the rate limit)
+    pub allowed: bool,
+}
+
+/// Bounded log of external commands, newest last, with per-source rate limits
+#[derive(Debug, Clone, Default)]
+pub struct ControlLog {
+    entries: VecDeque<ControlEntry>,
+    /// Arrival times of each source's accepted commands within its window
+    accepted: HashMap<ControlSource, VecDeque<Instant>>,
+}
+
+impl ControlLog {
+    /// Record a command and return whether it may run
+    pub fn admit(&mut self, source: ControlSource, command: impl Into<String>) -> bool {
+        self.admit_at(source, command.into(), Instant::now())
+    }
+
+    fn admit_at(&mut self, source: ControlSource, command: String, now: Instant) -> bool {
+        let (limit, window) = source.rate_limit();
+        let accepted = self.accepted.entry(source).or_default();
+        while accepted
+            .front()
+            .is_some_and(|&t| now.duration_since(t) >= window)
+        {
+            accepted.pop_front();
+        }
+
+        let allowed = accepted.len() < limit;
+        if allowed {
+            accepted.push_back(now);
+            tracing::info!("{} command: {}", source.name(), command);
+        } else {
+            tracing::warn!(
+                "{} command refused, over {} per {:?}: {}",
+                source.name(),
+                limit,
+                window,
+                command
+            );
+        }
+
+        if self.entries.len() >= MAX_CONTROL_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ControlEntry {
+            source,
+            command,
+            at: now,
+            allowed,
+        });
+        allowed
+    }
+
+    /// Iterate over commands, newest first
+    pub fn iter_recent(&self) -> impl Iterator<Item = &ControlEntry> {
+        self.entries.iter().rev()
+    }
+
+    /// Number of logged commands the rate limit refused
+    pub fn refused(&self) -> usize {
+        self.entries.iter().filter(|e| !e.allowed).count()
+    }
+
+    /// Remove all entries (rate limits keep counting)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of recorded entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the log is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_per_source() {
+        let mut log = ControlLog::default();
+        let start = Instant::now();
+        let (limit, window) = ControlSource::Hotkey.rate_limit();
+
+        for _ in 0..limit {
+            assert!(log.admit_at(ControlSource::Hotkey, "Toggle overlay".into(), start));
+        }
+        assert!(!log.admit_at(ControlSource::Hotkey, "Toggle overlay".into(), start));
+        // Other sources have their own limit
+        assert!(log.admit_at(ControlSource::Mcp, "send_overlay_tip".into(), start));
+        // Accepted again once the window has passed
+        assert!(log.admit_at(
+            ControlSource::Hotkey,
+            "Toggle overlay".into(),
+            start + window
+        ));
+
+        assert_eq!(log.len(), limit + 3);
+        assert_eq!(log.refused(), 1);
+        let newest = log.iter_recent().next().unwrap();
+        assert!(newest.allowed && newest.source == ControlSource::Hotkey);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut log = ControlLog::default();
+        let start = Instant::now();
+        for i in 0..(MAX_CONTROL_ENTRIES + 10) {
+            log.admit_at(
+                ControlSource::Mcp,
+                format!("tool {}", i),
+                start + Duration::from_secs(60 * i as u64),
+            );
+        }
+
+        assert_eq!(log.len(), MAX_CONTROL_ENTRIES);
+        assert_eq!(
+            log.iter_recent().next().unwrap().command,
+            format!("tool {}", MAX_CONTROL_ENTRIES + 9)
+        );
+    }
+}
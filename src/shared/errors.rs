@@ -0,0 +1,282 @@
+#![allow(dead_code)]
+//! Structured application errors
+//!
+//! Typed error taxonomy surfaced to the user through the dashboard error center.
+//! Each error carries a category with a suggested fix so failures are actionable
+//! instead of being reduced to an opaque string.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Maximum number of errors retained in the error log
+const MAX_ERROR_ENTRIES: usize = 50;
+
+/// Category of an application error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Screen capture (Windows Graphics Capture)
+    Capture,
+    /// Text recognition (Windows OCR / PaddleOCR)
+    Ocr,
+    /// OCR model files (download, verification, loading)
+    Model,
+    /// Overlay window and hotkeys
+    Overlay,
+    /// Profiles, config and other files on disk
+    Storage,
+    /// Network access (downloads, integrations)
+    Network,
+}
+
+impl ErrorCategory {
+    /// Get the display name for this category
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorCategory::Capture => "Capture",
+            ErrorCategory::Ocr => "OCR",
+            ErrorCategory::Model => "Model",
+            ErrorCategory::Overlay => "Overlay",
+            ErrorCategory::Storage => "Storage",
+            ErrorCategory::Network => "Network",
+        }
+    }
+
+    /// Get a suggested fix for errors of this category
+    pub fn suggested_fix(&self) -> &'static str {
+        match self {
+            ErrorCategory::Capture => {
+                "Check that the target window is open and not minimized, or pick a monitor \
+                 instead of a window in the Capture view."
+            }
+            ErrorCategory::Ocr => {
+                "Try the other OCR backend in the Vision view, or install the Windows OCR \
+                 language pack for your display language."
+            }
+            ErrorCategory::Model => {
+                "Re-download the OCR models from the Vision view. If the problem persists, \
+                 delete the models folder and download again."
+            }
+            ErrorCategory::Overlay => {
+                "Restart the overlay from the Home view. If hotkeys fail, another application \
+                 may already be using the same key combination."
+            }
+            ErrorCategory::Storage => {
+                "Make sure the data folder is writable and not locked by another program, \
+                 and that there is free disk space."
+            }
+            ErrorCategory::Network => {
+                "Check your internet connection and any proxy or firewall settings, then retry."
+            }
+        }
+    }
+}
+
+/// A typed application error
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AppError {
+    /// Screen capture failure
+    #[error("{0}")]
+    Capture(String),
+    /// OCR failure
+    #[error("{0}")]
+    Ocr(String),
+    /// Model file failure
+    #[error("{0}")]
+    Model(String),
+    /// Overlay failure
+    #[error("{0}")]
+    Overlay(String),
+    /// Storage failure
+    #[error("{0}")]
+    Storage(String),
+    /// Network failure
+    #[error("{0}")]
+    Network(String),
+}
+
+impl AppError {
+    /// Get the category of this error
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            AppError::Capture(_) => ErrorCategory::Capture,
+            AppError::Ocr(_) => ErrorCategory::Ocr,
+            AppError::Model(_) => ErrorCategory::Model,
+            AppError::Overlay(_) => ErrorCategory::Overlay,
+            AppError::Storage(_) => ErrorCategory::Storage,
+            AppError::Network(_) => ErrorCategory::Network,
+        }
+    }
+
+    /// Get the error message without the category
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Capture(msg)
+            | AppError::Ocr(msg)
+            | AppError::Model(msg)
+            | AppError::Overlay(msg)
+            | AppError::Storage(msg)
+            | AppError::Network(msg) => msg,
+        }
+    }
+}
+
+/// An error recorded in the error log
+#[derive(Debug, Clone)]
+pub struct ErrorEntry {
+    /// The error
+    pub error: AppError,
+    /// When the error first occurred
+    pub first_seen: Instant,
+    /// When the error last occurred
+    pub last_seen: Instant,
+    /// Number of occurrences
+    pub count: u32,
+}
+
+/// Bounded log of recent errors, newest last
+#[derive(Debug, Clone, Default)]
+pub struct ErrorLog {
+    entries: VecDeque<ErrorEntry>,
+}
+
+impl ErrorLog {
+    /// Record an error
+    ///
+    /// Repeats of an error already in the log are collapsed into its entry,
+    /// which moves to the newest position, so per-frame failures don't flood
+    /// the log even when several of them alternate.
+    pub fn push(&mut self, error: AppError) {
+        let now = Instant::now();
+
+        if let Some(index) = self.entries.iter().position(|entry| entry.error == error) {
+            if let Some(mut entry) = self.entries.remove(index) {
+                entry.count += 1;
+                entry.last_seen = now;
+                self.entries.push_back(entry);
+                return;
+            }
+        }
+
+        if self.entries.len() >= MAX_ERROR_ENTRIES {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(ErrorEntry {
+            error,
+            first_seen: now,
+            last_seen: now,
+            count: 1,
+        });
+    }
+
+    /// Iterate over errors, newest first
+    pub fn iter_recent(&self) -> impl Iterator<Item = &ErrorEntry> {
+        self.entries.iter().rev()
+    }
+
+    /// Remove the entry at the given position (as returned by `iter_recent`)
+    pub fn dismiss(&mut self, recent_index: usize) {
+        if recent_index < self.entries.len() {
+            let index = self.entries.len() - 1 - recent_index;
+            self.entries.remove(index);
+        }
+    }
+
+    /// Remove all entries
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of recorded entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the log is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Most recent entry
+    pub fn latest(&self) -> Option<&ErrorEntry> {
+        self.entries.back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_mapping() {
+        assert_eq!(
+            AppError::Capture("x".into()).category(),
+            ErrorCategory::Capture
+        );
+        assert_eq!(AppError::Ocr("x".into()).category(), ErrorCategory::Ocr);
+        assert_eq!(
+            AppError::Network("x".into()).category(),
+            ErrorCategory::Network
+        );
+    }
+
+    #[test]
+    fn test_display_is_message() {
+        let err = AppError::Storage("Disk full".into());
+        assert_eq!(err.to_string(), "Disk full");
+        assert_eq!(err.message(), "Disk full");
+    }
+
+    #[test]
+    fn test_repeated_errors_collapse() {
+        let mut log = ErrorLog::default();
+        log.push(AppError::Ocr("Zone failed".into()));
+        log.push(AppError::Ocr("Zone failed".into()));
+        log.push(AppError::Ocr("Zone failed".into()));
+
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.latest().unwrap().count, 3);
+    }
+
+    #[test]
+    fn test_alternating_errors_collapse() {
+        let mut log = ErrorLog::default();
+        for _ in 0..40 {
+            log.push(AppError::Ocr("Zone OCR failed".into()));
+            log.push(AppError::Ocr("OCR backend not ready".into()));
+        }
+
+        assert_eq!(log.len(), 2);
+        assert!(log.iter_recent().all(|entry| entry.count == 40));
+        assert_eq!(
+            log.latest().unwrap().error.message(),
+            "OCR backend not ready"
+        );
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut log = ErrorLog::default();
+        for i in 0..(MAX_ERROR_ENTRIES + 10) {
+            log.push(AppError::Capture(format!("error {}", i)));
+        }
+
+        assert_eq!(log.len(), MAX_ERROR_ENTRIES);
+        assert_eq!(
+            log.latest().unwrap().error.message(),
+            format!("error {}", MAX_ERROR_ENTRIES + 9)
+        );
+    }
+
+    #[test]
+    fn test_dismiss_uses_recent_order() {
+        let mut log = ErrorLog::default();
+        log.push(AppError::Capture("first".into()));
+        log.push(AppError::Overlay("second".into()));
+
+        log.dismiss(0);
+
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.latest().unwrap().error.message(), "first");
+    }
+}
@@ -3,8 +3,12 @@
 //! This module provides thread-safe shared state and message passing
 //! for communication between the dashboard UI and overlay components.
 
+pub mod channels;
+pub mod control;
+pub mod errors;
 pub mod messages;
 pub mod state;
 
+pub use errors::AppError;
 pub use messages::{DashboardToOverlay, OverlayToDashboard};
 pub use state::{CaptureCommand, OverlayCommand, SharedAppState};
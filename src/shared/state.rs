@@ -1,10 +1,25 @@
 #![allow(dead_code)]
 //! Shared application state between dashboard and overlay
 
-use crate::capture::CaptureConfig;
-use crate::config::AppConfig;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use super::control::ControlLog;
+use super::errors::{AppError, ErrorLog};
+use crate::analysis::goals::SessionGoals;
+use crate::analysis::latency::LatencyStats;
+use crate::capture::{CaptureColorFormat, CaptureConfig, CaptureTarget};
+use crate::config::{AppConfig, ConfigIssue, ExtraCaptureSource, TipDesign, MIN_CAPTURE_SCALE};
+use crate::cpu_budget::CpuBudgetStatus;
+use crate::diagnostics::CapabilityCheck;
 use crate::overlay::OverlayConfig;
+use crate::power::PowerStatus;
+use crate::qos::QosStatus;
 use crate::storage::profiles::GameProfile;
+use crate::storage::rule_library::LibraryRule;
+use crate::telemetry::UsageCounters;
+use crate::vision::gpu_adapters::GpuAdapter;
+use crate::vision::scene::SceneMetrics;
 use crate::vision::ScreenMatch;
 
 /// Central shared state between dashboard and overlay
@@ -20,6 +35,8 @@ pub struct SharedAppState {
     pub profiles: Vec<GameProfile>,
     /// Currently active profile ID
     pub active_profile_id: Option<String>,
+    /// Rules that profiles can attach by reference
+    pub rule_library: Vec<LibraryRule>,
     /// Runtime state (not persisted)
     pub runtime: RuntimeState,
 }
@@ -49,7 +66,13 @@ impl SharedAppState {
         };
 
         let capture_config = CaptureConfig {
-            target: if let Some(ref window) = config.capture.target_window {
+            target: if config.capture.use_demo {
+                crate::capture::CaptureTarget::Demo
+            } else if config.capture.use_composite && !config.capture.composite_sources.is_empty() {
+                crate::capture::CaptureTarget::Composite(config.capture.composite_sources.clone())
+            } else if let Some(ref executable) = config.capture.target_executable {
+                crate::capture::CaptureTarget::Executable(executable.clone())
+            } else if let Some(ref window) = config.capture.target_window {
                 crate::capture::CaptureTarget::Window(window.clone())
             } else {
                 crate::capture::CaptureTarget::PrimaryMonitor
@@ -57,6 +80,11 @@ impl SharedAppState {
             max_fps: config.capture.max_fps,
             capture_cursor: config.capture.capture_cursor,
             draw_border: config.capture.draw_border,
+            gpu_frames: config.capture.gpu_frames,
+            color_format: CaptureColorFormat::from_settings(&config.capture),
+            scale: config.capture.capture_scale.clamp(MIN_CAPTURE_SCALE, 1.0),
+            client_area_only: config.capture.client_area_only,
+            secondary_windows: config.capture.secondary_windows,
         };
 
         Self {
@@ -65,6 +93,7 @@ impl SharedAppState {
             capture_config,
             profiles: Vec::new(),
             active_profile_id: None,
+            rule_library: Vec::new(),
             runtime: RuntimeState::default(),
         }
     }
@@ -76,6 +105,22 @@ impl SharedAppState {
             .and_then(|id| self.profiles.iter().find(|p| &p.id == id))
     }
 
+    /// Sources captured next to the main target: the configured ones and
+    /// the active profile's secondary windows (a configured source wins over
+    /// a window of the same ID)
+    pub fn capture_sources(&self) -> Vec<ExtraCaptureSource> {
+        let mut sources = self.config.capture.extra_sources.clone();
+        let windows = self
+            .active_profile()
+            .map_or(&[][..], |profile| &profile.secondary_windows[..]);
+        for window in windows {
+            if !sources.iter().any(|source| source.id == window.id) {
+                sources.push(window.clone());
+            }
+        }
+        sources
+    }
+
     /// Set the active profile by ID
     pub fn set_active_profile(&mut self, profile_id: Option<String>) {
         self.active_profile_id = profile_id;
@@ -103,12 +148,21 @@ impl SharedAppState {
 }
 
 /// Command to control capture from UI
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CaptureCommand {
     /// Start capture
     Start,
     /// Stop capture
     Stop,
+    /// Save the next frame as a PNG screenshot
+    Screenshot {
+        /// Target to grab a frame of (None = the running capture, or the
+        /// selected target while capture is stopped)
+        target: Option<CaptureTarget>,
+        /// File to save to, relative to the screenshots folder (None = a
+        /// new timestamped file there)
+        path: Option<PathBuf>,
+    },
 }
 
 /// Command to control overlay from UI
@@ -122,6 +176,16 @@ pub enum OverlayCommand {
     ToggleVisibility,
 }
 
+/// The game running in exclusive full screen, where the overlay can't draw
+/// over it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FullscreenGame {
+    /// Monitor the game covers (index as in the overlay settings)
+    pub monitor: Option<usize>,
+    /// Monitor the overlay moved to meanwhile, in second monitor mode
+    pub tips_monitor: Option<usize>,
+}
+
 /// Runtime state that is not persisted
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeState {
@@ -135,18 +199,76 @@ pub struct RuntimeState {
     pub current_capture_target: Option<String>,
     /// Last error message (if any)
     pub last_error: Option<String>,
+    /// Recent errors shown in the error center
+    pub errors: ErrorLog,
+    /// Commands from hotkeys and MCP, with their rate limits
+    pub control_log: ControlLog,
+    /// Started in safe mode (default config, no profile auto-activation, no hotkeys)
+    pub safe_mode: bool,
+    /// Problems found in config.toml when it was loaded
+    pub config_issues: Vec<ConfigIssue>,
+    /// Request to rewrite config.toml without the problem entries
+    pub rewrite_config: bool,
+    /// Results of the system capability checks (None while they run)
+    pub capability_checks: Option<Vec<CapabilityCheck>>,
+    /// Request to re-run the system capability checks
+    pub recheck_capabilities: bool,
+    /// Hardware GPUs, listed at startup and with the capability checks
+    pub gpu_adapters: Vec<GpuAdapter>,
+    /// Feature usage counted this session (only while telemetry is enabled)
+    pub usage: UsageCounters,
     /// Current FPS of capture
     pub capture_fps: f32,
+    /// Captured frames replaced by a newer one before they were processed
+    pub dropped_frames: u64,
+    /// Tips and commands the overlay fell too far behind to receive
+    pub dropped_overlay_events: u64,
+    /// Tips and commands waiting for the overlay to pick them up
+    pub overlay_queue_depth: usize,
+    /// Time from capture to each processing stage, and stale tips dropped
+    pub latency: LatencyStats,
+    /// When the frame the latest zone readings came from was captured
+    pub readings_captured_at: Option<std::time::Instant>,
+    /// ID of the game phase the match is in
+    pub current_phase: Option<String>,
+    /// When the current match started (for phases that start by time)
+    pub match_started_at: Option<std::time::Instant>,
     /// Number of tips currently displayed
     pub tips_displayed: usize,
     /// Pending capture command from UI
     pub capture_command: Option<CaptureCommand>,
+    /// Where the sources are in the frames of a composite capture
+    pub composite_layout: Option<crate::capture::CompositeLayout>,
+    /// Window that couldn't be captured because it runs as administrator
+    pub capture_access_denied: Option<String>,
+    /// Request to restart GamersToolKit as administrator
+    pub relaunch_elevated: bool,
     /// Pending overlay command from UI
     pub overlay_command: Option<OverlayCommand>,
-    /// Request to send a test tip
-    pub send_test_tip: bool,
-    /// Custom message for the test tip
-    pub test_tip_message: Option<(String, u32)>,
+    /// Request to send a test tip, as designed
+    pub send_test_tip: Option<TipDesign>,
+    /// Request to save the replay buffer, with what asked for it
+    pub save_replay: Option<String>,
+    /// Whether a replay is being saved
+    pub saving_replay: bool,
+    /// Outcome of the save that just finished, for DashboardApp to report
+    pub saved_replay: Option<Result<PathBuf, String>>,
+    /// Last replay saved
+    pub last_replay: Option<PathBuf>,
+    /// Seconds and bytes of capture in the replay buffer
+    pub replay_buffered: (f32, usize),
+    /// Outcome of the screenshot just saved, for DashboardApp to report
+    pub saved_screenshot: Option<Result<PathBuf, String>>,
+    /// Latest screenshots, newest first
+    pub recent_screenshots: Vec<PathBuf>,
+    /// Request to export a snapshot of the app state
+    pub export_snapshot: bool,
+    /// Whether a snapshot is being exported
+    pub exporting_snapshot: bool,
+    /// Outcome of the snapshot export that just finished, for DashboardApp to report
+    pub saved_snapshot: Option<Result<PathBuf, String>>,
+    /// Last snapshot exported
+    pub last_snapshot: Option<PathBuf>,
     // Screen Recognition State
     /// Currently detected screen (if screen recognition is active)
     pub current_screen: Option<ScreenMatch>,
@@ -156,6 +278,26 @@ pub struct RuntimeState {
     pub screen_just_changed: bool,
     /// Last screen recognition time in milliseconds
     pub last_screen_check_ms: u64,
+    /// IDs of the color triggers whose color is currently showing
+    pub active_color_triggers: HashSet<String>,
+    /// Brightness and saturation of the latest frame
+    pub scene: SceneMetrics,
+    /// Mouse cursor position in the latest frame, if it was over the capture
+    pub cursor: Option<(u32, u32)>,
+    /// Whether quiet hours hold back non-critical tips right now
+    pub quiet_hours_active: bool,
+    /// Thread and process priorities in effect
+    pub qos: QosStatus,
+    /// Set while the game runs in exclusive full screen
+    pub fullscreen_game: Option<FullscreenGame>,
+    /// Power source and whether the low-power preset is on
+    pub power: PowerStatus,
+    /// CPU usage and the capture frame rate limit keeping it under budget
+    pub cpu_budget: CpuBudgetStatus,
+    /// Whether zone OCR and screen rules are paused because the player is away
+    pub afk: bool,
+    /// Goals of the session being captured, if any were set
+    pub session_goals: Option<SessionGoals>,
     // MCP Support Fields
     /// Tips queued by MCP tools (consumed by overlay when running)
     pub pending_tips: Vec<crate::analysis::Tip>,
@@ -171,9 +313,11 @@ impl RuntimeState {
         self.last_error = None;
     }
 
-    /// Set an error message
-    pub fn set_error(&mut self, error: impl Into<String>) {
-        self.last_error = Some(error.into());
+    /// Record an error and make it the current error message
+    pub fn set_error(&mut self, error: AppError) {
+        tracing::warn!("{} error: {}", error.category().name(), error);
+        self.last_error = Some(error.to_string());
+        self.errors.push(error);
     }
 
     /// Update the current screen match
@@ -0,0 +1,267 @@
+//! Automatic profile backups
+//!
+//! Before a destructive change (deleting a screen, zone or profile, or importing
+//! over an existing profile) the profile saved on disk is copied to
+//! `backups/<profile id>/<unix millis>-<reason>.json` so it can be restored from
+//! the Profiles view. Backups embed the template images, so they don't depend
+//! on image files that are cleaned up once no profile uses them.
+
+use anyhow::{Context, Result};
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::profiles::{load_profile, GameProfile};
+
+/// Backups kept per profile (oldest are removed first)
+const MAX_BACKUPS_PER_PROFILE: usize = 20;
+
+/// Why a backup was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupReason {
+    /// A screen was deleted
+    DeleteScreen,
+    /// One or more OCR zones were deleted
+    DeleteZone,
+    /// One or more labeled regions were deleted or merged
+    DeleteLabels,
+    /// The whole profile was deleted
+    DeleteProfile,
+    /// A profile with the same ID was imported over it
+    Import,
+    /// Another backup was restored over it
+    Restore,
+}
+
+impl BackupReason {
+    /// Slug used in backup file names
+    fn slug(&self) -> &'static str {
+        match self {
+            BackupReason::DeleteScreen => "delete-screen",
+            BackupReason::DeleteZone => "delete-zone",
+            BackupReason::DeleteLabels => "delete-labels",
+            BackupReason::DeleteProfile => "delete-profile",
+            BackupReason::Import => "import",
+            BackupReason::Restore => "restore",
+        }
+    }
+
+    fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "delete-screen" => Some(BackupReason::DeleteScreen),
+            "delete-zone" => Some(BackupReason::DeleteZone),
+            "delete-labels" => Some(BackupReason::DeleteLabels),
+            "delete-profile" => Some(BackupReason::DeleteProfile),
+            "import" => Some(BackupReason::Import),
+            "restore" => Some(BackupReason::Restore),
+            _ => None,
+        }
+    }
+
+    /// Description for display
+    pub fn description(&self) -> &'static str {
+        match self {
+            BackupReason::DeleteScreen => "Before deleting a screen",
+            BackupReason::DeleteZone => "Before deleting zones",
+            BackupReason::DeleteLabels => "Before deleting labels",
+            BackupReason::DeleteProfile => "Before deleting the profile",
+            BackupReason::Import => "Before an import",
+            BackupReason::Restore => "Before restoring a backup",
+        }
+    }
+}
+
+/// A stored profile snapshot
+#[derive(Debug, Clone)]
+pub struct ProfileBackup {
+    /// ID of the backed up profile
+    pub profile_id: String,
+    /// Why the backup was taken
+    pub reason: BackupReason,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    /// Backup file
+    pub path: PathBuf,
+}
+
+impl ProfileBackup {
+    /// Load the profile stored in this backup
+    pub fn load(&self) -> Result<GameProfile> {
+        load_profile(&self.path).with_context(|| format!("Failed to read backup {:?}", self.path))
+    }
+}
+
+/// Snapshot the saved profile file before a destructive change
+///
+/// Does nothing (and returns `None`) if the profile has never been saved.
+pub fn backup_profile(
+    profiles_dir: &Path,
+    backups_dir: &Path,
+    profile_id: &str,
+    reason: BackupReason,
+) -> Result<Option<PathBuf>> {
+    let source = profiles_dir.join(format!("{}.json", profile_id));
+    if !source.exists() {
+        return Ok(None);
+    }
+
+    let dir = backups_dir.join(profile_id);
+    std::fs::create_dir_all(&dir)?;
+
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let target = dir.join(format!("{}-{}.json", timestamp_ms, reason.slug()));
+    let profile = load_profile(&source)
+        .with_context(|| format!("Failed to back up profile '{}'", profile_id))?;
+    std::fs::write(&target, serde_json::to_string_pretty(&profile)?)
+        .with_context(|| format!("Failed to back up profile '{}'", profile_id))?;
+
+    prune_backups(&dir)?;
+    Ok(Some(target))
+}
+
+/// All backups, newest first
+pub fn list_backups(backups_dir: &Path) -> Vec<ProfileBackup> {
+    let Ok(profile_dirs) = std::fs::read_dir(backups_dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<ProfileBackup> = profile_dirs
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .flat_map(|entry| backups_in(&entry.path()))
+        .collect();
+    backups.sort_by_key(|backup| Reverse(backup.timestamp_ms));
+    backups
+}
+
+/// Backups of a single profile, in no particular order
+fn backups_in(dir: &Path) -> Vec<ProfileBackup> {
+    let Some(profile_id) = dir.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let stem = path.file_stem()?.to_str()?;
+            if path.extension()? != "json" {
+                return None;
+            }
+            let (timestamp, reason) = stem.split_once('-')?;
+            Some(ProfileBackup {
+                profile_id: profile_id.to_string(),
+                reason: BackupReason::from_slug(reason)?,
+                timestamp_ms: timestamp.parse().ok()?,
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Remove the oldest backups beyond the per-profile limit
+fn prune_backups(dir: &Path) -> Result<()> {
+    let mut backups = backups_in(dir);
+    if backups.len() <= MAX_BACKUPS_PER_PROFILE {
+        return Ok(());
+    }
+
+    backups.sort_by_key(|backup| Reverse(backup.timestamp_ms));
+    for backup in &backups[MAX_BACKUPS_PER_PROFILE..] {
+        std::fs::remove_file(&backup.path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::save_profile;
+    use tempfile::TempDir;
+
+    fn test_profile(id: &str) -> GameProfile {
+        GameProfile {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            executables: vec![],
+            version: "1.0.0".to_string(),
+            ocr_regions: vec![],
+            templates: vec![],
+            rules: vec![],
+            library_rules: vec![],
+            labeled_regions: vec![],
+            screens: vec![],
+            screen_recognition_enabled: false,
+            screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
+        }
+    }
+
+    #[test]
+    fn test_backup_and_list() {
+        let profiles = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        save_profile(&test_profile("game"), &profiles.path().join("game.json")).unwrap();
+
+        let path = backup_profile(
+            profiles.path(),
+            backups.path(),
+            "game",
+            BackupReason::DeleteScreen,
+        )
+        .unwrap();
+        assert!(path.is_some());
+
+        let listed = list_backups(backups.path());
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].profile_id, "game");
+        assert_eq!(listed[0].reason, BackupReason::DeleteScreen);
+        assert_eq!(listed[0].load().unwrap().id, "game");
+    }
+
+    #[test]
+    fn test_backup_of_unsaved_profile_is_skipped() {
+        let profiles = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+
+        let path = backup_profile(
+            profiles.path(),
+            backups.path(),
+            "missing",
+            BackupReason::Import,
+        )
+        .unwrap();
+        assert!(path.is_none());
+        assert!(list_backups(backups.path()).is_empty());
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let backups = TempDir::new().unwrap();
+        let dir = backups.path().join("game");
+        std::fs::create_dir_all(&dir).unwrap();
+        for i in 0..MAX_BACKUPS_PER_PROFILE + 3 {
+            std::fs::write(dir.join(format!("{}-import.json", 1000 + i)), "{}").unwrap();
+        }
+
+        prune_backups(&dir).unwrap();
+
+        let listed = list_backups(backups.path());
+        assert_eq!(listed.len(), MAX_BACKUPS_PER_PROFILE);
+        assert_eq!(listed.last().unwrap().timestamp_ms, 1003);
+    }
+}
@@ -1,27 +1,641 @@
 #![allow(dead_code)]
 //! SQLite database for persistent storage
+//!
+//! Records dashboard sessions, notable events, zone OCR readings, where on
+//! screen tips came from and daily playtime. The data browser in the dashboard reads it through a separate
+//! read-only connection.
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OpenFlags};
 use std::path::Path;
 
+/// Tables shown in the data browser
+pub const BROWSABLE_TABLES: &[&str] = &[
+    "sessions",
+    "events",
+    "zone_readings",
+    "tip_origins",
+    "playtime",
+];
+
+/// Days of playtime shown in the stats, today included
+pub const PLAYTIME_DAYS: usize = 7;
+
 /// Database connection wrapper
 pub struct Database {
-    // TODO: rusqlite::Connection
+    conn: Connection,
+}
+
+/// A zone OCR reading to record
+#[derive(Debug, Clone)]
+pub struct ZoneReading<'a> {
+    /// Active profile ID
+    pub profile_id: Option<&'a str>,
+    /// Zone ID
+    pub zone_id: &'a str,
+    /// Zone name
+    pub zone_name: &'a str,
+    /// Recognized (filtered) text
+    pub text: &'a str,
+    /// Mean OCR confidence of the detected text regions
+    pub confidence: Option<f32>,
+}
+
+/// An event read back from the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    /// When it happened (UTC, `YYYY-MM-DD HH:MM:SS`)
+    pub timestamp: String,
+    /// Kind of event, e.g. "capture"
+    pub kind: String,
+    /// What happened
+    pub message: String,
+}
+
+/// Where on screen a tip that was shown came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct TipOrigin {
+    /// Tip ID
+    pub tip_id: String,
+    /// What raised the tip, e.g. "label Gold" or "watch zone.health"
+    pub source: String,
+    /// Region it was read from (x, y, width, height) as fractions of the
+    /// frame (0.0-1.0)
+    pub bounds: (f32, f32, f32, f32),
+}
+
+/// Playtime of one profile over the last days
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaytimeStats {
+    /// Profile ID
+    pub profile_id: String,
+    /// Profile name when last played
+    pub profile_name: String,
+    /// Seconds played per day, oldest first (the last entry is today)
+    pub daily_seconds: [u64; PLAYTIME_DAYS],
+}
+
+impl PlaytimeStats {
+    /// Seconds played today
+    pub fn today(&self) -> u64 {
+        self.daily_seconds[PLAYTIME_DAYS - 1]
+    }
+
+    /// Seconds played over all days
+    pub fn total(&self) -> u64 {
+        self.daily_seconds.iter().sum()
+    }
+}
+
+/// A zone reading as recorded, for comparing sessions
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedReading {
+    /// Zone ID
+    pub zone_id: String,
+    /// Zone name
+    pub zone_name: String,
+    /// When it was read (Unix time, seconds)
+    pub at: i64,
+    /// Recognized text
+    pub text: String,
+}
+
+/// Zone readings of one session, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionReadings {
+    /// Session ID
+    pub session_id: i64,
+    /// Readings in the order they were recorded
+    pub readings: Vec<RecordedReading>,
+}
+
+/// Result of a data browser query, with every value rendered as text
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    /// Column names
+    pub columns: Vec<String>,
+    /// Row values
+    pub rows: Vec<Vec<String>>,
+    /// Whether more rows were available than returned
+    pub truncated: bool,
 }
 
 impl Database {
     /// Open or create database at path
-    pub fn open(_path: &Path) -> Result<Self> {
-        // TODO: Open SQLite database
-        Ok(Self {})
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open database {:?}", path))?;
+        // WAL lets the data browser read while the dashboard writes
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+
+        let db = Self { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Open a private in-memory database
+    pub fn open_in_memory() -> Result<Self> {
+        let db = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        db.init_schema()?;
+        Ok(db)
     }
 
     /// Initialize database schema
     pub fn init_schema(&self) -> Result<()> {
-        // TODO: Create tables if not exist
-        // - settings
-        // - profiles
-        // - logs
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                id INTEGER PRIMARY KEY,
+                started_at TEXT NOT NULL DEFAULT (datetime('now')),
+                ended_at TEXT,
+                mode TEXT NOT NULL,
+                app_version TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS events (
+                id INTEGER PRIMARY KEY,
+                session_id INTEGER REFERENCES sessions(id),
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                kind TEXT NOT NULL,
+                message TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS zone_readings (
+                id INTEGER PRIMARY KEY,
+                session_id INTEGER REFERENCES sessions(id),
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                profile_id TEXT,
+                zone_id TEXT NOT NULL,
+                zone_name TEXT NOT NULL,
+                text TEXT NOT NULL,
+                confidence REAL
+            );
+            CREATE TABLE IF NOT EXISTS tip_origins (
+                id INTEGER PRIMARY KEY,
+                session_id INTEGER REFERENCES sessions(id),
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                tip_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                x REAL NOT NULL,
+                y REAL NOT NULL,
+                width REAL NOT NULL,
+                height REAL NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS playtime (
+                id INTEGER PRIMARY KEY,
+                day TEXT NOT NULL,
+                profile_id TEXT NOT NULL,
+                profile_name TEXT NOT NULL,
+                seconds INTEGER NOT NULL DEFAULT 0,
+                UNIQUE (day, profile_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_events_session ON events(session_id);
+            CREATE INDEX IF NOT EXISTS idx_zone_readings_zone ON zone_readings(zone_id, timestamp);",
+        )?;
         Ok(())
     }
+
+    /// Record the start of a session, returning its ID
+    pub fn start_session(&self, mode: &str) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO sessions (mode, app_version) VALUES (?1, ?2)",
+            params![mode, env!("CARGO_PKG_VERSION")],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Record the end of a session
+    pub fn end_session(&self, session_id: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET ended_at = datetime('now') WHERE id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Record an event (capture started, error, ...)
+    pub fn record_event(&self, session_id: i64, kind: &str, message: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO events (session_id, kind, message) VALUES (?1, ?2, ?3)",
+            params![session_id, kind, message],
+        )?;
+        Ok(())
+    }
+
+    /// Record an event that happened at `timestamp` (UTC, `YYYY-MM-DD HH:MM:SS`)
+    pub fn record_event_at(
+        &self,
+        session_id: i64,
+        timestamp: &str,
+        kind: &str,
+        message: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO events (session_id, timestamp, kind, message) VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, timestamp, kind, message],
+        )?;
+        Ok(())
+    }
+
+    /// The latest `limit` events, oldest first
+    pub fn recent_events(&self, limit: usize) -> Result<Vec<RecordedEvent>> {
+        recent_events(&self.conn, limit)
+    }
+
+    /// Record a zone OCR reading
+    pub fn record_zone_reading(&self, session_id: i64, reading: &ZoneReading) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO zone_readings (session_id, profile_id, zone_id, zone_name, text, confidence)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                session_id,
+                reading.profile_id,
+                reading.zone_id,
+                reading.zone_name,
+                reading.text,
+                reading.confidence
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record where on screen a tip that was shown came from
+    pub fn record_tip_origin(&self, session_id: i64, origin: &TipOrigin) -> Result<()> {
+        let (x, y, width, height) = origin.bounds;
+        self.conn.execute(
+            "INSERT INTO tip_origins (session_id, tip_id, source, x, y, width, height)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                session_id,
+                origin.tip_id,
+                origin.source,
+                x,
+                y,
+                width,
+                height
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Zone readings of the last `sessions` sessions that have any, newest first
+    pub fn recent_session_readings(&self, sessions: usize) -> Result<Vec<SessionReadings>> {
+        recent_session_readings(&self.conn, sessions)
+    }
+
+    /// Add play time to a profile's total for today (local time)
+    pub fn add_playtime(&self, profile_id: &str, profile_name: &str, seconds: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO playtime (day, profile_id, profile_name, seconds)
+             VALUES (date('now', 'localtime'), ?1, ?2, ?3)
+             ON CONFLICT (day, profile_id) DO UPDATE
+             SET seconds = seconds + excluded.seconds, profile_name = excluded.profile_name",
+            params![profile_id, profile_name, seconds as i64],
+        )?;
+        Ok(())
+    }
+}
+
+/// Playtime per profile over the last [`PLAYTIME_DAYS`] days, most played first
+pub fn playtime_stats(conn: &Connection) -> Result<Vec<PlaytimeStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT profile_id, profile_name, seconds,
+                CAST(julianday(date('now', 'localtime')) - julianday(day) AS INTEGER)
+         FROM playtime
+         WHERE day > date('now', 'localtime', ?1)
+         ORDER BY day",
+    )?;
+    let rows = stmt.query_map([format!("-{} days", PLAYTIME_DAYS)], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+
+    let mut stats: Vec<PlaytimeStats> = Vec::new();
+    for row in rows {
+        let (profile_id, profile_name, seconds, days_ago) = row?;
+        let Some(day) = (PLAYTIME_DAYS - 1).checked_sub(days_ago.max(0) as usize) else {
+            continue;
+        };
+        let index = match stats.iter().position(|s| s.profile_id == profile_id) {
+            Some(index) => index,
+            None => {
+                stats.push(PlaytimeStats {
+                    profile_id,
+                    profile_name: String::new(),
+                    daily_seconds: [0; PLAYTIME_DAYS],
+                });
+                stats.len() - 1
+            }
+        };
+        // Rows come oldest first, so the latest name wins
+        stats[index].profile_name = profile_name;
+        stats[index].daily_seconds[day] += seconds.max(0) as u64;
+    }
+
+    stats.sort_by_key(|s| std::cmp::Reverse(s.total()));
+    Ok(stats)
+}
+
+/// The latest `limit` events, oldest first
+pub fn recent_events(conn: &Connection, limit: usize) -> Result<Vec<RecordedEvent>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, kind, message FROM (
+             SELECT id, timestamp, kind, message FROM events ORDER BY id DESC LIMIT ?1
+         ) ORDER BY id",
+    )?;
+    let rows = stmt.query_map([limit as i64], |row| {
+        Ok(RecordedEvent {
+            timestamp: row.get(0)?,
+            kind: row.get(1)?,
+            message: row.get(2)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// The latest `limit` tip origins recorded, newest first
+pub fn tip_origins(conn: &Connection, limit: usize) -> Result<Vec<TipOrigin>> {
+    let mut stmt = conn.prepare(
+        "SELECT tip_id, source, x, y, width, height FROM tip_origins ORDER BY id DESC LIMIT ?1",
+    )?;
+    let rows = stmt.query_map([limit as i64], |row| {
+        Ok(TipOrigin {
+            tip_id: row.get(0)?,
+            source: row.get(1)?,
+            bounds: (row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?),
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Zone readings of the last `sessions` sessions that have any, newest first
+pub fn recent_session_readings(conn: &Connection, sessions: usize) -> Result<Vec<SessionReadings>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, zone_id, zone_name, CAST(strftime('%s', timestamp) AS INTEGER), text
+         FROM zone_readings
+         WHERE session_id IN (
+             SELECT DISTINCT session_id FROM zone_readings
+             WHERE session_id IS NOT NULL
+             ORDER BY session_id DESC
+             LIMIT ?1
+         )
+         ORDER BY session_id DESC, id",
+    )?;
+    let rows = stmt.query_map([sessions as i64], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            RecordedReading {
+                zone_id: row.get(1)?,
+                zone_name: row.get(2)?,
+                at: row.get(3)?,
+                text: row.get(4)?,
+            },
+        ))
+    })?;
+
+    let mut result: Vec<SessionReadings> = Vec::new();
+    for row in rows {
+        let (session_id, reading) = row?;
+        match result.last_mut() {
+            Some(session) if session.session_id == session_id => session.readings.push(reading),
+            _ => result.push(SessionReadings {
+                session_id,
+                readings: vec![reading],
+            }),
+        }
+    }
+    Ok(result)
+}
+
+/// Open a read-only connection for the data browser
+pub fn open_read_only(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open database {:?}", path))
+}
+
+/// Browse a table, newest rows first
+///
+/// `filter` keeps rows where any column contains the text (case-insensitive).
+pub fn browse_table(
+    conn: &Connection,
+    table: &str,
+    filter: &str,
+    limit: usize,
+) -> Result<QueryResult> {
+    if !BROWSABLE_TABLES.contains(&table) {
+        bail!("Unknown table '{}'", table);
+    }
+
+    let columns: Vec<String> = conn
+        .prepare(&format!("PRAGMA table_info({})", table))?
+        .query_map([], |row| row.get(1))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let filter = filter.trim();
+    let sql = if filter.is_empty() {
+        format!("SELECT * FROM {} ORDER BY id DESC", table)
+    } else {
+        let conditions: Vec<String> = columns
+            .iter()
+            .map(|column| format!("CAST({} AS TEXT) LIKE ?1", column))
+            .collect();
+        format!(
+            "SELECT * FROM {} WHERE {} ORDER BY id DESC",
+            table,
+            conditions.join(" OR ")
+        )
+    };
+
+    let mut stmt = conn.prepare(&sql)?;
+    if filter.is_empty() {
+        collect_rows(&mut stmt, [], limit)
+    } else {
+        collect_rows(&mut stmt, [format!("%{}%", filter)], limit)
+    }
+}
+
+/// Run an ad-hoc query, refusing anything that could modify the database
+pub fn run_read_only_query(conn: &Connection, sql: &str, limit: usize) -> Result<QueryResult> {
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        bail!("Only read-only queries (SELECT, PRAGMA, EXPLAIN) are allowed");
+    }
+    collect_rows(&mut stmt, [], limit)
+}
+
+fn collect_rows<P: rusqlite::Params>(
+    stmt: &mut rusqlite::Statement,
+    params: P,
+    limit: usize,
+) -> Result<QueryResult> {
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let column_count = columns.len();
+
+    let mut result = QueryResult {
+        columns,
+        ..Default::default()
+    };
+
+    let mut rows = stmt.query(params)?;
+    while let Some(row) = rows.next()? {
+        if result.rows.len() == limit {
+            result.truncated = true;
+            break;
+        }
+        let values = (0..column_count)
+            .map(|i| row.get_ref(i).map(format_value))
+            .collect::<rusqlite::Result<_>>()?;
+        result.rows.push(values);
+    }
+
+    Ok(result)
+}
+
+fn format_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => format!("{:.3}", f),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+        ValueRef::Blob(blob) => format!("<{} bytes>", blob.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn populated() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        let session = db.start_session("dashboard").unwrap();
+        db.record_event(session, "capture", "Capture started")
+            .unwrap();
+        for (text, confidence) in [("100", 0.9), ("120", 0.4)] {
+            db.record_zone_reading(
+                session,
+                &ZoneReading {
+                    profile_id: Some("game"),
+                    zone_id: "gold",
+                    zone_name: "Gold",
+                    text,
+                    confidence: Some(confidence),
+                },
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_browse_table_with_filter() {
+        let db = populated();
+
+        let all = browse_table(&db.conn, "zone_readings", "", 10).unwrap();
+        assert_eq!(all.rows.len(), 2);
+        assert!(all.columns.contains(&"confidence".to_string()));
+
+        let filtered = browse_table(&db.conn, "zone_readings", "120", 10).unwrap();
+        assert_eq!(filtered.rows.len(), 1);
+
+        assert!(browse_table(&db.conn, "sqlite_master", "", 10).is_err());
+    }
+
+    #[test]
+    fn test_query_limit_and_read_only() {
+        let db = populated();
+
+        let result = run_read_only_query(&db.conn, "SELECT text FROM zone_readings", 1).unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert!(result.truncated);
+
+        assert!(run_read_only_query(&db.conn, "DELETE FROM events", 10).is_err());
+        assert!(run_read_only_query(&db.conn, "DROP TABLE sessions", 10).is_err());
+    }
+
+    #[test]
+    fn test_playtime_stats() {
+        let db = Database::open_in_memory().unwrap();
+        db.add_playtime("game", "Game", 600).unwrap();
+        db.add_playtime("game", "Game (renamed)", 300).unwrap();
+        db.add_playtime("other", "Other", 60).unwrap();
+        db.conn
+            .execute_batch(
+                "INSERT INTO playtime (day, profile_id, profile_name, seconds) VALUES
+                    (date('now', 'localtime', '-2 days'), 'game', 'Game', 1200),
+                    (date('now', 'localtime', '-30 days'), 'game', 'Game', 9999);",
+            )
+            .unwrap();
+
+        let stats = playtime_stats(&db.conn).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].profile_id, "game");
+        assert_eq!(stats[0].profile_name, "Game (renamed)");
+        assert_eq!(stats[0].today(), 900);
+        assert_eq!(stats[0].daily_seconds[PLAYTIME_DAYS - 3], 1200);
+        // Older days are left out
+        assert_eq!(stats[0].total(), 2100);
+        assert_eq!(stats[1].total(), 60);
+    }
+
+    #[test]
+    fn test_recent_session_readings() {
+        let db = populated();
+        db.start_session("dashboard").unwrap();
+        let latest = db.start_session("dashboard").unwrap();
+        db.record_zone_reading(
+            latest,
+            &ZoneReading {
+                profile_id: None,
+                zone_id: "gold",
+                zone_name: "Gold",
+                text: "150",
+                confidence: None,
+            },
+        )
+        .unwrap();
+
+        // The session without readings is skipped
+        let sessions = recent_session_readings(&db.conn, 5).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session_id, latest);
+        assert_eq!(sessions[0].readings[0].text, "150");
+        let texts: Vec<&str> = sessions[1]
+            .readings
+            .iter()
+            .map(|r| r.text.as_str())
+            .collect();
+        assert_eq!(texts, ["100", "120"]);
+
+        assert_eq!(recent_session_readings(&db.conn, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tip_origins() {
+        let db = Database::open_in_memory().unwrap();
+        let session = db.start_session("dashboard").unwrap();
+        for (tip_id, x) in [("first", 0.1), ("second", 0.5)] {
+            db.record_tip_origin(
+                session,
+                &TipOrigin {
+                    tip_id: tip_id.to_string(),
+                    source: "label Gold".to_string(),
+                    bounds: (x, 0.25, 0.125, 0.0625),
+                },
+            )
+            .unwrap();
+        }
+
+        let origins = tip_origins(&db.conn, 10).unwrap();
+        assert_eq!(origins.len(), 2);
+        assert_eq!(origins[0].tip_id, "second");
+        assert_eq!(origins[0].bounds, (0.5, 0.25, 0.125, 0.0625));
+        assert_eq!(tip_origins(&db.conn, 1).unwrap().len(), 1);
+    }
 }
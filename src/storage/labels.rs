@@ -0,0 +1,292 @@
+//! Labeled region maintenance
+//!
+//! Bulk edits for a profile's labeled regions: renaming a selection from a
+//! pattern, merging regions that carry the same label, and finding labels
+//! whose text hasn't been read for a number of dashboard sessions. Also
+//! reads the current value at each region, notices when it changes, and
+//! finds a region's text again when the UI moved it.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::profiles::LabeledRegion;
+use crate::vision::screen_recognition::text_similarity;
+
+/// New label for a region from a rename pattern
+///
+/// `{label}` is the current label, `{text}` the matched text and `{n}` the
+/// region's position in the selection, counting from 1.
+pub fn apply_rename_pattern(pattern: &str, region: &LabeledRegion, n: usize) -> String {
+    pattern
+        .replace("{label}", &region.label)
+        .replace("{text}", &region.matched_text)
+        .replace("{n}", &n.to_string())
+        .trim()
+        .to_string()
+}
+
+/// Rename the selected regions (indices) from a pattern
+///
+/// Returns how many labels changed.
+pub fn rename_selected(
+    regions: &mut [LabeledRegion],
+    selected: &BTreeSet<usize>,
+    pattern: &str,
+) -> usize {
+    let mut renamed = 0;
+    for (n, &index) in selected.iter().enumerate() {
+        let Some(region) = regions.get_mut(index) else {
+            continue;
+        };
+        let label = apply_rename_pattern(pattern, region, n + 1);
+        if !label.is_empty() && label != region.label {
+            region.label = label;
+            renamed += 1;
+        }
+    }
+    renamed
+}
+
+/// Label compared for duplicates (case and spacing ignored)
+fn label_key(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Number of regions that would go away when merging duplicates
+pub fn duplicate_count(regions: &[LabeledRegion]) -> usize {
+    let labels: BTreeSet<String> = regions.iter().map(|r| label_key(&r.label)).collect();
+    regions.len() - labels.len()
+}
+
+/// Merge regions that carry the same label into one
+///
+/// The merged region keeps the place of the first one, the text and bounds
+/// of the most confident one, and the most recent match. It notifies of
+/// changes if any of them did. Returns how many regions were removed.
+pub fn merge_duplicates(regions: &mut Vec<LabeledRegion>) -> usize {
+    let before = regions.len();
+    let mut merged: Vec<LabeledRegion> = Vec::with_capacity(before);
+
+    for region in regions.drain(..) {
+        let key = label_key(&region.label);
+        match merged.iter_mut().find(|kept| label_key(&kept.label) == key) {
+            Some(kept) => {
+                let last_matched = kept.last_matched_session.max(region.last_matched_session);
+                kept.notify_on_change |= region.notify_on_change;
+                if region.confidence > kept.confidence {
+                    kept.matched_text = region.matched_text;
+                    kept.bounds = region.bounds;
+                    kept.confidence = region.confidence;
+                }
+                kept.last_matched_session = last_matched;
+            }
+            None => merged.push(region),
+        }
+    }
+
+    *regions = merged;
+    before - regions.len()
+}
+
+/// Whether a region's text hasn't been read for at least `sessions` sessions
+///
+/// Regions not tracked yet are never stale.
+pub fn is_stale(region: &LabeledRegion, current_session: i64, sessions: u32) -> bool {
+    region
+        .last_matched_session
+        .is_some_and(|last| current_session - last >= i64::from(sessions))
+}
+
+/// Pixel bounds (x, y, width, height)
+pub type Bounds = (u32, u32, u32, u32);
+
+/// Area two bounds share
+fn overlap_area(a: Bounds, b: Bounds) -> u64 {
+    let width = (a.0 + a.2).min(b.0 + b.2).saturating_sub(a.0.max(b.0));
+    let height = (a.1 + a.3).min(b.1 + b.3).saturating_sub(a.1.max(b.1));
+    u64::from(width) * u64::from(height)
+}
+
+/// Text read at a region: the detection overlapping it most
+///
+/// A detection counts when the overlap covers at least half of the smaller
+/// of the two boxes.
+pub fn read_value<'a>(bounds: Bounds, detections: &[(&'a str, Bounds)]) -> Option<&'a str> {
+    let area = |b: Bounds| u64::from(b.2) * u64::from(b.3);
+    detections
+        .iter()
+        .map(|&(text, detection)| (text, overlap_area(bounds, detection), detection))
+        .filter(|&(_, overlap, detection)| {
+            overlap > 0 && overlap * 2 >= area(bounds).min(area(detection))
+        })
+        .max_by_key(|&(_, overlap, _)| overlap)
+        .map(|(text, _, _)| text.trim())
+        .filter(|text| !text.is_empty())
+}
+
+/// Bounds a region's text moved to, when nothing is read at its own
+///
+/// Searches every detection for the one most like the region's matched
+/// text, at least `threshold` similar.
+pub fn relocate(
+    region: &LabeledRegion,
+    detections: &[(&str, Bounds)],
+    threshold: f32,
+) -> Option<Bounds> {
+    detections
+        .iter()
+        .filter(|&&(_, bounds)| bounds != region.bounds)
+        .map(|&(text, bounds)| (text_similarity(text, &region.matched_text), bounds))
+        .filter(|&(similarity, _)| similarity >= threshold)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, bounds)| bounds)
+}
+
+/// Last value read at each label, for noticing changes
+///
+/// A new value has to be read on two checks in a row to count, so a single
+/// misread doesn't register as a change.
+#[derive(Debug, Default)]
+pub struct LabelValues {
+    values: HashMap<String, String>,
+    pending: HashMap<String, String>,
+}
+
+impl LabelValues {
+    /// Current value of a label
+    pub fn get(&self, label: &str) -> Option<&str> {
+        self.values.get(label).map(String::as_str)
+    }
+
+    /// Note the value read at a label
+    ///
+    /// Returns the previous value when the value changed. The first value
+    /// read isn't a change.
+    pub fn update(&mut self, label: &str, value: &str) -> Option<String> {
+        match self.values.get(label) {
+            Some(current) if current == value => {
+                self.pending.remove(label);
+                None
+            }
+            Some(_) if self.pending.get(label).is_some_and(|p| p == value) => {
+                self.pending.remove(label);
+                self.values.insert(label.to_string(), value.to_string())
+            }
+            Some(_) => {
+                self.pending.insert(label.to_string(), value.to_string());
+                None
+            }
+            None => {
+                self.values.insert(label.to_string(), value.to_string());
+                None
+            }
+        }
+    }
+
+    /// Forget every value, e.g. when another profile is activated
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(label: &str, text: &str, confidence: f32, last: Option<i64>) -> LabeledRegion {
+        LabeledRegion {
+            label: label.to_string(),
+            matched_text: text.to_string(),
+            bounds: (0, 0, 10, 10),
+            confidence,
+            last_matched_session: last,
+            notify_on_change: false,
+        }
+    }
+
+    #[test]
+    fn test_rename_selected() {
+        let mut regions = vec![
+            region("ore", "Parsteel", 0.9, None),
+            region("ore", "Tritanium", 0.9, None),
+            region("Gold", "1,234", 0.9, None),
+        ];
+        let selected = BTreeSet::from([0, 1, 7]);
+
+        assert_eq!(rename_selected(&mut regions, &selected, "{text} #{n}"), 2);
+        assert_eq!(regions[0].label, "Parsteel #1");
+        assert_eq!(regions[1].label, "Tritanium #2");
+        assert_eq!(regions[2].label, "Gold");
+        // Blank results keep the old label
+        assert_eq!(rename_selected(&mut regions, &selected, "  "), 0);
+    }
+
+    #[test]
+    fn test_merge_duplicates() {
+        let mut regions = vec![
+            region("Gold", "1,234", 0.7, Some(3)),
+            region("Ammo", "30", 0.9, None),
+            region(" gold ", "1,284", 0.95, Some(1)),
+        ];
+        assert_eq!(duplicate_count(&regions), 1);
+
+        assert_eq!(merge_duplicates(&mut regions), 1);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].label, "Gold");
+        assert_eq!(regions[0].matched_text, "1,284");
+        assert_eq!(regions[0].last_matched_session, Some(3));
+        assert_eq!(duplicate_count(&regions), 0);
+    }
+
+    #[test]
+    fn test_stale_labels() {
+        assert!(is_stale(&region("a", "", 1.0, Some(2)), 7, 5));
+        assert!(!is_stale(&region("a", "", 1.0, Some(3)), 7, 5));
+        assert!(!is_stale(&region("a", "", 1.0, None), 7, 5));
+    }
+
+    #[test]
+    fn test_read_value() {
+        let detections = [
+            ("Gold", (10, 10, 40, 20)),
+            ("1,234", (60, 10, 50, 20)),
+            ("12:00", (200, 10, 50, 20)),
+        ];
+        assert_eq!(read_value((58, 8, 60, 24), &detections), Some("1,234"));
+        // Barely touching the detection
+        assert_eq!(read_value((100, 25, 60, 24), &detections), None);
+        assert_eq!(read_value((0, 100, 10, 10), &detections), None);
+    }
+
+    #[test]
+    fn test_relocate() {
+        let gold = region("Gold", "1,234", 0.9, None);
+        let detections = [
+            ("Ammo", (400, 300, 40, 20)),
+            ("1,284", (140, 90, 50, 20)),
+            ("1,234 g", (600, 90, 70, 20)),
+        ];
+        assert_eq!(relocate(&gold, &detections, 0.7), Some((140, 90, 50, 20)));
+        assert_eq!(relocate(&gold, &detections[..1], 0.7), None);
+    }
+
+    #[test]
+    fn test_label_value_changes() {
+        let mut values = LabelValues::default();
+        assert_eq!(values.update("Reset", "03:00"), None);
+        assert_eq!(values.update("Reset", "03:00"), None);
+
+        // A single misread isn't a change
+        assert_eq!(values.update("Reset", "08:00"), None);
+        assert_eq!(values.update("Reset", "03:00"), None);
+        assert_eq!(values.get("Reset"), Some("03:00"));
+
+        assert_eq!(values.update("Reset", "Ready"), None);
+        assert_eq!(values.update("Reset", "Ready"), Some("03:00".to_string()));
+        assert_eq!(values.get("Reset"), Some("Ready"));
+    }
+}
@@ -3,14 +3,48 @@
 //!
 //! Handles persistence of profiles, settings, and logs using SQLite.
 
+pub mod backups;
 pub mod database;
+pub mod labels;
+pub mod profile_images;
 pub mod profiles;
+pub mod rule_library;
+pub mod samples;
+pub mod screenshots;
+pub mod session;
+pub mod share;
+pub mod snapshot;
+pub mod starter_packs;
+pub mod writer;
 
 use anyhow::Result;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Directory replacing the data and config directories for this run
+static DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir/data` and `dir/config` instead of the user's directories for the
+/// rest of the run (for loading snapshots); can only be set once, at startup
+pub fn set_dir_override(dir: PathBuf) -> Result<()> {
+    DIR_OVERRIDE
+        .set(dir)
+        .map_err(|_| anyhow::anyhow!("Data directory was already overridden"))
+}
+
+/// Whether the data and config directories are overridden
+pub fn is_dir_overridden() -> bool {
+    DIR_OVERRIDE.get().is_some()
+}
 
 /// Get the application data directory
 pub fn get_data_dir() -> Result<PathBuf> {
+    if let Some(dir) = DIR_OVERRIDE.get() {
+        let data_dir = dir.join("data");
+        std::fs::create_dir_all(&data_dir)?;
+        return Ok(data_dir);
+    }
+
     let proj_dirs = directories::ProjectDirs::from("com", "gamerstoolkit", "GamersToolKit")
         .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
 
@@ -22,6 +56,12 @@ pub fn get_data_dir() -> Result<PathBuf> {
 
 /// Get the configuration directory
 pub fn get_config_dir() -> Result<PathBuf> {
+    if let Some(dir) = DIR_OVERRIDE.get() {
+        let config_dir = dir.join("config");
+        std::fs::create_dir_all(&config_dir)?;
+        return Ok(config_dir);
+    }
+
     let proj_dirs = directories::ProjectDirs::from("com", "gamerstoolkit", "GamersToolKit")
         .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
 
@@ -38,3 +78,64 @@ pub fn get_profiles_dir() -> Result<PathBuf> {
     std::fs::create_dir_all(&profiles_dir)?;
     Ok(profiles_dir)
 }
+
+/// Get the profile backups directory (inside data directory)
+pub fn get_backups_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let backups_dir = data_dir.join("backups");
+    std::fs::create_dir_all(&backups_dir)?;
+    Ok(backups_dir)
+}
+
+/// Get the sample frames directory (inside data directory)
+pub fn get_samples_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let samples_dir = data_dir.join("samples");
+    std::fs::create_dir_all(&samples_dir)?;
+    Ok(samples_dir)
+}
+
+/// Get the saved replays directory (inside data directory)
+pub fn get_replays_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let replays_dir = data_dir.join("replays");
+    std::fs::create_dir_all(&replays_dir)?;
+    Ok(replays_dir)
+}
+
+/// Get the screenshots directory (inside data directory)
+pub fn get_screenshots_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let screenshots_dir = data_dir.join("screenshots");
+    std::fs::create_dir_all(&screenshots_dir)?;
+    Ok(screenshots_dir)
+}
+
+/// Get the exported snapshots directory (inside data directory)
+pub fn get_snapshots_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let snapshots_dir = data_dir.join("snapshots");
+    std::fs::create_dir_all(&snapshots_dir)?;
+    Ok(snapshots_dir)
+}
+
+/// Get the directory of caches that can be rebuilt (inside data directory)
+pub fn get_cache_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let cache_dir = data_dir.join("cache");
+    std::fs::create_dir_all(&cache_dir)?;
+    Ok(cache_dir)
+}
+
+/// Get the rules library directory (inside data directory)
+pub fn get_rules_dir() -> Result<PathBuf> {
+    let data_dir = get_data_dir()?;
+    let rules_dir = data_dir.join("rules");
+    std::fs::create_dir_all(&rules_dir)?;
+    Ok(rules_dir)
+}
+
+/// Get the SQLite database path (inside data directory)
+pub fn get_database_path() -> Result<PathBuf> {
+    Ok(get_data_dir()?.join("gamerstoolkit.db"))
+}
@@ -0,0 +1,190 @@
+//! Template images of profiles, stored next to them
+//!
+//! Anchor and screen templates are PNGs. Embedded in the profile JSON they
+//! made files huge and slow to diff or share, so saved profiles keep them in
+//! `images/<hash>.png` next to the profile file and refer to them by relative
+//! path. Profiles in memory always hold the PNG bytes; the references only
+//! exist on disk. Images are named by their content, so profiles with the
+//! same template share one file.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::profiles::GameProfile;
+
+/// Directory the images are kept in, next to the profile files
+pub const IMAGES_DIR: &str = "images";
+
+/// Call `f` with the PNG bytes and file reference of every template image
+///
+/// Empty bytes stand for no image.
+fn for_each_image(profile: &mut GameProfile, mut f: impl FnMut(&mut Vec<u8>, &mut Option<String>)) {
+    for screen in &mut profile.screens {
+        for anchor in &mut screen.anchors {
+            let mut data = anchor.template_data.take().unwrap_or_default();
+            f(&mut data, &mut anchor.template_file);
+            anchor.template_data = (!data.is_empty()).then_some(data);
+        }
+        for template in screen
+            .full_template
+            .iter_mut()
+            .chain(&mut screen.extra_templates)
+        {
+            f(&mut template.image_data, &mut template.image_file);
+        }
+    }
+}
+
+/// Whether any template image is still embedded in the profile
+pub(super) fn has_embedded_images(profile: &GameProfile) -> bool {
+    profile.screens.iter().any(|screen| {
+        screen.anchors.iter().any(|a| a.template_data.is_some())
+            || screen
+                .full_template
+                .iter()
+                .chain(&screen.extra_templates)
+                .any(|t| !t.image_data.is_empty())
+    })
+}
+
+/// Write the template images of a profile about to be saved to `dir` and
+/// replace them by references
+pub(super) fn store_images(profile: &mut GameProfile, dir: &Path) -> Result<()> {
+    let images_dir = dir.join(IMAGES_DIR);
+    let mut result = Ok(());
+    for_each_image(profile, |data, file| {
+        *file = None;
+        if data.is_empty() || result.is_err() {
+            return;
+        }
+        let name = image_name(data);
+        match write_image(&images_dir, &name, data) {
+            Ok(()) => {
+                *file = Some(format!("{}/{}", IMAGES_DIR, name));
+                data.clear();
+            }
+            Err(e) => result = Err(e),
+        }
+    });
+    result
+}
+
+/// Read the template images a profile loaded from `dir` refers to
+///
+/// A missing image is logged and leaves its template empty rather than
+/// failing the whole profile.
+pub(super) fn load_images(profile: &mut GameProfile, dir: &Path) {
+    for_each_image(profile, |data, file| {
+        let Some(reference) = file.take() else {
+            return;
+        };
+        if !data.is_empty() {
+            return;
+        }
+        let Some(path) = image_file_name(&reference).map(|name| dir.join(IMAGES_DIR).join(name))
+        else {
+            tracing::warn!(
+                "Ignoring template image outside the profiles folder: {}",
+                reference
+            );
+            return;
+        };
+        match std::fs::read(&path) {
+            Ok(bytes) => *data = bytes,
+            Err(e) => tracing::warn!("Failed to read template image {:?}: {}", path, e),
+        }
+    });
+}
+
+/// Delete images in `dir` that no profile there refers to anymore
+///
+/// Nothing is deleted if a profile can't be read, since its images would
+/// look unused. Returns how many images were deleted.
+pub fn remove_orphaned_images(dir: &Path) -> Result<usize> {
+    let images_dir = dir.join(IMAGES_DIR);
+    if !images_dir.is_dir() {
+        return Ok(0);
+    }
+
+    let mut used = HashSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let content = std::fs::read_to_string(&path)?;
+            let mut profile: GameProfile = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to read profile {:?}", path))?;
+            for_each_image(&mut profile, |_, file| {
+                if let Some(name) = file.as_deref().and_then(image_file_name) {
+                    used.insert(name.to_string());
+                }
+            });
+        }
+    }
+
+    let mut removed = 0;
+    for entry in std::fs::read_dir(&images_dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if path.extension().is_some_and(|ext| ext == "png") && !used.contains(name) {
+            std::fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// File name of an image: a hash of its bytes
+fn image_name(data: &[u8]) -> String {
+    let digest = Sha256::digest(data);
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}.png", hex)
+}
+
+/// File name a reference points to in the images directory, if it's a plain
+/// `images/<name>` reference (profiles are shared, so nothing else is read)
+fn image_file_name(reference: &str) -> Option<&str> {
+    let name = reference.strip_prefix(IMAGES_DIR)?.strip_prefix('/')?;
+    let plain = !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\', ':']);
+    plain.then_some(name)
+}
+
+/// Write an image unless a file with its name (and so its bytes) exists
+fn write_image(images_dir: &Path, name: &str, data: &[u8]) -> Result<()> {
+    let path = images_dir.join(name);
+    if path.exists() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(images_dir)?;
+    // Written under another name first, so a crash never leaves a partial
+    // image under the name of a complete one
+    let partial = path.with_extension("png.partial");
+    std::fs::write(&partial, data)
+        .and_then(|()| std::fs::rename(&partial, &path))
+        .with_context(|| format!("Failed to write template image {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_file_name() {
+        assert_eq!(image_file_name("images/ab12.png"), Some("ab12.png"));
+        assert_eq!(image_file_name("images/../secret.png"), None);
+        assert_eq!(image_file_name("images/sub/ab12.png"), None);
+        assert_eq!(image_file_name("C:\\images\\ab12.png"), None);
+        assert_eq!(image_file_name("other/ab12.png"), None);
+        assert_eq!(image_file_name("images/"), None);
+    }
+
+    #[test]
+    fn test_image_name_follows_content() {
+        assert_eq!(image_name(&[1, 2, 3]), image_name(&[1, 2, 3]));
+        assert_ne!(image_name(&[1, 2, 3]), image_name(&[1, 2, 4]));
+        assert_eq!(image_name(&[1, 2, 3]).len(), 32 + ".png".len());
+    }
+}
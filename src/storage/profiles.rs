@@ -4,6 +4,9 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use super::profile_images;
+use super::rule_library::RuleReference;
+
 /// A game profile definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameProfile {
@@ -21,6 +24,9 @@ pub struct GameProfile {
     pub templates: Vec<TemplateDefinition>,
     /// Rules to apply
     pub rules: Vec<RuleDefinition>,
+    /// Rules attached from the rules library
+    #[serde(default)]
+    pub library_rules: Vec<RuleReference>,
     /// User-defined labeled regions from vision/OCR
     #[serde(default)]
     pub labeled_regions: Vec<LabeledRegion>,
@@ -33,12 +39,124 @@ pub struct GameProfile {
     /// Interval between screen recognition checks in milliseconds
     #[serde(default = "default_screen_check_interval")]
     pub screen_check_interval_ms: u32,
+    /// OCR corrections and known words for this game
+    #[serde(default)]
+    pub dictionary: RecognitionDictionary,
+    /// Pixel-color triggers checked on every frame
+    #[serde(default)]
+    pub color_triggers: Vec<ColorTrigger>,
+    /// User-entered facts the screen never shows (character level, difficulty)
+    #[serde(default)]
+    pub variables: Vec<ProfileVariable>,
+    /// Coaching checklists (pre-raid prep, daily tasks)
+    #[serde(default)]
+    pub checklists: Vec<Checklist>,
+    /// Per-backend OCR confidence curves fit from the sample library
+    #[serde(default)]
+    pub confidence_calibration: Vec<ConfidenceCalibration>,
+    /// What to start when one of the executables launches
+    #[serde(default)]
+    pub auto_start: AutoStart,
+    /// Capture a frame every few seconds instead of streaming
+    #[serde(default)]
+    pub snapshot_capture: SnapshotCapture,
+    /// Detached tool windows of the game captured next to it; zones pick
+    /// one as their source to be read from its frames
+    #[serde(default)]
+    pub secondary_windows: Vec<crate::config::ExtraCaptureSource>,
+    /// Stages of a match (early, mid, late game) in the order they're reached
+    #[serde(default)]
+    pub phases: Vec<GamePhase>,
+}
+
+/// What to start when a profile's game launches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoStart {
+    /// Activate the profile and capture the game's window
+    #[serde(default)]
+    pub capture: bool,
+    /// Also start the overlay
+    #[serde(default)]
+    pub overlay: bool,
+}
+
+/// Snapshot mode: one frame every few seconds instead of a stream, for
+/// turn-based and menu-heavy games that change little between frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotCapture {
+    /// Capture snapshots instead of streaming
+    pub enabled: bool,
+    /// Time between snapshots in milliseconds
+    pub interval_ms: u32,
+}
+
+/// Shortest time between snapshots offered, in milliseconds
+pub const MIN_SNAPSHOT_INTERVAL_MS: u32 = 250;
+
+impl Default for SnapshotCapture {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 2000,
+        }
+    }
+}
+
+impl SnapshotCapture {
+    /// Time between frames, or None to stream
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        self.enabled.then(|| {
+            std::time::Duration::from_millis(self.interval_ms.max(MIN_SNAPSHOT_INTERVAL_MS) as u64)
+        })
+    }
+}
+
+impl GameProfile {
+    /// Values of the profile's variables, by name
+    pub fn variable_values(&self) -> std::collections::HashMap<String, String> {
+        self.variables
+            .iter()
+            .map(|variable| (variable.name.clone(), variable.value.clone()))
+            .collect()
+    }
 }
 
 fn default_screen_check_interval() -> u32 {
     500
 }
 
+/// Game-specific OCR corrections
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecognitionDictionary {
+    /// Literal replacements applied to raw OCR text, in order
+    #[serde(default)]
+    pub substitutions: Vec<SubstitutionRule>,
+    /// Known words and names; close OCR readings snap to these
+    #[serde(default)]
+    pub vocabulary: Vec<String>,
+}
+
+/// Replace a common misread with the intended text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubstitutionRule {
+    /// Text as OCR reads it (e.g. "0rc")
+    pub from: String,
+    /// Replacement (e.g. "Orc")
+    pub to: String,
+}
+
+/// How an OCR backend's raw confidence maps to how often it reads right
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceCalibration {
+    /// Backend the curve was fit for
+    pub backend: crate::vision::OcrBackend,
+    /// Curve points as (raw, calibrated), by increasing raw confidence
+    pub points: Vec<(f32, f32)>,
+    /// Labeled readings the curve was fit from
+    pub samples: u32,
+}
+
 /// A labeled region that maps detected text to a user-defined name
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabeledRegion {
@@ -50,10 +168,16 @@ pub struct LabeledRegion {
     pub bounds: (u32, u32, u32, u32),
     /// Confidence score from OCR
     pub confidence: f32,
+    /// Dashboard session the text was last read in (None = not tracked yet)
+    #[serde(default)]
+    pub last_matched_session: Option<i64>,
+    /// Show a tip when the value read at the region changes
+    #[serde(default)]
+    pub notify_on_change: bool,
 }
 
 /// A region to run OCR on (Zone OCR)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OcrRegion {
     /// Region identifier (unique within profile)
     pub id: String,
@@ -70,6 +194,54 @@ pub struct OcrRegion {
     /// Zone-specific OCR preprocessing settings (uses global settings if None)
     #[serde(default)]
     pub preprocessing: Option<crate::config::OcrPreprocessing>,
+    /// Read with both OCR backends and vote (for zones where a misread is costly)
+    #[serde(default)]
+    pub ensemble: bool,
+    /// Characters OCR may produce for this zone (`None` = any)
+    #[serde(default)]
+    pub allowed_chars: Option<String>,
+    /// Rotation and perspective correction applied before OCR
+    #[serde(default)]
+    pub geometry: ZoneGeometry,
+    /// Scrolling log (chat, combat log): record only newly scrolled-in lines
+    #[serde(default)]
+    pub scrolling: bool,
+    /// Smallest change of a number or percentage that records a new value
+    /// (0 = any change)
+    #[serde(default)]
+    pub value_epsilon: f64,
+    /// Composite capture source the bounds are relative to (`None` = the
+    /// whole frame)
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Game phases the zone is read in (empty = all)
+    #[serde(default)]
+    pub phases: Vec<String>,
+}
+
+/// Geometric correction for zones with angled or skewed text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ZoneGeometry {
+    /// Clockwise rotation in degrees that levels the text
+    #[serde(default)]
+    pub rotation: f32,
+    /// Corners of the text area (top-left, top-right, bottom-right,
+    /// bottom-left) relative to the zone (0.0-1.0), stretched to fill the zone
+    #[serde(default)]
+    pub perspective: Option<[(f32, f32); 4]>,
+}
+
+impl ZoneGeometry {
+    /// Corners that leave the zone unchanged
+    pub const IDENTITY_CORNERS: [(f32, f32); 4] = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+
+    /// Whether the correction changes the image at all
+    pub fn is_identity(&self) -> bool {
+        self.rotation.abs() < 0.01
+            && self
+                .perspective
+                .map_or(true, |corners| corners == Self::IDENTITY_CORNERS)
+    }
 }
 
 fn default_true() -> bool {
@@ -108,6 +280,44 @@ pub struct RuleDefinition {
     pub enabled: bool,
     /// Rhai script code
     pub script: String,
+    /// Game phases the rule runs in (empty = all)
+    #[serde(default)]
+    pub phases: Vec<String>,
+    /// Lowest confidence of the readings the rule may act on (None = any)
+    #[serde(default)]
+    pub confidence_floor: Option<ConfidenceFloor>,
+}
+
+/// Lowest confidence of the zones and screen a rule reads it trusts, so
+/// misread OCR doesn't turn into advice that looks certain
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceFloor {
+    /// Minimum confidence (0.0-1.0)
+    pub min: f32,
+    /// What happens to the rule's tips below it
+    #[serde(default)]
+    pub below: LowConfidenceAction,
+}
+
+impl Default for ConfidenceFloor {
+    fn default() -> Self {
+        Self {
+            min: 0.6,
+            below: LowConfidenceAction::default(),
+        }
+    }
+}
+
+/// What happens to a rule's tips when its readings are below its
+/// confidence floor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LowConfidenceAction {
+    /// Show them marked as uncertain
+    #[default]
+    MarkUncertain,
+    /// Don't show them
+    Suppress,
 }
 
 // ============================================================================
@@ -132,6 +342,10 @@ pub struct ScreenDefinition {
     /// Full screenshot template for full-screen matching
     #[serde(default)]
     pub full_template: Option<ScreenTemplate>,
+    /// Full templates captured at other resolutions or aspect ratios; the
+    /// closest one to the frame is used
+    #[serde(default)]
+    pub extra_templates: Vec<ScreenTemplate>,
     /// Minimum confidence threshold for a match (0.0-1.0)
     #[serde(default = "default_match_threshold")]
     pub match_threshold: f32,
@@ -150,6 +364,61 @@ pub struct ScreenDefinition {
     /// Whether to show overlay notification on screen detection
     #[serde(default = "default_true")]
     pub show_notification: bool,
+    /// Required anchors that must match (`None` = all), so the screen is still
+    /// recognized when a popup covers some of them
+    #[serde(default)]
+    pub min_required_anchors: Option<u32>,
+    /// Area covered when this screen is a popup or modal over the game
+    /// (`None` = not a popup); zones under it are occluded while it's shown
+    #[serde(default)]
+    pub popup_bounds: Option<(f32, f32, f32, f32)>,
+}
+
+impl ScreenDefinition {
+    /// Every full template, the primary one first
+    pub fn full_templates(&self) -> impl Iterator<Item = &ScreenTemplate> {
+        self.full_template.iter().chain(&self.extra_templates)
+    }
+
+    /// Add a full template, replacing one captured at the same resolution
+    pub fn add_full_template(&mut self, template: ScreenTemplate) {
+        let size = (template.width, template.height);
+        let same_size = |t: &ScreenTemplate| (t.width, t.height) == size;
+        match self.full_template {
+            Some(ref existing) if !same_size(existing) => {
+                match self.extra_templates.iter_mut().find(|t| same_size(t)) {
+                    Some(existing) => *existing = template,
+                    None => self.extra_templates.push(template),
+                }
+            }
+            _ => self.full_template = Some(template),
+        }
+    }
+
+    /// Remove the full template at an index of [`Self::full_templates`]
+    pub fn remove_full_template(&mut self, index: usize) {
+        let index = match self.full_template {
+            Some(_) if index == 0 => {
+                self.full_template =
+                    (!self.extra_templates.is_empty()).then(|| self.extra_templates.remove(0));
+                return;
+            }
+            Some(_) => index - 1,
+            None => index,
+        };
+        if index < self.extra_templates.len() {
+            self.extra_templates.remove(index);
+        }
+    }
+
+    /// Whether this screen, as a popup, covers part of a zone
+    pub fn occludes(&self, zone_bounds: (f32, f32, f32, f32)) -> bool {
+        let Some((px, py, pw, ph)) = self.popup_bounds else {
+            return false;
+        };
+        let (zx, zy, zw, zh) = zone_bounds;
+        px < zx + zw && zx < px + pw && py < zy + zh && zy < py + ph
+    }
 }
 
 fn default_match_threshold() -> f32 {
@@ -176,8 +445,12 @@ pub struct ScreenAnchor {
     /// Region bounds (x, y, width, height) as percentages of screen (0.0-1.0)
     pub bounds: (f32, f32, f32, f32),
     /// Template image data for visual anchors (PNG encoded)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub template_data: Option<Vec<u8>>,
+    /// Where a saved profile keeps `template_data`, relative to the profile
+    /// file (only set on disk)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_file: Option<String>,
     /// Expected text for text anchors
     #[serde(default)]
     pub expected_text: Option<String>,
@@ -187,12 +460,32 @@ pub struct ScreenAnchor {
     /// Whether this anchor must match (vs optional/bonus)
     #[serde(default = "default_true")]
     pub required: bool,
+    /// Weight of this anchor in the screen's confidence, relative to the others
+    #[serde(default = "default_anchor_weight")]
+    pub weight: f32,
+    /// How a visual anchor's template is compared to its region
+    #[serde(default)]
+    pub match_strategy: MatchStrategy,
+}
+
+/// How a visual template is compared to the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchStrategy {
+    /// Pixel correlation (NCC); fast, but needs the same position and scale
+    #[default]
+    Ncc,
+    /// Keypoint features (ORB); tolerates slight scaling and rotation
+    Features,
 }
 
 fn default_text_similarity() -> f32 {
     0.8
 }
 
+fn default_anchor_weight() -> f32 {
+    1.0
+}
+
 /// Type of anchor for screen matching
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AnchorType {
@@ -207,7 +500,12 @@ pub enum AnchorType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenTemplate {
     /// PNG-encoded image data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub image_data: Vec<u8>,
+    /// Where a saved profile keeps `image_data`, relative to the profile file
+    /// (only set on disk)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_file: Option<String>,
     /// Original image width
     pub width: u32,
     /// Original image height
@@ -216,6 +514,149 @@ pub struct ScreenTemplate {
     pub captured_at: String,
 }
 
+/// A value the user enters for rules and tip templates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileVariable {
+    /// Name used in rules and as `{name}` in tip templates
+    pub name: String,
+    /// Current value
+    #[serde(default)]
+    pub value: String,
+    /// Allowed values, picked from a list (empty = free text)
+    #[serde(default)]
+    pub choices: Vec<String>,
+}
+
+/// A list of things to do, e.g. before a raid or every day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checklist {
+    /// Checklist identifier (unique within profile)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Items in order
+    #[serde(default)]
+    pub items: Vec<ChecklistItem>,
+    /// When the items are unchecked again
+    #[serde(default)]
+    pub reset: ChecklistReset,
+    /// Unix time (seconds) the items were last unchecked
+    #[serde(default)]
+    pub last_reset: Option<u64>,
+    /// Whether the overlay shows this checklist and the hotkey ticks it
+    #[serde(default)]
+    pub pinned: bool,
+    /// Game phases the overlay shows the checklist in (empty = all)
+    #[serde(default)]
+    pub phases: Vec<String>,
+}
+
+/// One thing to do on a checklist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Item identifier (unique within checklist)
+    pub id: String,
+    /// What to do
+    pub text: String,
+    /// Whether it's done
+    #[serde(default)]
+    pub done: bool,
+    /// Condition that checks the item by itself (None = ticked by hand)
+    #[serde(default)]
+    pub auto_check: Option<ChecklistCondition>,
+}
+
+/// Something seen on screen that marks a checklist item done
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChecklistCondition {
+    /// A screen is recognized
+    Screen { screen_id: String },
+    /// A color trigger fires
+    ColorTrigger { trigger_id: String },
+    /// An OCR zone reads a text (case-insensitive, anywhere in the reading)
+    ZoneText { zone_id: String, text: String },
+}
+
+/// When a checklist's items are unchecked again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChecklistReset {
+    /// Only by hand
+    #[default]
+    Manual,
+    /// Every time the app starts
+    SessionStart,
+    /// Every day at an hour (UTC)
+    Daily { hour_utc: u8 },
+    /// Every week on a weekday (0 = Monday) at an hour (UTC)
+    Weekly { weekday: u8, hour_utc: u8 },
+}
+
+/// A stage of a match, such as early, mid or late game
+///
+/// Zones and checklists can be limited to some phases so the overlay only
+/// shows what matters at that point of the match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GamePhase {
+    /// Phase identifier (unique within profile)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// When the match enters this phase
+    #[serde(default)]
+    pub start: PhaseStart,
+}
+
+/// What moves a match into a phase
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PhaseStart {
+    /// As soon as the match starts
+    #[default]
+    MatchStart,
+    /// Once the match has run for some minutes
+    Elapsed { minutes: u32 },
+    /// Once something shows on screen
+    Seen { condition: ChecklistCondition },
+}
+
+/// Watches a small patch of pixels for a color (e.g. a low-health vignette)
+///
+/// A cheap alternative to templates and OCR for state shown by color alone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorTrigger {
+    /// Trigger identifier (unique within profile)
+    pub id: String,
+    /// User-friendly name
+    #[serde(default)]
+    pub name: String,
+    /// Center of the patch (x, y) as percentages of screen (0.0-1.0)
+    pub position: (f32, f32),
+    /// Side of the square patch in pixels (1 = a single pixel)
+    #[serde(default = "default_patch_size")]
+    pub patch_size: u32,
+    /// Color the patch shows when the trigger fires (RGB)
+    pub color: [u8; 3],
+    /// Largest per-channel difference still counted as the color
+    #[serde(default = "default_color_tolerance")]
+    pub tolerance: u8,
+    /// Whether the trigger is checked
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Save the replay buffer when the color starts showing
+    #[serde(default)]
+    pub save_replay: bool,
+}
+
+fn default_patch_size() -> u32 {
+    3
+}
+
+fn default_color_tolerance() -> u8 {
+    24
+}
+
 /// Override for an OCR zone when a screen is active
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZoneOverride {
@@ -225,21 +666,96 @@ pub struct ZoneOverride {
     pub enabled: bool,
 }
 
-/// Load a game profile from file
+/// Load a game profile from file, with the template images it refers to
 pub fn load_profile(path: &Path) -> Result<GameProfile> {
-    let content = std::fs::read_to_string(path)?;
-    let profile: GameProfile = serde_json::from_str(&content)?;
+    let mut profile = read_profile_file(path)?;
+    profile_images::load_images(&mut profile, profile_dir(path));
     Ok(profile)
 }
 
-/// Save a game profile to file
+/// Save a game profile to file, with its template images in separate files
+/// next to it
 pub fn save_profile(profile: &GameProfile, path: &Path) -> Result<()> {
-    let content = serde_json::to_string_pretty(profile)?;
-    std::fs::write(path, content)?;
+    let mut profile = profile.clone();
+    profile_images::store_images(&mut profile, profile_dir(path))?;
+    write_if_changed(path, &serde_json::to_string_pretty(&profile)?)?;
     Ok(())
 }
 
+/// Sections of a profile changed since it was saved (`None` = unchanged)
+///
+/// The dashboard autosaves zones and labels every few seconds while they
+/// change; saving just those leaves screens and their template images alone.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSections {
+    /// Profile identifier
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// OCR zones
+    pub ocr_regions: Option<Vec<OcrRegion>>,
+    /// Labeled regions
+    pub labeled_regions: Option<Vec<LabeledRegion>>,
+}
+
+impl ProfileSections {
+    /// Put the changed sections into a profile
+    pub fn apply(&self, profile: &mut GameProfile) {
+        if let Some(ref regions) = self.ocr_regions {
+            profile.ocr_regions = regions.clone();
+        }
+        if let Some(ref regions) = self.labeled_regions {
+            profile.labeled_regions = regions.clone();
+        }
+    }
+
+    /// Add sections changed later, which win over these
+    pub fn merge(&mut self, newer: ProfileSections) {
+        self.name = newer.name;
+        if newer.ocr_regions.is_some() {
+            self.ocr_regions = newer.ocr_regions;
+        }
+        if newer.labeled_regions.is_some() {
+            self.labeled_regions = newer.labeled_regions;
+        }
+    }
+}
+
+/// Save changed sections of a profile saved before, keeping the rest of its
+/// file as it is
+///
+/// Template images aren't read or written. Returns whether the file changed.
+pub fn save_profile_sections(sections: &ProfileSections, path: &Path) -> Result<bool> {
+    let mut profile = read_profile_file(path)?;
+    sections.apply(&mut profile);
+    write_if_changed(path, &serde_json::to_string_pretty(&profile)?)
+}
+
+/// Write a file unless it already holds `content`, so saves that change
+/// nothing don't touch the disk; returns whether it was written
+fn write_if_changed(path: &Path, content: &str) -> Result<bool> {
+    if std::fs::read_to_string(path).is_ok_and(|saved| saved == content) {
+        return Ok(false);
+    }
+    std::fs::write(path, content)?;
+    Ok(true)
+}
+
+/// Profile JSON as it's stored, images still referenced by file
+fn read_profile_file(path: &Path) -> Result<GameProfile> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Directory a profile file's image references are relative to
+fn profile_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new("."))
+}
+
 /// Load all game profiles from a directory
+///
+/// Profiles from before template images were kept in separate files are
+/// rewritten that way, and images no profile refers to anymore are deleted.
 pub fn load_all_profiles(dir: &Path) -> Result<Vec<GameProfile>> {
     let mut profiles = Vec::new();
 
@@ -253,8 +769,22 @@ pub fn load_all_profiles(dir: &Path) -> Result<Vec<GameProfile>> {
 
         // Only load .json files
         if path.extension().is_some_and(|ext| ext == "json") {
-            match load_profile(&path) {
-                Ok(profile) => {
+            match read_profile_file(&path) {
+                Ok(mut profile) => {
+                    if profile_images::has_embedded_images(&profile) {
+                        match save_profile(&profile, &path) {
+                            Ok(()) => tracing::info!(
+                                "Moved template images of profile '{}' to separate files",
+                                profile.id
+                            ),
+                            Err(e) => tracing::warn!(
+                                "Failed to move template images of profile '{}': {:#}",
+                                profile.id,
+                                e
+                            ),
+                        }
+                    }
+                    profile_images::load_images(&mut profile, dir);
                     profiles.push(profile);
                 }
                 Err(e) => {
@@ -264,6 +794,12 @@ pub fn load_all_profiles(dir: &Path) -> Result<Vec<GameProfile>> {
         }
     }
 
+    match profile_images::remove_orphaned_images(dir) {
+        Ok(0) => {}
+        Ok(removed) => tracing::info!("Removed {} unused template images", removed),
+        Err(e) => tracing::warn!("Failed to clean up template images: {:#}", e),
+    }
+
     Ok(profiles)
 }
 
@@ -295,6 +831,13 @@ mod tests {
                     content_type: ContentType::Number,
                     enabled: true,
                     preprocessing: None,
+                    ensemble: false,
+                    allowed_chars: None,
+                    geometry: Default::default(),
+                    scrolling: false,
+                    value_epsilon: 0.0,
+                    source: None,
+                    phases: vec![],
                 },
                 OcrRegion {
                     id: "mana".to_string(),
@@ -303,6 +846,13 @@ mod tests {
                     content_type: ContentType::Percentage,
                     enabled: true,
                     preprocessing: None,
+                    ensemble: false,
+                    allowed_chars: None,
+                    geometry: Default::default(),
+                    scrolling: false,
+                    value_epsilon: 0.0,
+                    source: None,
+                    phases: vec![],
                 },
             ],
             templates: vec![TemplateDefinition {
@@ -315,12 +865,17 @@ mod tests {
                 name: "Low Health Warning".to_string(),
                 enabled: true,
                 script: r#"if health < 20 { alert("Low health!") }"#.to_string(),
+                phases: vec![],
+                confidence_floor: None,
             }],
+            library_rules: vec![],
             labeled_regions: vec![LabeledRegion {
                 label: "Gold".to_string(),
                 matched_text: "1,234".to_string(),
                 bounds: (100, 50, 80, 20),
                 confidence: 0.95,
+                last_matched_session: None,
+                notify_on_change: false,
             }],
             screens: vec![ScreenDefinition {
                 id: "main_menu".to_string(),
@@ -332,20 +887,35 @@ mod tests {
                     anchor_type: AnchorType::Text,
                     bounds: (0.4, 0.1, 0.2, 0.1),
                     template_data: None,
+                    template_file: None,
                     expected_text: Some("Test Game".to_string()),
                     text_similarity: 0.9,
                     required: true,
+                    weight: 1.0,
+                    match_strategy: MatchStrategy::Ncc,
                 }],
                 full_template: None,
+                extra_templates: vec![],
                 match_threshold: 0.8,
                 enabled: true,
                 priority: 10,
                 ocr_zone_overrides: vec![],
                 rules_to_trigger: vec![],
                 show_notification: true,
+                min_required_anchors: None,
+                popup_bounds: None,
             }],
             screen_recognition_enabled: true,
             screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
         }
     }
 
@@ -377,6 +947,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_snapshot_capture() {
+        let mut profile = create_test_profile();
+        assert_eq!(profile.snapshot_capture.interval(), None);
+
+        profile.snapshot_capture = SnapshotCapture {
+            enabled: true,
+            interval_ms: 3000,
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        let parsed: GameProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.snapshot_capture.interval(),
+            Some(std::time::Duration::from_secs(3))
+        );
+
+        let too_fast = SnapshotCapture {
+            enabled: true,
+            interval_ms: 0,
+        };
+        assert_eq!(
+            too_fast.interval(),
+            Some(std::time::Duration::from_millis(
+                MIN_SNAPSHOT_INTERVAL_MS as u64
+            ))
+        );
+    }
+
     #[test]
     fn test_save_and_load_profile() {
         let profile = create_test_profile();
@@ -404,6 +1002,13 @@ mod tests {
             content_type: ContentType::Text,
             enabled: true,
             preprocessing: None,
+            ensemble: false,
+            allowed_chars: None,
+            geometry: Default::default(),
+            scrolling: false,
+            value_epsilon: 0.0,
+            source: None,
+            phases: vec![],
         };
 
         assert_eq!(region.bounds.0, 0.5); // x
@@ -453,6 +1058,11 @@ mod tests {
             name: "Test Rule".to_string(),
             enabled: false,
             script: "print(\"hello\")".to_string(),
+            phases: vec![],
+            confidence_floor: Some(ConfidenceFloor {
+                min: 0.7,
+                below: LowConfidenceAction::Suppress,
+            }),
         };
 
         let json = serde_json::to_string(&rule).unwrap();
@@ -462,6 +1072,12 @@ mod tests {
         assert_eq!(rule.name, parsed.name);
         assert_eq!(rule.enabled, parsed.enabled);
         assert_eq!(rule.script, parsed.script);
+        assert_eq!(rule.confidence_floor, parsed.confidence_floor);
+
+        // Rules saved before confidence floors act on any reading
+        let old: RuleDefinition =
+            serde_json::from_str(r#"{"id":"r","name":"R","enabled":true,"script":""}"#).unwrap();
+        assert_eq!(old.confidence_floor, None);
     }
 
     #[test]
@@ -498,10 +1114,20 @@ mod tests {
             ocr_regions: vec![],
             templates: vec![],
             rules: vec![],
+            library_rules: vec![],
             labeled_regions: vec![],
             screens: vec![],
             screen_recognition_enabled: false,
             screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
         };
 
         let json = serde_json::to_string(&profile).unwrap();
@@ -529,21 +1155,28 @@ mod tests {
                     anchor_type: AnchorType::Text,
                     bounds: (0.1, 0.05, 0.3, 0.1),
                     template_data: None,
+                    template_file: None,
                     expected_text: Some("INVENTORY".to_string()),
                     text_similarity: 0.85,
                     required: true,
+                    weight: 1.0,
+                    match_strategy: MatchStrategy::Ncc,
                 },
                 ScreenAnchor {
                     id: "icon".to_string(),
                     anchor_type: AnchorType::Visual,
                     bounds: (0.05, 0.05, 0.05, 0.05),
                     template_data: Some(vec![1, 2, 3, 4]), // Dummy PNG data
+                    template_file: None,
                     expected_text: None,
                     text_similarity: 0.8,
                     required: false,
+                    weight: 1.0,
+                    match_strategy: MatchStrategy::Ncc,
                 },
             ],
             full_template: None,
+            extra_templates: vec![],
             match_threshold: 0.75,
             enabled: true,
             priority: 5,
@@ -553,6 +1186,8 @@ mod tests {
             }],
             rules_to_trigger: vec!["inventory_opened".to_string()],
             show_notification: false,
+            min_required_anchors: None,
+            popup_bounds: None,
         };
 
         let json = serde_json::to_string_pretty(&screen).unwrap();
@@ -570,6 +1205,136 @@ mod tests {
         assert_eq!(screen.rules_to_trigger.len(), parsed.rules_to_trigger.len());
     }
 
+    #[test]
+    fn test_full_templates_per_resolution() {
+        let template = |width, height, captured_at: &str| ScreenTemplate {
+            image_data: vec![],
+            image_file: None,
+            width,
+            height,
+            captured_at: captured_at.to_string(),
+        };
+        let mut screen: ScreenDefinition = serde_json::from_str(
+            r#"{"id": "menu", "name": "Menu", "match_mode": "FullScreenshot"}"#,
+        )
+        .unwrap();
+
+        screen.add_full_template(template(1920, 1080, "1"));
+        screen.add_full_template(template(2560, 1440, "2"));
+        screen.add_full_template(template(1920, 1080, "3"));
+        let captured: Vec<_> = screen
+            .full_templates()
+            .map(|t| t.captured_at.as_str())
+            .collect();
+        assert_eq!(captured, ["3", "2"]);
+
+        screen.remove_full_template(0);
+        assert_eq!(screen.full_template.as_ref().unwrap().width, 2560);
+        assert!(screen.extra_templates.is_empty());
+        screen.remove_full_template(0);
+        assert_eq!(screen.full_templates().count(), 0);
+    }
+
+    #[test]
+    fn test_popup_occludes_overlapping_zones() {
+        let mut screen: ScreenDefinition =
+            serde_json::from_str(r#"{"id": "popup", "name": "Popup", "match_mode": "Anchors"}"#)
+                .unwrap();
+        assert!(!screen.occludes((0.0, 0.0, 1.0, 1.0)));
+
+        screen.popup_bounds = Some((0.25, 0.25, 0.5, 0.5));
+        assert!(screen.occludes((0.7, 0.7, 0.1, 0.1)));
+        assert!(screen.occludes((0.0, 0.0, 1.0, 1.0)));
+        assert!(!screen.occludes((0.8, 0.1, 0.1, 0.1)));
+        assert!(!screen.occludes((0.75, 0.3, 0.1, 0.1)));
+    }
+
+    #[test]
+    fn test_template_images_saved_separately() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test-game.json");
+        let mut profile = create_test_profile();
+        profile.screens[0].anchors[0].template_data = Some(vec![1, 2, 3]);
+        profile.screens[0].full_template = Some(ScreenTemplate {
+            image_data: vec![4, 5, 6],
+            image_file: None,
+            width: 1920,
+            height: 1080,
+            captured_at: "1".to_string(),
+        });
+
+        save_profile(&profile, &path).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(!json.contains("template_data") && !json.contains("image_data"));
+        assert_eq!(json.matches("\"images/").count(), 2);
+
+        let loaded = load_profile(&path).unwrap();
+        let anchor = &loaded.screens[0].anchors[0];
+        assert_eq!(anchor.template_data, Some(vec![1, 2, 3]));
+        assert_eq!(anchor.template_file, None);
+        let template = loaded.screens[0].full_template.as_ref().unwrap();
+        assert_eq!(template.image_data, vec![4, 5, 6]);
+        assert_eq!(template.image_file, None);
+    }
+
+    #[test]
+    fn test_save_profile_sections_keeps_the_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test-game.json");
+        let mut profile = create_test_profile();
+        profile.screens[0].anchors[0].template_data = Some(vec![1, 2, 3]);
+        save_profile(&profile, &path).unwrap();
+
+        let mut sections = ProfileSections {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            ocr_regions: Some(vec![profile.ocr_regions[1].clone()]),
+            labeled_regions: None,
+        };
+        assert!(save_profile_sections(&sections, &path).unwrap());
+        assert!(!save_profile_sections(&sections, &path).unwrap());
+
+        let loaded = load_profile(&path).unwrap();
+        assert_eq!(loaded.ocr_regions.len(), 1);
+        assert_eq!(loaded.ocr_regions[0].id, "mana");
+        assert_eq!(loaded.labeled_regions.len(), 1);
+        assert_eq!(
+            loaded.screens[0].anchors[0].template_data,
+            Some(vec![1, 2, 3])
+        );
+
+        // Later sections win, untouched ones are kept
+        sections.merge(ProfileSections {
+            labeled_regions: Some(vec![]),
+            ..Default::default()
+        });
+        assert_eq!(sections.ocr_regions.as_ref().map(Vec::len), Some(1));
+        assert_eq!(sections.labeled_regions.as_ref().map(Vec::len), Some(0));
+    }
+
+    #[test]
+    fn test_load_all_moves_embedded_images_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test-game.json");
+        let mut profile = create_test_profile();
+        profile.screens[0].anchors[0].template_data = Some(vec![7, 8, 9]);
+        std::fs::write(&path, serde_json::to_string(&profile).unwrap()).unwrap();
+        let images = dir.path().join(profile_images::IMAGES_DIR);
+        std::fs::create_dir_all(&images).unwrap();
+        std::fs::write(images.join("unused.png"), [0]).unwrap();
+
+        let loaded = load_all_profiles(dir.path()).unwrap();
+        assert_eq!(
+            loaded[0].screens[0].anchors[0].template_data,
+            Some(vec![7, 8, 9])
+        );
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("template_data"));
+        assert!(!images.join("unused.png").exists());
+        assert_eq!(std::fs::read_dir(&images).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_screen_match_mode_serialization() {
         let modes = vec![ScreenMatchMode::FullScreenshot, ScreenMatchMode::Anchors];
@@ -0,0 +1,268 @@
+//! Rules library
+//!
+//! Rules that aren't tied to one game ("remind me to stretch", "screenshot on
+//! the victory screen") live in a library next to the profiles. Profiles
+//! attach them by reference and may override their parameters, so a rule
+//! fixed in the library is fixed everywhere it's used. Each library rule is
+//! a JSON file in the rules directory and can be shared on its own.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::profiles::{ConfidenceFloor, GameProfile, RuleDefinition};
+
+/// A rule kept in the library, independent of any profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryRule {
+    /// Rule identifier
+    pub id: String,
+    /// Rule name
+    pub name: String,
+    /// What the rule does, for the library list
+    #[serde(default)]
+    pub description: String,
+    /// Rhai script code; parameters are available as constants
+    pub script: String,
+    /// Values profiles can override (durations, thresholds, messages)
+    #[serde(default)]
+    pub parameters: Vec<RuleParameter>,
+}
+
+/// A value of a library rule that profiles can set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleParameter {
+    /// Constant name in the script (letters, digits and underscores)
+    pub name: String,
+    /// Value used unless a profile overrides it
+    #[serde(default)]
+    pub default: String,
+    /// What the value controls
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A library rule attached to a profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleReference {
+    /// ID of the library rule
+    pub rule_id: String,
+    /// Whether the rule runs for this profile
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Parameter values for this profile, by parameter name
+    #[serde(default)]
+    pub overrides: BTreeMap<String, String>,
+    /// Game phases the rule runs in for this profile (empty = all)
+    #[serde(default)]
+    pub phases: Vec<String>,
+    /// Lowest confidence of readings the rule acts on for this profile
+    #[serde(default)]
+    pub confidence_floor: Option<ConfidenceFloor>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl RuleReference {
+    /// Attach a rule with its default parameters
+    pub fn new(rule_id: impl Into<String>) -> Self {
+        Self {
+            rule_id: rule_id.into(),
+            enabled: true,
+            overrides: BTreeMap::new(),
+            phases: vec![],
+            confidence_floor: None,
+        }
+    }
+}
+
+impl LibraryRule {
+    /// Library copy of a profile's rule, without parameters
+    pub fn from_rule(rule: &RuleDefinition) -> Self {
+        Self {
+            id: rule.id.clone(),
+            name: rule.name.clone(),
+            description: String::new(),
+            script: rule.script.clone(),
+            parameters: vec![],
+        }
+    }
+
+    /// The rule as a profile runs it
+    ///
+    /// The script is preceded by a constant for each parameter, holding the
+    /// profile's override or else the default. Overrides of parameters the
+    /// rule no longer has are ignored.
+    pub fn resolve(&self, reference: &RuleReference) -> RuleDefinition {
+        let mut script = String::new();
+        for parameter in &self.parameters {
+            let value = reference
+                .overrides
+                .get(&parameter.name)
+                .unwrap_or(&parameter.default);
+            script.push_str(&format!(
+                "const {} = {};\n",
+                parameter.name,
+                script_literal(value)
+            ));
+        }
+        script.push_str(&self.script);
+
+        RuleDefinition {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            enabled: reference.enabled,
+            script,
+            phases: reference.phases.clone(),
+            confidence_floor: reference.confidence_floor,
+        }
+    }
+}
+
+/// A parameter value as a script literal
+///
+/// Numbers and booleans are kept as they are, anything else becomes a string.
+fn script_literal(value: &str) -> String {
+    let value = value.trim();
+    if value == "true" || value == "false" || value.parse::<f64>().is_ok_and(f64::is_finite) {
+        return value.to_string();
+    }
+    format!("{:?}", value)
+}
+
+/// Make a parameter name usable as a script constant
+///
+/// Anything but letters, digits and underscores becomes an underscore, and a
+/// leading digit gets one in front.
+pub fn parameter_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Every rule a profile runs: its own, then the attached library rules
+///
+/// References to rules missing from the library are skipped.
+pub fn effective_rules(profile: &GameProfile, library: &[LibraryRule]) -> Vec<RuleDefinition> {
+    let attached = profile.library_rules.iter().filter_map(|reference| {
+        library
+            .iter()
+            .find(|rule| rule.id == reference.rule_id)
+            .map(|rule| rule.resolve(reference))
+    });
+    profile.rules.iter().cloned().chain(attached).collect()
+}
+
+/// Load a library rule from file
+pub fn load_library_rule(path: &Path) -> Result<LibraryRule> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Save a library rule to the rules directory
+pub fn save_library_rule(rule: &LibraryRule, dir: &Path) -> Result<()> {
+    let content = serde_json::to_string_pretty(rule)?;
+    std::fs::write(dir.join(format!("{}.json", rule.id)), content)?;
+    Ok(())
+}
+
+/// Load every rule in the rules directory, sorted by name
+pub fn load_library(dir: &Path) -> Result<Vec<LibraryRule>> {
+    let mut rules = Vec::new();
+
+    if !dir.exists() {
+        return Ok(rules);
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            match load_library_rule(&path) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => {
+                    tracing::warn!("Failed to load library rule from {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    rules.sort_by_key(|rule| rule.name.to_lowercase());
+    Ok(rules)
+}
+
+/// Delete a library rule file
+pub fn delete_library_rule(dir: &Path, rule_id: &str) -> Result<()> {
+    let path = dir.join(format!("{}.json", rule_id));
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stretch_rule() -> LibraryRule {
+        LibraryRule {
+            id: "stretch".to_string(),
+            name: "Stretch reminder".to_string(),
+            description: String::new(),
+            script: "remind(message, minutes);".to_string(),
+            parameters: vec![
+                RuleParameter {
+                    name: "minutes".to_string(),
+                    default: "60".to_string(),
+                    description: String::new(),
+                },
+                RuleParameter {
+                    name: "message".to_string(),
+                    default: "Time to stretch".to_string(),
+                    description: String::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_resolve_with_overrides() {
+        let mut reference = RuleReference::new("stretch");
+        reference
+            .overrides
+            .insert("minutes".to_string(), "45".to_string());
+        reference
+            .overrides
+            .insert("gone".to_string(), "1".to_string());
+        reference.phases = vec!["late".to_string()];
+        reference.confidence_floor = Some(ConfidenceFloor::default());
+
+        let rule = stretch_rule().resolve(&reference);
+        assert_eq!(
+            rule.script,
+            "const minutes = 45;\nconst message = \"Time to stretch\";\nremind(message, minutes);"
+        );
+        assert!(rule.enabled);
+        assert_eq!(rule.phases, ["late"]);
+        assert_eq!(rule.confidence_floor, reference.confidence_floor);
+    }
+
+    #[test]
+    fn test_script_literals() {
+        assert_eq!(script_literal(" 1.5 "), "1.5");
+        assert_eq!(script_literal("false"), "false");
+        assert_eq!(script_literal("inf"), "\"inf\"");
+        assert_eq!(script_literal("Say \"hi\""), "\"Say \\\"hi\\\"\"");
+        assert_eq!(parameter_name(" max hp%"), "max_hp_");
+        assert_eq!(parameter_name("2nd"), "_2nd");
+    }
+}
@@ -0,0 +1,188 @@
+//! Per-profile sample frame library
+//!
+//! Representative frames (day and night, other resolutions, menus) are saved
+//! as `samples/<profile id>/<name>.png` so zones and screens can be checked
+//! against them in the Vision view without reproducing the situation in-game.
+//! Next to each image, `<name>.json` keeps what the sample is expected to read
+//! as, for profile validation.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::capture::CapturedFrame;
+
+/// A saved sample frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleFrame {
+    /// Display name (the file name without extension)
+    pub name: String,
+    /// PNG file
+    pub path: PathBuf,
+}
+
+impl SampleFrame {
+    /// Load the sample's pixels
+    pub fn load(&self) -> Result<CapturedFrame> {
+        let image = image::open(&self.path)
+            .with_context(|| format!("Failed to read sample {:?}", self.path))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(CapturedFrame::new(image.into_raw(), width, height))
+    }
+
+    /// File holding the sample's expected results
+    fn expectation_path(&self) -> PathBuf {
+        self.path.with_extension("json")
+    }
+
+    /// Expected results, if the sample has been validated before
+    pub fn load_expectation(&self) -> Option<SampleExpectation> {
+        let json = std::fs::read_to_string(self.expectation_path()).ok()?;
+        serde_json::from_str(&json)
+            .map_err(|e| tracing::warn!("Ignoring expectation of sample '{}': {}", self.name, e))
+            .ok()
+    }
+
+    /// Store the sample's expected results
+    pub fn save_expectation(&self, expectation: &SampleExpectation) -> Result<()> {
+        let path = self.expectation_path();
+        let json = serde_json::to_string_pretty(expectation)?;
+        std::fs::write(&path, json).with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+/// What a sample read as when its results were last accepted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SampleExpectation {
+    /// Screen recognized in the sample (`None` = no screen)
+    #[serde(default)]
+    pub screen_id: Option<String>,
+    /// Zone text by zone ID
+    #[serde(default)]
+    pub zones: BTreeMap<String, String>,
+}
+
+/// File-name-safe version of a sample name
+fn file_stem(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '(' | ')') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Save a frame to a profile's library under the given name
+pub fn save_sample(
+    samples_dir: &Path,
+    profile_id: &str,
+    name: &str,
+    frame: &CapturedFrame,
+) -> Result<SampleFrame> {
+    let stem = file_stem(name);
+    if stem.is_empty() {
+        anyhow::bail!("Sample name is empty");
+    }
+
+    let dir = samples_dir.join(profile_id);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.png", stem));
+    if path.exists() {
+        anyhow::bail!("A sample named '{}' already exists", stem);
+    }
+
+    frame
+        .to_rgba_image()
+        .ok_or_else(|| anyhow::anyhow!("Frame data does not match its size"))?
+        .save(&path)
+        .with_context(|| format!("Failed to save sample {:?}", path))?;
+
+    Ok(SampleFrame { name: stem, path })
+}
+
+/// A profile's samples, sorted by name
+pub fn list_samples(samples_dir: &Path, profile_id: &str) -> Vec<SampleFrame> {
+    let Ok(entries) = std::fs::read_dir(samples_dir.join(profile_id)) else {
+        return Vec::new();
+    };
+
+    let mut samples: Vec<SampleFrame> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "png" {
+                return None;
+            }
+            Some(SampleFrame {
+                name: path.file_stem()?.to_str()?.to_string(),
+                path,
+            })
+        })
+        .collect();
+    samples.sort_by_key(|sample| sample.name.to_lowercase());
+    samples
+}
+
+/// Remove a sample (and its expected results) from the library
+pub fn delete_sample(sample: &SampleFrame) -> Result<()> {
+    std::fs::remove_file(&sample.path)
+        .with_context(|| format!("Failed to delete sample {:?}", sample.path))?;
+
+    let expectation = sample.expectation_path();
+    if expectation.exists() {
+        std::fs::remove_file(&expectation)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn frame() -> CapturedFrame {
+        CapturedFrame::new(vec![255; 4 * 4 * 3], 4, 3)
+    }
+
+    #[test]
+    fn test_save_list_and_delete() {
+        let dir = TempDir::new().unwrap();
+        save_sample(dir.path(), "game", "night", &frame()).unwrap();
+        save_sample(dir.path(), "game", "Main menu 1440p", &frame()).unwrap();
+        assert!(save_sample(dir.path(), "game", "night", &frame()).is_err());
+
+        let samples = list_samples(dir.path(), "game");
+        let names: Vec<_> = samples.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Main menu 1440p", "night"]);
+        assert!(list_samples(dir.path(), "other").is_empty());
+
+        let loaded = samples[1].load().unwrap();
+        assert_eq!(loaded.dimensions(), (4, 3));
+
+        // Expectations live next to the image and are not listed as samples
+        let expectation = SampleExpectation {
+            screen_id: Some("hud".to_string()),
+            zones: [("health".to_string(), "100".to_string())].into(),
+        };
+        assert_eq!(samples[1].load_expectation(), None);
+        samples[1].save_expectation(&expectation).unwrap();
+        assert_eq!(samples[1].load_expectation(), Some(expectation));
+        assert_eq!(list_samples(dir.path(), "game").len(), 2);
+
+        delete_sample(&samples[1]).unwrap();
+        assert_eq!(list_samples(dir.path(), "game").len(), 1);
+        assert!(!samples[1].expectation_path().exists());
+    }
+
+    #[test]
+    fn test_names_are_file_safe() {
+        assert_eq!(file_stem(" Boss: phase 2/3 "), "Boss_ phase 2_3");
+        assert_eq!(file_stem("   "), "");
+    }
+}
@@ -0,0 +1,108 @@
+//! Screenshots
+//!
+//! Frames saved on demand (from a hotkey or the dashboard) as PNG files in
+//! the screenshots folder, named by when they were taken unless a path is
+//! given.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::capture::CapturedFrame;
+
+/// Screenshots listed on the Home view
+pub const RECENT_SCREENSHOTS: usize = 5;
+
+/// Where a screenshot is saved: `path` as given (relative to `dir`, `.png`
+/// added when it has no extension), or a new timestamped file in `dir`
+fn screenshot_path(dir: &Path, path: Option<&Path>) -> PathBuf {
+    if let Some(path) = path {
+        let path = dir.join(path);
+        return if path.extension().is_some() {
+            path
+        } else {
+            path.with_extension("png")
+        };
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut path = dir.join(format!("screenshot-{}.png", timestamp));
+    let mut index = 1;
+    while path.exists() {
+        index += 1;
+        path = dir.join(format!("screenshot-{}-{}.png", timestamp, index));
+    }
+    path
+}
+
+/// Save a frame as a PNG screenshot, returning the file
+pub fn save_screenshot(dir: &Path, frame: &CapturedFrame, path: Option<&Path>) -> Result<PathBuf> {
+    let path = screenshot_path(dir, path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    frame
+        .to_rgba_image()
+        .ok_or_else(|| anyhow::anyhow!("Frame data does not match its size"))?
+        .save_with_format(&path, image::ImageFormat::Png)
+        .with_context(|| format!("Failed to save screenshot {:?}", path))?;
+    Ok(path)
+}
+
+/// The newest screenshots in `dir`, newest first
+pub fn list_screenshots(dir: &Path, limit: usize) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut screenshots: Vec<(SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "png" {
+                return None;
+            }
+            Some((entry.metadata().ok()?.modified().ok()?, path))
+        })
+        .collect();
+    screenshots.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    screenshots
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn frame() -> CapturedFrame {
+        CapturedFrame::new(vec![255; 4 * 4 * 3], 4, 3)
+    }
+
+    #[test]
+    fn test_save_and_list() {
+        let dir = TempDir::new().unwrap();
+        let first = save_screenshot(dir.path(), &frame(), None).unwrap();
+        let second = save_screenshot(dir.path(), &frame(), None).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.extension().unwrap(), "png");
+
+        // A given path is kept, relative to the folder
+        let named = save_screenshot(dir.path(), &frame(), Some(Path::new("boss/phase 2"))).unwrap();
+        assert_eq!(named, dir.path().join("boss").join("phase 2.png"));
+        assert_eq!(image::open(&named).unwrap().width(), 4);
+
+        let listed = list_screenshots(dir.path(), 5);
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&first) && listed.contains(&second));
+        assert_eq!(list_screenshots(dir.path(), 1).len(), 1);
+        assert!(list_screenshots(&dir.path().join("missing"), 5).is_empty());
+    }
+}
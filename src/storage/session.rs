@@ -0,0 +1,109 @@
+//! Session tracking for crash detection
+//!
+//! A lock file is written when the app starts and removed on clean shutdown.
+//! If it is still there on the next start, the previous session crashed.
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::warn;
+
+/// Name of the session lock file in the data directory
+const SESSION_FILE: &str = "session.lock";
+
+/// Consecutive crashes after which safe mode is entered automatically
+pub const SAFE_MODE_CRASH_THRESHOLD: u32 = 2;
+
+/// Mark the start of a session
+///
+/// Returns the number of consecutive sessions that ended without a clean shutdown.
+pub fn begin_session(data_dir: &Path) -> Result<u32> {
+    let path = data_dir.join(SESSION_FILE);
+
+    let crashes = if path.exists() {
+        let previous = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| content.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+        previous + 1
+    } else {
+        0
+    };
+
+    std::fs::write(&path, crashes.to_string())?;
+    Ok(crashes)
+}
+
+/// Mark a clean shutdown, resetting the crash count
+pub fn end_session(data_dir: &Path) -> Result<()> {
+    let path = data_dir.join(SESSION_FILE);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Run the rest of a session, then mark a clean shutdown
+///
+/// Returning an error still ends the session cleanly; only a panic, which
+/// unwinds past this, or an abort leaves it counted as a crash.
+pub fn finish_session<T>(data_dir: Option<&Path>, run: impl FnOnce() -> Result<T>) -> Result<T> {
+    let result = run();
+    if let Some(dir) = data_dir {
+        if let Err(e) = end_session(dir) {
+            warn!("Failed to record clean shutdown: {}", e);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clean_sessions_have_no_crashes() {
+        let dir = TempDir::new().unwrap();
+
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+        end_session(dir.path()).unwrap();
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_consecutive_crashes_are_counted() {
+        let dir = TempDir::new().unwrap();
+
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+        assert_eq!(begin_session(dir.path()).unwrap(), 1);
+        assert_eq!(begin_session(dir.path()).unwrap(), 2);
+
+        end_session(dir.path()).unwrap();
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_errors_are_not_crashes() {
+        let dir = TempDir::new().unwrap();
+
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+        let result: Result<()> =
+            finish_session(Some(dir.path()), || anyhow::bail!("Window creation failed"));
+        assert!(result.is_err());
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_panics_are_crashes() {
+        let dir = TempDir::new().unwrap();
+
+        assert_eq!(begin_session(dir.path()).unwrap(), 0);
+        let result = std::panic::catch_unwind(|| {
+            finish_session(Some(dir.path()), || -> Result<()> {
+                panic!("Dashboard panicked")
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(begin_session(dir.path()).unwrap(), 1);
+    }
+}
@@ -0,0 +1,233 @@
+//! Share codes for individual zones, screens, rules and library rules
+//!
+//! A share code is a single pasteable string (`GTK1.<base64 JSON>`) so users can
+//! exchange one piece of a profile without sending files around.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::profiles::{GameProfile, OcrRegion, RuleDefinition, ScreenDefinition};
+use super::rule_library::{LibraryRule, RuleReference};
+
+/// Prefix identifying the share code format version
+const SHARE_CODE_PREFIX: &str = "GTK1.";
+
+/// A piece of a profile that can be shared on its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum SharedItem {
+    /// An OCR zone
+    Zone(OcrRegion),
+    /// A screen definition
+    Screen(ScreenDefinition),
+    /// A rule
+    Rule(RuleDefinition),
+    /// A rule from the rules library, with its parameters
+    LibraryRule(LibraryRule),
+}
+
+impl SharedItem {
+    /// Kind of item for display
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            SharedItem::Zone(_) => "Zone",
+            SharedItem::Screen(_) => "Screen",
+            SharedItem::Rule(_) => "Rule",
+            SharedItem::LibraryRule(_) => "Library rule",
+        }
+    }
+
+    /// Display name of the item
+    pub fn name(&self) -> &str {
+        match self {
+            SharedItem::Zone(zone) if !zone.name.is_empty() => &zone.name,
+            SharedItem::Zone(zone) => &zone.id,
+            SharedItem::Screen(screen) => &screen.name,
+            SharedItem::Rule(rule) => &rule.name,
+            SharedItem::LibraryRule(rule) => &rule.name,
+        }
+    }
+
+    /// Encode as a share code
+    pub fn to_share_code(&self) -> Result<String> {
+        let json = serde_json::to_vec(self)?;
+        Ok(format!(
+            "{}{}",
+            SHARE_CODE_PREFIX,
+            URL_SAFE_NO_PAD.encode(json)
+        ))
+    }
+
+    /// Decode a share code (surrounding whitespace and quotes are ignored)
+    pub fn from_share_code(code: &str) -> Result<Self> {
+        let code = code.trim().trim_matches(|c| c == '`' || c == '"');
+        let Some(payload) = code.strip_prefix(SHARE_CODE_PREFIX) else {
+            bail!("Not a GamersToolKit share code (should start with {SHARE_CODE_PREFIX})");
+        };
+
+        let json = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|e| anyhow!("Share code is damaged or incomplete: {}", e))?;
+        serde_json::from_slice(&json).context("Share code contains an unsupported item")
+    }
+
+    /// Add the item to a profile, renaming its ID if it clashes with an existing one
+    ///
+    /// A library rule is attached by reference (once); the rule itself
+    /// belongs in the library. Returns the ID the item was added under.
+    pub fn add_to(self, profile: &mut GameProfile) -> String {
+        match self {
+            SharedItem::Zone(mut zone) => {
+                let existing: Vec<&str> =
+                    profile.ocr_regions.iter().map(|z| z.id.as_str()).collect();
+                zone.id = unique_id(&zone.id, &existing);
+                let id = zone.id.clone();
+                profile.ocr_regions.push(zone);
+                id
+            }
+            SharedItem::Screen(mut screen) => {
+                let existing: Vec<&str> = profile.screens.iter().map(|s| s.id.as_str()).collect();
+                screen.id = unique_id(&screen.id, &existing);
+                // A parent from the source profile may not exist here
+                if screen
+                    .parent_id
+                    .as_ref()
+                    .is_some_and(|parent| !existing.contains(&parent.as_str()))
+                {
+                    screen.parent_id = None;
+                }
+                let id = screen.id.clone();
+                profile.screens.push(screen);
+                id
+            }
+            SharedItem::Rule(mut rule) => {
+                let existing: Vec<&str> = profile.rules.iter().map(|r| r.id.as_str()).collect();
+                rule.id = unique_id(&rule.id, &existing);
+                let id = rule.id.clone();
+                profile.rules.push(rule);
+                id
+            }
+            SharedItem::LibraryRule(rule) => {
+                if !profile.library_rules.iter().any(|r| r.rule_id == rule.id) {
+                    profile
+                        .library_rules
+                        .push(RuleReference::new(rule.id.clone()));
+                }
+                rule.id
+            }
+        }
+    }
+}
+
+/// Make an ID unique among existing ones by appending a number
+pub fn unique_id(id: &str, existing: &[&str]) -> String {
+    if !existing.contains(&id) {
+        return id.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", id, n))
+        .find(|candidate| !existing.contains(&candidate.as_str()))
+        .unwrap_or_else(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::ContentType;
+
+    fn test_zone(id: &str) -> OcrRegion {
+        OcrRegion {
+            id: id.to_string(),
+            name: "Gold".to_string(),
+            bounds: (0.1, 0.2, 0.3, 0.4),
+            content_type: ContentType::Number,
+            enabled: true,
+            preprocessing: None,
+            ensemble: false,
+            allowed_chars: None,
+            geometry: Default::default(),
+            scrolling: false,
+            value_epsilon: 0.0,
+            source: None,
+            phases: vec![],
+        }
+    }
+
+    fn empty_profile() -> GameProfile {
+        GameProfile {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            executables: vec![],
+            version: "1.0.0".to_string(),
+            ocr_regions: vec![],
+            templates: vec![],
+            rules: vec![],
+            library_rules: vec![],
+            labeled_regions: vec![],
+            screens: vec![],
+            screen_recognition_enabled: false,
+            screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
+        }
+    }
+
+    #[test]
+    fn test_share_code_roundtrip() {
+        let code = SharedItem::Zone(test_zone("gold")).to_share_code().unwrap();
+        assert!(code.starts_with(SHARE_CODE_PREFIX));
+        assert!(!code.contains(char::is_whitespace));
+
+        let SharedItem::Zone(zone) =
+            SharedItem::from_share_code(&format!("  `{}`\n", code)).unwrap()
+        else {
+            panic!("expected a zone");
+        };
+        assert_eq!(zone.id, "gold");
+        assert_eq!(zone.content_type, ContentType::Number);
+    }
+
+    #[test]
+    fn test_invalid_share_codes() {
+        assert!(SharedItem::from_share_code("hello").is_err());
+        assert!(SharedItem::from_share_code("GTK1.!!!").is_err());
+        assert!(SharedItem::from_share_code("GTK1.e30").is_err()); // "{}"
+    }
+
+    #[test]
+    fn test_add_to_renames_clashing_ids() {
+        let mut profile = empty_profile();
+        SharedItem::Zone(test_zone("gold")).add_to(&mut profile);
+        let id = SharedItem::Zone(test_zone("gold")).add_to(&mut profile);
+
+        assert_eq!(id, "gold_2");
+        assert_eq!(profile.ocr_regions.len(), 2);
+    }
+
+    #[test]
+    fn test_library_rule_attached_once() {
+        let rule = LibraryRule {
+            id: "stretch".to_string(),
+            name: "Stretch reminder".to_string(),
+            description: String::new(),
+            script: String::new(),
+            parameters: vec![],
+        };
+        let mut profile = empty_profile();
+        SharedItem::LibraryRule(rule.clone()).add_to(&mut profile);
+        let id = SharedItem::LibraryRule(rule).add_to(&mut profile);
+
+        assert_eq!(id, "stretch");
+        assert_eq!(profile.library_rules, [RuleReference::new("stretch")]);
+        assert!(profile.rules.is_empty());
+    }
+}
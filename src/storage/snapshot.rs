@@ -0,0 +1,332 @@
+//! App state snapshots
+//!
+//! A snapshot is a single JSON file holding the config, the active profile,
+//! its most recent sample frames and the last pipeline events, so a bug report
+//! can be replayed on another machine with `--load-snapshot <file>`. Loading
+//! restores it into a throwaway data directory and leaves the user's own data
+//! untouched.
+
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::database::{Database, RecordedEvent};
+use super::profiles::{self, GameProfile};
+use super::samples::{self, SampleExpectation};
+use crate::config::{self, AppConfig};
+
+/// Snapshot format version
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Most recent sample frames included
+pub const SNAPSHOT_SAMPLES: usize = 10;
+
+/// Most recent events included
+pub const SNAPSHOT_EVENTS: usize = 500;
+
+/// A sample frame in a snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotSample {
+    /// Sample name
+    pub name: String,
+    /// PNG file contents, base64
+    pub png: String,
+    /// Expected results, if the sample was validated
+    #[serde(default)]
+    pub expectation: Option<SampleExpectation>,
+}
+
+/// An event in a snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEvent {
+    /// When it happened (UTC, `YYYY-MM-DD HH:MM:SS`)
+    pub timestamp: String,
+    /// Kind of event
+    pub kind: String,
+    /// What happened
+    pub message: String,
+}
+
+impl From<RecordedEvent> for SnapshotEvent {
+    fn from(event: RecordedEvent) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            kind: event.kind,
+            message: event.message,
+        }
+    }
+}
+
+/// Everything needed to reproduce the app's state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Snapshot format version
+    pub version: u32,
+    /// App version that exported it
+    pub app_version: String,
+    /// When it was exported (Unix seconds)
+    pub created_at: u64,
+    /// Settings
+    pub config: AppConfig,
+    /// Active profile
+    #[serde(default)]
+    pub profile: Option<GameProfile>,
+    /// The active profile's most recent sample frames
+    #[serde(default)]
+    pub samples: Vec<SnapshotSample>,
+    /// Last pipeline events, oldest first
+    #[serde(default)]
+    pub events: Vec<SnapshotEvent>,
+}
+
+impl Snapshot {
+    /// Collect a snapshot of the config, the profile's newest samples and
+    /// the given events
+    pub fn capture(
+        config: &AppConfig,
+        profile: Option<&GameProfile>,
+        samples_dir: &Path,
+        events: Vec<RecordedEvent>,
+    ) -> Result<Self> {
+        let samples = match profile {
+            Some(profile) => recent_samples(samples_dir, &profile.id)?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            version: SNAPSHOT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            config: config.clone(),
+            profile: profile.cloned(),
+            samples,
+            events: events.into_iter().map(SnapshotEvent::from).collect(),
+        })
+    }
+
+    /// Write the snapshot to a file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    /// Read a snapshot file
+    pub fn load(path: &Path) -> Result<Self> {
+        let json =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+        let snapshot: Self = serde_json::from_str(&json)
+            .with_context(|| format!("{:?} is not a GamersToolKit snapshot", path))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            bail!(
+                "Snapshot was made by a newer version ({}), update to load it",
+                snapshot.app_version
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Recreate the snapshot's state under `dir` (`config/` and `data/`, the
+    /// layout `set_dir_override` expects)
+    ///
+    /// Telemetry is switched off so a replayed report doesn't send anything.
+    pub fn restore(&self, dir: &Path) -> Result<()> {
+        let config_dir = dir.join("config");
+        let data_dir = dir.join("data");
+        std::fs::create_dir_all(&config_dir)?;
+        std::fs::create_dir_all(data_dir.join("profiles"))?;
+
+        let mut config = self.config.clone();
+        config.telemetry.enabled = false;
+        config::save_config(&config, &config_dir.join("config.toml"))?;
+
+        if let Some(ref profile) = self.profile {
+            check_file_name(&profile.id)?;
+            let path = data_dir
+                .join("profiles")
+                .join(format!("{}.json", profile.id));
+            profiles::save_profile(profile, &path)?;
+
+            let samples_dir = data_dir.join("samples").join(&profile.id);
+            std::fs::create_dir_all(&samples_dir)?;
+            for sample in &self.samples {
+                check_file_name(&sample.name)?;
+                let png = STANDARD
+                    .decode(&sample.png)
+                    .with_context(|| format!("Sample '{}' is damaged", sample.name))?;
+                let path = samples_dir.join(format!("{}.png", sample.name));
+                std::fs::write(&path, png)?;
+                if let Some(ref expectation) = sample.expectation {
+                    std::fs::write(
+                        path.with_extension("json"),
+                        serde_json::to_string_pretty(expectation)?,
+                    )?;
+                }
+            }
+        }
+
+        let db = Database::open(&data_dir.join("gamerstoolkit.db"))?;
+        let session_id = db.start_session("snapshot")?;
+        for event in &self.events {
+            db.record_event_at(session_id, &event.timestamp, &event.kind, &event.message)?;
+        }
+        db.end_session(session_id)?;
+
+        Ok(())
+    }
+}
+
+/// Refuse names from a snapshot file that would write outside the sandbox
+fn check_file_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', ':']) {
+        bail!("Snapshot contains an invalid file name '{}'", name);
+    }
+    Ok(())
+}
+
+/// A profile's newest samples, newest first
+fn recent_samples(samples_dir: &Path, profile_id: &str) -> Result<Vec<SnapshotSample>> {
+    let mut samples: Vec<_> = samples::list_samples(samples_dir, profile_id)
+        .into_iter()
+        .map(|sample| {
+            let modified = std::fs::metadata(&sample.path)
+                .and_then(|m| m.modified())
+                .unwrap_or(UNIX_EPOCH);
+            (modified, sample)
+        })
+        .collect();
+    samples.sort_by(|a, b| b.0.cmp(&a.0));
+
+    samples
+        .into_iter()
+        .take(SNAPSHOT_SAMPLES)
+        .map(|(_, sample)| {
+            let png = std::fs::read(&sample.path)
+                .with_context(|| format!("Failed to read sample {:?}", sample.path))?;
+            Ok(SnapshotSample {
+                expectation: sample.load_expectation(),
+                name: sample.name,
+                png: STANDARD.encode(png),
+            })
+        })
+        .collect()
+}
+
+/// Path for a new snapshot file in `dir`
+pub fn snapshot_path(dir: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut path = dir.join(format!("snapshot-{}.json", secs));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("snapshot-{}-{}.json", secs, n));
+    }
+    path
+}
+
+/// Restore a snapshot file into a fresh temporary directory and point the
+/// data and config directories there for the rest of the run
+pub fn load_into_sandbox(path: &Path) -> Result<PathBuf> {
+    let snapshot = Snapshot::load(path)?;
+    let dir = std::env::temp_dir().join(format!("gamerstoolkit-snapshot-{}", snapshot.created_at));
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to clear old sandbox {:?}", dir))?;
+    }
+    snapshot.restore(&dir)?;
+    super::set_dir_override(dir.clone())?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::CapturedFrame;
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let samples_dir = dir.path().join("samples");
+
+        let profile = GameProfile {
+            id: "test_game".to_string(),
+            name: "Test Game".to_string(),
+            executables: vec![],
+            version: "1.0.0".to_string(),
+            ocr_regions: vec![],
+            templates: vec![],
+            rules: vec![],
+            library_rules: vec![],
+            labeled_regions: vec![],
+            screens: vec![],
+            screen_recognition_enabled: false,
+            screen_check_interval_ms: 500,
+            dictionary: Default::default(),
+            color_triggers: vec![],
+            variables: vec![],
+            checklists: vec![],
+            confidence_calibration: vec![],
+            auto_start: Default::default(),
+            snapshot_capture: Default::default(),
+            secondary_windows: vec![],
+            phases: vec![],
+        };
+        let frame = CapturedFrame::new(vec![255; 2 * 2 * 4], 2, 2);
+        let sample = samples::save_sample(&samples_dir, &profile.id, "menu", &frame).unwrap();
+        let expectation = SampleExpectation {
+            screen_id: Some("menu".to_string()),
+            ..Default::default()
+        };
+        sample.save_expectation(&expectation).unwrap();
+
+        let mut config = AppConfig::default();
+        config.dashboard.active_profile_id = Some(profile.id.clone());
+        config.telemetry.enabled = true;
+        let events = vec![RecordedEvent {
+            timestamp: "2026-01-02 03:04:05".to_string(),
+            kind: "capture".to_string(),
+            message: "Capture started".to_string(),
+        }];
+
+        let snapshot = Snapshot::capture(&config, Some(&profile), &samples_dir, events).unwrap();
+        let path = dir.path().join("snapshot.json");
+        snapshot.save(&path).unwrap();
+        let loaded = Snapshot::load(&path).unwrap();
+        assert_eq!(loaded.samples.len(), 1);
+        assert_eq!(loaded.samples[0].expectation, Some(expectation.clone()));
+
+        let sandbox = dir.path().join("sandbox");
+        loaded.restore(&sandbox).unwrap();
+
+        let restored = config::load_config(&sandbox.join("config/config.toml")).unwrap();
+        assert_eq!(
+            restored.dashboard.active_profile_id.as_deref(),
+            Some("test_game")
+        );
+        assert!(!restored.telemetry.enabled);
+
+        let data = sandbox.join("data");
+        let restored_profile =
+            profiles::load_profile(&data.join("profiles/test_game.json")).unwrap();
+        assert_eq!(restored_profile.name, "Test Game");
+
+        let restored_samples = samples::list_samples(&data.join("samples"), "test_game");
+        assert_eq!(restored_samples.len(), 1);
+        assert_eq!(restored_samples[0].load_expectation(), Some(expectation));
+        assert_eq!(restored_samples[0].load().unwrap().width, 2);
+
+        let db = Database::open(&data.join("gamerstoolkit.db")).unwrap();
+        let events = db.recent_events(10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].timestamp, "2026-01-02 03:04:05");
+        assert_eq!(events[0].message, "Capture started");
+    }
+}
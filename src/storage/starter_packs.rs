@@ -0,0 +1,102 @@
+//! Curated starter profiles embedded in the binary
+//!
+//! Each pack is a regular profile JSON (see `profiles/starter/`) with screens,
+//! common zones and a few example rules, so new users have something to start
+//! from instead of an empty profile list.
+
+use anyhow::{Context, Result};
+
+use super::profiles::GameProfile;
+
+/// A profile bundled with the application
+#[derive(Debug, Clone, Copy)]
+pub struct StarterPack {
+    /// Profile ID of the pack
+    pub id: &'static str,
+    /// Game name
+    pub name: &'static str,
+    /// Short description shown in the dashboard
+    pub description: &'static str,
+    /// Profile JSON
+    json: &'static str,
+}
+
+impl StarterPack {
+    /// Parse the embedded profile
+    pub fn profile(&self) -> Result<GameProfile> {
+        serde_json::from_str(self.json)
+            .with_context(|| format!("Invalid starter pack '{}'", self.id))
+    }
+}
+
+/// All bundled starter packs
+pub const STARTER_PACKS: &[StarterPack] = &[
+    StarterPack {
+        id: "starter-stfc",
+        name: "Star Trek Fleet Command",
+        description: "Resource counters, cargo tracking, station and system screens",
+        json: include_str!("../../profiles/starter/star-trek-fleet-command.json"),
+    },
+    StarterPack {
+        id: "starter-lol",
+        name: "League of Legends",
+        description: "Gold, game timer and CS zones with recall and dragon reminders",
+        json: include_str!("../../profiles/starter/league-of-legends.json"),
+    },
+    StarterPack {
+        id: "starter-diablo4",
+        name: "Diablo IV",
+        description: "Gold and potion zones, inventory and map screens",
+        json: include_str!("../../profiles/starter/diablo-iv.json"),
+    },
+    StarterPack {
+        id: "starter-poe",
+        name: "Path of Exile",
+        description: "Life and area name zones, stash and passive tree screens",
+        json: include_str!("../../profiles/starter/path-of-exile.json"),
+    },
+];
+
+/// Find a starter pack by ID
+pub fn find_starter_pack(id: &str) -> Option<&'static StarterPack> {
+    STARTER_PACKS.iter().find(|pack| pack.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_packs_parse_with_matching_ids() {
+        for pack in STARTER_PACKS {
+            let profile = pack.profile().unwrap();
+            assert_eq!(profile.id, pack.id);
+            assert!(!profile.ocr_regions.is_empty(), "{} has no zones", pack.id);
+            assert!(!profile.screens.is_empty(), "{} has no screens", pack.id);
+        }
+    }
+
+    #[test]
+    fn test_pack_references_are_valid() {
+        for pack in STARTER_PACKS {
+            let profile = pack.profile().unwrap();
+            let zone_ids: Vec<_> = profile.ocr_regions.iter().map(|z| &z.id).collect();
+            let rule_ids: Vec<_> = profile.rules.iter().map(|r| &r.id).collect();
+
+            for screen in &profile.screens {
+                for zone_override in &screen.ocr_zone_overrides {
+                    assert!(zone_ids.contains(&&zone_override.zone_id));
+                }
+                for rule_id in &screen.rules_to_trigger {
+                    assert!(rule_ids.contains(&rule_id));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_starter_pack() {
+        assert!(find_starter_pack("starter-lol").is_some());
+        assert!(find_starter_pack("missing").is_none());
+    }
+}
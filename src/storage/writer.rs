@@ -0,0 +1,289 @@
+//! Background writer for config and profile saves
+//!
+//! Serializing a profile with embedded template PNGs and writing it to disk
+//! can take long enough to stutter the dashboard, so saves are queued to a
+//! thread instead. Saves of the same file that queue up while another is
+//! being written are coalesced: only the newest one is written, with the
+//! sections of a partial save folded into a whole one queued before it.
+
+use anyhow::Result;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+use super::profiles::{self, GameProfile, ProfileSections};
+use crate::config::{self, AppConfig};
+
+/// What a save writes
+#[derive(Debug, Clone)]
+pub enum SaveJob {
+    /// The app config
+    Config(Box<AppConfig>),
+    /// A game profile
+    Profile(Box<GameProfile>),
+    /// Changed sections of a game profile saved before
+    ProfileSections(Box<ProfileSections>),
+}
+
+impl SaveJob {
+    fn write(&self, path: &std::path::Path) -> Result<()> {
+        match self {
+            SaveJob::Config(config) => config::save_config(config, path),
+            SaveJob::Profile(profile) => profiles::save_profile(profile, path),
+            SaveJob::ProfileSections(sections) => {
+                profiles::save_profile_sections(sections, path).map(|_| ())
+            }
+        }
+    }
+
+    /// Replace this queued save by a newer one of the same file
+    fn merge(&mut self, newer: SaveJob) {
+        match (self, newer) {
+            (SaveJob::Profile(profile), SaveJob::ProfileSections(sections)) => {
+                sections.apply(profile);
+                profile.name = sections.name.clone();
+            }
+            (SaveJob::ProfileSections(queued), SaveJob::ProfileSections(sections)) => {
+                queued.merge(*sections);
+            }
+            (queued, newer) => *queued = newer,
+        }
+    }
+
+    fn target(&self) -> SaveTarget {
+        match self {
+            SaveJob::Config(_) => SaveTarget::Config,
+            SaveJob::Profile(profile) => SaveTarget::Profile {
+                id: profile.id.clone(),
+                name: profile.name.clone(),
+            },
+            SaveJob::ProfileSections(sections) => SaveTarget::Profile {
+                id: sections.id.clone(),
+                name: sections.name.clone(),
+            },
+        }
+    }
+}
+
+/// What a finished save wrote
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveTarget {
+    /// The app config
+    Config,
+    /// A game profile
+    Profile { id: String, name: String },
+}
+
+/// Outcome of a save
+#[derive(Debug, Clone)]
+pub struct SaveResult {
+    /// File written
+    pub path: PathBuf,
+    /// What was in it
+    pub target: SaveTarget,
+    /// Why the write failed (None = saved)
+    pub error: Option<String>,
+}
+
+enum Message {
+    Save(PathBuf, SaveJob),
+    /// Answered once everything queued before it is written
+    Flush(Sender<()>),
+}
+
+/// Thread writing queued saves in order
+pub struct StorageWriter {
+    sender: Option<Sender<Message>>,
+    results: Receiver<SaveResult>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StorageWriter {
+    /// Start the writer thread
+    pub fn spawn() -> Self {
+        let (sender, messages) = unbounded();
+        let (result_sender, results) = unbounded();
+        let thread = std::thread::Builder::new()
+            .name("storage-writer".to_string())
+            .spawn(move || run(messages, result_sender))
+            .map_err(|e| tracing::error!("Failed to start storage writer: {}", e))
+            .ok();
+        Self {
+            sender: Some(sender),
+            results,
+            thread,
+        }
+    }
+
+    /// Queue a save of the config
+    pub fn save_config(&self, config: &AppConfig, path: PathBuf) {
+        self.queue(path, SaveJob::Config(Box::new(config.clone())));
+    }
+
+    /// Queue a save of a profile
+    pub fn save_profile(&self, profile: &GameProfile, path: PathBuf) {
+        self.queue(path, SaveJob::Profile(Box::new(profile.clone())));
+    }
+
+    /// Queue a save of some sections of a profile saved before
+    pub fn save_profile_sections(&self, sections: ProfileSections, path: PathBuf) {
+        self.queue(path, SaveJob::ProfileSections(Box::new(sections)));
+    }
+
+    fn queue(&self, path: PathBuf, job: SaveJob) {
+        if self.thread.is_none() {
+            // No writer thread: write right away rather than lose the save
+            if let Err(e) = job.write(&path) {
+                tracing::error!("Failed to save {:?}: {}", path, e);
+            }
+            return;
+        }
+        if let Some(ref sender) = self.sender {
+            let _ = sender.send(Message::Save(path, job));
+        }
+    }
+
+    /// A save finished since the last call, if any
+    pub fn try_result(&self) -> Option<SaveResult> {
+        self.results.try_recv().ok()
+    }
+
+    /// Wait until every queued save is written (before writing the same
+    /// files directly, and on exit)
+    pub fn flush(&self) {
+        let (Some(sender), Some(_)) = (&self.sender, &self.thread) else {
+            return;
+        };
+        let (done, wait) = crossbeam_channel::bounded(1);
+        if sender.send(Message::Flush(done)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+}
+
+impl Drop for StorageWriter {
+    fn drop(&mut self) {
+        // Closing the channel lets the thread finish what's queued and stop
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Write saves as they come, newest only for saves of the same file that
+/// queued up meanwhile
+fn run(messages: Receiver<Message>, results: Sender<SaveResult>) {
+    while let Ok(first) = messages.recv() {
+        let mut pending = Vec::new();
+        let mut flushes = Vec::new();
+        for message in std::iter::once(first).chain(messages.try_iter()) {
+            match message {
+                Message::Save(path, job) => coalesce(&mut pending, path, job),
+                Message::Flush(done) => flushes.push(done),
+            }
+        }
+
+        for (path, job) in pending {
+            let error = job.write(&path).err().map(|e| format!("{:#}", e));
+            if let Some(ref error) = error {
+                tracing::warn!("Failed to save {:?}: {}", path, error);
+            }
+            let _ = results.send(SaveResult {
+                target: job.target(),
+                path,
+                error,
+            });
+        }
+        for done in flushes {
+            let _ = done.send(());
+        }
+    }
+}
+
+/// Queue a save, merged into a queued save of the same file
+fn coalesce(pending: &mut Vec<(PathBuf, SaveJob)>, path: PathBuf, job: SaveJob) {
+    match pending.iter_mut().find(|(queued, _)| *queued == path) {
+        Some(queued) => queued.1.merge(job),
+        None => pending.push((path, job)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_fps(max_fps: u32) -> SaveJob {
+        let mut config = AppConfig::default();
+        config.capture.max_fps = max_fps;
+        SaveJob::Config(Box::new(config))
+    }
+
+    #[test]
+    fn test_coalesce_keeps_newest_save_per_file() {
+        let mut pending = Vec::new();
+        coalesce(&mut pending, PathBuf::from("a.toml"), config_with_fps(10));
+        coalesce(&mut pending, PathBuf::from("b.toml"), config_with_fps(20));
+        coalesce(&mut pending, PathBuf::from("a.toml"), config_with_fps(30));
+
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].0, PathBuf::from("a.toml"));
+        assert!(matches!(pending[0].1, SaveJob::Config(ref c) if c.capture.max_fps == 30));
+    }
+
+    #[test]
+    fn test_coalesce_folds_sections_into_whole_save() {
+        let profile = crate::storage::starter_packs::STARTER_PACKS[0]
+            .profile()
+            .unwrap();
+        let path = PathBuf::from("game.json");
+        let sections = |name: &str| ProfileSections {
+            id: profile.id.clone(),
+            name: name.to_string(),
+            ocr_regions: Some(vec![]),
+            labeled_regions: None,
+        };
+
+        let mut pending = Vec::new();
+        coalesce(
+            &mut pending,
+            path.clone(),
+            SaveJob::Profile(Box::new(profile.clone())),
+        );
+        coalesce(
+            &mut pending,
+            path.clone(),
+            SaveJob::ProfileSections(Box::new(sections("Renamed"))),
+        );
+
+        assert_eq!(pending.len(), 1);
+        let SaveJob::Profile(ref saved) = pending[0].1 else {
+            panic!("whole save was replaced by a partial one");
+        };
+        assert!(saved.ocr_regions.is_empty());
+        assert_eq!(saved.name, "Renamed");
+        assert_eq!(saved.screens.len(), profile.screens.len());
+    }
+
+    #[test]
+    fn test_writer_saves_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let writer = StorageWriter::spawn();
+
+        let mut config = AppConfig::default();
+        config.capture.max_fps = 45;
+        writer.save_config(&config, path.clone());
+        writer.save_config(&config, dir.path().join("missing/config.toml"));
+        writer.flush();
+
+        let saved = config::load_config(&path).unwrap();
+        assert_eq!(saved.capture.max_fps, 45);
+
+        let results: Vec<_> = std::iter::from_fn(|| writer.try_result()).collect();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.target == SaveTarget::Config));
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.is_some());
+    }
+}
@@ -0,0 +1,368 @@
+#![allow(dead_code)]
+//! Opt-in anonymous telemetry
+//!
+//! Nothing is recorded or sent unless the user enables telemetry in Settings.
+//! When enabled, coarse feature usage counters and panic reports are kept in
+//! the data directory and sent to the configured endpoint on the next start.
+//! The exact payload can be previewed in Settings before anything leaves the
+//! machine.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::TelemetrySettings;
+
+/// Whether telemetry is currently enabled (read by the panic hook)
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// File holding usage counters not yet sent
+const USAGE_FILE: &str = "usage.json";
+
+/// Prefix of pending crash report files
+const CRASH_FILE_PREFIX: &str = "crash-";
+
+/// Coarse features whose usage is counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Dashboard,
+    OverlayOnly,
+    McpServer,
+    SafeMode,
+    CaptureStarted,
+    OverlayStarted,
+    WindowsOcr,
+    PaddleOcr,
+    ProfileCreated,
+    ShareCodeImported,
+}
+
+/// Per-feature usage counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageCounters {
+    counts: BTreeMap<Feature, u64>,
+}
+
+impl UsageCounters {
+    /// Count one use of a feature (no-op while telemetry is disabled)
+    pub fn record(&mut self, feature: Feature) {
+        if is_enabled() {
+            *self.counts.entry(feature).or_insert(0) += 1;
+        }
+    }
+
+    /// Add another set of counts to this one
+    pub fn merge(&mut self, other: &UsageCounters) {
+        for (feature, count) in &other.counts {
+            *self.counts.entry(*feature).or_insert(0) += count;
+        }
+    }
+
+    /// Whether nothing has been counted
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// A panic captured while telemetry was enabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Panic message
+    pub message: String,
+    /// Source location of the panic
+    pub location: Option<String>,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+}
+
+impl CrashReport {
+    /// The report with user names taken out of the paths in it
+    ///
+    /// Panic messages often quote file paths, e.g. of a config file that
+    /// failed to open, and those include the user's name.
+    pub fn redacted(&self) -> Self {
+        Self {
+            message: redact_paths(&self.message),
+            location: self.location.as_deref().map(redact_paths),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Replace the user name in paths such as `C:\Users\<name>\...` or
+/// `/home/<name>/...` with `<user>`
+pub fn redact_paths(text: &str) -> String {
+    static USER_DIR: OnceLock<Regex> = OnceLock::new();
+    // A name with spaces only counts when a path separator follows it
+    let pattern = USER_DIR.get_or_init(|| {
+        Regex::new(r#"(?i)([\\/](?:users|home)[\\/]+)(?:[^\\/:'"\r\n]+([\\/])|[^\\/\s:'"]+)"#)
+            .expect("valid regex")
+    });
+    pattern
+        .replace_all(text, |caps: &regex::Captures| {
+            let separator = caps.get(2).map_or("", |m| m.as_str());
+            format!("{}<user>{}", &caps[1], separator)
+        })
+        .into_owned()
+}
+
+/// Everything that would be sent to the telemetry endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryPayload {
+    /// Random ID generated when telemetry was enabled
+    pub install_id: String,
+    /// Application version
+    pub app_version: &'static str,
+    /// Operating system
+    pub os: &'static str,
+    /// CPU architecture
+    pub arch: &'static str,
+    /// Feature usage counts
+    pub usage: UsageCounters,
+    /// Crash reports, with user names taken out of their paths
+    pub crashes: Vec<CrashReport>,
+}
+
+impl TelemetryPayload {
+    /// Whether there is anything worth sending
+    pub fn is_empty(&self) -> bool {
+        self.usage.is_empty() && self.crashes.is_empty()
+    }
+}
+
+/// Enable or disable telemetry for this process
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether telemetry is enabled
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Directory holding pending telemetry data
+pub fn telemetry_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("telemetry")
+}
+
+/// Install a panic hook that stores a crash report while telemetry is enabled
+///
+/// The previous hook still runs, so panics are logged as before.
+pub fn install_panic_hook(dir: PathBuf) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if is_enabled() {
+            let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = info.payload().downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "unknown panic".to_string()
+            };
+            let report = CrashReport {
+                message,
+                location: info
+                    .location()
+                    .map(|l| format!("{}:{}", l.file(), l.line())),
+                timestamp: unix_time(),
+            };
+            let _ = write_crash_report(&dir, &report);
+        }
+        previous(info);
+    }));
+}
+
+/// Store a crash report for sending later
+fn write_crash_report(dir: &Path, report: &CrashReport) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}{}.json", CRASH_FILE_PREFIX, report.timestamp));
+    std::fs::write(path, serde_json::to_string_pretty(report)?)?;
+    Ok(())
+}
+
+/// Add this session's usage to the pending counters
+pub fn save_usage(dir: &Path, session: &UsageCounters) -> Result<()> {
+    if session.is_empty() {
+        return Ok(());
+    }
+
+    let mut usage = load_usage(dir);
+    usage.merge(session);
+
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join(USAGE_FILE), serde_json::to_string_pretty(&usage)?)?;
+    Ok(())
+}
+
+fn load_usage(dir: &Path) -> UsageCounters {
+    std::fs::read_to_string(dir.join(USAGE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Crash report files waiting to be sent
+fn crash_report_paths(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(CRASH_FILE_PREFIX))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Build the payload from pending data plus the current session's usage
+pub fn build_payload(
+    settings: &TelemetrySettings,
+    dir: &Path,
+    session: &UsageCounters,
+) -> TelemetryPayload {
+    let mut usage = load_usage(dir);
+    usage.merge(session);
+
+    let crashes = crash_report_paths(dir)
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str::<CrashReport>(&content).ok())
+        .map(|report| report.redacted())
+        .collect();
+
+    TelemetryPayload {
+        install_id: settings.install_id.clone().unwrap_or_default(),
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        usage,
+        crashes,
+    }
+}
+
+/// Delete all pending telemetry data
+pub fn clear_pending(dir: &Path) -> Result<()> {
+    for path in crash_report_paths(dir) {
+        std::fs::remove_file(path)?;
+    }
+    let usage_path = dir.join(USAGE_FILE);
+    if usage_path.exists() {
+        std::fs::remove_file(usage_path)?;
+    }
+    Ok(())
+}
+
+/// Send pending data to the configured endpoint and clear it on success
+pub async fn send_pending(settings: TelemetrySettings, dir: PathBuf) -> Result<()> {
+    let Some(ref endpoint) = settings.endpoint else {
+        return Ok(());
+    };
+
+    let payload = build_payload(&settings, &dir, &UsageCounters::default());
+    if payload.is_empty() {
+        return Ok(());
+    }
+
+    let client = crate::network::http_client(std::time::Duration::from_secs(30))?;
+
+    let body = serde_json::to_vec(&payload)?;
+    let response = client
+        .post(endpoint)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .context("Failed to send telemetry")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Telemetry endpoint returned {}", response.status());
+    }
+
+    clear_pending(&dir)
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_usage_merge() {
+        let mut a = UsageCounters::default();
+        a.counts.insert(Feature::CaptureStarted, 2);
+        let mut b = UsageCounters::default();
+        b.counts.insert(Feature::CaptureStarted, 1);
+        b.counts.insert(Feature::WindowsOcr, 1);
+
+        a.merge(&b);
+
+        assert_eq!(a.counts[&Feature::CaptureStarted], 3);
+        assert_eq!(a.counts[&Feature::WindowsOcr], 1);
+    }
+
+    #[test]
+    fn test_redact_paths() {
+        assert_eq!(
+            redact_paths(r"Failed to read C:\Users\Alice Smith\AppData\Roaming\config.toml"),
+            r"Failed to read C:\Users\<user>\AppData\Roaming\config.toml"
+        );
+        assert_eq!(
+            redact_paths("No such file: '/home/alice/.config/gtk/config.toml'"),
+            "No such file: '/home/<user>/.config/gtk/config.toml'"
+        );
+        assert_eq!(
+            redact_paths("index out of bounds: the len is 3"),
+            "index out of bounds: the len is 3"
+        );
+    }
+
+    #[test]
+    fn test_pending_data_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let settings = TelemetrySettings {
+            enabled: true,
+            install_id: Some("test-id".to_string()),
+            endpoint: None,
+        };
+
+        let mut session = UsageCounters::default();
+        session.counts.insert(Feature::OverlayStarted, 1);
+        save_usage(dir.path(), &session).unwrap();
+        write_crash_report(
+            dir.path(),
+            &CrashReport {
+                message: "boom".to_string(),
+                location: Some("src/main.rs:1".to_string()),
+                timestamp: 42,
+            },
+        )
+        .unwrap();
+
+        let payload = build_payload(&settings, dir.path(), &session);
+        assert_eq!(payload.install_id, "test-id");
+        assert_eq!(payload.usage.counts[&Feature::OverlayStarted], 2);
+        assert_eq!(payload.crashes.len(), 1);
+
+        clear_pending(dir.path()).unwrap();
+        let payload = build_payload(&settings, dir.path(), &UsageCounters::default());
+        assert!(payload.is_empty());
+    }
+}
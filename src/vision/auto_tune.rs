@@ -0,0 +1,157 @@
+//! Per-zone preprocessing auto-tune
+//!
+//! Grid-searches preprocessing settings (scale, contrast, grayscale,
+//! threshold, invert) for a zone. Each candidate is run over the current zone
+//! image and any saved sample frames, and scored by OCR confidence plus whether
+//! the reading looks like the zone's content type.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::config::OcrPreprocessing;
+use crate::storage::profiles::ContentType;
+
+/// Sample frames kept per zone
+pub const MAX_SAMPLES_PER_ZONE: usize = 5;
+
+/// Upscale factors tried
+const SCALES: [u32; 4] = [1, 2, 3, 4];
+
+/// Contrast factors tried
+const CONTRASTS: [f32; 3] = [1.0, 1.5, 2.0];
+
+/// Binarization thresholds tried (0 = off)
+const THRESHOLDS: [u8; 3] = [96, 128, 160];
+
+/// Weight of OCR confidence in a reading's score; the rest is pattern validity
+const CONFIDENCE_WEIGHT: f32 = 0.5;
+
+/// All preprocessing settings tried by auto-tune, starting with no preprocessing
+pub fn candidate_settings() -> Vec<OcrPreprocessing> {
+    let mut candidates = vec![OcrPreprocessing::default()];
+
+    // Color handling: as-is, grayscale, or grayscale binarized at a threshold
+    let mut color_modes = vec![(false, 0), (true, 0)];
+    color_modes.extend(THRESHOLDS.iter().map(|&threshold| (true, threshold)));
+
+    for scale in SCALES {
+        for contrast in CONTRASTS {
+            for &(grayscale, threshold) in &color_modes {
+                for invert in [false, true] {
+                    candidates.push(OcrPreprocessing {
+                        enabled: true,
+                        grayscale,
+                        contrast,
+                        sharpen: 0.0,
+                        invert,
+                        scale,
+                        threshold,
+                    });
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Whether a filtered reading has the shape expected for the content type
+pub fn is_valid_reading(text: &str, content_type: &ContentType) -> bool {
+    static NUMBER: OnceLock<Regex> = OnceLock::new();
+    static PERCENTAGE: OnceLock<Regex> = OnceLock::new();
+    static TIME: OnceLock<Regex> = OnceLock::new();
+    static TEXT: OnceLock<Regex> = OnceLock::new();
+
+    let pattern = match content_type {
+        ContentType::Number => NUMBER.get_or_init(|| {
+            Regex::new(r"^-?(\d{1,3}(,\d{3})+|\d+)(\.\d+)?$").expect("valid regex")
+        }),
+        ContentType::Percentage => {
+            PERCENTAGE.get_or_init(|| Regex::new(r"^-?\d{1,3}(\.\d+)?%?$").expect("valid regex"))
+        }
+        ContentType::Time => {
+            TIME.get_or_init(|| Regex::new(r"^\d{1,2}(:[0-5]\d){1,2}$").expect("valid regex"))
+        }
+        ContentType::Text => TEXT.get_or_init(|| Regex::new(r"\w").expect("valid regex")),
+    };
+
+    pattern.is_match(text.trim())
+}
+
+/// Score a single reading from 0.0 to 1.0
+///
+/// Empty readings score zero; otherwise half the score comes from the OCR
+/// confidence and half from matching the content type's pattern.
+pub fn score_reading(text: &str, confidence: f32, content_type: &ContentType) -> f32 {
+    if text.trim().is_empty() {
+        return 0.0;
+    }
+
+    let validity = if is_valid_reading(text, content_type) {
+        1.0
+    } else {
+        0.0
+    };
+    CONFIDENCE_WEIGHT * confidence.clamp(0.0, 1.0) + (1.0 - CONFIDENCE_WEIGHT) * validity
+}
+
+/// Short description of a candidate for progress messages
+pub fn describe(settings: &OcrPreprocessing) -> String {
+    if !settings.enabled {
+        return "no preprocessing".to_string();
+    }
+
+    let mut parts = vec![
+        format!("scale={}x", settings.scale),
+        format!("contrast={:.1}", settings.contrast),
+    ];
+    if settings.threshold > 0 {
+        parts.push(format!("threshold={}", settings.threshold));
+    } else if settings.grayscale {
+        parts.push("gray".to_string());
+    }
+    if settings.invert {
+        parts.push("inverted".to_string());
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_start_with_baseline() {
+        let candidates = candidate_settings();
+        assert!(!candidates[0].enabled);
+        // 4 scales * 3 contrasts * 5 color modes * 2 invert + baseline
+        assert_eq!(candidates.len(), 4 * 3 * 5 * 2 + 1);
+        assert!(candidates.iter().any(|c| c.threshold == 128 && c.invert));
+    }
+
+    #[test]
+    fn test_reading_validity() {
+        assert!(is_valid_reading("1,250", &ContentType::Number));
+        assert!(is_valid_reading("-3.5", &ContentType::Number));
+        assert!(!is_valid_reading("1,2,5", &ContentType::Number));
+        assert!(!is_valid_reading("", &ContentType::Number));
+
+        assert!(is_valid_reading("75%", &ContentType::Percentage));
+        assert!(!is_valid_reading("7%5", &ContentType::Percentage));
+
+        assert!(is_valid_reading("1:23:45", &ContentType::Time));
+        assert!(!is_valid_reading("12:99", &ContentType::Time));
+
+        assert!(is_valid_reading("Gold", &ContentType::Text));
+        assert!(!is_valid_reading("--", &ContentType::Text));
+    }
+
+    #[test]
+    fn test_score_prefers_valid_readings() {
+        let valid = score_reading("120", 0.6, &ContentType::Number);
+        let invalid = score_reading("1-2-0", 0.9, &ContentType::Number);
+        assert!(valid > invalid);
+        assert!((valid - 0.8).abs() < 1e-6);
+        assert_eq!(score_reading("  ", 1.0, &ContentType::Text), 0.0);
+    }
+}
@@ -0,0 +1,208 @@
+//! Side-by-side comparison of the OCR backends
+//!
+//! Both backends read the same frame and their text regions are paired up by
+//! where they are. PaddleOCR reads whole lines where Windows OCR may read
+//! words, so a region is compared with every region of the other backend
+//! covering it, in reading order.
+
+use crate::vision::{OcrBackend, TextRegion};
+
+/// Share of the smaller of two regions they must have in common to cover
+/// the same text
+const MIN_OVERLAP: f32 = 0.5;
+
+/// How a region compares with the other backend's reading of the same spot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionDiff {
+    /// The other backend read the same text there
+    Same,
+    /// The other backend read different text there
+    Different,
+    /// The other backend found no text there
+    Missing,
+}
+
+/// A region one backend read, compared with the other backend
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparedRegion {
+    /// Text read
+    pub text: String,
+    /// Bounding box (x, y, width, height)
+    pub bounds: (u32, u32, u32, u32),
+    /// Confidence score
+    pub confidence: f32,
+    /// How the other backend's reading compares
+    pub diff: RegionDiff,
+    /// What the other backend read there, when it differs
+    pub other_text: Option<String>,
+}
+
+/// One backend's readings of the compared frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendReadings {
+    /// Backend that read them
+    pub backend: OcrBackend,
+    /// Regions, compared with the other backend
+    pub regions: Vec<ComparedRegion>,
+    /// Time the backend took
+    pub processing_time_ms: u64,
+}
+
+impl BackendReadings {
+    /// Regions the other backend didn't read alike
+    pub fn differences(&self) -> usize {
+        self.regions
+            .iter()
+            .filter(|r| r.diff != RegionDiff::Same)
+            .count()
+    }
+}
+
+/// Readings of both backends of one frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendComparison {
+    /// Readings of the selected backend, then of the other one
+    pub sides: [BackendReadings; 2],
+}
+
+impl BackendComparison {
+    /// Compare the regions two backends read, with the time each took
+    pub fn new(
+        first: (OcrBackend, &[TextRegion], u64),
+        second: (OcrBackend, &[TextRegion], u64),
+    ) -> Self {
+        let side = |(backend, regions, time): (OcrBackend, &[TextRegion], u64),
+                    others: &[TextRegion]| BackendReadings {
+            backend,
+            regions: regions.iter().map(|r| compare_region(r, others)).collect(),
+            processing_time_ms: time,
+        };
+        Self {
+            sides: [side(first, second.1), side(second, first.1)],
+        }
+    }
+
+    /// Share of the regions of both backends read alike (1.0 when neither
+    /// found text)
+    pub fn agreement(&self) -> f32 {
+        let total: usize = self.sides.iter().map(|s| s.regions.len()).sum();
+        if total == 0 {
+            return 1.0;
+        }
+        let different: usize = self.sides.iter().map(|s| s.differences()).sum();
+        (total - different) as f32 / total as f32
+    }
+}
+
+/// A region compared with the regions the other backend read over it
+fn compare_region(region: &TextRegion, others: &[TextRegion]) -> ComparedRegion {
+    let mut covering: Vec<&TextRegion> = others
+        .iter()
+        .filter(|other| overlap(region.bounds, other.bounds) >= MIN_OVERLAP)
+        .collect();
+    covering.sort_by_key(|r| (r.bounds.1 + r.bounds.3 / 2, r.bounds.0));
+
+    let (diff, other_text) = if covering.is_empty() {
+        (RegionDiff::Missing, None)
+    } else {
+        let other_text = covering
+            .iter()
+            .map(|r| r.text.trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if same_text(&region.text, &other_text) {
+            (RegionDiff::Same, None)
+        } else {
+            (RegionDiff::Different, Some(other_text))
+        }
+    };
+
+    ComparedRegion {
+        text: region.text.clone(),
+        bounds: region.bounds,
+        confidence: region.confidence,
+        diff,
+        other_text,
+    }
+}
+
+/// Area two boxes have in common, as a share of the smaller one
+fn overlap(a: (u32, u32, u32, u32), b: (u32, u32, u32, u32)) -> f32 {
+    let width = (a.0 + a.2).min(b.0 + b.2).saturating_sub(a.0.max(b.0));
+    let height = (a.1 + a.3).min(b.1 + b.3).saturating_sub(a.1.max(b.1));
+    let smaller = (a.2 as u64 * a.3 as u64).min(b.2 as u64 * b.3 as u64);
+    if smaller == 0 {
+        return 0.0;
+    }
+    (width as u64 * height as u64) as f32 / smaller as f32
+}
+
+/// Whether the words of one text run on in the other, ignoring case
+///
+/// A word is the same as the line around it, since the backends split text
+/// differently.
+fn same_text(a: &str, b: &str) -> bool {
+    let words =
+        |text: &str| -> Vec<String> { text.split_whitespace().map(|w| w.to_lowercase()).collect() };
+    let (a, b) = (words(a), words(b));
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    !shorter.is_empty()
+        && longer
+            .windows(shorter.len())
+            .any(|w| w == shorter.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(text: &str, bounds: (u32, u32, u32, u32)) -> TextRegion {
+        TextRegion {
+            text: text.to_string(),
+            bounds,
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_compare_pairs_regions_by_position() {
+        let windows = [
+            region("HP", (10, 10, 20, 10)),
+            region("120", (40, 10, 30, 10)),
+            region("Gold", (10, 50, 40, 10)),
+        ];
+        let paddle = [
+            region("hp 120", (8, 9, 64, 12)),
+            region("Go1d", (10, 50, 40, 10)),
+            region("Quest", (200, 200, 50, 10)),
+        ];
+
+        let comparison = BackendComparison::new(
+            (OcrBackend::WindowsOcr, &windows[..], 12),
+            (OcrBackend::PaddleOcr, &paddle[..], 80),
+        );
+        let [ref first, ref second] = comparison.sides;
+        let diffs =
+            |side: &BackendReadings| side.regions.iter().map(|r| r.diff).collect::<Vec<_>>();
+
+        assert_eq!(
+            diffs(first),
+            [RegionDiff::Same, RegionDiff::Same, RegionDiff::Different]
+        );
+        assert_eq!(first.regions[2].other_text.as_deref(), Some("Go1d"));
+        assert_eq!(
+            diffs(second),
+            [RegionDiff::Same, RegionDiff::Different, RegionDiff::Missing]
+        );
+        assert_eq!(second.processing_time_ms, 80);
+        assert!((comparison.agreement() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_same_text() {
+        assert!(same_text("Level 12", "level 12"));
+        assert!(same_text("12", "Level 12"));
+        assert!(!same_text("1", "Level 12"));
+        assert!(!same_text("", "Level"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Batch OCR over a screenshots folder
+//!
+//! Runs a profile's zones over every image in a folder and writes one CSV row
+//! per image, so an existing screenshot archive can be mined for stats. Images
+//! are processed one at a time so the dashboard can spread a run over frames.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::capture::CapturedFrame;
+use crate::config::OcrPreprocessing;
+use crate::storage::profiles::{OcrRegion, RecognitionDictionary};
+use crate::vision::ensemble::Reading;
+use crate::vision::zone_text::zone_reading;
+use crate::vision::VisionPipeline;
+
+/// CSV file name used when no output path is given
+pub const DEFAULT_OUTPUT_NAME: &str = "zone_values.csv";
+
+/// File extensions treated as screenshots
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "webp"];
+
+/// Images in a folder (not recursive), sorted by file name
+pub fn list_images(folder: &Path) -> Result<Vec<PathBuf>> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(folder)
+        .with_context(|| format!("Failed to read folder {:?}", folder))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .collect();
+    images.sort();
+    Ok(images)
+}
+
+/// Load an image file as a frame
+pub fn load_frame(path: &Path) -> Result<CapturedFrame> {
+    let image = image::open(path)
+        .with_context(|| format!("Failed to open image {:?}", path))?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(CapturedFrame::new(image.into_raw(), width, height))
+}
+
+/// Read each zone from a frame (`None` where the zone is too small or OCR failed)
+pub fn read_zones(
+    pipeline: &mut VisionPipeline,
+    frame: &CapturedFrame,
+    zones: &[OcrRegion],
+    preprocessing: &OcrPreprocessing,
+    dictionary: Option<&RecognitionDictionary>,
+) -> Vec<Option<Reading>> {
+    let readings = zones
+        .iter()
+        .map(|zone| {
+            let x = (zone.bounds.0 * frame.width as f32) as u32;
+            let y = (zone.bounds.1 * frame.height as f32) as u32;
+            let w =
+                ((zone.bounds.2 * frame.width as f32) as u32).min(frame.width.saturating_sub(x));
+            let h =
+                ((zone.bounds.3 * frame.height as f32) as u32).min(frame.height.saturating_sub(y));
+            if w < 5 || h < 5 {
+                return None;
+            }
+
+            let preprocessing = zone
+                .preprocessing
+                .as_ref()
+                .or(Some(preprocessing))
+                .filter(|pp| pp.enabled);
+            pipeline.set_allowed_chars(zone.allowed_chars.as_deref().filter(|a| !a.is_empty()));
+            match pipeline.process_region_with_geometry(
+                frame,
+                x,
+                y,
+                w,
+                h,
+                preprocessing,
+                Some(&zone.geometry),
+            ) {
+                Ok(result) => Some(zone_reading(&result, zone, dictionary)),
+                Err(e) => {
+                    tracing::debug!("Zone '{}': OCR failed: {}", zone.name, e);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    pipeline.set_allowed_chars(None);
+    readings
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Join fields into a CSV line
+fn csv_line<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    fields
+        .into_iter()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A batch run writing one CSV row per image
+#[derive(Debug)]
+pub struct BatchRun {
+    /// Images to process
+    images: Vec<PathBuf>,
+    /// Index of the next image
+    next: usize,
+    /// Enabled zones of the profile, one CSV column each
+    zones: Vec<OcrRegion>,
+    /// CSV output
+    writer: BufWriter<File>,
+    /// CSV output path
+    output: PathBuf,
+    /// Images that could not be opened
+    failed: usize,
+}
+
+impl BatchRun {
+    /// List the folder's images and write the CSV header
+    pub fn start(folder: &Path, output: &Path, zones: &[OcrRegion]) -> Result<Self> {
+        let images = list_images(folder)?;
+        if images.is_empty() {
+            anyhow::bail!("No images found in {:?}", folder);
+        }
+
+        let zones: Vec<OcrRegion> = zones.iter().filter(|z| z.enabled).cloned().collect();
+        if zones.is_empty() {
+            anyhow::bail!("The profile has no enabled zones");
+        }
+
+        let file =
+            File::create(output).with_context(|| format!("Failed to create {:?}", output))?;
+        let mut writer = BufWriter::new(file);
+        let header = std::iter::once("file").chain(zones.iter().map(|z| z.name.as_str()));
+        writeln!(writer, "{}", csv_line(header))?;
+
+        Ok(Self {
+            images,
+            next: 0,
+            zones,
+            writer,
+            output: output.to_path_buf(),
+            failed: 0,
+        })
+    }
+
+    /// Number of images in the run
+    pub fn total(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Number of images processed so far
+    pub fn processed(&self) -> usize {
+        self.next
+    }
+
+    /// Number of images that could not be opened
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Whether every image has been processed
+    pub fn is_done(&self) -> bool {
+        self.next >= self.images.len()
+    }
+
+    /// CSV output path
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// OCR the next image and append its row, flushing after the last one
+    ///
+    /// Images that fail to open are skipped and counted; write errors abort.
+    pub fn process_next(
+        &mut self,
+        pipeline: &mut VisionPipeline,
+        preprocessing: &OcrPreprocessing,
+        dictionary: Option<&RecognitionDictionary>,
+    ) -> Result<()> {
+        let Some(path) = self.images.get(self.next) else {
+            return Ok(());
+        };
+        self.next += 1;
+
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        match load_frame(path) {
+            Ok(frame) => {
+                let readings = read_zones(pipeline, &frame, &self.zones, preprocessing, dictionary);
+                let values = readings
+                    .iter()
+                    .map(|reading| reading.as_ref().map_or("", |r| r.text.as_str()));
+                writeln!(
+                    self.writer,
+                    "{}",
+                    csv_line(std::iter::once(file_name.as_str()).chain(values))
+                )?;
+            }
+            Err(e) => {
+                tracing::warn!("Skipping {}: {:#}", file_name, e);
+                self.failed += 1;
+            }
+        }
+
+        if self.is_done() {
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_csv_quoting() {
+        assert_eq!(
+            csv_line(["shot.png", "1,250", "say \"hi\"", "75%"]),
+            "shot.png,\"1,250\",\"say \"\"hi\"\"\",75%"
+        );
+    }
+
+    #[test]
+    fn test_list_images() {
+        let dir = TempDir::new().unwrap();
+        for name in ["b.PNG", "a.jpg", "notes.txt"] {
+            File::create(dir.path().join(name)).unwrap();
+        }
+
+        let images = list_images(dir.path()).unwrap();
+        let names: Vec<_> = images
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a.jpg", "b.PNG"]);
+    }
+}
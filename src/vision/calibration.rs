@@ -0,0 +1,154 @@
+//! OCR confidence calibration
+//!
+//! Raw confidences aren't comparable between backends: Windows OCR reports
+//! 1.0 for every word and PaddleOCR's character scores run low. A calibration
+//! maps a backend's raw confidence to how often readings with that confidence
+//! were right on the profile's labeled samples, so one recognition threshold
+//! means the same thing for both backends.
+
+use crate::storage::profiles::ConfidenceCalibration;
+use crate::vision::OcrBackend;
+
+/// Labeled readings needed before a curve is fit
+pub const MIN_SAMPLES: usize = 10;
+
+/// Fit a monotone curve through labeled readings
+///
+/// Each sample is a raw confidence and whether the reading was right. Uses
+/// isotonic regression (pool adjacent violators), so a higher raw confidence
+/// never calibrates lower. Returns `None` with fewer than [`MIN_SAMPLES`].
+pub fn fit(backend: OcrBackend, samples: &[(f32, bool)]) -> Option<ConfidenceCalibration> {
+    let mut sorted: Vec<(f32, bool)> = samples
+        .iter()
+        .copied()
+        .filter(|(raw, _)| raw.is_finite())
+        .collect();
+    if sorted.len() < MIN_SAMPLES {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let count = sorted.len() as u32;
+
+    // Blocks of (raw sum, right count, count); equal raw values share a block
+    let mut blocks: Vec<(f32, f32, f32)> = Vec::new();
+    for (raw, right) in sorted {
+        let right = if right { 1.0 } else { 0.0 };
+        match blocks.last_mut() {
+            Some(last) if last.0 / last.2 == raw => {
+                last.0 += raw;
+                last.1 += right;
+                last.2 += 1.0;
+            }
+            _ => blocks.push((raw, right, 1.0)),
+        }
+
+        // Pool while the previous block is right more often
+        while blocks.len() > 1 {
+            let (cur, prev) = (blocks[blocks.len() - 1], blocks[blocks.len() - 2]);
+            if prev.1 / prev.2 <= cur.1 / cur.2 {
+                break;
+            }
+            blocks.pop();
+            let last = blocks.last_mut().expect("at least one block left");
+            last.0 += cur.0;
+            last.1 += cur.1;
+            last.2 += cur.2;
+        }
+    }
+
+    Some(ConfidenceCalibration {
+        backend,
+        points: blocks
+            .iter()
+            .map(|&(raw_sum, right, count)| (raw_sum / count, right / count))
+            .collect(),
+        samples: count,
+    })
+}
+
+/// Calibrated confidence for a raw one
+///
+/// Interpolates linearly between the curve's points and holds the end values
+/// beyond them. An empty curve leaves the confidence as is.
+pub fn apply(calibration: &ConfidenceCalibration, raw: f32) -> f32 {
+    let points = &calibration.points;
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return raw;
+    };
+    if raw <= first.0 {
+        return first.1;
+    }
+    if raw >= last.0 {
+        return last.1;
+    }
+
+    let upper = points.partition_point(|p| p.0 < raw);
+    let (x0, y0) = points[upper - 1];
+    let (x1, y1) = points[upper];
+    y0 + (y1 - y0) * (raw - x0) / (x1 - x0)
+}
+
+/// Calibrated confidence with the backend's calibration, if there is one
+pub fn calibrate(calibrations: &[ConfidenceCalibration], backend: OcrBackend, raw: f32) -> f32 {
+    calibrations
+        .iter()
+        .find(|c| c.backend == backend)
+        .map_or(raw, |c| apply(c, raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_is_monotone() {
+        // Right more often at low confidence than at 0.5, which gets pooled
+        let samples = [
+            (0.1, false),
+            (0.2, true),
+            (0.3, false),
+            (0.5, false),
+            (0.5, false),
+            (0.7, true),
+            (0.8, true),
+            (0.8, false),
+            (0.9, true),
+            (0.95, true),
+        ];
+        let calibration = fit(OcrBackend::PaddleOcr, &samples).unwrap();
+        assert_eq!(calibration.samples, 10);
+        assert!(calibration.points.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(calibration.points.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(calibration.points[0], (0.1, 0.0));
+        assert_eq!(calibration.points.last(), Some(&(0.95, 1.0)));
+
+        assert!(fit(OcrBackend::PaddleOcr, &samples[..5]).is_none());
+    }
+
+    #[test]
+    fn test_constant_confidence_calibrates_to_accuracy() {
+        // Windows OCR always reports 1.0
+        let samples: Vec<(f32, bool)> = (0..20).map(|i| (1.0, i % 4 != 0)).collect();
+        let calibration = fit(OcrBackend::WindowsOcr, &samples).unwrap();
+        assert_eq!(calibration.points, [(1.0, 0.75)]);
+        assert_eq!(apply(&calibration, 1.0), 0.75);
+        assert_eq!(apply(&calibration, 0.2), 0.75);
+    }
+
+    #[test]
+    fn test_apply_interpolates() {
+        let calibration = ConfidenceCalibration {
+            backend: OcrBackend::PaddleOcr,
+            points: vec![(0.2, 0.1), (0.6, 0.5), (0.8, 0.9)],
+            samples: 30,
+        };
+        assert!((apply(&calibration, 0.4) - 0.3).abs() < 1e-6);
+        assert!((apply(&calibration, 0.7) - 0.7).abs() < 1e-6);
+        assert_eq!(apply(&calibration, 0.0), 0.1);
+        assert_eq!(apply(&calibration, 1.0), 0.9);
+
+        let calibrations = [calibration];
+        assert_eq!(calibrate(&calibrations, OcrBackend::WindowsOcr, 1.0), 1.0);
+        assert!((calibrate(&calibrations, OcrBackend::PaddleOcr, 0.4) - 0.3).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,69 @@
+//! Per-zone character whitelists
+//!
+//! A zone can restrict OCR output to a set of allowed characters. Readings are
+//! filtered after OCR (mapping look-alike misreads such as `O` → `0` onto
+//! allowed characters first), and PaddleOCR additionally constrains its
+//! decoder so disallowed characters are never picked.
+
+/// Presets offered in the zone settings dialog: (label, characters)
+pub const PRESETS: &[(&str, &str)] = &[
+    ("Digits", "0123456789"),
+    ("Digits + :/%", "0123456789:/%"),
+    ("Digits + .,-", "0123456789.,-"),
+];
+
+/// Characters commonly confused by OCR, tried when a character isn't allowed
+fn lookalikes(c: char) -> &'static [char] {
+    match c {
+        'O' | 'o' | 'D' | 'Q' => &['0'],
+        '0' => &['O', 'o'],
+        'I' | 'l' | '|' | 'i' | '!' => &['1'],
+        '1' => &['I', 'l'],
+        'Z' | 'z' => &['2'],
+        'S' | 's' => &['5'],
+        '5' => &['S', 's'],
+        'B' => &['8'],
+        '8' => &['B'],
+        'G' | 'b' => &['6'],
+        'g' | 'q' => &['9'],
+        'T' => &['7'],
+        _ => &[],
+    }
+}
+
+/// Keep only allowed characters, replacing look-alike misreads where possible
+///
+/// Whitespace is kept so multi-word readings stay readable, unless the
+/// whitelist is digits/symbols only, in which case it is dropped too.
+pub fn apply_whitelist(text: &str, allowed: &str) -> String {
+    let keep_spaces = allowed.chars().any(char::is_alphabetic) || allowed.contains(' ');
+
+    text.chars()
+        .filter_map(|c| {
+            if allowed.contains(c) || (keep_spaces && c == ' ') {
+                Some(c)
+            } else {
+                lookalikes(c).iter().copied().find(|&l| allowed.contains(l))
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookalikes_are_mapped() {
+        assert_eq!(apply_whitelist("I2O", "0123456789"), "120");
+        assert_eq!(apply_whitelist("1 2:3O%", "0123456789:/%"), "12:30%");
+    }
+
+    #[test]
+    fn test_disallowed_characters_are_dropped() {
+        assert_eq!(apply_whitelist("HP 45/100", "0123456789/"), "45/100");
+        assert_eq!(apply_whitelist("Lv 5x", "Lv0123456789"), "Lv 5");
+    }
+}
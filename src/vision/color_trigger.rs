@@ -0,0 +1,189 @@
+//! Pixel-color triggers
+//!
+//! Averages a few pixels at a fixed spot of the frame and compares them with
+//! a stored color, e.g. the red of a low-health vignette. Cheap enough to run
+//! on every frame, unlike templates and OCR.
+
+use std::collections::HashSet;
+
+use crate::capture::{CapturedFrame, PixelFormat};
+use crate::storage::profiles::ColorTrigger;
+
+/// Average color (RGB) of the square patch centered on a relative position
+///
+/// Returns `None` for an empty frame; patches at the edge are clipped.
+pub fn sample_color(
+    frame: &CapturedFrame,
+    position: (f32, f32),
+    patch_size: u32,
+) -> Option<[u8; 3]> {
+    let (x, y) = pixel_at(frame.width, frame.height, position)?;
+    match frame.format {
+        PixelFormat::Rgba8 => {
+            patch_color(&frame.data, frame.width, frame.height, (x, y), patch_size)
+        }
+        // Only the patch of a grayscale frame is expanded, which leaves its
+        // brightness to compare
+        PixelFormat::Gray8 => {
+            let half = patch_size.max(1) / 2;
+            let (x0, y0) = (x.saturating_sub(half), y.saturating_sub(half));
+            let (x1, y1) = (
+                (x + half).min(frame.width - 1),
+                (y + half).min(frame.height - 1),
+            );
+            let patch = frame.extract_region(x0, y0, x1 - x0 + 1, y1 - y0 + 1)?;
+            patch_color(
+                &patch.rgba(),
+                patch.width,
+                patch.height,
+                (x - x0, y - y0),
+                patch_size,
+            )
+        }
+    }
+}
+
+/// Pixel (x, y) at a relative position of an image, `None` if it's empty
+pub fn pixel_at(width: u32, height: u32, position: (f32, f32)) -> Option<(u32, u32)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let x = (position.0.clamp(0.0, 1.0) * width as f32) as u32;
+    let y = (position.1.clamp(0.0, 1.0) * height as f32) as u32;
+    Some((x.min(width - 1), y.min(height - 1)))
+}
+
+/// Average color (RGB) of the square patch centered on a pixel of RGBA data
+pub fn patch_color(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    center: (u32, u32),
+    patch_size: u32,
+) -> Option<[u8; 3]> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let (center_x, center_y) = (center.0.min(width - 1), center.1.min(height - 1));
+    let half = patch_size.max(1) / 2;
+
+    let (x0, x1) = (
+        center_x.saturating_sub(half),
+        (center_x + half).min(width - 1),
+    );
+    let (y0, y1) = (
+        center_y.saturating_sub(half),
+        (center_y + half).min(height - 1),
+    );
+
+    let mut sums = [0u64; 3];
+    let mut count = 0u64;
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            let i = ((y * width + x) * 4) as usize;
+            let pixel = rgba.get(i..i + 3)?;
+            for (sum, &channel) in sums.iter_mut().zip(pixel) {
+                *sum += channel as u64;
+            }
+            count += 1;
+        }
+    }
+
+    Some(sums.map(|sum| (sum / count) as u8))
+}
+
+/// Whether every channel is within the tolerance of the target color
+pub fn color_matches(color: [u8; 3], target: [u8; 3], tolerance: u8) -> bool {
+    color
+        .iter()
+        .zip(target)
+        .all(|(&c, t)| c.abs_diff(t) <= tolerance)
+}
+
+/// Whether the trigger's color is showing in the frame
+pub fn is_triggered(trigger: &ColorTrigger, frame: &CapturedFrame) -> bool {
+    sample_color(frame, trigger.position, trigger.patch_size)
+        .is_some_and(|color| color_matches(color, trigger.color, trigger.tolerance))
+}
+
+/// IDs of the enabled triggers whose color is showing in the frame
+pub fn active_triggers(triggers: &[ColorTrigger], frame: &CapturedFrame) -> HashSet<String> {
+    triggers
+        .iter()
+        .filter(|trigger| trigger.enabled && is_triggered(trigger, frame))
+        .map(|trigger| trigger.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 frame, red on the left half and gray on the right
+    fn frame() -> CapturedFrame {
+        let data = (0..100)
+            .flat_map(|i| {
+                if i % 10 < 5 {
+                    [200, 20, 20, 255]
+                } else {
+                    [100, 100, 100, 255]
+                }
+            })
+            .collect();
+        CapturedFrame::new(data, 10, 10)
+    }
+
+    fn trigger(position: (f32, f32)) -> ColorTrigger {
+        ColorTrigger {
+            id: "low_health".to_string(),
+            name: "Low health".to_string(),
+            position,
+            patch_size: 3,
+            color: [210, 25, 15],
+            tolerance: 24,
+            enabled: true,
+            save_replay: false,
+        }
+    }
+
+    #[test]
+    fn test_sample_color_averages_patch() {
+        assert_eq!(sample_color(&frame(), (0.2, 0.5), 3), Some([200, 20, 20]));
+        assert_eq!(sample_color(&frame(), (0.8, 0.5), 1), Some([100, 100, 100]));
+        // Straddles the edge between red and gray: two red columns, one gray
+        assert_eq!(sample_color(&frame(), (0.4, 0.5), 3), Some([166, 46, 46]));
+        // Clipped at the frame corner
+        assert_eq!(sample_color(&frame(), (1.0, 1.0), 5), Some([100, 100, 100]));
+        assert_eq!(
+            sample_color(&CapturedFrame::new(vec![], 0, 0), (0.5, 0.5), 3),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pixel_at_stays_in_image() {
+        assert_eq!(pixel_at(10, 10, (0.25, 0.5)), Some((2, 5)));
+        assert_eq!(pixel_at(10, 10, (1.0, 1.5)), Some((9, 9)));
+        assert_eq!(pixel_at(0, 10, (0.5, 0.5)), None);
+    }
+
+    #[test]
+    fn test_color_tolerance() {
+        assert!(color_matches([200, 20, 20], [210, 25, 15], 10));
+        assert!(!color_matches([200, 20, 20], [210, 25, 15], 9));
+    }
+
+    #[test]
+    fn test_active_triggers() {
+        assert!(is_triggered(&trigger((0.2, 0.5)), &frame()));
+        assert!(!is_triggered(&trigger((0.8, 0.5)), &frame()));
+
+        let mut disabled = trigger((0.2, 0.5));
+        disabled.enabled = false;
+        assert!(active_triggers(&[disabled], &frame()).is_empty());
+        assert_eq!(
+            active_triggers(&[trigger((0.2, 0.5))], &frame()),
+            HashSet::from(["low_health".to_string()])
+        );
+    }
+}
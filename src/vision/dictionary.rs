@@ -0,0 +1,83 @@
+//! Profile recognition dictionary
+//!
+//! Applies a profile's OCR substitution rules and snaps readings to its domain
+//! vocabulary, so game-specific names survive common misreads.
+
+use crate::storage::profiles::RecognitionDictionary;
+use crate::vision::screen_recognition::text_similarity;
+
+/// Minimum similarity for a reading to snap to a vocabulary entry
+pub const VOCABULARY_MIN_SIMILARITY: f32 = 0.75;
+
+/// Apply the substitution rules to raw OCR text, in order
+pub fn apply_substitutions(text: &str, dictionary: &RecognitionDictionary) -> String {
+    dictionary
+        .substitutions
+        .iter()
+        .filter(|rule| !rule.from.is_empty())
+        .fold(text.to_string(), |text, rule| {
+            text.replace(&rule.from, &rule.to)
+        })
+}
+
+/// The vocabulary entry closest to `text`, if it is similar enough
+pub fn snap_to_vocabulary<'a>(
+    text: &str,
+    dictionary: &'a RecognitionDictionary,
+) -> Option<&'a str> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    dictionary
+        .vocabulary
+        .iter()
+        .map(|word| (word, text_similarity(text, word)))
+        .filter(|(_, similarity)| *similarity >= VOCABULARY_MIN_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(word, _)| word.as_str())
+}
+
+/// Substitute, then snap to the vocabulary when a close entry exists
+pub fn correct_text(text: &str, dictionary: &RecognitionDictionary) -> String {
+    let substituted = apply_substitutions(text, dictionary);
+    match snap_to_vocabulary(&substituted, dictionary) {
+        Some(word) => word.to_string(),
+        None => substituted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::profiles::SubstitutionRule;
+
+    fn dictionary() -> RecognitionDictionary {
+        RecognitionDictionary {
+            substitutions: vec![SubstitutionRule {
+                from: "0rc".to_string(),
+                to: "Orc".to_string(),
+            }],
+            vocabulary: vec!["Elden Ring".to_string(), "Tritanium".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_substitutions() {
+        assert_eq!(
+            apply_substitutions("0rc Warrior", &dictionary()),
+            "Orc Warrior"
+        );
+        assert_eq!(apply_substitutions("Goblin", &dictionary()), "Goblin");
+    }
+
+    #[test]
+    fn test_vocabulary_snapping() {
+        let dict = dictionary();
+        assert_eq!(snap_to_vocabulary("Eldenn Ríng", &dict), Some("Elden Ring"));
+        assert_eq!(snap_to_vocabulary("Tritaniurn", &dict), Some("Tritanium"));
+        assert_eq!(snap_to_vocabulary("Parsteel", &dict), None);
+        assert_eq!(correct_text("Eldenn Ring", &dict), "Elden Ring");
+    }
+}
@@ -0,0 +1,151 @@
+//! OCR ensemble voting
+//!
+//! For critical zones both OCR backends read the same region. When they agree
+//! the reading is trusted more; when they disagree the reading that matches the
+//! zone's content type wins, falling back to the more confident one.
+
+use crate::storage::profiles::ContentType;
+use crate::vision::auto_tune::is_valid_reading;
+
+/// A filtered reading from one backend
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// Text after content-type filtering
+    pub text: String,
+    /// Mean confidence of the detected text regions
+    pub confidence: Option<f32>,
+}
+
+/// How the backends' readings related
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agreement {
+    /// Both backends read the same text
+    Agreed,
+    /// The backends read different text and one was chosen
+    Disagreed,
+    /// Only one backend produced text
+    Single,
+}
+
+/// Reconciled reading of both backends
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnsembleReading {
+    /// Chosen text
+    pub text: String,
+    /// Confidence after voting
+    pub confidence: Option<f32>,
+    /// How the backends related
+    pub agreement: Agreement,
+}
+
+/// Reconcile the readings of the primary and secondary backend
+///
+/// Agreement combines the confidences as independent votes
+/// (`1 - (1 - a)(1 - b)`). On disagreement the reading that matches the
+/// content type's pattern wins; if both or neither match, the more confident
+/// one wins, with ties going to the primary backend.
+pub fn reconcile(
+    primary: Reading,
+    secondary: Reading,
+    content_type: &ContentType,
+) -> EnsembleReading {
+    let primary_empty = primary.text.trim().is_empty();
+    let secondary_empty = secondary.text.trim().is_empty();
+
+    if primary_empty || secondary_empty {
+        let reading = if primary_empty && !secondary_empty {
+            secondary
+        } else {
+            primary
+        };
+        return EnsembleReading {
+            text: reading.text,
+            confidence: reading.confidence,
+            agreement: Agreement::Single,
+        };
+    }
+
+    if normalize(&primary.text) == normalize(&secondary.text) {
+        let confidence = match (primary.confidence, secondary.confidence) {
+            (Some(a), Some(b)) => Some(1.0 - (1.0 - a) * (1.0 - b)),
+            (a, b) => a.or(b),
+        };
+        return EnsembleReading {
+            text: primary.text,
+            confidence,
+            agreement: Agreement::Agreed,
+        };
+    }
+
+    let primary_valid = is_valid_reading(&primary.text, content_type);
+    let secondary_valid = is_valid_reading(&secondary.text, content_type);
+    let use_secondary = if primary_valid != secondary_valid {
+        secondary_valid
+    } else {
+        secondary.confidence.unwrap_or(0.0) > primary.confidence.unwrap_or(0.0)
+    };
+
+    let reading = if use_secondary { secondary } else { primary };
+    EnsembleReading {
+        text: reading.text,
+        confidence: reading.confidence,
+        agreement: Agreement::Disagreed,
+    }
+}
+
+/// Compare readings ignoring case and whitespace
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(text: &str, confidence: f32) -> Reading {
+        Reading {
+            text: text.to_string(),
+            confidence: Some(confidence),
+        }
+    }
+
+    #[test]
+    fn test_agreement_boosts_confidence() {
+        let result = reconcile(
+            reading("120", 0.6),
+            reading("120", 0.5),
+            &ContentType::Number,
+        );
+        assert_eq!(result.agreement, Agreement::Agreed);
+        assert!((result.confidence.unwrap() - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_disagreement_prefers_valid_reading() {
+        let result = reconcile(
+            reading("1:205", 0.9),
+            reading("1:20", 0.4),
+            &ContentType::Time,
+        );
+        assert_eq!(result.agreement, Agreement::Disagreed);
+        assert_eq!(result.text, "1:20");
+
+        // Both valid: the more confident one wins
+        let result = reconcile(
+            reading("120", 0.5),
+            reading("128", 0.7),
+            &ContentType::Number,
+        );
+        assert_eq!(result.text, "128");
+    }
+
+    #[test]
+    fn test_single_reading() {
+        let result = reconcile(reading("", 0.0), reading("Gold", 0.8), &ContentType::Text);
+        assert_eq!(result.agreement, Agreement::Single);
+        assert_eq!(result.text, "Gold");
+    }
+}
@@ -0,0 +1,285 @@
+//! Feature-based template matching
+//!
+//! An ORB-style matcher for elements the game may scale or rotate slightly
+//! (minimaps, rotating compasses): oriented FAST keypoints described with
+//! rotation-steered BRIEF, matched by Hamming distance and verified with a
+//! RANSAC homography. Slower than NCC, so templates opt into it.
+
+use image::GrayImage;
+use imageproc::corners::oriented_fast;
+use imageproc::filter::box_filter;
+use imageproc::geometric_transformations::Projection;
+use std::sync::OnceLock;
+
+/// Keypoints kept per image
+const MAX_KEYPOINTS: usize = 300;
+
+/// Radius of the patch around a keypoint; keypoints closer to the edge are skipped
+const PATCH_RADIUS: u32 = 15;
+
+/// Test points lie within this radius, so they stay in the patch when rotated
+const TEST_RADIUS: f32 = 12.0;
+
+/// Descriptor length in 64-bit words (256 intensity tests)
+pub const DESCRIPTOR_WORDS: usize = 4;
+
+/// Descriptors further apart than this never match
+const MAX_DISTANCE: u32 = 64;
+
+/// The best match must be this much closer than the second best (Lowe's ratio test)
+const MAX_DISTANCE_RATIO: f32 = 0.8;
+
+/// Homographies tried by RANSAC
+const RANSAC_ITERATIONS: usize = 200;
+
+/// Reprojection error (pixels) within which a match agrees with a homography
+const INLIER_ERROR: f32 = 3.0;
+
+/// Inliers needed to consider the template found at all
+const MIN_INLIERS: usize = 6;
+
+/// Inliers at which the match count no longer limits the confidence
+const CONFIDENT_INLIERS: usize = 20;
+
+/// Seed for keypoint thresholds, test points and RANSAC, so results are repeatable
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+type Descriptor = [u64; DESCRIPTOR_WORDS];
+
+/// Two points (relative to the keypoint) whose intensities a descriptor bit compares
+type TestPair = ((f32, f32), (f32, f32));
+
+/// Keypoints and their descriptors in an image
+#[derive(Debug, Clone, Default)]
+pub struct Features {
+    /// Keypoint positions
+    points: Vec<(f32, f32)>,
+    /// Descriptor of each keypoint
+    descriptors: Vec<Descriptor>,
+}
+
+impl Features {
+    /// Find and describe keypoints
+    pub fn detect(image: &GrayImage) -> Self {
+        let (width, height) = image.dimensions();
+        let edge = PATCH_RADIUS + 1;
+        if width <= 2 * edge + 2 || height <= 2 * edge + 2 {
+            return Self::default();
+        }
+
+        let corners = oriented_fast(image, None, MAX_KEYPOINTS, edge, Some(SEED));
+        // Tests compare small neighbourhood averages rather than single pixels
+        let smoothed = box_filter(image, 2, 2);
+
+        let mut features = Self::default();
+        for corner in corners {
+            let (x, y) = (corner.corner.x as f32, corner.corner.y as f32);
+            let (sin, cos) = corner.orientation.sin_cos();
+            let sample = |(dx, dy): (f32, f32)| {
+                let px = (x + dx * cos - dy * sin).round() as u32;
+                let py = (y + dx * sin + dy * cos).round() as u32;
+                smoothed.get_pixel(px, py).0[0]
+            };
+
+            let mut descriptor = [0u64; DESCRIPTOR_WORDS];
+            for (i, &(a, b)) in test_pairs().iter().enumerate() {
+                if sample(a) < sample(b) {
+                    descriptor[i / 64] |= 1 << (i % 64);
+                }
+            }
+            features.points.push((x, y));
+            features.descriptors.push(descriptor);
+        }
+        features
+    }
+
+    /// Number of keypoints
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether no keypoints were found
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Keypoint positions and their descriptors, for caching
+    pub fn parts(&self) -> (&[(f32, f32)], &[[u64; DESCRIPTOR_WORDS]]) {
+        (&self.points, &self.descriptors)
+    }
+
+    /// Features from cached keypoints and descriptors (None if their counts differ)
+    pub fn from_parts(
+        points: Vec<(f32, f32)>,
+        descriptors: Vec<[u64; DESCRIPTOR_WORDS]>,
+    ) -> Option<Self> {
+        (points.len() == descriptors.len()).then_some(Self {
+            points,
+            descriptors,
+        })
+    }
+}
+
+/// Confidence (0.0-1.0) that the template appears in the region, possibly
+/// scaled or rotated
+pub fn similarity(template: &Features, region: &Features) -> f32 {
+    let matches = match_descriptors(template, region);
+    if matches.len() < MIN_INLIERS {
+        return 0.0;
+    }
+
+    let inliers = ransac_inliers(template, region, &matches);
+    if inliers < MIN_INLIERS {
+        return 0.0;
+    }
+
+    let inlier_ratio = inliers as f32 / matches.len() as f32;
+    let support = inliers.min(CONFIDENT_INLIERS) as f32 / CONFIDENT_INLIERS as f32;
+    inlier_ratio * support
+}
+
+/// Template/region keypoint index pairs passing the distance and ratio tests
+fn match_descriptors(template: &Features, region: &Features) -> Vec<(usize, usize)> {
+    let distance = |a: &Descriptor, b: &Descriptor| -> u32 {
+        a.iter().zip(b).map(|(a, b)| (a ^ b).count_ones()).sum()
+    };
+
+    template
+        .descriptors
+        .iter()
+        .enumerate()
+        .filter_map(|(t, descriptor)| {
+            let (mut best, mut second) = ((u32::MAX, 0), u32::MAX);
+            for (r, other) in region.descriptors.iter().enumerate() {
+                let d = distance(descriptor, other);
+                if d < best.0 {
+                    second = best.0;
+                    best = (d, r);
+                } else if d < second {
+                    second = d;
+                }
+            }
+            let passes_ratio =
+                second == u32::MAX || (best.0 as f32) < MAX_DISTANCE_RATIO * second as f32;
+            (best.0 <= MAX_DISTANCE && passes_ratio).then_some((t, best.1))
+        })
+        .collect()
+}
+
+/// Most matches agreeing with a single homography
+fn ransac_inliers(template: &Features, region: &Features, matches: &[(usize, usize)]) -> usize {
+    let mut rng = XorShift(SEED);
+    let mut best = 0;
+
+    for _ in 0..RANSAC_ITERATIONS {
+        let mut sample = [0usize; 4];
+        for i in 0..4 {
+            sample[i] = loop {
+                let candidate = rng.below(matches.len());
+                if !sample[..i].contains(&candidate) {
+                    break candidate;
+                }
+            };
+        }
+
+        let from = sample.map(|i| template.points[matches[i].0]);
+        let to = sample.map(|i| region.points[matches[i].1]);
+        let Some(projection) = Projection::from_control_points(from, to) else {
+            continue;
+        };
+
+        let inliers = matches
+            .iter()
+            .filter(|&&(t, r)| {
+                let (x, y) = projection * template.points[t];
+                let (rx, ry) = region.points[r];
+                (x - rx).hypot(y - ry) < INLIER_ERROR
+            })
+            .count();
+        best = best.max(inliers);
+        if best == matches.len() {
+            break;
+        }
+    }
+    best
+}
+
+/// Point pairs compared by the descriptor, relative to the keypoint
+fn test_pairs() -> &'static [TestPair] {
+    static PAIRS: OnceLock<Vec<TestPair>> = OnceLock::new();
+    PAIRS.get_or_init(|| {
+        let mut rng = XorShift(SEED);
+        let mut point = || loop {
+            let x = (rng.unit() * 2.0 - 1.0) * TEST_RADIUS;
+            let y = (rng.unit() * 2.0 - 1.0) * TEST_RADIUS;
+            if x.hypot(y) <= TEST_RADIUS {
+                break (x, y);
+            }
+        };
+        (0..DESCRIPTOR_WORDS * 64)
+            .map(|_| (point(), point()))
+            .collect()
+    })
+}
+
+/// Small deterministic random number generator
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Uniform in `0.0..1.0`
+    fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{imageops, Luma};
+
+    /// Blocky random texture with plenty of corners
+    fn texture(width: u32, height: u32, seed: u64) -> GrayImage {
+        let mut rng = XorShift(seed);
+        let blocks: Vec<u8> = (0..64 * 64).map(|_| (rng.next() % 256) as u8).collect();
+        GrayImage::from_fn(width, height, |x, y| {
+            Luma([blocks[((y / 8) % 64 * 64 + (x / 8) % 64) as usize]])
+        })
+    }
+
+    #[test]
+    fn test_finds_scaled_template() {
+        let frame = texture(200, 200, 7);
+        let template = imageops::crop_imm(&frame, 40, 40, 120, 120).to_image();
+        let scaled = imageops::resize(&template, 138, 138, imageops::FilterType::Triangle);
+
+        let template = Features::detect(&template);
+        assert!(!template.is_empty());
+        assert!(similarity(&template, &template) > 0.9);
+        assert!(similarity(&template, &Features::detect(&scaled)) > 0.5);
+    }
+
+    #[test]
+    fn test_rejects_other_content() {
+        let template = Features::detect(&texture(120, 120, 7));
+        let other = Features::detect(&texture(120, 120, 99));
+        assert!(similarity(&template, &other) < 0.2);
+    }
+
+    #[test]
+    fn test_small_images_have_no_features() {
+        assert!(Features::detect(&GrayImage::new(20, 20)).is_empty());
+        assert_eq!(similarity(&Features::default(), &Features::default()), 0.0);
+    }
+}
@@ -0,0 +1,117 @@
+//! GPU adapter selection
+//!
+//! On hybrid laptops DirectML and wgpu don't necessarily pick the GPU the
+//! game runs on. Adapters are listed through DXGI, whose enumeration order is
+//! what DirectML's device id refers to, and the one chosen in the settings is
+//! found again by name, since indices shift when adapters come and go.
+
+use anyhow::{Context, Result};
+use windows::Win32::Graphics::Dxgi::{
+    CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE,
+};
+
+/// A hardware display adapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuAdapter {
+    /// DXGI enumeration index (DirectML's device id)
+    pub index: u32,
+    /// Adapter description, e.g. "NVIDIA GeForce RTX 4060 Laptop GPU"
+    pub name: String,
+    /// PCI vendor id
+    pub vendor_id: u32,
+    /// PCI device id
+    pub device_id: u32,
+    /// Dedicated video memory in MB (small for integrated GPUs)
+    pub dedicated_memory_mb: u64,
+}
+
+impl GpuAdapter {
+    /// Name with the dedicated memory, for pickers
+    pub fn label(&self) -> String {
+        if self.dedicated_memory_mb >= 1024 {
+            format!(
+                "{} ({:.1} GB)",
+                self.name,
+                self.dedicated_memory_mb as f64 / 1024.0
+            )
+        } else {
+            format!("{} ({} MB)", self.name, self.dedicated_memory_mb)
+        }
+    }
+}
+
+/// List the hardware (non-software) display adapters in DXGI order
+pub fn list_adapters() -> Result<Vec<GpuAdapter>> {
+    let mut adapters = Vec::new();
+
+    // SAFETY: plain DXGI enumeration, every returned interface is owned and released on drop
+    unsafe {
+        let factory =
+            CreateDXGIFactory1::<IDXGIFactory1>().context("Failed to create DXGI factory")?;
+
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            let current = index;
+            index += 1;
+            let Ok(desc) = adapter.GetDesc1() else {
+                continue;
+            };
+            if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+                continue;
+            }
+            let len = desc
+                .Description
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(desc.Description.len());
+            adapters.push(GpuAdapter {
+                index: current,
+                name: String::from_utf16_lossy(&desc.Description[..len]),
+                vendor_id: desc.VendorId,
+                device_id: desc.DeviceId,
+                dedicated_memory_mb: desc.DedicatedVideoMemory as u64 / (1024 * 1024),
+            });
+        }
+    }
+
+    Ok(adapters)
+}
+
+/// The adapter chosen by name, if it's still present
+pub fn find_adapter<'a>(adapters: &'a [GpuAdapter], name: &str) -> Option<&'a GpuAdapter> {
+    adapters.iter().find(|a| a.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(index: u32, name: &str, dedicated_memory_mb: u64) -> GpuAdapter {
+        GpuAdapter {
+            index,
+            name: name.to_string(),
+            vendor_id: 0x10de,
+            device_id: 0x28e0,
+            dedicated_memory_mb,
+        }
+    }
+
+    #[test]
+    fn test_find_adapter_by_name() {
+        let adapters = [
+            adapter(0, "Intel(R) UHD Graphics", 128),
+            adapter(1, "NVIDIA GeForce RTX 4060 Laptop GPU", 8192),
+        ];
+        assert_eq!(
+            find_adapter(&adapters, "NVIDIA GeForce RTX 4060 Laptop GPU").map(|a| a.index),
+            Some(1)
+        );
+        assert!(find_adapter(&adapters, "AMD Radeon RX 7600").is_none());
+
+        assert_eq!(adapters[0].label(), "Intel(R) UHD Graphics (128 MB)");
+        assert_eq!(
+            adapters[1].label(),
+            "NVIDIA GeForce RTX 4060 Laptop GPU (8.0 GB)"
+        );
+    }
+}
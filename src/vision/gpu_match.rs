@@ -0,0 +1,328 @@
+//! GPU template matching
+//!
+//! Computes the normalized cross-correlation of a batch of image/template
+//! pairs in a single wgpu compute dispatch (Direct3D 12 on Windows), so
+//! profiles with many screens and anchors don't spend the frame on the CPU.
+//! Each invocation sums one row of one pair; rows are combined on the CPU.
+
+use anyhow::{Context, Result};
+use image::GrayImage;
+use wgpu::util::DeviceExt;
+
+use crate::vision::gpu_adapters::GpuAdapter;
+use crate::vision::screen_recognition::ncc_from_sums;
+
+/// Sums per row: image x template, image², template², image, template
+const SUMS_PER_ROW: usize = 5;
+
+/// Rows summed by one workgroup
+const WORKGROUP_SIZE: u32 = 64;
+
+const SHADER: &str = r#"
+struct Pair {
+    image_offset: u32,
+    image_stride: u32,
+    template_offset: u32,
+    template_stride: u32,
+    width: u32,
+    height: u32,
+    row_offset: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> images: array<u32>;
+@group(0) @binding(1) var<storage, read> templates: array<u32>;
+@group(0) @binding(2) var<storage, read> pairs: array<Pair>;
+@group(0) @binding(3) var<storage, read_write> sums: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let pair = pairs[id.y];
+    let row = id.x;
+    if (row >= pair.height) {
+        return;
+    }
+
+    var sum_it = 0.0;
+    var sum_i2 = 0.0;
+    var sum_t2 = 0.0;
+    var sum_i = 0.0;
+    var sum_t = 0.0;
+    for (var x = 0u; x < pair.width; x++) {
+        // Centered on mid-gray to keep f32 sums precise; the correlation
+        // doesn't change when a constant is subtracted
+        let i = f32(images[pair.image_offset + row * pair.image_stride + x]) - 128.0;
+        let t = f32(templates[pair.template_offset + row * pair.template_stride + x]) - 128.0;
+        sum_it += i * t;
+        sum_i2 += i * i;
+        sum_t2 += t * t;
+        sum_i += i;
+        sum_t += t;
+    }
+
+    let out = (pair.row_offset + row) * 5u;
+    sums[out] = sum_it;
+    sums[out + 1u] = sum_i2;
+    sums[out + 2u] = sum_t2;
+    sums[out + 3u] = sum_i;
+    sums[out + 4u] = sum_t;
+}
+"#;
+
+/// Where a pair's pixels are in the uploaded buffers (matches the shader's `Pair`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PairInfo {
+    image_offset: u32,
+    image_stride: u32,
+    template_offset: u32,
+    template_stride: u32,
+    width: u32,
+    height: u32,
+    row_offset: u32,
+    _pad: u32,
+}
+
+/// Template matcher running on the GPU
+pub struct GpuMatcher {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    adapter_name: String,
+}
+
+impl std::fmt::Debug for GpuMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GpuMatcher")
+            .field("adapter_name", &self.adapter_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GpuMatcher {
+    /// Set up the compute pipeline on the chosen adapter, or the default
+    /// high-performance one
+    pub fn new(preferred: Option<&GpuAdapter>) -> Result<Self> {
+        let instance = wgpu::Instance::default();
+        let chosen = preferred.and_then(|preferred| {
+            let found = instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .into_iter()
+                .find(|adapter| {
+                    let info = adapter.get_info();
+                    info.vendor == preferred.vendor_id && info.device == preferred.device_id
+                });
+            if found.is_none() {
+                tracing::warn!(
+                    "GPU '{}' not available for template matching, using the default",
+                    preferred.name
+                );
+            }
+            found
+        });
+        let adapter = match chosen {
+            Some(adapter) => adapter,
+            None => pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            }))
+            .context("No GPU adapter available")?,
+        };
+        let adapter_name = adapter.get_info().name;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("template_matching"),
+                ..Default::default()
+            },
+            None,
+        ))
+        .with_context(|| format!("Failed to open GPU '{}'", adapter_name))?;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("template_matching"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("template_matching"),
+            entries: &[
+                storage(0, true),
+                storage(1, true),
+                storage(2, true),
+                storage(3, false),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("template_matching"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("template_matching"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "main",
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        tracing::info!("GPU template matching on '{}'", adapter_name);
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            adapter_name,
+        })
+    }
+
+    /// Name of the GPU in use
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    /// Similarity (0.0-1.0) of each image to its template
+    ///
+    /// Pairs are compared over their common size, like the CPU path.
+    pub fn similarities(&self, pairs: &[(&GrayImage, &GrayImage)]) -> Result<Vec<f32>> {
+        if pairs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Storage bindings can't be empty, hence the leading zero pixel
+        let mut images = vec![0u32];
+        let mut templates = vec![0u32];
+        let mut infos = Vec::with_capacity(pairs.len());
+        let mut rows = 0u32;
+        for (image, template) in pairs {
+            let width = image.width().min(template.width());
+            let height = image.height().min(template.height());
+            infos.push(PairInfo {
+                image_offset: images.len() as u32,
+                image_stride: image.width(),
+                template_offset: templates.len() as u32,
+                template_stride: template.width(),
+                width,
+                height,
+                row_offset: rows,
+                _pad: 0,
+            });
+            images.extend(image.as_raw().iter().map(|&p| p as u32));
+            templates.extend(template.as_raw().iter().map(|&p| p as u32));
+            rows += height;
+        }
+
+        let buffer = |label, contents: &[u8]| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents,
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+        };
+        let images = buffer("images", bytemuck::cast_slice(&images));
+        let templates = buffer("templates", bytemuck::cast_slice(&templates));
+        let infos_buffer = buffer("pairs", bytemuck::cast_slice(&infos));
+
+        let sums_size = ((rows.max(1) as usize) * SUMS_PER_ROW * 4) as u64;
+        let sums = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sums"),
+            size: sums_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sums_readback"),
+            size: sums_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("template_matching"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: images.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: templates.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: infos_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: sums.as_entire_binding(),
+                },
+            ],
+        });
+
+        let max_height = infos.iter().map(|info| info.height).max().unwrap_or(0);
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("template_matching"),
+            });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("template_matching"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                max_height.div_ceil(WORKGROUP_SIZE).max(1),
+                infos.len() as u32,
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&sums, 0, &readback, 0, sums_size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .context("GPU readback was dropped")?
+            .context("Failed to read GPU results")?;
+
+        let row_sums: Vec<f32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+
+        Ok(infos
+            .iter()
+            .map(|info| {
+                let start = info.row_offset as usize * SUMS_PER_ROW;
+                let end = start + info.height as usize * SUMS_PER_ROW;
+                let mut totals = [0.0f64; SUMS_PER_ROW];
+                for row in row_sums[start..end].chunks_exact(SUMS_PER_ROW) {
+                    for (total, &sum) in totals.iter_mut().zip(row) {
+                        *total += sum as f64;
+                    }
+                }
+                ncc_from_sums(totals, (info.width * info.height) as f64)
+            })
+            .collect())
+    }
+}
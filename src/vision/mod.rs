@@ -6,23 +6,43 @@
 //! - Windows OCR API (recommended for game text)
 //! - PaddleOCR via ONNX Runtime
 
+pub mod auto_tune;
+pub mod backend_compare;
+pub mod batch;
+pub mod calibration;
+pub mod char_whitelist;
+pub mod color_trigger;
 pub mod detection;
+pub mod dictionary;
+pub mod ensemble;
+pub mod feature_match;
+pub mod gpu_adapters;
+pub mod gpu_match;
 pub mod models;
 pub mod ocr;
 pub mod ocr_preprocess;
 pub mod preprocess;
+pub mod scene;
 pub mod screen_recognition;
+pub mod scroll_stitch;
+pub mod template_cache;
+pub mod validation;
 pub mod windows_ocr;
+pub mod zone_stats;
+pub mod zone_text;
 
 pub use ocr_preprocess::apply_preprocessing_with_scale;
 
 use anyhow::Result;
-use std::time::Instant;
+use crossbeam_channel::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
-use crate::capture::frame::CapturedFrame;
+use crate::capture::diff::FrameDiff;
+use crate::capture::frame::{CapturedFrame, PixelFormat};
+use crate::storage::profiles::ConfidenceCalibration;
 
-pub use models::{ModelManager, ModelType};
+pub use models::{ModelIntegrity, ModelManager, ModelType, ModelVariant};
 pub use ocr::OcrEngine;
 pub use screen_recognition::{ScreenMatch, ScreenRecognizer};
 pub use windows_ocr::WindowsOcr;
@@ -38,6 +58,16 @@ pub enum OcrBackend {
     PaddleOcr,
 }
 
+impl OcrBackend {
+    /// The other backend (used as the second voter in ensemble mode)
+    pub fn other(&self) -> Self {
+        match self {
+            OcrBackend::WindowsOcr => OcrBackend::PaddleOcr,
+            OcrBackend::PaddleOcr => OcrBackend::WindowsOcr,
+        }
+    }
+}
+
 /// OCR result granularity
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -49,6 +79,50 @@ pub enum OcrGranularity {
     Line,
 }
 
+/// PaddleOCR trade-off between reading accuracy and speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrQuality {
+    /// Detect text at a higher resolution
+    Accurate,
+    /// The standard models at the standard resolution
+    #[default]
+    Balanced,
+    /// Lower resolution and the int8 models when they're installed
+    Fast,
+}
+
+impl OcrQuality {
+    /// All levels, from most accurate to fastest
+    pub const ALL: [OcrQuality; 3] = [OcrQuality::Accurate, OcrQuality::Balanced, OcrQuality::Fast];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            OcrQuality::Accurate => "Accurate",
+            OcrQuality::Balanced => "Balanced",
+            OcrQuality::Fast => "Fast",
+        }
+    }
+
+    /// Size text detection scales frames to
+    pub fn detection_size(&self) -> u32 {
+        match self {
+            OcrQuality::Accurate => 1280,
+            OcrQuality::Balanced => 960,
+            OcrQuality::Fast => 640,
+        }
+    }
+
+    /// Model set to load, if it's installed
+    pub fn model_variant(&self) -> ModelVariant {
+        match self {
+            OcrQuality::Fast => ModelVariant::Quantized,
+            OcrQuality::Accurate | OcrQuality::Balanced => ModelVariant::Standard,
+        }
+    }
+}
+
 /// Detected text region from OCR
 #[derive(Debug, Clone)]
 pub struct TextRegion {
@@ -82,6 +156,10 @@ pub struct VisionConfig {
     pub recognition_threshold: f32,
     /// Whether to use GPU acceleration (PaddleOCR only)
     pub use_gpu: bool,
+    /// DirectML adapter as a DXGI index (`None` = Windows' default)
+    pub gpu_device_id: Option<u32>,
+    /// PaddleOCR accuracy vs speed
+    pub quality: OcrQuality,
     /// Maximum image dimension for processing (larger images are scaled down)
     pub max_image_size: u32,
     /// Language for Windows OCR (e.g., "en-US")
@@ -95,6 +173,8 @@ impl Default for VisionConfig {
             detection_threshold: 0.5,
             recognition_threshold: 0.5,
             use_gpu: true,
+            gpu_device_id: None,
+            quality: OcrQuality::default(),
             max_image_size: 1920,
             ocr_language: "en-US".to_string(),
         }
@@ -111,6 +191,16 @@ pub struct VisionPipeline {
     config: VisionConfig,
     /// Model manager for PaddleOCR
     model_manager: ModelManager,
+    /// Confidence calibration of the active profile
+    calibration: Vec<ConfidenceCalibration>,
+    /// Model set PaddleOCR is (being) loaded from
+    paddle_variant: ModelVariant,
+    /// PaddleOCR loading on a background thread
+    paddle_warm_up: Option<Receiver<Result<OcrEngine>>>,
+    /// Last time PaddleOCR was loaded or read something
+    paddle_last_used: Option<Instant>,
+    /// Zone regions as they were last read, to skip unchanged ones
+    region_diff: FrameDiff,
 }
 
 impl VisionPipeline {
@@ -128,6 +218,11 @@ impl VisionPipeline {
             windows_ocr: None,
             config,
             model_manager,
+            calibration: Vec::new(),
+            paddle_variant: ModelVariant::Standard,
+            paddle_warm_up: None,
+            paddle_last_used: None,
+            region_diff: FrameDiff::default(),
         })
     }
 
@@ -141,6 +236,76 @@ impl VisionPipeline {
         self.config.backend = backend;
     }
 
+    /// PaddleOCR accuracy vs speed
+    pub fn quality(&self) -> OcrQuality {
+        self.config.quality
+    }
+
+    /// Set PaddleOCR's accuracy vs speed
+    ///
+    /// The detection size changes right away. If the quality needs another
+    /// model set, PaddleOCR (or its warm-up) is dropped to load that set on
+    /// next use, and this returns true.
+    pub fn set_quality(&mut self, quality: OcrQuality) -> bool {
+        if self.config.quality == quality {
+            return false;
+        }
+        self.config.quality = quality;
+        if let Some(ref mut ocr) = self.paddle_ocr {
+            ocr.set_detection_size(quality.detection_size());
+        }
+        let loaded = self.paddle_ocr.is_some() || self.paddle_warm_up.is_some();
+        if loaded && self.model_variant() != self.paddle_variant {
+            info!(
+                "Unloading PaddleOCR to switch to the {:?} models",
+                self.model_variant()
+            );
+            self.paddle_ocr = None;
+            self.paddle_warm_up = None;
+            return true;
+        }
+        false
+    }
+
+    /// Model set the quality calls for, or the standard one if it isn't installed
+    pub fn model_variant(&self) -> ModelVariant {
+        let variant = self.config.quality.model_variant();
+        if self.model_manager.is_variant_available(variant) {
+            variant
+        } else {
+            ModelVariant::Standard
+        }
+    }
+
+    /// DirectML adapter PaddleOCR loads on
+    pub fn gpu_device_id(&self) -> Option<u32> {
+        self.config.gpu_device_id
+    }
+
+    /// Set the DirectML adapter (applies the next time PaddleOCR loads)
+    pub fn set_gpu_device_id(&mut self, device_id: Option<u32>) {
+        self.config.gpu_device_id = device_id;
+    }
+
+    /// Confidence calibration applied before the recognition threshold
+    pub fn calibration(&self) -> &[ConfidenceCalibration] {
+        &self.calibration
+    }
+
+    /// Set the confidence calibration (empty = raw confidences)
+    pub fn set_calibration(&mut self, calibration: Vec<ConfidenceCalibration>) {
+        self.calibration = calibration;
+    }
+
+    /// Restrict PaddleOCR decoding to a character whitelist (`None` = any)
+    ///
+    /// Windows OCR can't be constrained; its output is filtered afterwards.
+    pub fn set_allowed_chars(&mut self, allowed: Option<&str>) {
+        if let Some(ref mut ocr) = self.paddle_ocr {
+            ocr.set_allowed_chars(allowed);
+        }
+    }
+
     /// Check if models are ready (for PaddleOCR)
     pub fn are_models_ready(&self) -> bool {
         self.model_manager.are_models_ready()
@@ -153,7 +318,12 @@ impl VisionPipeline {
 
     /// Initialize the OCR engine for the current backend
     pub fn init_ocr(&mut self) -> Result<()> {
-        match self.config.backend {
+        self.init_backend(self.config.backend)
+    }
+
+    /// Initialize the OCR engine for a specific backend without switching to it
+    pub fn init_backend(&mut self, backend: OcrBackend) -> Result<()> {
+        match backend {
             OcrBackend::WindowsOcr => self.init_windows_ocr(),
             OcrBackend::PaddleOcr => self.init_paddle_ocr(),
         }
@@ -178,32 +348,176 @@ impl VisionPipeline {
             return Ok(());
         }
 
-        info!("Initializing PaddleOCR backend");
+        // Finish a warm-up rather than loading the models twice
+        if let Some(warm_up) = self.paddle_warm_up.take() {
+            info!("Waiting for PaddleOCR warm-up");
+            if let Ok(engine) = warm_up.recv() {
+                let mut engine = engine?;
+                engine.set_detection_size(self.config.quality.detection_size());
+                self.paddle_ocr = Some(engine);
+                self.paddle_last_used = Some(Instant::now());
+                return Ok(());
+            }
+        }
 
-        // Ensure models are available
-        let det_path = self.model_manager.ensure_model(ModelType::Detection)?;
-        let rec_path = self.model_manager.ensure_model(ModelType::Recognition)?;
+        let variant = self.model_variant();
+        info!("Initializing PaddleOCR backend ({:?} models)", variant);
+
+        // Ensure models are available (only the standard ones are downloaded)
+        let (det_path, rec_path) = match variant {
+            ModelVariant::Standard => (
+                self.model_manager.ensure_model(ModelType::Detection)?,
+                self.model_manager.ensure_model(ModelType::Recognition)?,
+            ),
+            ModelVariant::Quantized => (
+                self.model_manager
+                    .variant_path(ModelType::Detection, variant),
+                self.model_manager
+                    .variant_path(ModelType::Recognition, variant),
+            ),
+        };
 
         // Initialize OCR engine
-        let ocr_engine = OcrEngine::new(
+        let mut ocr_engine = OcrEngine::new(
             det_path.to_str().unwrap(),
             rec_path.to_str().unwrap(),
             self.config.use_gpu,
+            self.config.gpu_device_id,
         )?;
+        ocr_engine.set_detection_size(self.config.quality.detection_size());
 
         self.paddle_ocr = Some(ocr_engine);
+        self.paddle_variant = variant;
+        self.paddle_last_used = Some(Instant::now());
         info!("PaddleOCR initialized successfully");
         Ok(())
     }
 
+    /// Load PaddleOCR on a background thread so its first use doesn't stall
+    ///
+    /// Also reads a blank image once, which compiles the GPU kernels. Does nothing
+    /// (and returns false) if PaddleOCR is loaded or loading, or its models
+    /// haven't been downloaded.
+    pub fn warm_up_paddle(&mut self) -> bool {
+        let variant = self.model_variant();
+        if self.paddle_ocr.is_some()
+            || self.paddle_warm_up.is_some()
+            || !self.model_manager.is_variant_available(variant)
+        {
+            return false;
+        }
+
+        let det_path = self
+            .model_manager
+            .variant_path(ModelType::Detection, variant);
+        let rec_path = self
+            .model_manager
+            .variant_path(ModelType::Recognition, variant);
+        let (use_gpu, gpu_device_id) = (self.config.use_gpu, self.config.gpu_device_id);
+        let detection_size = self.config.quality.detection_size();
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        std::thread::spawn(move || {
+            let start = Instant::now();
+            let engine = OcrEngine::new(
+                &det_path.to_string_lossy(),
+                &rec_path.to_string_lossy(),
+                use_gpu,
+                gpu_device_id,
+            )
+            .and_then(|mut engine| {
+                engine.set_detection_size(detection_size);
+                const SIZE: u32 = 64;
+                engine.recognize(&vec![255; (SIZE * SIZE * 4) as usize], SIZE, SIZE)?;
+                Ok(engine)
+            });
+            if engine.is_ok() {
+                info!("PaddleOCR warmed up in {:?}", start.elapsed());
+            }
+            let _ = sender.send(engine);
+        });
+
+        self.paddle_warm_up = Some(receiver);
+        self.paddle_variant = variant;
+        info!(
+            "Warming up PaddleOCR in the background ({:?} models)",
+            variant
+        );
+        true
+    }
+
+    /// Whether PaddleOCR is loading in the background
+    pub fn is_warming_up(&self) -> bool {
+        self.paddle_warm_up.is_some()
+    }
+
+    /// Pick up a finished warm-up
+    ///
+    /// Returns `None` while none is finished, or the outcome once.
+    pub fn poll_warm_up(&mut self) -> Option<Result<()>> {
+        let result = match self.paddle_warm_up.as_ref()?.try_recv() {
+            Ok(engine) => engine,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("PaddleOCR warm-up stopped")),
+        };
+        self.paddle_warm_up = None;
+        Some(result.map(|engine| {
+            self.paddle_ocr = Some(engine);
+            self.paddle_last_used = Some(Instant::now());
+        }))
+    }
+
+    /// Unload PaddleOCR once it hasn't been used for `idle`
+    ///
+    /// Frees its memory (and GPU memory); the next use loads it again.
+    /// Returns whether it was unloaded.
+    pub fn unload_idle_paddle(&mut self, idle: Duration) -> bool {
+        let is_idle = self
+            .paddle_last_used
+            .is_some_and(|used| used.elapsed() >= idle);
+        if self.paddle_ocr.is_none() || !is_idle {
+            return false;
+        }
+        self.paddle_ocr = None;
+        self.paddle_last_used = None;
+        info!("Unloaded PaddleOCR after {:?} idle", idle);
+        true
+    }
+
     /// Check if OCR is initialized for the current backend
     pub fn is_ocr_ready(&self) -> bool {
-        match self.config.backend {
+        self.is_backend_ready(self.config.backend)
+    }
+
+    /// Check if OCR is initialized for a specific backend
+    pub fn is_backend_ready(&self, backend: OcrBackend) -> bool {
+        match backend {
             OcrBackend::WindowsOcr => self.windows_ocr.is_some(),
             OcrBackend::PaddleOcr => self.paddle_ocr.is_some(),
         }
     }
 
+    /// Whether a region (a zone, by ID) changed since it was last read, so
+    /// OCR of an unchanged one can be skipped
+    ///
+    /// See [`FrameDiff::changed`].
+    pub fn region_changed(
+        &mut self,
+        key: &str,
+        frame: &CapturedFrame,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> bool {
+        self.region_diff.changed(key, frame, x, y, width, height)
+    }
+
+    /// Count every region as changed next time, e.g. after its zone or
+    /// preprocessing changed
+    pub fn reset_region_diff(&mut self) {
+        self.region_diff.reset();
+    }
+
     /// Process a captured frame and extract text/visual elements
     pub fn process(&mut self, frame: &CapturedFrame) -> Result<VisionResult> {
         self.process_with_granularity(frame, OcrGranularity::Word)
@@ -216,19 +530,20 @@ impl VisionPipeline {
         granularity: OcrGranularity,
     ) -> Result<VisionResult> {
         let start = Instant::now();
+        let rgba = frame.rgba();
 
         let text_regions = match self.config.backend {
             OcrBackend::WindowsOcr => match granularity {
                 OcrGranularity::Word => {
-                    self.process_windows_ocr(&frame.data, frame.width, frame.height)?
+                    self.process_windows_ocr(&rgba, frame.width, frame.height)?
                 }
                 OcrGranularity::Line => {
-                    self.process_windows_ocr_lines(&frame.data, frame.width, frame.height)?
+                    self.process_windows_ocr_lines(&rgba, frame.width, frame.height)?
                 }
             },
             OcrBackend::PaddleOcr => {
                 // PaddleOCR already returns line-level results, so we use the same for both
-                self.process_paddle_ocr(&frame.data, frame.width, frame.height)?
+                self.process_paddle_ocr(&rgba, frame.width, frame.height)?
             }
         };
 
@@ -258,12 +573,12 @@ impl VisionPipeline {
 
         Ok(results
             .into_iter()
-            .filter(|r| r.confidence >= self.config.recognition_threshold)
             .map(|r| TextRegion {
                 text: r.text,
                 bounds: r.bounds,
-                confidence: r.confidence,
+                confidence: self.calibrated(r.confidence),
             })
+            .filter(|r| r.confidence >= self.config.recognition_threshold)
             .collect())
     }
 
@@ -286,7 +601,8 @@ impl VisionPipeline {
             .map(|line| TextRegion {
                 text: line.text,
                 bounds: line.bounds,
-                confidence: 1.0, // Windows OCR doesn't provide line-level confidence
+                // Windows OCR doesn't provide line-level confidence
+                confidence: self.calibrated(1.0),
             })
             .collect())
     }
@@ -301,20 +617,26 @@ impl VisionPipeline {
         let Some(ocr) = &mut self.paddle_ocr else {
             return Ok(vec![]);
         };
+        self.paddle_last_used = Some(Instant::now());
 
         let results = ocr.recognize(data, width, height)?;
 
         Ok(results
             .into_iter()
-            .filter(|r| r.confidence >= self.config.recognition_threshold)
             .map(|r| TextRegion {
                 text: r.text,
                 bounds: polygon_to_bounds(&r.polygon),
-                confidence: r.confidence,
+                confidence: self.calibrated(r.confidence),
             })
+            .filter(|r| r.confidence >= self.config.recognition_threshold)
             .collect())
     }
 
+    /// A raw confidence of the current backend, calibrated
+    fn calibrated(&self, raw: f32) -> f32 {
+        calibration::calibrate(&self.calibration, self.config.backend, raw)
+    }
+
     /// Process a specific region of a frame
     pub fn process_region(
         &mut self,
@@ -337,61 +659,41 @@ impl VisionPipeline {
         height: u32,
         preprocessing: Option<&crate::config::OcrPreprocessing>,
     ) -> Result<VisionResult> {
-        // Extract the region from the frame
-        let region_data =
-            extract_region(&frame.data, frame.width, frame.height, x, y, width, height);
-
-        // Determine auto-upscale factor for small regions
-        // Windows OCR needs ~40+ pixel height for reliable detection
-        const MIN_OCR_DIMENSION: u32 = 80;
-        let auto_scale = if height < MIN_OCR_DIMENSION || width < MIN_OCR_DIMENSION {
-            let height_scale = (MIN_OCR_DIMENSION as f32 / height as f32).ceil() as u32;
-            let width_scale = (MIN_OCR_DIMENSION as f32 / width as f32).ceil() as u32;
-            height_scale.max(width_scale).clamp(2, 4) // Scale 2x-4x for small regions
-        } else {
-            1
-        };
+        self.process_region_with_geometry(frame, x, y, width, height, preprocessing, None)
+    }
 
-        // Apply preprocessing if provided, with auto-upscaling for small regions
-        let (processed_data, proc_width, proc_height) = if let Some(pp) = preprocessing {
-            // Merge auto-scale with user's scale setting
-            let effective_scale = pp.scale.max(auto_scale);
-            if effective_scale != pp.scale {
-                info!(
-                    "Auto-upscaling small region from {}x{} by {}x (user scale: {})",
-                    width, height, effective_scale, pp.scale
-                );
-                let mut adjusted_pp = pp.clone();
-                adjusted_pp.scale = effective_scale;
-                let result =
-                    apply_preprocessing_with_scale(&region_data, width, height, &adjusted_pp);
-                (result.data, result.width, result.height)
-            } else {
-                let result = apply_preprocessing_with_scale(&region_data, width, height, pp);
-                (result.data, result.width, result.height)
-            }
-        } else if auto_scale > 1 {
-            // No preprocessing specified but region is small - apply auto-upscaling
-            info!(
-                "Auto-upscaling small region from {}x{} by {}x",
-                width, height, auto_scale
-            );
-            let auto_pp = crate::config::OcrPreprocessing {
-                enabled: true,
-                scale: auto_scale,
-                ..Default::default()
-            };
-            let result = apply_preprocessing_with_scale(&region_data, width, height, &auto_pp);
-            (result.data, result.width, result.height)
-        } else {
-            (region_data, width, height)
-        };
+    /// Process a specific region of a frame, correcting rotation and
+    /// perspective before any other preprocessing
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_region_with_geometry(
+        &mut self,
+        frame: &CapturedFrame,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        preprocessing: Option<&crate::config::OcrPreprocessing>,
+        geometry: Option<&crate::storage::profiles::ZoneGeometry>,
+    ) -> Result<VisionResult> {
+        let prepared = prepare_region(frame, x, y, width, height, preprocessing, geometry);
+        self.process_prepared_region(&prepared, x, y, width)
+    }
 
+    /// Process a region already prepared by [`prepare_region`] from the
+    /// frame region at `x`, `y` that is `width` wide
+    pub fn process_prepared_region(
+        &mut self,
+        (processed_data, proc_width, proc_height): &(Vec<u8>, u32, u32),
+        x: u32,
+        y: u32,
+        width: u32,
+    ) -> Result<VisionResult> {
+        let (proc_width, proc_height) = (*proc_width, *proc_height);
         let start = Instant::now();
 
         let text_regions = match self.config.backend {
             OcrBackend::WindowsOcr => {
-                let results = self.process_windows_ocr(&processed_data, proc_width, proc_height)?;
+                let results = self.process_windows_ocr(processed_data, proc_width, proc_height)?;
                 // Offset bounds by region position (scale back if preprocessing scaled)
                 let scale_factor = if proc_width != width {
                     width as f32 / proc_width as f32
@@ -413,7 +715,7 @@ impl VisionPipeline {
                     .collect()
             }
             OcrBackend::PaddleOcr => {
-                let results = self.process_paddle_ocr(&processed_data, proc_width, proc_height)?;
+                let results = self.process_paddle_ocr(processed_data, proc_width, proc_height)?;
                 let scale_factor = if proc_width != width {
                     width as f32 / proc_width as f32
                 } else {
@@ -450,6 +752,87 @@ impl VisionPipeline {
     }
 }
 
+/// Region of a frame as OCR gets it: cut out, geometry corrected,
+/// preprocessed and upscaled when small (RGBA data, width, height)
+pub fn prepare_region(
+    frame: &CapturedFrame,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    preprocessing: Option<&crate::config::OcrPreprocessing>,
+    geometry: Option<&crate::storage::profiles::ZoneGeometry>,
+) -> (Vec<u8>, u32, u32) {
+    // Extract the region from the frame (a grayscale frame is expanded
+    // one region at a time)
+    let region_data = match frame.format {
+        PixelFormat::Rgba8 => {
+            extract_region(&frame.data, frame.width, frame.height, x, y, width, height)
+        }
+        PixelFormat::Gray8 => {
+            let (x, y) = (x.min(frame.width), y.min(frame.height));
+            let (w, h) = (width.min(frame.width - x), height.min(frame.height - y));
+            frame
+                .extract_region(x, y, w, h)
+                .map(|region| region.rgba().into_owned())
+                .unwrap_or_default()
+        }
+    };
+
+    // Level angled or skewed text first
+    let region_data = match geometry {
+        Some(geometry) if !geometry.is_identity() => {
+            preprocess::correct_zone_geometry(&region_data, width, height, geometry)
+        }
+        _ => region_data,
+    };
+
+    // Determine auto-upscale factor for small regions
+    // Windows OCR needs ~40+ pixel height for reliable detection
+    const MIN_OCR_DIMENSION: u32 = 80;
+    let auto_scale = if height < MIN_OCR_DIMENSION || width < MIN_OCR_DIMENSION {
+        let height_scale = (MIN_OCR_DIMENSION as f32 / height as f32).ceil() as u32;
+        let width_scale = (MIN_OCR_DIMENSION as f32 / width as f32).ceil() as u32;
+        height_scale.max(width_scale).clamp(2, 4) // Scale 2x-4x for small regions
+    } else {
+        1
+    };
+
+    // Apply preprocessing if provided, with auto-upscaling for small regions
+    if let Some(pp) = preprocessing {
+        // Merge auto-scale with user's scale setting
+        let effective_scale = pp.scale.max(auto_scale);
+        if effective_scale != pp.scale {
+            info!(
+                "Auto-upscaling small region from {}x{} by {}x (user scale: {})",
+                width, height, effective_scale, pp.scale
+            );
+            let mut adjusted_pp = pp.clone();
+            adjusted_pp.scale = effective_scale;
+            let result = apply_preprocessing_with_scale(&region_data, width, height, &adjusted_pp);
+            (result.data, result.width, result.height)
+        } else {
+            let result = apply_preprocessing_with_scale(&region_data, width, height, pp);
+            (result.data, result.width, result.height)
+        }
+    } else if auto_scale > 1 {
+        // No preprocessing specified but region is small - apply auto-upscaling
+        info!(
+            "Auto-upscaling small region from {}x{} by {}x",
+            width, height, auto_scale
+        );
+        let auto_pp = crate::config::OcrPreprocessing {
+            enabled: true,
+            scale: auto_scale,
+            ..Default::default()
+        };
+        let result = apply_preprocessing_with_scale(&region_data, width, height, &auto_pp);
+        (result.data, result.width, result.height)
+    } else {
+        (region_data, width, height)
+    }
+}
+
 /// Result of vision processing on a frame
 #[derive(Debug)]
 pub struct VisionResult {
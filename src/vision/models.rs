@@ -12,6 +12,22 @@ use std::path::{Path, PathBuf};
 use tokio::runtime::Runtime;
 use tracing::{debug, info, warn};
 
+/// Hugging Face repo the models are downloaded from unless a mirror has them
+pub const DEFAULT_MODEL_SOURCE: &str = "https://huggingface.co/monkt/paddleocr-onnx/resolve/main";
+
+/// URLs to try for a model: the configured mirrors in order, then Hugging Face
+///
+/// Mirrors are base URLs laid out like the Hugging Face repo.
+pub fn download_urls(model_type: ModelType, mirrors: &[String]) -> Vec<String> {
+    mirrors
+        .iter()
+        .map(|mirror| mirror.trim().trim_end_matches('/'))
+        .filter(|mirror| !mirror.is_empty())
+        .map(|mirror| format!("{}/{}", mirror, model_type.download_path()))
+        .chain(std::iter::once(model_type.download_url()))
+        .collect()
+}
+
 /// Model identifier for PaddleOCR components
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ModelType {
@@ -36,29 +52,26 @@ impl ModelType {
         }
     }
 
-    /// Get the download URL for this model
-    /// Using PaddleOCR models from Hugging Face (monkt/paddleocr-onnx)
-    pub fn download_url(&self) -> &'static str {
+    /// Path of this model relative to a download source
+    /// Laid out as in the Hugging Face repo (monkt/paddleocr-onnx)
+    pub fn download_path(&self) -> &'static str {
         match self {
             // PaddleOCR v3 detection model
-            ModelType::Detection => {
-                "https://huggingface.co/monkt/paddleocr-onnx/resolve/main/detection/v3/det.onnx"
-            }
+            ModelType::Detection => "detection/v3/det.onnx",
             // PaddleOCR English recognition model
-            ModelType::Recognition => {
-                "https://huggingface.co/monkt/paddleocr-onnx/resolve/main/languages/english/rec.onnx"
-            }
+            ModelType::Recognition => "languages/english/rec.onnx",
             // No classifier in this repo - use detection as fallback (not used currently)
-            ModelType::Classifier => {
-                "https://huggingface.co/monkt/paddleocr-onnx/resolve/main/detection/v3/det.onnx"
-            }
+            ModelType::Classifier => "detection/v3/det.onnx",
             // Character dictionary for English recognition
-            ModelType::Dictionary => {
-                "https://huggingface.co/monkt/paddleocr-onnx/resolve/main/languages/english/dict.txt"
-            }
+            ModelType::Dictionary => "languages/english/dict.txt",
         }
     }
 
+    /// Get the download URL for this model on Hugging Face
+    pub fn download_url(&self) -> String {
+        format!("{}/{}", DEFAULT_MODEL_SOURCE, self.download_path())
+    }
+
     /// Expected file size for integrity check (approximate, in bytes)
     pub fn expected_size_range(&self) -> (u64, u64) {
         match self {
@@ -93,6 +106,29 @@ impl ModelType {
     }
 }
 
+/// Set of PaddleOCR detection and recognition models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelVariant {
+    /// The downloaded float models
+    Standard,
+    /// Int8-quantized models, faster on the CPU at a small cost in accuracy
+    ///
+    /// These aren't downloaded: they're put in the models folder by hand,
+    /// e.g. made from the standard ones with ONNX Runtime's `quantize_dynamic`.
+    Quantized,
+}
+
+impl ModelVariant {
+    /// File name of a model in this set
+    pub fn filename(&self, model_type: ModelType) -> &'static str {
+        match (self, model_type) {
+            (ModelVariant::Quantized, ModelType::Detection) => "det.int8.onnx",
+            (ModelVariant::Quantized, ModelType::Recognition) => "rec.int8.onnx",
+            _ => model_type.filename(),
+        }
+    }
+}
+
 /// Model manifest tracking downloaded models
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ModelManifest {
@@ -107,6 +143,72 @@ pub struct ModelInfo {
     pub size_bytes: u64,
     pub sha256: Option<String>,
     pub downloaded_at: String,
+    /// File modification time (Unix seconds) when the checksum last matched
+    #[serde(default)]
+    pub verified_modified: Option<u64>,
+}
+
+/// Models checked against the manifest at startup
+pub const VERIFIED_MODELS: [ModelType; 3] = [
+    ModelType::Detection,
+    ModelType::Recognition,
+    ModelType::Dictionary,
+];
+
+/// Result of checking a model file against the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelIntegrity {
+    /// Matches the manifest, or isn't in it and has a plausible size
+    Ok,
+    /// Not downloaded
+    Missing,
+    /// Truncated or changed since it was downloaded
+    Corrupted(String),
+}
+
+/// Compare a model file with its manifest entry
+///
+/// The size is checked first, which catches truncated downloads without
+/// reading the file. The checksum is only computed when the file was modified
+/// since it last matched.
+fn check_against_manifest(
+    info: &ModelInfo,
+    size: u64,
+    modified: Option<u64>,
+    sha256: impl FnOnce() -> std::io::Result<String>,
+) -> ModelIntegrity {
+    if size != info.size_bytes {
+        return ModelIntegrity::Corrupted(format!("{} of {} bytes", size, info.size_bytes));
+    }
+    let Some(ref expected) = info.sha256 else {
+        return ModelIntegrity::Ok;
+    };
+    if modified.is_some() && modified == info.verified_modified {
+        return ModelIntegrity::Ok;
+    }
+    match sha256() {
+        Ok(hash) if hash == *expected => ModelIntegrity::Ok,
+        Ok(_) => ModelIntegrity::Corrupted("checksum mismatch".to_string()),
+        Err(e) => ModelIntegrity::Corrupted(format!("unreadable: {}", e)),
+    }
+}
+
+/// SHA256 of a file as lowercase hex
+fn file_sha256(path: &Path) -> std::io::Result<String> {
+    let data = std::fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&data);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Modification time of a file in Unix seconds
+fn modified_secs(metadata: &std::fs::Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
 }
 
 /// Progress callback for download operations
@@ -175,6 +277,27 @@ impl ModelManager {
             && self.is_model_available(ModelType::Recognition)
     }
 
+    /// Get the path to a model file of a model set
+    pub fn variant_path(&self, model_type: ModelType, variant: ModelVariant) -> PathBuf {
+        self.models_dir.join(variant.filename(model_type))
+    }
+
+    /// Check if a model set's detection and recognition models are present
+    pub fn is_variant_available(&self, variant: ModelVariant) -> bool {
+        match variant {
+            ModelVariant::Standard => self.are_models_ready(),
+            // Quantized sizes depend on how they were made; only check they're there
+            ModelVariant::Quantized => {
+                [ModelType::Detection, ModelType::Recognition]
+                    .iter()
+                    .all(|&model_type| {
+                        std::fs::metadata(self.variant_path(model_type, variant))
+                            .is_ok_and(|m| m.len() > 0)
+                    })
+            }
+        }
+    }
+
     /// Get status of all models
     pub fn get_model_status(&self) -> Vec<(ModelType, bool, Option<u64>)> {
         let models = [
@@ -233,27 +356,36 @@ impl ModelManager {
         model_type: ModelType,
         progress: Option<DownloadProgressCallback>,
     ) -> Result<()> {
-        let url = model_type.download_url();
+        let urls = download_urls(model_type, &crate::network::settings().model_mirrors);
         let path = self.model_path(model_type);
 
-        info!(
-            "Downloading {} model from {}",
-            model_type.display_name(),
-            url
-        );
-
         // Check if we're in offline mode
         if std::env::var("GAMERS_TOOLKIT_OFFLINE").is_ok() {
-            anyhow::bail!("Offline mode: cannot download models. Please download manually from {} and place at {:?}", url, path);
+            anyhow::bail!("Offline mode: cannot download models. Please download manually from {} and place at {:?}", model_type.download_url(), path);
         }
 
         // Create a tokio runtime for async download
         let rt = Runtime::new().context("Failed to create tokio runtime")?;
 
-        rt.block_on(async {
-            self.download_file_async(url, &path, model_type, progress)
-                .await
-        })?;
+        // Fail over to the next source until one works
+        let mut errors = Vec::new();
+        for url in &urls {
+            info!(
+                "Downloading {} model from {}",
+                model_type.display_name(),
+                url
+            );
+            match rt.block_on(self.download_file_async(url, &path, model_type, progress.as_ref())) {
+                Ok(()) => break,
+                Err(e) => {
+                    warn!("Download from {} failed: {:#}", url, e);
+                    errors.push(format!("{}: {:#}", url, e));
+                }
+            }
+        }
+        if errors.len() == urls.len() {
+            anyhow::bail!("All download sources failed:\n{}", errors.join("\n"));
+        }
 
         // Verify the download
         if !self.is_model_available(model_type) {
@@ -276,12 +408,10 @@ impl ModelManager {
         url: &str,
         path: &Path,
         model_type: ModelType,
-        progress: Option<DownloadProgressCallback>,
+        progress: Option<&DownloadProgressCallback>,
     ) -> Result<()> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(300)) // 5 minute timeout
-            .build()
-            .context("Failed to create HTTP client")?;
+        // 5 minute timeout, through the configured proxy
+        let client = crate::network::http_client(std::time::Duration::from_secs(300))?;
 
         let response = client
             .get(url)
@@ -314,7 +444,7 @@ impl ModelManager {
             downloaded += chunk.len() as u64;
 
             // Report progress
-            if let Some(ref callback) = progress {
+            if let Some(callback) = progress {
                 callback(downloaded, total_size);
             }
         }
@@ -352,12 +482,7 @@ impl ModelManager {
         let metadata = std::fs::metadata(&path)?;
 
         // Calculate SHA256
-        let hash = {
-            let data = std::fs::read(&path)?;
-            let mut hasher = Sha256::new();
-            hasher.update(&data);
-            format!("{:x}", hasher.finalize())
-        };
+        let hash = file_sha256(&path)?;
 
         let model_info = ModelInfo {
             model_type: format!("{:?}", model_type),
@@ -365,6 +490,7 @@ impl ModelManager {
             size_bytes: metadata.len(),
             sha256: Some(hash),
             downloaded_at: chrono_lite_now(),
+            verified_modified: modified_secs(&metadata),
         };
 
         // Update or add model info
@@ -416,6 +542,66 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Check the downloaded models against the manifest
+    ///
+    /// Files unchanged since they last matched aren't hashed again, so this
+    /// is cheap after the first run. Models the manifest doesn't know (e.g.
+    /// copied in by hand) only get the size range check.
+    pub fn verify_models(&self) -> Vec<(ModelType, ModelIntegrity)> {
+        let mut manifest = self.load_manifest().unwrap_or_default();
+        let mut verified = false;
+
+        let results = VERIFIED_MODELS
+            .iter()
+            .map(|&model_type| {
+                let path = self.model_path(model_type);
+                let Ok(metadata) = std::fs::metadata(&path) else {
+                    return (model_type, ModelIntegrity::Missing);
+                };
+                let Some(info) = manifest
+                    .models
+                    .iter_mut()
+                    .find(|m| m.filename == model_type.filename())
+                else {
+                    let integrity = if self.is_model_available(model_type) {
+                        ModelIntegrity::Ok
+                    } else {
+                        ModelIntegrity::Corrupted(format!(
+                            "unexpected size of {} bytes",
+                            metadata.len()
+                        ))
+                    };
+                    return (model_type, integrity);
+                };
+
+                let modified = modified_secs(&metadata);
+                let integrity =
+                    check_against_manifest(info, metadata.len(), modified, || file_sha256(&path));
+                if integrity == ModelIntegrity::Ok && info.verified_modified != modified {
+                    info.verified_modified = modified;
+                    verified = true;
+                }
+                if let ModelIntegrity::Corrupted(ref reason) = integrity {
+                    warn!("Model {} is corrupted: {}", model_type.filename(), reason);
+                }
+                (model_type, integrity)
+            })
+            .collect();
+
+        if verified {
+            if let Err(e) = self.save_manifest(&manifest) {
+                warn!("Failed to save model manifest: {}", e);
+            }
+        }
+        results
+    }
+
+    /// Download a model again, replacing the file on disk
+    pub fn redownload_model(&self, model_type: ModelType) -> Result<()> {
+        info!("Re-downloading {} model", model_type.display_name());
+        self.download_model(model_type)
+    }
+
     /// Load the model manifest
     pub fn load_manifest(&self) -> Result<ModelManifest> {
         let manifest_path = self.models_dir.join("manifest.json");
@@ -482,7 +668,9 @@ impl OnnxSession {
     }
 
     /// Create session with GPU acceleration if available
-    pub fn new_with_gpu(model_path: &Path) -> Result<Self> {
+    ///
+    /// `device_id` picks the DirectML adapter by DXGI index (`None` = default).
+    pub fn new_with_gpu(model_path: &Path, device_id: Option<u32>) -> Result<Self> {
         info!(
             "Loading ONNX model with GPU acceleration from {:?}",
             model_path
@@ -497,11 +685,13 @@ impl OnnxSession {
         #[cfg(target_os = "windows")]
         let session_builder = {
             use ort::execution_providers::DirectMLExecutionProvider;
-            match session_builder
-                .with_execution_providers([DirectMLExecutionProvider::default().build()])
-            {
+            let mut provider = DirectMLExecutionProvider::default();
+            if let Some(device_id) = device_id {
+                provider = provider.with_device_id(device_id as i32);
+            }
+            match session_builder.with_execution_providers([provider.build()]) {
                 Ok(builder) => {
-                    info!("DirectML GPU acceleration enabled");
+                    info!("DirectML GPU acceleration enabled (device {:?})", device_id);
                     builder
                 }
                 Err(e) => {
@@ -629,6 +819,68 @@ mod tests {
         assert_eq!(ModelType::Detection.filename(), "det.onnx");
         assert_eq!(ModelType::Recognition.filename(), "rec.onnx");
         assert_eq!(ModelType::Classifier.filename(), "cls.onnx");
+
+        let quantized = ModelVariant::Quantized;
+        assert_eq!(quantized.filename(ModelType::Detection), "det.int8.onnx");
+        assert_eq!(quantized.filename(ModelType::Recognition), "rec.int8.onnx");
+        assert_eq!(
+            ModelVariant::Standard.filename(ModelType::Detection),
+            "det.onnx"
+        );
+    }
+
+    #[test]
+    fn test_download_urls_fail_over_to_hugging_face() {
+        let mirrors = vec![
+            "https://mirror.example.com/paddleocr/".to_string(),
+            " ".to_string(),
+            "http://10.0.0.5:8000".to_string(),
+        ];
+        assert_eq!(
+            download_urls(ModelType::Detection, &mirrors),
+            [
+                "https://mirror.example.com/paddleocr/detection/v3/det.onnx",
+                "http://10.0.0.5:8000/detection/v3/det.onnx",
+                "https://huggingface.co/monkt/paddleocr-onnx/resolve/main/detection/v3/det.onnx",
+            ]
+        );
+        assert_eq!(
+            download_urls(ModelType::Dictionary, &[]),
+            [ModelType::Dictionary.download_url()]
+        );
+    }
+
+    #[test]
+    fn test_manifest_check() {
+        let info = ModelInfo {
+            model_type: "Recognition".to_string(),
+            filename: "rec.onnx".to_string(),
+            size_bytes: 100,
+            sha256: Some("abc".to_string()),
+            downloaded_at: "0".to_string(),
+            verified_modified: Some(42),
+        };
+        let unreachable = || -> std::io::Result<String> { panic!("hashed") };
+
+        // Truncated files are caught by size, unchanged ones aren't hashed
+        assert!(matches!(
+            check_against_manifest(&info, 60, Some(42), unreachable),
+            ModelIntegrity::Corrupted(_)
+        ));
+        assert_eq!(
+            check_against_manifest(&info, 100, Some(42), unreachable),
+            ModelIntegrity::Ok
+        );
+
+        // Modified files are hashed again
+        assert_eq!(
+            check_against_manifest(&info, 100, Some(43), || Ok("abc".to_string())),
+            ModelIntegrity::Ok
+        );
+        assert_eq!(
+            check_against_manifest(&info, 100, None, || Ok("def".to_string())),
+            ModelIntegrity::Corrupted("checksum mismatch".to_string())
+        );
     }
 
     #[test]
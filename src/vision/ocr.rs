@@ -4,15 +4,16 @@
 //! Uses PaddleOCR models via ONNX Runtime for text detection and recognition.
 
 use anyhow::{Context, Result};
-use ndarray::{Array2, Array3};
+use ndarray::{Array2, Array4};
 use std::path::Path;
 use tracing::{debug, info, warn};
 
 use super::models::OnnxSession;
 use super::preprocess::{
-    crop_polygon, preprocess_for_detection, preprocess_for_recognition, rgba_to_rgb_f32,
-    PreprocessConfig,
+    batch_recognition_tensors, crop_polygon, preprocess_for_detection, preprocess_for_recognition,
+    rgba_to_rgb_f32, PreprocessConfig,
 };
+use crate::qos;
 
 /// OCR engine using PaddleOCR via ONNX Runtime
 pub struct OcrEngine {
@@ -20,13 +21,27 @@ pub struct OcrEngine {
     recognition_session: OnnxSession,
     preprocess_config: PreprocessConfig,
     vocabulary: Vec<char>,
+    /// Decoder constraint: whether each vocabulary character may be emitted
+    allowed_vocabulary: Option<Vec<bool>>,
     detection_threshold: f32,
     recognition_threshold: f32,
+    /// Text lines per recognition call (1 once the model rejected a batch)
+    rec_batch_size: usize,
 }
 
+/// Text lines recognized per inference call, as PaddleOCR does by default
+const REC_BATCH_SIZE: usize = 6;
+
 impl OcrEngine {
     /// Initialize OCR engine with model paths
-    pub fn new(detection_model: &str, recognition_model: &str, use_gpu: bool) -> Result<Self> {
+    ///
+    /// `gpu_device_id` picks the DirectML adapter when `use_gpu` is set.
+    pub fn new(
+        detection_model: &str,
+        recognition_model: &str,
+        use_gpu: bool,
+        gpu_device_id: Option<u32>,
+    ) -> Result<Self> {
         // Use default dictionary path (same directory as models)
         let dict_path = Path::new(recognition_model)
             .parent()
@@ -37,6 +52,7 @@ impl OcrEngine {
             recognition_model,
             dict_path.to_str().unwrap_or("dict.txt"),
             use_gpu,
+            gpu_device_id,
         )
     }
 
@@ -46,33 +62,39 @@ impl OcrEngine {
         recognition_model: &str,
         dict_path: &str,
         use_gpu: bool,
+        gpu_device_id: Option<u32>,
     ) -> Result<Self> {
         info!("Initializing OCR engine...");
         info!("  Detection model: {}", detection_model);
         info!("  Recognition model: {}", recognition_model);
         info!("  Dictionary: {}", dict_path);
         info!("  GPU acceleration: {}", use_gpu);
+        if let Some(device_id) = gpu_device_id.filter(|_| use_gpu) {
+            info!("  GPU adapter: {}", device_id);
+        }
 
         let detection_session = if use_gpu {
-            OnnxSession::new_with_gpu(std::path::Path::new(detection_model)).or_else(|e| {
-                warn!(
-                    "Failed to load detection model with GPU, falling back to CPU: {}",
-                    e
-                );
-                OnnxSession::new(std::path::Path::new(detection_model))
-            })?
+            OnnxSession::new_with_gpu(std::path::Path::new(detection_model), gpu_device_id)
+                .or_else(|e| {
+                    warn!(
+                        "Failed to load detection model with GPU, falling back to CPU: {}",
+                        e
+                    );
+                    OnnxSession::new(std::path::Path::new(detection_model))
+                })?
         } else {
             OnnxSession::new(std::path::Path::new(detection_model))?
         };
 
         let recognition_session = if use_gpu {
-            OnnxSession::new_with_gpu(std::path::Path::new(recognition_model)).or_else(|e| {
-                warn!(
-                    "Failed to load recognition model with GPU, falling back to CPU: {}",
-                    e
-                );
-                OnnxSession::new(std::path::Path::new(recognition_model))
-            })?
+            OnnxSession::new_with_gpu(std::path::Path::new(recognition_model), gpu_device_id)
+                .or_else(|e| {
+                    warn!(
+                        "Failed to load recognition model with GPU, falling back to CPU: {}",
+                        e
+                    );
+                    OnnxSession::new(std::path::Path::new(recognition_model))
+                })?
         } else {
             OnnxSession::new(std::path::Path::new(recognition_model))?
         };
@@ -94,8 +116,10 @@ impl OcrEngine {
             recognition_session,
             preprocess_config: PreprocessConfig::default(),
             vocabulary,
+            allowed_vocabulary: None,
             detection_threshold: 0.3,
             recognition_threshold: 0.001, // Very low threshold - PaddleOCR ONNX can have low confidence
+            rec_batch_size: REC_BATCH_SIZE,
         })
     }
 
@@ -130,6 +154,11 @@ impl OcrEngine {
         Ok(vocabulary)
     }
 
+    /// Set the size text detection scales images to
+    pub fn set_detection_size(&mut self, size: u32) {
+        self.preprocess_config.det_target_size = size;
+    }
+
     /// Set detection confidence threshold
     pub fn set_detection_threshold(&mut self, threshold: f32) {
         self.detection_threshold = threshold.clamp(0.0, 1.0);
@@ -140,6 +169,19 @@ impl OcrEngine {
         self.recognition_threshold = threshold.clamp(0.0, 1.0);
     }
 
+    /// Restrict decoding to the given characters (`None` allows everything)
+    ///
+    /// Disallowed characters are skipped when picking the most likely class,
+    /// so the decoder chooses the best allowed character instead.
+    pub fn set_allowed_chars(&mut self, allowed: Option<&str>) {
+        self.allowed_vocabulary = allowed.map(|allowed| {
+            self.vocabulary
+                .iter()
+                .map(|&c| c == ' ' || allowed.contains(c))
+                .collect()
+        });
+    }
+
     /// Run OCR on an image buffer (BGRA format)
     pub fn recognize(
         &mut self,
@@ -161,20 +203,49 @@ impl OcrEngine {
             return Ok(vec![]);
         }
 
-        // Step 2: Recognize text in each region
+        // Step 2: Crop and preprocess every region, in parallel
         let rgb_image = rgba_to_rgb_f32(image_data, width, height);
-        let mut results = Vec::with_capacity(detections.len());
-
-        for detection in detections {
-            if let Some(text_result) = self.recognize_region(&rgb_image, &detection)? {
-                if text_result.confidence >= self.recognition_threshold {
-                    results.push(text_result);
+        let config = &self.preprocess_config;
+        let lines: Vec<Option<Array4<f32>>> = parallel_map(&detections, |detection| {
+            let cropped = crop_polygon(&rgb_image, &detection.polygon);
+            let (h, w, _) = cropped.dim();
+            // Skip regions too small to read
+            (h >= 2 && w >= 2).then(|| preprocess_for_recognition(&cropped, config))
+        });
+
+        // Step 3: Recognize in batches of similar width, so little is padded
+        let mut order: Vec<usize> = (0..lines.len()).filter(|&i| lines[i].is_some()).collect();
+        order.sort_by_key(|&i| lines[i].as_ref().map_or(0, |line| line.dim().3));
+
+        let mut results = Vec::with_capacity(order.len());
+        let mut start = 0;
+        while start < order.len() {
+            let batch = &order[start..(start + self.rec_batch_size).min(order.len())];
+            start += batch.len();
+
+            let inputs: Vec<&Array4<f32>> =
+                batch.iter().filter_map(|&i| lines[i].as_ref()).collect();
+            for (&i, (text, confidence)) in batch.iter().zip(self.recognize_batch(&inputs)?) {
+                debug!("CTC decoded: text='{}', confidence={:.3}", text, confidence);
+                if !text.is_empty() && confidence >= self.recognition_threshold {
+                    let polygon = detections[i].polygon.to_vec();
+                    results.push((
+                        i,
+                        OcrResult {
+                            text,
+                            polygon,
+                            confidence,
+                        },
+                    ));
                 }
             }
         }
 
+        // Back in detection order
+        results.sort_by_key(|(i, _)| *i);
+
         debug!("Recognized {} text regions", results.len());
-        Ok(results)
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
     /// Detect text regions in an image
@@ -314,31 +385,55 @@ impl OcrEngine {
         Ok(detections)
     }
 
-    /// Recognize text in a detected region
-    fn recognize_region(
-        &mut self,
-        image: &Array3<f32>,
-        region: &DetectedRegion,
-    ) -> Result<Option<OcrResult>> {
-        // Crop the region
-        let cropped = crop_polygon(image, &region.polygon);
-
-        let (h, w, _) = cropped.dim();
+    /// Recognize a batch of preprocessed text lines in one inference call
+    ///
+    /// Returns the decoded text and confidence of each line, in order. If the
+    /// model rejects batches, lines are recognized one at a time from then on.
+    fn recognize_batch(&mut self, lines: &[&Array4<f32>]) -> Result<Vec<(String, f32)>> {
+        let batch = batch_recognition_tensors(lines);
+        debug!("Recognition input tensor shape: {:?}", batch.dim());
+
+        let (shape, data) = match self.run_recognition(batch) {
+            Ok(output) => output,
+            Err(e) if lines.len() > 1 => {
+                warn!(
+                    "Batched recognition failed, recognizing lines one at a time: {:#}",
+                    e
+                );
+                self.rec_batch_size = 1;
+                let mut decoded = Vec::with_capacity(lines.len());
+                for line in lines {
+                    decoded.extend(self.recognize_batch(&[line])?);
+                }
+                return Ok(decoded);
+            }
+            Err(e) => return Err(e),
+        };
         debug!(
-            "Cropped region size: {}x{}, polygon: {:?}",
-            w, h, region.polygon
+            "Recognition output shape: {:?}, data len: {}",
+            shape,
+            data.len()
         );
-        if h < 2 || w < 2 {
-            debug!("Region too small, skipping");
-            return Ok(None);
-        }
 
-        // Preprocess for recognition
-        let input_tensor = preprocess_for_recognition(&cropped, &self.preprocess_config);
-        debug!("Recognition input tensor shape: {:?}", input_tensor.dim());
+        // Output is [lines, time steps, classes]; decode each line on its own
+        if shape.len() < 3 || shape[1] <= 0 || shape[2] <= 0 {
+            return Ok(vec![(String::new(), 0.0); lines.len()]);
+        }
+        let line_shape = [1, shape[1], shape[2]];
+        let rows: Vec<&[f32]> = data
+            .chunks((shape[1] * shape[2]) as usize)
+            .take(lines.len())
+            .collect();
+        let engine = &*self;
+        parallel_map(&rows, |row| engine.ctc_decode_raw(&line_shape, row))
+            .into_iter()
+            .collect()
+    }
 
+    /// Run the recognition model, returning the output shape and data
+    fn run_recognition(&mut self, input: Array4<f32>) -> Result<(Vec<i64>, Vec<f32>)> {
         // Create ONNX tensor from ndarray
-        let input_value = ort::value::Tensor::from_array(input_tensor)?;
+        let input_value = ort::value::Tensor::from_array(input)?;
 
         // Run recognition inference
         let outputs = self
@@ -358,31 +453,7 @@ impl OcrEngine {
             .context("Failed to extract recognition output")?;
 
         // Clone data to release borrow on outputs/self
-        let shape_vec: Vec<i64> = shape.iter().copied().collect();
-        let data_vec: Vec<f32> = data.to_vec();
-
-        // Drop outputs to release borrow
-        drop(outputs);
-
-        // Decode output using CTC
-        debug!(
-            "Recognition output shape: {:?}, data len: {}",
-            shape_vec,
-            data_vec.len()
-        );
-        let (text, confidence) = self.ctc_decode_raw(&shape_vec, &data_vec)?;
-        debug!("CTC decoded: text='{}', confidence={:.3}", text, confidence);
-
-        if text.is_empty() {
-            debug!("Empty text after CTC decode");
-            return Ok(None);
-        }
-
-        Ok(Some(OcrResult {
-            text,
-            polygon: region.polygon.to_vec(),
-            confidence,
-        }))
+        Ok((shape.iter().copied().collect(), data.to_vec()))
     }
 
     /// CTC decoding for recognition output from raw tensor data
@@ -425,6 +496,9 @@ impl OcrEngine {
             let mut max_val = f32::NEG_INFINITY;
 
             for v in 0..vocab_size {
+                if !self.is_class_allowed(v, vocab_size) {
+                    continue;
+                }
                 let idx = t * vocab_size + v;
                 if idx < data.len() {
                     let val = data[idx];
@@ -484,6 +558,19 @@ impl OcrEngine {
 
         Ok((text, avg_confidence))
     }
+
+    /// Whether the decoder may pick a model class under the current whitelist
+    fn is_class_allowed(&self, class_idx: usize, vocab_size: usize) -> bool {
+        // Blank and end tokens are always allowed
+        if class_idx == 0 || class_idx >= vocab_size - 1 {
+            return true;
+        }
+        match self.allowed_vocabulary {
+            // Model index 1 maps to vocabulary index 0 (see ctc_decode_raw)
+            Some(ref allowed) => allowed.get(class_idx - 1).copied().unwrap_or(false),
+            None => true,
+        }
+    }
 }
 
 /// Detected text region before recognition
@@ -504,6 +591,44 @@ pub struct OcrResult {
     pub confidence: f32,
 }
 
+/// Map items on the analysis threads, keeping their order
+///
+/// Items are split into one contiguous chunk per thread, using no more than
+/// [`qos::analysis_threads`]. The threads run at the calling (vision) thread's
+/// priority, so OCR stays below normal when low-priority workers are on.
+fn parallel_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = qos::analysis_threads().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let priority = qos::current_thread_priority();
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    if let Err(e) = qos::set_current_thread_priority(priority) {
+                        debug!("OCR thread priority unchanged: {:#}", e);
+                    }
+                    chunk.iter().map(f).collect::<Vec<R>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("OCR worker thread panicked"))
+            .collect()
+    })
+}
+
 /// Find text bounding boxes from binary map (simplified connected components)
 fn find_text_boxes(binary_map: &Array2<u8>, _threshold: f32) -> Vec<(usize, usize, usize, usize)> {
     let (h, w) = binary_map.dim();
@@ -597,6 +722,16 @@ fn softmax_prob(data: &[f32], t: usize, vocab_size: usize, class_idx: usize) ->
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u32> = (0..100).collect();
+        let doubled = parallel_map(&items, |&i| i * 2);
+        assert_eq!(doubled, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+        assert!(parallel_map(&[] as &[u32], |&i| i).is_empty());
+    }
+
     // Note: ENGLISH_CHARS constant was removed in a previous refactor
     // This test is currently disabled until vocabulary management is revisited
     // #[test]
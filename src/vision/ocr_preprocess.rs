@@ -47,8 +47,13 @@ pub fn apply_preprocessing_with_scale(
     }
 
     debug!(
-        "OCR preprocessing enabled: grayscale={}, invert={}, contrast={}, sharpen={}, scale={}",
-        settings.grayscale, settings.invert, settings.contrast, settings.sharpen, settings.scale
+        "OCR preprocessing enabled: grayscale={}, invert={}, contrast={}, sharpen={}, scale={}, threshold={}",
+        settings.grayscale,
+        settings.invert,
+        settings.contrast,
+        settings.sharpen,
+        settings.scale,
+        settings.threshold
     );
 
     // Apply upscaling first if requested (before other filters for better quality)
@@ -76,6 +81,11 @@ pub fn apply_preprocessing_with_scale(
         apply_grayscale(&mut result);
     }
 
+    // Binarize after grayscale so the cut-off applies to luminance
+    if settings.threshold > 0 {
+        apply_threshold(&mut result, settings.threshold);
+    }
+
     // Invert colors last
     if settings.invert {
         apply_invert(&mut result);
@@ -115,6 +125,20 @@ fn apply_grayscale(data: &mut [u8]) {
     }
 }
 
+/// Binarize RGBA data: pixels with luminance above `threshold` become white,
+/// the rest black
+fn apply_threshold(data: &mut [u8], threshold: u8) {
+    for chunk in data.chunks_exact_mut(4) {
+        let luminance =
+            (0.299 * chunk[0] as f32 + 0.587 * chunk[1] as f32 + 0.114 * chunk[2] as f32) as u8;
+        let value = if luminance > threshold { 255 } else { 0 };
+        chunk[0] = value;
+        chunk[1] = value;
+        chunk[2] = value;
+        // Alpha unchanged
+    }
+}
+
 /// Invert RGB colors (useful for light text on dark backgrounds)
 fn apply_invert(data: &mut [u8]) {
     for chunk in data.chunks_exact_mut(4) {
@@ -256,6 +280,14 @@ mod tests {
         assert_eq!(data[2], 76);
     }
 
+    #[test]
+    fn test_threshold() {
+        let mut data = vec![200, 200, 200, 255, 50, 60, 70, 128];
+        apply_threshold(&mut data, 128);
+        assert_eq!(&data[..4], &[255, 255, 255, 255]);
+        assert_eq!(&data[4..], &[0, 0, 0, 128]); // Alpha unchanged
+    }
+
     #[test]
     fn test_invert() {
         let mut data = vec![0, 100, 255, 255];
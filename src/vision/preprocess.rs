@@ -1,8 +1,13 @@
 //! Image preprocessing for OCR models
 //!
-//! Handles image resizing, normalization, and tensor conversion for PaddleOCR models.
+//! Handles image resizing, normalization, and tensor conversion for PaddleOCR models,
+//! plus per-zone rotation and perspective correction of RGBA regions.
 
-use ndarray::{Array3, Array4};
+use image::{Rgba, RgbaImage};
+use imageproc::geometric_transformations::{rotate_about_center, warp, Interpolation, Projection};
+use ndarray::{s, Array3, Array4};
+
+use crate::storage::profiles::ZoneGeometry;
 
 /// Preprocessing configuration
 #[derive(Debug, Clone)]
@@ -258,10 +263,116 @@ pub fn preprocess_for_recognition(image: &Array3<f32>, config: &PreprocessConfig
     hwc_to_nchw(&normalized)
 }
 
+/// Stack recognition tensors (batch size 1, same height) into one batch
+///
+/// Narrower lines are padded on the right with zeros, which is mid-gray
+/// after normalization, the way PaddleOCR pads its recognition batches.
+pub fn batch_recognition_tensors(lines: &[&Array4<f32>]) -> Array4<f32> {
+    let (c, h) = lines
+        .first()
+        .map_or((3, 0), |line| (line.dim().1, line.dim().2));
+    let w = lines.iter().map(|line| line.dim().3).max().unwrap_or(0);
+
+    let mut batch = Array4::<f32>::zeros((lines.len(), c, h, w));
+    for (i, line) in lines.iter().enumerate() {
+        batch
+            .slice_mut(s![i, .., .., ..line.dim().3])
+            .assign(&line.slice(s![0, .., .., ..]));
+    }
+    batch
+}
+
+/// Apply a zone's perspective and rotation correction to RGBA data
+///
+/// The output keeps the input dimensions: the perspective corners are
+/// stretched to fill the region, then the result is rotated about its center.
+/// Uncovered pixels are filled with the region's mean color so no hard edges
+/// are introduced for OCR to pick up.
+pub fn correct_zone_geometry(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    geometry: &ZoneGeometry,
+) -> Vec<u8> {
+    if geometry.is_identity() || width < 2 || height < 2 {
+        return data.to_vec();
+    }
+    let Some(mut image) = RgbaImage::from_raw(width, height, data.to_vec()) else {
+        return data.to_vec();
+    };
+    let fill = mean_color(&image);
+
+    if let Some(corners) = geometry.perspective {
+        let (max_x, max_y) = ((width - 1) as f32, (height - 1) as f32);
+        let from = corners.map(|(x, y)| (x * max_x, y * max_y));
+        let to = ZoneGeometry::IDENTITY_CORNERS.map(|(x, y)| (x * max_x, y * max_y));
+        if let Some(projection) = Projection::from_control_points(from, to) {
+            image = warp(&image, &projection, Interpolation::Bilinear, fill);
+        }
+    }
+
+    if geometry.rotation.abs() >= 0.01 {
+        image = rotate_about_center(
+            &image,
+            geometry.rotation.to_radians(),
+            Interpolation::Bilinear,
+            fill,
+        );
+    }
+
+    image.into_raw()
+}
+
+/// Mean color of an image
+fn mean_color(image: &RgbaImage) -> Rgba<u8> {
+    let mut sums = [0u64; 4];
+    for pixel in image.pixels() {
+        for (sum, &channel) in sums.iter_mut().zip(pixel.0.iter()) {
+            *sum += channel as u64;
+        }
+    }
+    let count = (image.width() as u64 * image.height() as u64).max(1);
+    Rgba(sums.map(|sum| (sum / count) as u8))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_identity_geometry_is_unchanged() {
+        let data: Vec<u8> = (0..4 * 4 * 4).map(|i| i as u8).collect();
+        let geometry = ZoneGeometry {
+            rotation: 0.0,
+            perspective: Some(ZoneGeometry::IDENTITY_CORNERS),
+        };
+        assert_eq!(correct_zone_geometry(&data, 4, 4, &geometry), data);
+    }
+
+    #[test]
+    fn test_perspective_stretches_corners() {
+        // 4x4 image, left half black and right half white
+        let mut data = vec![0u8; 4 * 4 * 4];
+        for (i, pixel) in data.chunks_exact_mut(4).enumerate() {
+            let value = if i % 4 >= 2 { 255 } else { 0 };
+            pixel.copy_from_slice(&[value, value, value, 255]);
+        }
+
+        // Select only the right half: the output becomes white (the last row
+        // and column have no bilinear neighbours and get the fill color)
+        let geometry = ZoneGeometry {
+            rotation: 0.0,
+            perspective: Some([(2.0 / 3.0, 0.0), (1.0, 0.0), (1.0, 1.0), (2.0 / 3.0, 1.0)]),
+        };
+        let corrected = correct_zone_geometry(&data, 4, 4, &geometry);
+        assert_eq!(corrected.len(), data.len());
+        for (i, pixel) in corrected.chunks_exact(4).enumerate() {
+            if i % 4 < 3 && i / 4 < 3 {
+                assert!(pixel[0] > 200, "pixel {} is {:?}", i, pixel);
+            }
+        }
+    }
+
     #[test]
     fn test_rgba_to_rgb() {
         // Create a 2x2 RGBA image
@@ -308,4 +419,17 @@ mod tests {
         // Check that normalization was applied
         assert!((normalized[[0, 0, 0]] - (0.5 - 0.485) / 0.229).abs() < 0.01);
     }
+
+    #[test]
+    fn test_batch_pads_narrow_lines() {
+        let narrow = Array4::<f32>::from_elem((1, 3, 4, 2), 1.0);
+        let wide = Array4::<f32>::from_elem((1, 3, 4, 5), -1.0);
+
+        let batch = batch_recognition_tensors(&[&narrow, &wide]);
+
+        assert_eq!(batch.dim(), (2, 3, 4, 5));
+        assert_eq!(batch[[0, 2, 3, 1]], 1.0);
+        assert_eq!(batch[[0, 2, 3, 2]], 0.0);
+        assert_eq!(batch[[1, 0, 0, 4]], -1.0);
+    }
 }
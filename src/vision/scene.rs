@@ -0,0 +1,201 @@
+//! Scene brightness detection
+//!
+//! Builds a brightness histogram and mean saturation over a sparse grid of
+//! the frame, cheap enough for every frame, so rules can tell when the game
+//! turns dark (night, a cave) and react, e.g. by switching OCR preprocessing.
+//! The dark flag only flips after the brightness stays past the far threshold
+//! for several frames, so a flash or a menu fading in doesn't make it flap.
+
+use crate::capture::{CapturedFrame, PixelFormat};
+
+/// Pixels skipped between samples in each direction
+const SAMPLE_STEP: u32 = 8;
+
+/// Global brightness and color of a frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SceneMetrics {
+    /// Mean luminance (0.0-1.0)
+    pub brightness: f32,
+    /// Luminance below which the darkest tenth of the frame falls (0.0-1.0)
+    pub shadows: f32,
+    /// Mean saturation (0.0-1.0)
+    pub saturation: f32,
+    /// Whether the scene is considered dark (with hysteresis)
+    pub dark: bool,
+}
+
+/// Thresholds of the dark-scene flag
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneThresholds {
+    /// Brightness below which the scene turns dark
+    pub dark_below: f32,
+    /// Brightness above which a dark scene turns light again
+    pub light_above: f32,
+    /// Consecutive frames past a threshold before the flag flips
+    pub frames_to_switch: u32,
+}
+
+impl Default for SceneThresholds {
+    fn default() -> Self {
+        Self {
+            dark_below: 0.2,
+            light_above: 0.3,
+            frames_to_switch: 10,
+        }
+    }
+}
+
+/// Tracks scene metrics across frames
+#[derive(Debug, Clone, Default)]
+pub struct SceneAnalyzer {
+    thresholds: SceneThresholds,
+    dark: bool,
+    /// Frames in a row that argued for flipping the flag
+    pending_frames: u32,
+}
+
+impl SceneAnalyzer {
+    /// Create an analyzer with custom thresholds
+    pub fn with_thresholds(thresholds: SceneThresholds) -> Self {
+        Self {
+            thresholds,
+            ..Default::default()
+        }
+    }
+
+    /// Measure a frame and update the dark flag
+    pub fn analyze(&mut self, frame: &CapturedFrame) -> SceneMetrics {
+        let mut metrics = measure(frame);
+
+        let wants_flip = if self.dark {
+            metrics.brightness > self.thresholds.light_above
+        } else {
+            metrics.brightness < self.thresholds.dark_below
+        };
+        if wants_flip {
+            self.pending_frames += 1;
+            if self.pending_frames >= self.thresholds.frames_to_switch {
+                self.dark = !self.dark;
+                self.pending_frames = 0;
+            }
+        } else {
+            self.pending_frames = 0;
+        }
+
+        metrics.dark = self.dark;
+        metrics
+    }
+}
+
+/// Brightness and saturation of a frame, without the dark flag
+pub fn measure(frame: &CapturedFrame) -> SceneMetrics {
+    let mut histogram = [0u32; 256];
+    let mut saturation_sum = 0.0f64;
+    let mut count = 0u32;
+
+    for y in (0..frame.height).step_by(SAMPLE_STEP as usize) {
+        for x in (0..frame.width).step_by(SAMPLE_STEP as usize) {
+            let i = (y * frame.width + x) as usize;
+            let (r, g, b) = match frame.format {
+                PixelFormat::Rgba8 => match frame.data.get(i * 4..i * 4 + 3) {
+                    Some(&[r, g, b]) => (r, g, b),
+                    _ => continue,
+                },
+                PixelFormat::Gray8 => match frame.data.get(i) {
+                    Some(&value) => (value, value, value),
+                    None => continue,
+                },
+            };
+            let luma = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32).round();
+            histogram[luma as usize] += 1;
+
+            let max = r.max(g).max(b);
+            if max > 0 {
+                saturation_sum += (max - r.min(g).min(b)) as f64 / max as f64;
+            }
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return SceneMetrics::default();
+    }
+
+    let luma_sum: u64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(luma, &n)| luma as u64 * n as u64)
+        .sum();
+
+    let mut below = 0;
+    let shadows = histogram
+        .iter()
+        .position(|&n| {
+            below += n;
+            below * 10 >= count
+        })
+        .unwrap_or(0);
+
+    SceneMetrics {
+        brightness: luma_sum as f32 / count as f32 / 255.0,
+        shadows: shadows as f32 / 255.0,
+        saturation: (saturation_sum / count as f64) as f32,
+        dark: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform(rgb: [u8; 3]) -> CapturedFrame {
+        let data = (0..64 * 64)
+            .flat_map(|_| [rgb[0], rgb[1], rgb[2], 255])
+            .collect();
+        CapturedFrame::new(data, 64, 64)
+    }
+
+    #[test]
+    fn test_measure() {
+        let gray = measure(&uniform([128, 128, 128]));
+        assert!((gray.brightness - 128.0 / 255.0).abs() < 0.01);
+        assert!((gray.shadows - 128.0 / 255.0).abs() < 0.01);
+        assert_eq!(gray.saturation, 0.0);
+
+        let red = measure(&uniform([200, 0, 0]));
+        assert!((red.saturation - 1.0).abs() < 0.01);
+        assert!(red.brightness < 0.3);
+
+        assert_eq!(
+            measure(&CapturedFrame::new(vec![], 0, 0)),
+            SceneMetrics::default()
+        );
+    }
+
+    #[test]
+    fn test_dark_flag_has_hysteresis() {
+        let mut analyzer = SceneAnalyzer::with_thresholds(SceneThresholds {
+            frames_to_switch: 3,
+            ..Default::default()
+        });
+        let dark = uniform([20, 20, 20]);
+        let dim = uniform([64, 64, 64]);
+        let light = uniform([200, 200, 200]);
+
+        // A single dark frame doesn't flip the flag
+        assert!(!analyzer.analyze(&dark).dark);
+        assert!(!analyzer.analyze(&light).dark);
+        assert!(!analyzer.analyze(&dark).dark);
+        assert!(!analyzer.analyze(&dark).dark);
+        assert!(analyzer.analyze(&dark).dark);
+
+        // Between the thresholds the scene stays dark
+        for _ in 0..5 {
+            assert!(analyzer.analyze(&dim).dark);
+        }
+        for _ in 0..2 {
+            assert!(analyzer.analyze(&light).dark);
+        }
+        assert!(!analyzer.analyze(&light).dark);
+    }
+}
@@ -4,13 +4,22 @@
 //! Recognizes game screens (menus, HUDs, etc.) using template matching and anchor-based detection.
 //! Supports hierarchical screen organization for context-aware OCR zone switching.
 
-use anyhow::{Context, Result};
 use image::{GrayImage, Luma};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
-use crate::storage::profiles::{AnchorType, ScreenAnchor, ScreenDefinition, ScreenMatchMode};
+use crate::capture::{CapturedFrame, PixelFormat};
+use crate::storage::profiles::{
+    AnchorType, MatchStrategy, RecognitionDictionary, ScreenAnchor, ScreenDefinition,
+    ScreenMatchMode,
+};
+use crate::vision::dictionary::correct_text;
+use crate::vision::feature_match::{self, Features};
+use crate::vision::gpu_adapters::GpuAdapter;
+use crate::vision::gpu_match::GpuMatcher;
+use crate::vision::template_cache::TemplateCache;
 
 /// Result of screen recognition
 #[derive(Debug, Clone)]
@@ -40,6 +49,23 @@ pub struct AnchorMatch {
     pub detected_text: Option<String>,
 }
 
+/// How well a frame matched one screen, whether or not it was recognized
+#[derive(Debug, Clone)]
+pub struct ScreenScore {
+    /// Screen ID
+    pub screen_id: String,
+    /// Screen name
+    pub screen_name: String,
+    /// Match confidence (None = nothing to compare, e.g. no template)
+    pub confidence: Option<f32>,
+    /// Confidence the screen needs to match
+    pub threshold: f32,
+    /// Whether the screen matched (and its parent did)
+    pub matched: bool,
+    /// Individual anchor matches
+    pub anchors: Vec<AnchorMatch>,
+}
+
 /// A node in the screen hierarchy tree
 #[derive(Debug, Clone)]
 pub struct ScreenNode {
@@ -64,6 +90,13 @@ pub struct ScreenRecognitionConfig {
     pub enable_cache: bool,
     /// Cache validity duration in milliseconds
     pub cache_ttl_ms: u64,
+    /// Compute template similarities on the GPU, falling back to the CPU
+    /// when no GPU is available
+    pub use_gpu: bool,
+    /// Time template similarities on both the CPU and the GPU
+    pub benchmark: bool,
+    /// GPU to match on (`None` = the default high-performance one)
+    pub gpu_adapter: Option<GpuAdapter>,
 }
 
 impl Default for ScreenRecognitionConfig {
@@ -74,10 +107,39 @@ impl Default for ScreenRecognitionConfig {
             match_scale: 0.5, // Match at 50% resolution for speed
             enable_cache: true,
             cache_ttl_ms: 200,
+            use_gpu: false,
+            benchmark: false,
+            gpu_adapter: None,
         }
     }
 }
 
+/// Time taken by one recognition's template similarities on each path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchBenchmark {
+    /// Image/template pairs compared
+    pub pairs: usize,
+    /// Time on the CPU
+    pub cpu: Duration,
+    /// Time on the GPU (`None` when unavailable)
+    pub gpu: Option<Duration>,
+}
+
+/// Similarities of a frame to the visual anchor and full screen templates
+#[derive(Debug, Default)]
+struct FrameSimilarities {
+    /// Anchor ID -> similarity of the anchor's region
+    anchors: HashMap<String, f32>,
+    /// Screen ID -> similarity of the whole frame
+    screens: HashMap<String, f32>,
+}
+
+/// What a compared image/template pair belongs to
+enum SimilarityKey {
+    Anchor(String),
+    Screen(String),
+}
+
 /// Screen recognizer engine
 pub struct ScreenRecognizer {
     /// Screen definitions indexed by ID
@@ -86,12 +148,22 @@ pub struct ScreenRecognizer {
     config: ScreenRecognitionConfig,
     /// Cached templates for visual anchors (anchor_id -> grayscale image)
     anchor_templates: HashMap<String, GrayImage>,
-    /// Cached full screen templates (screen_id -> grayscale image)
-    screen_templates: HashMap<String, GrayImage>,
+    /// Keypoints of anchor templates matched by features (anchor_id -> features)
+    anchor_features: HashMap<String, Features>,
+    /// Cached full screen templates (screen_id -> grayscale images, one per resolution)
+    screen_templates: HashMap<String, Vec<GrayImage>>,
     /// Last match result for caching
     last_match: Option<(ScreenMatch, Instant)>,
     /// Pre-computed screen hierarchy
     hierarchy_cache: Option<Vec<ScreenNode>>,
+    /// Profile dictionary used to correct text anchor readings
+    dictionary: RecognitionDictionary,
+    /// GPU matcher, created on first use (`Err` = unavailable)
+    gpu: Option<std::result::Result<GpuMatcher, String>>,
+    /// Latest CPU/GPU timing while benchmarking
+    last_benchmark: Option<MatchBenchmark>,
+    /// Templates decoded on an earlier run
+    template_cache: TemplateCache,
 }
 
 impl ScreenRecognizer {
@@ -106,16 +178,69 @@ impl ScreenRecognizer {
             screens: HashMap::new(),
             config,
             anchor_templates: HashMap::new(),
+            anchor_features: HashMap::new(),
             screen_templates: HashMap::new(),
             last_match: None,
             hierarchy_cache: None,
+            dictionary: RecognitionDictionary::default(),
+            gpu: None,
+            last_benchmark: None,
+            template_cache: TemplateCache::default(),
         }
     }
 
+    /// Compute template similarities on the GPU (when available) or the CPU
+    pub fn set_use_gpu(&mut self, use_gpu: bool) {
+        self.config.use_gpu = use_gpu;
+    }
+
+    /// Pick the GPU to match on (reopens the GPU on the next match)
+    pub fn set_gpu_adapter(&mut self, adapter: Option<GpuAdapter>) {
+        if self.config.gpu_adapter != adapter {
+            self.config.gpu_adapter = adapter;
+            self.gpu = None;
+        }
+    }
+
+    /// Time template similarities on both the CPU and the GPU
+    pub fn set_benchmark(&mut self, benchmark: bool) {
+        self.config.benchmark = benchmark;
+        if !benchmark {
+            self.last_benchmark = None;
+        }
+    }
+
+    /// Latest CPU/GPU timing while benchmarking
+    pub fn last_benchmark(&self) -> Option<MatchBenchmark> {
+        self.last_benchmark
+    }
+
+    /// Why GPU matching is unavailable, if it was tried
+    pub fn gpu_error(&self) -> Option<&str> {
+        self.gpu.as_ref()?.as_ref().err().map(String::as_str)
+    }
+
+    /// Set the dictionary used to correct OCR text of text anchors
+    pub fn set_dictionary(&mut self, dictionary: RecognitionDictionary) {
+        self.dictionary = dictionary;
+        self.last_match = None;
+    }
+
+    /// Decode templates through a cache kept between runs
+    pub fn set_template_cache(&mut self, cache: TemplateCache) {
+        self.template_cache = cache;
+    }
+
+    /// The template cache, to save once screens are loaded
+    pub fn template_cache_mut(&mut self) -> &mut TemplateCache {
+        &mut self.template_cache
+    }
+
     /// Load screens from a list of screen definitions
     pub fn load_screens(&mut self, screens: Vec<ScreenDefinition>) {
         self.screens.clear();
         self.anchor_templates.clear();
+        self.anchor_features.clear();
         self.screen_templates.clear();
         self.hierarchy_cache = None;
         self.last_match = None;
@@ -123,6 +248,8 @@ impl ScreenRecognizer {
         for screen in screens {
             self.add_screen(screen);
         }
+        // Built now rather than on the first match
+        self.hierarchy_cache = Some(self.build_hierarchy());
 
         info!("Loaded {} screens for recognition", self.screens.len());
     }
@@ -133,18 +260,30 @@ impl ScreenRecognizer {
         for anchor in &screen.anchors {
             if anchor.anchor_type == AnchorType::Visual {
                 if let Some(ref data) = anchor.template_data {
-                    if let Ok(template) = self.decode_template(data) {
-                        self.anchor_templates.insert(anchor.id.clone(), template);
+                    let features = anchor.match_strategy == MatchStrategy::Features;
+                    if let Ok(template) = self.template_cache.decode(data, features) {
+                        if let Some(features) = template.features.filter(|_| features) {
+                            self.anchor_features.insert(anchor.id.clone(), features);
+                        }
+                        self.anchor_templates
+                            .insert(anchor.id.clone(), template.image);
                     }
                 }
             }
         }
 
-        // Pre-process and cache full screen template
-        if let Some(ref template) = screen.full_template {
-            if let Ok(gray) = self.decode_template(&template.image_data) {
-                self.screen_templates.insert(screen.id.clone(), gray);
-            }
+        // Pre-process and cache full screen templates
+        let templates: Vec<GrayImage> = screen
+            .full_templates()
+            .filter_map(|template| {
+                self.template_cache
+                    .decode(&template.image_data, false)
+                    .ok()
+                    .map(|template| template.image)
+            })
+            .collect();
+        if !templates.is_empty() {
+            self.screen_templates.insert(screen.id.clone(), templates);
         }
 
         self.screens.insert(screen.id.clone(), screen);
@@ -157,6 +296,7 @@ impl ScreenRecognizer {
         if let Some(screen) = self.screens.get(id) {
             for anchor in &screen.anchors {
                 self.anchor_templates.remove(&anchor.id);
+                self.anchor_features.remove(&anchor.id);
             }
             self.screen_templates.remove(id);
         }
@@ -231,20 +371,121 @@ impl ScreenRecognizer {
     where
         F: Fn(u32, u32, u32, u32) -> Option<String>,
     {
-        // Check cache first
-        if self.config.enable_cache {
-            if let Some((ref cached_match, timestamp)) = self.last_match {
-                if timestamp.elapsed().as_millis() < self.config.cache_ttl_ms as u128 {
-                    debug!("Using cached screen match: {}", cached_match.screen_name);
-                    return Some(cached_match.clone());
+        if let Some(cached) = self.cached_match() {
+            return Some(cached);
+        }
+
+        // Convert to grayscale for matching
+        let grayscale = bgra_to_grayscale(image_data, width, height);
+        self.recognize_gray(&grayscale, ocr_fn)
+    }
+
+    /// Recognize the current screen from a captured frame of any pixel format
+    ///
+    /// Grayscale frames are matched as they are, without a conversion.
+    pub fn recognize_frame<F>(
+        &mut self,
+        frame: &CapturedFrame,
+        ocr_fn: Option<F>,
+    ) -> Option<ScreenMatch>
+    where
+        F: Fn(u32, u32, u32, u32) -> Option<String>,
+    {
+        match frame.format {
+            PixelFormat::Rgba8 => self.recognize(&frame.data, frame.width, frame.height, ocr_fn),
+            PixelFormat::Gray8 => {
+                if let Some(cached) = self.cached_match() {
+                    return Some(cached);
                 }
+                let grayscale = frame.to_gray_image()?;
+                self.recognize_gray(&grayscale, ocr_fn)
             }
         }
+    }
 
-        let start = Instant::now();
+    /// Score every enabled screen against a frame, in priority order
+    ///
+    /// Unlike recognition, screens below their threshold are scored too and
+    /// the cache is left alone. Text anchors aren't read.
+    pub fn score_screens(&mut self, frame: &CapturedFrame) -> Vec<ScreenScore> {
+        let grayscale = match frame.format {
+            PixelFormat::Rgba8 => bgra_to_grayscale(&frame.data, frame.width, frame.height),
+            PixelFormat::Gray8 => match frame.to_gray_image() {
+                Some(grayscale) => grayscale,
+                None => return Vec::new(),
+            },
+        };
+        let (width, height) = grayscale.dimensions();
+        let similarities = self.frame_similarities(&grayscale, width, height);
+        let no_ocr: Option<fn(u32, u32, u32, u32) -> Option<String>> = None;
 
-        // Convert to grayscale for matching
-        let grayscale = bgra_to_grayscale(image_data, width, height);
+        let mut screens: Vec<_> = self.screens.values().filter(|s| s.enabled).collect();
+        screens.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+        let mut matched_screen_ids = std::collections::HashSet::new();
+        screens
+            .into_iter()
+            .map(|screen| {
+                let (confidence, anchors, threshold) = match screen.match_mode {
+                    ScreenMatchMode::FullScreenshot => (
+                        similarities.screens.get(&screen.id).copied(),
+                        Vec::new(),
+                        screen.match_threshold.max(self.config.full_match_threshold),
+                    ),
+                    ScreenMatchMode::Anchors => {
+                        let anchors: Vec<AnchorMatch> = screen
+                            .anchors
+                            .iter()
+                            .map(|anchor| {
+                                self.match_anchor(anchor, &similarities, width, height, &no_ocr)
+                            })
+                            .collect();
+                        let confidence =
+                            score_anchors(&screen.anchors, &anchors, screen.min_required_anchors);
+                        (confidence, anchors, screen.match_threshold)
+                    }
+                };
+                let parent_matched = screen
+                    .parent_id
+                    .as_ref()
+                    .map_or(true, |parent| matched_screen_ids.contains(parent));
+                let matched = parent_matched && confidence.is_some_and(|c| c >= threshold);
+                if matched {
+                    matched_screen_ids.insert(screen.id.clone());
+                }
+                ScreenScore {
+                    screen_id: screen.id.clone(),
+                    screen_name: screen.name.clone(),
+                    confidence,
+                    threshold,
+                    matched,
+                    anchors,
+                }
+            })
+            .collect()
+    }
+
+    /// The last match, if caching is on and it's recent enough
+    fn cached_match(&self) -> Option<ScreenMatch> {
+        if !self.config.enable_cache {
+            return None;
+        }
+        let (cached_match, timestamp) = self.last_match.as_ref()?;
+        if timestamp.elapsed().as_millis() < self.config.cache_ttl_ms as u128 {
+            debug!("Using cached screen match: {}", cached_match.screen_name);
+            return Some(cached_match.clone());
+        }
+        None
+    }
+
+    /// Recognize the current screen from a grayscale frame
+    fn recognize_gray<F>(&mut self, grayscale: &GrayImage, ocr_fn: Option<F>) -> Option<ScreenMatch>
+    where
+        F: Fn(u32, u32, u32, u32) -> Option<String>,
+    {
+        let start = Instant::now();
+        let (width, height) = grayscale.dimensions();
+        let similarities = self.frame_similarities(grayscale, width, height);
 
         // Get sorted screens by priority
         let mut screens: Vec<_> = self.screens.values().collect();
@@ -272,7 +513,7 @@ impl ScreenRecognizer {
 
             // Try to match this screen
             if let Some(screen_match) =
-                self.match_screen(screen, &grayscale, width, height, &ocr_fn)
+                self.match_screen(screen, &similarities, width, height, &ocr_fn)
             {
                 if screen_match.confidence >= screen.match_threshold {
                     matched_screen_ids.insert(screen.id.clone());
@@ -304,7 +545,7 @@ impl ScreenRecognizer {
     fn match_screen<F>(
         &self,
         screen: &ScreenDefinition,
-        grayscale: &GrayImage,
+        similarities: &FrameSimilarities,
         width: u32,
         height: u32,
         ocr_fn: &Option<F>,
@@ -313,51 +554,166 @@ impl ScreenRecognizer {
         F: Fn(u32, u32, u32, u32) -> Option<String>,
     {
         match screen.match_mode {
-            ScreenMatchMode::FullScreenshot => self.match_full_screenshot(screen, grayscale),
+            ScreenMatchMode::FullScreenshot => self.match_full_screenshot(screen, similarities),
             ScreenMatchMode::Anchors => {
-                self.match_anchors(screen, grayscale, width, height, ocr_fn)
+                self.match_anchors(screen, similarities, width, height, ocr_fn)
             }
         }
     }
 
-    /// Match using full screenshot template
-    fn match_full_screenshot(
-        &self,
-        screen: &ScreenDefinition,
+    /// Compare the frame to every visual anchor and full screen template,
+    /// in one batch on the GPU when enabled
+    fn frame_similarities(
+        &mut self,
         grayscale: &GrayImage,
-    ) -> Option<ScreenMatch> {
-        let template = self.screen_templates.get(&screen.id)?;
-
-        // Scale down for faster matching
-        let scaled_image = if (self.config.match_scale - 1.0).abs() > 0.01 {
-            let new_w = (grayscale.width() as f32 * self.config.match_scale) as u32;
-            let new_h = (grayscale.height() as f32 * self.config.match_scale) as u32;
-            image::imageops::resize(
-                grayscale,
-                new_w,
-                new_h,
-                image::imageops::FilterType::Triangle,
-            )
-        } else {
-            grayscale.clone()
+        width: u32,
+        height: u32,
+    ) -> FrameSimilarities {
+        let wants_gpu = self.config.use_gpu || self.config.benchmark;
+        if wants_gpu && self.gpu.is_none() {
+            self.gpu = Some(
+                GpuMatcher::new(self.config.gpu_adapter.as_ref()).map_err(|e| {
+                    tracing::warn!("GPU template matching unavailable, using the CPU: {:#}", e);
+                    format!("{:#}", e)
+                }),
+            );
+        }
+
+        let screens: Vec<&ScreenDefinition> = self.screens.values().filter(|s| s.enabled).collect();
+
+        // Full templates are compared at a reduced scale for speed
+        let scaled_frame = screens
+            .iter()
+            .any(|s| s.match_mode == ScreenMatchMode::FullScreenshot)
+            .then(|| self.scale_for_matching(grayscale));
+
+        let mut similarities = FrameSimilarities::default();
+        let mut jobs: Vec<(SimilarityKey, Cow<GrayImage>, Cow<GrayImage>)> = Vec::new();
+        for screen in screens {
+            match screen.match_mode {
+                ScreenMatchMode::FullScreenshot => {
+                    let (Some(frame), Some(template)) = (
+                        scaled_frame.as_ref(),
+                        self.screen_templates
+                            .get(&screen.id)
+                            .and_then(|t| closest_template(t, grayscale.dimensions())),
+                    ) else {
+                        continue;
+                    };
+                    let template =
+                        fit_template(frame, Cow::Owned(self.scale_for_matching(template)));
+                    jobs.push((
+                        SimilarityKey::Screen(screen.id.clone()),
+                        Cow::Borrowed(frame),
+                        template,
+                    ));
+                }
+                ScreenMatchMode::Anchors => {
+                    for anchor in &screen.anchors {
+                        let Some(template) = self.anchor_templates.get(&anchor.id) else {
+                            continue;
+                        };
+                        let x = (anchor.bounds.0 * width as f32) as u32;
+                        let y = (anchor.bounds.1 * height as f32) as u32;
+                        let w = (anchor.bounds.2 * width as f32) as u32;
+                        let h = (anchor.bounds.3 * height as f32) as u32;
+                        let region = extract_gray_region(grayscale, x, y, w, h);
+                        // Feature matching runs on the CPU, outside the NCC batch
+                        if let Some(features) = self.anchor_features.get(&anchor.id) {
+                            let similarity =
+                                feature_match::similarity(features, &Features::detect(&region));
+                            similarities.anchors.insert(anchor.id.clone(), similarity);
+                            continue;
+                        }
+                        let template = fit_template(&region, Cow::Borrowed(template));
+                        jobs.push((
+                            SimilarityKey::Anchor(anchor.id.clone()),
+                            Cow::Owned(region),
+                            template,
+                        ));
+                    }
+                }
+            }
+        }
+
+        let pairs: Vec<(&GrayImage, &GrayImage)> = jobs
+            .iter()
+            .map(|(_, image, template)| (image.as_ref(), template.as_ref()))
+            .collect();
+
+        let mut gpu_failure = None;
+        let gpu_run = match self.gpu {
+            Some(Ok(ref gpu)) if wants_gpu && !pairs.is_empty() => {
+                let start = Instant::now();
+                match gpu.similarities(&pairs) {
+                    Ok(values) => Some((values, start.elapsed())),
+                    Err(e) => {
+                        gpu_failure = Some(format!("{:#}", e));
+                        None
+                    }
+                }
+            }
+            _ => None,
         };
 
-        let scaled_template = if (self.config.match_scale - 1.0).abs() > 0.01 {
-            let new_w = (template.width() as f32 * self.config.match_scale) as u32;
-            let new_h = (template.height() as f32 * self.config.match_scale) as u32;
-            image::imageops::resize(
-                template,
-                new_w,
-                new_h,
-                image::imageops::FilterType::Triangle,
-            )
-        } else {
-            template.clone()
+        let use_gpu_values = self.config.use_gpu && gpu_run.is_some();
+        let cpu_run = (self.config.benchmark || !use_gpu_values).then(|| {
+            let start = Instant::now();
+            let values: Vec<f32> = pairs
+                .iter()
+                .map(|(image, template)| ncc(image, template))
+                .collect();
+            (values, start.elapsed())
+        });
+
+        let benchmark = self.config.benchmark.then(|| MatchBenchmark {
+            pairs: pairs.len(),
+            cpu: cpu_run.as_ref().map_or(Duration::ZERO, |(_, time)| *time),
+            gpu: gpu_run.as_ref().map(|(_, time)| *time),
+        });
+
+        let values = match (gpu_run, cpu_run) {
+            (Some((values, _)), _) if use_gpu_values => values,
+            (_, Some((values, _))) => values,
+            _ => Vec::new(),
         };
 
-        // Compute normalized cross-correlation at center position
-        // For full screenshot matching, we compare the entire image
-        let confidence = compute_image_similarity(&scaled_image, &scaled_template);
+        for ((key, _, _), value) in jobs.into_iter().zip(values) {
+            match key {
+                SimilarityKey::Anchor(id) => similarities.anchors.insert(id, value),
+                SimilarityKey::Screen(id) => similarities.screens.insert(id, value),
+            };
+        }
+
+        if let Some(error) = gpu_failure {
+            tracing::warn!("GPU template matching failed, using the CPU: {}", error);
+            self.gpu = Some(Err(error));
+        }
+        if benchmark.is_some() {
+            self.last_benchmark = benchmark;
+        }
+
+        similarities
+    }
+
+    /// Scale an image by the configured match scale
+    fn scale_for_matching(&self, image: &GrayImage) -> GrayImage {
+        if (self.config.match_scale - 1.0).abs() > 0.01 {
+            let new_w = (image.width() as f32 * self.config.match_scale) as u32;
+            let new_h = (image.height() as f32 * self.config.match_scale) as u32;
+            image::imageops::resize(image, new_w, new_h, image::imageops::FilterType::Triangle)
+        } else {
+            image.clone()
+        }
+    }
+
+    /// Match using full screenshot template
+    fn match_full_screenshot(
+        &self,
+        screen: &ScreenDefinition,
+        similarities: &FrameSimilarities,
+    ) -> Option<ScreenMatch> {
+        let confidence = *similarities.screens.get(&screen.id)?;
 
         if confidence >= self.config.full_match_threshold {
             Some(ScreenMatch {
@@ -376,7 +732,7 @@ impl ScreenRecognizer {
     fn match_anchors<F>(
         &self,
         screen: &ScreenDefinition,
-        grayscale: &GrayImage,
+        similarities: &FrameSimilarities,
         width: u32,
         height: u32,
         ocr_fn: &Option<F>,
@@ -388,41 +744,17 @@ impl ScreenRecognizer {
             return None;
         }
 
-        let mut matched_anchors = Vec::new();
-        let mut required_match_count = 0;
-        let mut required_total = 0;
-        let mut total_confidence = 0.0;
-        let mut anchor_count = 0;
+        let matched_anchors: Vec<AnchorMatch> = screen
+            .anchors
+            .iter()
+            .map(|anchor| self.match_anchor(anchor, similarities, width, height, ocr_fn))
+            .collect();
 
-        for anchor in &screen.anchors {
-            let anchor_match = self.match_anchor(anchor, grayscale, width, height, ocr_fn);
-
-            if anchor.required {
-                required_total += 1;
-                if anchor_match.matched {
-                    required_match_count += 1;
-                }
-            }
-
-            if anchor_match.matched {
-                total_confidence += anchor_match.confidence;
-                anchor_count += 1;
-            }
-
-            matched_anchors.push(anchor_match);
-        }
-
-        // All required anchors must match
-        if required_match_count < required_total {
-            return None;
-        }
-
-        // Calculate overall confidence
-        let confidence = if anchor_count > 0 {
-            total_confidence / anchor_count as f32
-        } else {
-            0.0
-        };
+        let confidence = score_anchors(
+            &screen.anchors,
+            &matched_anchors,
+            screen.min_required_anchors,
+        )?;
 
         Some(ScreenMatch {
             screen_id: screen.id.clone(),
@@ -437,7 +769,7 @@ impl ScreenRecognizer {
     fn match_anchor<F>(
         &self,
         anchor: &ScreenAnchor,
-        grayscale: &GrayImage,
+        similarities: &FrameSimilarities,
         width: u32,
         height: u32,
         ocr_fn: &Option<F>,
@@ -452,38 +784,19 @@ impl ScreenRecognizer {
         let h = (anchor.bounds.3 * height as f32) as u32;
 
         match anchor.anchor_type {
-            AnchorType::Visual => self.match_visual_anchor(anchor, grayscale, x, y, w, h),
+            AnchorType::Visual => self.match_visual_anchor(anchor, similarities),
             AnchorType::Text => self.match_text_anchor(anchor, x, y, w, h, ocr_fn),
         }
     }
 
-    /// Match a visual anchor using template matching
+    /// Match a visual anchor using its precomputed template similarity
     fn match_visual_anchor(
         &self,
         anchor: &ScreenAnchor,
-        grayscale: &GrayImage,
-        x: u32,
-        y: u32,
-        w: u32,
-        h: u32,
+        similarities: &FrameSimilarities,
     ) -> AnchorMatch {
-        let template = match self.anchor_templates.get(&anchor.id) {
-            Some(t) => t,
-            None => {
-                return AnchorMatch {
-                    anchor_id: anchor.id.clone(),
-                    matched: false,
-                    confidence: 0.0,
-                    detected_text: None,
-                };
-            }
-        };
-
-        // Extract the region from the grayscale image
-        let region = extract_gray_region(grayscale, x, y, w, h);
-
-        // Compute similarity
-        let confidence = compute_image_similarity(&region, template);
+        // Anchors without a template have no similarity
+        let confidence = similarities.anchors.get(&anchor.id).copied().unwrap_or(0.0);
 
         AnchorMatch {
             anchor_id: anchor.id.clone(),
@@ -520,7 +833,7 @@ impl ScreenRecognizer {
 
         // Get OCR result for the region
         let detected_text = match ocr_fn {
-            Some(f) => f(x, y, w, h),
+            Some(f) => f(x, y, w, h).map(|text| correct_text(&text, &self.dictionary)),
             None => None,
         };
 
@@ -557,12 +870,6 @@ impl ScreenRecognizer {
         chain
     }
 
-    /// Decode PNG template data to grayscale image
-    fn decode_template(&self, data: &[u8]) -> Result<GrayImage> {
-        let img = image::load_from_memory(data).context("Failed to decode template image")?;
-        Ok(img.to_luma8())
-    }
-
     /// Clear all cached data
     pub fn clear_cache(&mut self) {
         self.last_match = None;
@@ -585,6 +892,71 @@ impl Default for ScreenRecognizer {
     }
 }
 
+/// Weighted confidence of a screen's anchor matches
+///
+/// Returns `None` when fewer required anchors matched than the screen needs
+/// (`min_required`, or all of them). Matched anchors contribute their
+/// confidence times their weight; a required anchor that didn't match still
+/// counts its weight, so a screen recognized with an occluded anchor scores
+/// lower. Unmatched optional anchors don't count.
+fn score_anchors(
+    anchors: &[ScreenAnchor],
+    matches: &[AnchorMatch],
+    min_required: Option<u32>,
+) -> Option<f32> {
+    let mut required_total = 0;
+    let mut required_matched = 0;
+    let mut weighted_confidence = 0.0;
+    let mut total_weight = 0.0;
+
+    for (anchor, anchor_match) in anchors.iter().zip(matches) {
+        let weight = anchor.weight.max(0.0);
+        if anchor.required {
+            required_total += 1;
+            if anchor_match.matched {
+                required_matched += 1;
+            }
+        }
+
+        if anchor_match.matched {
+            weighted_confidence += anchor_match.confidence * weight;
+            total_weight += weight;
+        } else if anchor.required {
+            total_weight += weight;
+        }
+    }
+
+    let needed = min_required.map_or(required_total, |k| (k as usize).min(required_total));
+    if required_matched < needed {
+        return None;
+    }
+
+    Some(if total_weight > 0.0 {
+        weighted_confidence / total_weight
+    } else {
+        0.0
+    })
+}
+
+/// How far a template's resolution is from the frame's; a different aspect
+/// ratio counts for more than a different size
+fn resolution_distance(template: (u32, u32), frame: (u32, u32)) -> f32 {
+    let aspect = |(w, h): (u32, u32)| w.max(1) as f32 / h.max(1) as f32;
+    let aspect_diff = (aspect(template) / aspect(frame)).ln().abs();
+    let size_diff = (template.0.max(1) as f32 / frame.0.max(1) as f32)
+        .ln()
+        .abs();
+    aspect_diff * 10.0 + size_diff
+}
+
+/// The template captured closest to the frame's resolution
+fn closest_template(templates: &[GrayImage], frame: (u32, u32)) -> Option<&GrayImage> {
+    templates.iter().min_by(|a, b| {
+        resolution_distance(a.dimensions(), frame)
+            .total_cmp(&resolution_distance(b.dimensions(), frame))
+    })
+}
+
 /// Convert BGRA image data to grayscale
 fn bgra_to_grayscale(data: &[u8], width: u32, height: u32) -> GrayImage {
     let mut gray = GrayImage::new(width, height);
@@ -634,58 +1006,64 @@ fn extract_gray_region(image: &GrayImage, x: u32, y: u32, w: u32, h: u32) -> Gra
 
 /// Compute similarity between two grayscale images using normalized cross-correlation
 fn compute_image_similarity(image: &GrayImage, template: &GrayImage) -> f32 {
-    let (img_w, img_h) = image.dimensions();
-    let (tmpl_w, tmpl_h) = template.dimensions();
-
-    // If sizes differ significantly, resize template to match
-    let template =
-        if (img_w as i32 - tmpl_w as i32).abs() > 5 || (img_h as i32 - tmpl_h as i32).abs() > 5 {
-            image::imageops::resize(
-                template,
-                img_w,
-                img_h,
-                image::imageops::FilterType::Triangle,
-            )
-        } else {
-            template.clone()
-        };
+    let template = fit_template(image, Cow::Borrowed(template));
+    ncc(image, &template)
+}
 
+/// Resize a template to the image's size when they differ significantly
+fn fit_template<'a>(image: &GrayImage, template: Cow<'a, GrayImage>) -> Cow<'a, GrayImage> {
+    let (img_w, img_h) = image.dimensions();
     let (tmpl_w, tmpl_h) = template.dimensions();
-    let compare_w = img_w.min(tmpl_w);
-    let compare_h = img_h.min(tmpl_h);
 
-    if compare_w == 0 || compare_h == 0 {
-        return 0.0;
+    if (img_w as i32 - tmpl_w as i32).abs() > 5 || (img_h as i32 - tmpl_h as i32).abs() > 5 {
+        Cow::Owned(image::imageops::resize(
+            template.as_ref(),
+            img_w,
+            img_h,
+            image::imageops::FilterType::Triangle,
+        ))
+    } else {
+        template
     }
+}
 
-    // Compute normalized cross-correlation
-    let mut sum_it = 0.0f64;
-    let mut sum_i2 = 0.0f64;
-    let mut sum_t2 = 0.0f64;
-    let mut sum_i = 0.0f64;
-    let mut sum_t = 0.0f64;
-    let count = (compare_w * compare_h) as f64;
+/// Normalized cross-correlation over the images' common size
+fn ncc(image: &GrayImage, template: &GrayImage) -> f32 {
+    let compare_w = image.width().min(template.width());
+    let compare_h = image.height().min(template.height());
 
+    // Sums of image x template, image², template², image and template
+    let mut sums = [0.0f64; 5];
     for y in 0..compare_h {
         for x in 0..compare_w {
             let img_val = image.get_pixel(x, y).0[0] as f64;
             let tmpl_val = template.get_pixel(x, y).0[0] as f64;
 
-            sum_it += img_val * tmpl_val;
-            sum_i2 += img_val * img_val;
-            sum_t2 += tmpl_val * tmpl_val;
-            sum_i += img_val;
-            sum_t += tmpl_val;
+            sums[0] += img_val * tmpl_val;
+            sums[1] += img_val * img_val;
+            sums[2] += tmpl_val * tmpl_val;
+            sums[3] += img_val;
+            sums[4] += tmpl_val;
         }
     }
 
-    // Zero-mean normalized cross-correlation
+    ncc_from_sums(sums, (compare_w * compare_h) as f64)
+}
+
+/// Zero-mean normalized cross-correlation from pixel sums (image x template,
+/// image², template², image, template) over `count` pixels
+pub(crate) fn ncc_from_sums(sums: [f64; 5], count: f64) -> f32 {
+    if count == 0.0 {
+        return 0.0;
+    }
+    let [sum_it, sum_i2, sum_t2, sum_i, sum_t] = sums;
+
     let mean_i = sum_i / count;
     let mean_t = sum_t / count;
 
     let numerator = sum_it - count * mean_i * mean_t;
-    let denom_i = (sum_i2 - count * mean_i * mean_i).sqrt();
-    let denom_t = (sum_t2 - count * mean_t * mean_t).sqrt();
+    let denom_i = (sum_i2 - count * mean_i * mean_i).max(0.0).sqrt();
+    let denom_t = (sum_t2 - count * mean_t * mean_t).max(0.0).sqrt();
 
     let denominator = denom_i * denom_t;
 
@@ -697,7 +1075,7 @@ fn compute_image_similarity(image: &GrayImage, template: &GrayImage) -> f32 {
 }
 
 /// Compute text similarity using Levenshtein distance
-fn text_similarity(a: &str, b: &str) -> f32 {
+pub(crate) fn text_similarity(a: &str, b: &str) -> f32 {
     let a = a.to_lowercase();
     let b = b.to_lowercase();
 
@@ -793,12 +1171,15 @@ mod tests {
             match_mode: ScreenMatchMode::Anchors,
             anchors: vec![],
             full_template: None,
+            extra_templates: vec![],
             match_threshold: 0.8,
             enabled: true,
             priority: 10,
             ocr_zone_overrides: vec![],
             rules_to_trigger: vec![],
             show_notification: true,
+            min_required_anchors: None,
+            popup_bounds: None,
         };
 
         recognizer.add_screen(screen);
@@ -821,12 +1202,15 @@ mod tests {
             match_mode: ScreenMatchMode::Anchors,
             anchors: vec![],
             full_template: None,
+            extra_templates: vec![],
             match_threshold: 0.8,
             enabled: true,
             priority: 10,
             ocr_zone_overrides: vec![],
             rules_to_trigger: vec![],
             show_notification: true,
+            min_required_anchors: None,
+            popup_bounds: None,
         });
 
         // Add child screen
@@ -837,12 +1221,15 @@ mod tests {
             match_mode: ScreenMatchMode::Anchors,
             anchors: vec![],
             full_template: None,
+            extra_templates: vec![],
             match_threshold: 0.8,
             enabled: true,
             priority: 5,
             ocr_zone_overrides: vec![],
             rules_to_trigger: vec![],
             show_notification: true,
+            min_required_anchors: None,
+            popup_bounds: None,
         });
 
         let hierarchy = recognizer.get_hierarchy();
@@ -852,6 +1239,102 @@ mod tests {
         assert_eq!(hierarchy[0].children[0].screen.id, "child");
     }
 
+    fn anchor(id: &str, required: bool, weight: f32) -> ScreenAnchor {
+        ScreenAnchor {
+            id: id.to_string(),
+            anchor_type: AnchorType::Visual,
+            bounds: (0.0, 0.0, 0.1, 0.1),
+            template_data: None,
+            template_file: None,
+            expected_text: None,
+            text_similarity: 0.8,
+            required,
+            weight,
+            match_strategy: MatchStrategy::Ncc,
+        }
+    }
+
+    fn anchor_match(id: &str, confidence: Option<f32>) -> AnchorMatch {
+        AnchorMatch {
+            anchor_id: id.to_string(),
+            matched: confidence.is_some(),
+            confidence: confidence.unwrap_or(0.0),
+            detected_text: None,
+        }
+    }
+
+    #[test]
+    fn test_score_anchors_all_required() {
+        let anchors = vec![anchor("a", true, 1.0), anchor("b", true, 1.0)];
+
+        let score = score_anchors(
+            &anchors,
+            &[anchor_match("a", Some(0.9)), anchor_match("b", Some(0.7))],
+            None,
+        );
+        assert!((score.unwrap() - 0.8).abs() < 0.001);
+
+        let occluded = [anchor_match("a", Some(0.9)), anchor_match("b", None)];
+        assert_eq!(score_anchors(&anchors, &occluded, None), None);
+    }
+
+    #[test]
+    fn test_score_anchors_weighted_partial_match() {
+        let anchors = vec![
+            anchor("title", true, 3.0),
+            anchor("icon", true, 1.0),
+            anchor("bonus", false, 1.0),
+        ];
+        let matches = [
+            anchor_match("title", Some(1.0)),
+            anchor_match("icon", None),
+            anchor_match("bonus", None),
+        ];
+
+        // 1 of 2 required anchors is enough; the missing one lowers the score
+        let score = score_anchors(&anchors, &matches, Some(1)).unwrap();
+        assert!((score - 0.75).abs() < 0.001);
+        assert_eq!(score_anchors(&anchors, &matches, Some(2)), None);
+    }
+
+    #[test]
+    fn test_ncc_ignores_brightness_offset() {
+        let image = GrayImage::from_fn(8, 8, |x, y| Luma([(x * 20 + y * 5) as u8]));
+        let brighter = GrayImage::from_fn(8, 8, |x, y| Luma([(x * 20 + y * 5 + 40) as u8]));
+        assert!((ncc(&image, &brighter) - 1.0).abs() < 0.001);
+
+        // The GPU shader sums pixels centered on mid-gray
+        let centered = |image: &GrayImage| -> Vec<f64> {
+            image.pixels().map(|p| p.0[0] as f64 - 128.0).collect()
+        };
+        let (i, t) = (centered(&image), centered(&brighter));
+        let sums = [
+            i.iter().zip(&t).map(|(a, b)| a * b).sum(),
+            i.iter().map(|a| a * a).sum(),
+            t.iter().map(|b| b * b).sum(),
+            i.iter().sum(),
+            t.iter().sum(),
+        ];
+        assert!((ncc_from_sums(sums, 64.0) - ncc(&image, &brighter)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_closest_template_resolution() {
+        let templates = vec![
+            GrayImage::new(1920, 1080),
+            GrayImage::new(2560, 1440),
+            GrayImage::new(3440, 1440),
+        ];
+        let closest = |frame| closest_template(&templates, frame).unwrap().dimensions();
+
+        assert_eq!(closest((1920, 1080)), (1920, 1080));
+        assert_eq!(closest((2560, 1440)), (2560, 1440));
+        assert_eq!(closest((3840, 2160)), (2560, 1440));
+        // A matching aspect ratio beats a closer size
+        assert_eq!(closest((2560, 1080)), (3440, 1440));
+        assert!(closest_template(&[], (1920, 1080)).is_none());
+    }
+
     #[test]
     fn test_image_similarity_identical() {
         let img = GrayImage::from_fn(10, 10, |x, y| Luma([((x + y) % 256) as u8]));
@@ -0,0 +1,167 @@
+//! Scrolling-text zone stitching
+//!
+//! Chat and combat-log zones scroll: most lines in a frame were already read
+//! in the previous one, just shifted up. Instead of recording the whole box on
+//! every change, the stitcher finds how many lines the text scrolled by
+//! matching the previous frame's bottom lines against the current frame's top
+//! lines, and emits only the lines below the overlap.
+
+use std::collections::VecDeque;
+
+use crate::vision::screen_recognition::text_similarity;
+use crate::vision::TextRegion;
+
+/// Minimum similarity for two OCR lines to count as the same line
+const LINE_MATCH_SIMILARITY: f32 = 0.8;
+
+/// Stitched lines kept per zone for display
+pub const MAX_RECENT_LINES: usize = 50;
+
+/// Group detected text regions into lines, top to bottom
+///
+/// A region joins the current line when its vertical center falls within the
+/// line's extent; regions within a line are ordered left to right.
+pub fn group_lines(regions: &[TextRegion]) -> Vec<String> {
+    let mut sorted: Vec<&TextRegion> = regions
+        .iter()
+        .filter(|r| !r.text.trim().is_empty())
+        .collect();
+    sorted.sort_by_key(|r| r.bounds.1 + r.bounds.3 / 2);
+
+    // (top, bottom, regions)
+    let mut lines: Vec<(u32, u32, Vec<&TextRegion>)> = Vec::new();
+    for region in sorted {
+        let (_, y, _, h) = region.bounds;
+        let center = y + h / 2;
+        match lines.last_mut() {
+            Some((top, bottom, members)) if center >= *top && center <= *bottom => {
+                *top = (*top).min(y);
+                *bottom = (*bottom).max(y + h);
+                members.push(region);
+            }
+            _ => lines.push((y, y + h, vec![region])),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(_, _, mut members)| {
+            members.sort_by_key(|r| r.bounds.0);
+            members
+                .iter()
+                .map(|r| r.text.trim())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// Number of lines at the top of `current` that were already at the bottom
+/// of `previous`
+pub fn scroll_overlap(previous: &[String], current: &[String]) -> usize {
+    (1..=previous.len().min(current.len()))
+        .rev()
+        .find(|&overlap| {
+            previous[previous.len() - overlap..]
+                .iter()
+                .zip(&current[..overlap])
+                .all(|(a, b)| text_similarity(a, b) >= LINE_MATCH_SIMILARITY)
+        })
+        .unwrap_or(0)
+}
+
+/// Tracks a scrolling zone's visible lines between frames
+#[derive(Debug, Clone, Default)]
+pub struct ScrollStitcher {
+    /// Lines visible in the last frame
+    visible: Vec<String>,
+    /// Most recently emitted lines, oldest first
+    recent: VecDeque<String>,
+}
+
+impl ScrollStitcher {
+    /// Feed the lines visible in a new frame, returning the ones not seen before
+    ///
+    /// The first frame emits every visible line. Frames without any text are
+    /// ignored so a momentarily unreadable box doesn't re-emit its whole
+    /// content afterwards.
+    pub fn push(&mut self, lines: Vec<String>) -> Vec<String> {
+        if lines.is_empty() {
+            return Vec::new();
+        }
+
+        let overlap = scroll_overlap(&self.visible, &lines);
+        let new_lines = lines[overlap..].to_vec();
+
+        for line in &new_lines {
+            if self.recent.len() == MAX_RECENT_LINES {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(line.clone());
+        }
+        self.visible = lines;
+
+        new_lines
+    }
+
+    /// Most recently emitted lines, oldest first
+    pub fn recent(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.recent.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn region(text: &str, x: u32, y: u32) -> TextRegion {
+        TextRegion {
+            text: text.to_string(),
+            bounds: (x, y, 40, 12),
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_group_lines() {
+        let regions = vec![
+            region("world", 50, 21),
+            region("second", 0, 40),
+            region("hello", 0, 20),
+        ];
+        assert_eq!(group_lines(&regions), lines(&["hello world", "second"]));
+    }
+
+    #[test]
+    fn test_only_scrolled_in_lines_are_emitted() {
+        let mut stitcher = ScrollStitcher::default();
+        assert_eq!(
+            stitcher.push(lines(&["[Guild] hi", "[Say] gg", "[Guild] brb"])),
+            lines(&["[Guild] hi", "[Say] gg", "[Guild] brb"])
+        );
+
+        // Scrolled by one line, with a small misread on an old line
+        assert_eq!(
+            stitcher.push(lines(&["[Say] gq", "[Guild] brb", "[Trade] WTS ore"])),
+            lines(&["[Trade] WTS ore"])
+        );
+
+        // Unchanged and unreadable frames emit nothing
+        assert!(stitcher
+            .push(lines(&["[Say] gg", "[Guild] brb", "[Trade] WTS ore"]))
+            .is_empty());
+        assert!(stitcher.push(Vec::new()).is_empty());
+        assert_eq!(stitcher.recent().len(), 4);
+    }
+
+    #[test]
+    fn test_repeated_lines_are_kept() {
+        let previous = lines(&["a: hello", "b: gg"]);
+        let current = lines(&["b: gg", "b: gg"]);
+        assert_eq!(scroll_overlap(&previous, &current), 1);
+    }
+}
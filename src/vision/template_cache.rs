@@ -0,0 +1,289 @@
+//! Decoded template cache
+//!
+//! Screen templates are stored in profiles as PNGs. Decoding them to grayscale
+//! and detecting the keypoints of feature-matched anchors is most of the time
+//! spent loading an image-heavy profile, so the results are kept in a binary
+//! file per profile, keyed by a hash of each PNG. The whole file is thrown
+//! away when the profile file is newer than it.
+
+use anyhow::{Context, Result};
+use image::GrayImage;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::feature_match::{Features, DESCRIPTOR_WORDS};
+
+/// Start of every cache file
+const MAGIC: &[u8; 4] = b"GTKT";
+
+/// Cache format version; older files are rebuilt
+const CACHE_VERSION: u32 = 1;
+
+/// A decoded template
+#[derive(Debug, Clone)]
+pub struct CachedTemplate {
+    /// Grayscale pixels
+    pub image: GrayImage,
+    /// Keypoints, for anchors matched by features
+    pub features: Option<Features>,
+}
+
+/// Decoded templates of one profile
+#[derive(Debug, Default)]
+pub struct TemplateCache {
+    /// Modification time of the profile file the templates came from
+    profile_modified: Option<SystemTime>,
+    /// Templates by PNG hash
+    entries: HashMap<u64, CachedTemplate>,
+    /// Keys looked up since loading, so templates no longer used are dropped
+    used: HashSet<u64>,
+    /// Whether templates were decoded that aren't in the file yet
+    changed: bool,
+}
+
+impl TemplateCache {
+    /// Read the cache for a profile last modified at `profile_modified`
+    ///
+    /// A missing, damaged or outdated file gives an empty cache.
+    pub fn load(path: &Path, profile_modified: SystemTime) -> Self {
+        let entries = std::fs::read(path)
+            .ok()
+            .and_then(|bytes| read_entries(&bytes, profile_modified));
+        Self {
+            profile_modified: Some(profile_modified),
+            entries: entries.unwrap_or_default(),
+            used: HashSet::new(),
+            changed: false,
+        }
+    }
+
+    /// Number of cached templates
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no templates are cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// A template decoded from PNG data, from the cache if it's there
+    ///
+    /// Keypoints are detected too when `features` is set.
+    pub fn decode(&mut self, data: &[u8], features: bool) -> Result<CachedTemplate> {
+        let key = template_hash(data);
+        self.used.insert(key);
+        if let Some(cached) = self.entries.get_mut(&key) {
+            if features && cached.features.is_none() {
+                cached.features = Some(Features::detect(&cached.image));
+                self.changed = true;
+            }
+            return Ok(cached.clone());
+        }
+
+        let image = image::load_from_memory(data)
+            .context("Failed to decode template image")?
+            .to_luma8();
+        let template = CachedTemplate {
+            features: features.then(|| Features::detect(&image)),
+            image,
+        };
+        self.entries.insert(key, template.clone());
+        self.changed = true;
+        Ok(template)
+    }
+
+    /// Write the cache if templates were added or dropped since it was read
+    pub fn save_if_changed(&mut self, path: &Path) -> Result<()> {
+        let before = self.entries.len();
+        let used = std::mem::take(&mut self.used);
+        self.entries.retain(|key, _| used.contains(key));
+        if !self.changed && self.entries.len() == before {
+            return Ok(());
+        }
+        let Some(modified) = self.profile_modified else {
+            return Ok(());
+        };
+
+        std::fs::write(path, write_entries(&self.entries, modified))
+            .with_context(|| format!("Failed to write {:?}", path))?;
+        self.changed = false;
+        Ok(())
+    }
+}
+
+/// Cache key of a PNG
+fn template_hash(data: &[u8]) -> u64 {
+    let digest = Sha256::digest(data);
+    u64::from_le_bytes(digest[..8].try_into().expect("digest is 32 bytes"))
+}
+
+/// Seconds and nanoseconds since the Unix epoch
+fn timestamp(time: SystemTime) -> (u64, u32) {
+    let since = time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+    (since.as_secs(), since.subsec_nanos())
+}
+
+/// Cache file contents
+fn write_entries(entries: &HashMap<u64, CachedTemplate>, modified: SystemTime) -> Vec<u8> {
+    let mut out = Vec::new();
+    let (secs, nanos) = timestamp(modified);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+    out.extend_from_slice(&secs.to_le_bytes());
+    out.extend_from_slice(&nanos.to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+
+    for (key, template) in entries {
+        out.extend_from_slice(&key.to_le_bytes());
+        out.extend_from_slice(&template.image.width().to_le_bytes());
+        out.extend_from_slice(&template.image.height().to_le_bytes());
+        out.extend_from_slice(template.image.as_raw());
+        match template.features {
+            Some(ref features) => {
+                let (points, descriptors) = features.parts();
+                out.push(1);
+                out.extend_from_slice(&(points.len() as u32).to_le_bytes());
+                for &(x, y) in points {
+                    out.extend_from_slice(&x.to_le_bytes());
+                    out.extend_from_slice(&y.to_le_bytes());
+                }
+                for word in descriptors.iter().flatten() {
+                    out.extend_from_slice(&word.to_le_bytes());
+                }
+            }
+            None => out.push(0),
+        }
+    }
+    out
+}
+
+/// Templates in a cache file, or None if it's damaged, from another version
+/// or older than the profile
+fn read_entries(bytes: &[u8], modified: SystemTime) -> Option<HashMap<u64, CachedTemplate>> {
+    let mut reader = Reader(bytes);
+    if reader.take(4)? != MAGIC || reader.u32()? != CACHE_VERSION {
+        return None;
+    }
+    if (reader.u64()?, reader.u32()?) != timestamp(modified) {
+        return None;
+    }
+
+    let count = reader.u32()?;
+    let mut entries = HashMap::new();
+    for _ in 0..count {
+        let key = reader.u64()?;
+        let (width, height) = (reader.u32()?, reader.u32()?);
+        let pixels = reader.take(width as usize * height as usize)?.to_vec();
+        let image = GrayImage::from_raw(width, height, pixels)?;
+        let features = match reader.take(1)?[0] {
+            0 => None,
+            _ => {
+                let len = reader.u32()? as usize;
+                let points = (0..len)
+                    .map(|_| Some((reader.f32()?, reader.f32()?)))
+                    .collect::<Option<Vec<_>>>()?;
+                let descriptors = (0..len)
+                    .map(|_| {
+                        let mut words = [0u64; DESCRIPTOR_WORDS];
+                        for word in &mut words {
+                            *word = reader.u64()?;
+                        }
+                        Some(words)
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Features::from_parts(points, descriptors)?)
+            }
+        };
+        entries.insert(key, CachedTemplate { image, features });
+    }
+    Some(entries)
+}
+
+/// Little-endian values read off the front of a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = GrayImage::from_fn(width, height, |x, y| image::Luma([(x * 7 + y * 13) as u8]));
+        let mut data = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_template_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.bin");
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let (small, large) = (png(4, 3), png(64, 64));
+
+        let mut cache = TemplateCache::load(&path, modified);
+        assert!(cache.is_empty());
+        let decoded = cache.decode(&small, false).unwrap();
+        assert_eq!(decoded.image.dimensions(), (4, 3));
+        cache.decode(&large, true).unwrap();
+        cache.save_if_changed(&path).unwrap();
+
+        let mut cache = TemplateCache::load(&path, modified);
+        assert_eq!(cache.len(), 2);
+        let cached = cache.decode(&small, false).unwrap();
+        assert_eq!(cached.image, decoded.image);
+        assert!(cache.decode(&large, true).unwrap().features.is_some());
+        assert!(!cache.changed);
+
+        // Templates not looked up are dropped when saving
+        let mut cache = TemplateCache::load(&path, modified);
+        cache.decode(&small, false).unwrap();
+        cache.save_if_changed(&path).unwrap();
+        assert_eq!(TemplateCache::load(&path, modified).len(), 1);
+
+        // A newer profile file makes the cache stale
+        let newer = modified + Duration::from_secs(1);
+        assert!(TemplateCache::load(&path, newer).is_empty());
+    }
+
+    #[test]
+    fn test_damaged_cache_is_ignored() {
+        let modified = UNIX_EPOCH + Duration::from_secs(5);
+        let mut cache = TemplateCache::default();
+        cache.profile_modified = Some(modified);
+        cache.decode(&png(4, 4), false).unwrap();
+        let bytes = write_entries(&cache.entries, modified);
+
+        assert!(read_entries(&bytes, modified).is_some());
+        assert!(read_entries(&bytes[..bytes.len() - 1], modified).is_none());
+        assert!(read_entries(b"nope", modified).is_none());
+    }
+}
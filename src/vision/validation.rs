@@ -0,0 +1,248 @@
+//! Profile validation against its sample library
+//!
+//! Runs screen recognition and every enabled zone over a saved sample frame
+//! and compares the results with what the sample read as when its results were
+//! last accepted, so UI changes from a game patch show up as failing screens
+//! and zones. A sample without accepted results only fails zones that read
+//! nothing; if it passes, its results become the expectation.
+
+use crate::capture::CapturedFrame;
+use crate::config::OcrPreprocessing;
+use crate::storage::profiles::{OcrRegion, RecognitionDictionary, ScreenDefinition};
+use crate::storage::samples::SampleExpectation;
+use crate::vision::batch::read_zones;
+use crate::vision::screen_recognition::{ScreenRecognitionConfig, ScreenRecognizer};
+use crate::vision::VisionPipeline;
+
+/// Result of one zone on a sample
+#[derive(Debug, Clone)]
+pub struct ZoneCheck {
+    /// Zone ID
+    pub zone_id: String,
+    /// Zone name
+    pub zone_name: String,
+    /// Text read from the sample
+    pub text: String,
+    /// Raw mean OCR confidence of the reading
+    pub confidence: Option<f32>,
+    /// Whether the sample's accepted results include this zone
+    pub labeled: bool,
+    /// Why the zone fails, if it does
+    pub issue: Option<String>,
+}
+
+/// Result of validating one sample
+#[derive(Debug, Clone, Default)]
+pub struct SampleValidation {
+    /// Sample name
+    pub sample: String,
+    /// ID of the recognized screen
+    pub screen_id: Option<String>,
+    /// Name of the recognized screen
+    pub screen_name: Option<String>,
+    /// Why screen recognition fails, if it does
+    pub screen_issue: Option<String>,
+    /// Zone results
+    pub zones: Vec<ZoneCheck>,
+    /// The sample had no accepted results yet
+    pub new_baseline: bool,
+    /// The sample could not be loaded
+    pub error: Option<String>,
+}
+
+impl SampleValidation {
+    /// A sample that could not be loaded
+    pub fn load_failed(sample: &str, error: String) -> Self {
+        Self {
+            sample: sample.to_string(),
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the screen and every zone read as expected
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && self.screen_issue.is_none()
+            && self.zones.iter().all(|zone| zone.issue.is_none())
+    }
+
+    /// Raw confidence of each labeled zone reading and whether it was right
+    pub fn labeled_readings(&self) -> impl Iterator<Item = (f32, bool)> + '_ {
+        self.zones
+            .iter()
+            .filter(|zone| zone.labeled)
+            .filter_map(|zone| Some((zone.confidence?, zone.issue.is_none())))
+    }
+
+    /// These results as the sample's expectation
+    pub fn expectation(&self) -> SampleExpectation {
+        SampleExpectation {
+            screen_id: self.screen_id.clone(),
+            zones: self
+                .zones
+                .iter()
+                .map(|zone| (zone.zone_id.clone(), zone.text.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Why a zone reading fails, given the accepted reading (if any)
+pub fn check_zone(text: &str, expected: Option<&str>) -> Option<String> {
+    match expected {
+        Some(expected) if expected != text => {
+            Some(format!("Read '{}', expected '{}'", text, expected))
+        }
+        Some(_) => None,
+        None if text.is_empty() => Some("No text read".to_string()),
+        None => None,
+    }
+}
+
+/// Why screen recognition fails, given the accepted screen (if any)
+///
+/// `expected` is `None` when the sample has no accepted results, and
+/// `Some(None)` when no screen was recognized in it.
+pub fn check_screen(
+    actual: Option<&str>,
+    expected: Option<Option<&str>>,
+    screens: &[ScreenDefinition],
+) -> Option<String> {
+    let expected = expected?;
+    if actual == expected {
+        return None;
+    }
+
+    let name = |id: Option<&str>| match id {
+        Some(id) => screens
+            .iter()
+            .find(|screen| screen.id == id)
+            .map_or_else(|| id.to_string(), |screen| screen.name.clone()),
+        None => "no screen".to_string(),
+    };
+    Some(format!(
+        "Recognized {}, expected {}",
+        name(actual),
+        name(expected)
+    ))
+}
+
+/// Run screen recognition and the enabled zones on a sample frame
+///
+/// Zone confidences are reported raw, without the pipeline's calibration, so
+/// the results can be used to fit a new one.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_sample(
+    pipeline: &mut VisionPipeline,
+    frame: &CapturedFrame,
+    sample: &str,
+    zones: &[OcrRegion],
+    screens: &[ScreenDefinition],
+    dictionary: &RecognitionDictionary,
+    preprocessing: &OcrPreprocessing,
+    expectation: Option<&SampleExpectation>,
+) -> SampleValidation {
+    let mut recognizer = ScreenRecognizer::with_config(ScreenRecognitionConfig {
+        enable_cache: false,
+        ..Default::default()
+    });
+    recognizer.set_dictionary(dictionary.clone());
+    recognizer.load_screens(screens.to_vec());
+    let screen =
+        recognizer.recognize_frame::<fn(u32, u32, u32, u32) -> Option<String>>(frame, None);
+    let screen_id = screen.as_ref().map(|m| m.screen_id.clone());
+    let screen_issue = check_screen(
+        screen_id.as_deref(),
+        expectation.map(|e| e.screen_id.as_deref()),
+        screens,
+    );
+
+    let zones: Vec<OcrRegion> = zones.iter().filter(|z| z.enabled).cloned().collect();
+    let calibration = pipeline.calibration().to_vec();
+    pipeline.set_calibration(Vec::new());
+    let readings = read_zones(pipeline, frame, &zones, preprocessing, Some(dictionary));
+    pipeline.set_calibration(calibration);
+    let zones = zones
+        .iter()
+        .zip(readings)
+        .map(|(zone, reading)| {
+            let (text, confidence) =
+                reading.map_or((String::new(), None), |r| (r.text, r.confidence));
+            // Zones added since the results were accepted have no expectation
+            let expected = expectation
+                .and_then(|e| e.zones.get(&zone.id))
+                .map(String::as_str);
+            ZoneCheck {
+                zone_id: zone.id.clone(),
+                zone_name: zone.name.clone(),
+                confidence,
+                labeled: expected.is_some(),
+                issue: check_zone(&text, expected),
+                text,
+            }
+        })
+        .collect();
+
+    SampleValidation {
+        sample: sample.to_string(),
+        screen_id,
+        screen_name: screen.map(|m| m.screen_name),
+        screen_issue,
+        zones,
+        new_baseline: expectation.is_none(),
+        error: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_zone() {
+        assert_eq!(check_zone("100", Some("100")), None);
+        assert_eq!(
+            check_zone("10O", Some("100")),
+            Some("Read '10O', expected '100'".to_string())
+        );
+        assert_eq!(check_zone("", Some("")), None);
+        assert_eq!(check_zone("", None), Some("No text read".to_string()));
+        assert_eq!(check_zone("100", None), None);
+    }
+
+    #[test]
+    fn test_labeled_readings() {
+        let zone = |confidence, labeled, issue: Option<&str>| ZoneCheck {
+            zone_id: "gold".to_string(),
+            zone_name: "Gold".to_string(),
+            text: "100".to_string(),
+            confidence,
+            labeled,
+            issue: issue.map(str::to_string),
+        };
+        let validation = SampleValidation {
+            zones: vec![
+                zone(Some(0.9), true, None),
+                zone(Some(0.4), true, Some("Read '10O', expected '100'")),
+                zone(Some(0.8), false, None),
+                zone(None, true, Some("Read '', expected '100'")),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            validation.labeled_readings().collect::<Vec<_>>(),
+            [(0.9, true), (0.4, false)]
+        );
+    }
+
+    #[test]
+    fn test_check_screen() {
+        assert_eq!(check_screen(Some("hud"), None, &[]), None);
+        assert_eq!(check_screen(Some("hud"), Some(Some("hud")), &[]), None);
+        assert_eq!(
+            check_screen(None, Some(Some("hud")), &[]),
+            Some("Recognized no screen, expected hud".to_string())
+        );
+    }
+}
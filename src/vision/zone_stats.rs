@@ -0,0 +1,178 @@
+//! Per-zone OCR quality tracking
+//!
+//! Aggregates OCR confidence and failure rate per zone over a session in fixed
+//! time buckets, so zones that read poorly (often after a game patch moves the
+//! UI) stand out in the Zone OCR panel.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Time covered by one bucket
+pub const BUCKET_DURATION: Duration = Duration::from_secs(10);
+
+/// Number of buckets kept (10 minutes of history)
+pub const MAX_BUCKETS: usize = 60;
+
+/// OCR attempts aggregated over a time span
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsBucket {
+    /// OCR runs
+    pub attempts: u32,
+    /// Runs that errored or produced no usable text
+    pub failures: u32,
+    confidence_sum: f32,
+    confidence_count: u32,
+}
+
+impl StatsBucket {
+    fn record(&mut self, confidence: Option<f32>, failed: bool) {
+        self.attempts += 1;
+        if failed {
+            self.failures += 1;
+        }
+        if let Some(confidence) = confidence {
+            self.confidence_sum += confidence;
+            self.confidence_count += 1;
+        }
+    }
+
+    /// Mean confidence of runs that reported one
+    pub fn mean_confidence(&self) -> Option<f32> {
+        (self.confidence_count > 0).then(|| self.confidence_sum / self.confidence_count as f32)
+    }
+
+    /// Fraction of runs that failed (0.0 when there were none)
+    pub fn failure_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.failures as f32 / self.attempts as f32
+        }
+    }
+
+    /// Combined quality from 0.0 (always failing) to 1.0, `None` without attempts
+    pub fn quality(&self) -> Option<f32> {
+        if self.attempts == 0 {
+            return None;
+        }
+        let confidence = self.mean_confidence().unwrap_or(0.0);
+        Some(confidence * (1.0 - self.failure_rate()))
+    }
+}
+
+/// OCR quality history of a single zone
+#[derive(Debug, Clone)]
+pub struct ZoneOcrStats {
+    /// Buckets, oldest first; the last one is the current bucket
+    buckets: VecDeque<StatsBucket>,
+    /// Start of the current bucket
+    current_start: Instant,
+    /// Totals for the whole session
+    total: StatsBucket,
+}
+
+impl ZoneOcrStats {
+    /// Start tracking at `now`
+    pub fn new(now: Instant) -> Self {
+        let mut buckets = VecDeque::with_capacity(MAX_BUCKETS);
+        buckets.push_back(StatsBucket::default());
+        Self {
+            buckets,
+            current_start: now,
+            total: StatsBucket::default(),
+        }
+    }
+
+    /// Record one OCR run
+    ///
+    /// `confidence` is the mean confidence of the detected text (if any), and
+    /// `failed` is set when OCR errored or no usable text was found.
+    pub fn record(&mut self, now: Instant, confidence: Option<f32>, failed: bool) {
+        self.advance(now);
+        if let Some(bucket) = self.buckets.back_mut() {
+            bucket.record(confidence, failed);
+        }
+        self.total.record(confidence, failed);
+    }
+
+    /// Buckets oldest first, with empty buckets for periods without OCR runs
+    pub fn buckets(&self) -> impl Iterator<Item = &StatsBucket> {
+        self.buckets.iter()
+    }
+
+    /// Totals for the whole session
+    pub fn total(&self) -> &StatsBucket {
+        &self.total
+    }
+
+    /// Start new buckets until the current one contains `now`
+    fn advance(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.current_start);
+        let steps = (elapsed.as_millis() / BUCKET_DURATION.as_millis()) as usize;
+        if steps == 0 {
+            return;
+        }
+
+        for _ in 0..steps.min(MAX_BUCKETS) {
+            self.buckets.push_back(StatsBucket::default());
+        }
+        while self.buckets.len() > MAX_BUCKETS {
+            self.buckets.pop_front();
+        }
+        self.current_start += BUCKET_DURATION * steps as u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_rates() {
+        let mut bucket = StatsBucket::default();
+        assert_eq!(bucket.quality(), None);
+
+        bucket.record(Some(0.8), false);
+        bucket.record(Some(0.6), false);
+        bucket.record(None, true);
+        bucket.record(Some(1.0), true);
+
+        assert_eq!(bucket.attempts, 4);
+        assert!((bucket.mean_confidence().unwrap() - 0.8).abs() < 1e-6);
+        assert!((bucket.failure_rate() - 0.5).abs() < 1e-6);
+        assert!((bucket.quality().unwrap() - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_records_go_into_time_buckets() {
+        let start = Instant::now();
+        let mut stats = ZoneOcrStats::new(start);
+
+        stats.record(start, Some(0.9), false);
+        stats.record(start + BUCKET_DURATION * 2, None, true);
+
+        let buckets: Vec<_> = stats.buckets().collect();
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].attempts, 1);
+        assert_eq!(buckets[1].attempts, 0);
+        assert_eq!(buckets[2].failures, 1);
+        assert_eq!(stats.total().attempts, 2);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let start = Instant::now();
+        let mut stats = ZoneOcrStats::new(start);
+
+        for i in 0..MAX_BUCKETS as u32 * 2 {
+            stats.record(start + BUCKET_DURATION * i, Some(0.5), false);
+        }
+        assert_eq!(stats.buckets().count(), MAX_BUCKETS);
+
+        // A long pause leaves only empty buckets plus the new one
+        stats.record(start + BUCKET_DURATION * 1000, Some(0.5), false);
+        assert_eq!(stats.buckets().count(), MAX_BUCKETS);
+        assert_eq!(stats.buckets().filter(|b| b.attempts > 0).count(), 1);
+        assert_eq!(stats.total().attempts, MAX_BUCKETS as u32 * 2 + 1);
+    }
+}
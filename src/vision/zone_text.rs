@@ -0,0 +1,216 @@
+//! Zone text filtering
+//!
+//! Turns a zone's raw OCR result into its reading: the profile's
+//! substitutions, the content type filter, the zone's character whitelist and
+//! vocabulary snapping, in that order.
+
+use crate::storage::profiles::{ContentType, OcrRegion, RecognitionDictionary};
+use crate::vision::char_whitelist::apply_whitelist;
+use crate::vision::dictionary;
+use crate::vision::ensemble::Reading;
+use crate::vision::VisionResult;
+
+/// Filter OCR text for a zone
+///
+/// Applies the profile's substitutions to the raw text, then the content type
+/// filter and character whitelist, and finally snaps text zones to the
+/// profile vocabulary.
+pub fn filter_zone_text(
+    text: &str,
+    zone: &OcrRegion,
+    dictionary: Option<&RecognitionDictionary>,
+) -> String {
+    let text = match dictionary {
+        Some(dictionary) => dictionary::apply_substitutions(text, dictionary),
+        None => text.to_string(),
+    };
+    let text = filter_text_by_content_type(&text, &zone.content_type);
+    let text = match zone.allowed_chars.as_deref() {
+        Some(allowed) if !allowed.is_empty() => apply_whitelist(&text, allowed),
+        _ => text,
+    };
+
+    match dictionary {
+        Some(dictionary) if zone.content_type == ContentType::Text => {
+            dictionary::snap_to_vocabulary(&text, dictionary)
+                .map(str::to_string)
+                .unwrap_or(text)
+        }
+        _ => text,
+    }
+}
+
+/// Filter OCR text based on the expected content type
+/// This helps clean up OCR results by removing characters that don't match the expected type
+fn filter_text_by_content_type(text: &str, content_type: &ContentType) -> String {
+    match content_type {
+        ContentType::Text => {
+            // For text, just trim whitespace
+            text.trim().to_string()
+        }
+        ContentType::Number => {
+            // Keep only digits, decimal points, commas (for thousands), and minus sign
+            // Also handle common OCR mistakes: O->0, l/I->1, S->5, B->8
+            let cleaned: String = text
+                .chars()
+                .map(|c| match c {
+                    'O' | 'o' => '0',
+                    'l' | 'I' | '|' => '1',
+                    'S' | 's' => '5',
+                    'B' => '8',
+                    _ => c,
+                })
+                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',' || *c == '-')
+                .collect();
+            cleaned
+        }
+        ContentType::Percentage => {
+            // Keep digits, decimal points, and percent sign
+            let cleaned: String = text
+                .chars()
+                .map(|c| match c {
+                    'O' | 'o' => '0',
+                    'l' | 'I' | '|' => '1',
+                    'S' | 's' => '5',
+                    'B' => '8',
+                    _ => c,
+                })
+                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '%' || *c == '-')
+                .collect();
+            // Ensure % is at the end if present anywhere
+            if cleaned.contains('%') {
+                let without_percent: String = cleaned.chars().filter(|c| *c != '%').collect();
+                format!("{}%", without_percent)
+            } else {
+                cleaned
+            }
+        }
+        ContentType::Time => {
+            // Keep digits and colons for time formats like 12:34 or 1:23:45
+            let cleaned: String = text
+                .chars()
+                .map(|c| match c {
+                    'O' | 'o' => '0',
+                    'l' | 'I' | '|' => '1',
+                    _ => c,
+                })
+                .filter(|c| c.is_ascii_digit() || *c == ':')
+                .collect();
+            cleaned
+        }
+    }
+}
+
+/// Numeric value of a filtered number or percentage reading
+pub fn numeric_value(text: &str) -> Option<f64> {
+    text.trim()
+        .trim_end_matches('%')
+        .replace(',', "")
+        .parse()
+        .ok()
+}
+
+/// Whether a zone's reading differs enough from its last recorded value
+///
+/// Numbers and percentages within the zone's epsilon of the last value count
+/// as the same reading, so a flickering last digit isn't recorded at every
+/// frame. Anything else is new when its text changes.
+pub fn is_new_value(previous: &str, text: &str, zone: &OcrRegion) -> bool {
+    let numeric = matches!(
+        zone.content_type,
+        ContentType::Number | ContentType::Percentage
+    );
+    if numeric && zone.value_epsilon > 0.0 {
+        if let (Some(previous), Some(value)) = (numeric_value(previous), numeric_value(text)) {
+            return (value - previous).abs() > zone.value_epsilon;
+        }
+    }
+    previous != text
+}
+
+/// Filtered text and mean confidence of a zone's OCR result
+pub fn zone_reading(
+    result: &VisionResult,
+    zone: &OcrRegion,
+    dictionary: Option<&RecognitionDictionary>,
+) -> Reading {
+    // Combine all detected text
+    let raw_text: String = result
+        .text_regions
+        .iter()
+        .map(|r| r.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let confidence = (!result.text_regions.is_empty()).then(|| {
+        result
+            .text_regions
+            .iter()
+            .map(|r| r.confidence)
+            .sum::<f32>()
+            / result.text_regions.len() as f32
+    });
+
+    Reading {
+        text: filter_zone_text(&raw_text, zone, dictionary),
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_zone_text() {
+        let mut zone = OcrRegion {
+            id: "gold".to_string(),
+            name: "Gold".to_string(),
+            bounds: (0.0, 0.0, 0.1, 0.1),
+            content_type: ContentType::Number,
+            enabled: true,
+            preprocessing: None,
+            ensemble: false,
+            allowed_chars: None,
+            geometry: Default::default(),
+            scrolling: false,
+            value_epsilon: 0.0,
+            source: None,
+            phases: vec![],
+        };
+        assert_eq!(filter_zone_text("Gold: 1,2O5", &zone, None), "1,205");
+
+        zone.allowed_chars = Some("0123456789".to_string());
+        assert_eq!(filter_zone_text("Gold: 1,2O5", &zone, None), "1205");
+    }
+
+    #[test]
+    fn test_is_new_value() {
+        let mut zone = OcrRegion {
+            id: "health".to_string(),
+            name: "Health".to_string(),
+            bounds: (0.0, 0.0, 0.1, 0.1),
+            content_type: ContentType::Percentage,
+            enabled: true,
+            preprocessing: None,
+            ensemble: false,
+            allowed_chars: None,
+            geometry: Default::default(),
+            scrolling: false,
+            value_epsilon: 0.0,
+            source: None,
+            phases: vec![],
+        };
+        assert!(is_new_value("80%", "81%", &zone));
+        assert!(!is_new_value("80%", "80%", &zone));
+
+        zone.value_epsilon = 2.0;
+        assert!(!is_new_value("80%", "82%", &zone));
+        assert!(is_new_value("80%", "82.5%", &zone));
+        // Unreadable values fall back to comparing text
+        assert!(is_new_value("80%", "", &zone));
+
+        zone.content_type = ContentType::Number;
+        assert!(!is_new_value("1,200", "1201", &zone));
+    }
+}